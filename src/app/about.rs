@@ -0,0 +1,435 @@
+//! "About this song" for the Info view 📰
+//!
+//! What Genius says about the song (its description and the annotations on
+//! its lyrics, with `genius_token` set) and the Wikipedia summaries of the
+//! song and the artist. Looked up only while the Info view is showing,
+//! once per track.
+
+use crate::app::events::AppEvent;
+use crate::app::App;
+use anyhow::Result;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+
+/// Genius annotations shown at most
+const MAX_ANNOTATIONS: usize = 8;
+/// Words in a Wikipedia description that say it's about a musician
+const MUSIC_WORDS: [&str; 12] = [
+    "band",
+    "musician",
+    "singer",
+    "rapper",
+    "composer",
+    "group",
+    "duo",
+    "artist",
+    "producer",
+    "dj",
+    "songwriter",
+    "orchestra",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AboutSection {
+    pub heading: String,
+    pub text: String,
+    /// "Genius" or "Wikipedia"
+    pub source: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum AboutState {
+    #[default]
+    Idle,
+    Loading,
+    Loaded(Vec<AboutSection>),
+    NotFound,
+    Failed(String),
+}
+
+/// Request key: one lookup per song
+pub fn about_key(artist: &str, title: &str) -> String {
+    format!("{}\u{1f}{}", artist, title)
+}
+
+/// Start the lookup for the playing track unless it's done or running
+pub fn ensure(app: &mut App, tx: &mpsc::Sender<AppEvent>, client: &Client) {
+    let Some(track) = app.track.as_ref() else {
+        return;
+    };
+    if track.artist.trim().is_empty() || track.name.trim().is_empty() {
+        return;
+    }
+    let key = about_key(&track.artist, &track.name);
+    if app.about_request.wants(&key) {
+        return;
+    }
+    let (artist, title) = (track.artist.clone(), track.name.clone());
+    let token = app.genius_token.clone();
+    let (tx, client) = (tx.clone(), client.clone());
+    app.about = AboutState::Loading;
+    app.about_scroll = 0;
+    app.about_request.start(key.clone(), async move {
+        let state = match fetch(&client, &artist, &title, token.as_deref()).await {
+            Ok(sections) if sections.is_empty() => AboutState::NotFound,
+            Ok(sections) => AboutState::Loaded(sections),
+            Err(e) => AboutState::Failed(e.to_string()),
+        };
+        if let Err(e) = tx.send(AppEvent::AboutUpdate(key, state)).await {
+            tracing::debug!("Channel closed: {}", e);
+        }
+    });
+}
+
+/// Everything found about the song; an error only if every source failed
+pub async fn fetch(
+    client: &Client,
+    artist: &str,
+    title: &str,
+    genius_token: Option<&str>,
+) -> Result<Vec<AboutSection>> {
+    let mut sections = Vec::new();
+    let mut failure = None;
+
+    let (mut description, mut annotations) = (None, Vec::new());
+    if let Some(token) = genius_token {
+        match genius(client, token, artist, title).await {
+            Ok(found) => (description, annotations) = found,
+            Err(e) => {
+                tracing::debug!("Genius lookup failed: {}", e);
+                failure = Some(e);
+            }
+        }
+    }
+
+    // Genius' description is the better read when there is one
+    if let Some(text) = description {
+        sections.push(section(crate::t!("about.song"), text, "Genius"));
+    } else {
+        match wikipedia(client, &song_titles(artist, title), |p| {
+            p.is_song_by(artist)
+        })
+        .await
+        {
+            Ok(Some(text)) => sections.push(section(crate::t!("about.song"), text, "Wikipedia")),
+            Ok(None) => {}
+            Err(e) => failure = Some(e),
+        }
+    }
+    sections.append(&mut annotations);
+    match wikipedia(client, &artist_titles(artist), WikiPage::is_musician).await {
+        Ok(Some(text)) => sections.push(section(
+            crate::t!("about.artist", artist = artist),
+            text,
+            "Wikipedia",
+        )),
+        Ok(None) => {}
+        Err(e) => failure = Some(e),
+    }
+
+    match failure {
+        Some(e) if sections.is_empty() => Err(e),
+        _ => Ok(sections),
+    }
+}
+
+fn section(heading: String, text: String, source: &'static str) -> AboutSection {
+    AboutSection {
+        heading,
+        text,
+        source,
+    }
+}
+
+/// Page titles a song's article may have, most specific first
+fn song_titles(artist: &str, title: &str) -> Vec<String> {
+    vec![
+        format!("{} ({} song)", title, artist),
+        format!("{} (song)", title),
+        title.to_string(),
+    ]
+}
+
+fn artist_titles(artist: &str) -> Vec<String> {
+    ["", " (band)", " (musician)", " (singer)", " (rapper)"]
+        .iter()
+        .map(|suffix| format!("{}{}", artist, suffix))
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct WikiPage {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    extract: String,
+}
+
+impl WikiPage {
+    fn is_article(&self) -> bool {
+        self.kind == "standard" && !self.extract.trim().is_empty()
+    }
+
+    /// An article about a song by `artist`
+    fn is_song_by(&self, artist: &str) -> bool {
+        let artist = artist.to_lowercase();
+        self.is_article()
+            && (self.description.to_lowercase().contains(&artist)
+                || self.extract.to_lowercase().contains(&artist))
+            && (self.description.to_lowercase().contains("song")
+                || self.description.to_lowercase().contains("single")
+                || self.extract.to_lowercase().contains("song"))
+    }
+
+    fn is_musician(&self) -> bool {
+        let description = self.description.to_lowercase();
+        self.is_article()
+            && description
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| MUSIC_WORDS.contains(&word))
+    }
+}
+
+/// First of `titles` with a summary that `wanted` accepts
+async fn wikipedia(
+    client: &Client,
+    titles: &[String],
+    wanted: impl Fn(&WikiPage) -> bool,
+) -> Result<Option<String>> {
+    for title in titles {
+        let url = format!(
+            "https://en.wikipedia.org/api/rest_v1/page/summary/{}",
+            urlencode(&title.replace(' ', "_"))
+        );
+        let response = client.get(&url).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            continue;
+        }
+        let page: WikiPage = response.error_for_status()?.json().await?;
+        if wanted(&page) {
+            return Ok(Some(page.extract.trim().to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Percent-encode a page title for the URL path
+fn urlencode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'(' | b')' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusResponse<T> {
+    response: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusSearch {
+    hits: Vec<GeniusHit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusHit {
+    result: GeniusSongRef,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusSongRef {
+    id: u64,
+    primary_artist: GeniusArtist,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusArtist {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusSongResponse {
+    song: GeniusSong,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusSong {
+    description: Option<GeniusText>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusText {
+    plain: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusReferents {
+    referents: Vec<GeniusReferent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusReferent {
+    fragment: String,
+    annotations: Vec<GeniusAnnotation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeniusAnnotation {
+    body: GeniusText,
+}
+
+/// The song's id on Genius, if a hit is by `artist`
+fn genius_song_id(search: &GeniusSearch, artist: &str) -> Option<u64> {
+    let artist = artist.to_lowercase();
+    search
+        .hits
+        .iter()
+        .find(|hit| {
+            let name = hit.result.primary_artist.name.to_lowercase();
+            name.contains(&artist) || artist.contains(&name)
+        })
+        .map(|hit| hit.result.id)
+}
+
+/// The description and the annotated lines
+fn genius_sections(
+    song: GeniusSong,
+    referents: Vec<GeniusReferent>,
+) -> (Option<String>, Vec<AboutSection>) {
+    // "?" is what Genius has for "no description yet"
+    let description = song
+        .description
+        .map(|d| d.plain.trim().to_string())
+        .filter(|d| !d.is_empty() && d != "?");
+    let mut sections = Vec::new();
+    for referent in referents.into_iter().take(MAX_ANNOTATIONS) {
+        let Some(annotation) = referent.annotations.into_iter().next() else {
+            continue;
+        };
+        let text = annotation.body.plain.trim().to_string();
+        if text.is_empty() || text == "?" {
+            continue;
+        }
+        sections.push(section(
+            format!("“{}”", referent.fragment.trim()),
+            text,
+            "Genius",
+        ));
+    }
+    (description, sections)
+}
+
+async fn genius(
+    client: &Client,
+    token: &str,
+    artist: &str,
+    title: &str,
+) -> Result<(Option<String>, Vec<AboutSection>)> {
+    let get = |path: String| {
+        client
+            .get(format!("https://api.genius.com{}", path))
+            .bearer_auth(token)
+            .query(&[("text_format", "plain")])
+    };
+    let search: GeniusResponse<GeniusSearch> = get("/search".to_string())
+        .query(&[("q", format!("{} {}", artist, title))])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let Some(id) = genius_song_id(&search.response, artist) else {
+        return Ok((None, Vec::new()));
+    };
+    let song: GeniusResponse<GeniusSongResponse> = get(format!("/songs/{}", id))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let referents: GeniusResponse<GeniusReferents> = get("/referents".to_string())
+        .query(&[("song_id", id.to_string()), ("per_page", "20".to_string())])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(genius_sections(
+        song.response.song,
+        referents.response.referents,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wikipedia_pages_are_checked() {
+        let page = |kind: &str, description: &str, extract: &str| WikiPage {
+            kind: kind.into(),
+            description: description.into(),
+            extract: extract.into(),
+        };
+        let band = page(
+            "standard",
+            "English rock band",
+            "Radiohead are an English rock band.",
+        );
+        assert!(band.is_musician());
+        assert!(!page("standard", "Radio station", "A station.").is_musician());
+        assert!(!page("disambiguation", "Topics referred to by the same term", "x").is_musician());
+
+        let song = page(
+            "standard",
+            "1997 single by Radiohead",
+            "\"Karma Police\" is a song by the English rock band Radiohead.",
+        );
+        assert!(song.is_song_by("Radiohead"));
+        assert!(!song.is_song_by("Muse"));
+        assert_eq!(
+            song_titles("Radiohead", "Creep")[0],
+            "Creep (Radiohead song)"
+        );
+        assert_eq!(urlencode("AC/DC_(band)"), "AC%2FDC_(band)");
+    }
+
+    #[test]
+    fn test_genius_results() {
+        let search: GeniusResponse<GeniusSearch> = serde_json::from_str(
+            r#"{"response":{"hits":[
+                {"result":{"id":1,"primary_artist":{"name":"Cover Band"}}},
+                {"result":{"id":378195,"primary_artist":{"name":"Radiohead"}}}
+            ]}}"#,
+        )
+        .unwrap();
+        assert_eq!(genius_song_id(&search.response, "radiohead"), Some(378195));
+        assert_eq!(genius_song_id(&search.response, "Muse"), None);
+
+        let song: GeniusSong =
+            serde_json::from_str(r#"{"description":{"plain":"Written in 1992."}}"#).unwrap();
+        let referents: GeniusReferents = serde_json::from_str(
+            r#"{"referents":[
+                {"fragment":"But I'm a creep","annotations":[{"body":{"plain":"The hook."}}]},
+                {"fragment":"Unexplained","annotations":[{"body":{"plain":"?"}}]},
+                {"fragment":"No notes","annotations":[]}
+            ]}"#,
+        )
+        .unwrap();
+        let (description, sections) = genius_sections(song, referents.referents);
+        assert_eq!(description.as_deref(), Some("Written in 1992."));
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].heading, "“But I'm a creep”");
+        assert_eq!(sections[0].text, "The hook.");
+
+        let empty: GeniusSong = serde_json::from_str(r#"{"description":{"plain":"?"}}"#).unwrap();
+        assert_eq!(genius_sections(empty, Vec::new()), (None, Vec::new()));
+    }
+}
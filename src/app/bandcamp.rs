@@ -0,0 +1,630 @@
+//! Bandcamp purchases in the Library's Bandcamp tab 🎟️
+//!
+//! Bandcamp has no public API, so this signs in the way its website does:
+//! with the `identity` cookie of a logged-in browser (`bandcamp_identity` in
+//! `config.toml`). The tab lists the fan's collection. `Enter` streams an
+//! album through MPD like `:open` does (see [`super::ytdlp`]), `a` queues it,
+//! and `W` downloads it in `bandcamp_format` into
+//! `<music dir>/<artist>/<album>`, unzipped, with any tags Bandcamp left out
+//! filled in.
+
+use crate::app::downloads::{self, path_part, Reporter, TransferKind};
+use crate::app::events::AppEvent;
+use crate::app::ytdlp::Stream;
+use crate::app::App;
+use crate::t;
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::header::COOKIE;
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+
+const SUMMARY_URL: &str = "https://bandcamp.com/api/fan/2/collection_summary";
+const COLLECTION_URL: &str = "https://bandcamp.com/api/fancollection/1/collection_items";
+/// Purchases asked for per page
+const PAGE: usize = 100;
+/// Request key for the collection: one listing per session
+pub const COLLECTION_KEY: &str = "collection";
+/// Audio files tagged after a download
+#[cfg_attr(not(feature = "mpd"), allow(dead_code))]
+const AUDIO: [&str; 7] = ["flac", "mp3", "m4a", "ogg", "wav", "aiff", "aif"];
+
+/// An album or track in the fan's collection
+#[derive(Debug, Clone, PartialEq)]
+pub struct Purchase {
+    pub title: String,
+    pub artist: String,
+    /// The album a track purchase is from (the title for albums)
+    pub album: String,
+    /// Its page on `<band>.bandcamp.com`
+    pub url: String,
+    pub is_track: bool,
+    /// The "download" page Bandcamp made for this purchase
+    pub download_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CollectionState {
+    #[default]
+    Idle,
+    Loading,
+    Loaded(Vec<Purchase>),
+    Failed(String),
+}
+
+impl CollectionState {
+    pub fn purchases(&self) -> &[Purchase] {
+        match self {
+            CollectionState::Loaded(purchases) => purchases,
+            _ => &[],
+        }
+    }
+}
+
+/// List the collection once the tab is showing, unless it's done or running
+pub fn ensure(app: &mut App, tx: &mpsc::Sender<AppEvent>, client: &Client) {
+    let Some(identity) = app.bandcamp_identity.clone() else {
+        return;
+    };
+    if app.bandcamp_request.wants(COLLECTION_KEY) {
+        return;
+    }
+    let (tx, client) = (tx.clone(), client.clone());
+    app.bandcamp = CollectionState::Loading;
+    app.bandcamp_request
+        .start(COLLECTION_KEY.to_string(), async move {
+            let state = match collection(&client, &identity).await {
+                Ok(purchases) => CollectionState::Loaded(purchases),
+                Err(e) => CollectionState::Failed(e.to_string()),
+            };
+            if let Err(e) = tx.send(AppEvent::BandcampCollection(state)).await {
+                tracing::debug!("Channel closed: {}", e);
+            }
+        });
+}
+
+/// Stream `purchase` through MPD: queued, and started with `play`
+pub fn open(
+    app: &mut App,
+    tx: &mpsc::Sender<AppEvent>,
+    client: &Client,
+    purchase: &Purchase,
+    play: bool,
+) {
+    if !app.is_mpd {
+        app.show_warning(&t!("stream.needs_mpd"));
+        return;
+    }
+    let identity = app.bandcamp_identity.clone().unwrap_or_default();
+    app.show_toast(&t!("bandcamp.loading", title = purchase.title));
+    let (tx, client, purchase) = (tx.clone(), client.clone(), purchase.clone());
+    tokio::spawn(async move {
+        let result = streams(&client, &identity, &purchase)
+            .await
+            .map_err(|e| e.to_string());
+        let event = AppEvent::BandcampStreams(purchase.title, play, result);
+        if let Err(e) = tx.send(event).await {
+            tracing::debug!("Channel closed: {}", e);
+        }
+    });
+}
+
+/// Download `purchase` into the music directory in the background
+pub fn start_download(
+    app: &mut App,
+    tx: &mpsc::Sender<AppEvent>,
+    client: &Client,
+    purchase: &Purchase,
+) {
+    let identity = app.bandcamp_identity.clone().unwrap_or_default();
+    let format = app.bandcamp_format.clone();
+    let music_dir = PathBuf::from(&app.music_directory);
+    app.show_toast(&t!("downloads.started", title = purchase.title));
+    let (client, title, purchase) = (client.clone(), purchase.title.clone(), purchase.clone());
+    app.downloads
+        .start(TransferKind::Download, &title, tx, |reporter| async move {
+            download(
+                &client, &identity, &purchase, &format, &music_dir, &reporter,
+            )
+            .await
+        });
+}
+
+fn cookie(identity: &str) -> String {
+    format!("identity={}", identity.trim())
+}
+
+/// A GET that signs in when it goes to Bandcamp itself. Artists' custom
+/// domains and the file servers never see the cookie.
+fn get(client: &Client, url: &str, identity: &str) -> RequestBuilder {
+    let request = client.get(url);
+    if is_bandcamp(url) {
+        request.header(COOKIE, cookie(identity))
+    } else {
+        request
+    }
+}
+
+/// `bandcamp.com` or one of its subdomains, over HTTPS
+fn is_bandcamp(url: &str) -> bool {
+    reqwest::Url::parse(url).is_ok_and(|url| {
+        url.scheme() == "https"
+            && url.host_str().is_some_and(|host| {
+                let host = host.to_ascii_lowercase();
+                host == "bandcamp.com" || host.ends_with(".bandcamp.com")
+            })
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct Summary {
+    fan_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionPage {
+    #[serde(default)]
+    items: Vec<Item>,
+    #[serde(default)]
+    more_available: bool,
+    last_token: Option<String>,
+    #[serde(default)]
+    redownload_urls: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Item {
+    #[serde(default)]
+    tralbum_type: String,
+    item_title: Option<String>,
+    album_title: Option<String>,
+    #[serde(default)]
+    band_name: String,
+    item_url: Option<String>,
+    sale_item_type: Option<String>,
+    sale_item_id: Option<u64>,
+}
+
+/// Every album and track the fan bought, newest first
+pub async fn collection(client: &Client, identity: &str) -> Result<Vec<Purchase>> {
+    let summary: Summary = client
+        .get(SUMMARY_URL)
+        .header(COOKIE, cookie(identity))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let fan_id = summary
+        .fan_id
+        .ok_or_else(|| anyhow!(t!("bandcamp.signed_out")))?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let mut token = format!("{}::a::", now);
+    let mut purchases = Vec::new();
+    loop {
+        let page: CollectionPage = client
+            .post(COLLECTION_URL)
+            .header(COOKIE, cookie(identity))
+            .json(&serde_json::json!({
+                "fan_id": fan_id,
+                "older_than_token": token,
+                "count": PAGE,
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        purchases.extend(purchases_on(&page));
+        match page.last_token {
+            Some(last) if page.more_available && last != token => token = last,
+            _ => break,
+        }
+    }
+    Ok(purchases)
+}
+
+/// The purchases on one page of the collection; subscriptions and merch
+/// without a page of their own are left out
+fn purchases_on(page: &CollectionPage) -> Vec<Purchase> {
+    page.items
+        .iter()
+        .filter_map(|item| {
+            let title = item.item_title.clone()?;
+            let download_url = match (&item.sale_item_type, item.sale_item_id) {
+                (Some(kind), Some(id)) => page.redownload_urls.get(&format!("{}{}", kind, id)),
+                _ => None,
+            };
+            Some(Purchase {
+                album: item.album_title.clone().unwrap_or_else(|| title.clone()),
+                title,
+                artist: item.band_name.clone(),
+                url: item.item_url.clone()?,
+                is_track: item.tralbum_type == "t",
+                download_url: download_url.cloned(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct Tralbum {
+    #[serde(default)]
+    trackinfo: Vec<TralbumTrack>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TralbumTrack {
+    title: Option<String>,
+    duration: Option<f64>,
+    /// Format to stream URL; null for tracks that can't be played
+    file: Option<HashMap<String, String>>,
+}
+
+/// The purchase's tracks as streams, in album order
+pub async fn streams(client: &Client, identity: &str, purchase: &Purchase) -> Result<Vec<Stream>> {
+    let html = get(client, &purchase.url, identity)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let streams = parse_tralbum(&html, purchase)?;
+    if streams.is_empty() {
+        bail!(t!("bandcamp.no_streams", title = purchase.title));
+    }
+    Ok(streams)
+}
+
+/// The `data-tralbum` JSON on an album or track page
+fn parse_tralbum(html: &str, purchase: &Purchase) -> Result<Vec<Stream>> {
+    let json = data_attribute(html, "data-tralbum").context("No album data on the page")?;
+    let tralbum: Tralbum = serde_json::from_str(&json)?;
+    Ok(tralbum
+        .trackinfo
+        .into_iter()
+        .filter_map(|track| {
+            let url = track.file?.remove("mp3-128")?;
+            Some(Stream {
+                url,
+                title: track.title.unwrap_or_else(|| purchase.title.clone()),
+                uploader: Some(purchase.artist.clone()),
+                album: Some(purchase.album.clone()),
+                // The MP3 itself is the download
+                page: None,
+                site: "Bandcamp".into(),
+                duration_ms: track.duration.map_or(0, |d| (d * 1000.0) as u64),
+            })
+        })
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadBlob {
+    #[serde(default)]
+    download_items: Vec<DownloadItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadItem {
+    #[serde(default)]
+    downloads: HashMap<String, DownloadLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadLink {
+    url: String,
+}
+
+/// Download `purchase` in `format` ("flac", "mp3-320", ...) into its folder
+/// under `music_dir`. Returns the folder, relative to `music_dir`.
+pub async fn download(
+    client: &Client,
+    identity: &str,
+    purchase: &Purchase,
+    format: &str,
+    music_dir: &Path,
+    reporter: &Reporter,
+) -> Result<String> {
+    let page = purchase
+        .download_url
+        .as_deref()
+        .ok_or_else(|| anyhow!(t!("bandcamp.no_download", title = purchase.title)))?;
+    let html = get(client, page, identity)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let url = download_link(&html, format)?;
+
+    let folder = format!(
+        "{}/{}",
+        path_part(&purchase.artist),
+        path_part(&purchase.album)
+    );
+    let dir = music_dir.join(&folder);
+    tokio::fs::create_dir_all(&dir).await?;
+
+    // Albums come zipped, single tracks as they are. Each purchase has its
+    // own partial file: a track and its album can download side by side.
+    let partial = dir.join(format!(
+        ".{}.{}.part",
+        path_part(&purchase.title),
+        extension(format)
+    ));
+    downloads::save(get(client, &url, identity), &partial, reporter).await?;
+
+    let mut magic = [0u8; 4];
+    let is_zip = std::fs::File::open(&partial)
+        .and_then(|mut f| std::io::Read::read_exact(&mut f, &mut magic))
+        .is_ok()
+        && &magic == b"PK\x03\x04";
+    if is_zip {
+        let unzipped = tokio::process::Command::new("unzip")
+            .args(["-o", "-q"])
+            .arg(&partial)
+            .arg("-d")
+            .arg(&dir)
+            .status()
+            .await
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => anyhow!(t!("bandcamp.no_unzip")),
+                _ => e.into(),
+            });
+        let removed = tokio::fs::remove_file(&partial).await;
+        let status = unzipped?;
+        removed?;
+        if !status.success() {
+            bail!("unzip exited with {}", status);
+        }
+    } else {
+        let name = format!(
+            "{} - {}.{}",
+            path_part(&purchase.artist),
+            path_part(&purchase.title),
+            extension(format)
+        );
+        tokio::fs::rename(&partial, dir.join(name)).await?;
+    }
+
+    let purchase = purchase.clone();
+    tokio::task::spawn_blocking(move || tag_folder(&dir, &purchase)).await??;
+    Ok(folder)
+}
+
+/// The link to the file in `format` on a purchase's download page
+fn download_link(html: &str, format: &str) -> Result<String> {
+    let json = data_attribute(html, "data-blob").context("No downloads on the page")?;
+    let blob: DownloadBlob = serde_json::from_str(&json)?;
+    let mut item = blob
+        .download_items
+        .into_iter()
+        .next()
+        .context("No downloads on the page")?;
+    if let Some(link) = item.downloads.remove(format) {
+        return Ok(link.url);
+    }
+    let mut formats: Vec<String> = item.downloads.into_keys().collect();
+    formats.sort();
+    bail!(t!(
+        "bandcamp.no_format",
+        format = format,
+        formats = formats.join(", ")
+    ))
+}
+
+/// Fill in the tags Bandcamp left out of the downloaded songs
+#[cfg(feature = "mpd")]
+fn tag_folder(dir: &Path, purchase: &Purchase) -> Result<()> {
+    use crate::app::{tags, TagField};
+
+    for path in audio_files(dir)? {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let (number, title) = name_parts(&stem, purchase);
+        let wanted = [
+            (TagField::Album, Some(purchase.album.clone())),
+            (TagField::AlbumArtist, Some(purchase.artist.clone())),
+            (TagField::Artist, Some(purchase.artist.clone())),
+            (TagField::Title, Some(title)),
+            (TagField::Track, number.map(|n| n.to_string())),
+        ];
+        if let Err(e) = tags::fill(&path, &wanted) {
+            tracing::warn!("Couldn't tag {}: {}", path.display(), e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "mpd"))]
+fn tag_folder(_dir: &Path, _purchase: &Purchase) -> Result<()> {
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "mpd"), allow(dead_code))]
+fn audio_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| AUDIO.contains(&e.to_lowercase().as_str()))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Track number and title from Bandcamp's file names:
+/// "Artist - Album - 01 Title" for albums, "Artist - Title" for tracks
+#[cfg_attr(not(feature = "mpd"), allow(dead_code))]
+fn name_parts(stem: &str, purchase: &Purchase) -> (Option<u32>, String) {
+    let rest = stem
+        .strip_prefix(&format!("{} - ", purchase.artist))
+        .unwrap_or(stem);
+    let rest = rest
+        .strip_prefix(&format!("{} - ", purchase.album))
+        .unwrap_or(rest);
+    match rest.split_once(' ') {
+        Some((n, title)) if n.len() <= 3 && n.chars().all(|c| c.is_ascii_digit()) => {
+            (n.parse().ok(), title.to_string())
+        }
+        _ => (None, rest.to_string()),
+    }
+}
+
+/// File extension of a Bandcamp download format
+fn extension(format: &str) -> &'static str {
+    match format {
+        "flac" => "flac",
+        "wav" => "wav",
+        "aiff-lossless" => "aiff",
+        "vorbis" => "ogg",
+        "aac-hi" | "alac" => "m4a",
+        _ => "mp3",
+    }
+}
+
+/// An HTML attribute holding JSON, unescaped
+fn data_attribute(html: &str, name: &str) -> Option<String> {
+    let start = html.find(&format!("{}=\"", name))? + name.len() + 2;
+    let len = html[start..].find('"')?;
+    Some(unescape_html(&html[start..start + len]))
+}
+
+fn unescape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(end) = rest.find(';').filter(|&end| end <= 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "quot" => Some('"'),
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix('#')
+                .and_then(|n| match n.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => n.parse().ok(),
+                })
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn purchase() -> Purchase {
+        Purchase {
+            title: "Mezzanine".into(),
+            artist: "Massive Attack".into(),
+            album: "Mezzanine".into(),
+            url: "https://massiveattack.bandcamp.com/album/mezzanine".into(),
+            is_track: false,
+            download_url: Some("https://bandcamp.com/download?payment_id=1".into()),
+        }
+    }
+
+    #[test]
+    fn test_collection_and_pages() {
+        let page: CollectionPage = serde_json::from_str(
+            r#"{"more_available": false, "last_token": "1::a::",
+            "items": [
+                {"tralbum_type": "a", "item_title": "Mezzanine", "band_name": "Massive Attack",
+                 "item_url": "https://massiveattack.bandcamp.com/album/mezzanine",
+                 "sale_item_type": "p", "sale_item_id": 1},
+                {"tralbum_type": "t", "item_title": "Teardrop", "album_title": "Mezzanine",
+                 "band_name": "Massive Attack", "item_url": "https://massiveattack.bandcamp.com/track/teardrop",
+                 "sale_item_type": "p", "sale_item_id": 2},
+                {"tralbum_type": "a", "item_title": null, "band_name": "Gone"}
+            ],
+            "redownload_urls": {"p1": "https://bandcamp.com/download?payment_id=1"}}"#,
+        )
+        .unwrap();
+        let purchases = purchases_on(&page);
+        assert_eq!(purchases.len(), 2);
+        assert_eq!(purchases[0], purchase());
+        assert!(purchases[1].is_track);
+        assert_eq!(purchases[1].album, "Mezzanine");
+        assert_eq!(purchases[1].download_url, None);
+
+        let html = r#"<script data-tralbum="{&quot;trackinfo&quot;:[{&quot;title&quot;:&quot;Angel&quot;,&quot;duration&quot;:379.5,&quot;file&quot;:{&quot;mp3-128&quot;:&quot;https://t4.bcbits.com/stream/1?p=0&amp;ts=2&quot;}},{&quot;title&quot;:&quot;Risingson&quot;,&quot;file&quot;:null}]}" data-embed="{}"></script>"#;
+        let streams = parse_tralbum(html, &purchase()).unwrap();
+        assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0].url, "https://t4.bcbits.com/stream/1?p=0&ts=2");
+        assert_eq!(streams[0].album.as_deref(), Some("Mezzanine"));
+        assert_eq!(streams[0].duration_ms, 379_500);
+
+        let blob = r#"<div id="pagedata" data-blob="{&quot;download_items&quot;:[{&quot;downloads&quot;:{&quot;flac&quot;:{&quot;url&quot;:&quot;https://p4.bcbits.com/download/album?enc=flac&quot;},&quot;mp3-320&quot;:{&quot;url&quot;:&quot;https://p4.bcbits.com/download/album?enc=mp3-320&quot;}}}]}"></div>"#;
+        assert_eq!(
+            download_link(blob, "flac").unwrap(),
+            "https://p4.bcbits.com/download/album?enc=flac"
+        );
+        assert!(is_bandcamp(
+            "https://massiveattack.bandcamp.com/album/mezzanine"
+        ));
+        assert!(is_bandcamp("https://bandcamp.com/download?payment_id=1"));
+        assert!(!is_bandcamp(
+            "https://p4.bcbits.com/download/album?enc=flac"
+        ));
+        assert!(!is_bandcamp("https://music.example.com/album/x"));
+        assert!(!is_bandcamp("https://evilbandcamp.com/"));
+        assert!(!is_bandcamp("http://bandcamp.com/"));
+        assert!(download_link(blob, "wav")
+            .unwrap_err()
+            .to_string()
+            .contains("flac, mp3-320"));
+    }
+
+    #[test]
+    fn test_downloaded_file_names() {
+        let album = purchase();
+        assert_eq!(
+            name_parts("Massive Attack - Mezzanine - 03 Teardrop", &album),
+            (Some(3), "Teardrop".to_string())
+        );
+        let track = Purchase {
+            title: "Teardrop".into(),
+            ..purchase()
+        };
+        assert_eq!(
+            name_parts("Massive Attack - Teardrop", &track),
+            (None, "Teardrop".to_string())
+        );
+        assert_eq!(
+            unescape_html("Rock &amp; Roll &#39;n&#x27; &bogus"),
+            "Rock & Roll 'n' &bogus"
+        );
+    }
+}
@@ -0,0 +1,503 @@
+//! Audio CDs 💿
+//!
+//! `:cd` reads the disc's table of contents with cdparanoia and looks it up
+//! on MusicBrainz by its disc ID. The popup plays it through MPD (its
+//! `cdio_paranoia` input, so MPD has to run on the machine with the drive)
+//! and rips it to FLAC with cdparanoia and flac, tagged from the release,
+//! into `<music dir>/<artist>/<album>`. Rips run in the `:downloads` list.
+
+use crate::app::downloads::{path_part, Reporter, TransferKind, TransferUpdate};
+use crate::app::events::AppEvent;
+use crate::app::tag_lookup::{credit, year_of};
+use crate::app::ytdlp::Stream;
+use crate::app::App;
+use crate::artwork::{ArtworkRenderer, MUSICBRAINZ_USER_AGENT};
+use crate::t;
+use anyhow::{anyhow, bail, Result};
+use base64::Engine;
+use reqwest::Client;
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+
+/// Sectors per second of CD audio
+const SECTORS_PER_SECOND: u64 = 75;
+/// The lead-in before the first track, which disc IDs count
+const LEAD_IN: u32 = 150;
+
+/// One audio track, in sectors from the start of the disc
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocTrack {
+    pub number: u32,
+    pub start: u32,
+    pub length: u32,
+}
+
+/// The disc's audio tracks
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toc {
+    pub tracks: Vec<TocTrack>,
+}
+
+impl Toc {
+    fn lead_out(&self) -> u32 {
+        self.tracks
+            .last()
+            .map_or(0, |t| t.start + t.length + LEAD_IN)
+    }
+
+    /// "first last lead-out offsets...", MusicBrainz's `toc` parameter
+    fn mb_toc(&self) -> String {
+        let (first, last) = self.span();
+        let mut parts = vec![
+            first.to_string(),
+            last.to_string(),
+            self.lead_out().to_string(),
+        ];
+        parts.extend(self.tracks.iter().map(|t| (t.start + LEAD_IN).to_string()));
+        parts.join("+")
+    }
+
+    fn span(&self) -> (u32, u32) {
+        let number = |t: Option<&TocTrack>| t.map_or(0, |t| t.number);
+        (number(self.tracks.first()), number(self.tracks.last()))
+    }
+
+    /// The MusicBrainz disc ID: SHA-1 of the TOC in hex, in base64 with
+    /// `._-` for `+/=`
+    pub fn disc_id(&self) -> String {
+        let (first, last) = self.span();
+        let mut text = format!("{:02X}{:02X}{:08X}", first, last, self.lead_out());
+        for i in 1..100 {
+            let offset = self
+                .tracks
+                .iter()
+                .find(|t| t.number == i)
+                .map_or(0, |t| t.start + LEAD_IN);
+            text.push_str(&format!("{:08X}", offset));
+        }
+        base64::engine::general_purpose::STANDARD
+            .encode(Sha1::digest(text.as_bytes()))
+            .replace('+', ".")
+            .replace('/', "_")
+            .replace('=', "-")
+    }
+}
+
+/// A MusicBrainz release with this disc
+#[derive(Debug, Clone, PartialEq)]
+pub struct Release {
+    pub title: String,
+    pub artist: String,
+    pub year: Option<String>,
+    /// Which disc of how many, for sets
+    pub disc: (u32, u32),
+    /// (title, artist) for each track
+    pub tracks: Vec<(String, String)>,
+}
+
+/// The disc in the drive
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disc {
+    pub toc: Toc,
+    pub id: String,
+    /// The matches, or why the lookup failed
+    pub releases: Result<Vec<Release>, String>,
+}
+
+impl Disc {
+    pub fn release(&self, index: usize) -> Option<&Release> {
+        self.releases.as_ref().ok()?.get(index)
+    }
+
+    /// The disc as streams for MPD, named after `release` when there is one
+    pub fn streams(&self, release: Option<&Release>) -> Vec<Stream> {
+        self.toc
+            .tracks
+            .iter()
+            .enumerate()
+            .map(|(i, track)| {
+                let named = release.and_then(|r| r.tracks.get(i));
+                Stream {
+                    url: format!("cdda:///{}", track.number),
+                    title: named.map_or_else(
+                        || t!("cd.track", number = track.number),
+                        |(title, _)| title.clone(),
+                    ),
+                    uploader: named.map(|(_, artist)| artist.clone()),
+                    album: release.map(|r| r.title.clone()),
+                    page: None,
+                    site: "CD".to_string(),
+                    duration_ms: track.length as u64 * 1000 / SECTORS_PER_SECOND,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CdState {
+    Reading,
+    Failed(String),
+    Ready(Disc),
+}
+
+/// The `:cd` popup
+#[derive(Debug, Clone, PartialEq)]
+pub struct CdView {
+    pub state: CdState,
+    /// The selected track
+    pub selected: usize,
+    /// The release the tracks are named after
+    pub release: usize,
+}
+
+impl CdView {
+    pub fn disc(&self) -> Option<&Disc> {
+        match &self.state {
+            CdState::Ready(disc) => Some(disc),
+            _ => None,
+        }
+    }
+}
+
+/// `:cd`: read the disc and look it up in the background
+pub fn open(app: &mut App, tx: &mpsc::Sender<AppEvent>, client: &Client) {
+    app.cd = Some(CdView {
+        state: CdState::Reading,
+        selected: 0,
+        release: 0,
+    });
+    let (tx, client) = (tx.clone(), client.clone());
+    tokio::spawn(async move {
+        let result = read(&client).await.map_err(|e| e.to_string());
+        if let Err(e) = tx.send(AppEvent::CdRead(result)).await {
+            tracing::debug!("Channel closed: {}", e);
+        }
+    });
+}
+
+/// Rip the disc to FLAC, named and tagged after the chosen release
+pub fn start_rip(app: &mut App, tx: &mpsc::Sender<AppEvent>) {
+    let Some(view) = &app.cd else {
+        return;
+    };
+    let Some(disc) = view.disc().cloned() else {
+        return;
+    };
+    let release = disc.release(view.release).cloned();
+    let album = release
+        .as_ref()
+        .map_or_else(|| t!("cd.unknown_album"), |r| r.title.clone());
+    let music_dir = PathBuf::from(&app.music_directory);
+    app.show_toast(&t!("cd.ripping", album = album));
+    app.downloads
+        .start(TransferKind::Rip, &album, tx, |reporter| async move {
+            rip(&disc, release.as_ref(), &music_dir, &reporter).await
+        });
+}
+
+/// The TOC and its MusicBrainz releases. A failed lookup still reads the
+/// disc; it just has no names.
+async fn read(client: &Client) -> Result<Disc> {
+    let output = tokio::process::Command::new("cdparanoia")
+        .arg("-Q")
+        .output()
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => anyhow!(t!("cd.no_cdparanoia")),
+            _ => e.into(),
+        })?;
+    // The TOC goes to stderr, as do the errors
+    let text = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        let last = text.lines().rev().find(|l| !l.trim().is_empty());
+        bail!(
+            "{}",
+            last.map(|l| l.trim().to_string())
+                .unwrap_or_else(|| format!("cdparanoia exited with {}", output.status))
+        );
+    }
+    let toc = parse_toc(&text).ok_or_else(|| anyhow!(t!("cd.no_audio")))?;
+    let id = toc.disc_id();
+    let releases = lookup(client, &id, &toc).await.map_err(|e| {
+        tracing::debug!("Disc lookup failed: {}", e);
+        e.to_string()
+    });
+    Ok(Disc { toc, id, releases })
+}
+
+/// The audio tracks in `cdparanoia -Q`'s table:
+/// `  1.    16503 [03:40.03]        0 [00:00.00]    no   no  2`
+pub fn parse_toc(text: &str) -> Option<Toc> {
+    let tracks: Vec<TocTrack> = text
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let number = parts.first()?.strip_suffix('.')?.parse().ok()?;
+            Some(TocTrack {
+                number,
+                length: parts.get(1)?.parse().ok()?,
+                start: parts.get(3)?.parse().ok()?,
+            })
+        })
+        .collect();
+    (!tracks.is_empty()).then_some(Toc { tracks })
+}
+
+#[derive(Debug, Deserialize)]
+struct MbDiscLookup {
+    #[serde(default)]
+    releases: Vec<MbRelease>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbRelease {
+    title: String,
+    date: Option<String>,
+    #[serde(rename = "artist-credit", default)]
+    artist_credit: Vec<MbArtistCredit>,
+    #[serde(default)]
+    media: Vec<MbMedium>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbArtistCredit {
+    name: String,
+    #[serde(default)]
+    joinphrase: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbMedium {
+    #[serde(default)]
+    position: u32,
+    #[serde(default)]
+    discs: Vec<MbDisc>,
+    #[serde(default)]
+    tracks: Vec<MbTrack>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbDisc {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbTrack {
+    title: String,
+    #[serde(rename = "artist-credit", default)]
+    artist_credit: Vec<MbArtistCredit>,
+}
+
+/// Releases with this disc ID, or failing that with a close enough TOC
+async fn lookup(client: &Client, id: &str, toc: &Toc) -> Result<Vec<Release>> {
+    ArtworkRenderer::musicbrainz_throttle().await;
+    let response = client
+        .get(format!("https://musicbrainz.org/ws/2/discid/{}", id))
+        .header(reqwest::header::USER_AGENT, MUSICBRAINZ_USER_AGENT)
+        .query(&[
+            ("toc", toc.mb_toc().as_str()),
+            ("inc", "recordings artist-credits"),
+            ("fmt", "json"),
+        ])
+        .send()
+        .await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    let data: MbDiscLookup = response.error_for_status()?.json().await?;
+    Ok(releases(data, id, toc.tracks.len()))
+}
+
+/// Each release's medium that is this disc: the one with its ID, else the
+/// first with as many tracks
+fn releases(data: MbDiscLookup, id: &str, track_count: usize) -> Vec<Release> {
+    data.releases
+        .into_iter()
+        .filter_map(|release| {
+            let media_count = release.media.len() as u32;
+            let medium = release
+                .media
+                .iter()
+                .find(|m| m.discs.iter().any(|d| d.id == id))
+                .or_else(|| release.media.iter().find(|m| m.tracks.len() == track_count))?;
+            let artist = credit(
+                release
+                    .artist_credit
+                    .iter()
+                    .map(|c| (c.name.as_str(), c.joinphrase.as_str())),
+            );
+            let tracks = medium
+                .tracks
+                .iter()
+                .map(|track| {
+                    let by = credit(
+                        track
+                            .artist_credit
+                            .iter()
+                            .map(|c| (c.name.as_str(), c.joinphrase.as_str())),
+                    );
+                    let by = if by.is_empty() { artist.clone() } else { by };
+                    (track.title.clone(), by)
+                })
+                .collect();
+            Some(Release {
+                title: release.title.clone(),
+                artist,
+                year: year_of(release.date.as_deref()),
+                disc: (medium.position.max(1), media_count.max(1)),
+                tracks,
+            })
+        })
+        .collect()
+}
+
+/// Rip every track to `<artist>/<album>/NN Title.flac`, one at a time
+async fn rip(
+    disc: &Disc,
+    release: Option<&Release>,
+    music_dir: &Path,
+    reporter: &Reporter,
+) -> Result<String> {
+    let folder = match release {
+        Some(release) => {
+            let mut album = release.title.clone();
+            if release.disc.1 > 1 {
+                album = format!("{} (Disc {})", album, release.disc.0);
+            }
+            format!("{}/{}", path_part(&release.artist), path_part(&album))
+        }
+        None => format!("{}/{}", path_part(&t!("cd.unknown_album")), disc.id),
+    };
+    let dir = music_dir.join(&folder);
+    tokio::fs::create_dir_all(&dir).await?;
+
+    let streams = disc.streams(release);
+    let count = streams.len();
+    let mut bytes = 0;
+    for (i, (track, stream)) in disc.toc.tracks.iter().zip(streams).enumerate() {
+        let wav = dir.join(format!(".{:02}.wav", track.number));
+        let flac = dir.join(format!(
+            "{:02} {}.flac",
+            track.number,
+            path_part(&stream.title)
+        ));
+        let ripped = run(
+            tokio::process::Command::new("cdparanoia")
+                .args(["-q", "--", &track.number.to_string()])
+                .arg(&wav),
+            "cd.no_cdparanoia",
+        )
+        .await;
+        let encoded = match ripped {
+            Ok(()) => {
+                run(
+                    tokio::process::Command::new("flac")
+                        .args(["--silent", "--best", "--force", "-o"])
+                        .arg(&flac)
+                        .arg(&wav),
+                    "cd.no_flac",
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        let _ = tokio::fs::remove_file(&wav).await;
+        encoded?;
+
+        #[cfg(feature = "mpd")]
+        tag(flac.clone(), stream, release.cloned(), (i + 1, count)).await;
+        #[cfg(not(feature = "mpd"))]
+        let _ = (i, count, stream);
+
+        bytes += tokio::fs::metadata(&flac).await.map_or(0, |m| m.len());
+        reporter
+            .send(TransferUpdate::Progress { bytes, total: None })
+            .await;
+        reporter.send(TransferUpdate::Song).await;
+    }
+    Ok(folder)
+}
+
+/// Run a ripping tool, with its last line of output as the error
+async fn run(command: &mut tokio::process::Command, missing: &str) -> Result<()> {
+    let output = command
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => anyhow!(t!(missing)),
+            _ => e.into(),
+        })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last = stderr.lines().rev().find(|l| !l.trim().is_empty());
+        bail!(
+            "{}",
+            last.map(|l| l.trim().to_string())
+                .unwrap_or_else(|| format!("exited with {}", output.status))
+        );
+    }
+    Ok(())
+}
+
+/// Tag a ripped track from its release
+#[cfg(feature = "mpd")]
+async fn tag(path: PathBuf, stream: Stream, release: Option<Release>, (n, count): (usize, usize)) {
+    use crate::app::{tags, TagField};
+
+    let tagged = tokio::task::spawn_blocking(move || {
+        let disc = release
+            .as_ref()
+            .filter(|r| r.disc.1 > 1)
+            .map(|r| format!("{}/{}", r.disc.0, r.disc.1));
+        let wanted = [
+            (TagField::Title, Some(stream.title)),
+            (TagField::Artist, stream.uploader),
+            (TagField::Album, stream.album),
+            (
+                TagField::AlbumArtist,
+                release.as_ref().map(|r| r.artist.clone()),
+            ),
+            (TagField::Year, release.and_then(|r| r.year)),
+            (TagField::Track, Some(format!("{}/{}", n, count))),
+            (TagField::Disc, disc),
+        ];
+        tags::fill(&path, &wanted).map_err(|e| (path, e))
+    })
+    .await;
+    if let Ok(Err((path, e))) = tagged {
+        tracing::warn!("Couldn't tag {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disc_id_from_cdparanoia_toc() {
+        // MusicBrainz's worked example, as cdparanoia lists it
+        let text = "\
+cdparanoia III release 10.2 (September 11, 2008)
+
+Table of contents (audio tracks only):
+track        length               begin        copy pre ch
+===========================================================
+  1.    15213 [03:22.63]        0 [00:00.00]    no   no  2
+  2.    16951 [03:46.01]    15213 [03:22.63]    no   no  2
+  3.    14278 [03:10.28]    32164 [07:08.64]    no   no  2
+  4.    16822 [03:44.22]    46442 [10:19.17]    no   no  2
+  5.    17075 [03:47.50]    63264 [14:03.39]    no   no  2
+  6.    14973 [03:19.48]    80339 [17:51.14]    no   no  2
+TOTAL   95312 [21:10.62]    (audio only)
+";
+        let toc = parse_toc(text).unwrap();
+        assert_eq!(toc.tracks.len(), 6);
+        assert_eq!(toc.mb_toc(), "1+6+95462+150+15363+32314+46592+63414+80489");
+        assert_eq!(toc.disc_id(), "49HHV7Eb8UKF3aQiNmu1GR8vKTY-");
+        assert_eq!(parse_toc("Unable to open disc."), None);
+    }
+}
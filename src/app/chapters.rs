@@ -0,0 +1,361 @@
+//! Chapters of audiobooks and single-file albums 📖
+//!
+//! Taken from the first source that has any: a CUE sheet embedded in the
+//! tags (`CUESHEET`), Nero chapters in an MP4/M4B (`moov/udta/chpl`), or a
+//! sidecar `.cue` next to the file (same name, or any sheet whose `FILE`
+//! line names it).
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    pub start_ms: u64,
+}
+
+/// Chapters of the file at `path`, empty if it has none
+pub fn load(path: &Path) -> Vec<Chapter> {
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string());
+    let mut chapters = embedded_cue(path)
+        .map(|cue| parse_cue(&cue, None))
+        .unwrap_or_default();
+    if chapters.is_empty() && is_mp4(path) {
+        chapters = File::open(path)
+            .and_then(|mut f| read_chpl(&mut f))
+            .unwrap_or_else(|e| {
+                tracing::debug!("No MP4 chapters in {}: {}", path.display(), e);
+                Vec::new()
+            });
+    }
+    if chapters.is_empty() {
+        if let Some(cue) = sidecar_cue(path) {
+            chapters = parse_cue(&cue, file_name.as_deref());
+        }
+    }
+    // One "chapter" is just the track
+    if chapters.len() < 2 {
+        chapters.clear();
+    }
+    chapters
+}
+
+/// Index of the chapter playing at `position_ms`
+pub fn current(chapters: &[Chapter], position_ms: u64) -> Option<usize> {
+    chapters.iter().rposition(|c| c.start_ms <= position_ms)
+}
+
+fn is_mp4(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e.to_lowercase().as_str(), "m4b" | "m4a" | "mp4" | "aac"))
+}
+
+#[cfg(feature = "mpd")]
+fn embedded_cue(path: &Path) -> Option<String> {
+    use lofty::file::TaggedFileExt;
+    use lofty::tag::ItemKey;
+
+    let tagged = lofty::read_from_path(path).ok()?;
+    let key = ItemKey::Unknown("CUESHEET".to_string());
+    tagged
+        .tags()
+        .iter()
+        .find_map(|tag| tag.get_string(&key).map(str::to_string))
+}
+
+#[cfg(not(feature = "mpd"))]
+fn embedded_cue(_path: &Path) -> Option<String> {
+    None
+}
+
+/// `Album.cue` for `Album.flac`, else a sheet in the folder naming the file
+fn sidecar_cue(path: &Path) -> Option<String> {
+    let same_name = path.with_extension("cue");
+    if let Ok(text) = std::fs::read_to_string(&same_name) {
+        return Some(text);
+    }
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    std::fs::read_dir(path.parent()?)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("cue")))
+        .filter_map(|p| std::fs::read_to_string(p).ok())
+        .find(|text| cue_files(text).any(|f| f.to_lowercase() == name))
+}
+
+/// `"quoted value"` or the first word
+fn cue_value(rest: &str) -> &str {
+    let rest = rest.trim();
+    match rest.strip_prefix('"') {
+        Some(quoted) => quoted.rsplit_once('"').map_or(quoted, |(v, _)| v),
+        None => rest.split_whitespace().next().unwrap_or(""),
+    }
+}
+
+/// File names of the `FILE` lines (without folders)
+fn cue_files(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().filter_map(|line| {
+        let file = cue_value(line.trim().strip_prefix("FILE ")?);
+        file.rsplit(['/', '\\']).next()
+    })
+}
+
+/// Tracks of a CUE sheet as chapters. With `file_name`, only the tracks
+/// of that `FILE` (sheets can span several files).
+pub fn parse_cue(text: &str, file_name: Option<&str>) -> Vec<Chapter> {
+    let mut chapters = Vec::new();
+    let mut album_performer = String::new();
+    let mut in_file = file_name.is_none();
+    let mut seen_track = false;
+    // Number, title, performer, start of the track being read
+    let mut track: Option<(String, String, String, Option<u64>)> = None;
+
+    let mut finish = |track: Option<(String, String, String, Option<u64>)>,
+                      album_performer: &str| {
+        if let Some((number, title, performer, Some(start_ms))) = track {
+            let title = match (
+                title.is_empty(),
+                performer.is_empty() || performer == album_performer,
+            ) {
+                (true, _) => format!("Track {}", number),
+                (false, true) => title,
+                (false, false) => format!("{} – {}", performer, title),
+            };
+            chapters.push(Chapter { title, start_ms });
+        }
+    };
+
+    for line in text.lines() {
+        let line = line.trim().trim_start_matches('\u{feff}');
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match command {
+            "FILE" => {
+                finish(track.take(), &album_performer);
+                let file = cue_value(rest);
+                let file = file.rsplit(['/', '\\']).next().unwrap_or(file);
+                in_file = file_name.is_none_or(|n| n.eq_ignore_ascii_case(file));
+            }
+            "TRACK" => {
+                finish(track.take(), &album_performer);
+                seen_track = true;
+                if in_file {
+                    track = Some((
+                        cue_value(rest).to_string(),
+                        String::new(),
+                        String::new(),
+                        None,
+                    ));
+                }
+            }
+            "TITLE" => {
+                if let Some(track) = track.as_mut() {
+                    track.1 = cue_value(rest).to_string();
+                }
+            }
+            "PERFORMER" => match track.as_mut() {
+                Some(track) => track.2 = cue_value(rest).to_string(),
+                None if !seen_track => album_performer = cue_value(rest).to_string(),
+                None => {}
+            },
+            "INDEX" => {
+                let mut words = rest.split_whitespace();
+                if let (Some("01"), Some(time), Some(track)) =
+                    (words.next(), words.next(), track.as_mut())
+                {
+                    track.3 = cue_time(time);
+                }
+            }
+            _ => {}
+        }
+    }
+    finish(track.take(), &album_performer);
+    chapters
+}
+
+/// `mm:ss:ff` (75 frames a second) in ms
+fn cue_time(time: &str) -> Option<u64> {
+    let mut parts = time.split(':').map(|p| p.parse::<u64>().ok());
+    let (m, s, f) = (parts.next()??, parts.next()??, parts.next()??);
+    Some((m * 60 + s) * 1000 + f * 1000 / 75)
+}
+
+/// Box `kind` between `start` and `end`: where its body starts and ends
+fn find_box<R: Read + Seek>(
+    r: &mut R,
+    start: u64,
+    end: u64,
+    kind: &[u8; 4],
+) -> std::io::Result<Option<(u64, u64)>> {
+    let mut pos = start;
+    while pos + 8 <= end {
+        r.seek(SeekFrom::Start(pos))?;
+        let mut header = [0u8; 8];
+        r.read_exact(&mut header)?;
+        let mut size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+        let mut body = pos + 8;
+        if size == 1 {
+            let mut large = [0u8; 8];
+            r.read_exact(&mut large)?;
+            size = u64::from_be_bytes(large);
+            body += 8;
+        } else if size == 0 {
+            size = end - pos;
+        }
+        if size < body - pos {
+            break;
+        }
+        if &header[4..8] == kind {
+            return Ok(Some((body, (pos + size).min(end))));
+        }
+        pos += size;
+    }
+    Ok(None)
+}
+
+/// Nero chapters (`moov/udta/chpl`), as written by most audiobook tools
+pub fn read_chpl<R: Read + Seek>(r: &mut R) -> std::io::Result<Vec<Chapter>> {
+    let len = r.seek(SeekFrom::End(0))?;
+    let mut range = (0, len);
+    for kind in [b"moov", b"udta", b"chpl"] {
+        match find_box(r, range.0, range.1, kind)? {
+            Some(found) => range = found,
+            None => return Ok(Vec::new()),
+        }
+    }
+
+    // Chapter titles only: a few KB at most
+    let mut data = Vec::new();
+    r.seek(SeekFrom::Start(range.0))?;
+    r.take((range.1 - range.0).min(1 << 20))
+        .read_to_end(&mut data)?;
+
+    let mut chapters = Vec::new();
+    // Version, flags, and a reserved word in version 1
+    let mut i = if data.first() == Some(&1) { 8 } else { 4 };
+    let Some(&count) = data.get(i) else {
+        return Ok(chapters);
+    };
+    i += 1;
+    for _ in 0..count {
+        let Some(start) = data.get(i..i + 8) else {
+            break;
+        };
+        // 100 ns units
+        let start_ms = u64::from_be_bytes(start.try_into().unwrap_or_default()) / 10_000;
+        let Some(&title_len) = data.get(i + 8) else {
+            break;
+        };
+        let title_start = i + 9;
+        let Some(title) = data.get(title_start..title_start + title_len as usize) else {
+            break;
+        };
+        chapters.push(Chapter {
+            title: String::from_utf8_lossy(title).to_string(),
+            start_ms,
+        });
+        i = title_start + title_len as usize;
+    }
+    Ok(chapters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cue_tracks_become_chapters() {
+        let cue = "\u{feff}PERFORMER \"Various\"\n\
+            TITLE \"Mix\"\n\
+            FILE \"other.flac\" WAVE\n\
+              TRACK 01 AUDIO\n\
+                TITLE \"Elsewhere\"\n\
+                INDEX 01 00:00:00\n\
+            FILE \"Mix.flac\" WAVE\n\
+              TRACK 02 AUDIO\n\
+                TITLE \"Opening\"\n\
+                PERFORMER \"Various\"\n\
+                INDEX 00 00:00:00\n\
+                INDEX 01 00:00:30\n\
+              TRACK 03 AUDIO\n\
+                TITLE \"Deep Cut\"\n\
+                PERFORMER \"DJ Someone\"\n\
+                INDEX 01 05:02:60\n\
+              TRACK 04 AUDIO\n\
+                INDEX 01 61:00:00\n";
+
+        assert_eq!(
+            parse_cue(cue, Some("mix.FLAC")),
+            vec![
+                Chapter {
+                    title: "Opening".into(),
+                    start_ms: 400
+                },
+                Chapter {
+                    title: "DJ Someone – Deep Cut".into(),
+                    start_ms: 302_800
+                },
+                Chapter {
+                    title: "Track 04".into(),
+                    start_ms: 3_660_000
+                },
+            ]
+        );
+        assert_eq!(parse_cue(cue, None).len(), 4);
+        assert_eq!(
+            cue_files(cue).collect::<Vec<_>>(),
+            ["other.flac", "Mix.flac"]
+        );
+
+        let chapters = parse_cue(cue, Some("Mix.flac"));
+        assert_eq!(current(&chapters, 0), None);
+        assert_eq!(current(&chapters, 302_800), Some(1));
+        assert_eq!(current(&chapters, 9_000_000), Some(2));
+    }
+
+    #[test]
+    fn test_mp4_nero_chapters() {
+        fn mp4_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+            let mut out = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+            out.extend_from_slice(kind);
+            out.extend_from_slice(body);
+            out
+        }
+        let mut chpl = vec![1, 0, 0, 0, 0, 0, 0, 0, 2];
+        for (start, title) in [(0u64, "Prologue"), (6_000_000_000, "Chapter 1")] {
+            chpl.extend_from_slice(&start.to_be_bytes());
+            chpl.push(title.len() as u8);
+            chpl.extend_from_slice(title.as_bytes());
+        }
+        let udta = mp4_box(b"udta", &mp4_box(b"chpl", &chpl));
+        let moov = mp4_box(b"moov", &[mp4_box(b"mvhd", &[0; 12]), udta].concat());
+        let file = [
+            mp4_box(b"ftyp", b"M4B \0\0\0\0"),
+            mp4_box(b"mdat", &[0; 64]),
+            moov,
+        ]
+        .concat();
+
+        let chapters = read_chpl(&mut std::io::Cursor::new(file)).unwrap();
+        assert_eq!(
+            chapters,
+            vec![
+                Chapter {
+                    title: "Prologue".into(),
+                    start_ms: 0
+                },
+                Chapter {
+                    title: "Chapter 1".into(),
+                    start_ms: 600_000
+                },
+            ]
+        );
+        assert!(
+            read_chpl(&mut std::io::Cursor::new(mp4_box(b"ftyp", b"M4A ")))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
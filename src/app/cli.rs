@@ -1,4 +1,9 @@
-use clap::Parser;
+use crate::app::export::Format;
+use crate::app::remote::{RemoteCommand, Seek, Volume};
+use crate::logging::LogLevel;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Parser, Subcommand};
+use std::path::PathBuf;
 
 /// Vyom - A beautiful music companion for your terminal 🎵
 #[derive(Parser, Debug)]
@@ -12,6 +17,10 @@ pub struct Args {
     #[arg(long, short = 'm')]
     pub mini: bool,
 
+    /// One-line now playing bar (also used automatically below 6 rows)
+    #[arg(long)]
+    pub bar: bool,
+
     /// Play MP3/FLAC music (Defaults to MPD Client mode)
     #[arg(long, short = 'c')]
     pub controller: bool,
@@ -26,7 +35,147 @@ pub struct Args {
     #[arg(long, default_value_t = 6600)]
     pub mpd_port: u16,
 
+    /// How much goes into ~/.cache/vyom/vyom.log and the log viewer (F12)
+    #[arg(long, value_enum, default_value_t = LogLevel::Info)]
+    pub log_level: LogLevel,
+
     /// Generate default config.toml to stdout
     #[arg(long)]
     pub generate_config: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// One-shot commands that run without starting the TUI
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Equalizer preset management
+    Eq {
+        #[command(subcommand)]
+        action: EqCommand,
+    },
+    /// Start playback (in the running player, else on MPD)
+    Play,
+    /// Pause playback
+    Pause,
+    /// Toggle play/pause
+    Toggle,
+    /// Skip to the next track
+    Next,
+    /// Go back to the previous track
+    Prev,
+    /// Seek to a time (90, 1:30) or by an offset (+10, -10)
+    Seek {
+        #[arg(allow_hyphen_values = true)]
+        position: String,
+    },
+    /// Set the volume (0-100) or change it (+5, -5)
+    Volume {
+        #[arg(allow_hyphen_values = true)]
+        level: String,
+    },
+    /// Queue management
+    Queue {
+        #[command(subcommand)]
+        action: QueueCommand,
+    },
+    /// Check MPD, the audio feed, lyrics and the terminal, with fixes
+    Doctor,
+    /// List the LADSPA plugins `[[dsp.plugins]]` can use
+    Plugins,
+    /// Write the listening history, ratings, favorites and playlists out
+    Export {
+        #[arg(long, value_enum, default_value_t = Format::Json)]
+        format: Format,
+
+        /// File to write (defaults to stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Read what `vyom export` wrote (JSON or CSV) back in
+    Import { file: PathBuf },
+    /// Turn a Spotify playlist into an MPD playlist of the songs you have
+    Spotify {
+        /// Playlist link (open.spotify.com/playlist/...) or ID
+        url: String,
+
+        /// MPD playlist to create (default: the Spotify playlist's name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Overwrite the MPD playlist if it already exists
+        #[arg(long)]
+        replace: bool,
+    },
+}
+
+impl Command {
+    /// The playback command behind `vyom play` & co.; `None` for the others
+    pub fn remote(&self) -> Option<Result<RemoteCommand, String>> {
+        Some(match self {
+            Command::Eq { .. }
+            | Command::Doctor
+            | Command::Plugins
+            | Command::Export { .. }
+            | Command::Import { .. }
+            | Command::Spotify { .. } => return None,
+            Command::Play => Ok(RemoteCommand::Play),
+            Command::Pause => Ok(RemoteCommand::Pause),
+            Command::Toggle => Ok(RemoteCommand::Toggle),
+            Command::Next => Ok(RemoteCommand::Next),
+            Command::Prev => Ok(RemoteCommand::Prev),
+            Command::Seek { position } => Seek::parse(position).map(RemoteCommand::Seek),
+            Command::Volume { level } => Volume::parse(level).map(RemoteCommand::Volume),
+            Command::Queue {
+                action: QueueCommand::Add { path },
+            } => Ok(RemoteCommand::QueueAdd(path.to_string_lossy().to_string())),
+        })
+    }
+}
+
+impl Args {
+    /// Fill in what `config.toml` sets and the command line didn't
+    #[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+    pub fn apply_config(&mut self, config: &crate::app::config::UserConfig, matches: &ArgMatches) {
+        let from_cli = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+        #[cfg(feature = "mpd")]
+        {
+            if let Some(host) = config.mpd_host.as_ref().filter(|_| !from_cli("mpd_host")) {
+                self.mpd_host = host.clone();
+            }
+            if let Some(port) = config.mpd_port.filter(|_| !from_cli("mpd_port")) {
+                self.mpd_port = port;
+            }
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum QueueCommand {
+    /// Add a song or folder (a path inside the music directory, or MPD's name for it)
+    Add { path: PathBuf },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum EqCommand {
+    /// Import a preset: one `vyom eq export` wrote, or an AutoEq profile
+    /// (ParametricEQ.txt or GraphicEQ.txt)
+    Import {
+        /// Path to the AutoEq export
+        file: PathBuf,
+
+        /// Preset name (defaults to the headphone name from the file)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Write a preset as JSON that `vyom eq import` reads back
+    Export {
+        /// Preset name, as shown in the EQ view
+        name: String,
+
+        /// File to write (defaults to stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
 }
@@ -0,0 +1,325 @@
+//! AutoEq headphone profile import 🎧
+//!
+//! Supports both export formats from the AutoEq project:
+//! - `ParametricEQ.txt`: `Filter 1: ON PK Fc 105 Hz Gain -2.3 dB Q 0.70`
+//! - `GraphicEQ.txt`:    `GraphicEQ: 20 -3.2; 21 -3.1; ...`
+//!
+//! Either way the profile is sampled at our 10 band centers and
+//! converted to a regular `EqPreset`.
+
+use super::presets::EqPreset;
+use crate::audio::dsp::{db_to_value, EQ_FREQUENCIES};
+use crate::error::VyomError;
+use std::f32::consts::PI;
+use std::path::Path;
+
+/// Sample rate used to evaluate parametric filters (only affects the top octave slightly)
+const EVAL_SAMPLE_RATE: f32 = 48000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FilterKind {
+    Peak,
+    LowShelf,
+    HighShelf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ParametricFilter {
+    kind: FilterKind,
+    freq: f32,
+    gain_db: f32,
+    q: f32,
+}
+
+/// A parsed AutoEq profile, before mapping to bands
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoEqProfile {
+    /// Preamp suggested by AutoEq (dB, usually negative)
+    pub preamp_db: f32,
+    /// Gain in dB at each of the 10 band centers (unclamped)
+    pub band_gains_db: [f32; 10],
+}
+
+impl AutoEqProfile {
+    /// Parse either a ParametricEQ or GraphicEQ file
+    pub fn parse(content: &str) -> Result<Self, VyomError> {
+        if let Some(line) = content
+            .lines()
+            .find(|l| l.trim_start().starts_with("GraphicEQ:"))
+        {
+            return Self::parse_graphic(line);
+        }
+        Self::parse_parametric(content)
+    }
+
+    fn parse_parametric(content: &str) -> Result<Self, VyomError> {
+        let mut preamp_db = 0.0;
+        let mut filters = Vec::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("Preamp:") {
+                preamp_db = parse_number(rest.trim().trim_end_matches("dB")).unwrap_or(0.0);
+                continue;
+            }
+            if !line.starts_with("Filter") {
+                continue;
+            }
+
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            // Skip disabled filters ("Filter 3: OFF ...")
+            if !tokens.contains(&"ON") {
+                continue;
+            }
+            let kind = if tokens.contains(&"PK") || tokens.contains(&"PEQ") {
+                FilterKind::Peak
+            } else if tokens.contains(&"LSC") || tokens.contains(&"LS") {
+                FilterKind::LowShelf
+            } else if tokens.contains(&"HSC") || tokens.contains(&"HS") {
+                FilterKind::HighShelf
+            } else {
+                tracing::debug!("Skipping unsupported AutoEq filter: {}", line);
+                continue;
+            };
+
+            let value_after = |key: &str| {
+                tokens
+                    .iter()
+                    .position(|t| *t == key)
+                    .and_then(|i| tokens.get(i + 1))
+                    .and_then(|v| parse_number(v))
+            };
+
+            let (Some(freq), Some(gain_db)) = (value_after("Fc"), value_after("Gain")) else {
+                return Err(VyomError::ConfigParse(format!(
+                    "Malformed AutoEq filter: {}",
+                    line
+                )));
+            };
+            let q = value_after("Q").unwrap_or(0.707);
+
+            filters.push(ParametricFilter {
+                kind,
+                freq,
+                gain_db,
+                q,
+            });
+        }
+
+        if filters.is_empty() {
+            return Err(VyomError::ConfigParse(
+                "No AutoEq filters found (expected ParametricEQ or GraphicEQ format)".to_string(),
+            ));
+        }
+
+        let mut band_gains_db = [0.0; 10];
+        for (gain, &freq) in band_gains_db.iter_mut().zip(EQ_FREQUENCIES.iter()) {
+            *gain = filters.iter().map(|f| f.response_db(freq)).sum();
+        }
+
+        Ok(Self {
+            preamp_db,
+            band_gains_db,
+        })
+    }
+
+    fn parse_graphic(line: &str) -> Result<Self, VyomError> {
+        let data = line.trim_start().trim_start_matches("GraphicEQ:").trim();
+
+        let mut points: Vec<(f32, f32)> = data
+            .split(';')
+            .filter_map(|pair| {
+                let mut it = pair.split_whitespace();
+                Some((parse_number(it.next()?)?, parse_number(it.next()?)?))
+            })
+            .filter(|(freq, _)| *freq > 0.0)
+            .collect();
+
+        if points.is_empty() {
+            return Err(VyomError::ConfigParse(
+                "GraphicEQ line has no frequency/gain points".to_string(),
+            ));
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut band_gains_db = [0.0; 10];
+        for (gain, &freq) in band_gains_db.iter_mut().zip(EQ_FREQUENCIES.iter()) {
+            *gain = interpolate_log(&points, freq);
+        }
+
+        // GraphicEQ has no separate preamp; suggest one that avoids clipping
+        let max_boost = band_gains_db.iter().cloned().fold(0.0f32, f32::max);
+
+        Ok(Self {
+            preamp_db: -max_boost,
+            band_gains_db,
+        })
+    }
+
+    /// Convert into a preset usable by the 10-band engine (±12dB clamp)
+    pub fn to_preset(&self, name: &str) -> EqPreset {
+        let mut bands = [0.5; 10];
+        for (band, db) in bands.iter_mut().zip(self.band_gains_db.iter()) {
+            *band = db_to_value(db.clamp(-12.0, 12.0));
+        }
+        EqPreset::new(name, bands)
+    }
+}
+
+/// Derive a preset name from an AutoEq file path
+/// ("Sennheiser HD 600 ParametricEQ.txt" -> "Sennheiser HD 600")
+pub fn preset_name_from_path(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "AutoEq".to_string());
+    let name = stem
+        .trim_end_matches("ParametricEQ")
+        .trim_end_matches("GraphicEQ")
+        .trim_end_matches(['-', '_', ' '])
+        .to_string();
+    if name.is_empty() {
+        "AutoEq".to_string()
+    } else {
+        name
+    }
+}
+
+fn parse_number(s: &str) -> Option<f32> {
+    s.trim().parse::<f32>().ok()
+}
+
+/// Linear interpolation on a log-frequency axis
+pub(super) fn interpolate_log(points: &[(f32, f32)], freq: f32) -> f32 {
+    let first = points[0];
+    let last = points[points.len() - 1];
+    if freq <= first.0 {
+        return first.1;
+    }
+    if freq >= last.0 {
+        return last.1;
+    }
+    for pair in points.windows(2) {
+        let (f1, g1) = pair[0];
+        let (f2, g2) = pair[1];
+        if freq >= f1 && freq <= f2 {
+            if (f2 - f1).abs() < f32::EPSILON {
+                return g1;
+            }
+            let t = (freq.ln() - f1.ln()) / (f2.ln() - f1.ln());
+            return g1 + (g2 - g1) * t;
+        }
+    }
+    last.1
+}
+
+impl ParametricFilter {
+    /// Magnitude response (dB) at `freq`, using the RBJ cookbook biquads
+    fn response_db(&self, freq: f32) -> f32 {
+        let w0 = 2.0 * PI * self.freq / EVAL_SAMPLE_RATE;
+        let a = 10.0_f32.powf(self.gain_db / 40.0);
+        let alpha = w0.sin() / (2.0 * self.q.max(0.01));
+        let cos_w0 = w0.cos();
+
+        let (b0, b1, b2, a0, a1, a2) = match self.kind {
+            FilterKind::Peak => (
+                1.0 + alpha * a,
+                -2.0 * cos_w0,
+                1.0 - alpha * a,
+                1.0 + alpha / a,
+                -2.0 * cos_w0,
+                1.0 - alpha / a,
+            ),
+            FilterKind::LowShelf => {
+                let sq = 2.0 * a.sqrt() * alpha;
+                (
+                    a * ((a + 1.0) - (a - 1.0) * cos_w0 + sq),
+                    2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0),
+                    a * ((a + 1.0) - (a - 1.0) * cos_w0 - sq),
+                    (a + 1.0) + (a - 1.0) * cos_w0 + sq,
+                    -2.0 * ((a - 1.0) + (a + 1.0) * cos_w0),
+                    (a + 1.0) + (a - 1.0) * cos_w0 - sq,
+                )
+            }
+            FilterKind::HighShelf => {
+                let sq = 2.0 * a.sqrt() * alpha;
+                (
+                    a * ((a + 1.0) + (a - 1.0) * cos_w0 + sq),
+                    -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0),
+                    a * ((a + 1.0) + (a - 1.0) * cos_w0 - sq),
+                    (a + 1.0) - (a - 1.0) * cos_w0 + sq,
+                    2.0 * ((a - 1.0) - (a + 1.0) * cos_w0),
+                    (a + 1.0) - (a - 1.0) * cos_w0 - sq,
+                )
+            }
+        };
+
+        // Evaluate H(e^jw)
+        let w = 2.0 * PI * freq / EVAL_SAMPLE_RATE;
+        let (c1, s1, c2, s2) = (w.cos(), w.sin(), (2.0 * w).cos(), (2.0 * w).sin());
+        let num_re = b0 + b1 * c1 + b2 * c2;
+        let num_im = -(b1 * s1 + b2 * s2);
+        let den_re = a0 + a1 * c1 + a2 * c2;
+        let den_im = -(a1 * s1 + a2 * s2);
+
+        let mag =
+            ((num_re * num_re + num_im * num_im) / (den_re * den_re + den_im * den_im)).sqrt();
+        20.0 * mag.max(1e-9).log10()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parametric_peak_hits_center() {
+        let content = "Preamp: -6.0 dB\nFilter 1: ON PK Fc 1000 Hz Gain 6.0 dB Q 1.41\n";
+        let profile = AutoEqProfile::parse(content).unwrap();
+        assert!((profile.preamp_db + 6.0).abs() < 0.01);
+        // Band 5 is 1kHz
+        assert!((profile.band_gains_db[5] - 6.0).abs() < 0.1);
+        // Far away bands are barely touched
+        assert!(profile.band_gains_db[0].abs() < 0.5);
+    }
+
+    #[test]
+    fn test_parametric_skips_disabled_filters() {
+        let content = "Filter 1: OFF PK Fc 1000 Hz Gain 6.0 dB Q 1.41\n\
+                       Filter 2: ON LSC Fc 105 Hz Gain 4.0 dB Q 0.70\n";
+        let profile = AutoEqProfile::parse(content).unwrap();
+        assert!(profile.band_gains_db[5].abs() < 0.5);
+        assert!((profile.band_gains_db[0] - 4.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_graphic_interpolation() {
+        let content = "GraphicEQ: 20 -4.0; 1000 2.0; 20000 0.0";
+        let profile = AutoEqProfile::parse(content).unwrap();
+        assert!((profile.band_gains_db[5] - 2.0).abs() < 0.01);
+        assert!((profile.preamp_db + 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_preset_clamps_to_engine_range() {
+        let profile = AutoEqProfile {
+            preamp_db: 0.0,
+            band_gains_db: [20.0, -20.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        };
+        let preset = profile.to_preset("Test");
+        assert!((preset.bands[0] - 1.0).abs() < f32::EPSILON);
+        assert!((preset.bands[1] - 0.0).abs() < f32::EPSILON);
+        assert!((preset.bands[2] - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_empty_file_is_error() {
+        assert!(AutoEqProfile::parse("# nothing here").is_err());
+    }
+
+    #[test]
+    fn test_preset_name_from_path() {
+        let p = Path::new("/tmp/Sennheiser HD 600 ParametricEQ.txt");
+        assert_eq!(preset_name_from_path(p), "Sennheiser HD 600");
+    }
+}
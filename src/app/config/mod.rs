@@ -1,15 +1,17 @@
 use std::fs;
 use std::path::PathBuf;
 
+pub mod autoeq;
 pub mod persistence;
 pub mod presets;
+pub mod sharing;
 pub mod user;
 
 // Fix circular dependency: create a wrapper module or re-export to allow `persistence.rs` to find `AppConfig`.
 // Actually, declaring `pub mod mod_container` logic here.
 
 use persistence::LegacyConfigMixin;
-pub use persistence::PersistentState;
+pub use persistence::{PaneState, PersistentState, SessionState};
 pub use presets::{get_default_presets, EqPreset};
 pub use user::UserConfig;
 
@@ -38,6 +40,12 @@ impl AppConfig {
         Self::get_config_dir().join("state.toml")
     }
 
+    /// Write `config.toml` (comments in an existing file are lost)
+    pub fn write_user_config(config: &UserConfig) -> Result<(), String> {
+        let content = toml::to_string_pretty(config).map_err(|e| e.to_string())?;
+        fs::write(Self::get_config_path(), content).map_err(|e| e.to_string())
+    }
+
     /// Load both (with migration)
     pub fn load() -> (UserConfig, PersistentState, Option<String>) {
         let config_path = Self::get_config_path();
@@ -60,10 +68,8 @@ impl AppConfig {
         } else {
             // Create default config.toml if missing
             let c = UserConfig::default();
-            if let Ok(content) = toml::to_string_pretty(&c) {
-                if let Err(e) = fs::write(&config_path, content) {
-                    tracing::warn!("Failed to write default UserConfig: {}", e);
-                }
+            if let Err(e) = Self::write_user_config(&c) {
+                tracing::warn!("Failed to write default UserConfig: {}", e);
             }
             c
         };
@@ -92,6 +98,14 @@ impl AppConfig {
                             volume: legacy.volume,
                             presets: legacy.presets, // Migrate presets too
                             art_style: crate::app::state::artwork::ArtStyle::default(),
+                            lyrics_offsets: Default::default(),
+                            resume_positions: Default::default(),
+                            track_volumes: Default::default(),
+                            bookmarks: Default::default(),
+                            theme: None,
+                            theme_config: None,
+                            panes: None,
+                            session: None,
                         };
                         s.save(); // Save to new state.toml immediately
                         s
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 
 use super::presets::EqPreset;
+use crate::app::state::{LibraryMode, ViewMode};
 
 /// Automatically saved session state
 /// stored in `state.toml`
@@ -29,6 +30,62 @@ pub struct PersistentState {
 
     #[serde(default)]
     pub art_style: crate::app::state::artwork::ArtStyle,
+
+    /// Per-track lyrics sync offsets in ms ("Artist - Title" -> offset)
+    #[serde(default)]
+    pub lyrics_offsets: std::collections::BTreeMap<String, i64>,
+
+    /// Where long tracks were left off in ms (file -> position)
+    #[serde(default)]
+    pub resume_positions: std::collections::BTreeMap<String, u64>,
+
+    /// Volume set by hand per album or track ("album:<folder>" -> 0-100)
+    #[serde(default)]
+    pub track_volumes: std::collections::BTreeMap<String, u8>,
+
+    /// Bookmarks in ms, per file
+    #[serde(default)]
+    pub bookmarks: std::collections::BTreeMap<String, Vec<u64>>,
+
+    /// Theme chosen in the theme picker (overrides `theme` in config.toml)
+    #[serde(default)]
+    pub theme: Option<String>,
+
+    /// config.toml's `theme` when the pick was made. Once the config names
+    /// another, it was edited since and wins over the pick.
+    #[serde(default)]
+    pub theme_config: Option<String>,
+
+    /// Pane sizes / order set with the pane keys (overrides `[layout]`)
+    #[serde(default)]
+    pub panes: Option<PaneState>,
+
+    /// Where the last session left off
+    #[serde(default)]
+    pub session: Option<SessionState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PaneState {
+    pub side_split: u16,
+    pub stack_split: u16,
+    pub swapped: bool,
+}
+
+/// Views and library position at quit, restored on the next start
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    pub view_mode: ViewMode,
+    pub split_view: Option<ViewMode>,
+    pub library_mode: LibraryMode,
+    pub browse_path: Vec<String>,
+    pub library_selected: usize,
+    pub search_query: String,
+    pub album_open: Option<String>,
+    /// Album artist of `album_open`
+    pub album_open_artist: String,
+    pub eq_selected: usize,
 }
 
 fn default_bands() -> [f32; 10] {
@@ -52,6 +109,14 @@ impl Default for PersistentState {
             volume: 50,
             presets: Vec::new(),
             art_style: crate::app::state::artwork::ArtStyle::default(),
+            lyrics_offsets: Default::default(),
+            resume_positions: Default::default(),
+            track_volumes: Default::default(),
+            bookmarks: Default::default(),
+            theme: None,
+            theme_config: None,
+            panes: None,
+            session: None,
         }
     }
 }
@@ -80,6 +145,13 @@ pub(crate) struct LegacyConfigMixin {
 }
 
 impl PersistentState {
+    /// The picker's theme, unless config.toml's `theme` changed after it
+    pub fn picked_theme(&self, config_theme: Option<&str>) -> Option<String> {
+        self.theme
+            .clone()
+            .filter(|_| self.theme_config.as_deref() == config_theme)
+    }
+
     pub fn save(&self) {
         let path = super::AppConfig::get_state_path();
         if let Ok(content) = toml::to_string_pretty(self) {
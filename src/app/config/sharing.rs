@@ -0,0 +1,254 @@
+//! Portable EQ presets 🎚️
+//!
+//! `vyom eq export <name>` writes a preset as JSON for someone else's
+//! `vyom eq import <file>` (or `P` in the EQ view):
+//!
+//! ```json
+//! {
+//!   "format": "vyom-eq",
+//!   "version": 1,
+//!   "name": "Warm",
+//!   "preamp_db": -2.0,
+//!   "bands": [{ "hz": 32.0, "db": 2.5 }, ...],
+//!   "checksum": "fnv1a64:9f2c..."
+//! }
+//! ```
+//!
+//! Gains are in dB at each band's frequency, so a file stays meaningful if
+//! the bands ever move: other frequencies are read off the curve between
+//! them. Fields a reader doesn't know are ignored; a newer `version` means
+//! the fields it does know may have changed, so it is refused. The
+//! checksum catches files that were cut short or edited by hand; it is
+//! optional, for presets written by hand in the first place.
+
+use super::autoeq::{interpolate_log, preset_name_from_path, AutoEqProfile};
+use super::presets::{get_default_presets, EqPreset};
+use super::AppConfig;
+use crate::audio::dsp::{db_to_value, value_to_db, EQ_FREQUENCIES};
+use crate::error::VyomError;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const FORMAT: &str = "vyom-eq";
+/// Bumped when a field changes meaning
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedPreset {
+    pub format: String,
+    pub version: u32,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preamp_db: Option<f32>,
+    pub bands: Vec<SharedBand>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SharedBand {
+    pub hz: f32,
+    pub db: f32,
+}
+
+impl SharedPreset {
+    pub fn new(preset: &EqPreset, preamp_db: Option<f32>) -> Self {
+        let bands: Vec<SharedBand> = EQ_FREQUENCIES
+            .iter()
+            .zip(preset.bands.iter())
+            .map(|(&hz, &value)| SharedBand {
+                hz,
+                // Two decimals is finer than any step the EQ view takes
+                db: (value_to_db(value) * 100.0).round() / 100.0,
+            })
+            .collect();
+        let mut shared = Self {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            name: preset.name.clone(),
+            preamp_db,
+            bands,
+            checksum: None,
+        };
+        shared.checksum = Some(shared.digest());
+        shared
+    }
+
+    /// FNV-1a over the fields that matter, rounded so that the float
+    /// formatting of whoever wrote the file doesn't
+    fn digest(&self) -> String {
+        let mut text = format!("{}|{}|{}", self.format, self.version, self.name);
+        if let Some(preamp) = self.preamp_db {
+            text.push_str(&format!("|{:.2}", preamp));
+        }
+        for band in &self.bands {
+            text.push_str(&format!("|{:.1}:{:.2}", band.hz, band.db));
+        }
+        let hash = text.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        format!("fnv1a64:{:016x}", hash)
+    }
+
+    /// The preset for our bands, after checking the file is one we can read
+    pub fn to_preset(&self) -> Result<EqPreset, VyomError> {
+        let invalid = |why: String| VyomError::ConfigParse(why);
+        if self.format != FORMAT {
+            return Err(invalid(format!("Not a Vyom EQ preset ({})", self.format)));
+        }
+        if self.version > VERSION {
+            return Err(invalid(format!(
+                "Preset format version {} is newer than this Vyom reads ({}); update Vyom",
+                self.version, VERSION
+            )));
+        }
+        if let Some(checksum) = &self.checksum {
+            if *checksum != self.digest() {
+                return Err(invalid(
+                    "Preset checksum doesn't match (file damaged or edited)".to_string(),
+                ));
+            }
+        }
+        let mut points: Vec<(f32, f32)> = self
+            .bands
+            .iter()
+            .filter(|b| b.hz > 0.0 && b.db.is_finite())
+            .map(|b| (b.hz, b.db))
+            .collect();
+        if points.is_empty() {
+            return Err(invalid("Preset has no bands".to_string()));
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut bands = [0.5; 10];
+        for (band, &hz) in bands.iter_mut().zip(EQ_FREQUENCIES.iter()) {
+            *band = db_to_value(interpolate_log(&points, hz).clamp(-12.0, 12.0));
+        }
+        Ok(EqPreset::new(&self.name, bands))
+    }
+}
+
+/// `preset` as the JSON `vyom eq export` writes
+pub fn to_json(preset: &EqPreset, preamp_db: Option<f32>) -> String {
+    // Only floats and strings: this can't fail
+    serde_json::to_string_pretty(&SharedPreset::new(preset, preamp_db)).unwrap_or_default()
+}
+
+/// The built-in or saved preset called `name` (any case), with the preamp
+/// when it is the one in use
+pub fn export(name: &str) -> Result<String, VyomError> {
+    let (_, state, _) = AppConfig::load();
+    let mut presets = get_default_presets();
+    presets.extend(state.presets.iter().cloned());
+    if state.last_preset_name == "Custom" {
+        presets.push(EqPreset::new("Custom", state.eq_bands));
+    }
+    let preset = presets
+        .iter()
+        .rev()
+        .find(|p| p.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let names: Vec<&str> = presets.iter().map(|p| p.name.as_str()).collect();
+            VyomError::ConfigParse(format!(
+                "No preset called \"{}\" (there's {})",
+                name,
+                names.join(", ")
+            ))
+        })?;
+    let preamp = (preset.name == state.last_preset_name && state.preamp_db != 0.0)
+        .then_some(state.preamp_db);
+    Ok(to_json(preset, preamp))
+}
+
+/// A preset file of either kind: our JSON, or an AutoEq export. The
+/// suggested preamp comes with it.
+pub fn read_file(path: &Path, name: Option<String>) -> Result<(EqPreset, f32), VyomError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| VyomError::ConfigParse(format!("{}: {}", path.display(), e)))?;
+    if content.trim_start().starts_with('{') {
+        let shared: SharedPreset = serde_json::from_str(&content)
+            .map_err(|e| VyomError::ConfigParse(format!("{}: {}", path.display(), e)))?;
+        let mut preset = shared.to_preset()?;
+        preset.name = own_name(name.unwrap_or(preset.name));
+        return Ok((preset, shared.preamp_db.unwrap_or(0.0)));
+    }
+    let profile = AutoEqProfile::parse(&content)?;
+    let name = own_name(name.unwrap_or_else(|| preset_name_from_path(path)));
+    Ok((profile.to_preset(&name), profile.preamp_db))
+}
+
+/// Built-in names (and "Custom") aren't saved as user presets, so an
+/// import called that gets a name of its own
+fn own_name(name: String) -> String {
+    let taken = name == "Custom" || get_default_presets().iter().any(|p| p.name == name);
+    if taken {
+        format!("{} (imported)", name)
+    } else {
+        name
+    }
+}
+
+/// Read a preset file and store it in state.toml. An existing preset with
+/// the same name is replaced.
+pub fn import_file(path: &Path, name: Option<String>) -> Result<(EqPreset, f32), VyomError> {
+    let (preset, preamp_db) = read_file(path, name)?;
+    let (_, mut state, _) = AppConfig::load();
+    match state.presets.iter_mut().find(|p| p.name == preset.name) {
+        Some(existing) => *existing = preset.clone(),
+        None => state.presets.push(preset.clone()),
+    }
+    state.save();
+    Ok((preset, preamp_db))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_preset_round_trip() {
+        let preset = EqPreset::new(
+            "Warm",
+            [0.6, 0.6, 0.55, 0.5, 0.5, 0.5, 0.45, 0.45, 0.5, 0.5],
+        );
+        let json = to_json(&preset, Some(-2.4));
+        let shared: SharedPreset = serde_json::from_str(&json).unwrap();
+        assert_eq!(shared.version, VERSION);
+        assert_eq!(shared.preamp_db, Some(-2.4));
+        let back = shared.to_preset().unwrap();
+        assert_eq!(back.name, "Warm");
+        for (a, b) in back.bands.iter().zip(preset.bands.iter()) {
+            assert!((a - b).abs() < 0.001);
+        }
+
+        // Edited by hand: the checksum no longer matches
+        let mut edited = shared.clone();
+        edited.bands[0].db = 12.0;
+        assert!(edited.to_preset().is_err());
+        // ...unless there isn't one
+        edited.checksum = None;
+        assert!((edited.to_preset().unwrap().bands[0] - 1.0).abs() < f32::EPSILON);
+
+        let mut newer = shared;
+        newer.version = VERSION + 1;
+        assert!(newer.to_preset().is_err());
+    }
+
+    #[test]
+    fn test_shared_preset_reads_other_bands_and_fields() {
+        // Different frequencies, and a field from some later version
+        let json = r#"{
+            "format": "vyom-eq",
+            "version": 1,
+            "name": "Smile",
+            "bands": [{ "hz": 20, "db": 6 }, { "hz": 1000, "db": 0 }, { "hz": 20000, "db": 6 }],
+            "author": "someone"
+        }"#;
+        let shared: SharedPreset = serde_json::from_str(json).unwrap();
+        let preset = shared.to_preset().unwrap();
+        // 1 kHz is band 5; 32 Hz and 16 kHz are near the ends
+        assert!((preset.bands[5] - 0.5).abs() < 0.001);
+        assert!(preset.bands[0] > 0.7);
+        assert!(preset.bands[9] > 0.7);
+    }
+}
@@ -6,8 +6,130 @@ use serde::{Deserialize, Serialize};
 pub struct UserConfig {
     #[serde(default)]
     pub keys: crate::app::keys::KeyConfig,
+    /// MPD server, unless `--mpd-host`/`--mpd-port` are given
+    #[serde(default)]
+    pub mpd_host: Option<String>,
+    #[serde(default)]
+    pub mpd_port: Option<u16>,
     #[serde(default = "default_music_dir")]
     pub music_directory: String,
+    /// Show a slim stereo level meter in the player card
+    #[serde(default)]
+    pub show_level_meter: bool,
+    /// Skip leading/trailing silence in tracks (toggle per track with `T`)
+    #[serde(default)]
+    pub silence_trim: bool,
+    /// Level (dBFS) below which audio counts as silence
+    #[serde(default = "default_silence_threshold")]
+    pub silence_threshold_db: f32,
+    /// Album art graphics protocol: auto, kitty, iterm2, sixel or halfblocks
+    #[serde(default)]
+    pub art_protocol: crate::app::GraphicsProtocol,
+    /// Scaling filter for Block/ASCII/Braille art: nearest, triangle,
+    /// catmull-rom, gaussian or lanczos
+    #[serde(default)]
+    pub art_filter: crate::app::ArtFilter,
+    /// Map text art onto 256 colors: off, auto, ordered or floyd-steinberg
+    #[serde(default)]
+    pub art_dither: crate::app::ArtDither,
+    /// ASCII instead of emoji, symbols and box drawing; unset = only on the
+    /// Linux console or a locale that isn't UTF-8
+    #[serde(default)]
+    pub ascii_only: Option<bool>,
+    /// Artwork files looked for next to a track, in order ("cover" = cover.*)
+    #[serde(default = "default_artwork_filenames")]
+    pub artwork_filenames: Vec<String>,
+    /// Take accent colors from the current album art
+    #[serde(default)]
+    pub dynamic_theme: bool,
+    /// Dimmed, blurred album art behind the player card
+    #[serde(default)]
+    pub art_backdrop: bool,
+    /// Extra folder searched for `.lrc` files ("Artist - Title.lrc" or same name as the track)
+    #[serde(default)]
+    pub lyrics_directory: Option<String>,
+    /// Show a romanized line under non-Latin lyrics (toggle with `r`)
+    #[serde(default)]
+    pub lyrics_romanization: bool,
+    /// Color theme by name: a bundled one or `~/.config/vyom/themes/<name>.toml`
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// Panel arrangement (`[layout]`: a preset plus overrides)
+    #[serde(default)]
+    pub layout: crate::ui::layout::LayoutConfig,
+    /// Split, size and status line inside tmux (`[tmux]`)
+    #[serde(default)]
+    pub tmux: crate::app::tmux::TmuxConfig,
+    /// DSP chain order and stage settings (`[dsp]`)
+    #[serde(default)]
+    pub dsp: crate::audio::dsp::chain::DspConfig,
+    /// Output bit depth, dither and sample rate (`[output]`)
+    #[serde(default)]
+    pub output: crate::audio::output::OutputConfig,
+    /// How long a notification stays up, in ms (warnings and errors: twice that)
+    #[serde(default = "default_toast_duration")]
+    pub toast_duration_ms: u64,
+    /// Start in party mode: no deleting, tag editing or quitting on one key
+    #[serde(default)]
+    pub party_mode: bool,
+    /// Remember where tracks at least this long (minutes) were left off; 0 = off
+    #[serde(default = "default_resume_min_minutes")]
+    pub resume_min_minutes: u32,
+    /// Seek there when such a track starts again (off: offer it with `Ctrl+o`)
+    #[serde(default = "default_resume_auto")]
+    pub resume_auto: bool,
+    /// Remember volume set by hand per "album" or "track" and put it back
+    /// when that plays again ("off" by default)
+    #[serde(default)]
+    pub volume_memory: crate::app::state::VolumeMemoryMode,
+    /// Volume the duck key turns down to (0-100)
+    #[serde(default = "default_duck_volume")]
+    pub duck_volume: u8,
+    /// Keep a history of what was played, for `:wrapped`
+    #[serde(default = "default_listening_history")]
+    pub listening_history: bool,
+    /// Song paths in exported M3U8 playlists: relative (to the file) or absolute
+    #[serde(default)]
+    pub playlist_paths: crate::app::playlist_files::PathStyle,
+    /// Album headers over consecutive songs of one album in the queue
+    #[serde(default = "default_group_queue")]
+    pub group_queue: bool,
+    /// Write `~/.cache/vyom/crash-<time>.txt` when Vyom panics
+    #[serde(default = "default_crash_reports")]
+    pub crash_reports: bool,
+    /// UI language ("de", "es", ...); unset follows `LANG`
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Rule-based playlists (`[[smart_playlists]]`: name, rule, optional limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub smart_playlists: Vec<crate::app::smart_playlists::SmartPlaylist>,
+    /// AcoustID API key: lets "fetch tags" fingerprint songs (needs `fpcalc`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acoustid_key: Option<String>,
+    /// Genius API token: song descriptions and annotations in the Info view
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genius_token: Option<String>,
+    /// Last.fm API key: similar artists in the Related view
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lastfm_api_key: Option<String>,
+    /// Spotify app credentials for `vyom spotify` (developer.spotify.com)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spotify_client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spotify_client_secret: Option<String>,
+    /// Invidious server for `:stream` searches (e.g. "https://yewtu.be"); unset uses yt-dlp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invidious_instance: Option<String>,
+    /// Bandcamp `identity` cookie from a logged-in browser: the Bandcamp tab
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bandcamp_identity: Option<String>,
+    /// Format for Bandcamp downloads ("flac", "mp3-320", "alac", ...)
+    #[serde(default = "default_bandcamp_format")]
+    pub bandcamp_format: String,
+}
+
+fn default_bandcamp_format() -> String {
+    "flac".to_string()
 }
 
 fn default_music_dir() -> String {
@@ -17,11 +139,103 @@ fn default_music_dir() -> String {
     format!("{}/Music", home)
 }
 
+fn default_artwork_filenames() -> Vec<String> {
+    ["cover", "folder", "front", "album", "albumart"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn default_toast_duration() -> u64 {
+    2000
+}
+
+fn default_resume_min_minutes() -> u32 {
+    20
+}
+
+fn default_resume_auto() -> bool {
+    true
+}
+
+fn default_duck_volume() -> u8 {
+    20
+}
+
+fn default_listening_history() -> bool {
+    true
+}
+
+fn default_group_queue() -> bool {
+    true
+}
+
+fn default_crash_reports() -> bool {
+    true
+}
+
+fn default_silence_threshold() -> f32 {
+    crate::audio::dsp::silence::DEFAULT_THRESHOLD_DB
+}
+
 impl Default for UserConfig {
     fn default() -> Self {
         Self {
             keys: crate::app::keys::KeyConfig::default(),
+            mpd_host: None,
+            mpd_port: None,
             music_directory: default_music_dir(),
+            show_level_meter: false,
+            silence_trim: false,
+            silence_threshold_db: default_silence_threshold(),
+            art_protocol: crate::app::GraphicsProtocol::default(),
+            art_filter: Default::default(),
+            art_dither: Default::default(),
+            ascii_only: None,
+            artwork_filenames: default_artwork_filenames(),
+            dynamic_theme: false,
+            art_backdrop: false,
+            lyrics_directory: None,
+            lyrics_romanization: false,
+            theme: None,
+            layout: Default::default(),
+            tmux: Default::default(),
+            dsp: Default::default(),
+            output: Default::default(),
+            toast_duration_ms: default_toast_duration(),
+            party_mode: false,
+            resume_min_minutes: default_resume_min_minutes(),
+            resume_auto: default_resume_auto(),
+            volume_memory: Default::default(),
+            duck_volume: default_duck_volume(),
+            listening_history: default_listening_history(),
+            playlist_paths: Default::default(),
+            group_queue: default_group_queue(),
+            crash_reports: default_crash_reports(),
+            language: None,
+            smart_playlists: Vec::new(),
+            acoustid_key: None,
+            genius_token: None,
+            lastfm_api_key: None,
+            spotify_client_id: None,
+            spotify_client_secret: None,
+            invidious_instance: None,
+            bandcamp_identity: None,
+            bandcamp_format: default_bandcamp_format(),
+        }
+    }
+}
+
+impl UserConfig {
+    /// `lyrics_directory` with a leading `~` expanded
+    pub fn lyrics_dir(&self) -> Option<std::path::PathBuf> {
+        let dir = self.lyrics_directory.as_deref()?.trim();
+        if dir.is_empty() {
+            return None;
+        }
+        match dir.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+            None => Some(std::path::PathBuf::from(dir)),
         }
     }
 }
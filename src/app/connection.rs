@@ -0,0 +1,114 @@
+//! Is MPD there? 🔌
+//!
+//! The track poller reports every success and failure to a [`Reconnector`],
+//! which says what to show (the dot in the Now Playing title, a banner when
+//! it isn't connected) and how long to wait before trying again. Waits
+//! double from half a second up to half a minute, so an MPD that is off
+//! isn't hammered four times a second.
+
+use std::time::Duration;
+
+/// First wait after losing the connection
+const FIRST_RETRY: Duration = Duration::from_millis(500);
+/// Longest wait between tries
+const MAX_RETRY: Duration = Duration::from_secs(30);
+/// Failed tries before "reconnecting" turns into "down"
+const DOWN_AFTER: u32 = 3;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ConnectionState {
+    #[default]
+    Connected,
+    /// Lost it moments ago; trying again in `retry_in`
+    Reconnecting { attempt: u32, retry_in: Duration },
+    /// Still failing after a few tries
+    Down { error: String, retry_in: Duration },
+}
+
+impl ConnectionState {
+    /// For the Now Playing title
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ConnectionState::Connected => "●",
+            ConnectionState::Reconnecting { .. } => "◌",
+            ConnectionState::Down { .. } => "✖",
+        }
+    }
+
+    /// The banner text, `None` when connected
+    pub fn banner(&self) -> Option<String> {
+        match self {
+            ConnectionState::Connected => None,
+            ConnectionState::Reconnecting { attempt, .. } => {
+                Some(crate::t!("connection.reconnecting", attempt = attempt))
+            }
+            ConnectionState::Down { error, retry_in } => Some(crate::t!(
+                "connection.down",
+                error = error,
+                secs = retry_in.as_secs().max(1)
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Reconnector {
+    failures: u32,
+}
+
+impl Reconnector {
+    pub fn succeeded(&mut self) -> ConnectionState {
+        self.failures = 0;
+        ConnectionState::Connected
+    }
+
+    /// The new state and how long to wait before the next try
+    pub fn failed(&mut self, error: &str) -> (ConnectionState, Duration) {
+        self.failures += 1;
+        let retry_in = FIRST_RETRY
+            .saturating_mul(1 << (self.failures - 1).min(16))
+            .min(MAX_RETRY);
+        let state = if self.failures <= DOWN_AFTER {
+            ConnectionState::Reconnecting {
+                attempt: self.failures,
+                retry_in,
+            }
+        } else {
+            ConnectionState::Down {
+                error: error.to_string(),
+                retry_in,
+            }
+        };
+        (state, retry_in)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_and_states() {
+        let mut r = Reconnector::default();
+        let waits: Vec<u64> = (0..8)
+            .map(|_| r.failed("refused").1.as_millis() as u64)
+            .collect();
+        assert_eq!(
+            waits,
+            vec![500, 1000, 2000, 4000, 8000, 16000, 30000, 30000]
+        );
+        assert!(matches!(
+            r.failed("refused").0,
+            ConnectionState::Down { .. }
+        ));
+
+        assert_eq!(r.succeeded(), ConnectionState::Connected);
+        assert_eq!(
+            r.failed("refused").0,
+            ConnectionState::Reconnecting {
+                attempt: 1,
+                retry_in: Duration::from_millis(500)
+            }
+        );
+    }
+}
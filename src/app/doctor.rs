@@ -0,0 +1,420 @@
+//! `vyom doctor`: what works, what doesn't and how to fix it 🩺
+//!
+//! Each check is independent and quick (short timeouts), so one broken piece
+//! doesn't hide the others. The visualizer and EQ are built in; what they
+//! need is the audio feed from MPD, which is what the audio checks look at.
+
+use crate::app::cli::Args;
+use crate::app::config::{AppConfig, UserConfig};
+use crate::audio::types::{DEFAULT_FIFO_PATH, DEFAULT_HOST, DEFAULT_PORT};
+use ratatui_image::picker::ProtocolType;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// Works, but something is missing or degraded
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+    /// What to do about it
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Run every check; `config` is what the player would start with
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+pub async fn run(args: &Args, config: &UserConfig, client: &reqwest::Client) -> Vec<Check> {
+    let mut checks = vec![config_check(
+        std::fs::read_to_string(AppConfig::get_config_path())
+            .ok()
+            .as_deref(),
+    )];
+    if let Some(warning) = crate::ui::themes::resolve(config.theme.as_deref()).1 {
+        checks.push(Check::warn(
+            "Theme",
+            warning,
+            "Pick one with `t` in the player, or fix `theme` in config.toml",
+        ));
+    }
+
+    #[cfg(feature = "mpd")]
+    if !args.controller {
+        let (host, port) = (args.mpd_host.clone(), args.mpd_port);
+        let mpd = tokio::task::spawn_blocking(move || mpd_checks(&host, port))
+            .await
+            .unwrap_or_default();
+        checks.extend(mpd);
+    }
+    checks.push(
+        tokio::task::spawn_blocking(fifo_check)
+            .await
+            .unwrap_or_else(|e| Check::fail("FIFO", e.to_string(), "")),
+    );
+
+    checks.push(lyrics_check(client).await);
+    checks.extend(terminal_checks(
+        |key| std::env::var(key).ok(),
+        config.art_protocol.protocol_type(),
+        config.ascii_only,
+    ));
+    checks
+}
+
+/// Whether `config.toml` parses (the player silently falls back otherwise)
+pub fn config_check(content: Option<&str>) -> Check {
+    let path = AppConfig::get_config_path();
+    match content {
+        None => Check::warn(
+            "Config",
+            format!("No {} yet, using defaults", path.display()),
+            "Run `vyom` once for the setup, or `vyom --generate-config > config.toml`",
+        ),
+        Some(content) => match toml::from_str::<UserConfig>(content) {
+            Ok(_) => Check::ok("Config", path.display().to_string()),
+            Err(e) => Check::fail(
+                "Config",
+                format!("Syntax error: {}", e.message()),
+                format!(
+                    "Fix {} (the player uses defaults until then); `vyom --generate-config` prints a valid one",
+                    path.display()
+                ),
+            ),
+        },
+    }
+}
+
+/// Connection, then the outputs the audio pipeline reads from
+#[cfg(feature = "mpd")]
+fn mpd_checks(host: &str, port: u16) -> Vec<Check> {
+    use crate::app::wizard::{check_mpd, MpdCheck};
+    use crate::player::mpd::console::{send, ConsoleReply};
+
+    let mut checks = Vec::new();
+    match check_mpd(host, &port.to_string()) {
+        MpdCheck::Connected(version, songs) => checks.push(Check::ok(
+            "MPD",
+            format!("{}:{} · MPD {} · {} songs", host, port, version, songs),
+        )),
+        MpdCheck::Failed(e) => {
+            checks.push(Check::fail(
+                "MPD",
+                format!("{}:{}: {}", host, port, e),
+                "Start MPD (`mpd` or `systemctl --user start mpd`), or set mpd_host/mpd_port in config.toml",
+            ));
+            return checks;
+        }
+        MpdCheck::Untested => {}
+    }
+
+    match send(host, port, "outputs") {
+        Ok(ConsoleReply::Ok(lines)) => checks.push(outputs_check(&lines)),
+        Ok(ConsoleReply::Ack(e)) => checks.push(Check::warn("Outputs", e, "")),
+        Err(e) => checks.push(Check::warn("Outputs", e.to_string(), "")),
+    }
+
+    let addr = format!("{}:{}", DEFAULT_HOST, DEFAULT_PORT);
+    let stream = addr
+        .parse()
+        .ok()
+        .and_then(|addr| std::net::TcpStream::connect_timeout(&addr, TIMEOUT).ok());
+    checks.push(match stream {
+        Some(_) => Check::ok("Audio stream", format!("http://{}", addr)),
+        None => Check::fail(
+            "Audio stream",
+            format!("Nothing listening on {}: no EQ, visualizer or meters", addr),
+            format!(
+                "Add to mpd.conf: audio_output {{ type \"httpd\" name \"Vyom\" port \"{}\" encoder \"wave\" }}",
+                DEFAULT_PORT
+            ),
+        ),
+    });
+    checks
+}
+
+/// MPD's `outputs` reply: is the httpd (or FIFO) output Vyom reads enabled?
+pub fn outputs_check(lines: &[String]) -> Check {
+    // Each `outputid:` starts a record
+    let mut outputs: Vec<(String, String, bool)> = Vec::new();
+    for line in lines {
+        let Some((key, value)) = line.split_once(": ") else {
+            continue;
+        };
+        match key {
+            "outputid" => outputs.push(Default::default()),
+            "outputname" => {
+                if let Some(o) = outputs.last_mut() {
+                    o.0 = value.to_string();
+                }
+            }
+            "plugin" => {
+                if let Some(o) = outputs.last_mut() {
+                    o.1 = value.to_string();
+                }
+            }
+            "outputenabled" => {
+                if let Some(o) = outputs.last_mut() {
+                    o.2 = value == "1";
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let feeds: Vec<_> = outputs
+        .iter()
+        .filter(|(_, plugin, _)| plugin == "httpd" || plugin == "fifo")
+        .collect();
+    match feeds.iter().find(|(_, _, enabled)| *enabled) {
+        Some((name, plugin, _)) => Check::ok("Outputs", format!("{} ({})", name, plugin)),
+        None => match feeds.first() {
+            Some((name, _, _)) => Check::fail(
+                "Outputs",
+                format!("\"{}\" is disabled", name),
+                format!("Enable it: `mpc enable \"{}\"`", name),
+            ),
+            None => Check::fail(
+                "Outputs",
+                "No httpd or fifo output in mpd.conf",
+                "Add an httpd output on port 8000 (see the Audio stream fix)",
+            ),
+        },
+    }
+}
+
+/// The optional Hi-Res FIFO: absent is fine, a wrong one isn't
+fn fifo_check() -> Check {
+    let path = std::path::Path::new(DEFAULT_FIFO_PATH);
+    let Ok(meta) = std::fs::metadata(path) else {
+        return Check::ok("FIFO", format!("{} not used (optional)", DEFAULT_FIFO_PATH));
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        if !meta.file_type().is_fifo() {
+            return Check::fail(
+                "FIFO",
+                format!("{} is not a FIFO", DEFAULT_FIFO_PATH),
+                format!(
+                    "Remove it (`rm {}`) and let MPD recreate it",
+                    DEFAULT_FIFO_PATH
+                ),
+            );
+        }
+        let c_path = std::ffi::CString::new(DEFAULT_FIFO_PATH).unwrap_or_default();
+        // SAFETY: access only reads the NUL-terminated path
+        if unsafe { libc::access(c_path.as_ptr(), libc::R_OK) } != 0 {
+            return Check::fail(
+                "FIFO",
+                format!("{} is not readable by this user", DEFAULT_FIFO_PATH),
+                format!("`chmod a+r {}`, or run MPD as your user", DEFAULT_FIFO_PATH),
+            );
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = meta;
+    Check::ok("FIFO", DEFAULT_FIFO_PATH)
+}
+
+async fn lyrics_check(client: &reqwest::Client) -> Check {
+    let request = client
+        .get("https://lrclib.net/api/search")
+        .query(&[("q", "vyom")])
+        .timeout(TIMEOUT)
+        .send();
+    match request.await {
+        Ok(res) if res.status().is_success() => Check::ok("Lyrics", "lrclib.net reachable"),
+        Ok(res) => Check::warn(
+            "Lyrics",
+            format!("lrclib.net answered {}", res.status()),
+            "Usually temporary; local .lrc files still work",
+        ),
+        Err(e) => Check::warn(
+            "Lyrics",
+            format!("lrclib.net unreachable: {}", e),
+            "Check your network or proxy; local .lrc files still work",
+        ),
+    }
+}
+
+/// Colors and artwork, from the environment (the terminal isn't queried
+/// here, so `auto` artwork may still do better inside the player)
+pub fn terminal_checks(
+    var: impl Fn(&str) -> Option<String>,
+    forced: Option<ProtocolType>,
+    ascii_only: Option<bool>,
+) -> Vec<Check> {
+    let term = var("TERM").unwrap_or_default();
+    let in_tmux = var("TMUX").is_some_and(|v| !v.is_empty());
+    let mut checks = Vec::new();
+
+    let truecolor = var("COLORTERM").is_some_and(|c| c == "truecolor" || c == "24bit");
+    checks.push(if truecolor {
+        Check::ok("Colors", "24-bit")
+    } else if in_tmux {
+        Check::warn(
+            "Colors",
+            format!("COLORTERM not set inside tmux ({})", term),
+            "Add to tmux.conf: set -ga terminal-overrides \",*:Tc\"",
+        )
+    } else {
+        Check::warn(
+            "Colors",
+            format!("No truecolor reported ({}): themes are approximated", term),
+            "Use a truecolor terminal, or export COLORTERM=truecolor if yours is one",
+        )
+    });
+
+    let protocol = forced.or_else(|| crate::artwork::protocol_from_env(&var));
+    checks.push(match protocol {
+        Some(ProtocolType::Halfblocks) | None => Check::warn(
+            "Artwork",
+            "Half blocks (no graphics protocol detected)",
+            "Kitty, Ghostty, WezTerm, iTerm2 or foot show real images; set art_protocol to force one",
+        ),
+        Some(protocol) if in_tmux => Check::warn(
+            "Artwork",
+            format!("{:?} inside tmux", protocol),
+            "Add to tmux.conf: set -g allow-passthrough on",
+        ),
+        Some(protocol) => Check::ok(
+            "Artwork",
+            format!("{:?}{}", protocol, if forced.is_some() { " (forced)" } else { "" }),
+        ),
+    });
+
+    checks.push(match ascii_only {
+        Some(true) => Check::ok("Glyphs", "ASCII only (forced)"),
+        Some(false) => Check::ok("Glyphs", "Unicode (forced)"),
+        None if crate::ui::ascii::detect(&var) => Check::warn(
+            "Glyphs",
+            format!("ASCII only ({} or no UTF-8 locale)", term),
+            "Use a UTF-8 locale and a terminal with emoji fonts, or set ascii_only = false",
+        ),
+        None => Check::ok("Glyphs", "Unicode"),
+    });
+    checks
+}
+
+/// Print the report; `false` when something failed
+pub fn print(checks: &[Check]) -> bool {
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for check in checks {
+        let mark = match check.status {
+            Status::Ok => "✔",
+            Status::Warn => "!",
+            Status::Fail => "✖",
+        };
+        println!("{} {:<width$}  {}", mark, check.name, check.detail);
+        if let Some(fix) = check.fix.as_ref().filter(|f| !f.is_empty()) {
+            println!("  {:<width$}  → {}", "", fix);
+        }
+    }
+    !checks.iter().any(|c| c.status == Status::Fail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_config_and_outputs_checks() {
+        assert_eq!(config_check(Some("theme = \"nord\"")).status, Status::Ok);
+        assert_eq!(config_check(Some("theme = ")).status, Status::Fail);
+        assert_eq!(config_check(None).status, Status::Warn);
+
+        let outputs = |s: &str| s.lines().map(str::to_string).collect::<Vec<_>>();
+        let check = outputs_check(&outputs(
+            "outputid: 0\noutputname: Speakers\nplugin: pulse\noutputenabled: 1\n\
+             outputid: 1\noutputname: Vyom\nplugin: httpd\noutputenabled: 0",
+        ));
+        assert_eq!(check.status, Status::Fail);
+        assert_eq!(
+            check.fix.as_deref(),
+            Some("Enable it: `mpc enable \"Vyom\"`")
+        );
+        let check = outputs_check(&outputs(
+            "outputid: 0\noutputname: Hi-Res\nplugin: fifo\noutputenabled: 1",
+        ));
+        assert_eq!(check.status, Status::Ok);
+        assert_eq!(outputs_check(&[]).status, Status::Fail);
+    }
+
+    #[test]
+    fn test_terminal_checks() {
+        let env = |pairs: &[(&str, &str)]| {
+            let map: HashMap<String, String> = pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            move |key: &str| map.get(key).cloned()
+        };
+
+        let checks = terminal_checks(
+            env(&[("TERM", "xterm-kitty"), ("COLORTERM", "truecolor")]),
+            None,
+            None,
+        );
+        assert!(checks.iter().all(|c| c.status == Status::Ok));
+
+        let checks = terminal_checks(
+            env(&[
+                ("TERM", "screen-256color"),
+                ("TMUX", "/tmp/tmux-1000/default"),
+            ]),
+            Some(ProtocolType::Kitty),
+            None,
+        );
+        assert!(checks[0].fix.as_deref().unwrap().contains("Tc"));
+        assert!(checks[1]
+            .fix
+            .as_deref()
+            .unwrap()
+            .contains("allow-passthrough"));
+
+        let checks = terminal_checks(env(&[("TERM", "xterm")]), None, None);
+        assert_eq!(checks[1].status, Status::Warn);
+
+        let checks = terminal_checks(env(&[("TERM", "linux")]), None, None);
+        assert_eq!(checks[2].status, Status::Warn);
+        let checks = terminal_checks(env(&[("TERM", "linux")]), None, Some(false));
+        assert_eq!(checks[2].status, Status::Ok);
+    }
+}
@@ -0,0 +1,756 @@
+//! Downloads and radio recordings, in the background ⬇️
+//!
+//! `:download` saves the playing `:open` or Bandcamp stream into
+//! `<music dir>/Downloads` (`:download <link>` any page yt-dlp knows), and
+//! `:record` records the playing radio station into
+//! `<music dir>/Recordings/<station>`, one file per song as its ICY titles
+//! change. Bandcamp downloads (`W`) run here too. `:downloads` lists them
+//! all with what each has written so far; MPD is asked to update as files
+//! land.
+
+use crate::app::events::AppEvent;
+use crate::app::App;
+use crate::t;
+use anyhow::{anyhow, bail, Result};
+use reqwest::{Client, RequestBuilder};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+
+/// Folders under the music directory
+pub const DOWNLOADS_DIR: &str = "Downloads";
+pub const RECORDINGS_DIR: &str = "Recordings";
+/// Bytes between progress reports
+const REPORT_EVERY: u64 = 256 * 1024;
+/// Audio held for the first song while no title has come, about a minute
+/// at 128 kbit/s; past it the station's name is the file name
+const PENDING_MAX: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferKind {
+    Download,
+    Recording,
+    /// An audio CD, to FLAC
+    Rip,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferState {
+    Running,
+    Done,
+    Failed(String),
+    Stopped,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transfer {
+    pub id: u64,
+    pub kind: TransferKind,
+    pub title: String,
+    pub state: TransferState,
+    pub bytes: u64,
+    pub total: Option<u64>,
+    /// Where it was saved, relative to the music directory
+    pub path: Option<String>,
+    /// Recordings and rips: songs finished so far
+    pub songs: usize,
+}
+
+/// What a transfer's task reports
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferUpdate {
+    Title(String),
+    Progress {
+        bytes: u64,
+        total: Option<u64>,
+    },
+    /// A recording or rip finished a song and started the next
+    Song,
+    /// Saved, at this path relative to the music directory
+    Done(String),
+    Failed(String),
+}
+
+/// How a running task reports on its transfer
+#[derive(Debug, Clone)]
+pub struct Reporter {
+    id: u64,
+    tx: mpsc::Sender<AppEvent>,
+}
+
+impl Reporter {
+    pub async fn send(&self, update: TransferUpdate) {
+        if let Err(e) = self.tx.send(AppEvent::Transfer(self.id, update)).await {
+            tracing::debug!("Channel closed: {}", e);
+        }
+    }
+}
+
+/// Every transfer of the session, oldest first
+#[derive(Debug, Default)]
+pub struct Transfers {
+    pub list: Vec<Transfer>,
+    tasks: HashMap<u64, AbortHandle>,
+    next_id: u64,
+}
+
+impl Transfers {
+    /// Run `task` as a new transfer; what it returns is the saved path
+    pub fn start<F, Fut>(
+        &mut self,
+        kind: TransferKind,
+        title: &str,
+        tx: &mpsc::Sender<AppEvent>,
+        task: F,
+    ) -> u64
+    where
+        F: FnOnce(Reporter) -> Fut,
+        Fut: Future<Output = Result<String>> + Send + 'static,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.list.push(Transfer {
+            id,
+            kind,
+            title: title.to_string(),
+            state: TransferState::Running,
+            bytes: 0,
+            total: None,
+            path: None,
+            songs: 0,
+        });
+        let reporter = Reporter { id, tx: tx.clone() };
+        let work = task(reporter.clone());
+        let handle = tokio::spawn(async move {
+            let update = match work.await {
+                Ok(path) => TransferUpdate::Done(path),
+                Err(e) => TransferUpdate::Failed(e.to_string()),
+            };
+            reporter.send(update).await;
+        });
+        self.tasks.insert(id, handle.abort_handle());
+        id
+    }
+
+    /// Apply what a task reported; the transfer, unless it was stopped
+    pub fn update(&mut self, id: u64, update: TransferUpdate) -> Option<&Transfer> {
+        let transfer = self
+            .list
+            .iter_mut()
+            .find(|t| t.id == id && t.state == TransferState::Running)?;
+        match update {
+            TransferUpdate::Title(title) => transfer.title = title,
+            TransferUpdate::Progress { bytes, total } => {
+                transfer.bytes = bytes;
+                transfer.total = total;
+            }
+            TransferUpdate::Song => transfer.songs += 1,
+            TransferUpdate::Done(path) => {
+                transfer.state = TransferState::Done;
+                transfer.path = Some(path);
+                self.tasks.remove(&id);
+            }
+            TransferUpdate::Failed(error) => {
+                transfer.state = TransferState::Failed(error);
+                self.tasks.remove(&id);
+            }
+        }
+        Some(transfer)
+    }
+
+    /// Stop a running transfer; whatever it wrote stays
+    pub fn stop(&mut self, id: u64) -> bool {
+        let Some(task) = self.tasks.remove(&id) else {
+            return false;
+        };
+        task.abort();
+        if let Some(transfer) = self.list.iter_mut().find(|t| t.id == id) {
+            transfer.state = TransferState::Stopped;
+        }
+        true
+    }
+
+    /// Forget the ones that are over
+    pub fn clear_finished(&mut self) {
+        self.list.retain(|t| t.state == TransferState::Running);
+    }
+
+    pub fn running(&self) -> usize {
+        self.tasks.len()
+    }
+}
+
+/// `:download`: save the playing stream into the library
+pub fn download_playing(app: &mut App, tx: &mpsc::Sender<AppEvent>, client: &Client) {
+    let Some(file) = app.playing_file().map(str::to_string) else {
+        app.show_warning(&t!("downloads.nothing_playing"));
+        return;
+    };
+    let Some(stream) = app.streams.get(&file).cloned() else {
+        let hint = if is_url(&file) {
+            t!("downloads.use_record")
+        } else {
+            t!("downloads.already_local")
+        };
+        app.show_warning(&hint);
+        return;
+    };
+    if let Some(page) = &stream.page {
+        download_link(app, tx, page);
+        return;
+    }
+
+    // Streams without a page yt-dlp knows (Bandcamp's) are saved as they are
+    let music_dir = PathBuf::from(&app.music_directory);
+    let client = client.clone();
+    let title = stream.title.clone();
+    app.show_toast(&t!("downloads.started", title = title));
+    app.downloads
+        .start(TransferKind::Download, &title, tx, |reporter| async move {
+            let name = match &stream.uploader {
+                Some(artist) => format!("{} - {}", artist, stream.title),
+                None => stream.title.clone(),
+            };
+            let file = format!(
+                "{}/{}.{}",
+                DOWNLOADS_DIR,
+                path_part(&name),
+                url_extension(&stream.url).unwrap_or("mp3")
+            );
+            let path = music_dir.join(&file);
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            save(client.get(&stream.url), &path, &reporter).await?;
+            #[cfg(feature = "mpd")]
+            tag(path, stream.title, stream.uploader, stream.album).await;
+            Ok(file)
+        });
+}
+
+/// `:download <link>`: whatever yt-dlp finds there, as audio
+pub fn download_link(app: &mut App, tx: &mpsc::Sender<AppEvent>, link: &str) {
+    if !(link.starts_with("https://") || link.starts_with("http://")) {
+        app.show_error(&t!("stream.not_a_link", link = link));
+        return;
+    }
+    let dir = PathBuf::from(&app.music_directory).join(DOWNLOADS_DIR);
+    let link = link.to_string();
+    app.show_toast(&t!("downloads.started", title = link));
+    app.downloads.start(
+        TransferKind::Download,
+        &link.clone(),
+        tx,
+        |reporter| async move {
+            let (path, info) = ytdlp_download(&link, &dir, &reporter).await?;
+            let file = path
+                .file_name()
+                .map(|n| format!("{}/{}", DOWNLOADS_DIR, n.to_string_lossy()))
+                .unwrap_or_else(|| DOWNLOADS_DIR.to_string());
+            #[cfg(feature = "mpd")]
+            if let Some((title, artist, album)) = info {
+                tag(path, title, artist, album).await;
+            }
+            #[cfg(not(feature = "mpd"))]
+            let _ = info;
+            Ok(file)
+        },
+    );
+}
+
+/// `:record`: record the playing radio station, a file per song
+pub fn record_playing(app: &mut App, tx: &mpsc::Sender<AppEvent>, client: &Client) {
+    let Some(url) = app.playing_file().filter(|f| is_url(f)).map(str::to_string) else {
+        app.show_warning(&t!("downloads.not_radio"));
+        return;
+    };
+    if app.streams.contains_key(&url) {
+        // An `:open` stream is one song; it downloads whole
+        download_playing(app, tx, client);
+        return;
+    }
+    let music_dir = PathBuf::from(&app.music_directory);
+    let client = client.clone();
+    let host = reqwest::Url::parse(&url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.clone());
+    app.show_toast(&t!("downloads.recording", station = host));
+    app.downloads.start(
+        TransferKind::Recording,
+        &host.clone(),
+        tx,
+        |reporter| async move { record(&client, &url, &music_dir, &host, &reporter).await },
+    );
+}
+
+/// Have MPD pick up what was saved
+#[cfg(feature = "mpd")]
+pub fn update_mpd(app: &mut App, args: &crate::app::cli::Args) {
+    crate::app::send_mpd(app, args, |mpd| {
+        if let Err(e) = mpd.update() {
+            tracing::warn!("MPD update after a download failed: {}", e);
+        }
+    });
+}
+
+/// Stream the response to `path`, reporting progress
+pub async fn save(request: RequestBuilder, path: &Path, reporter: &Reporter) -> Result<u64> {
+    let mut response = request.send().await?.error_for_status()?;
+    let total = response.content_length();
+    let mut file = tokio::fs::File::create(path).await?;
+    let (mut bytes, mut reported) = (0u64, 0u64);
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+        bytes += chunk.len() as u64;
+        if bytes - reported >= REPORT_EVERY {
+            reported = bytes;
+            reporter
+                .send(TransferUpdate::Progress { bytes, total })
+                .await;
+        }
+    }
+    file.flush().await?;
+    Ok(bytes)
+}
+
+/// What yt-dlp prints while downloading (see `ytdlp_download`)
+#[derive(Debug, Clone, PartialEq)]
+enum YtdlpLine {
+    /// Title, artist, album
+    Info(String, Option<String>, Option<String>),
+    Progress(u64, Option<u64>),
+    Path(PathBuf),
+}
+
+type Info = Option<(String, Option<String>, Option<String>)>;
+
+/// Download `link` into `dir`; the file, and what yt-dlp knew about it
+async fn ytdlp_download(link: &str, dir: &Path, reporter: &Reporter) -> Result<(PathBuf, Info)> {
+    tokio::fs::create_dir_all(dir).await?;
+    let template = dir.join("%(artist,uploader|Unknown)s - %(title)s.%(ext)s");
+    let mut child = tokio::process::Command::new("yt-dlp")
+        // m4a first: lofty can tag it, webm it can't
+        .args(["--format", "bestaudio[ext=m4a]/bestaudio/best"])
+        .args(["--no-playlist", "--no-warnings", "--newline", "--progress"])
+        .args([
+            "--progress-template",
+            "download:progress %(progress.downloaded_bytes)s %(progress.total_bytes,progress.total_bytes_estimate)s",
+        ])
+        .args(["--print", "before_dl:info\t%(title)s\t%(artist,uploader|)s\t%(album|)s"])
+        .args(["--print", "after_move:path %(filepath)s"])
+        .arg("--output")
+        .arg(&template)
+        .args(["--", link])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => anyhow!(t!("stream.no_ytdlp")),
+            _ => e.into(),
+        })?;
+
+    let mut out = BufReader::new(child.stdout.take().ok_or_else(|| anyhow!("no stdout"))?).lines();
+    let mut err = BufReader::new(child.stderr.take().ok_or_else(|| anyhow!("no stderr"))?).lines();
+    let (mut out_done, mut err_done) = (false, false);
+    let (mut path, mut info, mut last_error) = (None, None, None);
+    let mut reported = 0;
+    while !(out_done && err_done) {
+        let line = tokio::select! {
+            line = out.next_line(), if !out_done => {
+                let line = line?;
+                out_done = line.is_none();
+                line
+            }
+            line = err.next_line(), if !err_done => {
+                let line = line?;
+                err_done = line.is_none();
+                line
+            }
+        };
+        let Some(line) = line else {
+            continue;
+        };
+        match parse_line(&line) {
+            Some(YtdlpLine::Info(title, artist, album)) => {
+                reporter.send(TransferUpdate::Title(title.clone())).await;
+                info = Some((title, artist, album));
+            }
+            Some(YtdlpLine::Progress(bytes, total))
+                if bytes < reported || bytes - reported >= REPORT_EVERY =>
+            {
+                reported = bytes;
+                reporter
+                    .send(TransferUpdate::Progress { bytes, total })
+                    .await;
+            }
+            Some(YtdlpLine::Progress(..)) => {}
+            Some(YtdlpLine::Path(file)) => path = Some(file),
+            None if !line.trim().is_empty() => last_error = Some(line),
+            None => {}
+        }
+    }
+    let status = child.wait().await?;
+    if !status.success() {
+        bail!(
+            "{}",
+            last_error
+                .map(|l| l.trim_start_matches("ERROR: ").to_string())
+                .unwrap_or_else(|| format!("yt-dlp exited with {}", status))
+        );
+    }
+    let path = path.ok_or_else(|| anyhow!("yt-dlp didn't say where it saved the file"))?;
+    Ok((path, info))
+}
+
+fn parse_line(line: &str) -> Option<YtdlpLine> {
+    let some = |s: &str| (!s.is_empty() && s != "NA").then(|| s.to_string());
+    if let Some(rest) = line.strip_prefix("info\t") {
+        let mut parts = rest.split('\t');
+        let title = parts.next()?.to_string();
+        return Some(YtdlpLine::Info(
+            title,
+            parts.next().and_then(some),
+            parts.next().and_then(some),
+        ));
+    }
+    if let Some(rest) = line.strip_prefix("progress ") {
+        let (bytes, total) = rest.trim().split_once(' ')?;
+        // Estimates come as floats
+        let number = |s: &str| s.parse::<f64>().ok().map(|n| n as u64);
+        return Some(YtdlpLine::Progress(number(bytes)?, number(total)));
+    }
+    line.strip_prefix("path ")
+        .map(|p| YtdlpLine::Path(PathBuf::from(p.trim())))
+}
+
+/// Fill in the tags a downloaded file is missing
+#[cfg(feature = "mpd")]
+async fn tag(path: PathBuf, title: String, artist: Option<String>, album: Option<String>) {
+    use crate::app::{tags, TagField};
+
+    let tagged = tokio::task::spawn_blocking(move || {
+        let wanted = [
+            (TagField::Title, Some(title)),
+            (TagField::Artist, artist),
+            (TagField::Album, album),
+        ];
+        tags::fill(&path, &wanted).map_err(|e| (path, e))
+    })
+    .await;
+    if let Ok(Err((path, e))) = tagged {
+        tracing::warn!("Couldn't tag {}: {}", path.display(), e);
+    }
+}
+
+/// Record `url` until it ends (or the transfer is stopped)
+async fn record(
+    client: &Client,
+    url: &str,
+    music_dir: &Path,
+    host: &str,
+    reporter: &Reporter,
+) -> Result<String> {
+    let mut response = client
+        .get(url)
+        .header("Icy-MetaData", "1")
+        .send()
+        .await?
+        .error_for_status()?;
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let station = header("icy-name").unwrap_or_else(|| host.to_string());
+    let metaint = header("icy-metaint").and_then(|v| v.parse().ok());
+    let ext = header("content-type")
+        .map(|c| content_extension(&c))
+        .unwrap_or("mp3");
+    reporter.send(TransferUpdate::Title(station.clone())).await;
+
+    let folder = format!("{}/{}", RECORDINGS_DIR, path_part(&station));
+    let dir = music_dir.join(&folder);
+    tokio::fs::create_dir_all(&dir).await?;
+
+    // Audio before the first title goes into the first song. Numbering
+    // goes on from an earlier recording of the station.
+    let mut icy = Icy::new(metaint);
+    let mut pending = Vec::new();
+    let mut file: Option<tokio::fs::File> = None;
+    let last = {
+        let dir = dir.clone();
+        tokio::task::spawn_blocking(move || last_number(&dir)).await?
+    };
+    let (mut title, mut songs) = (None::<String>, last);
+    let (mut bytes, mut reported) = (0u64, 0u64);
+    let open = |songs: usize, name: &str| {
+        let path = dir.join(format!("{:03} {}.{}", songs, path_part(name), ext));
+        async move {
+            tokio::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+                .await
+        }
+    };
+    if metaint.is_none() {
+        songs += 1;
+        file = Some(open(songs, &station).await?);
+    }
+    while let Some(chunk) = response.chunk().await? {
+        for piece in icy.feed(&chunk) {
+            match piece {
+                Piece::Audio(data) => {
+                    bytes += data.len() as u64;
+                    match file.as_mut() {
+                        Some(file) => file.write_all(&data).await?,
+                        None => pending.extend_from_slice(&data),
+                    }
+                    if pending.len() > PENDING_MAX {
+                        songs += 1;
+                        let mut new = open(songs, &station).await?;
+                        new.write_all(&std::mem::take(&mut pending)).await?;
+                        file = Some(new);
+                    }
+                }
+                Piece::Title(next) if title.as_ref() != Some(&next) => {
+                    if let Some(mut done) = file.take() {
+                        done.flush().await?;
+                        reporter.send(TransferUpdate::Song).await;
+                    }
+                    songs += 1;
+                    let mut new = open(songs, &next).await?;
+                    new.write_all(&std::mem::take(&mut pending)).await?;
+                    file = Some(new);
+                    title = Some(next);
+                }
+                Piece::Title(_) => {}
+            }
+        }
+        if bytes - reported >= REPORT_EVERY {
+            reported = bytes;
+            reporter
+                .send(TransferUpdate::Progress { bytes, total: None })
+                .await;
+        }
+    }
+    if let Some(mut last) = file {
+        last.flush().await?;
+    }
+    Ok(folder)
+}
+
+/// Highest "NNN " number of the recordings already in `dir`, 0 for none
+fn last_number(dir: &Path) -> usize {
+    std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let (number, _) = name.to_str()?.split_once(' ')?;
+            number.parse().ok()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// A piece of an ICY stream
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    Audio(Vec<u8>),
+    /// A new `StreamTitle` (blank ones, often ads, are left out)
+    Title(String),
+}
+
+/// Splits Shoutcast / Icecast metadata out of the audio: every `metaint`
+/// bytes of audio there's a length byte (× 16) and that much metadata
+#[derive(Debug)]
+struct Icy {
+    metaint: Option<usize>,
+    /// Audio bytes until the next length byte
+    audio_left: usize,
+    /// Metadata being read, and its length
+    meta: Vec<u8>,
+    meta_len: usize,
+}
+
+impl Icy {
+    fn new(metaint: Option<usize>) -> Self {
+        Self {
+            metaint,
+            audio_left: metaint.unwrap_or(0),
+            meta: Vec::new(),
+            meta_len: 0,
+        }
+    }
+
+    fn feed(&mut self, mut data: &[u8]) -> Vec<Piece> {
+        let Some(metaint) = self.metaint.filter(|&m| m > 0) else {
+            return vec![Piece::Audio(data.to_vec())];
+        };
+        let mut pieces = Vec::new();
+        while !data.is_empty() {
+            if self.meta_len > 0 {
+                let take = (self.meta_len - self.meta.len()).min(data.len());
+                self.meta.extend_from_slice(&data[..take]);
+                data = &data[take..];
+                if self.meta.len() == self.meta_len {
+                    if let Some(title) = stream_title(&self.meta) {
+                        pieces.push(Piece::Title(title));
+                    }
+                    self.meta.clear();
+                    self.meta_len = 0;
+                    self.audio_left = metaint;
+                }
+            } else if self.audio_left == 0 {
+                self.meta_len = data[0] as usize * 16;
+                data = &data[1..];
+                if self.meta_len == 0 {
+                    self.audio_left = metaint;
+                }
+            } else {
+                let take = self.audio_left.min(data.len());
+                match pieces.last_mut() {
+                    Some(Piece::Audio(audio)) => audio.extend_from_slice(&data[..take]),
+                    _ => pieces.push(Piece::Audio(data[..take].to_vec())),
+                }
+                data = &data[take..];
+                self.audio_left -= take;
+            }
+        }
+        pieces
+    }
+}
+
+/// `StreamTitle='Artist - Title';` out of an ICY metadata block
+fn stream_title(meta: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(meta);
+    let start = text.find("StreamTitle='")? + "StreamTitle='".len();
+    let rest = &text[start..];
+    let end = rest.find("';").unwrap_or(rest.trim_end_matches('\0').len());
+    let title = rest[..end].trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+fn content_extension(content_type: &str) -> &'static str {
+    match content_type.split(';').next().unwrap_or("").trim() {
+        "audio/aac" | "audio/aacp" | "audio/x-aac" => "aac",
+        "audio/ogg" | "application/ogg" => "ogg",
+        "audio/flac" => "flac",
+        _ => "mp3",
+    }
+}
+
+fn url_extension(url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next()?;
+    let ext = path.rsplit_once('.')?.1.to_lowercase();
+    ["mp3", "m4a", "ogg", "opus", "flac", "aac"]
+        .into_iter()
+        .find(|e| *e == ext)
+}
+
+fn is_url(file: &str) -> bool {
+    file.starts_with("http://") || file.starts_with("https://")
+}
+
+/// A name made safe for one path component
+pub fn path_part(name: &str) -> String {
+    let name = name.replace(['/', '\\'], "_");
+    let name = name.trim().trim_start_matches('.');
+    if name.is_empty() {
+        "Unknown".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icy_titles_split_the_audio() {
+        // metaint 4: "abcd", then 1 × 16 bytes of metadata, "efgh", no
+        // metadata, "ij"
+        let mut stream = b"abcd\x01StreamTitle='A';efgh\x00ij".to_vec();
+        stream.extend_from_slice(b"kl\x01StreamTitle='';\0mn");
+        let mut icy = Icy::new(Some(4));
+        let pieces: Vec<Piece> = stream.chunks(3).flat_map(|c| icy.feed(c)).collect();
+        let mut audio = Vec::new();
+        let mut titles = Vec::new();
+        for piece in pieces {
+            match piece {
+                Piece::Audio(data) => audio.extend(data),
+                Piece::Title(title) => titles.push((title, audio.len())),
+            }
+        }
+        assert_eq!(audio, b"abcdefghijklmn");
+        assert_eq!(titles, vec![("A".to_string(), 4)]);
+        assert_eq!(
+            stream_title(b"StreamTitle='Guns N' Roses - Patience';StreamUrl='';\0\0"),
+            Some("Guns N' Roses - Patience".into())
+        );
+
+        let mut plain = Icy::new(None);
+        assert_eq!(plain.feed(b"xyz"), vec![Piece::Audio(b"xyz".to_vec())]);
+    }
+
+    #[test]
+    fn test_ytdlp_progress_lines() {
+        assert_eq!(
+            parse_line("info\tAirbag\tRadiohead\t"),
+            Some(YtdlpLine::Info(
+                "Airbag".into(),
+                Some("Radiohead".into()),
+                None
+            ))
+        );
+        assert_eq!(
+            parse_line("progress 1024 4096"),
+            Some(YtdlpLine::Progress(1024, Some(4096)))
+        );
+        assert_eq!(
+            parse_line("progress 1024 5000.5"),
+            Some(YtdlpLine::Progress(1024, Some(5000)))
+        );
+        assert_eq!(
+            parse_line("progress 1024 NA"),
+            Some(YtdlpLine::Progress(1024, None))
+        );
+        assert_eq!(
+            parse_line("path /music/Downloads/Radiohead - Airbag.m4a"),
+            Some(YtdlpLine::Path(
+                "/music/Downloads/Radiohead - Airbag.m4a".into()
+            ))
+        );
+        assert_eq!(parse_line("ERROR: Video unavailable"), None);
+        assert_eq!(
+            url_extension("https://t4.bcbits.com/stream/1/mp3-128/2?p=0"),
+            None
+        );
+        assert_eq!(url_extension("http://x/a.MP3?t=1"), Some("mp3"));
+        assert_eq!(path_part(" AC/DC "), "AC_DC");
+        assert_eq!(path_part(".."), "Unknown");
+    }
+
+    #[test]
+    fn test_recordings_continue_numbering() {
+        let dir = std::env::temp_dir().join(format!("vyom-recordings-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(last_number(&dir), 0);
+        for name in ["001 Intro.mp3", "012 Song.mp3", "cover.jpg", "Live 2.mp3"] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+        assert_eq!(last_number(&dir), 12);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,17 +1,111 @@
+use crate::app::lyrics::LyricsCandidate;
+use crate::app::remote::RemoteCommand;
 use crate::app::{ArtworkState, LyricsState};
+use crate::error::VyomError;
 use crate::player::TrackInfo;
-use crate::ui::theme::Theme;
 use crossterm::event::Event;
+use tokio::sync::mpsc;
+
+/// What the retry key (`Ctrl+t`) does after an error
+#[derive(Debug, Clone, PartialEq)]
+pub enum Retry {
+    /// Send the player command again
+    Command(RemoteCommand),
+    /// Look up the current track's lyrics again
+    Lyrics,
+    /// Restart the audio pipeline
+    Pipeline,
+}
 
 pub enum AppEvent {
     Input(Event),
     TrackUpdate(Option<TrackInfo>),
     LyricsUpdate(String, LyricsState),
+    /// Manual lookup results for a track id
+    LyricsCandidates(String, Result<Vec<LyricsCandidate>, String>),
+    /// Cover for an artwork request key (see `requests::art_key`)
     ArtworkUpdate(String, ArtworkState),
-    ThemeUpdate(Theme),
+    /// Text artwork resized in the background
+    ArtCellsReady(crate::app::ArtCells),
+    LibraryArtUpdate(String, ArtworkState),
+    /// Cover for an album grid tile, by first-track path
+    AlbumArtUpdate(String, ArtworkState),
+    /// A theme file changed on disk: reload the active theme
+    ThemeFilesChanged,
+    /// `theme` changed in config.toml
+    ThemeSelect(Option<String>),
     KeyConfigUpdate(Box<crate::app::keys::KeyConfig>),
-    QueueUpdate(Vec<(String, String, u64, bool, String)>),
+    QueueUpdate(Vec<crate::player::QueueItem>),
+    /// Only what changed in the queue since the last update
+    QueueChanged(crate::player::QueueDelta),
     StatusUpdate(bool, crate::player::RepeatMode),
-    ToastUpdate(String),
+    /// Info view lookup for an `about::about_key`
+    AboutUpdate(String, crate::app::about::AboutState),
+    /// Related view lookup for a `related::related_key`
+    RelatedUpdate(String, crate::app::related::RelatedState),
+    /// Stream search results for a query
+    StreamResults(
+        String,
+        Result<Vec<crate::app::stream_search::StreamHit>, String>,
+    ),
+    /// A link from `:open`, resolved by yt-dlp
+    StreamResolved(Result<crate::app::ytdlp::Stream, String>),
+    /// The Bandcamp collection, listed
+    BandcampCollection(crate::app::bandcamp::CollectionState),
+    /// A Bandcamp purchase's streams, to queue (and play with `true`)
+    BandcampStreams(String, bool, Result<Vec<crate::app::ytdlp::Stream>, String>),
+    /// How a download or recording is going, by transfer id
+    Transfer(u64, crate::app::downloads::TransferUpdate),
+    /// The CD in the drive, looked up on MusicBrainz
+    CdRead(Result<crate::app::cd::Disc, String>),
+    /// Chapters read from a file (relative to the music directory)
+    Chapters(String, Vec<crate::app::chapters::Chapter>),
+    /// The track poller reached MPD, or didn't
+    ConnectionUpdate(crate::app::connection::ConnectionState),
+    ToastUpdate(crate::app::ToastLevel, String),
+    /// Tag editor: "fetch tags" results for these files
+    TagCandidates(
+        Vec<String>,
+        Result<Vec<crate::app::tag_lookup::TagCandidate>, String>,
+    ),
+    /// MPD's database changed: the fresh library listing
+    LibraryCacheUpdate(std::sync::Arc<crate::app::library_cache::LibraryCache>),
+    /// Tag editor: this many files written so far
+    TagProgress(usize),
+    /// Tag editor: done writing
+    #[cfg(feature = "mpd")]
+    TagsWritten(crate::app::tags::WriteReport),
+    /// The playing file's remembered volume, and whether it has
+    /// ReplayGain tags (which leave its volume alone)
+    #[cfg(feature = "mpd")]
+    RememberedVolume(String, Option<u8>, bool),
+    /// The MPD console's command and what the server said to it
+    #[cfg(feature = "mpd")]
+    ConsoleReply(
+        String,
+        Result<crate::player::mpd::console::ConsoleReply, String>,
+    ),
+    /// Something failed in the background: error toast, maybe with a retry
+    Error(VyomError, Option<Retry>),
+    /// A command from `vyom play` & co., answered on the channel
+    Remote(
+        crate::app::remote::RemoteCommand,
+        tokio::sync::oneshot::Sender<crate::app::remote::Reply>,
+    ),
+    /// A Shared Audio Mode instance wants our state
+    Subscribe(
+        tokio::sync::oneshot::Sender<Result<tokio::sync::broadcast::Receiver<String>, String>>,
+    ),
+    /// State from the audio master
+    Sync(crate::app::sync::SyncUpdate),
     Tick,
 }
+
+/// Report a failure from a background task. Never blocks, so it is safe
+/// from `spawn_blocking` and async code alike.
+pub fn report(tx: &mpsc::Sender<AppEvent>, error: VyomError, retry: Option<Retry>) {
+    tracing::warn!("{}", error);
+    if let Err(e) = tx.try_send(AppEvent::Error(error, retry)) {
+        tracing::debug!("Channel closed: {}", e);
+    }
+}
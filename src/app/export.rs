@@ -0,0 +1,417 @@
+//! `vyom export` / `vyom import`: playback data in and out 📦
+//!
+//! The listening history, ratings and favorites (the `rating` and `like`
+//! song stickers MPD keeps for clients) and the saved playlists, as one JSON
+//! document or one CSV table with a `kind` column. Importing merges the
+//! history (plays already there are skipped), sets the stickers and
+//! replaces playlists of the same name, so the same file can be imported
+//! twice without doubling anything.
+
+use crate::app::history::{self, Play};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Bumped when the layout changes in a way old readers can't handle
+const VERSION: u32 = 1;
+const CSV_HEADER: [&str; 10] = [
+    "kind",
+    "name",
+    "file",
+    "value",
+    "at",
+    "artist",
+    "title",
+    "album",
+    "genre",
+    "listened_ms",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Format {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Export {
+    pub version: u32,
+    pub history: Vec<Play>,
+    pub ratings: Vec<Sticker>,
+    pub favorites: Vec<Sticker>,
+    pub playlists: Vec<SavedPlaylist>,
+}
+
+/// A song sticker's value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sticker {
+    pub file: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPlaylist {
+    pub name: String,
+    pub files: Vec<String>,
+}
+
+/// What an import changed
+#[derive(Debug, Default, PartialEq)]
+pub struct Imported {
+    pub plays: usize,
+    pub plays_known: usize,
+    pub ratings: usize,
+    pub favorites: usize,
+    pub playlists: usize,
+    /// Stickers and songs MPD refused (files not in its database)
+    pub failed: usize,
+}
+
+impl Export {
+    /// The listening history; see [`Export::add_from_mpd`] for the rest
+    pub fn collect() -> Self {
+        Export {
+            version: VERSION,
+            history: history::load(&history::path()),
+            ..Default::default()
+        }
+    }
+
+    /// Ratings, favorites and playlists from MPD
+    #[cfg(feature = "mpd")]
+    pub fn add_from_mpd(&mut self, mpd: &mut mpd::Client) -> Result<(), mpd::error::Error> {
+        let mut stickers = |name: &str| -> Result<Vec<Sticker>, mpd::error::Error> {
+            let mut found: Vec<Sticker> = mpd
+                .find_sticker("song", "", name)?
+                .into_iter()
+                .map(|(file, value)| Sticker { file, value })
+                .collect();
+            found.sort_by(|a, b| a.file.cmp(&b.file));
+            Ok(found)
+        };
+        self.ratings = stickers("rating")?;
+        self.favorites = stickers("like")?;
+        for playlist in mpd.playlists()? {
+            let files = mpd
+                .playlist(&playlist.name)?
+                .into_iter()
+                .map(|s| s.file)
+                .collect();
+            self.playlists.push(SavedPlaylist {
+                name: playlist.name,
+                files,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// One row per play, sticker and playlist entry
+    pub fn to_csv(&self) -> String {
+        let mut out = csv_row(&CSV_HEADER);
+        for play in &self.history {
+            out.push_str(&csv_row(&[
+                "history",
+                "",
+                "",
+                "",
+                &play.at.to_string(),
+                &play.artist,
+                &play.title,
+                &play.album,
+                play.genre.as_deref().unwrap_or(""),
+                &play.listened_ms.to_string(),
+            ]));
+        }
+        for (kind, stickers) in [("rating", &self.ratings), ("favorite", &self.favorites)] {
+            for sticker in stickers {
+                out.push_str(&csv_row(&[
+                    kind,
+                    "",
+                    &sticker.file,
+                    &sticker.value,
+                    "",
+                    "",
+                    "",
+                    "",
+                    "",
+                    "",
+                ]));
+            }
+        }
+        for playlist in &self.playlists {
+            for file in &playlist.files {
+                out.push_str(&csv_row(&[
+                    "playlist",
+                    &playlist.name,
+                    file,
+                    "",
+                    "",
+                    "",
+                    "",
+                    "",
+                    "",
+                    "",
+                ]));
+            }
+        }
+        out
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Json => self.to_json(),
+            Format::Csv => self.to_csv(),
+        }
+    }
+
+    /// Read either format back
+    pub fn parse(text: &str) -> Result<Self, String> {
+        if text.trim_start().starts_with('{') {
+            let export: Export = serde_json::from_str(text).map_err(|e| e.to_string())?;
+            if export.version > VERSION {
+                return Err(format!(
+                    "Made by a newer Vyom (format {}); update to import it",
+                    export.version
+                ));
+            }
+            return Ok(export);
+        }
+        Self::from_csv(text)
+    }
+
+    fn from_csv(text: &str) -> Result<Self, String> {
+        let mut rows = csv_rows(text).into_iter();
+        let header = rows.next().ok_or("The file is empty")?;
+        let column = |name: &str| header.iter().position(|h| h == name);
+        let columns: Vec<Option<usize>> = CSV_HEADER.iter().map(|name| column(name)).collect();
+        if columns[0].is_none() {
+            return Err("Not a Vyom export: no \"kind\" column".to_string());
+        }
+
+        let mut export = Export {
+            version: VERSION,
+            ..Default::default()
+        };
+        for (n, row) in rows.enumerate() {
+            let get = |i: usize| -> &str {
+                columns[i]
+                    .and_then(|c| row.get(c))
+                    .map(String::as_str)
+                    .unwrap_or("")
+            };
+            let bad = |what: &str| format!("Row {}: bad {}", n + 2, what);
+            match get(0) {
+                "history" => export.history.push(Play {
+                    at: get(4).parse().map_err(|_| bad("at"))?,
+                    artist: get(5).to_string(),
+                    title: get(6).to_string(),
+                    album: get(7).to_string(),
+                    genre: Some(get(8).to_string()).filter(|g| !g.is_empty()),
+                    listened_ms: get(9).parse().map_err(|_| bad("listened_ms"))?,
+                }),
+                kind @ ("rating" | "favorite") => {
+                    let sticker = Sticker {
+                        file: get(2).to_string(),
+                        value: get(3).to_string(),
+                    };
+                    if kind == "rating" {
+                        export.ratings.push(sticker);
+                    } else {
+                        export.favorites.push(sticker);
+                    }
+                }
+                "playlist" => {
+                    let (name, file) = (get(1), get(2).to_string());
+                    match export.playlists.iter_mut().find(|p| p.name == name) {
+                        Some(playlist) => playlist.files.push(file),
+                        None => export.playlists.push(SavedPlaylist {
+                            name: name.to_string(),
+                            files: vec![file],
+                        }),
+                    }
+                }
+                "" => {}
+                other => return Err(format!("Row {}: unknown kind \"{}\"", n + 2, other)),
+            }
+        }
+        Ok(export)
+    }
+
+    /// Merge the history into the history file
+    pub fn import_history(&self) -> std::io::Result<Imported> {
+        let path = history::path();
+        let (merged, added) = merge_history(history::load(&path), self.history.clone());
+        if added > 0 {
+            history::write(&path, &merged)?;
+        }
+        Ok(Imported {
+            plays: added,
+            plays_known: self.history.len() - added,
+            ..Default::default()
+        })
+    }
+
+    /// Set the stickers and write the playlists
+    #[cfg(feature = "mpd")]
+    pub fn import_to_mpd(&self, mpd: &mut mpd::Client, imported: &mut Imported) {
+        for (name, stickers, count) in [
+            ("rating", &self.ratings, &mut imported.ratings),
+            ("like", &self.favorites, &mut imported.favorites),
+        ] {
+            for sticker in stickers {
+                match mpd.set_sticker("song", &sticker.file, name, &sticker.value) {
+                    Ok(()) => *count += 1,
+                    Err(e) => {
+                        tracing::debug!("Sticker for {}: {}", sticker.file, e);
+                        imported.failed += 1;
+                    }
+                }
+            }
+        }
+        for playlist in &self.playlists {
+            // Replaced, not appended to: importing twice changes nothing
+            let _ = mpd.pl_remove(&playlist.name);
+            for file in &playlist.files {
+                let song = mpd::Song {
+                    file: file.clone(),
+                    ..Default::default()
+                };
+                if mpd.pl_push(&playlist.name, &song).is_err() {
+                    imported.failed += 1;
+                }
+            }
+            imported.playlists += 1;
+        }
+    }
+}
+
+/// `existing` plus the plays of `incoming` it doesn't have, oldest first,
+/// and how many were added
+pub fn merge_history(existing: Vec<Play>, incoming: Vec<Play>) -> (Vec<Play>, usize) {
+    let key = |p: &Play| (p.at, p.artist.clone(), p.title.clone());
+    let mut known: HashSet<_> = existing.iter().map(key).collect();
+    let mut merged = existing;
+    let before = merged.len();
+    for play in incoming {
+        if known.insert(key(&play)) {
+            merged.push(play);
+        }
+    }
+    let added = merged.len() - before;
+    merged.sort_by_key(|p| p.at);
+    (merged, added)
+}
+
+/// One CSV line, quoting fields that need it
+fn csv_row(fields: &[&str]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push('\n');
+    line
+}
+
+/// Split CSV text into rows of fields; quoted fields may hold commas,
+/// quotes ("") and line breaks
+fn csv_rows(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let (mut row, mut field) = (Vec::new(), String::new());
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.is_empty() => quoted = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(at: u64, title: &str) -> Play {
+        Play {
+            at,
+            artist: "Radiohead".into(),
+            title: title.into(),
+            album: "OK Computer".into(),
+            genre: Some("Rock".into()),
+            listened_ms: 180_000,
+        }
+    }
+
+    #[test]
+    fn test_both_formats_round_trip() {
+        let export = Export {
+            version: VERSION,
+            history: vec![
+                play(100, "Airbag"),
+                play(400, "Paranoid Android, Pt. \"1\""),
+            ],
+            ratings: vec![Sticker {
+                file: "Radiohead/OK Computer/01 Airbag.flac".into(),
+                value: "8".into(),
+            }],
+            favorites: vec![Sticker {
+                file: "Björk/Homogenic/06 Jóga.flac".into(),
+                value: "2".into(),
+            }],
+            playlists: vec![SavedPlaylist {
+                name: "Late, night".into(),
+                files: vec!["a.flac".into(), "b\nc.flac".into()],
+            }],
+        };
+        assert_eq!(Export::parse(&export.to_json()).unwrap(), export);
+        let csv = export.to_csv();
+        assert!(csv.starts_with("kind,name,file,value,at,"));
+        assert!(csv.contains("history,,,,400,Radiohead,\"Paranoid Android, Pt. \"\"1\"\"\""));
+        assert_eq!(Export::parse(&csv).unwrap(), export);
+
+        assert!(Export::parse("title,artist\nAirbag,Radiohead\n").is_err());
+        assert!(Export::parse(r#"{"version":99}"#).is_err());
+    }
+
+    #[test]
+    fn test_history_merge_skips_known_plays() {
+        let (merged, added) = merge_history(
+            vec![play(400, "Lucky"), play(100, "Airbag")],
+            vec![
+                play(100, "Airbag"),
+                play(250, "Subterranean Homesick Alien"),
+            ],
+        );
+        assert_eq!(added, 1);
+        let order: Vec<u64> = merged.iter().map(|p| p.at).collect();
+        assert_eq!(order, vec![100, 250, 400]);
+    }
+}
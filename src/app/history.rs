@@ -0,0 +1,220 @@
+//! Listening history, for Vyom Wrapped 📜
+//!
+//! Every song played for at least 30 seconds (or half of it, when it's
+//! shorter) is added as one JSON line to `~/.local/share/vyom/history.jsonl`:
+//! when it started, what it was and how long it actually played. Time
+//! skipped by seeking doesn't count. `listening_history = false` in
+//! `config.toml` stops recording.
+
+use crate::player::{PlayerState, TrackInfo};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A play counts after this much listening (or half the song)
+const MIN_PLAY_MS: u64 = 30_000;
+/// Position jumps bigger than this between updates were seeks
+const MAX_STEP_MS: u64 = 5_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Play {
+    /// Unix seconds when it started
+    pub at: u64,
+    pub artist: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub album: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genre: Option<String>,
+    /// Time actually played
+    pub listened_ms: u64,
+}
+
+/// The song playing now and how long it has been heard
+#[derive(Debug, Default)]
+pub struct Listening {
+    enabled: bool,
+    current: Option<(String, u64, Play)>,
+    last_position: u64,
+}
+
+impl Listening {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    /// Every track update, with the time now in unix seconds. Returns the
+    /// play that just ended if it counts.
+    pub fn update(&mut self, track: &TrackInfo, now: u64) -> Option<Play> {
+        if !self.enabled {
+            return None;
+        }
+        let id = format!("{}\u{1f}{}", track.artist, track.name);
+        let same = self.current.as_ref().is_some_and(|(c, _, _)| *c == id);
+        // Back at the start after the end: the same song again (repeat one)
+        let replayed = same
+            && track.position_ms + MAX_STEP_MS < self.last_position
+            && self.last_position + 2 * MAX_STEP_MS >= track.duration_ms;
+        let finished = if !same || replayed {
+            self.finish()
+        } else {
+            None
+        };
+
+        match &mut self.current {
+            Some((_, _, play)) => {
+                let step = track.position_ms.saturating_sub(self.last_position);
+                if track.state == PlayerState::Playing && step <= MAX_STEP_MS {
+                    play.listened_ms += step;
+                }
+            }
+            None => {
+                let play = Play {
+                    at: now,
+                    artist: track.artist.clone(),
+                    title: track.name.clone(),
+                    album: track.album.clone(),
+                    genre: track.genre.clone(),
+                    listened_ms: 0,
+                };
+                self.current = Some((id, track.duration_ms, play));
+            }
+        }
+        self.last_position = track.position_ms;
+        finished
+    }
+
+    /// Nothing playing any more (or quitting): the last play, if it counts
+    pub fn finish(&mut self) -> Option<Play> {
+        let (_, duration_ms, play) = self.current.take()?;
+        (play.listened_ms > 0 && play.listened_ms >= MIN_PLAY_MS.min(duration_ms / 2))
+            .then_some(play)
+    }
+}
+
+/// `~/.local/share/vyom/history.jsonl`
+pub fn path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from(".local/share"))
+        .join("vyom")
+        .join("history.jsonl")
+}
+
+/// Add `play` to the history file
+pub fn record(play: &Play) {
+    if let Err(e) = append(&path(), play) {
+        tracing::warn!("Failed to write listening history: {}", e);
+    }
+}
+
+fn append(path: &Path, play: &Play) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let line = serde_json::to_string(play)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", line)
+}
+
+/// Replace the file with `plays`
+pub fn write(path: &Path, plays: &[Play]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut content = String::new();
+    for play in plays {
+        content.push_str(&serde_json::to_string(play)?);
+        content.push('\n');
+    }
+    std::fs::write(path, content)
+}
+
+/// Every play in the file; lines that don't parse are skipped
+pub fn load(path: &Path) -> Vec<Play> {
+    std::fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(name: &str, position_ms: u64, state: PlayerState) -> TrackInfo {
+        TrackInfo {
+            name: name.into(),
+            artist: "Radiohead".into(),
+            album: "OK Computer".into(),
+            artwork_url: None,
+            duration_ms: 240_000,
+            position_ms,
+            state,
+            source: "MPD".into(),
+            codec: None,
+            bitrate: None,
+            sample_rate: None,
+            bit_depth: None,
+            file_path: None,
+            volume: None,
+            track_number: None,
+            track_total: None,
+            genre: Some("Rock".into()),
+        }
+    }
+
+    #[test]
+    fn test_plays_count_listening_only() {
+        let mut listening = Listening::new(true);
+        let playing = PlayerState::Playing;
+        assert_eq!(
+            listening.update(&track("Airbag", 0, playing.clone()), 100),
+            None
+        );
+        for s in 1..=20 {
+            listening.update(&track("Airbag", s * 1000, playing.clone()), 100);
+        }
+        // A seek and a pause add nothing
+        listening.update(&track("Airbag", 200_000, playing.clone()), 100);
+        listening.update(&track("Airbag", 200_000, PlayerState::Paused), 100);
+        // 20 s isn't enough
+        assert_eq!(
+            listening.update(&track("Lucky", 0, playing.clone()), 400),
+            None
+        );
+
+        for s in 1..=40 {
+            listening.update(&track("Lucky", s * 1000, playing.clone()), 400);
+        }
+        let play = listening.finish().expect("40 s counts");
+        assert_eq!((play.title.as_str(), play.at), ("Lucky", 400));
+        assert_eq!(play.listened_ms, 40_000);
+        assert_eq!(play.genre.as_deref(), Some("Rock"));
+
+        let path = std::env::temp_dir().join(format!("vyom-history-{}.jsonl", std::process::id()));
+        append(&path, &play).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut f| writeln!(f, "not json"))
+            .unwrap();
+        append(&path, &play).unwrap();
+        assert_eq!(load(&path), vec![play.clone(), play]);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            Listening::new(false).update(&track("Airbag", 0, playing), 0),
+            None
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use crate::app::bandcamp;
+use crate::app::events::AppEvent;
+use crate::app::{App, LibraryMode};
+use crossterm::event::KeyEvent;
+use reqwest::Client;
+use tokio::sync::mpsc;
+
+/// Bandcamp tab: `Enter` plays the selected purchase, `a` queues it and `W`
+/// downloads it. Navigation and tabs are left to the library handler.
+pub fn handle_bandcamp(
+    key: KeyEvent,
+    app: &mut App,
+    tx: &mpsc::Sender<AppEvent>,
+    client: &Client,
+) -> bool {
+    if app.library_mode != LibraryMode::Bandcamp {
+        return false;
+    }
+    let keys = &app.keys;
+    let (play, queue, download) = (
+        keys.matches(key, &keys.enter_dir),
+        keys.matches(key, &keys.add_to_queue),
+        keys.matches(key, &keys.download),
+    );
+    if !(play || queue || download) {
+        return false;
+    }
+    let Some(purchase) = app.bandcamp.purchases().get(app.library_selected).cloned() else {
+        return true;
+    };
+    if download {
+        bandcamp::start_download(app, tx, client, &purchase);
+    } else {
+        bandcamp::open(app, tx, client, &purchase, play);
+    }
+    true
+}
@@ -0,0 +1,61 @@
+use crate::app::cd;
+use crate::app::cli::Args;
+use crate::app::events::AppEvent;
+use crate::app::App;
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+/// `:cd` popup: `Enter` plays from the selected track, `a` queues the disc,
+/// `r` rips it and `Tab` names it after the next matching release.
+/// Captures every key while open.
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+pub async fn handle_cd(
+    key: KeyEvent,
+    app: &mut App,
+    args: &Args,
+    tx: &mpsc::Sender<AppEvent>,
+) -> bool {
+    let Some(view) = app.cd.as_mut() else {
+        return false;
+    };
+    let (tracks, releases) = view.disc().map_or((0, 0), |disc| {
+        (
+            disc.toc.tracks.len(),
+            disc.releases.as_ref().map_or(0, Vec::len),
+        )
+    });
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.cd = None,
+        KeyCode::Down | KeyCode::Char('j') => {
+            view.selected = (view.selected + 1).min(tracks.saturating_sub(1))
+        }
+        KeyCode::Up | KeyCode::Char('k') => view.selected = view.selected.saturating_sub(1),
+        KeyCode::Tab if releases > 1 => view.release = (view.release + 1) % releases,
+        KeyCode::Char('r') if tracks > 0 => {
+            cd::start_rip(app, tx);
+            app.cd = None;
+        }
+        KeyCode::Enter | KeyCode::Char('a') if tracks > 0 => {
+            if !app.is_mpd {
+                app.show_warning(&t!("stream.needs_mpd"));
+                return true;
+            }
+            #[cfg(feature = "mpd")]
+            {
+                let play = (key.code == KeyCode::Enter).then_some(view.selected);
+                let Some(disc) = view.disc() else {
+                    return true;
+                };
+                let release = disc.release(view.release);
+                let title = release.map_or_else(|| t!("cd.unknown_album"), |r| r.title.clone());
+                let streams = disc.streams(release);
+                crate::app::ytdlp::queue(app, args, &title, streams, play).await;
+                app.cd = None;
+            }
+        }
+        _ => {}
+    }
+    true
+}
@@ -0,0 +1,52 @@
+use crate::app::events::AppEvent;
+use crate::app::App;
+use crate::audio::pipeline::AudioPipeline;
+use crate::player::PlayerTrait;
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Open the chapter list on the chapter playing now
+pub fn open_chapters(app: &mut App) {
+    if app.chapters.is_empty() {
+        app.show_toast(&t!("toast.no_chapters"));
+        return;
+    }
+    app.chapter_list = Some(app.current_chapter().unwrap_or(0));
+}
+
+/// Chapter list: j/k move, Enter seeks there. Captures every key while open.
+pub fn handle_chapters(
+    key: KeyEvent,
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    audio_pipeline: &mut AudioPipeline,
+    tx: &mpsc::Sender<AppEvent>,
+) -> bool {
+    let Some(selected) = app.chapter_list else {
+        return false;
+    };
+    let last = app.chapters.len().saturating_sub(1);
+    let close = app.keys.matches(key, &app.keys.chapters);
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.chapter_list = None,
+        _ if close => app.chapter_list = None,
+        KeyCode::Down | KeyCode::Char('j') => app.chapter_list = Some((selected + 1).min(last)),
+        KeyCode::Up | KeyCode::Char('k') => app.chapter_list = Some(selected.saturating_sub(1)),
+        KeyCode::Char('g') | KeyCode::Home => app.chapter_list = Some(0),
+        KeyCode::Char('G') | KeyCode::End => app.chapter_list = Some(last),
+        KeyCode::Enter => {
+            app.chapter_list = None;
+            if let Some(chapter) = app.chapters.get(selected) {
+                let (title, start_ms) = (chapter.title.clone(), chapter.start_ms);
+                audio_pipeline.flush();
+                super::player::seek_to(app, player, tx, start_ms as f64 / 1000.0);
+                app.show_toast(&t!("toast.chapter", title = title));
+            }
+        }
+        _ => {}
+    }
+    true
+}
@@ -1,24 +1,75 @@
-use crate::app::App;
+use crate::app::{App, InputMode, InputState};
 use crossterm::event::{KeyCode, KeyEvent};
 
-pub fn handle_common_events(key: KeyEvent, app: &mut App, args: &crate::app::cli::Args) -> bool {
+pub async fn handle_common_events(
+    key: KeyEvent,
+    app: &mut App,
+    args: &crate::app::cli::Args,
+) -> bool {
     let keys = &app.keys;
 
     // Quit ('q')
     if keys.matches(key, &keys.quit) {
         // Close popups first, then quit (Neovim-style)
-        if app.show_keyhints {
-            app.show_keyhints = false;
-        } else if app.show_audio_info {
+        if app.show_audio_info {
             app.show_audio_info = false;
         } else {
-            app.is_running = false;
+            app.request_quit(std::time::Instant::now());
         }
         return true;
     }
 
     if keys.matches(key, &keys.toggle_keyhints) {
-        app.show_keyhints = !app.show_keyhints;
+        super::help::open_help(app);
+        return true;
+    }
+
+    // Undo / redo queue and playlist edits
+    if keys.matches(key, &keys.undo) {
+        super::library::undo_redo(app, args, true).await;
+        return true;
+    }
+    if keys.matches(key, &keys.redo) {
+        super::library::undo_redo(app, args, false).await;
+        return true;
+    }
+
+    // Resize / swap the player card and the panel
+    if keys.matches(key, &keys.pane_left) {
+        app.nudge_panes(-5);
+        return true;
+    }
+    if keys.matches(key, &keys.pane_right) {
+        app.nudge_panes(5);
+        return true;
+    }
+    if keys.matches(key, &keys.pane_swap) {
+        app.swap_panes();
+        return true;
+    }
+
+    if keys.matches(key, &keys.theme_picker) {
+        app.open_theme_picker();
+        return true;
+    }
+
+    if keys.matches(key, &keys.command_line) {
+        app.input_state = Some(InputState::new(InputMode::Command, " : ", ""));
+        return true;
+    }
+
+    if keys.matches(key, &keys.palette) {
+        super::palette::open_palette(app, args).await;
+        return true;
+    }
+
+    if keys.matches(key, &keys.track_info) {
+        super::track_info::open_track_details(app, args).await;
+        return true;
+    }
+
+    if keys.matches(key, &keys.chapters) {
+        super::chapters::open_chapters(app);
         return true;
     }
 
@@ -28,22 +79,24 @@ pub fn handle_common_events(key: KeyEvent, app: &mut App, args: &crate::app::cli
     }
 
     // Global Popup Close (Esc)
-    if (keys.matches(key, &keys.back_dir_alt) || key.code == KeyCode::Esc)
-        && (app.show_keyhints || app.show_audio_info)
-    {
-        if app.show_keyhints {
-            app.show_keyhints = false;
-        }
-        if app.show_audio_info {
-            app.show_audio_info = false;
-        }
+    if (keys.matches(key, &keys.back_dir_alt) || key.code == KeyCode::Esc) && app.show_audio_info {
+        app.show_audio_info = false;
+        return true;
+    }
+
+    // Leave full-screen art (Esc)
+    if key.code == KeyCode::Esc && app.fullscreen_art {
+        app.toggle_fullscreen_art();
         return true;
     }
 
     // Toggle Search (/) - Global Context -> Switch to Library and Focus Search
     #[cfg(feature = "mpd")]
-    if key.code == KeyCode::Char('/') && !args.controller {
-        app.view_mode = crate::app::ViewMode::Library;
+    if key.code == KeyCode::Char('/')
+        && !args.controller
+        && app.view_mode != crate::app::ViewMode::Lyrics
+    {
+        app.show_view(crate::app::ViewMode::Library);
         // Save current mode only if we are NOT already in Search mode
         if app.library_mode != crate::app::LibraryMode::Search {
             app.previous_library_mode = Some(app.library_mode);
@@ -52,6 +105,7 @@ pub fn handle_common_events(key: KeyEvent, app: &mut App, args: &crate::app::cli
         app.search_active = true;
         // Critical: Clear items so we don't see previous Directory contents
         app.library_items.clear();
+        app.album_open = None; // Back to the grid afterwards
         app.library_selected = 0;
         return true;
     }
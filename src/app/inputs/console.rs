@@ -0,0 +1,132 @@
+use crate::app::cli::Args;
+use crate::app::events::AppEvent;
+use crate::app::{App, ConsoleLineKind};
+use crate::player::mpd::console::{self, ConsoleReply};
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tokio::sync::mpsc;
+
+/// Raw MPD console ('`'). Captures every key while open.
+pub fn handle_console_events(
+    key: KeyEvent,
+    app: &mut App,
+    args: &Args,
+    tx: &mpsc::Sender<AppEvent>,
+) -> bool {
+    if !app.console.open {
+        if !args.controller && app.keys.matches(key, &app.keys.mpd_console) {
+            if app.party_locked() {
+                return true;
+            }
+            app.console.open = true;
+            if app.console.output.is_empty() {
+                app.console.push(
+                    ConsoleLineKind::Info,
+                    t!(
+                        "console.greeting",
+                        host = args.mpd_host,
+                        port = args.mpd_port
+                    ),
+                );
+            }
+            return true;
+        }
+        return false;
+    }
+
+    // Confirmation prompt for dangerous commands
+    if let Some(cmd) = app.console.pending_confirm.take() {
+        if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+            run_command(args, tx, cmd);
+        } else {
+            app.console
+                .push(ConsoleLineKind::Info, t!("console.cancelled"));
+        }
+        return true;
+    }
+
+    match key.code {
+        KeyCode::Esc => app.console.open = false,
+        KeyCode::Enter => {
+            let cmd = app.console.input.trim().to_string();
+            app.console.input.clear();
+            if cmd.is_empty() {
+                return true;
+            }
+            app.console.remember(&cmd);
+            app.console
+                .push(ConsoleLineKind::Command, format!("> {}", cmd));
+
+            if console::is_unsupported(&cmd) {
+                app.console.push(
+                    ConsoleLineKind::Error,
+                    t!("console.unsupported", command = console::command_name(&cmd)),
+                );
+            } else if console::needs_confirm(&cmd) {
+                app.console.push(
+                    ConsoleLineKind::Info,
+                    t!("console.confirm", command = console::command_name(&cmd)),
+                );
+                app.console.pending_confirm = Some(cmd);
+            } else {
+                run_command(args, tx, cmd);
+            }
+        }
+        KeyCode::Up => app.console.history_prev(),
+        KeyCode::Down => app.console.history_next(),
+        KeyCode::PageUp => {
+            app.console.scroll = (app.console.scroll + 10).min(app.console.output.len());
+        }
+        KeyCode::PageDown => app.console.scroll = app.console.scroll.saturating_sub(10),
+        KeyCode::Backspace => {
+            app.console.input.pop();
+        }
+        KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.console.output.clear();
+            app.console.scroll = 0;
+        }
+        KeyCode::Char(c) => app.console.input.push(c),
+        _ => {}
+    }
+    true
+}
+
+/// Send `cmd` off the UI task; the reply comes back as `AppEvent::ConsoleReply`
+fn run_command(args: &Args, tx: &mpsc::Sender<AppEvent>, cmd: String) {
+    let (host, port, tx) = (args.mpd_host.clone(), args.mpd_port, tx.clone());
+    tokio::spawn(async move {
+        let line = cmd.clone();
+        let reply = tokio::task::spawn_blocking(move || console::send(&host, port, &line))
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.map_err(|e| e.to_string()));
+        if let Err(e) = tx.send(AppEvent::ConsoleReply(cmd, reply)).await {
+            tracing::debug!("Channel closed: {}", e);
+        }
+    });
+}
+
+/// Print the server's answer to `cmd`
+pub fn show_reply(app: &mut App, cmd: &str, reply: Result<ConsoleReply, String>) {
+    match reply {
+        Ok(ConsoleReply::Ok(lines)) if lines.is_empty() => {
+            app.console.push(ConsoleLineKind::Response, "OK");
+        }
+        Ok(ConsoleReply::Ok(lines)) => {
+            for line in console::pretty_lines(&lines) {
+                app.console.push(ConsoleLineKind::Response, line);
+            }
+        }
+        Ok(ConsoleReply::Ack(err)) => {
+            app.console
+                .push(ConsoleLineKind::Error, format!("ACK {}", err));
+        }
+        Err(e) => {
+            tracing::warn!("MPD console command '{}' failed: {}", cmd, e);
+            app.console.push(
+                ConsoleLineKind::Error,
+                t!("console.connection_error", error = e),
+            );
+        }
+    }
+}
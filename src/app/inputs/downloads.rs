@@ -0,0 +1,40 @@
+use crate::app::cli::Args;
+use crate::app::downloads::TransferState;
+use crate::app::App;
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// `:downloads` popup: `x` stops the selected transfer, `c` clears the
+/// finished ones. Captures every key while open.
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+pub fn handle_downloads(key: KeyEvent, app: &mut App, args: &Args) -> bool {
+    let Some(selected) = app.downloads_view else {
+        return false;
+    };
+
+    let last = app.downloads.list.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => app.downloads_view = None,
+        KeyCode::Down | KeyCode::Char('j') => app.downloads_view = Some((selected + 1).min(last)),
+        KeyCode::Up | KeyCode::Char('k') => app.downloads_view = Some(selected.saturating_sub(1)),
+        KeyCode::Char('x') | KeyCode::Delete => {
+            let Some(transfer) = app.downloads.list.get(selected).cloned() else {
+                return true;
+            };
+            if transfer.state == TransferState::Running && app.downloads.stop(transfer.id) {
+                app.show_toast(&t!("downloads.stopped", title = transfer.title));
+                // A stopped recording or rip keeps the songs it has
+                #[cfg(feature = "mpd")]
+                if transfer.kind != crate::app::downloads::TransferKind::Download && app.is_mpd {
+                    crate::app::downloads::update_mpd(app, args);
+                }
+            }
+        }
+        KeyCode::Char('c') => {
+            app.downloads.clear_finished();
+            app.downloads_view = Some(selected.min(app.downloads.list.len().saturating_sub(1)));
+        }
+        _ => {}
+    }
+    true
+}
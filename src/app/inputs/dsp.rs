@@ -0,0 +1,122 @@
+use crate::app::App;
+use crate::audio::dsp::chain::StageKind;
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Sample rate plugin controls are shown at while no audio runs
+const FALLBACK_RATE: u32 = 48000;
+
+/// `:dsp` popup: Space switches the selected stage, Enter opens a plugin's
+/// controls (and switches the other stages). Captures every key while open.
+pub fn handle_dsp(key: KeyEvent, app: &mut App) -> bool {
+    let Some(selected) = app.dsp_view else {
+        return false;
+    };
+
+    let stages = app.dsp.config().stages();
+    let last = stages.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.dsp_view = None,
+        KeyCode::Down | KeyCode::Char('j') => app.dsp_view = Some((selected + 1).min(last)),
+        KeyCode::Up | KeyCode::Char('k') => app.dsp_view = Some(selected.saturating_sub(1)),
+        KeyCode::Enter if matches!(stages.get(selected), Some(StageKind::Plugin(_))) => {
+            let Some(&StageKind::Plugin(plugin)) = stages.get(selected) else {
+                return true;
+            };
+            match app.dsp.plugin(plugin).cloned() {
+                Some(Ok(_)) => app.plugin_controls = Some((plugin, 0)),
+                Some(Err(e)) => app.show_error(&e),
+                None => {}
+            }
+        }
+        KeyCode::Char(' ') | KeyCode::Enter => {
+            let Some(&kind) = stages.get(selected) else {
+                return true;
+            };
+            // The EQ keeps its own switch, the one `e` flips
+            let enabled = if kind == StageKind::Eq {
+                app.toggle_eq();
+                app.eq_enabled
+            } else {
+                let enabled = !app.dsp.is_enabled(kind);
+                app.dsp.set_enabled(kind, enabled);
+                enabled
+            };
+            app.show_toast(&t!(
+                "dsp.toggled",
+                stage = stage_name(app, kind),
+                state = if enabled {
+                    t!("common.on")
+                } else {
+                    t!("common.off")
+                }
+            ));
+        }
+        _ => {}
+    }
+    true
+}
+
+/// Plugin sliders over the `:dsp` popup: `h`/`l` move the selected one,
+/// `d` puts it back to the plugin's default
+pub fn handle_plugin_controls(key: KeyEvent, app: &mut App) -> bool {
+    let Some((plugin, selected)) = app.plugin_controls else {
+        return false;
+    };
+    let Some(Ok(loaded)) = app.dsp.plugin(plugin).cloned() else {
+        app.plugin_controls = None;
+        return true;
+    };
+    let controls = &loaded.info.controls;
+    let rate = control_rate(app);
+
+    let steps = match key.code {
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => 1,
+        KeyCode::Left | KeyCode::Char('h') => -1,
+        KeyCode::Char('L') => 5,
+        KeyCode::Char('H') => -5,
+        _ => 0,
+    };
+    if let (Some(control), true) = (controls.get(selected), steps != 0) {
+        let value = app.dsp.control(plugin, selected);
+        app.dsp
+            .set_control(plugin, selected, control.step(value, steps, rate));
+        return true;
+    }
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.plugin_controls = None,
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.plugin_controls =
+                Some((plugin, (selected + 1).min(controls.len().saturating_sub(1))))
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.plugin_controls = Some((plugin, selected.saturating_sub(1)))
+        }
+        KeyCode::Char('d') => {
+            if let Some(control) = controls.get(selected) {
+                let (min, max) = control.range(rate);
+                app.dsp
+                    .set_control(plugin, selected, control.default.clamp(min, max));
+            }
+        }
+        _ => {}
+    }
+    true
+}
+
+/// Rate for sample-rate-relative controls: the running chain's, if any
+pub fn control_rate(app: &App) -> u32 {
+    app.dsp.report().map_or(FALLBACK_RATE, |(rate, _)| rate)
+}
+
+/// Display name of a stage
+pub fn stage_name(app: &App, kind: StageKind) -> String {
+    match kind {
+        StageKind::Eq => t!("dsp.eq"),
+        StageKind::Compressor => t!("dsp.compressor"),
+        StageKind::Crossfeed => t!("dsp.crossfeed"),
+        StageKind::Limiter => t!("dsp.limiter"),
+        StageKind::Plugin(_) => app.dsp.stage_name(kind),
+    }
+}
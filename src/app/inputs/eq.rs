@@ -1,7 +1,8 @@
 use crate::app::cli::Args;
 #[cfg(feature = "mpd")]
-use crate::app::with_mpd;
+use crate::app::send_mpd;
 use crate::app::{self, App};
+use crate::t;
 use crossterm::event::KeyEvent;
 
 pub fn handle_eq_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
@@ -15,18 +16,28 @@ pub fn handle_eq_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
     if keys.matches(key, &keys.save_preset) {
         app.input_state = Some(app::InputState::new(
             app::InputMode::EqSave,
-            "Save Preset As",
+            &t!("input.save_preset"),
             "",
         ));
         return true;
     }
 
+    // EQ Import Preset (Shift+P)
+    if keys.matches(key, &keys.import_preset) {
+        app.input_state = Some(app::InputState::new(
+            app::InputMode::EqImport,
+            &t!("input.import_preset"),
+            "~/",
+        ));
+        return true;
+    }
+
     // EQ Delete Preset (Shift+X)
     if keys.matches(key, &keys.delete_preset) {
         if let Err(e) = app.delete_preset() {
-            app.show_toast(&format!("❌ {}", e));
+            app.show_error(&format!("❌ {}", e));
         } else {
-            app.show_toast("🗑️ Preset Deleted");
+            app.show_toast(&t!("toast.preset_deleted"));
         }
         return true;
     }
@@ -47,7 +58,11 @@ pub fn handle_eq_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
         app.mark_custom();
         app.sync_band_to_dsp(app.eq_selected);
         let db = (app.eq_bands[app.eq_selected] - 0.5) * 24.0;
-        app.show_toast(&format!("🎚 Band {}: {:+.1}dB", app.eq_selected + 1, db));
+        app.show_toast(&t!(
+            "toast.band_gain",
+            band = app.eq_selected + 1,
+            db = format!("{:+.1}", db)
+        ));
         return true;
     }
     if keys.matches(key, &keys.gain_down) || keys.matches(key, &keys.nav_down_alt) {
@@ -56,20 +71,82 @@ pub fn handle_eq_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
         app.mark_custom();
         app.sync_band_to_dsp(app.eq_selected);
         let db = (app.eq_bands[app.eq_selected] - 0.5) * 24.0;
-        app.show_toast(&format!("🎚 Band {}: {:+.1}dB", app.eq_selected + 1, db));
+        app.show_toast(&t!(
+            "toast.band_gain",
+            band = app.eq_selected + 1,
+            db = format!("{:+.1}", db)
+        ));
         return true;
     }
     if keys.matches(key, &keys.toggle_eq) {
         app.toggle_eq();
-        app.show_toast(&format!(
-            "🎛 EQ: {}",
-            if app.eq_enabled { "ON" } else { "OFF" }
-        ));
+        let state = if app.eq_enabled {
+            t!("common.on")
+        } else {
+            t!("common.off")
+        };
+        app.show_toast(&t!("toast.eq", state = state));
+        return true;
+    }
+    if keys.matches(key, &keys.ab_compare) {
+        app.toggle_ab();
+        app.show_toast(&if app.eq_ab_on_b {
+            t!("toast.eq_b")
+        } else {
+            t!("toast.eq_a")
+        });
+        return true;
+    }
+    if keys.matches(key, &keys.ab_store) {
+        app.store_ab_reference();
+        app.show_toast(&t!("toast.eq_b_stored"));
+        return true;
+    }
+    if keys.matches(key, &keys.spectrum_overlay) {
+        app.spectrum_overlay = app.spectrum_overlay.next();
+        app.show_toast(&match app.spectrum_overlay {
+            app::SpectrumOverlay::Post => t!("toast.spectrum_post"),
+            app::SpectrumOverlay::Pre => t!("toast.spectrum_pre"),
+            app::SpectrumOverlay::Off => t!("toast.spectrum_off"),
+        });
+        return true;
+    }
+    // Output format: session only, `[output]` in config.toml sets the start
+    if keys.matches(key, &keys.output_depth) {
+        let depth = app.output.depth().next();
+        app.output.set_depth(depth);
+        let depth = match depth.full_scale() {
+            Some(_) => format!("{} bit", depth.label()),
+            None => t!("audio_info.float"),
+        };
+        app.show_toast(&t!("toast.output_depth", depth = depth));
+        return true;
+    }
+    if keys.matches(key, &keys.output_dither) {
+        app.output.set_dither(!app.output.dither());
+        let state = if app.output.dither() {
+            t!("common.on")
+        } else {
+            t!("common.off")
+        };
+        app.show_toast(&t!("toast.output_dither", state = state));
+        return true;
+    }
+    if keys.matches(key, &keys.output_rate) {
+        let rate = app.output.next_sample_rate();
+        app.output.set_sample_rate(rate);
+        app.show_toast(&match rate {
+            Some(rate) => t!(
+                "toast.output_rate",
+                rate = format!("{:.1}", rate as f32 / 1000.0)
+            ),
+            None => t!("toast.output_rate_source"),
+        });
         return true;
     }
     if keys.matches(key, &keys.reset_eq) {
         app.reset_eq();
-        app.show_toast("🔄 EQ Reset");
+        app.show_toast(&t!("toast.eq_reset"));
         return true;
     }
     if keys.matches(key, &keys.reset_levels) {
@@ -77,17 +154,17 @@ pub fn handle_eq_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
         app.reset_balance();
         // app.mark_custom(); // Removed to keep current preset
         app.sync_band_to_dsp(app.eq_selected);
-        app.show_toast("🎯 Levels Reset");
+        app.show_toast(&t!("toast.levels_reset"));
         return true;
     }
     if keys.matches(key, &keys.tab_next) {
         app.next_preset();
-        app.show_toast(&format!("🎵 Preset: {}", app.get_preset_name()));
+        app.show_toast(&t!("toast.preset", name = app.get_preset_name()));
         return true;
     }
     if keys.matches(key, &keys.tab_prev) {
         app.prev_preset();
-        app.show_toast(&format!("🎵 Preset: {}", app.get_preset_name()));
+        app.show_toast(&t!("toast.preset", name = app.get_preset_name()));
         return true;
     }
     if keys.matches(key, &keys.preamp_up) {
@@ -111,7 +188,7 @@ pub fn handle_eq_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
         #[cfg(feature = "mpd")]
         if !args.controller {
             let secs = app.crossfade_secs as i64;
-            with_mpd(app, args, |mpd| {
+            send_mpd(app, args, move |mpd| {
                 if let Err(e) = mpd.crossfade(secs) {
                     tracing::warn!("Failed to set MPD crossfade: {}", e);
                 }
@@ -130,7 +207,7 @@ pub fn handle_eq_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
                 3 => mpd::status::ReplayGain::Auto,
                 _ => mpd::status::ReplayGain::Off,
             };
-            with_mpd(app, args, |mpd| {
+            send_mpd(app, args, move |mpd| {
                 if let Err(e) = mpd.replaygain(mode) {
                     tracing::warn!("Failed to set MPD replaygain: {}", e);
                 }
@@ -141,3 +218,26 @@ pub fn handle_eq_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
 
     false
 }
+
+/// Import a preset file (`vyom eq export`'s JSON or an AutoEq profile) and
+/// switch to it
+#[cfg(feature = "mpd")]
+pub(super) fn import_preset_file(app: &mut App, input: &str) {
+    let path = crate::app::playlist_files::expand(input);
+    match crate::app::config::sharing::read_file(&path, None) {
+        Ok((preset, preamp_db)) => {
+            let name = preset.name.clone();
+            app.import_preset(preset);
+            if preamp_db < 0.0 {
+                app.show_toast(&t!(
+                    "toast.preset_imported_preamp",
+                    name = name,
+                    db = format!("{:.1}", preamp_db)
+                ));
+            } else {
+                app.show_toast(&t!("toast.preset_imported", name = name));
+            }
+        }
+        Err(e) => app.show_error(&e.user_message()),
+    }
+}
@@ -0,0 +1,78 @@
+use crate::app::{App, HelpState, ViewMode};
+use crate::ui::widgets::popups::help::pages;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Open the help browser on the page for the current view
+pub fn open_help(app: &mut App) {
+    let context = match app.view_mode {
+        ViewMode::Library => "library",
+        ViewMode::EQ => "eq",
+        ViewMode::Lyrics => "lyrics",
+        ViewMode::Visualizer | ViewMode::Info | ViewMode::Related => "global",
+    };
+    app.help = HelpState {
+        page: pages(app).iter().position(|p| p.id == context).unwrap_or(0),
+        ..Default::default()
+    };
+    app.show_keyhints = true;
+}
+
+/// `?` help browser: Tab flips pages, `/` filters. Captures every key while open.
+pub fn handle_help(key: KeyEvent, app: &mut App) -> bool {
+    if !app.show_keyhints {
+        return false;
+    }
+    let page_count = pages(app).len();
+    let close =
+        app.keys.matches(key, &app.keys.quit) || app.keys.matches(key, &app.keys.toggle_keyhints);
+    let help = &mut app.help;
+
+    if help.searching {
+        match key.code {
+            KeyCode::Esc => {
+                help.query.clear();
+                help.searching = false;
+            }
+            KeyCode::Enter => help.searching = false,
+            KeyCode::Backspace => {
+                help.query.pop();
+                help.scroll = 0;
+            }
+            KeyCode::Down => help.scroll += 1,
+            KeyCode::Up => help.scroll = help.scroll.saturating_sub(1),
+            KeyCode::Char(c) => {
+                help.query.push(c);
+                help.scroll = 0;
+            }
+            _ => {}
+        }
+        return true;
+    }
+
+    match key.code {
+        KeyCode::Esc if !help.query.is_empty() => {
+            help.query.clear();
+            help.scroll = 0;
+        }
+        KeyCode::Esc => app.show_keyhints = false,
+        _ if close => app.show_keyhints = false,
+        KeyCode::Char('/') => help.searching = true,
+        KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => {
+            help.page = (help.page + 1) % page_count;
+            help.query.clear();
+            help.scroll = 0;
+        }
+        KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+            help.page = (help.page + page_count - 1) % page_count;
+            help.query.clear();
+            help.scroll = 0;
+        }
+        KeyCode::Down | KeyCode::Char('j') => help.scroll += 1,
+        KeyCode::Up | KeyCode::Char('k') => help.scroll = help.scroll.saturating_sub(1),
+        KeyCode::Char('g') | KeyCode::Home => help.scroll = 0,
+        // Clamped to the list when drawn
+        KeyCode::Char('G') | KeyCode::End => help.scroll = usize::MAX,
+        _ => {}
+    }
+    true
+}
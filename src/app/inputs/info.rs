@@ -0,0 +1,21 @@
+use crate::app::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Info view: j/k scroll, g back to the top
+pub fn handle_info_events(key: KeyEvent, app: &mut App) -> bool {
+    let keys = &app.keys;
+    if keys.matches(key, &keys.nav_down) || keys.matches(key, &keys.nav_down_alt) {
+        app.about_scroll = app.about_scroll.saturating_add(1);
+    } else if keys.matches(key, &keys.nav_up) || keys.matches(key, &keys.nav_up_alt) {
+        app.about_scroll = app.about_scroll.saturating_sub(1);
+    } else if key.code == KeyCode::PageDown {
+        app.about_scroll = app.about_scroll.saturating_add(10);
+    } else if key.code == KeyCode::PageUp {
+        app.about_scroll = app.about_scroll.saturating_sub(10);
+    } else if matches!(key.code, KeyCode::Char('g') | KeyCode::Home) {
+        app.about_scroll = 0;
+    } else {
+        return false;
+    }
+    true
+}
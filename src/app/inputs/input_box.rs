@@ -4,15 +4,12 @@ use crate::app::library_helpers::fetch_directory_items;
 #[cfg(feature = "mpd")]
 use crate::app::with_mpd;
 use crate::app::{self, App};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 #[allow(unused_imports)]
 use crate::app::cli::Args;
 use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
-#[cfg(feature = "mpd")]
-use lofty::file::TaggedFileExt;
-#[cfg(feature = "mpd")]
-use lofty::tag::Accessor;
 use reqwest::Client;
 use tokio::sync::mpsc;
 
@@ -20,8 +17,8 @@ pub async fn handle_input_box(
     key: KeyEvent,
     app: &mut App,
     args: &Args,
-    _tx: &mpsc::Sender<AppEvent>,
-    _client: &Client,
+    tx: &mpsc::Sender<AppEvent>,
+    client: &Client,
 ) -> bool {
     // Return true if input was consumed
 
@@ -39,27 +36,76 @@ pub async fn handle_input_box(
                                 #[cfg(feature = "mpd")]
                                 {
                                     let val = input.value.clone();
-                                    let result = with_mpd(app, args, |mpd| {
-                                        mpd.save(&val).map_err(|e| e.to_string())
-                                    });
+                                    let name = val.clone();
+                                    let result = with_mpd(app, args, move |mpd| {
+                                        mpd.save(&name).map_err(|e| e.to_string())
+                                    })
+                                    .await;
 
                                     if let Some(res) = result {
                                         match res {
                                             Ok(_) => {
-                                                app.show_toast(&format!("💾 Saved: {}", val));
+                                                app.show_toast(&t!(
+                                                    "toast.playlist_saved",
+                                                    name = val
+                                                ));
                                                 app.playlists.push(val);
                                             }
-                                            Err(e) => app.show_toast(&format!("❌ Error: {}", e)),
+                                            Err(e) => {
+                                                app.show_error(&t!("error.generic", error = e))
+                                            }
                                         }
                                     }
                                 }
                             }
                         }
 
+                        #[cfg(feature = "mpd")]
+                        app::InputMode::PlaylistImport if !input.value.trim().is_empty() => {
+                            super::library::import_playlist_file(app, args, input.value.trim())
+                                .await;
+                        }
+
+                        #[cfg(feature = "mpd")]
+                        app::InputMode::PlaylistExport(name) if !input.value.trim().is_empty() => {
+                            super::library::export_playlist_file(
+                                app,
+                                args,
+                                name,
+                                input.value.trim(),
+                            )
+                            .await;
+                        }
+
+                        app::InputMode::PlaylistImport | app::InputMode::PlaylistExport(_) => {}
+
+                        app::InputMode::LyricsLine(index) => {
+                            if let Some(editor) = app.lyrics_editor.as_mut() {
+                                editor.set_text(index, input.value.trim());
+                            }
+                        }
+
+                        app::InputMode::LyricsSearch => {
+                            super::lyrics::start_search(app, input.value.trim());
+                        }
+
+                        app::InputMode::Command => {
+                            if !run_background_command(app, tx, client, input.value.trim()) {
+                                run_command(app, args, input.value.trim()).await;
+                            }
+                        }
+
+                        #[cfg(feature = "mpd")]
+                        app::InputMode::EqImport if !input.value.trim().is_empty() => {
+                            super::eq::import_preset_file(app, input.value.trim());
+                        }
+
+                        app::InputMode::EqImport => {}
+
                         app::InputMode::EqSave => {
                             if !input.value.is_empty() {
                                 app.save_preset(input.value.clone());
-                                app.show_toast(&format!("💾 Preset Saved: {}", input.value));
+                                app.show_toast(&t!("toast.preset_saved", name = input.value));
                             }
                         }
 
@@ -70,24 +116,26 @@ pub async fn handle_input_box(
                                     let new_name = input.value.clone();
                                     let old = old_name.clone();
 
-                                    let result = with_mpd(app, args, |mpd| {
-                                        match mpd.pl_rename(&old, &new_name) {
+                                    let (from, to) = (old.clone(), new_name.clone());
+                                    let result = with_mpd(app, args, move |mpd| {
+                                        match mpd.pl_rename(&from, &to) {
                                             Ok(_) => mpd.playlists().map_err(|e| e.to_string()),
                                             Err(e) => Err(e.to_string()),
                                         }
-                                    });
+                                    })
+                                    .await;
 
                                     if let Some(res) = result {
                                         match res {
                                             Ok(playlists) => {
-                                                app.show_toast(&format!(
-                                                    "✏️ Renamed: {} -> {}",
-                                                    old, new_name
+                                                app.show_toast(&t!(
+                                                    "toast.playlist_renamed",
+                                                    old = old,
+                                                    new = new_name
                                                 ));
-                                                app.playlists = playlists
-                                                    .iter()
-                                                    .map(|p| p.name.clone())
-                                                    .collect();
+                                                app.set_playlists(
+                                                    playlists.into_iter().map(|p| p.name),
+                                                );
                                                 if app.library_mode == app::LibraryMode::Playlists {
                                                     app.library_items = app
                                                         .playlists
@@ -103,7 +151,9 @@ pub async fn handle_input_box(
                                                         .collect();
                                                 }
                                             }
-                                            Err(e) => app.show_toast(&format!("❌ Error: {}", e)),
+                                            Err(e) => {
+                                                app.show_error(&t!("error.generic", error = e))
+                                            }
                                         }
                                     }
                                 }
@@ -128,83 +178,55 @@ pub async fn handle_input_box(
     }
 
     // Tag editor input handling (takes priority)
-    if app.tag_edit.is_some() {
-        match key.code {
-            KeyCode::Esc => {
-                app.tag_edit = None; // Cancel
-            }
-            KeyCode::Tab => {
-                if let Some(ref mut tag) = app.tag_edit {
-                    tag.next_field();
+    if let Some(tag) = app.tag_edit.as_mut() {
+        if tag.progress.is_some() {
+            return true; // Writing: wait for it to finish
+        }
+        if let Some(lookup) = tag.lookup.as_mut() {
+            match key.code {
+                KeyCode::Esc => tag.lookup = None,
+                KeyCode::Up => lookup.selected = lookup.selected.saturating_sub(1),
+                KeyCode::Down if lookup.selected + 1 < lookup.results.len() => {
+                    lookup.selected += 1;
                 }
-            }
-            KeyCode::BackTab => {
-                if let Some(ref mut tag) = app.tag_edit {
-                    tag.prev_field();
+                KeyCode::Enter => {
+                    if let Some(candidate) = lookup.results.get(lookup.selected).cloned() {
+                        tag.apply(&candidate);
+                        tag.lookup = None;
+                    }
                 }
+                _ => {}
             }
-            KeyCode::Backspace => {
-                if let Some(ref mut tag) = app.tag_edit {
-                    tag.active_value().pop();
-                }
+            return true;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                app.tag_edit = None; // Cancel
             }
-            KeyCode::Enter => {
-                // Save tags using lofty
-                if let Some(ref tag_state) = app.tag_edit {
-                    #[cfg(feature = "mpd")]
-                    if !tag_state.file_path.is_empty() {
-                        // MPD music directory from config
-                        let music_dir = &app.music_directory;
-                        let full_path = format!("{}/{}", music_dir, tag_state.file_path);
-
-                        // Write tags using lofty
-                        if let Ok(mut tagged_file) = lofty::read_from_path(&full_path) {
-                            let mut modified = false;
-                            if let Some(tag) = tagged_file.primary_tag_mut() {
-                                tag.set_title(tag_state.title.clone());
-                                tag.set_artist(tag_state.artist.clone());
-                                if !tag_state.album.is_empty() {
-                                    tag.set_album(tag_state.album.clone());
-                                }
-                                modified = true;
-                            }
-
-                            if !modified {
-                                if let Some(tag) = tagged_file.first_tag_mut() {
-                                    tag.set_title(tag_state.title.clone());
-                                    tag.set_artist(tag_state.artist.clone());
-                                    if !tag_state.album.is_empty() {
-                                        tag.set_album(tag_state.album.clone());
-                                    }
-                                }
-                            }
-
-                            // Save to file
-                            if let Ok(mut file) = std::fs::OpenOptions::new()
-                                .read(true)
-                                .write(true)
-                                .open(&full_path)
-                            {
-                                use lofty::file::AudioFile;
-                                if let Err(e) = tagged_file
-                                    .save_to(&mut file, lofty::config::WriteOptions::default())
-                                {
-                                    tracing::warn!("Failed to save modified tags: {}", e);
-                                    app.show_toast(&format!("❌ Tag Save Error: {}", e));
-                                } else {
-                                    app.show_toast("💾 Tags Saved");
-                                }
-                            }
-                        }
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                tag.lookup = Some(app::TagLookup::searching());
+                let query = tag.query(&app.music_directory);
+                let files = tag.files.clone();
+                let key = app.acoustid_key.clone();
+                let tx = tx.clone();
+                let client = client.clone();
+                tokio::spawn(async move {
+                    let res = app::tag_lookup::lookup(&client, &query, key.as_deref())
+                        .await
+                        .map_err(|e| e.to_string());
+                    if let Err(e) = tx.send(AppEvent::TagCandidates(files, res)).await {
+                        tracing::debug!("Channel closed: {}", e);
                     }
-                }
-                app.tag_edit = None;
+                });
             }
-            KeyCode::Char(c) => {
-                if let Some(ref mut tag) = app.tag_edit {
-                    tag.active_value().push(c);
-                }
+            KeyCode::Tab => tag.next_field(),
+            KeyCode::BackTab => tag.prev_field(),
+            KeyCode::Backspace => tag.pop_char(),
+            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                tag.keep_active();
             }
+            KeyCode::Enter => save_tags(app, tx),
+            KeyCode::Char(c) => tag.push_char(c),
             _ => {}
         }
         return true;
@@ -231,10 +253,14 @@ pub async fn handle_input_box(
                     let target = target_mode;
                     let current_path = app.browse_path.join("/");
 
-                    if let Some(items) = with_mpd(app, args, |mpd| match target {
-                        app::LibraryMode::Directory => fetch_directory_items(mpd, &current_path)
-                            .ok()
-                            .map(|i| (None, i)),
+                    let cache = app.library_cache.clone();
+                    if let Some(items) = with_mpd(app, args, move |mpd| match target {
+                        app::LibraryMode::Directory => match &cache {
+                            Some(cache) => Some((None, cache.directory(&current_path))),
+                            None => fetch_directory_items(mpd, &current_path)
+                                .ok()
+                                .map(|i| (None, i)),
+                        },
                         app::LibraryMode::Playlists => {
                             if let Ok(playlists) = mpd.playlists() {
                                 let items = playlists
@@ -254,11 +280,12 @@ pub async fn handle_input_box(
                         }
                         _ => Some((None, Vec::new())),
                     })
+                    .await
                     .flatten()
                     {
                         let (playlists_opt, items) = items;
                         if let Some(playlists) = playlists_opt {
-                            app.playlists = playlists.iter().map(|p| p.name.clone()).collect();
+                            app.set_playlists(playlists.into_iter().map(|p| p.name));
                         }
 
                         if target == app::LibraryMode::Directory
@@ -278,46 +305,8 @@ pub async fn handle_input_box(
                 app.search_active = false;
                 // Perform MPD search
                 #[cfg(feature = "mpd")]
-                if !args.controller && !app.search_query.is_empty() {
-                    if let Some(songs) = with_mpd(app, args, |mpd| mpd.listall().ok()).flatten() {
-                        let matcher = SkimMatcherV2::default();
-                        // Fuzzy Match 🔍
-                        let mut matched_items: Vec<(i64, mpd::Song)> = songs
-                            .into_iter()
-                            .filter_map(|s| {
-                                let search_text = format!(
-                                    "{} {} {}",
-                                    s.title.as_deref().unwrap_or(""),
-                                    s.artist.as_deref().unwrap_or(""),
-                                    s.file
-                                );
-                                matcher
-                                    .fuzzy_match(&search_text, &app.search_query)
-                                    .map(|score| (score, s))
-                            })
-                            .collect();
-
-                        // Sort by score (descending)
-                        matched_items.sort_by(|a, b| b.0.cmp(&a.0));
-
-                        app.library_items = matched_items
-                            .into_iter()
-                            .take(50)
-                            .map(|(_, s)| app::LibraryItem {
-                                name: s.title.clone().unwrap_or_else(|| s.file.clone()),
-                                item_type: app::LibraryItemType::Song,
-                                artist: s.artist.clone().or_else(|| {
-                                    s.tags
-                                        .iter()
-                                        .find(|(k, _)| k == "Artist")
-                                        .map(|(_, v)| v.clone())
-                                }),
-                                duration_ms: s.duration.map(|d| d.as_millis() as u64),
-                                path: Some(s.file),
-                            })
-                            .collect();
-                        app.library_selected = 0;
-                    }
+                if !args.controller && run_search(app, args).await {
+                    app.library_selected = 0;
                 }
             }
             KeyCode::Up => {
@@ -337,3 +326,292 @@ pub async fn handle_input_box(
 
     false
 }
+
+/// `:` commands that start work in the background, which `run_command`
+/// can't. Returns whether `command` was one of them.
+pub(crate) fn run_background_command(
+    app: &mut App,
+    tx: &mpsc::Sender<AppEvent>,
+    client: &Client,
+    command: &str,
+) -> bool {
+    match command.split_once(' ').unwrap_or((command, "")) {
+        ("open", link) if !link.trim().is_empty() => app::ytdlp::open(app, tx, link.trim()),
+        ("download", "") => app::downloads::download_playing(app, tx, client),
+        ("download", link) => app::downloads::download_link(app, tx, link.trim()),
+        ("record", "") => app::downloads::record_playing(app, tx, client),
+        ("cd", "") => app::cd::open(app, tx, client),
+        _ => return false,
+    }
+    true
+}
+
+/// `:` commands
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+pub(crate) async fn run_command(app: &mut App, args: &Args, command: &str) {
+    match command.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [] => {}
+        ["party"] => set_party_mode(app, !app.party_mode),
+        ["party", "on"] => set_party_mode(app, true),
+        ["party", "off"] => set_party_mode(app, false),
+        // Emptying, replacing or rewriting things: not at a party
+        ["clear" | "missing" | "name2tag" | "tag2name", ..]
+        | ["snapshot" | "snap", "load" | "delete" | "rm", ..]
+            if app.party_locked() => {}
+        #[cfg(feature = "mpd")]
+        ["clear"] if !args.controller => clear_queue(app, args).await,
+        #[cfg(feature = "mpd")]
+        ["snapshot" | "snap", rest @ ..] if !args.controller => {
+            snapshot_command(app, args, rest).await
+        }
+        #[cfg(feature = "mpd")]
+        [tool @ ("name2tag" | "tag2name"), _, ..] if !args.controller => {
+            // The pattern as typed, spaces and all
+            let pattern = command.trim_start()[tool.len()..].trim();
+            tag_tool_preview(app, *tool == "name2tag", pattern);
+        }
+        #[cfg(feature = "mpd")]
+        ["missing"] if !args.controller => scan_missing(app, args).await,
+        ["stream", ..] => {
+            let query = command.trim_start()["stream".len()..].trim();
+            super::stream_search::open_stream_search(app, query);
+        }
+        ["wrapped"] => super::wrapped::open_wrapped(app, None),
+        ["wrapped", year] => match year.parse() {
+            Ok(year) => super::wrapped::open_wrapped(app, Some(year)),
+            Err(_) => app.show_error(&t!("error.unknown_command", command = command)),
+        },
+        ["messages" | "mes"] => app.messages_view = Some(0),
+        ["downloads"] => app.downloads_view = Some(0),
+        ["dsp"] => app.dsp_view = Some(0),
+        ["messages" | "mes", "clear"] => {
+            app.toasts.history.clear();
+            app.show_toast(&t!("toast.messages_cleared"));
+        }
+        _ => app.show_error(&t!("error.unknown_command", command = command)),
+    }
+}
+
+fn set_party_mode(app: &mut App, on: bool) {
+    app.party_mode = on;
+    app.quit_armed = None;
+    app.show_toast(&t!("party.state", state = super::player::on_off(on)));
+}
+
+/// `:name2tag <pattern>` / `:tag2name <pattern>`: a dry run over the marked
+/// songs (or the list on screen) to confirm with `Enter`
+#[cfg(feature = "mpd")]
+fn tag_tool_preview(app: &mut App, to_tags: bool, pattern: &str) {
+    let parsed = match crate::app::tag_patterns::Pattern::parse(pattern) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            app.show_error(&t!("error.generic", error = e));
+            return;
+        }
+    };
+    let files = app.target_songs();
+    if files.is_empty() {
+        app.show_warning(&t!("tag_tool.no_songs"));
+        return;
+    }
+    let rows = crate::app::tags::preview(&app.music_directory, &files, &parsed, to_tags);
+    app.tag_tool = Some(app::TagToolPreview {
+        pattern: pattern.to_string(),
+        rows,
+        scroll: 0,
+        progress: None,
+    });
+}
+
+/// Write the tag editor's changes, in the background and one file at a
+/// time; the editor shows progress until `AppEvent::TagsWritten`
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+fn save_tags(app: &mut App, tx: &mpsc::Sender<AppEvent>) {
+    let Some(edit) = app.tag_edit.as_ref() else {
+        return;
+    };
+    let changes = edit.changes();
+    if changes.is_empty() {
+        app.tag_edit = None;
+        return;
+    }
+    #[cfg(feature = "mpd")]
+    {
+        if let Err(e) = crate::app::tags::check(&changes) {
+            app.show_error(&t!("error.tag_save", error = e));
+            return;
+        }
+        let jobs = edit
+            .files
+            .iter()
+            .map(|file| (file.clone(), changes.clone()))
+            .collect();
+        crate::app::tags::spawn_write(&app.music_directory, jobs, tx.clone());
+        if let Some(edit) = app.tag_edit.as_mut() {
+            edit.progress = Some(0);
+        }
+    }
+    #[cfg(not(feature = "mpd"))]
+    {
+        app.tag_edit = None;
+    }
+}
+
+/// `:clear` empties the queue; `u` brings it back
+#[cfg(feature = "mpd")]
+async fn clear_queue(app: &mut App, args: &Args) {
+    let cleared = with_mpd(app, args, move |mpd| {
+        let files: Vec<String> = mpd.queue()?.into_iter().map(|s| s.file).collect();
+        mpd.clear()?;
+        Ok::<_, mpd::error::Error>(files)
+    })
+    .await;
+    match cleared {
+        Some(Ok(files)) => {
+            app.journal.record(app::Operation::QueueClear { files });
+            let key = app.keys.display(&app.keys.undo);
+            app.show_toast(&t!("toast.queue_cleared", key = key));
+        }
+        Some(Err(e)) => app.show_error(&t!("error.generic", error = e)),
+        None => {}
+    }
+}
+
+/// `:snapshot [list]`, `:snapshot save|load|delete <name>`
+#[cfg(feature = "mpd")]
+async fn snapshot_command(app: &mut App, args: &Args, words: &[&str]) {
+    use crate::app::snapshots;
+
+    let dir = snapshots::dir();
+    let (action, name) = match words {
+        [] | ["list"] => {
+            let names = snapshots::list_in(&dir);
+            if names.is_empty() {
+                app.show_toast(&t!("snapshot.none"));
+            } else {
+                app.show_toast(&t!("snapshot.list", names = names.join(", ")));
+            }
+            return;
+        }
+        [action, name @ ..] if !name.is_empty() => (*action, name.join(" ")),
+        _ => {
+            app.show_error(&t!("snapshot.usage"));
+            return;
+        }
+    };
+
+    let result = match action {
+        "save" => with_mpd(app, args, move |mpd| {
+            snapshots::capture(mpd).map_err(|e| e.to_string())
+        })
+        .await
+        .unwrap_or_else(|| Err(t!("remote.no_mpd")))
+        .and_then(|snapshot| {
+            snapshots::save_in(&dir, &name, &snapshot)?;
+            Ok(t!(
+                "snapshot.saved",
+                name = name,
+                count = snapshot.files.len()
+            ))
+        }),
+        "load" => match snapshots::load_in(&dir, &name) {
+            Ok(snapshot) => {
+                match with_mpd(app, args, move |mpd| snapshots::restore(mpd, &snapshot)).await {
+                    Some(Ok(0)) => Ok(t!("snapshot.loaded", name = name)),
+                    Some(Ok(missing)) => Ok(t!(
+                        "snapshot.loaded_missing",
+                        name = name,
+                        missing = missing
+                    )),
+                    Some(Err(e)) => Err(e.to_string()),
+                    None => Err(t!("remote.no_mpd")),
+                }
+            }
+            Err(e) => Err(e),
+        },
+        "delete" | "rm" => {
+            snapshots::delete_in(&dir, &name).map(|_| t!("snapshot.deleted", name = name))
+        }
+        _ => Err(t!("snapshot.usage")),
+    };
+    match result {
+        Ok(msg) => app.show_toast(&msg),
+        Err(e) => app.show_error(&t!("error.generic", error = e)),
+    }
+}
+
+/// `:missing`: list what can't be played, or say all is well
+#[cfg(feature = "mpd")]
+async fn scan_missing(app: &mut App, args: &Args) {
+    let music_dir = app.music_directory.clone();
+    match with_mpd(app, args, move |mpd| {
+        crate::app::missing::scan(mpd, &music_dir)
+    })
+    .await
+    {
+        Some(Ok((entries, disk_checked))) if entries.is_empty() => {
+            if disk_checked {
+                app.show_toast(&t!("missing.none"));
+            } else {
+                app.show_toast(&t!("missing.none_playlists"));
+            }
+        }
+        Some(Ok((entries, disk_checked))) => {
+            app.missing = Some(app::MissingReport {
+                entries,
+                scroll: 0,
+                disk_checked,
+            });
+        }
+        Some(Err(e)) => app.show_error(&t!("error.generic", error = e)),
+        None => app.show_error(&t!("remote.no_mpd")),
+    }
+}
+
+/// Fuzzy-match the whole library against the search query (best 50 first)
+#[cfg(feature = "mpd")]
+pub(crate) async fn run_search(app: &mut App, args: &Args) -> bool {
+    if app.search_query.is_empty() {
+        return false;
+    }
+    let songs = match app.library_cache.clone() {
+        Some(cache) => cache,
+        None => match with_mpd(app, args, move |mpd| app::library_cache::songs(mpd).ok())
+            .await
+            .flatten()
+        {
+            Some(songs) => std::sync::Arc::new(app::library_cache::LibraryCache {
+                songs,
+                ..Default::default()
+            }),
+            None => return false,
+        },
+    };
+    let matcher = SkimMatcherV2::default();
+    // Fuzzy Match 🔍
+    let mut matched_items: Vec<(i64, &app::library_cache::CachedSong)> = songs
+        .songs
+        .iter()
+        .filter_map(|s| {
+            let search_text = format!(
+                "{} {} {}",
+                s.title.as_deref().unwrap_or(""),
+                s.artist.as_deref().unwrap_or(""),
+                s.file
+            );
+            matcher
+                .fuzzy_match(&search_text, &app.search_query)
+                .map(|score| (score, s))
+        })
+        .collect();
+
+    // Sort by score (descending)
+    matched_items.sort_by_key(|item| std::cmp::Reverse(item.0));
+
+    app.library_items = matched_items
+        .into_iter()
+        .take(50)
+        .map(|(_, s)| s.item())
+        .collect();
+    true
+}
@@ -1,21 +1,23 @@
 use crate::app::cli::Args;
 #[cfg(feature = "mpd")]
-use crate::app::library_helpers::fetch_directory_items;
+use crate::app::library_helpers::{
+    album_query, album_tracks, albums, directory_items, fetch_album_tracks, fetch_directory_items,
+};
+#[cfg(feature = "mpd")]
+use crate::app::smart_playlists::{self, SmartPlaylist};
 #[cfg(feature = "mpd")]
 use crate::app::with_mpd;
 use crate::app::{self, App};
+use crate::t;
 use crossterm::event::KeyEvent;
-#[cfg(feature = "mpd")]
-use lofty::file::TaggedFileExt;
-#[cfg(feature = "mpd")]
-use lofty::tag::Accessor;
 
-pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
+pub async fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
     let keys = &app.keys;
 
     if app.view_mode != app::ViewMode::Library {
         return false;
     }
+    let pending_g = std::mem::take(&mut app.pending_g);
 
     // Queue Reordering with J/K (Shift+j/k)
     if keys.matches(key, &keys.move_down) && app.library_mode == app::LibraryMode::Queue {
@@ -25,14 +27,25 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
             if !args.controller {
                 let current_pos = app.library_selected as u32;
                 let new_pos = current_pos + 1;
+                let file = app
+                    .queue
+                    .get(app.library_selected)
+                    .map(|q| q.file_path.clone())
+                    .unwrap_or_default();
 
-                let success = with_mpd(app, args, |mpd| {
+                let success = with_mpd(app, args, move |mpd| {
                     mpd.shift(current_pos, new_pos as usize).is_ok()
                 })
+                .await
                 .unwrap_or(false);
 
                 if success {
                     app.library_selected = new_pos as usize;
+                    app.journal.record(app::Operation::QueueMove {
+                        from: current_pos,
+                        to: new_pos,
+                        file,
+                    });
                 }
             }
         }
@@ -46,14 +59,25 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
             if !args.controller {
                 let current_pos = app.library_selected as u32;
                 let new_pos = current_pos - 1;
+                let file = app
+                    .queue
+                    .get(app.library_selected)
+                    .map(|q| q.file_path.clone())
+                    .unwrap_or_default();
 
-                let success = with_mpd(app, args, |mpd| {
+                let success = with_mpd(app, args, move |mpd| {
                     mpd.shift(current_pos, new_pos as usize).is_ok()
                 })
+                .await
                 .unwrap_or(false);
 
                 if success {
                     app.library_selected = new_pos as usize;
+                    app.journal.record(app::Operation::QueueMove {
+                        from: current_pos,
+                        to: new_pos,
+                        file,
+                    });
                 }
             }
         }
@@ -64,28 +88,37 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
     if keys.matches(key, &keys.tab_next) {
         app.library_mode = match app.library_mode {
             app::LibraryMode::Queue => app::LibraryMode::Directory,
-            app::LibraryMode::Directory => app::LibraryMode::Playlists,
+            app::LibraryMode::Directory => app::LibraryMode::Albums,
+            app::LibraryMode::Albums => app::LibraryMode::Playlists,
             app::LibraryMode::Search => app::LibraryMode::Playlists,
-            app::LibraryMode::Playlists => app::LibraryMode::Queue,
+            // Bandcamp only once signed in
+            app::LibraryMode::Playlists if app.bandcamp_identity.is_some() => {
+                app::LibraryMode::Bandcamp
+            }
+            app::LibraryMode::Playlists | app::LibraryMode::Bandcamp => app::LibraryMode::Queue,
         };
         app.library_selected = 0;
         app.library_items.clear();
         app.browse_path.clear();
+        app.album_open = None;
         app.search_query.clear();
         app.search_active = false;
+        #[cfg(feature = "mpd")]
+        load_albums(app, args).await;
 
         #[cfg(feature = "mpd")]
         if app.library_mode == app::LibraryMode::Playlists && !args.controller {
-            if let Some(playlists) = with_mpd(app, args, |mpd| mpd.playlists().ok()).flatten() {
-                app.playlists = playlists.iter().map(|p| p.name.clone()).collect();
+            if let Some(playlists) = with_mpd(app, args, move |mpd| mpd.playlists().ok())
+                .await
+                .flatten()
+            {
+                app.set_playlists(playlists.into_iter().map(|p| p.name));
             }
         }
         #[cfg(feature = "mpd")]
         #[cfg(feature = "mpd")]
         if app.library_mode == app::LibraryMode::Directory && !args.controller {
-            if let Some(Some(items)) =
-                with_mpd(app, args, |mpd| fetch_directory_items(mpd, "").ok())
-            {
+            if let Some(items) = directory_items(app, args, "").await {
                 app.library_items = items;
             }
         }
@@ -94,20 +127,30 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
 
     if keys.matches(key, &keys.tab_prev) {
         app.library_mode = match app.library_mode {
-            app::LibraryMode::Queue => app::LibraryMode::Playlists,
+            app::LibraryMode::Queue if app.bandcamp_identity.is_some() => {
+                app::LibraryMode::Bandcamp
+            }
+            app::LibraryMode::Queue | app::LibraryMode::Bandcamp => app::LibraryMode::Playlists,
             app::LibraryMode::Directory => app::LibraryMode::Queue,
+            app::LibraryMode::Albums => app::LibraryMode::Directory,
             app::LibraryMode::Search => app::LibraryMode::Directory,
-            app::LibraryMode::Playlists => app::LibraryMode::Directory,
+            app::LibraryMode::Playlists => app::LibraryMode::Albums,
         };
         app.library_selected = 0;
         app.library_items.clear();
         app.browse_path.clear();
+        app.album_open = None;
         app.search_query.clear();
+        #[cfg(feature = "mpd")]
+        load_albums(app, args).await;
 
         #[cfg(feature = "mpd")]
         if app.library_mode == app::LibraryMode::Playlists && !args.controller {
-            if let Some(playlists) = with_mpd(app, args, |mpd| mpd.playlists().ok()).flatten() {
-                app.playlists = playlists.iter().map(|p| p.name.clone()).collect();
+            if let Some(playlists) = with_mpd(app, args, move |mpd| mpd.playlists().ok())
+                .await
+                .flatten()
+            {
+                app.set_playlists(playlists.into_iter().map(|p| p.name));
             }
         }
         return true;
@@ -116,18 +159,21 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
     if keys.matches(key, &keys.save_playlist) {
         app.input_state = Some(app::InputState::new(
             app::InputMode::PlaylistSave,
-            "Save Playlist As:",
+            &t!("input.save_playlist"),
             "",
         ));
         return true;
     }
 
     if keys.matches(key, &keys.rename_playlist) && app.library_mode == app::LibraryMode::Playlists {
+        if app.party_locked() {
+            return true;
+        }
         if !app.playlists.is_empty() {
             if let Some(pl_name) = app.playlists.get(app.library_selected) {
                 app.input_state = Some(app::InputState::new(
                     app::InputMode::PlaylistRename(pl_name.clone()),
-                    "Rename Playlist",
+                    &t!("input.rename_playlist"),
                     pl_name,
                 ));
             }
@@ -135,64 +181,79 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
         return true;
     }
 
+    if keys.matches(key, &keys.import_playlist) && !args.controller {
+        app.input_state = Some(app::InputState::new(
+            app::InputMode::PlaylistImport,
+            &t!("input.import_playlist"),
+            "~/",
+        ));
+        return true;
+    }
+
+    if keys.matches(key, &keys.export_playlist)
+        && app.library_mode == app::LibraryMode::Playlists
+        && !args.controller
+    {
+        if let Some(pl_name) = app.playlists.get(app.library_selected) {
+            let path = format!(
+                "{}/{}.m3u8",
+                app.music_directory.trim_end_matches('/'),
+                pl_name.replace('/', "_")
+            );
+            app.input_state = Some(app::InputState::new(
+                app::InputMode::PlaylistExport(pl_name.clone()),
+                &t!("input.export_playlist", name = pl_name),
+                &path,
+            ));
+        }
+        return true;
+    }
+
     if keys.matches(key, &keys.edit_tags) {
-        match app.library_mode {
-            app::LibraryMode::Queue => {
-                if let Some(item) = app.queue.get(app.library_selected) {
-                    let mut album = String::new();
-                    #[cfg(feature = "mpd")]
-                    if !args.controller {
-                        let full_path = format!("{}/{}", app.music_directory, item.file_path);
-                        if let Ok(tagged_file) = lofty::read_from_path(&full_path) {
-                            if let Some(tag) = tagged_file
-                                .primary_tag()
-                                .or_else(|| tagged_file.first_tag())
-                            {
-                                album = tag.album().as_deref().unwrap_or("").to_string();
-                            }
-                        }
-                    }
-                    app.tag_edit = Some(app::TagEditState::new(
-                        &item.file_path,
-                        &item.title,
-                        &item.artist,
-                        &album,
-                    ));
-                }
+        if app.party_locked() {
+            return true;
+        }
+        #[cfg(feature = "mpd")]
+        if !args.controller {
+            open_tag_editor(app, args).await;
+        }
+        return true;
+    }
+
+    if keys.matches(key, &keys.mark) {
+        let path = match app.library_mode {
+            app::LibraryMode::Queue => app
+                .queue
+                .get(app.library_selected)
+                .map(|q| q.file_path.clone()),
+            app::LibraryMode::Playlists | app::LibraryMode::Bandcamp => None,
+            _ => app
+                .library_items
+                .get(app.library_selected)
+                .filter(|item| item.item_type == app::LibraryItemType::Song)
+                .and_then(|item| item.path.clone()),
+        };
+        if let Some(path) = path {
+            app.toggle_mark(&path);
+            let len = app.library_len();
+            if app.library_selected + 1 < len {
+                app.library_selected += 1;
             }
-            app::LibraryMode::Directory => {
-                if let Some(item) = app.library_items.get(app.library_selected) {
-                    if item.item_type == app::LibraryItemType::Song {
-                        if let Some(path) = &item.path {
-                            let mut album = String::new();
-                            #[cfg(feature = "mpd")]
-                            if !args.controller {
-                                let full_path = format!("{}/{}", app.music_directory, path);
-                                if let Ok(tagged_file) = lofty::read_from_path(&full_path) {
-                                    if let Some(tag) = tagged_file
-                                        .primary_tag()
-                                        .or_else(|| tagged_file.first_tag())
-                                    {
-                                        album = tag.album().as_deref().unwrap_or("").to_string();
-                                    }
-                                }
-                            }
-                            app.tag_edit = Some(app::TagEditState::new(
-                                path,
-                                &item.name,
-                                item.artist.as_deref().unwrap_or(""),
-                                &album,
-                            ));
-                        }
-                    }
-                }
+            if app.marked.is_empty() {
+                app.show_toast(&t!("toast.marks_cleared"));
+            } else {
+                let key = app.keys.display(&app.keys.edit_tags);
+                let msg = t!("toast.marked", count = app.marked.len(), key = key);
+                app.show_toast(&msg);
             }
-            _ => {}
         }
         return true;
     }
 
     if keys.matches(key, &keys.delete_item) {
+        if app.party_locked() {
+            return true;
+        }
         #[cfg(feature = "mpd")]
         if !args.controller {
             use crate::app::LibraryMode;
@@ -203,34 +264,70 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
             } else {
                 None
             };
+            // Deleting would only drop the songs until the next load
+            if let Some(smart) = pl_name.as_deref().and_then(|name| app.smart_playlist(name)) {
+                let msg = t!("smart.defined_in_config", name = smart.name);
+                app.show_warning(&msg);
+                return true;
+            }
+            let queue_file = app.queue.get(selected).map(|q| q.file_path.clone());
 
-            let (new_playlists, success) = with_mpd(app, args, |mpd| {
+            let (new_playlists, done) = with_mpd(app, args, move |mpd| {
                 match mode {
-                    LibraryMode::Queue => {
-                        let ok = mpd.delete(selected as u32).is_ok();
-                        (None, ok)
-                    }
+                    LibraryMode::Queue => match queue_file {
+                        Some(file) if mpd.delete(selected as u32).is_ok() => (
+                            None,
+                            Ok(Some(app::Operation::QueueDelete {
+                                pos: selected as u32,
+                                file,
+                            })),
+                        ),
+                        _ => (None, Ok(None)),
+                    },
                     LibraryMode::Playlists => {
                         if let Some(name) = pl_name {
-                            let ok = mpd.pl_remove(&name).is_ok();
-                            (mpd.playlists().ok(), ok) // Return new playlists and success status
+                            // Keep the songs so the delete can be undone; a
+                            // playlist that can't be read isn't deleted
+                            let files: Vec<String> = match mpd.playlist(&name) {
+                                Ok(songs) => songs.into_iter().map(|s| s.file).collect(),
+                                Err(e) => return (None, Err(e.to_string())),
+                            };
+                            let op = mpd
+                                .pl_remove(&name)
+                                .is_ok()
+                                .then_some(app::Operation::PlaylistDelete { name, files });
+                            (mpd.playlists().ok(), Ok(op)) // Return new playlists and the edit
                         } else {
-                            (None, false)
+                            (None, Ok(None))
                         }
                     }
-                    _ => (None, false),
+                    _ => (None, Ok(None)),
                 }
             })
-            .unwrap_or((None, false)); // Default to failure if MPD connection fails
+            .await
+            .unwrap_or((None, Ok(None))); // Default to failure if MPD connection fails
 
             if let Some(pls) = new_playlists {
-                app.playlists = pls.iter().map(|p| p.name.clone()).collect();
+                app.set_playlists(pls.into_iter().map(|p| p.name));
             }
 
-            if success {
+            let done = match done {
+                Ok(op) => op,
+                Err(e) => {
+                    app.show_error(&t!("error.playlist_unreadable", error = e));
+                    None
+                }
+            };
+            if let Some(op) = done {
+                app.journal.record(op);
+                let undo = app.keys.display(&app.keys.undo);
                 match mode {
-                    LibraryMode::Queue => app.show_toast("🗑️ Removed from Queue"),
-                    LibraryMode::Playlists => app.show_toast("🗑️ Playlist Deleted"),
+                    LibraryMode::Queue => {
+                        app.show_toast(&t!("toast.removed_from_queue", key = undo))
+                    }
+                    LibraryMode::Playlists => {
+                        app.show_toast(&t!("toast.playlist_deleted", key = undo))
+                    }
                     _ => {}
                 }
             }
@@ -245,8 +342,69 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
         return true;
     }
 
+    if keys.matches(key, &keys.jump_to_playing) {
+        jump_to_playing(app, args).await;
+        return true;
+    }
+    if keys.matches(key, &keys.fold_album) && app.library_mode == app::LibraryMode::Queue {
+        if !app.toggle_queue_fold() {
+            app.show_toast(&t!("toast.not_in_album"));
+        }
+        return true;
+    }
+    if keys.matches(key, &keys.follow_playback) {
+        app.follow_playback = !app.follow_playback;
+        app.show_toast(&t!(
+            "toast.follow_playback",
+            state = super::player::on_off(app.follow_playback)
+        ));
+        if app.follow_playback && app.library_mode == app::LibraryMode::Queue {
+            jump_to_playing(app, args).await;
+        }
+        return true;
+    }
+
+    // Album grid: arrows move between tiles, up/down a whole row
+    if app.library_mode == app::LibraryMode::Albums && app.album_open.is_none() {
+        let cols = app.album_cols.max(1) as isize;
+        let step = if keys.matches(key, &keys.nav_left) || keys.matches(key, &keys.nav_left_alt) {
+            -1
+        } else if keys.matches(key, &keys.nav_right) || keys.matches(key, &keys.nav_right_alt) {
+            1
+        } else if keys.matches(key, &keys.nav_up) || keys.matches(key, &keys.nav_up_alt) {
+            -cols
+        } else if keys.matches(key, &keys.nav_down) || keys.matches(key, &keys.nav_down_alt) {
+            cols
+        } else {
+            0
+        };
+        if step != 0 {
+            app.move_library_selection(step);
+            return true;
+        }
+
+        if keys.matches(key, &keys.add_to_queue) {
+            #[cfg(feature = "mpd")]
+            if !args.controller {
+                if let Some(album) = app.albums.get(app.library_selected).cloned() {
+                    let key = album.key();
+                    let added = with_mpd(app, args, move |mpd| {
+                        mpd.findadd(&album_query(&key)).is_ok()
+                    })
+                    .await
+                    .unwrap_or(false);
+                    if added {
+                        app.show_toast(&t!("toast.added", name = album.name));
+                    }
+                }
+            }
+            return true;
+        }
+    }
+
     if keys.matches(key, &keys.add_to_queue)
         && (app.library_mode == app::LibraryMode::Directory
+            || app.library_mode == app::LibraryMode::Albums
             || app.library_mode == app::LibraryMode::Search)
     {
         #[cfg(feature = "mpd")]
@@ -254,7 +412,7 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
             let item = app.library_items.get(app.library_selected).cloned();
 
             if let Some(target_item) = item {
-                let (result, shuffle_on) = with_mpd(app, args, |mpd| {
+                let (result, shuffle_on) = with_mpd(app, args, move |mpd| {
                     let mut added_name = target_item.name.clone();
                     let added = match target_item.item_type {
                         app::LibraryItemType::Song => {
@@ -302,11 +460,16 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
                     };
                     (if added { Some(added_name) } else { None }, shuffle)
                 })
+                .await
                 .unwrap_or((None, false)); // Default to no add false shuffle if connection failed
 
                 if let Some(added_name) = result {
-                    let shuffle_msg = if shuffle_on { " (Shuffle ON)" } else { "" };
-                    app.show_toast(&format!("Added: {}{}", added_name, shuffle_msg));
+                    let msg = if shuffle_on {
+                        t!("toast.added_shuffled", name = added_name)
+                    } else {
+                        t!("toast.added", name = added_name)
+                    };
+                    app.show_toast(&msg);
                 }
             }
         }
@@ -329,9 +492,24 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
             } else {
                 None
             };
+            // Enter on a grid tile lists that album's tracks
+            let album_name = if mode == app::LibraryMode::Albums && app.album_open.is_none() {
+                app.albums
+                    .get(app.library_selected)
+                    .map(app::AlbumEntry::key)
+            } else {
+                None
+            };
+
+            if let Some(smart) = pl_name.as_deref().and_then(|name| app.smart_playlist(name)) {
+                let smart = smart.clone();
+                refresh_smart_playlist(app, args, &smart).await;
+            }
 
             let item_clone = item.clone();
-            let result_items = with_mpd(app, args, |mpd| match mode {
+            let cache = app.library_cache.clone();
+            let open_album = album_name.clone();
+            let result_items = with_mpd(app, args, move |mpd| match mode {
                 app::LibraryMode::Queue => {
                     if let Some(idx) = queue_idx {
                         if let Err(e) = mpd.switch(idx) {
@@ -345,7 +523,10 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
                         match target.item_type {
                             app::LibraryItemType::Folder => {
                                 if let Some(path) = &target.path {
-                                    return fetch_directory_items(mpd, path).ok();
+                                    return match &cache {
+                                        Some(cache) => Some(cache.directory(path)),
+                                        None => fetch_directory_items(mpd, path).ok(),
+                                    };
                                 }
                             }
                             app::LibraryItemType::Song => {
@@ -368,7 +549,13 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
                     }
                     None
                 }
-                app::LibraryMode::Search => {
+                app::LibraryMode::Albums if open_album.is_some() => {
+                    open_album.as_ref().and_then(|name| match &cache {
+                        Some(cache) => Some(cache.album_tracks(name)),
+                        None => fetch_album_tracks(mpd, name).ok(),
+                    })
+                }
+                app::LibraryMode::Search | app::LibraryMode::Albums => {
                     if let Some(target) = item_clone {
                         if let Some(path) = &target.path {
                             let song = mpd::Song {
@@ -392,7 +579,16 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
                     }
                     None
                 }
-            });
+                // Played from `inputs::bandcamp`
+                app::LibraryMode::Bandcamp => None,
+            })
+            .await;
+
+            if let (Some(name), Some(Some(items))) = (&album_name, &result_items) {
+                app.album_open = Some(name.clone());
+                app.library_items = items.clone();
+                app.library_selected = 0;
+            }
 
             // Post-processing for Directory change
             if mode == app::LibraryMode::Directory {
@@ -430,10 +626,12 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
         if !args.controller {
             let current_path = app.browse_path.join("/");
 
-            let (new_items, new_playlists) = with_mpd(app, args, |mpd| match target_mode {
-                app::LibraryMode::Directory => {
-                    (fetch_directory_items(mpd, &current_path).ok(), None)
-                }
+            let cache = app.library_cache.clone();
+            let (new_items, new_playlists) = with_mpd(app, args, move |mpd| match target_mode {
+                app::LibraryMode::Directory => match &cache {
+                    Some(cache) => (Some(cache.directory(&current_path)), None),
+                    None => (fetch_directory_items(mpd, &current_path).ok(), None),
+                },
                 app::LibraryMode::Playlists => {
                     if let Ok(playlists) = mpd.playlists() {
                         let items = playlists
@@ -453,13 +651,14 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
                 }
                 _ => (None, None),
             })
+            .await
             .unwrap_or((None, None));
 
             if let Some(items) = new_items {
                 app.library_items = items;
             }
             if let Some(playlists) = new_playlists {
-                app.playlists = playlists.iter().map(|p| p.name.clone()).collect();
+                app.set_playlists(playlists.into_iter().map(|p| p.name));
             }
             if target_mode == app::LibraryMode::Queue {
                 app.library_items.clear();
@@ -468,6 +667,21 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
         return true;
     }
 
+    // Back from an album's tracks to its tile
+    if (keys.matches(key, &keys.back_dir) || keys.matches(key, &keys.back_dir_alt))
+        && app.library_mode == app::LibraryMode::Albums
+        && app.album_open.is_some()
+    {
+        let name = app.album_open.take();
+        app.library_items.clear();
+        app.library_selected = app
+            .albums
+            .iter()
+            .position(|a| Some(a.key()) == name)
+            .unwrap_or(0);
+        return true;
+    }
+
     // Backspace Browser
     if (keys.matches(key, &keys.back_dir) || keys.matches(key, &keys.back_dir_alt))
         && app.library_mode == app::LibraryMode::Directory
@@ -486,11 +700,7 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
                 app.browse_path.join("/")
             };
 
-            if let Some(items) = with_mpd(app, args, |mpd| {
-                fetch_directory_items(mpd, &parent_path).ok()
-            })
-            .flatten()
-            {
+            if let Some(items) = directory_items(app, args, &parent_path).await {
                 app.library_items = items;
             }
         }
@@ -499,21 +709,412 @@ pub fn handle_library_events(key: KeyEvent, app: &mut App, args: &Args) -> bool
 
     // Navigation
     if keys.matches(key, &keys.nav_up) || keys.matches(key, &keys.nav_up_alt) {
-        app.library_selected = app.library_selected.saturating_sub(1);
+        app.move_library_selection(-1);
         return true;
     }
     if keys.matches(key, &keys.nav_down) || keys.matches(key, &keys.nav_down_alt) {
-        let max_items = match app.library_mode {
-            app::LibraryMode::Queue => app.queue.len().max(1),
-            // Fix: No hardcoded check
-            app::LibraryMode::Playlists => app.playlists.len().max(1),
-            _ => app.library_items.len().max(1),
-        };
-        if app.library_selected < max_items.saturating_sub(1) {
-            app.library_selected += 1;
+        app.move_library_selection(1);
+        return true;
+    }
+    if keys.matches(key, &keys.page_up) {
+        app.move_library_selection(-(app.library_page.max(1) as isize));
+        return true;
+    }
+    if keys.matches(key, &keys.page_down) {
+        app.move_library_selection(app.library_page.max(1) as isize);
+        return true;
+    }
+    if keys.matches(key, &keys.jump_top) {
+        // Vim style: a lone g waits for the second one
+        if pending_g {
+            app.library_selected = 0;
+        } else {
+            app.pending_g = true;
         }
         return true;
     }
+    if keys.matches(key, &keys.jump_bottom) {
+        app.library_selected = app.library_len().saturating_sub(1);
+        return true;
+    }
 
     false
 }
+
+/// `P`: queue the songs of an M3U, PLS or XSPF file
+#[cfg(feature = "mpd")]
+pub(super) async fn import_playlist_file(app: &mut App, args: &Args, input: &str) {
+    use crate::app::playlist_files;
+
+    let path = playlist_files::expand(input);
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| input.to_string());
+    let files = match playlist_files::read(&path, std::path::Path::new(&app.music_directory)) {
+        Ok(files) if files.is_empty() => {
+            app.show_warning(&t!("toast.playlist_file_empty", name = name));
+            return;
+        }
+        Ok(files) => files,
+        Err(e) => {
+            app.show_error(&t!("error.generic", error = e));
+            return;
+        }
+    };
+    let total = files.len();
+    let added = with_mpd(app, args, move |mpd| {
+        files
+            .into_iter()
+            .filter(|file| {
+                let song = mpd::Song {
+                    file: file.clone(),
+                    ..Default::default()
+                };
+                mpd.push(&song).is_ok()
+            })
+            .count()
+    })
+    .await;
+    match added {
+        Some(added) if added == total => {
+            app.show_toast(&t!("toast.playlist_imported", name = name, count = added));
+        }
+        Some(added) => app.show_warning(&t!(
+            "toast.playlist_imported_missing",
+            name = name,
+            count = added,
+            missing = total - added
+        )),
+        None => {}
+    }
+}
+
+/// `E`: write a saved playlist out as M3U8
+#[cfg(feature = "mpd")]
+pub(super) async fn export_playlist_file(app: &mut App, args: &Args, name: String, input: &str) {
+    use crate::app::playlist_files::{self, Entry};
+
+    let path = playlist_files::expand(input);
+    let songs = with_mpd(app, args, move |mpd| {
+        mpd.playlist(&name).map(|songs| {
+            songs
+                .into_iter()
+                .map(|song| Entry {
+                    file: song.file,
+                    artist: song.artist,
+                    title: song.title,
+                    secs: song.duration.map(|d| d.as_secs()),
+                })
+                .collect::<Vec<_>>()
+        })
+    })
+    .await;
+    let entries = match songs {
+        Some(Ok(entries)) => entries,
+        Some(Err(e)) => {
+            app.show_error(&t!("error.generic", error = e));
+            return;
+        }
+        None => return,
+    };
+    let dir = path.parent().unwrap_or(std::path::Path::new("."));
+    let text = playlist_files::to_m3u8(
+        &entries,
+        app.playlist_paths,
+        std::path::Path::new(&app.music_directory),
+        &std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf()),
+    );
+    match std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&path, text)) {
+        Ok(()) => app.show_toast(&t!(
+            "toast.playlist_exported",
+            count = entries.len(),
+            path = path.display()
+        )),
+        Err(e) => app.show_error(&t!("error.generic", error = e)),
+    }
+}
+
+/// `t`: the songs marked with `v` together, the album under the cursor in
+/// the grid, or else the selected song
+#[cfg(feature = "mpd")]
+async fn open_tag_editor(app: &mut App, args: &Args) {
+    use crate::app::{tags, TagEditState, TagField, TagValue};
+
+    // MPD's idea of the title and artist, for files without tags
+    let mut known = (String::new(), String::new());
+    let files: Vec<String> = if !app.marked.is_empty() {
+        app.marked.clone()
+    } else if app.library_mode == app::LibraryMode::Albums && app.album_open.is_none() {
+        let Some(album) = app
+            .albums
+            .get(app.library_selected)
+            .map(app::AlbumEntry::key)
+        else {
+            return;
+        };
+        album_tracks(app, args, &album)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|item| item.path)
+            .collect()
+    } else if app.library_mode == app::LibraryMode::Queue {
+        app.queue
+            .get(app.library_selected)
+            .map(|q| {
+                known = (q.title.clone(), q.artist.clone());
+                q.file_path.clone()
+            })
+            .into_iter()
+            .collect()
+    } else {
+        app.library_items
+            .get(app.library_selected)
+            .filter(|item| item.item_type == app::LibraryItemType::Song)
+            .and_then(|item| {
+                known = (item.name.clone(), item.artist.clone().unwrap_or_default());
+                item.path.clone()
+            })
+            .into_iter()
+            .collect()
+    };
+
+    // CUE tracks have no file of their own to write to
+    if files.iter().any(|f| app::cue_sheet(f).is_some()) {
+        app.show_toast(&t!("toast.cue_tags"));
+        return;
+    }
+
+    let music_dir = std::path::Path::new(&app.music_directory);
+    app.tag_edit = match files.as_slice() {
+        [] => None,
+        [file] => {
+            let values = tags::read(&music_dir.join(file), &TagEditState::SINGLE);
+            let fields = TagEditState::SINGLE
+                .iter()
+                .zip(values)
+                .map(|(&field, value)| {
+                    let value = value.or_else(|| match field {
+                        TagField::Title => Some(known.0.clone()),
+                        TagField::Artist => Some(known.1.clone()),
+                        _ => None,
+                    });
+                    TagValue::shared(field, &[value])
+                })
+                .collect();
+            Some(TagEditState::with_fields(files.clone(), fields))
+        }
+        _ => {
+            let read: Vec<Vec<Option<String>>> = files
+                .iter()
+                .map(|file| tags::read(&music_dir.join(file), &TagEditState::BATCH))
+                .collect();
+            let fields = TagEditState::BATCH
+                .iter()
+                .enumerate()
+                .map(|(i, &field)| {
+                    let values: Vec<Option<String>> = read.iter().map(|r| r[i].clone()).collect();
+                    TagValue::shared(field, &values)
+                })
+                .collect();
+            Some(TagEditState::with_fields(files, fields))
+        }
+    };
+}
+
+/// Rebuild a smart playlist from its rule, right before it is loaded
+#[cfg(feature = "mpd")]
+async fn refresh_smart_playlist(app: &mut App, args: &Args, smart: &SmartPlaylist) {
+    let rule = smart.clone();
+    match with_mpd(app, args, move |mpd| {
+        smart_playlists::materialize(mpd, &rule)
+    })
+    .await
+    {
+        Some(Ok(0)) => app.show_warning(&t!("smart.no_matches", name = smart.name)),
+        Some(Ok(count)) => app.show_toast(&t!("smart.refreshed", name = smart.name, count = count)),
+        Some(Err(e)) => app.show_error(&t!("error.generic", error = e)),
+        None => {}
+    }
+}
+
+/// Refill the library lists after a restored session (`App::restore_session`).
+/// A folder or album that is gone falls back to the top of its tab.
+#[cfg(feature = "mpd")]
+pub async fn restore_library(app: &mut App, args: &Args) {
+    if args.controller {
+        return;
+    }
+    match app.library_mode {
+        app::LibraryMode::Queue | app::LibraryMode::Bandcamp => {}
+        app::LibraryMode::Directory => {
+            let path = app.browse_path.join("/");
+            let items = directory_items(app, args, &path).await;
+            match items {
+                Some(items) => app.library_items = items,
+                None => {
+                    app.browse_path.clear();
+                    app.library_selected = 0;
+                    if let Some(items) = directory_items(app, args, "").await {
+                        app.library_items = items;
+                    }
+                }
+            }
+        }
+        app::LibraryMode::Albums => {
+            load_albums(app, args).await;
+            if let Some(name) = app.album_open.clone() {
+                match album_tracks(app, args, &name).await {
+                    Some(items) if !items.is_empty() => app.library_items = items,
+                    _ => {
+                        app.album_open = None;
+                        app.library_selected = 0;
+                    }
+                }
+            }
+        }
+        app::LibraryMode::Search => {
+            super::input_box::run_search(app, args).await;
+        }
+        app::LibraryMode::Playlists => {
+            if let Some(playlists) = with_mpd(app, args, move |mpd| mpd.playlists().ok())
+                .await
+                .flatten()
+            {
+                app.set_playlists(playlists.into_iter().map(|p| p.name));
+            }
+        }
+    }
+
+    // The queue arrives later from its poll, its cursor stays as saved
+    let len = match app.library_mode {
+        app::LibraryMode::Queue | app::LibraryMode::Bandcamp => return,
+        app::LibraryMode::Albums if app.album_open.is_none() => app.albums.len(),
+        app::LibraryMode::Playlists => app.playlists.len(),
+        _ => app.library_items.len(),
+    };
+    app.library_selected = app.library_selected.min(len.saturating_sub(1));
+}
+
+/// Undo (`u`) or redo the last queue / playlist edit through MPD
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+pub async fn undo_redo(app: &mut App, args: &Args, undo: bool) {
+    // Both replay edits through MPD, which party mode is there to prevent
+    if app.party_locked() {
+        return;
+    }
+    let op = if undo {
+        app.journal.take_undo()
+    } else {
+        app.journal.take_redo()
+    };
+    let Some(op) = op else {
+        app.show_toast(&if undo {
+            t!("toast.nothing_to_undo")
+        } else {
+            t!("toast.nothing_to_redo")
+        });
+        return;
+    };
+
+    #[cfg(feature = "mpd")]
+    {
+        use crate::app::library_helpers::replay;
+        let step = op.clone();
+        let result = with_mpd(app, args, move |mpd| {
+            let result = replay(mpd, &step, undo);
+            let playlists = mpd.playlists().ok();
+            (result, playlists)
+        })
+        .await;
+        let result = match result {
+            Some((result, playlists)) => {
+                if let Some(playlists) = playlists {
+                    app.set_playlists(playlists.into_iter().map(|p| p.name));
+                }
+                result
+            }
+            None => Err(t!("remote.no_mpd")),
+        };
+        match result {
+            Ok(()) => {
+                let action = op.describe();
+                if undo {
+                    app.show_toast(&t!("toast.undone", action = action));
+                    app.journal.undone(op);
+                } else {
+                    app.show_toast(&t!("toast.redone", action = action));
+                    app.journal.redone(op);
+                }
+            }
+            // Dropped: it would fail the same way next time
+            Err(e) => app.show_error(&t!("error.undo_failed", error = e)),
+        }
+    }
+}
+
+/// Fill the cover grid when its tab is entered
+#[cfg(feature = "mpd")]
+async fn load_albums(app: &mut App, args: &Args) {
+    if app.library_mode != app::LibraryMode::Albums || args.controller {
+        return;
+    }
+    if let Some(albums) = albums(app, args).await {
+        app.albums = albums;
+    }
+}
+
+/// Put the cursor on the playing track: in the queue, its folder, or its album
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+async fn jump_to_playing(app: &mut App, args: &Args) {
+    if app.track.is_none() {
+        app.show_toast(&t!("toast.nothing_playing"));
+        return;
+    }
+
+    match app.library_mode {
+        #[cfg(feature = "mpd")]
+        app::LibraryMode::Directory if !args.controller => {
+            let Some(file) = app.playing_file().map(str::to_string) else {
+                return;
+            };
+            let dir = file
+                .rsplit_once('/')
+                .map(|(dir, _)| dir.to_string())
+                .unwrap_or_default();
+            if let Some(items) = directory_items(app, args, &dir).await {
+                app.browse_path = dir
+                    .split('/')
+                    .filter(|c| !c.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                app.library_selected = items
+                    .iter()
+                    .position(|i| i.path.as_deref() == Some(file.as_str()))
+                    .unwrap_or(0);
+                app.library_items = items;
+            }
+        }
+        app::LibraryMode::Albums => {
+            let album = app.track.as_ref().map(|t| t.album.clone());
+            if let Some(idx) = app
+                .albums
+                .iter()
+                .position(|a| Some(&a.name) == album.as_ref())
+            {
+                app.album_open = None;
+                app.library_items.clear();
+                app.library_selected = idx;
+            }
+        }
+        _ => {
+            app.library_mode = app::LibraryMode::Queue;
+            app.library_items.clear();
+            if let Some(idx) = app.playing_queue_index() {
+                app.library_selected = idx;
+            }
+        }
+    }
+    app.center_library_selection();
+}
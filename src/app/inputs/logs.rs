@@ -0,0 +1,33 @@
+use crate::app::{App, LogView};
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Log viewer (`F12`). Captures every key while open.
+pub fn handle_log_view(key: KeyEvent, app: &mut App) -> bool {
+    let Some(view) = app.log_view.as_mut() else {
+        if app.keys.matches(key, &app.keys.log_view) {
+            app.log_view = Some(LogView::default());
+            return true;
+        }
+        return false;
+    };
+
+    let last = crate::logging::recent(view.min_level)
+        .len()
+        .saturating_sub(1);
+    let at = view.scroll.unwrap_or(last);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::F(12) => app.log_view = None,
+        KeyCode::Down | KeyCode::Char('j') => {
+            // Scrolling past the end follows new lines again
+            view.scroll = (at < last).then_some(at + 1);
+        }
+        KeyCode::Up | KeyCode::Char('k') => view.scroll = Some(at.saturating_sub(1)),
+        KeyCode::PageDown => view.scroll = (at + 10 < last).then_some(at + 10),
+        KeyCode::PageUp => view.scroll = Some(at.saturating_sub(10)),
+        KeyCode::Char('g') | KeyCode::Home => view.scroll = Some(0),
+        KeyCode::Char('G') | KeyCode::End => view.scroll = None,
+        KeyCode::Char('f') | KeyCode::Tab => view.cycle_level(),
+        _ => {}
+    }
+    true
+}
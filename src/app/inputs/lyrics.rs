@@ -1,6 +1,10 @@
-use crate::app::{self, App, LyricsState};
+use crate::app::lyrics::{plain_rows, LyricsFetcher};
+use crate::app::{
+    self, App, InputMode, InputState, LyricsEditor, LyricsLookup, LyricsSearch, LyricsState,
+};
 use crate::player::PlayerTrait;
-use crossterm::event::KeyEvent;
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
 use std::sync::Arc;
 
 pub async fn handle_lyrics_events(
@@ -14,11 +18,121 @@ pub async fn handle_lyrics_events(
         return false;
     }
 
+    if app.lyrics_editor.is_some() {
+        return handle_editor_events(key, app);
+    }
+
+    // Search the lyrics ('/'), then n/N between matches and Enter to seek
+    if keys.matches(key, &keys.search_global) {
+        if matches!(app.lyrics, LyricsState::Loaded(..) | LyricsState::Plain(..)) {
+            app.input_state = Some(InputState::new(
+                InputMode::LyricsSearch,
+                &t!("input.search_lyrics"),
+                "",
+            ));
+        }
+        return true;
+    }
+    if app.lyrics_search.is_some() {
+        if key.code == KeyCode::Esc {
+            app.lyrics_search = None;
+            return true;
+        }
+        let step = if keys.matches(key, &keys.search_next) {
+            app.lyrics_search.as_mut().and_then(|s| s.next_match())
+        } else if keys.matches(key, &keys.search_prev) {
+            app.lyrics_search.as_mut().and_then(|s| s.prev_match())
+        } else {
+            None
+        };
+        if let Some(line) = step {
+            jump_to_line(app, line);
+            return true;
+        }
+    }
+
+    // Wrong or missing lyrics: search by hand and pick a result
+    if keys.matches(key, &keys.lyrics_lookup) {
+        if let Some(track) = &app.track {
+            let id = format!("{}{}", track.name, track.artist);
+            app.lyrics_lookup = Some(LyricsLookup::new(&id, &track.artist, &track.name));
+        }
+        return true;
+    }
+
+    if keys.matches(key, &keys.lyrics_romanize) {
+        app.show_romanization = !app.show_romanization;
+        let msg = t!(
+            "toast.romanization",
+            state = super::player::on_off(app.show_romanization)
+        );
+        app.show_toast(&msg);
+        return true;
+    }
+
+    if keys.matches(key, &keys.lyrics_edit) {
+        match LyricsEditor::from_state(&app.lyrics) {
+            Some(mut editor) => {
+                // Start on the line that is playing now
+                let pos = app.lyrics_position_ms();
+                editor.selected = editor
+                    .lines
+                    .iter()
+                    .rposition(|l| l.timestamp_ms > 0 && l.timestamp_ms <= pos)
+                    .unwrap_or(0);
+                app.lyrics_editor = Some(editor);
+                let k = &app.keys;
+                let hint = t!(
+                    "toast.lyrics_edit_hint",
+                    stamp = k.display(&k.lyrics_retime),
+                    edit = k.display(&k.seek_to_line),
+                    save = k.display(&k.lyrics_save)
+                );
+                app.show_toast(&hint);
+            }
+            None => app.show_toast(&t!("toast.no_lyrics_to_edit")),
+        }
+        return true;
+    }
+
+    // Sync offset: '<' shows lyrics earlier, '>' later (100ms steps, remembered per track)
+    let sync_step = if keys.matches(key, &keys.lyrics_sync_earlier) {
+        Some(-100)
+    } else if keys.matches(key, &keys.lyrics_sync_later) {
+        Some(100)
+    } else {
+        None
+    };
+    if let Some(step) = sync_step {
+        if app.track.is_some() {
+            let offset = app.shift_lyrics_sync(step);
+            app.show_toast(&t!(
+                "toast.lyrics_offset",
+                offset = format!("{:+.1}", offset as f64 / 1000.0)
+            ));
+        }
+        return true;
+    }
+
+    // Unsynced lyrics just scroll
+    if let LyricsState::Plain(ref lines, _) = app.lyrics {
+        if keys.matches(key, &keys.nav_down) || keys.matches(key, &keys.nav_down_alt) {
+            let rows = plain_rows(lines, app.show_romanization);
+            let max = rows.last().copied().unwrap_or(0) as u16;
+            app.lyrics_scroll = (app.lyrics_scroll + 1).min(max);
+            return true;
+        }
+        if keys.matches(key, &keys.nav_up) || keys.matches(key, &keys.nav_up_alt) {
+            app.lyrics_scroll = app.lyrics_scroll.saturating_sub(1);
+            return true;
+        }
+    }
+
     if keys.matches(key, &keys.nav_down) || keys.matches(key, &keys.nav_down_alt) {
         if let LyricsState::Loaded(ref lines, _) = &app.lyrics {
             let max = lines.len().saturating_sub(1);
             let current_playing = {
-                let track_ms = app.track.as_ref().map(|t| t.position_ms).unwrap_or(0);
+                let track_ms = app.lyrics_position_ms();
                 lines
                     .iter()
                     .position(|l| l.timestamp_ms > track_ms)
@@ -38,7 +152,7 @@ pub async fn handle_lyrics_events(
         if let LyricsState::Loaded(ref lines, _) = &app.lyrics {
             let max = lines.len().saturating_sub(1);
             let current_playing = {
-                let track_ms = app.track.as_ref().map(|t| t.position_ms).unwrap_or(0);
+                let track_ms = app.lyrics_position_ms();
                 lines
                     .iter()
                     .position(|l| l.timestamp_ms > track_ms)
@@ -58,7 +172,9 @@ pub async fn handle_lyrics_events(
         if let LyricsState::Loaded(ref lines, _) = &app.lyrics {
             if let Some(idx) = app.lyrics_selected {
                 if idx < lines.len() {
-                    let target_ms = lines[idx].timestamp_ms;
+                    // Undo the sync offset so the line lands where it is displayed
+                    let target_ms =
+                        (lines[idx].timestamp_ms as i64 + app.lyrics_sync_ms()).max(0) as u64;
                     let target_secs = target_ms as f64 / 1000.0;
                     let player_bg = player.clone();
                     tokio::task::spawn_blocking(move || {
@@ -68,7 +184,10 @@ pub async fn handle_lyrics_events(
                     });
                     let mins = target_ms / 60000;
                     let secs = (target_ms % 60000) / 1000;
-                    app.show_toast(&format!("🎤 Jump to {}:{:02}", mins, secs));
+                    app.show_toast(&t!(
+                        "toast.lyrics_jump",
+                        time = format!("{}:{:02}", mins, secs)
+                    ));
                     app.lyrics_selected = None;
                     app.lyrics_offset = None;
                     app.last_scroll_time = None;
@@ -80,3 +199,141 @@ pub async fn handle_lyrics_events(
 
     false
 }
+
+/// Keys while the lyrics editor is open. Anything unhandled falls through to
+/// the player controls, so play/pause and seeking still work while timing.
+fn handle_editor_events(key: KeyEvent, app: &mut App) -> bool {
+    let keys = app.keys.clone();
+    let Some(editor) = app.lyrics_editor.as_mut() else {
+        return false;
+    };
+
+    if key.code == KeyCode::Esc {
+        let dirty = editor.dirty;
+        app.lyrics_editor = None;
+        if dirty {
+            app.show_toast(&t!("toast.edits_discarded"));
+        }
+        return true;
+    }
+
+    if keys.matches(key, &keys.nav_down) || keys.matches(key, &keys.nav_down_alt) {
+        editor.select_next();
+        return true;
+    }
+    if keys.matches(key, &keys.nav_up) || keys.matches(key, &keys.nav_up_alt) {
+        editor.select_prev();
+        return true;
+    }
+
+    if keys.matches(key, &keys.lyrics_retime) {
+        let pos = app.lyrics_position_ms();
+        if let Some(editor) = app.lyrics_editor.as_mut() {
+            editor.retime(pos);
+        }
+        return true;
+    }
+
+    if keys.matches(key, &keys.seek_to_line) {
+        let (index, text) = (editor.selected, editor.selected_text().to_string());
+        app.input_state = Some(InputState::new(
+            InputMode::LyricsLine(index),
+            &t!("input.edit_line"),
+            &text,
+        ));
+        return true;
+    }
+
+    if keys.matches(key, &keys.lyrics_save) {
+        save_editor(app);
+        return true;
+    }
+
+    false
+}
+
+fn save_editor(app: &mut App) {
+    let (Some(editor), Some(track)) = (&app.lyrics_editor, &app.track) else {
+        return;
+    };
+    let lines = editor.finish();
+    let result = LyricsFetcher::save_local(
+        track.file_path.as_deref(),
+        app.lyrics_dir.as_deref(),
+        &track.artist,
+        &track.name,
+        &lines,
+    );
+    match result {
+        Ok(path) => {
+            // Same key the runner uses for the in-memory cache
+            let id = format!("{}{}", track.name, track.artist);
+            let state = LyricsState::Loaded(lines, "Local .lrc file".to_string());
+            app.lyrics_cache.insert(id, state.clone());
+            app.lyrics = state;
+            app.lyrics_editor = None;
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            app.show_toast(&t!("toast.lyrics_saved", file = name));
+        }
+        Err(e) => app.show_error(&t!("error.save_failed", error = e)),
+    }
+}
+
+/// Run a lyrics search (from the input popup) and jump to the first hit
+pub fn start_search(app: &mut App, query: &str) {
+    if query.is_empty() {
+        app.lyrics_search = None;
+        return;
+    }
+    let search = match &app.lyrics {
+        LyricsState::Loaded(lines, _) => {
+            // Start looking from the line on screen
+            let pos = app.lyrics_position_ms();
+            let from = app.lyrics_selected.unwrap_or_else(|| {
+                lines
+                    .iter()
+                    .position(|l| l.timestamp_ms > pos)
+                    .map(|i| i.saturating_sub(1))
+                    .unwrap_or(0)
+            });
+            LyricsSearch::new(query, lines.iter().map(|l| l.text.as_str()), from)
+        }
+        LyricsState::Plain(lines, _) => {
+            // The line at the top of the paragraph
+            let rows = plain_rows(lines, app.show_romanization);
+            let from = rows
+                .iter()
+                .rposition(|&row| row <= app.lyrics_scroll as usize)
+                .unwrap_or(0);
+            LyricsSearch::new(query, lines.iter().map(String::as_str), from)
+        }
+        _ => return,
+    };
+
+    match search.line() {
+        Some(line) => {
+            app.lyrics_search = Some(search);
+            jump_to_line(app, line);
+        }
+        None => {
+            app.lyrics_search = None;
+            app.show_toast(&t!("toast.no_lyrics_match", query = query));
+        }
+    }
+}
+
+fn jump_to_line(app: &mut App, line: usize) {
+    if let LyricsState::Plain(lines, _) = &app.lyrics {
+        // Rows, not lines: romanized lines take two
+        let rows = plain_rows(lines, app.show_romanization);
+        app.lyrics_scroll = rows.get(line).copied().unwrap_or(line) as u16;
+    } else {
+        // Same as j/k selection, so Enter seeks to it
+        app.lyrics_selected = Some(line);
+        app.lyrics_offset = Some(line);
+        app.last_scroll_time = Some(std::time::Instant::now());
+    }
+}
@@ -0,0 +1,104 @@
+use crate::app::events::AppEvent;
+use crate::app::lyrics::{LyricsFetchResult, LyricsFetcher};
+use crate::app::{App, LookupStatus, LyricsState};
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
+use reqwest::Client;
+use tokio::sync::mpsc;
+
+/// Manual lyrics lookup popup. Captures every key while open.
+pub fn handle_lyrics_lookup(
+    key: KeyEvent,
+    app: &mut App,
+    tx: &mpsc::Sender<AppEvent>,
+    client: &Client,
+) -> bool {
+    let Some(lookup) = app.lyrics_lookup.as_mut() else {
+        return false;
+    };
+
+    match key.code {
+        KeyCode::Esc => app.lyrics_lookup = None,
+        KeyCode::Tab | KeyCode::BackTab => lookup.next_field(),
+        KeyCode::Down if lookup.active_field == 2 => lookup.select_next(),
+        KeyCode::Up if lookup.active_field == 2 => lookup.select_prev(),
+        KeyCode::Char('j') if lookup.active_field == 2 => lookup.select_next(),
+        KeyCode::Char('k') if lookup.active_field == 2 => lookup.select_prev(),
+        KeyCode::Backspace => {
+            if let Some(value) = lookup.active_value() {
+                value.pop();
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Some(value) = lookup.active_value() {
+                value.push(c);
+            }
+        }
+        KeyCode::Enter => {
+            if lookup.chosen().is_some() {
+                bind_selected(app);
+            } else if lookup.status != LookupStatus::Searching && !lookup.title.trim().is_empty() {
+                lookup.status = LookupStatus::Searching;
+                let (id, artist, title) = (
+                    lookup.track_id.clone(),
+                    lookup.artist.trim().to_string(),
+                    lookup.title.trim().to_string(),
+                );
+                let tx = tx.clone();
+                let client = client.clone();
+                tokio::spawn(async move {
+                    let fetcher = LyricsFetcher::new(client);
+                    let res = fetcher
+                        .search_candidates(&artist, &title)
+                        .await
+                        .map_err(|e| e.to_string());
+                    if let Err(e) = tx.send(AppEvent::LyricsCandidates(id, res)).await {
+                        tracing::debug!("Channel closed: {}", e);
+                    }
+                });
+            }
+        }
+        _ => {}
+    }
+    true
+}
+
+/// Show the picked result now and pin it in the caches so it sticks
+fn bind_selected(app: &mut App) {
+    let Some(lookup) = app.lyrics_lookup.take() else {
+        return;
+    };
+    let Some(candidate) = lookup.chosen().cloned() else {
+        return;
+    };
+
+    let state = match &candidate.lyrics {
+        LyricsFetchResult::Found(lines, _) => LyricsState::Loaded(lines.clone(), "Manual".into()),
+        LyricsFetchResult::Plain(lines, _) => LyricsState::Plain(lines.clone(), "Manual".into()),
+        _ => return,
+    };
+
+    app.lyrics_cache
+        .insert(lookup.track_id.clone(), state.clone());
+    let playing = app
+        .track
+        .as_ref()
+        .is_some_and(|t| format!("{}{}", t.name, t.artist) == lookup.track_id);
+    if playing {
+        app.lyrics = state;
+        app.lyrics_selected = None;
+        app.lyrics_offset = None;
+        app.lyrics_scroll = 0;
+        app.lyrics_search = None;
+    }
+
+    let (artist, title) = (lookup.track_artist, lookup.track_title);
+    tokio::task::spawn_blocking(move || {
+        LyricsFetcher::bind_to_track(&artist, &title, &candidate.lyrics)
+    });
+    app.show_toast(&t!(
+        "toast.lyrics_set",
+        artist = candidate.artist,
+        title = candidate.title
+    ));
+}
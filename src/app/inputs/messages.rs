@@ -0,0 +1,20 @@
+use crate::app::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// `:messages` history popup. Captures every key while open.
+pub fn handle_messages(key: KeyEvent, app: &mut App) -> bool {
+    let Some(scroll) = app.messages_view.as_mut() else {
+        return false;
+    };
+
+    let last = app.toasts.history.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => app.messages_view = None,
+        KeyCode::Down | KeyCode::Char('j') => *scroll = (*scroll + 1).min(last),
+        KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+        KeyCode::Char('g') | KeyCode::Home => *scroll = 0,
+        KeyCode::Char('G') | KeyCode::End => *scroll = last,
+        _ => {}
+    }
+    true
+}
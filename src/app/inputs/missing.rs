@@ -0,0 +1,40 @@
+use crate::app::cli::Args;
+use crate::app::missing::{self, Place};
+use crate::app::{with_mpd, App};
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// `:missing` report. Captures every key while open.
+pub async fn handle_missing(key: KeyEvent, app: &mut App, args: &Args) -> bool {
+    let Some(report) = app.missing.as_mut() else {
+        return false;
+    };
+
+    let last = report.entries.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.missing = None,
+        KeyCode::Down | KeyCode::Char('j') => report.scroll = (report.scroll + 1).min(last),
+        KeyCode::Up | KeyCode::Char('k') => report.scroll = report.scroll.saturating_sub(1),
+        KeyCode::Char('g') | KeyCode::Home => report.scroll = 0,
+        KeyCode::Char('G') | KeyCode::End => report.scroll = last,
+        KeyCode::Char('d') => purge(app, args).await,
+        _ => {}
+    }
+    true
+}
+
+/// Drop the broken playlist entries and have MPD forget deleted songs
+async fn purge(app: &mut App, args: &Args) {
+    let Some(report) = app.missing.take() else {
+        return;
+    };
+    let rescan = report.entries.iter().any(|e| e.place == Place::Library);
+    match with_mpd(app, args, move |mpd| missing::purge(mpd, &report.entries)).await {
+        Some(Ok(removed)) if rescan => {
+            app.show_toast(&t!("missing.purged_rescan", count = removed));
+        }
+        Some(Ok(removed)) => app.show_toast(&t!("missing.purged", count = removed)),
+        Some(Err(e)) => app.show_error(&t!("error.generic", error = e)),
+        None => app.show_error(&t!("remote.no_mpd")),
+    }
+}
@@ -8,12 +8,35 @@ use reqwest::Client;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
+pub mod bandcamp;
+pub mod cd;
+pub mod chapters;
 pub mod common;
+#[cfg(feature = "mpd")]
+pub mod console;
+pub mod downloads;
+pub mod dsp;
 pub mod eq;
+pub mod help;
+pub mod info;
 pub mod input_box;
 pub mod library;
+pub mod logs;
 pub mod lyrics;
+pub mod lyrics_lookup;
+pub mod messages;
+#[cfg(feature = "mpd")]
+pub mod missing;
+pub mod palette;
 pub mod player;
+pub mod related;
+pub mod retry;
+pub mod stream_search;
+#[cfg(feature = "mpd")]
+pub mod tag_tool;
+pub mod theme_picker;
+pub mod track_info;
+pub mod wrapped;
 
 pub async fn handle_event(
     key: KeyEvent,
@@ -30,31 +53,161 @@ pub async fn handle_event(
         return;
     }
 
+    // Filename <-> tag preview
+    #[cfg(feature = "mpd")]
+    if tag_tool::handle_tag_tool(key, app, args, tx) {
+        return;
+    }
+
+    if palette::handle_palette(key, app, args, tx, client).await {
+        return;
+    }
+
+    // Missing files report
+    #[cfg(feature = "mpd")]
+    if missing::handle_missing(key, app, args).await {
+        return;
+    }
+
+    // Manual lyrics lookup popup
+    if lyrics_lookup::handle_lyrics_lookup(key, app, tx, client) {
+        return;
+    }
+
+    if stream_search::handle_stream_search(key, app, tx, client) {
+        return;
+    }
+
+    if theme_picker::handle_theme_picker(key, app) {
+        return;
+    }
+
+    if messages::handle_messages(key, app) {
+        return;
+    }
+
+    if cd::handle_cd(key, app, args, tx).await {
+        return;
+    }
+
+    if downloads::handle_downloads(key, app, args) {
+        return;
+    }
+
+    if dsp::handle_plugin_controls(key, app) {
+        return;
+    }
+
+    if dsp::handle_dsp(key, app) {
+        return;
+    }
+
+    if wrapped::handle_wrapped(key, app) {
+        return;
+    }
+
+    if logs::handle_log_view(key, app) {
+        return;
+    }
+
+    if help::handle_help(key, app) {
+        return;
+    }
+
+    if track_info::handle_track_details(key, app) {
+        return;
+    }
+
+    if chapters::handle_chapters(key, app, player, audio_pipeline, tx) {
+        return;
+    }
+
+    if player::handle_seek_mode(key, app, player, audio_pipeline, tx) {
+        return;
+    }
+
+    // MPD Console captures everything while open
+    #[cfg(feature = "mpd")]
+    if console::handle_console_events(key, app, args, tx) {
+        return;
+    }
+
+    // Retry after an error toast
+    if retry::handle_retry(key, app, player, audio_pipeline, args, tx, client).await {
+        return;
+    }
+
     // 2. Common/Global Keys (Quit, Help, etc.)
-    if common::handle_common_events(key, app, args) {
+    if common::handle_common_events(key, app, args).await {
         return;
     }
 
     // 3. View Switchers
     // Check global view switch keys before context specific logic
     let keys = app.keys.clone(); // Clone keys to avoid borrowing app
+    for (n, binding) in [
+        &keys.workspace_1,
+        &keys.workspace_2,
+        &keys.workspace_3,
+        &keys.workspace_4,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if keys.matches(key, binding) {
+            app.switch_workspace(n);
+            return;
+        }
+    }
     if keys.matches(key, &keys.view_lyrics) {
-        app.view_mode = app::ViewMode::Lyrics;
+        app.show_view(app::ViewMode::Lyrics);
         return;
     }
     #[cfg(feature = "mpd")]
     if keys.matches(key, &keys.view_visualizer) && !args.controller {
-        app.view_mode = app::ViewMode::Visualizer;
+        app.show_view(app::ViewMode::Visualizer);
         return;
     }
     #[cfg(feature = "mpd")]
     if keys.matches(key, &keys.view_library) && !args.controller {
-        app.view_mode = app::ViewMode::Library;
+        app.show_view(app::ViewMode::Library);
         return;
     }
     #[cfg(feature = "mpd")]
     if keys.matches(key, &keys.view_eq) && !args.controller {
-        app.view_mode = app::ViewMode::EQ;
+        app.show_view(app::ViewMode::EQ);
+        return;
+    }
+    if keys.matches(key, &keys.view_info) {
+        app.show_view(app::ViewMode::Info);
+        return;
+    }
+    #[cfg(feature = "mpd")]
+    if keys.matches(key, &keys.view_related) && !args.controller {
+        app.show_view(app::ViewMode::Related);
+        return;
+    }
+
+    // Split view: two panel views at once, keys go to the focused one
+    if keys.matches(key, &keys.split_view) {
+        let mut candidates = vec![app::ViewMode::Lyrics, app::ViewMode::Info];
+        #[cfg(feature = "mpd")]
+        if !args.controller {
+            candidates.splice(
+                0..0,
+                [
+                    app::ViewMode::Visualizer,
+                    app::ViewMode::Library,
+                    app::ViewMode::EQ,
+                ],
+            );
+            candidates.push(app::ViewMode::Related);
+        }
+        app.toggle_split_view(&candidates);
+        return;
+    }
+    if keys.matches(key, &keys.split_focus) {
+        app.focus_other_pane();
         return;
     }
 
@@ -63,11 +216,16 @@ pub async fn handle_event(
     // If not, we fall through to "Global Player Controls".
 
     let consumed = match app.view_mode {
-        app::ViewMode::Library => library::handle_library_events(key, app, args),
+        app::ViewMode::Library => {
+            bandcamp::handle_bandcamp(key, app, tx, client)
+                || library::handle_library_events(key, app, args).await
+        }
 
         app::ViewMode::Lyrics => lyrics::handle_lyrics_events(key, app, player).await,
         app::ViewMode::Visualizer => false, // Visualizer has no specific controls other than global player/device
         app::ViewMode::EQ => eq::handle_eq_events(key, app, args),
+        app::ViewMode::Info => info::handle_info_events(key, app),
+        app::ViewMode::Related => related::handle_related_events(key, app, args).await,
     };
 
     if consumed {
@@ -77,5 +235,5 @@ pub async fn handle_event(
     // 5. Global Player Controls
     // These apply anywhere IF not consumed by specific view logic
     // (e.g. Space to Pause should work in Library, unless Library uses Space for selection)
-    if player::handle_player_events(key, app, player, audio_pipeline, args).await {}
+    if player::handle_player_events(key, app, player, audio_pipeline, args, tx).await {}
 }
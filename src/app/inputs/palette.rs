@@ -0,0 +1,180 @@
+use crate::app::cli::Args;
+use crate::app::events::AppEvent;
+use crate::app::palette::{self, Target};
+#[cfg(feature = "mpd")]
+use crate::app::{
+    self as app,
+    library_helpers::{album_tracks, albums},
+    with_mpd,
+};
+use crate::app::{App, InputMode, InputState, Palette};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use reqwest::Client;
+use tokio::sync::mpsc;
+
+/// Open the palette over the whole library (just views and commands in
+/// controller mode)
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+pub async fn open_palette(app: &mut App, args: &Args) {
+    #[cfg(feature = "mpd")]
+    if !args.controller {
+        if let Some(playlists) = with_mpd(app, args, move |mpd| mpd.playlists().ok())
+            .await
+            .flatten()
+        {
+            app.set_playlists(playlists.into_iter().map(|p| p.name));
+        }
+        let songs = match app.library_cache.clone() {
+            Some(cache) => Some(Ok(cache.songs.clone())),
+            None => with_mpd(app, args, app::library_cache::songs).await,
+        };
+        match songs {
+            Some(Ok(songs)) => {
+                app.palette = Some(Palette::new(palette::collect(&songs, &app.playlists)));
+                return;
+            }
+            Some(Err(e)) => tracing::warn!("Palette: could not list the library: {}", e),
+            None => {}
+        }
+    }
+    app.palette = Some(Palette::new(palette::commands()));
+}
+
+/// Typing filters, arrows pick, `Enter` goes. Captures every key while open.
+pub async fn handle_palette(
+    key: KeyEvent,
+    app: &mut App,
+    args: &Args,
+    tx: &mpsc::Sender<AppEvent>,
+    client: &Client,
+) -> bool {
+    let Some(palette) = app.palette.as_mut() else {
+        return false;
+    };
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => app.palette = None,
+        KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+        KeyCode::Char('p') if ctrl => palette.selected = palette.selected.saturating_sub(1),
+        KeyCode::Down | KeyCode::Tab => {
+            palette.selected = (palette.selected + 1).min(palette.matches.len().saturating_sub(1));
+        }
+        KeyCode::Char('n') if ctrl => {
+            palette.selected = (palette.selected + 1).min(palette.matches.len().saturating_sub(1));
+        }
+        KeyCode::Backspace => palette.pop_char(),
+        KeyCode::Enter => {
+            let target = palette.current().map(|entry| entry.target.clone());
+            app.palette = None;
+            match target {
+                Some(Target::Command(command))
+                    if super::input_box::run_background_command(app, tx, client, &command) => {}
+                Some(target) => go(app, args, target).await,
+                None => {}
+            }
+        }
+        KeyCode::Char(c) if !ctrl => palette.push_char(c),
+        _ => {}
+    }
+    true
+}
+
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+pub(super) async fn go(app: &mut App, args: &Args, target: Target) {
+    match target {
+        Target::View(view) => app.show_view(view),
+        Target::Command(command) if command.ends_with(' ') => {
+            app.input_state = Some(InputState::new(InputMode::Command, " : ", &command));
+        }
+        Target::Command(command) => super::input_box::run_command(app, args, &command).await,
+        #[cfg(feature = "mpd")]
+        Target::Song(file) => {
+            let played = with_mpd(app, args, move |mpd| {
+                let id = mpd.push(mpd::Song {
+                    file,
+                    ..Default::default()
+                })?;
+                mpd.switch(id)
+            })
+            .await;
+            if let Some(Err(e)) = played {
+                app.show_error(&crate::t!("error.generic", error = e));
+            }
+        }
+        #[cfg(feature = "mpd")]
+        Target::Album(album) => {
+            let found = albums(app, args)
+                .await
+                .zip(album_tracks(app, args, &album).await);
+            if let Some((albums, tracks)) = found {
+                open_library(app, app::LibraryMode::Albums);
+                app.albums = albums;
+                app.album_open = Some(album);
+                app.library_items = tracks;
+            }
+        }
+        #[cfg(feature = "mpd")]
+        Target::Artist(artist) => {
+            let found = match &app.library_cache {
+                Some(cache) => Some(
+                    cache
+                        .songs
+                        .iter()
+                        .filter(|s| s.artist.as_deref() == Some(artist.as_str()))
+                        .map(|s| s.item())
+                        .collect(),
+                ),
+                None => {
+                    let name = artist.clone();
+                    with_mpd(app, args, move |mpd| {
+                        let mut query = mpd::Query::new();
+                        query.and(mpd::Term::Tag("Artist".into()), name.as_str());
+                        mpd.find(&query, None)
+                    })
+                    .await
+                    .and_then(Result::ok)
+                    .map(|songs| {
+                        songs
+                            .into_iter()
+                            .map(|s| app::LibraryItem {
+                                name: s.title.clone().unwrap_or_else(|| s.file.clone()),
+                                item_type: app::LibraryItemType::Song,
+                                artist: s.artist.clone(),
+                                duration_ms: s.duration.map(|d| d.as_millis() as u64),
+                                path: Some(s.file),
+                            })
+                            .collect()
+                    })
+                }
+            };
+            if let Some(items) = found {
+                if app.library_mode != app::LibraryMode::Search {
+                    app.previous_library_mode = Some(app.library_mode);
+                }
+                open_library(app, app::LibraryMode::Search);
+                app.search_query = artist;
+                app.library_items = items;
+            }
+        }
+        #[cfg(feature = "mpd")]
+        Target::Playlist(name) => {
+            open_library(app, app::LibraryMode::Playlists);
+            app.library_selected = app.playlists.iter().position(|p| *p == name).unwrap_or(0);
+        }
+        #[cfg(not(feature = "mpd"))]
+        _ => {}
+    }
+}
+
+/// The library panel on `mode`, fresh: nothing left over from before
+#[cfg(feature = "mpd")]
+pub(super) fn open_library(app: &mut App, mode: app::LibraryMode) {
+    app.show_view(app::ViewMode::Library);
+    app.library_mode = mode;
+    app.library_items.clear();
+    app.library_selected = 0;
+    app.browse_path.clear();
+    app.album_open = None;
+    app.search_query.clear();
+    app.search_active = false;
+}
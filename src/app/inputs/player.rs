@@ -1,100 +1,378 @@
 use crate::app::cli::Args;
+use crate::app::events::{report, AppEvent, Retry};
+use crate::app::markers::{self, MarkerKind};
+use crate::app::remote::{RemoteCommand, Reply, Seek, Volume};
 #[cfg(feature = "mpd")]
 use crate::app::with_mpd;
-use crate::app::{self, App};
+use crate::app::{self, App, VolumeHold};
 use crate::audio::pipeline::AudioPipeline;
-use crate::player::PlayerTrait;
-use crossterm::event::KeyEvent;
+use crate::error::VyomError;
+use crate::player::{PlayerTrait, RepeatMode};
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
 use std::sync::Arc;
+use tokio::sync::mpsc;
 
-pub async fn handle_player_events(
+/// Run a player call off the UI thread; a failure becomes an error toast
+/// whose retry sends `retry` again
+fn spawn_command<F, T>(
+    tx: &mpsc::Sender<AppEvent>,
+    player: &Arc<dyn PlayerTrait>,
+    retry: RemoteCommand,
+    f: F,
+) where
+    F: FnOnce(&dyn PlayerTrait) -> anyhow::Result<T> + Send + 'static,
+    T: 'static,
+{
+    let p = player.clone();
+    let tx = tx.clone();
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = f(p.as_ref()) {
+            report(
+                &tx,
+                VyomError::PlayerCommand(e.to_string()),
+                Some(Retry::Command(retry)),
+            );
+        }
+    });
+}
+
+/// Seek mode: h/l nudge a ghost playhead, Enter seeks there. Captures every key while open.
+pub fn handle_seek_mode(
     key: KeyEvent,
     app: &mut App,
     player: &Arc<dyn PlayerTrait>,
     audio_pipeline: &mut AudioPipeline,
-    args: &Args,
+    tx: &mpsc::Sender<AppEvent>,
 ) -> bool {
+    let Some(target) = app.seek_preview else {
+        return false;
+    };
     let keys = &app.keys;
 
-    // Play/Pause ('Space')
-    if keys.matches(key, &keys.play_pause) {
+    if keys.matches(key, &keys.seek_backward) || key.code == KeyCode::Left {
+        app.nudge_seek_preview(-1000);
+    } else if keys.matches(key, &keys.seek_forward) || key.code == KeyCode::Right {
+        app.nudge_seek_preview(1000);
+    } else if key.code == KeyCode::Char('H') || key.code == KeyCode::Down {
+        app.nudge_seek_preview(-10_000);
+    } else if key.code == KeyCode::Char('L') || key.code == KeyCode::Up {
+        app.nudge_seek_preview(10_000);
+    } else if key.code == KeyCode::Enter {
+        app.seek_preview = None;
         audio_pipeline.flush();
-        let p = player.clone();
-        tokio::task::spawn_blocking(move || {
-            if let Err(e) = p.play_pause() {
-                tracing::warn!("Failed to toggle play/pause: {}", e);
+        seek_to(app, player, tx, target as f64 / 1000.0);
+        app.show_toast(&t!(
+            "toast.seek_to",
+            time = format!("{:02}:{:02}", target / 60000, (target % 60000) / 1000)
+        ));
+    } else if key.code == KeyCode::Esc || keys.matches(key, &keys.seek_mode) {
+        app.seek_preview = None;
+    }
+    true
+}
+
+/// Seek to `target` seconds unless a newer seek or a track change beats it
+pub(crate) fn seek_to(
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    tx: &mpsc::Sender<AppEvent>,
+    target: f64,
+) {
+    // Increment Seek ID (Generation Counter)
+    app.seek_id
+        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let my_id = app.seek_id.load(std::sync::atomic::Ordering::Relaxed);
+    let global_seek_id = app.seek_id.clone();
+
+    let original_track_key = app
+        .track
+        .as_ref()
+        .map(|t| (t.name.clone(), t.artist.clone()));
+    let retry = RemoteCommand::Seek(Seek::To(target));
+    spawn_command(tx, player, retry, move |p| {
+        // Check if a newer seek request has come in
+        if global_seek_id.load(std::sync::atomic::Ordering::Relaxed) != my_id {
+            return Ok(()); // Stale request, discard
+        }
+
+        if let Some(current_track) = p.get_current_track()? {
+            let current_key = (current_track.name.clone(), current_track.artist.clone());
+            if original_track_key.as_ref() == Some(&current_key) {
+                p.seek(target)?;
             }
-        });
+        }
+        Ok(())
+    });
+}
 
-        let is_playing = app
-            .track
-            .as_ref()
-            .map(|t| t.state == crate::player::PlayerState::Playing)
-            .unwrap_or(false);
+/// Play/pause with the matching toast (keys and `vyom toggle`)
+pub(crate) fn toggle_playback(
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    audio_pipeline: &mut AudioPipeline,
+    tx: &mpsc::Sender<AppEvent>,
+) {
+    audio_pipeline.flush();
+    spawn_command(tx, player, RemoteCommand::Toggle, |p| p.play_pause());
 
-        if is_playing {
-            app.show_toast("⏸ Pause");
-        } else {
-            app.show_toast("▶ Play");
+    let is_playing = app
+        .track
+        .as_ref()
+        .map(|t| t.state == crate::player::PlayerState::Playing)
+        .unwrap_or(false);
+
+    if is_playing {
+        app.show_toast(&t!("toast.pause"));
+    } else {
+        app.show_toast(&t!("toast.play"));
+    }
+}
+
+/// Next (`forward`) or previous track
+pub(crate) fn skip_track(
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    audio_pipeline: &mut AudioPipeline,
+    tx: &mpsc::Sender<AppEvent>,
+    forward: bool,
+) {
+    audio_pipeline.flush();
+    if forward {
+        spawn_command(tx, player, RemoteCommand::Next, |p| p.next());
+    } else {
+        spawn_command(tx, player, RemoteCommand::Prev, |p| p.prev());
+    }
+    if forward {
+        app.show_toast(&t!("toast.next_track"));
+    } else {
+        app.show_toast(&t!("toast.prev_track"));
+    }
+}
+
+/// Set the volume (0-100) by hand on the pipeline and the player, held to
+/// the configured cap; returns the volume that was set
+pub(crate) fn set_volume(
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    audio_pipeline: &mut AudioPipeline,
+    tx: &mpsc::Sender<AppEvent>,
+    new_vol: u8,
+) -> u8 {
+    let max = app.output.max_volume();
+    let limited = new_vol > max;
+    // Turning it up or down by hand ends a mute or duck
+    app.volume_hold = None;
+    let new_vol = apply_volume(app, player, audio_pipeline, tx, new_vol);
+    app.volume_memory.record(new_vol);
+    if limited {
+        app.show_warning(&t!("toast.volume_limited", percent = max));
+    } else {
+        app.show_toast(&t!("toast.volume", percent = new_vol));
+    }
+    new_vol
+}
+
+/// Set the volume without a toast or remembering it, held to the cap
+pub(crate) fn apply_volume(
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    audio_pipeline: &mut AudioPipeline,
+    tx: &mpsc::Sender<AppEvent>,
+    new_vol: u8,
+) -> u8 {
+    let new_vol = new_vol.min(app.output.max_volume());
+    app.app_volume = new_vol;
+    app.last_volume_action = Some(std::time::Instant::now());
+    audio_pipeline.set_volume(new_vol);
+    let retry = RemoteCommand::Volume(Volume::To(new_vol));
+    spawn_command(tx, player, retry, move |p| p.set_volume(new_vol));
+    new_vol
+}
+
+/// Put back the volume remembered for the playing album or track
+pub(crate) fn restore_volume(
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    audio_pipeline: &mut AudioPipeline,
+    tx: &mpsc::Sender<AppEvent>,
+    volume: u8,
+) {
+    if let Some((_, back_to)) = app.volume_hold.as_mut() {
+        // Stays muted or ducked, and comes back to this
+        *back_to = volume;
+        return;
+    }
+    if volume == app.app_volume {
+        return;
+    }
+    let volume = apply_volume(app, player, audio_pipeline, tx, volume);
+    app.show_toast(&t!("toast.volume_remembered", percent = volume));
+}
+
+/// Mute or duck (`hold`), or come back from it when it's already on
+pub(crate) fn toggle_volume_hold(
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    audio_pipeline: &mut AudioPipeline,
+    tx: &mpsc::Sender<AppEvent>,
+    hold: VolumeHold,
+) {
+    let (volume, toast) = match app.volume_hold {
+        Some((current, back_to)) if current == hold => {
+            app.volume_hold = None;
+            (back_to, t!("toast.volume", percent = back_to))
+        }
+        held => {
+            let back_to = held.map_or(app.app_volume, |(_, v)| v);
+            app.volume_hold = Some((hold, back_to));
+            match hold {
+                VolumeHold::Muted => (0, t!("toast.muted")),
+                VolumeHold::Ducked => {
+                    let volume = back_to.min(app.duck_volume);
+                    (volume, t!("toast.ducked", percent = volume))
+                }
+            }
+        }
+    };
+    apply_volume(app, player, audio_pipeline, tx, volume);
+    app.show_toast(&toast);
+}
+
+/// Run a command from the control socket (`vyom next` & co.) like its key
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+pub async fn handle_remote(
+    command: RemoteCommand,
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    audio_pipeline: &mut AudioPipeline,
+    args: &Args,
+    tx: &mpsc::Sender<AppEvent>,
+) -> Reply {
+    let is_playing = app
+        .track
+        .as_ref()
+        .is_some_and(|t| t.state == crate::player::PlayerState::Playing);
+    match command {
+        RemoteCommand::Toggle => {
+            toggle_playback(app, player, audio_pipeline, tx);
+            Ok(if is_playing {
+                t!("toast.pause")
+            } else {
+                t!("toast.play")
+            })
+        }
+        RemoteCommand::Play => {
+            if !is_playing {
+                toggle_playback(app, player, audio_pipeline, tx);
+            }
+            Ok(t!("toast.play"))
+        }
+        RemoteCommand::Pause => {
+            if is_playing {
+                toggle_playback(app, player, audio_pipeline, tx);
+            }
+            Ok(t!("toast.pause"))
+        }
+        RemoteCommand::Next => {
+            skip_track(app, player, audio_pipeline, tx, true);
+            Ok(t!("toast.next_track"))
+        }
+        RemoteCommand::Prev => {
+            skip_track(app, player, audio_pipeline, tx, false);
+            Ok(t!("toast.prev_track"))
+        }
+        RemoteCommand::Seek(seek) => {
+            let Some(duration_ms) = app.track.as_ref().map(|t| t.duration_ms) else {
+                return Err(t!("remote.nothing_playing"));
+            };
+            let position = app.get_current_position_ms() as f64 / 1000.0;
+            let target = seek.target(position, duration_ms as f64 / 1000.0);
+            audio_pipeline.flush();
+            seek_to(app, player, tx, target);
+            let msg = t!(
+                "toast.seek_to",
+                time = crate::app::remote::format_secs(target)
+            );
+            app.show_toast(&msg);
+            Ok(msg)
+        }
+        RemoteCommand::Volume(volume) => {
+            let new_vol = volume.apply(app.app_volume);
+            let new_vol = set_volume(app, player, audio_pipeline, tx, new_vol);
+            Ok(t!("toast.volume", percent = new_vol))
         }
+        RemoteCommand::QueueAdd(path) => {
+            #[cfg(feature = "mpd")]
+            if app.is_mpd {
+                let file = path.clone();
+                let result = with_mpd(app, args, move |mpd| {
+                    crate::app::remote::queue_add(mpd, &file)
+                })
+                .await
+                .unwrap_or_else(|| Err(t!("remote.no_mpd")));
+                return result.map(|_| {
+                    let msg = t!("remote.queued", path = path);
+                    app.show_toast(&msg);
+                    msg
+                });
+            }
+            Err(t!("remote.needs_mpd"))
+        }
+    }
+}
+
+pub async fn handle_player_events(
+    key: KeyEvent,
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    audio_pipeline: &mut AudioPipeline,
+    args: &Args,
+    tx: &mpsc::Sender<AppEvent>,
+) -> bool {
+    let keys = &app.keys;
+
+    // Play/Pause ('Space')
+    if keys.matches(key, &keys.play_pause) {
+        toggle_playback(app, player, audio_pipeline, tx);
         return true;
     }
 
     // Next Track ('n')
     if keys.matches(key, &keys.next_track) {
-        audio_pipeline.flush();
-        let p = player.clone();
-        tokio::task::spawn_blocking(move || {
-            if let Err(e) = p.next() {
-                tracing::warn!("Failed to skip to next track: {}", e);
-            }
-        });
-        app.show_toast("⏭ Next Track");
+        skip_track(app, player, audio_pipeline, tx, true);
         return true;
     }
 
     // Prev Track ('p')
     if keys.matches(key, &keys.prev_track) {
-        audio_pipeline.flush();
-        let p = player.clone();
-        tokio::task::spawn_blocking(move || {
-            if let Err(e) = p.prev() {
-                tracing::warn!("Failed to skip to previous track: {}", e);
-            }
-        });
-        app.show_toast("⏮ Previous Track");
+        skip_track(app, player, audio_pipeline, tx, false);
         return true;
     }
 
     // Volume Up ('+')
     if keys.matches(key, &keys.volume_up) {
         let new_vol = (app.app_volume.saturating_add(5)).min(100);
-        app.app_volume = new_vol;
-        app.last_volume_action = Some(std::time::Instant::now());
-        audio_pipeline.set_volume(new_vol);
-        let p = player.clone();
-        tokio::task::spawn_blocking(move || {
-            if let Err(e) = p.set_volume(new_vol) {
-                tracing::warn!("Failed to set volume: {}", e);
-            }
-        });
-        app.show_toast(&format!("Volume: {}%", new_vol));
+        set_volume(app, player, audio_pipeline, tx, new_vol);
         return true;
     }
 
     // Volume Down ('-')
     if keys.matches(key, &keys.volume_down) {
         let new_vol = app.app_volume.saturating_sub(5);
-        app.app_volume = new_vol;
-        app.last_volume_action = Some(std::time::Instant::now());
-        audio_pipeline.set_volume(new_vol);
-        let p = player.clone();
-        tokio::task::spawn_blocking(move || {
-            if let Err(e) = p.set_volume(new_vol) {
-                tracing::warn!("Failed to set volume: {}", e);
-            }
-        });
-        app.show_toast(&format!("Volume: {}%", new_vol));
+        set_volume(app, player, audio_pipeline, tx, new_vol);
+        return true;
+    }
+
+    // Mute ('m')
+    if keys.matches(key, &keys.mute) {
+        toggle_volume_hold(app, player, audio_pipeline, tx, VolumeHold::Muted);
+        return true;
+    }
+
+    // Duck ('Ctrl+d'): down for a conversation, back on the next press
+    if keys.matches(key, &keys.duck) {
+        toggle_volume_hold(app, player, audio_pipeline, tx, VolumeHold::Ducked);
         return true;
     }
 
@@ -131,33 +409,12 @@ pub async fn handle_player_events(
                 target = target.max(0.0);
             }
 
-            // Increment Seek ID (Generation Counter)
-            app.seek_id
-                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            let my_id = app.seek_id.load(std::sync::atomic::Ordering::Relaxed);
-            let global_seek_id = app.seek_id.clone();
-
-            let player_bg = player.clone();
-            let original_track_key = app
-                .track
-                .as_ref()
-                .map(|t| (t.name.clone(), t.artist.clone()));
-            tokio::task::spawn_blocking(move || {
-                // Check if a newer seek request has come in
-                if global_seek_id.load(std::sync::atomic::Ordering::Relaxed) != my_id {
-                    return; // Stale request, discard
-                }
-
-                if let Ok(Some(current_track)) = player_bg.get_current_track() {
-                    let current_key = (current_track.name.clone(), current_track.artist.clone());
-                    if original_track_key.as_ref() == Some(&current_key) {
-                        if let Err(e) = player_bg.seek(target) {
-                            tracing::warn!("Failed to seek: {}", e);
-                        }
-                    }
-                }
-            });
-            app.show_toast(&format!("⏪ Seek: {:+.0}s", app.seek_accumulator));
+            seek_to(app, player, tx, target);
+            app.seek_flash = Some(((target * 1000.0) as u64, now + app::frames::SEEK_FLASH));
+            app.show_toast(&t!(
+                "toast.seek_back",
+                secs = format!("{:+.0}", app.seek_accumulator)
+            ));
         }
         return true;
     }
@@ -195,37 +452,22 @@ pub async fn handle_player_events(
                 target = target.max(0.0);
             }
 
-            // Increment Seek ID (Generation Counter)
-            app.seek_id
-                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            let my_id = app.seek_id.load(std::sync::atomic::Ordering::Relaxed);
-            let global_seek_id = app.seek_id.clone();
-
-            let player_bg = player.clone();
-            let original_track_key = app
-                .track
-                .as_ref()
-                .map(|t| (t.name.clone(), t.artist.clone()));
-            tokio::task::spawn_blocking(move || {
-                // Check if a newer seek request has come in
-                if global_seek_id.load(std::sync::atomic::Ordering::Relaxed) != my_id {
-                    return; // Stale request, discard
-                }
-
-                if let Ok(Some(current_track)) = player_bg.get_current_track() {
-                    let current_key = (current_track.name.clone(), current_track.artist.clone());
-                    if original_track_key.as_ref() == Some(&current_key) {
-                        if let Err(e) = player_bg.seek(target) {
-                            tracing::warn!("Failed to seek: {}", e);
-                        }
-                    }
-                }
-            });
-            app.show_toast(&format!("⏩ Seek: {:+.0}s", app.seek_accumulator));
+            seek_to(app, player, tx, target);
+            app.seek_flash = Some(((target * 1000.0) as u64, now + app::frames::SEEK_FLASH));
+            app.show_toast(&t!(
+                "toast.seek_forward",
+                secs = format!("{:+.0}", app.seek_accumulator)
+            ));
         }
         return true;
     }
 
+    // Seek mode ('s'): precise seeking with a preview
+    if keys.matches(key, &keys.seek_mode) {
+        app.start_seek_mode();
+        return true;
+    }
+
     // Shuffle toggle
     if keys.matches(key, &keys.shuffle) {
         if args.controller {
@@ -234,14 +476,11 @@ pub async fn handle_player_events(
                 tracing::warn!("Failed to toggle shuffle: {}", e);
             }
             app.shuffle = new_state;
-            app.show_toast(&format!(
-                "🔀 Shuffle: {}",
-                if new_state { "ON" } else { "OFF" }
-            ));
+            app.show_toast(&t!("toast.shuffle", state = on_off(new_state)));
         } else {
             #[cfg(feature = "mpd")]
             {
-                let new_shuffle_state = with_mpd(app, args, |mpd| {
+                let new_shuffle_state = with_mpd(app, args, move |mpd| {
                     if let Ok(status) = mpd.status() {
                         let new_state = !status.random;
                         if let Err(e) = mpd.random(new_state) {
@@ -252,21 +491,44 @@ pub async fn handle_player_events(
                         None
                     }
                 })
+                .await
                 .flatten();
 
                 if let Some(state) = new_shuffle_state {
                     app.shuffle = state;
-                    app.show_toast(&format!("🔀 Shuffle: {}", if state { "ON" } else { "OFF" }));
+                    app.show_toast(&t!("toast.shuffle", state = on_off(state)));
                 }
             }
         }
         return true;
     }
 
+    // Queue shuffle modes ('Z'): each press rewrites the queue in the next mode
+    #[cfg(feature = "mpd")]
+    if keys.matches(key, &keys.shuffle_mode) && !args.controller {
+        let mode = app::ShuffleMode::after(app.shuffle_mode);
+        match with_mpd(app, args, move |mpd| {
+            crate::app::shuffle::reorder(mpd, mode)
+        })
+        .await
+        {
+            Some(Ok(count)) => {
+                app.shuffle_mode = Some(mode);
+                app.shuffle = false;
+                app.show_toast(&t!(
+                    "toast.shuffled",
+                    mode = shuffle_mode_name(mode),
+                    count = count
+                ));
+            }
+            Some(Err(e)) => app.report(&VyomError::PlayerCommand(e.to_string()), None),
+            None => {}
+        }
+        return true;
+    }
+
     // Repeat toggle
     if keys.matches(key, &keys.repeat) {
-        use crate::player::RepeatMode;
-
         let next_mode = match app.repeat {
             RepeatMode::Off => RepeatMode::Playlist,
             RepeatMode::Playlist => RepeatMode::Single,
@@ -278,16 +540,11 @@ pub async fn handle_player_events(
                 tracing::warn!("Failed to set repeat mode: {}", e);
             }
             app.repeat = next_mode;
-            let icon = match next_mode {
-                RepeatMode::Off => "OFF",
-                RepeatMode::Playlist => "🔁 All",
-                RepeatMode::Single => "🔂 One",
-            };
-            app.show_toast(&format!("Repeat: {}", icon));
+            app.show_toast(&repeat_toast(next_mode));
         } else {
             #[cfg(feature = "mpd")]
             {
-                let new_mode = with_mpd(app, args, |mpd| {
+                let new_mode = with_mpd(app, args, move |mpd| {
                     // We need to set repeat and single flags manually based on mode
                     let (repeat, single) = match next_mode {
                         RepeatMode::Off => (false, false),
@@ -304,22 +561,172 @@ pub async fn handle_player_events(
                         None
                     }
                 })
+                .await
                 .flatten();
 
                 if let Some(mode) = new_mode {
                     app.repeat = mode;
-                    let icon = match mode {
-                        RepeatMode::Off => "OFF",
-                        RepeatMode::Playlist => "🔁 All",
-                        RepeatMode::Single => "🔂 One",
-                    };
-                    app.show_toast(&format!("Repeat: {}", icon));
+                    app.show_toast(&repeat_toast(mode));
                 }
             }
         }
         return true;
     }
 
+    // Finish Album Mode ('F') - MPD only
+    #[cfg(feature = "mpd")]
+    if keys.matches(key, &keys.finish_album) && !args.controller {
+        app.finish_album = app.finish_album.next();
+        // Undo what was set on MPD for the old mode; the runner sets up the new one
+        app.finish_album_checked = None;
+        if let Some(armed) = app.finish_album_armed.take() {
+            app::send_mpd(app, args, move |mpd| {
+                let undone = match armed {
+                    app::FinishAlbumMode::Stop => mpd.single(false),
+                    _ => mpd.random(false),
+                };
+                if let Err(e) = undone {
+                    tracing::warn!("Failed to undo finish-album action: {}", e);
+                }
+            });
+        }
+        let msg = match app.finish_album {
+            app::FinishAlbumMode::Off => t!("toast.finish_album_off"),
+            app::FinishAlbumMode::Stop => t!("toast.finish_album_stop"),
+            app::FinishAlbumMode::Shuffle => t!("toast.finish_album_shuffle"),
+        };
+        app.show_toast(&msg);
+        return true;
+    }
+
+    // Silence Trim for this track ('T') - MPD only
+    #[cfg(feature = "mpd")]
+    if keys.matches(key, &keys.silence_trim) && !args.controller {
+        let msg = if app.silence_trim.toggle_track() {
+            t!("toast.silence_trim_on")
+        } else {
+            t!("toast.silence_trim_off")
+        };
+        app.show_toast(&msg);
+        return true;
+    }
+
+    // A-B Loop ('O'): set A, set B, clear - MPD only
+    #[cfg(feature = "mpd")]
+    if keys.matches(key, &keys.ab_loop) && !args.controller {
+        if app.track.is_none() {
+            return true;
+        }
+        app.ab_loop.mark(app.get_current_position_ms());
+        let mmss = |ms: u64| format!("{:02}:{:02}", ms / 60000, (ms % 60000) / 1000);
+        let msg = match app.ab_loop.range() {
+            Some((a, b)) => t!("toast.ab_loop_on", a = mmss(a), b = mmss(b)),
+            None if app.ab_loop.a.is_some() => t!("toast.ab_loop_a"),
+            None => t!("toast.ab_loop_off"),
+        };
+        app.show_toast(&msg);
+        app.needs_redraw = true;
+        return true;
+    }
+
+    // Repeat this song once ('Ctrl+x'): queue it again right after itself
+    #[cfg(feature = "mpd")]
+    if keys.matches(key, &keys.repeat_once) && !args.controller {
+        let (Some(pos), Some(file)) = (app.playing_queue_index(), app.playing_file()) else {
+            return true;
+        };
+        let song = mpd::Song {
+            file: file.to_string(),
+            ..Default::default()
+        };
+        let added = with_mpd(app, args, move |mpd| {
+            let id = mpd.insert(&song, pos + 1)?;
+            // With random on, the highest priority plays next
+            if let Err(e) = mpd.priority(mpd::song::Id(id as u32), 255) {
+                tracing::debug!("Failed to raise the repeat's priority: {}", e);
+            }
+            Ok::<_, mpd::error::Error>(())
+        })
+        .await;
+        match added {
+            Some(Ok(())) => app.show_toast(&t!("toast.repeat_once")),
+            Some(Err(e)) => app.report(&VyomError::MpdConnection(e.to_string()), None),
+            None => {}
+        }
+        return true;
+    }
+
+    // Resume where a long track was left off ('Ctrl+o'), when offered
+    if keys.matches(key, &keys.resume) {
+        if let Some(ms) = app.resume.offer.take() {
+            audio_pipeline.flush();
+            seek_to(app, player, tx, ms as f64 / 1000.0);
+            app.show_toast(&t!(
+                "toast.resumed",
+                time = format!("{:02}:{:02}", ms / 60000, (ms % 60000) / 1000)
+            ));
+            return true;
+        }
+    }
+
+    // Bookmark the playhead ('Ctrl+b'), or remove the one right there
+    if keys.matches(key, &keys.bookmark) {
+        let ms = app.get_current_position_ms();
+        let time = format!("{:02}:{:02}", ms / 60000, (ms % 60000) / 1000);
+        match app.toggle_bookmark() {
+            Some(true) => app.show_toast(&t!("toast.bookmark_added", time = time)),
+            Some(false) => app.show_toast(&t!("toast.bookmark_removed", time = time)),
+            None => return true,
+        }
+        app.save_state();
+        return true;
+    }
+
+    // Jump between bookmarks, chapters and lyric sections (',' / '.')
+    let forward = keys.matches(key, &keys.marker_next);
+    if forward || keys.matches(key, &keys.marker_prev) {
+        let markers = app.markers();
+        let pos = app.get_current_position_ms();
+        let target = if forward {
+            markers::next(&markers, pos)
+        } else {
+            markers::prev(&markers, pos)
+        };
+        let Some(marker) = target.cloned() else {
+            app.show_toast(&t!("toast.no_markers"));
+            return true;
+        };
+        audio_pipeline.flush();
+        seek_to(app, player, tx, marker.ms as f64 / 1000.0);
+        let time = format!("{:02}:{:02}", marker.ms / 60000, (marker.ms % 60000) / 1000);
+        app.show_toast(&match marker.kind {
+            MarkerKind::Chapter => t!("toast.chapter", title = marker.label),
+            MarkerKind::Section => t!("toast.marker_section", time = time, line = marker.label),
+            MarkerKind::Bookmark => t!("toast.marker_bookmark", time = time),
+        });
+        return true;
+    }
+
+    // Pitch Shift ('[' / ']') - applied by the local pipeline
+    if !args.controller {
+        let delta = if keys.matches(key, &keys.pitch_up) {
+            1
+        } else if keys.matches(key, &keys.pitch_down) {
+            -1
+        } else {
+            0
+        };
+        if delta != 0 {
+            app.shift_pitch(delta);
+            let msg = match app.pitch.get() {
+                0 => t!("toast.pitch_original"),
+                st => t!("toast.pitch", semitones = format!("{:+}", st)),
+            };
+            app.show_toast(&msg);
+            return true;
+        }
+    }
+
     // Audio Device Switching
     if app.view_mode == app::ViewMode::Lyrics
         || app.view_mode == app::ViewMode::Visualizer
@@ -341,5 +748,36 @@ pub async fn handle_player_events(
         return true;
     }
 
+    // Full-screen Art ('f')
+    if keys.matches(key, &keys.fullscreen_art) {
+        app.toggle_fullscreen_art();
+        return true;
+    }
+
     false
 }
+
+pub(super) fn on_off(on: bool) -> String {
+    if on {
+        t!("common.on")
+    } else {
+        t!("common.off")
+    }
+}
+
+#[cfg(feature = "mpd")]
+fn shuffle_mode_name(mode: app::ShuffleMode) -> String {
+    match mode {
+        app::ShuffleMode::Albums => t!("shuffle.albums"),
+        app::ShuffleMode::Weighted => t!("shuffle.weighted"),
+        app::ShuffleMode::ArtistSpread => t!("shuffle.artist_spread"),
+    }
+}
+
+fn repeat_toast(mode: RepeatMode) -> String {
+    match mode {
+        RepeatMode::Off => t!("toast.repeat_off"),
+        RepeatMode::Playlist => t!("toast.repeat_all"),
+        RepeatMode::Single => t!("toast.repeat_one"),
+    }
+}
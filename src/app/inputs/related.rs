@@ -0,0 +1,59 @@
+use crate::app::cli::Args;
+use crate::app::related::RelatedState;
+use crate::app::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Related view: j/k move, Enter jumps to the artist's albums
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+pub async fn handle_related_events(key: KeyEvent, app: &mut App, args: &Args) -> bool {
+    let RelatedState::Loaded(artists, _) = &app.related else {
+        return false;
+    };
+    let last = artists.len().saturating_sub(1);
+    let keys = &app.keys;
+    if keys.matches(key, &keys.nav_down) || keys.matches(key, &keys.nav_down_alt) {
+        app.related_selected = (app.related_selected + 1).min(last);
+    } else if keys.matches(key, &keys.nav_up) || keys.matches(key, &keys.nav_up_alt) {
+        app.related_selected = app.related_selected.saturating_sub(1);
+    } else if matches!(key.code, KeyCode::Char('g') | KeyCode::Home) {
+        app.related_selected = 0;
+    } else if matches!(key.code, KeyCode::Char('G') | KeyCode::End) {
+        app.related_selected = last;
+    } else if key.code == KeyCode::Enter {
+        if let Some(artist) = artists.get(app.related_selected) {
+            if !artist.in_library {
+                app.show_toast(&crate::t!("toast.not_in_library", artist = artist.name));
+                return true;
+            }
+            #[cfg(feature = "mpd")]
+            {
+                let name = artist.name.clone();
+                open_artist(app, args, name).await;
+            }
+        }
+    } else {
+        return false;
+    }
+    true
+}
+
+/// The album grid on the artist's first album, or their songs when none of
+/// the albums are filed under them (guest spots on compilations)
+#[cfg(feature = "mpd")]
+async fn open_artist(app: &mut App, args: &Args, name: String) {
+    use crate::app::related::normalize;
+
+    let Some(cache) = app.library_cache.clone() else {
+        return;
+    };
+    let albums = cache.albums();
+    let wanted = normalize(&name);
+    match albums.iter().position(|a| normalize(&a.artist) == wanted) {
+        Some(idx) => {
+            super::palette::open_library(app, crate::app::LibraryMode::Albums);
+            app.albums = albums;
+            app.library_selected = idx;
+        }
+        None => super::palette::go(app, args, crate::app::palette::Target::Artist(name)).await,
+    }
+}
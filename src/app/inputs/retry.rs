@@ -0,0 +1,53 @@
+use crate::app::cli::Args;
+use crate::app::events::{AppEvent, Retry};
+use crate::app::inputs::player::handle_remote;
+use crate::app::runner::spawn_lyrics_fetch;
+use crate::app::{App, LyricsState};
+use crate::audio::pipeline::AudioPipeline;
+use crate::error::VyomError;
+use crate::player::PlayerTrait;
+use crossterm::event::KeyEvent;
+use reqwest::Client;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Retry (`Ctrl+t`) whatever the last error toast offered. Without one the
+/// key falls through.
+pub async fn handle_retry(
+    key: KeyEvent,
+    app: &mut App,
+    player: &Arc<dyn PlayerTrait>,
+    audio_pipeline: &mut AudioPipeline,
+    args: &Args,
+    tx: &mpsc::Sender<AppEvent>,
+    client: &Client,
+) -> bool {
+    if !app.keys.matches(key, &app.keys.retry) {
+        return false;
+    }
+    let Some(retry) = app.take_retry() else {
+        return false;
+    };
+
+    match retry {
+        Retry::Command(command) => {
+            if let Err(msg) = handle_remote(command, app, player, audio_pipeline, args, tx).await {
+                app.show_error(&msg);
+            }
+        }
+        Retry::Lyrics => {
+            if let Some(track) = app.track.clone() {
+                app.lyrics = LyricsState::Loading;
+                spawn_lyrics_fetch(app, &track, tx, client);
+            }
+        }
+        Retry::Pipeline => {
+            audio_pipeline.stop();
+            match audio_pipeline.start() {
+                Ok(()) => audio_pipeline.set_volume(app.app_volume),
+                Err(e) => app.report(&VyomError::AudioPipeline(e), Some(Retry::Pipeline)),
+            }
+        }
+    }
+    true
+}
@@ -0,0 +1,62 @@
+use crate::app::events::AppEvent;
+use crate::app::stream_search::{self, StreamSearch};
+use crate::app::{App, LookupStatus};
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
+use reqwest::Client;
+use tokio::sync::mpsc;
+
+/// `:stream [query]`: the search popup, with the query filled in
+pub fn open_stream_search(app: &mut App, query: &str) {
+    if !app.is_mpd {
+        app.show_warning(&t!("stream.needs_mpd"));
+        return;
+    }
+    app.stream_search = Some(StreamSearch::new(query));
+}
+
+/// Stream search popup: type, `Enter` searches, `Enter` again plays the
+/// selection. Captures every key while open.
+pub fn handle_stream_search(
+    key: KeyEvent,
+    app: &mut App,
+    tx: &mpsc::Sender<AppEvent>,
+    client: &Client,
+) -> bool {
+    let Some(search) = app.stream_search.as_mut() else {
+        return false;
+    };
+
+    match key.code {
+        KeyCode::Esc => app.stream_search = None,
+        KeyCode::Down => search.select_next(),
+        KeyCode::Up => search.select_prev(),
+        KeyCode::Backspace => {
+            search.query.pop();
+        }
+        KeyCode::Char(c) => search.query.push(c),
+        KeyCode::Enter => {
+            if let Some(hit) = search.chosen() {
+                let url = hit.url.clone();
+                app.stream_search = None;
+                crate::app::ytdlp::open(app, tx, &url);
+            } else if search.status != LookupStatus::Searching && !search.query.trim().is_empty() {
+                let query = search.query.trim().to_string();
+                search.searched = query.clone();
+                search.status = LookupStatus::Searching;
+                let invidious = app.invidious_instance.clone();
+                let (tx, client) = (tx.clone(), client.clone());
+                tokio::spawn(async move {
+                    let res = stream_search::search(&client, &query, invidious.as_deref())
+                        .await
+                        .map_err(|e| e.to_string());
+                    if let Err(e) = tx.send(AppEvent::StreamResults(query, res)).await {
+                        tracing::debug!("Channel closed: {}", e);
+                    }
+                });
+            }
+        }
+        _ => {}
+    }
+    true
+}
@@ -0,0 +1,78 @@
+use crate::app::cli::Args;
+use crate::app::events::AppEvent;
+use crate::app::{send_mpd, tags, App, TagToolAction};
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+/// `:name2tag` / `:tag2name` preview. Captures every key while open.
+pub fn handle_tag_tool(
+    key: KeyEvent,
+    app: &mut App,
+    args: &Args,
+    tx: &mpsc::Sender<AppEvent>,
+) -> bool {
+    let Some(tool) = app.tag_tool.as_mut() else {
+        return false;
+    };
+    if tool.progress.is_some() {
+        return true; // Writing: wait for it to finish
+    }
+
+    let last = tool.rows.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.tag_tool = None,
+        KeyCode::Down | KeyCode::Char('j') => tool.scroll = (tool.scroll + 1).min(last),
+        KeyCode::Up | KeyCode::Char('k') => tool.scroll = tool.scroll.saturating_sub(1),
+        KeyCode::Char('g') | KeyCode::Home => tool.scroll = 0,
+        KeyCode::Char('G') | KeyCode::End => tool.scroll = last,
+        KeyCode::Enter => apply(app, args, tx),
+        _ => {}
+    }
+    true
+}
+
+/// Do what the preview shows, skipping the rows it can't do
+fn apply(app: &mut App, args: &Args, tx: &mpsc::Sender<AppEvent>) {
+    let Some(tool) = app.tag_tool.as_mut() else {
+        return;
+    };
+    if tool.ready() == 0 {
+        app.tag_tool = None;
+        return;
+    }
+
+    let jobs: Vec<_> = tool
+        .rows
+        .iter()
+        .filter_map(|row| match &row.action {
+            Ok(TagToolAction::Tags(fields)) => Some((row.file.clone(), fields.clone())),
+            _ => None,
+        })
+        .collect();
+    if !jobs.is_empty() {
+        tool.progress = Some(0);
+        tags::spawn_write(&app.music_directory, jobs, tx.clone());
+        return;
+    }
+
+    let report = tags::rename(&app.music_directory, &tool.rows);
+    app.tag_tool = None;
+    app.marked.clear();
+    // MPD only knows the new names after a database update
+    send_mpd(app, args, |mpd| {
+        if let Err(e) = mpd.update() {
+            tracing::warn!("MPD update after renaming failed: {}", e);
+        }
+    });
+    match report.failed.first() {
+        None => app.show_toast(&t!("tag_tool.renamed", count = report.written)),
+        Some((file, error)) => app.show_error(&t!(
+            "tag_tool.rename_failed",
+            failed = report.failed.len(),
+            total = report.written + report.failed.len(),
+            file = file,
+            error = error
+        )),
+    }
+}
@@ -0,0 +1,23 @@
+use crate::app::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Theme gallery: every move previews the theme, Enter keeps it, Esc reverts
+pub fn handle_theme_picker(key: KeyEvent, app: &mut App) -> bool {
+    let Some(picker) = app.theme_picker.as_mut() else {
+        return false;
+    };
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_theme_picker(false),
+        KeyCode::Enter => app.close_theme_picker(true),
+        KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => picker.select_next(),
+        KeyCode::Up | KeyCode::Char('k') | KeyCode::BackTab => picker.select_prev(),
+        _ => return true,
+    }
+
+    if let Some((_, theme)) = app.theme_picker.as_ref().and_then(|p| p.current()) {
+        let theme = theme.clone();
+        app.set_base_theme(theme);
+    }
+    true
+}
@@ -0,0 +1,100 @@
+#[allow(unused_imports)]
+use crate::app::cli::Args;
+#[cfg(feature = "mpd")]
+use crate::app::library_helpers::{file_details, song_details};
+#[cfg(feature = "mpd")]
+use crate::app::with_mpd;
+use crate::app::{App, LibraryItemType, LibraryMode, TrackDetails, ViewMode};
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Open the track info popup for the selected library song, or the playing one
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+pub async fn open_track_details(app: &mut App, args: &Args) {
+    let selected = if app.view_mode == ViewMode::Library {
+        match app.library_mode {
+            LibraryMode::Queue => app
+                .queue
+                .get(app.library_selected)
+                .map(|q| (q.title.clone(), q.file_path.clone())),
+            _ => app
+                .library_items
+                .get(app.library_selected)
+                .filter(|i| i.item_type == LibraryItemType::Song)
+                .and_then(|i| Some((i.name.clone(), i.path.clone()?))),
+        }
+    } else {
+        None
+    };
+    let (title, file) = match selected {
+        Some((title, file)) => (title, Some(file)),
+        None => (
+            app.track
+                .as_ref()
+                .map(|t| t.name.clone())
+                .unwrap_or_default(),
+            app.playing_file().map(str::to_string),
+        ),
+    };
+
+    let mut sections = Vec::new();
+    #[cfg(feature = "mpd")]
+    if let Some(file) = file.filter(|_| !args.controller) {
+        let full_path = std::path::Path::new(&app.music_directory).join(&file);
+        sections = match file_details(&full_path) {
+            Some(sections) => sections,
+            None => with_mpd(app, args, move |mpd| {
+                mpd.lsinfo(&mpd::Song {
+                    file: file.clone(),
+                    ..Default::default()
+                })
+            })
+            .await
+            .and_then(|res| res.ok())
+            .and_then(|songs| songs.into_iter().next())
+            .map(|song| song_details(&song))
+            .unwrap_or_default(),
+        };
+    }
+
+    // Controller mode (or nothing readable): what the player reported
+    if sections.is_empty() {
+        let Some(track) = app.track.as_ref() else {
+            app.show_toast(&t!("toast.no_track"));
+            return;
+        };
+        sections.push((
+            "Tags".to_string(),
+            vec![
+                ("Title".to_string(), track.name.clone()),
+                ("Artist".to_string(), track.artist.clone()),
+                ("Album".to_string(), track.album.clone()),
+                ("Source".to_string(), track.source.clone()),
+            ],
+        ));
+    }
+
+    app.track_details = Some(TrackDetails {
+        title,
+        sections,
+        scroll: 0,
+    });
+}
+
+/// Track info popup: j/k scroll. Captures every key while open.
+pub fn handle_track_details(key: KeyEvent, app: &mut App) -> bool {
+    let Some(details) = app.track_details.as_mut() else {
+        return false;
+    };
+    let close = app.keys.matches(key, &app.keys.track_info);
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.track_details = None,
+        _ if close => app.track_details = None,
+        KeyCode::Down | KeyCode::Char('j') => details.scroll += 1,
+        KeyCode::Up | KeyCode::Char('k') => details.scroll = details.scroll.saturating_sub(1),
+        KeyCode::Char('g') | KeyCode::Home => details.scroll = 0,
+        _ => {}
+    }
+    true
+}
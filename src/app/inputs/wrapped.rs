@@ -0,0 +1,58 @@
+use crate::app::history;
+use crate::app::wrapped::{self, Wrapped};
+use crate::app::App;
+use crate::t;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// `:wrapped [year]`: this year's report, or the latest year with history
+/// when this one has none yet
+pub fn open_wrapped(app: &mut App, year: Option<i32>) {
+    let plays = history::load(&history::path());
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let wanted = year.unwrap_or_else(|| wrapped::local(now).year);
+    let mut report = Wrapped::build(&plays, wanted, wrapped::local);
+    if report.plays == 0 && year.is_none() {
+        if let Some(&latest) = report.years.last() {
+            report = Wrapped::build(&plays, latest, wrapped::local);
+        }
+    }
+    if report.plays == 0 {
+        app.show_toast(&t!("toast.wrapped_empty", year = wanted));
+        return;
+    }
+    app.wrapped = Some(report);
+}
+
+/// Wrapped popup: ←/→ change the year, e saves it. Captures every key while
+/// open.
+pub fn handle_wrapped(key: KeyEvent, app: &mut App) -> bool {
+    let Some(report) = app.wrapped.as_ref() else {
+        return false;
+    };
+    let position = report.years.iter().position(|&y| y == report.year);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => app.wrapped = None,
+        KeyCode::Left | KeyCode::Char('h') => {
+            if let Some(&year) = position.and_then(|i| report.years.get(i.checked_sub(1)?)) {
+                open_wrapped(app, Some(year));
+            }
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            if let Some(&year) = position.and_then(|i| report.years.get(i + 1)) {
+                open_wrapped(app, Some(year));
+            }
+        }
+        KeyCode::Char('e') => {
+            let dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
+            match report.save_in(&dir) {
+                Ok(path) => app.show_toast(&t!("toast.wrapped_saved", path = path.display())),
+                Err(e) => app.show_error(&t!("error.generic", error = e)),
+            }
+        }
+        _ => {}
+    }
+    true
+}
@@ -11,8 +11,11 @@ pub struct KeyConfig {
     pub prev_track: String,
     pub volume_up: String,
     pub volume_down: String,
+    pub mute: String,
+    pub duck: String,
     pub toggle_keyhints: String,
     pub toggle_audio_info: String,
+    pub track_info: String,
     pub search_global: String,
 
     // View Switching
@@ -20,13 +23,33 @@ pub struct KeyConfig {
     pub view_visualizer: String,
     pub view_library: String,
     pub view_eq: String,
+    pub view_info: String,
+    pub view_related: String,
+
+    // Workspaces
+    pub workspace_1: String,
+    pub workspace_2: String,
+    pub workspace_3: String,
+    pub workspace_4: String,
 
     // Artwork
     pub cycle_art: String,
+    pub fullscreen_art: String,
+    pub theme_picker: String,
+    pub command_line: String,
+    pub palette: String,
+
+    // Panes
+    pub pane_left: String,
+    pub pane_right: String,
+    pub pane_swap: String,
+    pub split_view: String,
+    pub split_focus: String,
 
     // Seek
     pub seek_forward: String,
     pub seek_backward: String,
+    pub seek_mode: String,
 
     // Navigation (Shared)
     pub nav_up: String,
@@ -37,6 +60,10 @@ pub struct KeyConfig {
     pub nav_left_alt: String,
     pub nav_right: String,
     pub nav_right_alt: String,
+    pub page_up: String,
+    pub page_down: String,
+    pub jump_top: String, // pressed twice (gg)
+    pub jump_bottom: String,
 
     // Library
     pub enter_dir: String,
@@ -45,15 +72,31 @@ pub struct KeyConfig {
     pub add_to_queue: String,
     pub save_playlist: String,
     pub rename_playlist: String,
+    pub import_playlist: String,
+    pub export_playlist: String,
+    pub download: String,
     pub delete_item: String,
     pub edit_tags: String,
+    pub mark: String,
     pub move_down: String,
     pub move_up: String,
     pub tab_next: String,
     pub tab_prev: String,
+    pub jump_to_playing: String,
+    pub follow_playback: String,
+    pub fold_album: String,
 
     // Lyrics
     pub seek_to_line: String,
+    pub lyrics_sync_earlier: String,
+    pub lyrics_sync_later: String,
+    pub lyrics_edit: String,
+    pub lyrics_retime: String,
+    pub lyrics_save: String,
+    pub lyrics_romanize: String,
+    pub lyrics_lookup: String,
+    pub search_next: String,
+    pub search_prev: String,
 
     // EQ
     pub band_next: String,
@@ -67,6 +110,7 @@ pub struct KeyConfig {
     pub prev_preset: String, // uses tab_prev
     pub save_preset: String,
     pub delete_preset: String,
+    pub import_preset: String,
     pub preamp_up: String,
     pub preamp_down: String,
     pub balance_right: String,
@@ -75,10 +119,33 @@ pub struct KeyConfig {
     pub replay_gain: String,
     pub device_next: String,
     pub device_prev: String,
+    pub ab_compare: String,
+    pub ab_store: String,
+    pub spectrum_overlay: String,
+    pub output_depth: String,
+    pub output_dither: String,
+    pub output_rate: String,
 
     // MPD
     pub shuffle: String,
+    pub shuffle_mode: String,
     pub repeat: String,
+    pub finish_album: String,
+    pub pitch_up: String,
+    pub pitch_down: String,
+    pub silence_trim: String,
+    pub ab_loop: String,
+    pub repeat_once: String,
+    pub resume: String,
+    pub chapters: String,
+    pub bookmark: String,
+    pub marker_next: String,
+    pub marker_prev: String,
+    pub mpd_console: String,
+    pub log_view: String,
+    pub retry: String,
+    pub undo: String,
+    pub redo: String,
 }
 
 impl Default for KeyConfig {
@@ -90,19 +157,40 @@ impl Default for KeyConfig {
             prev_track: "p".to_string(),
             volume_up: "+".to_string(),
             volume_down: "-".to_string(),
+            mute: "m".to_string(),
+            duck: "Ctrl+d".to_string(),
             toggle_keyhints: "?".to_string(),
             toggle_audio_info: "i".to_string(),
+            track_info: "I".to_string(),
             search_global: "/".to_string(),
 
             view_lyrics: "1".to_string(),
             view_visualizer: "2".to_string(),
             view_library: "3".to_string(),
             view_eq: "4".to_string(),
+            view_info: "5".to_string(),
+            view_related: "6".to_string(),
+
+            workspace_1: "Alt+1".to_string(),
+            workspace_2: "Alt+2".to_string(),
+            workspace_3: "Alt+3".to_string(),
+            workspace_4: "Alt+4".to_string(),
 
             cycle_art: "A".to_string(),
+            fullscreen_art: "f".to_string(),
+            theme_picker: "C".to_string(),
+            command_line: ":".to_string(),
+            palette: "Ctrl+p".to_string(),
+
+            pane_left: "Ctrl+h".to_string(),
+            pane_right: "Ctrl+l".to_string(),
+            pane_swap: "Ctrl+s".to_string(),
+            split_view: "|".to_string(),
+            split_focus: "Ctrl+w".to_string(),
 
             seek_forward: "l".to_string(),
             seek_backward: "h".to_string(),
+            seek_mode: "Ctrl+g".to_string(),
 
             nav_up: "k".to_string(),
             nav_up_alt: "Up".to_string(),
@@ -112,6 +200,10 @@ impl Default for KeyConfig {
             nav_left_alt: "Left".to_string(),
             nav_right: "l".to_string(),
             nav_right_alt: "Right".to_string(),
+            page_up: "PageUp".to_string(),
+            page_down: "PageDown".to_string(),
+            jump_top: "g".to_string(),
+            jump_bottom: "G".to_string(),
 
             enter_dir: "Enter".to_string(),
             back_dir: "Backspace".to_string(),
@@ -119,14 +211,30 @@ impl Default for KeyConfig {
             add_to_queue: "a".to_string(),
             save_playlist: "s".to_string(),
             rename_playlist: "r".to_string(),
+            import_playlist: "P".to_string(),
+            export_playlist: "E".to_string(),
+            download: "W".to_string(),
             delete_item: "d".to_string(),
             edit_tags: "t".to_string(),
+            mark: "v".to_string(),
             move_down: "J".to_string(),
             move_up: "K".to_string(),
             tab_next: "Tab".to_string(),
             tab_prev: "BackTab".to_string(),
+            jump_to_playing: "o".to_string(),
+            follow_playback: "U".to_string(),
+            fold_album: "c".to_string(),
 
             seek_to_line: "Enter".to_string(),
+            lyrics_sync_earlier: "<".to_string(),
+            lyrics_sync_later: ">".to_string(),
+            lyrics_edit: "e".to_string(),
+            lyrics_retime: "t".to_string(),
+            lyrics_save: "w".to_string(),
+            lyrics_romanize: "r".to_string(),
+            lyrics_lookup: "L".to_string(),
+            search_next: "n".to_string(),
+            search_prev: "N".to_string(),
 
             band_next: "l".to_string(),
             band_prev: "h".to_string(),
@@ -139,6 +247,7 @@ impl Default for KeyConfig {
             prev_preset: "BackTab".to_string(),
             save_preset: "S".to_string(),
             delete_preset: "X".to_string(),
+            import_preset: "P".to_string(),
             preamp_up: "g".to_string(),
             preamp_down: "G".to_string(),
             balance_right: "b".to_string(),
@@ -147,15 +256,67 @@ impl Default for KeyConfig {
             replay_gain: "R".to_string(),
             device_next: "d".to_string(),
             device_prev: "D".to_string(),
+            ab_compare: "a".to_string(),
+            ab_store: "v".to_string(),
+            spectrum_overlay: "w".to_string(),
+            output_depth: "o".to_string(),
+            output_dither: "t".to_string(),
+            output_rate: "H".to_string(),
 
             shuffle: "z".to_string(),
+            shuffle_mode: "Z".to_string(),
             repeat: "x".to_string(),
+            finish_album: "F".to_string(),
+            pitch_up: "]".to_string(),
+            pitch_down: "[".to_string(),
+            silence_trim: "T".to_string(),
+            ab_loop: "O".to_string(),
+            repeat_once: "Ctrl+x".to_string(),
+            resume: "Ctrl+o".to_string(),
+            chapters: "M".to_string(),
+            bookmark: "Ctrl+b".to_string(),
+            marker_next: ".".to_string(),
+            marker_prev: ",".to_string(),
+            mpd_console: "`".to_string(),
+            log_view: "F12".to_string(),
+            retry: "Ctrl+t".to_string(),
+            undo: "u".to_string(),
+            redo: "Ctrl+r".to_string(),
         }
     }
 }
 
 impl KeyConfig {
     pub fn matches(&self, event: KeyEvent, key_str: &str) -> bool {
+        // "Ctrl+x": the same key with Control held
+        if let Some(rest) = key_str.strip_prefix("Ctrl+") {
+            return event.modifiers.contains(KeyModifiers::CONTROL)
+                && self.matches(
+                    KeyEvent {
+                        modifiers: event.modifiers - KeyModifiers::CONTROL,
+                        ..event
+                    },
+                    rest,
+                );
+        }
+        if let Some(rest) = key_str.strip_prefix("Alt+") {
+            return event.modifiers.contains(KeyModifiers::ALT)
+                && self.matches(
+                    KeyEvent {
+                        modifiers: event.modifiers - KeyModifiers::ALT,
+                        ..event
+                    },
+                    rest,
+                );
+        }
+        // Plain bindings never fire with Control or Alt held (Ctrl+h is not h)
+        if event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return false;
+        }
+
         match key_str {
             "Space" => event.code == KeyCode::Char(' '),
             "Enter" => event.code == KeyCode::Enter,
@@ -167,6 +328,12 @@ impl KeyConfig {
             "Down" => event.code == KeyCode::Down,
             "Left" => event.code == KeyCode::Left,
             "Right" => event.code == KeyCode::Right,
+            "PageUp" => event.code == KeyCode::PageUp,
+            "PageDown" => event.code == KeyCode::PageDown,
+            // Function keys: "F1" ... "F12"
+            s if s.len() > 1 && s.starts_with('F') => s[1..]
+                .parse::<u8>()
+                .is_ok_and(|n| event.code == KeyCode::F(n)),
             s if s.len() == 1 => {
                 if let Some(ch) = s.chars().next() {
                     // Check for shift modifier if char is uppercase
@@ -195,6 +362,8 @@ impl KeyConfig {
             "Right" => "→".to_string(),
             "BackTab" => "S-Tab".to_string(), // Shift+Tab
             "Backspace" => "Bksp".to_string(),
+            "PageUp" => "PgUp".to_string(),
+            "PageDown" => "PgDn".to_string(),
             _ => key_str.to_string(),
         }
     }
@@ -266,6 +435,8 @@ mod tests {
         assert!(cfg.matches(key(KeyCode::Down), &cfg.nav_down_alt));
         assert!(cfg.matches(key(KeyCode::Left), &cfg.nav_left_alt));
         assert!(cfg.matches(key(KeyCode::Right), &cfg.nav_right_alt));
+        assert!(cfg.matches(key(KeyCode::PageUp), &cfg.page_up));
+        assert!(cfg.matches(key(KeyCode::PageDown), &cfg.page_down));
     }
 
     #[test]
@@ -285,6 +456,42 @@ mod tests {
         assert!(cfg.matches(key(KeyCode::Char('A')), &cfg.cycle_art));
     }
 
+    #[test]
+    fn test_matches_ctrl_combos() {
+        let cfg = KeyConfig::default();
+        let ctrl_h = KeyEvent {
+            modifiers: KeyModifiers::CONTROL,
+            ..key(KeyCode::Char('h'))
+        };
+        assert!(cfg.matches(ctrl_h, &cfg.pane_left));
+        assert!(!cfg.matches(ctrl_h, &cfg.seek_backward));
+        assert!(!cfg.matches(key(KeyCode::Char('h')), &cfg.pane_left));
+
+        // Seek mode works everywhere, so it can't share `s` with the Library
+        let ctrl_g = KeyEvent {
+            modifiers: KeyModifiers::CONTROL,
+            ..key(KeyCode::Char('g'))
+        };
+        assert!(cfg.matches(ctrl_g, &cfg.seek_mode));
+        assert!(!cfg.matches(key(KeyCode::Char('s')), &cfg.seek_mode));
+
+        let alt_2 = KeyEvent {
+            modifiers: KeyModifiers::ALT,
+            ..key(KeyCode::Char('2'))
+        };
+        assert!(cfg.matches(alt_2, &cfg.workspace_2));
+        assert!(!cfg.matches(alt_2, &cfg.view_visualizer));
+    }
+
+    #[test]
+    fn test_matches_function_keys() {
+        let cfg = KeyConfig::default();
+        assert!(cfg.matches(key(KeyCode::F(12)), &cfg.log_view));
+        assert!(!cfg.matches(key(KeyCode::F(1)), &cfg.log_view));
+        // "F" alone is still Shift+f
+        assert!(!cfg.matches(key(KeyCode::F(12)), &cfg.finish_album));
+    }
+
     #[test]
     fn test_no_false_positive() {
         let cfg = KeyConfig::default();
@@ -304,6 +511,7 @@ mod tests {
         assert_eq!(cfg.display("Right"), "→");
         assert_eq!(cfg.display("BackTab"), "S-Tab");
         assert_eq!(cfg.display("Backspace"), "Bksp");
+        assert_eq!(cfg.display("PageDown"), "PgDn");
         assert_eq!(cfg.display("q"), "q");
     }
 }
@@ -0,0 +1,386 @@
+//! The library listing, kept in memory and on disk 📚
+//!
+//! Folders, albums, search and the palette read from this instead of asking
+//! MPD every time. A background task compares MPD's `db_update` stamp every
+//! few seconds and rebuilds the listing after a database update. The copy
+//! in `~/.cache/vyom/library.json` makes browsing instant from the start.
+
+use crate::app::{cue_sheet, sort_directory, AlbumEntry, AlbumKey, LibraryItem, LibraryItemType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Bump when the file layout changes; older files are ignored
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CachedSong {
+    /// Path relative to the music directory
+    pub file: String,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub duration_ms: Option<u64>,
+    /// 0 when unknown
+    pub disc: u32,
+    pub track: u32,
+}
+
+impl CachedSong {
+    /// The title, or the file name for untagged songs
+    pub fn name(&self) -> &str {
+        self.title
+            .as_deref()
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(|| self.file.rsplit('/').next().unwrap_or(&self.file))
+    }
+
+    /// The album it's filed under, when it has an album tag
+    pub fn album_key(&self) -> Option<AlbumKey> {
+        let name = self.album.as_deref().filter(|a| !a.trim().is_empty())?;
+        Some(AlbumKey {
+            artist: self
+                .album_artist
+                .clone()
+                .or_else(|| self.artist.clone())
+                .unwrap_or_default(),
+            name: name.to_string(),
+        })
+    }
+
+    pub fn item(&self) -> LibraryItem {
+        LibraryItem {
+            name: self.name().to_string(),
+            item_type: LibraryItemType::Song,
+            artist: self.artist.clone(),
+            duration_ms: self.duration_ms,
+            path: Some(self.file.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LibraryCache {
+    pub version: u32,
+    /// "host:port" the listing came from
+    pub server: String,
+    /// MPD's `db_update` stamp (seconds since the epoch) when it was read
+    pub db_update: u64,
+    /// In MPD's database order
+    pub songs: Vec<CachedSong>,
+}
+
+impl LibraryCache {
+    /// `~/.cache/vyom/library.json`
+    pub fn path() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from(".cache"))
+            .join("vyom")
+            .join("library.json")
+    }
+
+    /// The saved listing, if it is from `server` and this version of Vyom
+    pub fn load_from(path: &Path, server: &str) -> Option<Self> {
+        let content = std::fs::read(path).ok()?;
+        let cache: Self = serde_json::from_slice(&content).ok()?;
+        (cache.version == VERSION && cache.server == server).then_some(cache)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let content = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, content).map_err(|e| e.to_string())
+    }
+
+    /// Folders, then songs, directly in `path` ("" for the top)
+    pub fn directory(&self, path: &str) -> Vec<LibraryItem> {
+        let prefix = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", path.trim_end_matches('/'))
+        };
+        let mut folders = HashSet::new();
+        let mut items = Vec::new();
+        for song in &self.songs {
+            let Some(rest) = song.file.strip_prefix(&prefix) else {
+                continue;
+            };
+            let name = rest.split('/').next().unwrap_or(rest);
+            if name.starts_with('.') || name.trim().is_empty() {
+                continue;
+            }
+            // Tracks of a CUE sheet here are listed as songs, not a folder
+            let in_sheet =
+                cue_sheet(&song.file).is_some_and(|s| s.len() == prefix.len() + name.len());
+            if name.len() < rest.len() && !in_sheet {
+                if folders.insert(name) {
+                    items.push(LibraryItem {
+                        name: name.to_string(),
+                        item_type: LibraryItemType::Folder,
+                        artist: None,
+                        duration_ms: None,
+                        path: Some(format!("{}{}", prefix, name)),
+                    });
+                }
+            } else {
+                items.push(song.item());
+            }
+        }
+        // Same order as listing through MPD: folders first
+        sort_directory(&mut items);
+        items
+    }
+
+    /// Albums for the cover grid, each with its first song for the artwork
+    pub fn albums(&self) -> Vec<AlbumEntry> {
+        let mut seen = HashSet::new();
+        let mut albums: Vec<AlbumEntry> = self
+            .songs
+            .iter()
+            .filter_map(|song| {
+                let key = song.album_key()?;
+                seen.insert(key.clone()).then(|| AlbumEntry {
+                    name: key.name,
+                    artist: key.artist,
+                    file: song.file.clone(),
+                })
+            })
+            .collect();
+        albums.sort_by(|a, b| {
+            (a.artist.to_lowercase(), a.name.to_lowercase())
+                .cmp(&(b.artist.to_lowercase(), b.name.to_lowercase()))
+        });
+        albums
+    }
+
+    /// Tracks of one album, in disc/track order
+    pub fn album_tracks(&self, album: &AlbumKey) -> Vec<LibraryItem> {
+        let mut songs: Vec<&CachedSong> = self
+            .songs
+            .iter()
+            .filter(|s| s.album_key().as_ref() == Some(album))
+            .collect();
+        songs.sort_by(|a, b| (a.disc, a.track, &a.file).cmp(&(b.disc, b.track, &b.file)));
+        songs.into_iter().map(CachedSong::item).collect()
+    }
+}
+
+/// Every song in MPD's database, with the tags the listings need
+#[cfg(feature = "mpd")]
+pub fn songs(mpd: &mut mpd::Client) -> Result<Vec<CachedSong>, mpd::error::Error> {
+    use crate::app::library_helpers::song_tag;
+
+    let mut query = mpd::Query::new();
+    query.and(mpd::Term::Base, "");
+    // "4" or "4/11"
+    let number = |song: &mpd::Song, tag: &str| {
+        song_tag(song, tag)
+            .and_then(|v| v.split('/').next()?.trim().parse::<u32>().ok())
+            .unwrap_or(0)
+    };
+    Ok(mpd
+        .search(&query, None)?
+        .into_iter()
+        .map(|song| CachedSong {
+            album: song_tag(&song, "Album").map(str::to_string),
+            album_artist: song_tag(&song, "AlbumArtist").map(str::to_string),
+            disc: number(&song, "Disc"),
+            track: number(&song, "Track"),
+            duration_ms: song.duration.map(|d| d.as_millis() as u64),
+            title: song.title,
+            artist: song.artist,
+            file: song.file,
+        })
+        .collect())
+}
+
+/// Read the whole listing from MPD
+#[cfg(feature = "mpd")]
+pub fn fetch(mpd: &mut mpd::Client, server: &str) -> Result<LibraryCache, mpd::error::Error> {
+    let db_update = mpd.stats()?.db_update.as_secs();
+    Ok(LibraryCache {
+        version: VERSION,
+        server: server.to_string(),
+        db_update,
+        songs: songs(mpd)?,
+    })
+}
+
+/// How often the watcher asks MPD whether its database changed
+#[cfg(feature = "mpd")]
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Keep the listing current: re-read it whenever MPD's `db_update` differs
+/// from `known`, save it and hand it to the app as
+/// `AppEvent::LibraryCacheUpdate`
+#[cfg(feature = "mpd")]
+pub fn spawn_watcher(
+    server: String,
+    mut known: Option<u64>,
+    tx: tokio::sync::mpsc::Sender<crate::app::events::AppEvent>,
+) {
+    tokio::spawn(async move {
+        let mut client: Option<mpd::Client> = None;
+        loop {
+            let server_clone = server.clone();
+            let previous = client.take();
+            let checked = tokio::task::spawn_blocking(move || {
+                let mut mpd = match previous {
+                    Some(mpd) => mpd,
+                    None => mpd::Client::connect(server_clone.as_str())?,
+                };
+                let stamp = mpd.stats()?.db_update.as_secs();
+                let cache = if Some(stamp) == known {
+                    None
+                } else {
+                    let cache = fetch(&mut mpd, &server_clone)?;
+                    if let Err(e) = cache.save_to(&LibraryCache::path()) {
+                        tracing::warn!("Could not save the library cache: {}", e);
+                    }
+                    Some(cache)
+                };
+                Ok::<_, mpd::error::Error>((mpd, cache))
+            })
+            .await;
+
+            match checked {
+                Ok(Ok((mpd, cache))) => {
+                    client = Some(mpd);
+                    if let Some(cache) = cache {
+                        known = Some(cache.db_update);
+                        tracing::info!("Library cache: {} songs", cache.songs.len());
+                        let event = crate::app::events::AppEvent::LibraryCacheUpdate(
+                            std::sync::Arc::new(cache),
+                        );
+                        if tx.send(event).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                // MPD away: try again with a fresh connection next time
+                Ok(Err(e)) => tracing::debug!("Library cache check failed: {}", e),
+                Err(e) => tracing::debug!("Library cache task failed: {}", e),
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(file: &str, album: &str, track: u32) -> CachedSong {
+        CachedSong {
+            file: file.to_string(),
+            title: Some(format!("T{}", track)),
+            album: Some(album.to_string()),
+            artist: Some("Band".to_string()),
+            track,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cache_listings() {
+        let cache = LibraryCache {
+            version: VERSION,
+            server: "localhost:6600".into(),
+            db_update: 1,
+            songs: vec![
+                song("Band/B/2.flac", "B", 2),
+                song("Band/B/1.flac", "B", 1),
+                song("Band/A/1.flac", "A", 1),
+                song("loose.mp3", "", 0),
+                song("Band/.hidden/x.flac", "C", 1),
+            ],
+        };
+
+        let top = cache.directory("");
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].item_type, LibraryItemType::Folder);
+        assert_eq!(top[0].path.as_deref(), Some("Band"));
+        assert_eq!(top[1].path.as_deref(), Some("loose.mp3"));
+        let band: Vec<_> = cache
+            .directory("Band")
+            .into_iter()
+            .map(|i| i.name)
+            .collect();
+        assert_eq!(band, vec!["A", "B"]);
+        assert!(cache.directory("Nowhere").is_empty());
+
+        // A FLAC+CUE rip: the sheet's tracks stand in for the big file
+        let rip = LibraryCache {
+            songs: vec![
+                song("Live/Live.flac", "Live", 0),
+                song("Live/Live.cue/track0002", "Live", 2),
+                song("Live/Live.cue/track0001", "Live", 1),
+                song("Live/Bonus.flac", "Live", 3),
+                song("Live/Scans/back.flac", "Live", 0),
+            ],
+            ..cache.clone()
+        };
+        let live: Vec<_> = rip
+            .directory("Live")
+            .into_iter()
+            .filter_map(|i| i.path)
+            .collect();
+        assert_eq!(
+            live,
+            vec![
+                "Live/Scans",
+                "Live/Bonus.flac",
+                "Live/Live.cue/track0001",
+                "Live/Live.cue/track0002"
+            ]
+        );
+
+        let albums: Vec<_> = cache.albums().into_iter().map(|a| a.name).collect();
+        assert_eq!(albums, vec!["A", "B", "C"]);
+        let tracks: Vec<_> = cache
+            .album_tracks(&AlbumKey {
+                artist: "Band".into(),
+                name: "B".into(),
+            })
+            .into_iter()
+            .filter_map(|i| i.path)
+            .collect();
+        assert_eq!(tracks, vec!["Band/B/1.flac", "Band/B/2.flac"]);
+
+        // Same title, two album artists: two albums
+        let hits = LibraryCache {
+            songs: vec![
+                CachedSong {
+                    album_artist: Some("Other".into()),
+                    ..song("Other/Hits/1.flac", "Hits", 1)
+                },
+                song("Band/Hits/1.flac", "Hits", 1),
+            ],
+            ..cache.clone()
+        };
+        let albums: Vec<_> = hits.albums().into_iter().map(|a| a.artist).collect();
+        assert_eq!(albums, vec!["Band", "Other"]);
+        let tracks: Vec<_> = hits
+            .album_tracks(&AlbumKey {
+                artist: "Other".into(),
+                name: "Hits".into(),
+            })
+            .into_iter()
+            .filter_map(|i| i.path)
+            .collect();
+        assert_eq!(tracks, vec!["Other/Hits/1.flac"]);
+
+        // Only a file from the same server and layout is used
+        let path = std::env::temp_dir().join(format!("vyom-library-{}.json", std::process::id()));
+        cache.save_to(&path).unwrap();
+        assert_eq!(
+            LibraryCache::load_from(&path, "localhost:6600"),
+            Some(cache)
+        );
+        assert_eq!(LibraryCache::load_from(&path, "nas:6600"), None);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,5 +1,48 @@
 #[cfg(feature = "mpd")]
-use crate::app::{LibraryItem, LibraryItemType};
+use crate::app::cli::Args;
+#[cfg(feature = "mpd")]
+use crate::app::{
+    with_mpd, AlbumEntry, AlbumKey, App, DetailSection, LibraryItem, LibraryItemType, Operation,
+};
+
+/// A folder's contents: from the library cache, else asked from MPD
+#[cfg(feature = "mpd")]
+pub async fn directory_items(app: &mut App, args: &Args, path: &str) -> Option<Vec<LibraryItem>> {
+    if let Some(cache) = &app.library_cache {
+        return Some(cache.directory(path));
+    }
+    let path = path.to_string();
+    with_mpd(app, args, move |mpd| fetch_directory_items(mpd, &path).ok())
+        .await
+        .flatten()
+}
+
+/// The album grid: from the library cache, else asked from MPD
+#[cfg(feature = "mpd")]
+pub async fn albums(app: &mut App, args: &Args) -> Option<Vec<AlbumEntry>> {
+    if let Some(cache) = &app.library_cache {
+        return Some(cache.albums());
+    }
+    with_mpd(app, args, move |mpd| fetch_albums(mpd).ok())
+        .await
+        .flatten()
+}
+
+/// An album's tracks: from the library cache, else asked from MPD
+#[cfg(feature = "mpd")]
+pub async fn album_tracks(
+    app: &mut App,
+    args: &Args,
+    album: &AlbumKey,
+) -> Option<Vec<LibraryItem>> {
+    if let Some(cache) = &app.library_cache {
+        return Some(cache.album_tracks(album));
+    }
+    let album = album.clone();
+    with_mpd(app, args, move |mpd| fetch_album_tracks(mpd, &album).ok())
+        .await
+        .flatten()
+}
 
 // Helper to fetch directory contents (folders + songs)
 #[cfg(feature = "mpd")]
@@ -17,6 +60,35 @@ pub fn fetch_directory_items(
                 continue;
             }
 
+            // A CUE sheet: MPD lists its tracks under it
+            if display_name.to_lowercase().ends_with(".cue") {
+                let sheet = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", path, name)
+                };
+                let tracks = mpd.lsinfo(&mpd::Song {
+                    file: sheet,
+                    ..Default::default()
+                });
+                for song in tracks.into_iter().flatten() {
+                    items.push(LibraryItem {
+                        name: song
+                            .title
+                            .clone()
+                            .filter(|t| !t.trim().is_empty())
+                            .unwrap_or_else(|| {
+                                song.file.rsplit('/').next().unwrap_or("").to_string()
+                            }),
+                        item_type: LibraryItemType::Song,
+                        artist: song.artist.clone(),
+                        duration_ms: song.duration.map(|d| d.as_millis() as u64),
+                        path: Some(song.file),
+                    });
+                }
+                continue;
+            }
+
             if kind == "directory" {
                 let full_path = if path.is_empty() {
                     name.clone()
@@ -66,15 +138,290 @@ pub fn fetch_directory_items(
     }
 
     // Sort: folders first
-    items.sort_by(|a, b| match (&a.item_type, &b.item_type) {
-        (LibraryItemType::Folder, LibraryItemType::Song) => std::cmp::Ordering::Less,
-        (LibraryItemType::Song, LibraryItemType::Folder) => std::cmp::Ordering::Greater,
-        _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-    });
+    crate::app::sort_directory(&mut items);
 
     Ok(items)
 }
 
+// Albums for the cover grid, with the first track of each for its artwork.
+// The crate drops the keys of a grouped `list`, so this reads the song
+// listing once and groups it, like the library cache does.
+#[cfg(feature = "mpd")]
+pub fn fetch_albums(mpd: &mut mpd::Client) -> Result<Vec<AlbumEntry>, mpd::error::Error> {
+    let listing = crate::app::library_cache::LibraryCache {
+        songs: crate::app::library_cache::songs(mpd)?,
+        ..Default::default()
+    };
+    Ok(listing.albums())
+}
+
+/// Songs of one album: its title and album artist both match
+#[cfg(feature = "mpd")]
+pub fn album_query(album: &AlbumKey) -> mpd::Query<'_> {
+    let mut query = mpd::Query::new();
+    query
+        .and(mpd::Term::Tag("Album".into()), album.name.as_str())
+        .and(mpd::Term::Tag("AlbumArtist".into()), album.artist.as_str());
+    query
+}
+
+// Tracks of one album, in disc/track order
+#[cfg(feature = "mpd")]
+pub fn fetch_album_tracks(
+    mpd: &mut mpd::Client,
+    album: &AlbumKey,
+) -> Result<Vec<LibraryItem>, mpd::error::Error> {
+    let mut songs = mpd.find(&album_query(album), None)?;
+    // "4" or "4/11"
+    let number = |song: &mpd::Song, tag: &str| {
+        song_tag(song, tag)
+            .and_then(|v| v.split('/').next()?.trim().parse::<u32>().ok())
+            .unwrap_or(0)
+    };
+    songs.sort_by_key(|s| (number(s, "Disc"), number(s, "Track"), s.file.clone()));
+
+    Ok(songs
+        .into_iter()
+        .map(|song| LibraryItem {
+            name: song
+                .title
+                .clone()
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or_else(|| {
+                    song.file
+                        .rsplit('/')
+                        .next()
+                        .unwrap_or(&song.file)
+                        .to_string()
+                }),
+            item_type: LibraryItemType::Song,
+            artist: song.artist.clone(),
+            duration_ms: song.duration.map(|d| d.as_millis() as u64),
+            path: Some(song.file),
+        })
+        .collect())
+}
+
+/// Reverse a journal entry (`undo`) or apply it again. Refuses instead of
+/// touching the wrong song when the queue changed in between.
+#[cfg(feature = "mpd")]
+pub fn replay(mpd: &mut mpd::Client, op: &Operation, undo: bool) -> Result<(), String> {
+    let song = |file: &str| mpd::Song {
+        file: file.to_string(),
+        ..Default::default()
+    };
+    let err = |e: mpd::error::Error| e.to_string();
+    match (op, undo) {
+        (Operation::QueueDelete { pos, file }, true) => {
+            let len = mpd.status().map_err(err)?.queue_len;
+            mpd.insert(song(file), (*pos).min(len) as usize)
+                .map(drop)
+                .map_err(err)
+        }
+        (Operation::QueueDelete { pos, file }, false) => {
+            expect_at(mpd, *pos, file)?;
+            mpd.delete(*pos).map_err(err)
+        }
+        (Operation::QueueMove { from, to, file }, true) => {
+            expect_at(mpd, *to, file)?;
+            mpd.shift(*to, *from as usize).map_err(err)
+        }
+        (Operation::QueueMove { from, to, file }, false) => {
+            expect_at(mpd, *from, file)?;
+            mpd.shift(*from, *to as usize).map_err(err)
+        }
+        (Operation::QueueClear { files }, true) => {
+            for (pos, file) in files.iter().enumerate() {
+                mpd.insert(song(file), pos).map_err(err)?;
+            }
+            Ok(())
+        }
+        (Operation::QueueClear { files }, false) => {
+            if !same_files(&mpd.queue().map_err(err)?, files) {
+                return Err(crate::t!("error.queue_changed"));
+            }
+            mpd.clear().map_err(err)
+        }
+        (Operation::PlaylistDelete { name, files }, true) => {
+            if mpd
+                .playlists()
+                .map_err(err)?
+                .iter()
+                .any(|p| &p.name == name)
+            {
+                return Err(crate::t!("error.playlist_exists", name = name));
+            }
+            if files.is_empty() {
+                // MPD creates the file, so an empty playlist comes back too
+                return mpd.pl_clear(name).map_err(err);
+            }
+            for file in files {
+                mpd.pl_push(name, song(file)).map_err(err)?;
+            }
+            Ok(())
+        }
+        (Operation::PlaylistDelete { name, files }, false) => {
+            if !same_files(&mpd.playlist(name.as_str()).map_err(err)?, files) {
+                return Err(crate::t!("error.playlist_changed", name = name));
+            }
+            mpd.pl_remove(name).map_err(err)
+        }
+    }
+}
+
+/// Whether the playing song is the last of its album: MPD moves on to
+/// another album's song next, or to nothing
+#[cfg(feature = "mpd")]
+pub fn album_ends_after_current(mpd: &mut mpd::Client) -> Result<bool, mpd::error::Error> {
+    let Some(current) = mpd.currentsong()? else {
+        return Ok(false);
+    };
+    let Some(next) = mpd.status()?.nextsong else {
+        return Ok(true);
+    };
+    let next = mpd.songs(next.pos)?;
+    Ok(next
+        .first()
+        .is_none_or(|song| song_tag(song, "Album") != song_tag(&current, "Album")))
+}
+
+/// Make sure queue position `pos` still holds `file`
+#[cfg(feature = "mpd")]
+fn expect_at(mpd: &mut mpd::Client, pos: u32, file: &str) -> Result<(), String> {
+    match mpd.songs(pos) {
+        Ok(songs) if songs.first().is_some_and(|s| s.file == file) => Ok(()),
+        _ => Err(crate::t!("error.queue_changed")),
+    }
+}
+
+/// `songs` are exactly `files`, in order
+#[cfg(feature = "mpd")]
+fn same_files(songs: &[mpd::Song], files: &[String]) -> bool {
+    songs.len() == files.len() && songs.iter().zip(files).all(|(s, f)| &s.file == f)
+}
+
+#[cfg(feature = "mpd")]
+pub fn song_tag<'a>(song: &'a mpd::Song, key: &str) -> Option<&'a str> {
+    song.tags
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .map(|(_, v)| v.as_str())
+}
+
+/// Tags, audio properties and file facts for the track info popup
+#[cfg(feature = "mpd")]
+pub fn file_details(path: &std::path::Path) -> Option<Vec<DetailSection>> {
+    use lofty::file::{AudioFile, TaggedFileExt};
+
+    let tagged = lofty::read_from_path(path).ok()?;
+    let mut tags: Vec<(String, String)> = Vec::new();
+    let mut pictures = 0;
+    for tag in tagged.tags() {
+        pictures += tag.pictures().len();
+        for item in tag.items() {
+            let Some(value) = item.value().text() else {
+                continue;
+            };
+            let label = pretty_key(item.key());
+            // ID3v2 + APE on one file often repeat each other
+            if !tags.iter().any(|(l, v)| *l == label && v == value) {
+                tags.push((label, value.to_string()));
+            }
+        }
+    }
+    if pictures > 0 {
+        tags.push(("Pictures".to_string(), pictures.to_string()));
+    }
+
+    let props = tagged.properties();
+    let secs = props.duration().as_secs();
+    let mut audio = vec![
+        ("Format".to_string(), format!("{:?}", tagged.file_type())),
+        (
+            "Duration".to_string(),
+            format!("{}:{:02}", secs / 60, secs % 60),
+        ),
+    ];
+    if let Some(kbps) = props.audio_bitrate() {
+        audio.push(("Bitrate".to_string(), format!("{} kbps", kbps)));
+    }
+    if let Some(rate) = props.sample_rate() {
+        audio.push((
+            "Sample rate".to_string(),
+            format!("{:.1} kHz", rate as f32 / 1000.0),
+        ));
+    }
+    if let Some(depth) = props.bit_depth() {
+        audio.push(("Bit depth".to_string(), format!("{} bit", depth)));
+    }
+    if let Some(channels) = props.channels() {
+        audio.push(("Channels".to_string(), channels.to_string()));
+    }
+
+    let mut file = vec![("Path".to_string(), path.display().to_string())];
+    if let Ok(meta) = std::fs::metadata(path) {
+        file.push((
+            "Size".to_string(),
+            format!("{:.1} MB", meta.len() as f64 / 1_048_576.0),
+        ));
+    }
+
+    Some(vec![
+        ("Tags".to_string(), tags),
+        ("Audio".to_string(), audio),
+        ("File".to_string(), file),
+    ])
+}
+
+/// What MPD knows, for files Vyom can't open itself (remote servers)
+#[cfg(feature = "mpd")]
+pub fn song_details(song: &mpd::Song) -> Vec<DetailSection> {
+    let mut tags: Vec<(String, String)> = Vec::new();
+    if let Some(title) = &song.title {
+        tags.push(("Title".to_string(), title.clone()));
+    }
+    if let Some(artist) = &song.artist {
+        tags.push(("Artist".to_string(), artist.clone()));
+    }
+    tags.extend(song.tags.iter().cloned());
+
+    let mut file = vec![("Path".to_string(), song.file.clone())];
+    if let Some(d) = song.duration {
+        let secs = d.as_secs();
+        file.push((
+            "Duration".to_string(),
+            format!("{}:{:02}", secs / 60, secs % 60),
+        ));
+    }
+    if let Some(modified) = &song.last_mod {
+        file.push(("Modified".to_string(), modified.clone()));
+    }
+    vec![("Tags".to_string(), tags), ("File".to_string(), file)]
+}
+
+/// `MusicBrainzRecordingId` -> `MusicBrainz Recording ID`
+#[cfg(feature = "mpd")]
+fn pretty_key(key: &lofty::tag::ItemKey) -> String {
+    if let lofty::tag::ItemKey::Unknown(raw) = key {
+        return raw.clone();
+    }
+    let raw = format!("{:?}", key);
+    let mut out = String::new();
+    let mut prev_lower = false;
+    for c in raw.chars() {
+        if c.is_uppercase() && prev_lower {
+            out.push(' ');
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        out.push(c);
+    }
+    let out = out.replace("Music Brainz", "MusicBrainz");
+    match out.strip_suffix(" Id") {
+        Some(base) => format!("{} ID", base),
+        None => out,
+    }
+}
+
 // Recursive Add Helper
 #[cfg(feature = "mpd")]
 pub fn queue_folder_recursive(mpd: &mut mpd::Client, path: &str) -> Result<(), mpd::error::Error> {
@@ -1,40 +1,155 @@
+//! Which Vyom plays the audio 🔒
+//!
+//! The first instance takes an advisory lock on a per-user file and writes
+//! its PID and control socket into it; later ones run in Shared Audio Mode.
+//! The lock goes away with the process, so a crash never leaves Vyom
+//! silent. The file lives in `$XDG_RUNTIME_DIR` (or the temp dir, with the
+//! user id in its name) so users on one machine don't block each other.
+
 use fs2::FileExt;
 use std::fs::File;
 use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// `$XDG_RUNTIME_DIR/vyom-audio.lock`, else one per user in the temp dir
+pub fn lock_path() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join("vyom-audio.lock"),
+        #[cfg(unix)]
+        // SAFETY: getuid has no preconditions
+        None => std::env::temp_dir().join(format!("vyom-{}-audio.lock", unsafe { libc::getuid() })),
+        #[cfg(not(unix))]
+        None => std::env::temp_dir().join("vyom-audio.lock"),
+    }
+}
+
+/// What the lock file says about its owner
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockHolder {
+    pub pid: u32,
+    /// Its control socket, for Shared Audio Mode to subscribe to
+    pub socket: PathBuf,
+}
+
+impl LockHolder {
+    fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.lines();
+        let pid = lines.next()?.trim().parse().ok()?;
+        let socket = PathBuf::from(lines.next()?.trim());
+        Some(Self { pid, socket })
+    }
+}
+
+/// Held while this instance plays the audio
+pub struct AudioLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl AudioLock {
+    /// Give the lock up and remove the file
+    pub fn release(self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            tracing::warn!("Failed to remove audio lock file: {}", e);
+        }
+        if let Err(e) = FileExt::unlock(&self.file) {
+            tracing::debug!("Failed to unlock audio lock file: {}", e);
+        }
+    }
+}
+
+pub fn try_acquire_audio_lock() -> Option<AudioLock> {
+    acquire_at(&lock_path(), &crate::app::remote::socket_path())
+}
+
+/// Lock `path`, writing our PID and `socket`. `None` while a live
+/// instance holds it.
+pub fn acquire_at(path: &Path, socket: &Path) -> Option<AudioLock> {
+    for _ in 0..2 {
+        // Don't truncate before we hold it: the owner's details are in there
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map_err(|e| tracing::warn!("Failed to open {}: {}", path.display(), e))
+            .ok()?;
 
-const LOCK_FILE_PATH: &str = "/tmp/vyom_audio.lock";
-
-pub fn try_acquire_audio_lock() -> Option<File> {
-    // 1. Create/Open lock file
-    let mut file = std::fs::OpenOptions::new()
-        .read(true)
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(LOCK_FILE_PATH)
-        .ok()?;
-
-    // 2. Try to acquire exclusive lock 🔒
-    // If this fails, another process holds the lock
-    if file.try_lock_exclusive().is_ok() {
-        // We got the lock!
-        // Truncate file and write our PID (informational)
-        if file.set_len(0).is_ok() {
-            let pid = std::process::id();
-            if let Err(e) = write!(file, "{}", pid) {
-                tracing::warn!("Failed to write to PID lock file: {}", e);
+        if file.try_lock_exclusive().is_ok() {
+            if file.set_len(0).is_ok() {
+                let written = writeln!(file, "{}", std::process::id())
+                    .and_then(|_| writeln!(file, "{}", socket.display()));
+                if let Err(e) = written {
+                    tracing::warn!("Failed to write to audio lock file: {}", e);
+                }
             }
+            return Some(AudioLock {
+                file,
+                path: path.to_path_buf(),
+            });
         }
 
-        // Return the file handle. The lock is released when the file is closed (dropped).
-        return Some(file);
+        // Locked by a process that no longer exists (filesystems where the
+        // lock outlives it): clear it and try once more
+        match holder_at(path) {
+            Some(holder) if !is_alive(holder.pid) => {
+                tracing::info!("Removing stale audio lock of PID {}", holder.pid);
+                let _ = std::fs::remove_file(path);
+            }
+            _ => return None,
+        }
     }
-
     None
 }
 
-pub fn release_audio_lock() {
-    if let Err(e) = std::fs::remove_file(LOCK_FILE_PATH) {
-        tracing::warn!("Failed to remove PID lock file: {}", e);
+/// Who holds the audio lock, if anyone wrote it
+pub fn holder() -> Option<LockHolder> {
+    holder_at(&lock_path())
+}
+
+fn holder_at(path: &Path) -> Option<LockHolder> {
+    LockHolder::parse(&std::fs::read_to_string(path).ok()?)
+}
+
+#[cfg(unix)]
+fn is_alive(pid: u32) -> bool {
+    // SAFETY: signal 0 only checks that the process exists
+    let result = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn is_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_is_exclusive_and_records_the_owner() {
+        let path = std::env::temp_dir().join(format!("vyom-lock-{}.lock", std::process::id()));
+        let socket = PathBuf::from("/run/user/1000/vyom.sock");
+
+        // Left behind by a crashed instance: no lock held, so it's ours
+        std::fs::write(&path, "999999999\n/old.sock\n").unwrap();
+        let lock = acquire_at(&path, &socket).expect("stale file is taken over");
+        assert_eq!(
+            holder_at(&path),
+            Some(LockHolder {
+                pid: std::process::id(),
+                socket: socket.clone()
+            })
+        );
+
+        // A second instance runs in Shared Audio Mode and keeps the details
+        assert!(acquire_at(&path, Path::new("/other.sock")).is_none());
+        assert_eq!(holder_at(&path).unwrap().socket, socket);
+
+        lock.release();
+        assert!(!path.exists());
+        acquire_at(&path, &socket).unwrap().release();
     }
 }
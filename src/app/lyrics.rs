@@ -16,12 +16,19 @@ pub struct LrclibResponse {
     #[serde(default)]
     pub instrumental: bool,
     pub duration: Option<f64>,
+    #[serde(rename = "trackName")]
+    pub track_name: Option<String>,
+    #[serde(rename = "artistName")]
+    pub artist_name: Option<String>,
+    #[serde(rename = "albumName")]
+    pub album_name: Option<String>,
 }
 
-#[derive(Debug)]
-
+#[derive(Debug, Clone)]
 pub enum LyricsFetchResult {
     Found(Vec<LyricLine>, String),
+    /// Lyrics without timestamps (one entry per line)
+    Plain(Vec<String>, String),
     Instrumental,
     None,
 }
@@ -32,13 +39,88 @@ pub struct LyricLine {
     pub text: String,
 }
 
+/// One result of a manual lyrics search
+#[derive(Debug, Clone)]
+pub struct LyricsCandidate {
+    pub artist: String,
+    pub title: String,
+    pub album: String,
+    pub duration_secs: Option<f64>,
+    /// `Found` or `Plain`
+    pub lyrics: LyricsFetchResult,
+}
+
+impl LyricsCandidate {
+    pub fn is_synced(&self) -> bool {
+        matches!(self.lyrics, LyricsFetchResult::Found(..))
+    }
+}
+
+/// On-disk cache entry (older caches hold just the synced lines)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum CachedLyrics {
+    Synced(Vec<LyricLine>),
+    Plain { plain: Vec<String> },
+}
+
+/// Latin transliteration of a lyric line (kana, hangul, hanzi, cyrillic, ...).
+/// `None` when the line is already Latin script and needs no second line.
+pub fn romanize(text: &str) -> Option<String> {
+    // Latin, Latin-1 and Latin Extended letters read fine as they are
+    let foreign = text.chars().any(|c| c.is_alphabetic() && c as u32 > 0x024F);
+    if !foreign {
+        return None;
+    }
+    let mut out = String::new();
+    for c in text.chars() {
+        if (c as u32) < 0x80 {
+            out.push(c);
+            continue;
+        }
+        let ascii = any_ascii::any_ascii_char(c);
+        // Hanzi/kanji are one syllable each: keep them apart ("ni hao", not "nihao")
+        let han = matches!(c as u32, 0x3400..=0x4DBF | 0x4E00..=0x9FFF);
+        if han && !ascii.is_empty() && out.chars().last().is_some_and(|p| p.is_alphanumeric()) {
+            out.push(' ');
+        }
+        out.push_str(&ascii.to_lowercase());
+    }
+    let out = out.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!out.is_empty()).then_some(out)
+}
+
+/// Where each line of unsynced lyrics is drawn: with romanization on, a
+/// line that has one takes a second row for it
+pub fn plain_rows(lines: &[String], romanized: bool) -> Vec<usize> {
+    let mut row = 0;
+    lines
+        .iter()
+        .map(|line| {
+            let at = row;
+            row += 1 + usize::from(romanized && romanize(line).is_some());
+            at
+        })
+        .collect()
+}
+
 pub struct LyricsFetcher {
     client: Client,
+    /// Extra folder searched for `.lrc` files
+    lyrics_dir: Option<PathBuf>,
 }
 
 impl LyricsFetcher {
     pub fn new(client: Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            lyrics_dir: None,
+        }
+    }
+
+    pub fn with_lyrics_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.lyrics_dir = dir;
+        self
     }
 
     fn get_cache_path(artist: &str, title: &str) -> Option<PathBuf> {
@@ -61,7 +143,7 @@ impl LyricsFetcher {
         Some(path)
     }
 
-    fn load_from_cache(path: &PathBuf) -> Option<Vec<LyricLine>> {
+    fn load_from_cache(path: &PathBuf) -> Option<CachedLyrics> {
         if path.exists() {
             if let Ok(file) = fs::File::open(path) {
                 if let Ok(lyrics) = serde_json::from_reader(file) {
@@ -72,7 +154,7 @@ impl LyricsFetcher {
         None
     }
 
-    fn save_to_cache(path: &PathBuf, lyrics: &Vec<LyricLine>) {
+    fn save_to_cache(path: &PathBuf, lyrics: &impl Serialize) {
         if let Some(parent) = path.parent() {
             if let Err(e) = fs::create_dir_all(parent) {
                 tracing::debug!("Failed to create lyrics cache dir: {}", e);
@@ -145,10 +227,16 @@ impl LyricsFetcher {
         duration_ms: u64,
         file_path: Option<&String>,
     ) -> Result<LyricsFetchResult> {
-        // 0. Check Local File (Embedded or LRC) 📂 - BLOCKING WRAPPER
-        if let Some(path_str) = file_path {
-            let p = path_str.clone();
-            let local_res = tokio::task::spawn_blocking(move || Self::fetch_impl_local(&p)).await?;
+        // 0. Check Local Files (LRC or Embedded) 📂 - BLOCKING WRAPPER
+        // The lyrics folder works without a file path too (controller mode)
+        if file_path.is_some() || self.lyrics_dir.is_some() {
+            let p = file_path.cloned();
+            let dir = self.lyrics_dir.clone();
+            let (a, t) = (artist.to_string(), title.to_string());
+            let local_res = tokio::task::spawn_blocking(move || {
+                Self::fetch_impl_local(p.as_deref(), dir.as_deref(), &a, &t)
+            })
+            .await?;
 
             if let Some(res) = local_res {
                 // local_res is already LyricsFetchResult, so it has the source inside
@@ -171,8 +259,14 @@ impl LyricsFetcher {
         })
         .await?;
 
-        if let Some(lyrics) = cached_lyrics {
-            return Ok(LyricsFetchResult::Found(lyrics, "Disk Cache".to_string()));
+        match cached_lyrics {
+            Some(CachedLyrics::Synced(lines)) => {
+                return Ok(LyricsFetchResult::Found(lines, "Disk Cache".to_string()));
+            }
+            Some(CachedLyrics::Plain { plain }) => {
+                return Ok(LyricsFetchResult::Plain(plain, "Disk Cache".to_string()));
+            }
+            None => {}
         }
 
         let url = "https://lrclib.net/api/get";
@@ -196,9 +290,14 @@ impl LyricsFetcher {
 
             match resp_result {
                 Ok(resp) => {
+                    tracing::debug!("LRCLIB /get: {} for {} - {}", resp.status(), artist, title);
                     if resp.status().is_success() {
                         // If JSON parse fails, it's a data issue, don't retry network
-                        if let Ok(data) = resp.json::<LrclibResponse>().await {
+                        let data = resp.json::<LrclibResponse>().await;
+                        if let Err(e) = &data {
+                            tracing::warn!("LRCLIB /get: unreadable reply: {}", e);
+                        }
+                        if let Ok(data) = data {
                             let result = self.parse(data);
                             if let LyricsFetchResult::Found(ref lines, _) = result {
                                 if let Some(path) = &cache_path {
@@ -218,12 +317,14 @@ impl LyricsFetcher {
                     }
                     break; // Request succeeded (even if 404), proceed to Search
                 }
-                Err(_) => {
+                Err(e) => {
                     attempt += 1;
                     if attempt > MAX_RETRIES {
                         // Don't error out completely, just log/proceed to search
+                        tracing::warn!("LRCLIB /get failed, trying search: {}", e);
                         break;
                     }
+                    tracing::debug!("LRCLIB /get attempt {} failed: {}", attempt, e);
                     tokio::time::sleep(std::time::Duration::from_millis(500)).await;
                 }
             }
@@ -258,54 +359,146 @@ impl LyricsFetcher {
                     });
                 }
             }
-            Ok(primary_res)
+            match primary_res {
+                // Keep unsynced lyrics from the first search over nothing
+                LyricsFetchResult::None => Ok(search_res),
+                _ => Ok(primary_res),
+            }
         } else {
             // Already tried with this artist name (it was clean)
             Ok(search_res)
         }
     }
 
-    fn fetch_impl_local(path_str: &str) -> Option<LyricsFetchResult> {
-        let path = Path::new(path_str);
+    /// `.lrc` files that may hold lyrics for this track, most specific first
+    fn lrc_candidates(
+        track_path: Option<&Path>,
+        lyrics_dir: Option<&Path>,
+        artist: &str,
+        title: &str,
+    ) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+        if let Some(path) = track_path {
+            // Sidecar: "song.flac" -> "song.lrc" / "song.LRC"
+            candidates.push(path.with_extension("lrc"));
+            candidates.push(path.with_extension("LRC"));
+        }
+        if let Some(dir) = lyrics_dir {
+            if let Some(stem) = track_path.and_then(|p| p.file_stem()) {
+                let mut name = stem.to_os_string();
+                name.push(".lrc");
+                candidates.push(dir.join(name));
+            }
+            // Path separators in tags would escape the folder
+            let safe = |s: &str| s.replace(['/', '\\'], "_");
+            if !artist.is_empty() && !title.is_empty() {
+                candidates.push(dir.join(format!("{} - {}.lrc", safe(artist), safe(title))));
+            }
+        }
+        candidates
+    }
 
-        // A. Check sidecar .lrc file
-        let lrc_path = path.with_extension("lrc");
-        if lrc_path.exists() {
-            if let Ok(content) = fs::read_to_string(lrc_path) {
-                // Determine if synced or plain by checking for timestamps
-                let is_synced = content.contains(']');
-                if is_synced {
-                    let lines = Self::parse_lrc_content(&content);
-                    if !lines.is_empty() {
-                        return Some(LyricsFetchResult::Found(
-                            lines,
-                            "Local .lrc file".to_string(),
-                        ));
-                    }
+    /// Lyrics search for the manual lookup popup: every usable LRCLIB match,
+    /// synced ones first
+    pub async fn search_candidates(
+        &self,
+        artist: &str,
+        title: &str,
+    ) -> Result<Vec<LyricsCandidate>> {
+        let mut params = vec![("track_name", title)];
+        if !artist.is_empty() {
+            params.push(("artist_name", artist));
+        }
+        let results: Vec<LrclibResponse> = self
+            .client
+            .get("https://lrclib.net/api/search")
+            .query(&params)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut candidates: Vec<LyricsCandidate> = results
+            .iter()
+            .filter_map(|r| {
+                let lyrics = self.parse_ref(r);
+                if !matches!(
+                    lyrics,
+                    LyricsFetchResult::Found(..) | LyricsFetchResult::Plain(..)
+                ) {
+                    return None;
+                }
+                Some(LyricsCandidate {
+                    artist: r.artist_name.clone().unwrap_or_default(),
+                    title: r.track_name.clone().unwrap_or_default(),
+                    album: r.album_name.clone().unwrap_or_default(),
+                    duration_secs: r.duration,
+                    lyrics,
+                })
+            })
+            .collect();
+        // Stable sort keeps LRCLIB's relevance order within each group
+        candidates.sort_by_key(|c| !c.is_synced());
+        candidates.truncate(30);
+        Ok(candidates)
+    }
+
+    /// Remember a hand-picked result for this track, replacing whatever the
+    /// automatic lookup cached (blocking; call from a blocking task)
+    pub fn bind_to_track(artist: &str, title: &str, lyrics: &LyricsFetchResult) {
+        let Some(path) = Self::get_cache_path(artist, title) else {
+            return;
+        };
+        match lyrics {
+            LyricsFetchResult::Found(lines, _) => Self::save_to_cache(&path, lines),
+            LyricsFetchResult::Plain(plain, _) => Self::save_to_cache(
+                &path,
+                &CachedLyrics::Plain {
+                    plain: plain.clone(),
+                },
+            ),
+            _ => {}
+        }
+    }
+
+    fn fetch_impl_local(
+        track_path: Option<&str>,
+        lyrics_dir: Option<&Path>,
+        artist: &str,
+        title: &str,
+    ) -> Option<LyricsFetchResult> {
+        let path = track_path.map(Path::new);
+
+        // A. Check .lrc files (sidecar, then the lyrics folder)
+        for lrc_path in Self::lrc_candidates(path, lyrics_dir, artist, title) {
+            if !lrc_path.is_file() {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(&lrc_path) {
+                if let Some(res) = Self::from_text(&content, "Local .lrc file") {
+                    return Some(res);
                 }
             }
         }
 
         // B. Check Embedded Lyrics (lofty)
         #[cfg(feature = "mpd")]
-        {
+        if let Some(path) = path {
             if let Ok(tagged_file) = lofty::read_from_path(path) {
-                // Try primary tag first, then first tag
-                let tag = tagged_file
-                    .primary_tag()
-                    .or_else(|| tagged_file.first_tag());
-
-                if let Some(tag) = tag {
-                    if let Some(lyrics) = tag.get_string(&ItemKey::Lyrics).map(|s| s.to_string()) {
-                        let is_synced = lyrics.contains(']');
-                        if is_synced {
-                            let lines = Self::parse_lrc_content(&lyrics);
-                            if !lines.is_empty() {
-                                return Some(LyricsFetchResult::Found(
-                                    lines,
-                                    "Embedded Tags".to_string(),
-                                ));
-                            }
+                // USLT (ID3v2), ©lyr (MP4) and LYRICS (Vorbis/APE) all map to ItemKey::Lyrics.
+                // Primary tag first, then any other tag the file carries.
+                let primary = tagged_file.primary_tag();
+                let tags = primary.into_iter().chain(
+                    tagged_file
+                        .tags()
+                        .iter()
+                        .filter(|t| Some(t.tag_type()) != primary.map(|p| p.tag_type())),
+                );
+                for tag in tags {
+                    if let Some(lyrics) = tag.get_string(&ItemKey::Lyrics) {
+                        if let Some(res) = Self::from_text(lyrics, "Embedded Tags") {
+                            return Some(res);
                         }
                     }
                 }
@@ -315,22 +508,68 @@ impl LyricsFetcher {
         None
     }
 
+    /// Synced lyrics if the text has timestamps, otherwise plain lines
+    fn from_text(content: &str, source: &str) -> Option<LyricsFetchResult> {
+        let lines = Self::parse_lrc_content(content);
+        if !lines.is_empty() {
+            return Some(LyricsFetchResult::Found(lines, source.to_string()));
+        }
+        let plain = Self::parse_plain(content);
+        if plain.is_empty() {
+            None
+        } else {
+            Some(LyricsFetchResult::Plain(plain, source.to_string()))
+        }
+    }
+
+    /// Unsynced lyrics: drop LRC metadata tags and surrounding blank lines
+    fn parse_plain(content: &str) -> Vec<String> {
+        let lines: Vec<String> = content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !(l.starts_with('[') && l.ends_with(']') && l.contains(':')))
+            .map(str::to_string)
+            .collect();
+        let start = lines.iter().position(|l| !l.is_empty());
+        let end = lines.iter().rposition(|l| !l.is_empty());
+        match (start, end) {
+            (Some(s), Some(e)) => lines[s..=e].to_vec(),
+            _ => Vec::new(),
+        }
+    }
+
     fn parse_lrc_content(content: &str) -> Vec<LyricLine> {
         let mut lines = Vec::new();
+        // [offset:+/-ms] shifts every line (positive = lyrics earlier)
+        let mut offset_ms: i64 = 0;
         for line in content.lines() {
-            if let Some(idx) = line.find(']') {
-                if line.starts_with('[') {
-                    let timestamp_str = &line[1..idx];
-                    let text = line[idx + 1..].trim().to_string();
-                    if let Some(ms) = Self::parse_timestamp(timestamp_str) {
-                        lines.push(LyricLine {
-                            timestamp_ms: ms,
-                            text,
-                        });
-                    }
+            let mut rest = line.trim();
+            let mut stamps = Vec::new();
+            // A line may repeat for several timestamps: [00:12.00][01:30.00]Chorus
+            while let Some(tag) = rest.strip_prefix('[') {
+                let Some(idx) = tag.find(']') else { break };
+                let inner = &tag[..idx];
+                if let Some(ms) = Self::parse_timestamp(inner) {
+                    stamps.push(ms);
+                } else if let Some(v) = inner.strip_prefix("offset:") {
+                    offset_ms = v.trim().parse().unwrap_or(0);
                 }
+                rest = &tag[idx + 1..];
+            }
+            let text = rest.trim().to_string();
+            for ms in stamps {
+                lines.push(LyricLine {
+                    timestamp_ms: ms,
+                    text: text.clone(),
+                });
+            }
+        }
+        if offset_ms != 0 {
+            for line in &mut lines {
+                line.timestamp_ms = (line.timestamp_ms as i64 - offset_ms).max(0) as u64;
             }
         }
+        lines.sort_by_key(|l| l.timestamp_ms);
         lines
     }
 
@@ -350,17 +589,25 @@ impl LyricsFetcher {
         let resp = loop {
             match self.client.get(url).query(&params).send().await {
                 Ok(response) => break Ok(response),
-                Err(_) => {
+                Err(e) => {
                     attempt += 1;
                     if attempt > MAX_RETRIES {
+                        tracing::warn!("LRCLIB /search failed: {}", e);
                         break Err(anyhow::anyhow!("Lyrics Network Error"));
                     }
+                    tracing::debug!("LRCLIB /search attempt {} failed: {}", attempt, e);
                     tokio::time::sleep(std::time::Duration::from_millis(500)).await;
                 }
             }
         }?;
 
-        let results: Vec<LrclibResponse> = resp.json().await.unwrap_or_default();
+        let results: Vec<LrclibResponse> = match resp.json().await {
+            Ok(results) => results,
+            Err(e) => {
+                tracing::warn!("LRCLIB /search: unreadable reply: {}", e);
+                Vec::new()
+            }
+        };
 
         let target_dur = duration_ms as f64 / 1000.0;
 
@@ -387,6 +634,14 @@ impl LyricsFetcher {
             return Ok(LyricsFetchResult::Instrumental);
         }
 
+        // Last resort: unsynced lyrics
+        if let Some(found) = results
+            .iter()
+            .find(|r| r.plain_lyrics.is_some() && is_valid(r))
+        {
+            return Ok(self.parse_ref(found));
+        }
+
         Ok(LyricsFetchResult::None)
     }
 
@@ -395,17 +650,7 @@ impl LyricsFetcher {
             return LyricsFetchResult::Instrumental;
         }
 
-        let raw_opt = data.synced_lyrics.or(data.plain_lyrics);
-        if let Some(raw) = raw_opt {
-            let lines = Self::parse_lrc_content(&raw);
-            if lines.is_empty() {
-                LyricsFetchResult::None
-            } else {
-                LyricsFetchResult::Found(lines, "LRCLIB API".to_string())
-            }
-        } else {
-            LyricsFetchResult::None
-        }
+        self.parse_ref(&data)
     }
 
     // Helper for reference (Search)
@@ -414,15 +659,61 @@ impl LyricsFetcher {
             return LyricsFetchResult::Instrumental;
         }
         let raw_opt = data.synced_lyrics.as_ref().or(data.plain_lyrics.as_ref());
-        if let Some(raw) = raw_opt {
-            let lines = Self::parse_lrc_content(raw);
-            if lines.is_empty() {
-                LyricsFetchResult::None
-            } else {
-                LyricsFetchResult::Found(lines, "LRCLIB API".to_string())
+        raw_opt
+            .and_then(|raw| Self::from_text(raw, "LRCLIB API"))
+            .unwrap_or(LyricsFetchResult::None)
+    }
+
+    /// Serialize lines as LRC (`[mm:ss.xx]text`)
+    pub fn to_lrc(artist: &str, title: &str, lines: &[LyricLine]) -> String {
+        let mut out = format!("[ar:{}]\n[ti:{}]\n", artist, title);
+        for line in lines {
+            let ms = line.timestamp_ms;
+            out.push_str(&format!(
+                "[{:02}:{:02}.{:02}]{}\n",
+                ms / 60000,
+                (ms % 60000) / 1000,
+                (ms % 1000) / 10,
+                line.text
+            ));
+        }
+        out
+    }
+
+    /// Write edited lyrics where the local lookup finds them first: next to the
+    /// track, or in the lyrics folder when that isn't possible
+    pub fn save_local(
+        track_path: Option<&str>,
+        lyrics_dir: Option<&Path>,
+        artist: &str,
+        title: &str,
+        lines: &[LyricLine],
+    ) -> Result<PathBuf> {
+        let content = Self::to_lrc(artist, title, lines);
+        let candidates = Self::lrc_candidates(track_path.map(Path::new), lyrics_dir, artist, title);
+        // Sidecar first (same name as the track), then "Artist - Title.lrc"
+        let targets = candidates
+            .iter()
+            .filter(|p| p.extension().is_some_and(|e| e == "lrc"));
+
+        let mut last_err = None;
+        for target in targets {
+            if let Some(parent) = target.parent() {
+                if let Err(e) = fs::create_dir_all(parent) {
+                    last_err = Some(e);
+                    continue;
+                }
             }
-        } else {
-            LyricsFetchResult::None
+            match fs::write(target, &content) {
+                Ok(()) => return Ok(target.clone()),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        match last_err {
+            Some(e) => Err(e.into()),
+            None => Err(anyhow::anyhow!(
+                "No track file; set lyrics_directory in config.toml"
+            )),
         }
     }
 
@@ -516,4 +807,119 @@ mod tests {
         // [ar:Artist Name] has a ] but "ar" is not a valid timestamp → filtered out
         assert_eq!(lines.len(), 2);
     }
+
+    #[test]
+    fn test_parse_lrc_content_repeated_stamps_and_offset() {
+        let content = "[offset:+500]\n[00:30.00][00:10.00]Chorus\n[00:20.00]Verse";
+        let lines = LyricsFetcher::parse_lrc_content(content);
+        let stamps: Vec<u64> = lines.iter().map(|l| l.timestamp_ms).collect();
+        assert_eq!(stamps, vec![9500, 19500, 29500]);
+        assert_eq!(lines[0].text, "Chorus");
+        assert_eq!(lines[1].text, "Verse");
+    }
+
+    #[test]
+    fn test_local_lrc_sidecar_then_lyrics_dir() {
+        let root = std::env::temp_dir().join(format!("vyom-lrc-test-{}", std::process::id()));
+        let music = root.join("music");
+        let lyrics = root.join("lyrics");
+        fs::create_dir_all(&music).unwrap();
+        fs::create_dir_all(&lyrics).unwrap();
+        let track = music.join("01 Song.flac");
+        let track_str = track.to_string_lossy().to_string();
+
+        // Only the lyrics folder has it, named "Artist - Title.lrc"
+        fs::write(lyrics.join("AC_DC - Song.lrc"), "[00:01.00]From folder").unwrap();
+        let found = |dir: Option<&Path>| match LyricsFetcher::fetch_impl_local(
+            Some(&track_str),
+            dir,
+            "AC/DC",
+            "Song",
+        ) {
+            Some(LyricsFetchResult::Found(lines, _)) => Some(lines[0].text.clone()),
+            _ => None,
+        };
+        assert_eq!(found(Some(&lyrics)).as_deref(), Some("From folder"));
+        assert_eq!(found(None), None);
+
+        // A sidecar next to the track wins
+        fs::write(music.join("01 Song.lrc"), "[00:01.00]Sidecar").unwrap();
+        assert_eq!(found(Some(&lyrics)).as_deref(), Some("Sidecar"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_unsynced_text_becomes_plain() {
+        let content = "[ar:Someone]\n\nFirst line\n\nSecond verse\n  \n";
+        match LyricsFetcher::from_text(content, "Embedded Tags") {
+            Some(LyricsFetchResult::Plain(lines, source)) => {
+                assert_eq!(lines, vec!["First line", "", "Second verse"]);
+                assert_eq!(source, "Embedded Tags");
+            }
+            other => panic!("Expected plain lyrics, got {:?}", other),
+        }
+        assert!(matches!(
+            LyricsFetcher::from_text("[00:01.00]Synced", "x"),
+            Some(LyricsFetchResult::Found(..))
+        ));
+        assert!(LyricsFetcher::from_text("[ti:Only tags]\n", "x").is_none());
+    }
+
+    #[test]
+    fn test_saved_lrc_is_found_again() {
+        let root = std::env::temp_dir().join(format!("vyom-lrc-save-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let track = root.join("song.flac").to_string_lossy().to_string();
+        let lines = vec![
+            LyricLine {
+                timestamp_ms: 1_230,
+                text: "Hello".to_string(),
+            },
+            LyricLine {
+                timestamp_ms: 61_500,
+                text: "Again".to_string(),
+            },
+        ];
+
+        let path = LyricsFetcher::save_local(Some(&track), None, "A", "T", &lines).unwrap();
+        assert_eq!(path, root.join("song.lrc"));
+        match LyricsFetcher::fetch_impl_local(Some(&track), None, "A", "T") {
+            Some(LyricsFetchResult::Found(found, _)) => assert_eq!(found, lines),
+            other => panic!("Expected saved lyrics, got {:?}", other),
+        }
+
+        assert!(LyricsFetcher::save_local(None, None, "A", "T", &lines).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_romanize_only_foreign_scripts() {
+        assert_eq!(romanize("Hello, café"), None);
+        assert_eq!(romanize("こんにちは").as_deref(), Some("konnichiha"));
+        assert_eq!(romanize("你好 世界").as_deref(), Some("ni hao shi jie"));
+        assert_eq!(romanize("사랑해").as_deref(), Some("salanghae"));
+        assert_eq!(romanize("Привет").as_deref(), Some("privet"));
+
+        let lines: Vec<String> = ["Hello", "こんにちは", "", "사랑해", "Bye"]
+            .map(String::from)
+            .into();
+        assert_eq!(plain_rows(&lines, false), vec![0, 1, 2, 3, 4]);
+        assert_eq!(plain_rows(&lines, true), vec![0, 1, 3, 4, 6]);
+    }
+
+    #[test]
+    fn test_cache_entry_formats() {
+        // Old caches are a bare array of synced lines
+        let old: CachedLyrics =
+            serde_json::from_str(r#"[{"timestamp_ms":1000,"text":"Hi"}]"#).unwrap();
+        assert!(matches!(old, CachedLyrics::Synced(ref l) if l.len() == 1));
+
+        let plain = CachedLyrics::Plain {
+            plain: vec!["a".to_string()],
+        };
+        let json = serde_json::to_string(&plain).unwrap();
+        let back: CachedLyrics = serde_json::from_str(&json).unwrap();
+        assert!(matches!(back, CachedLyrics::Plain { ref plain } if plain == &["a"]));
+    }
 }
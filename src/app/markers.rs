@@ -0,0 +1,141 @@
+//! Structure points of the playing track, drawn on the progress bar 📍
+//!
+//! Bookmarks set by hand, chapters, and song sections found in synced
+//! lyrics: a line after a blank one, or after a long gap, starts a section.
+
+use crate::app::chapters::Chapter;
+use crate::app::lyrics::LyricLine;
+
+/// A gap this long between two lyric lines starts a new section
+const SECTION_GAP_MS: u64 = 10_000;
+/// Within this of a marker, "previous" goes to the one before it
+const BACK_GRACE_MS: u64 = 2_000;
+/// A bookmark this close to the playhead is the one to remove
+pub const BOOKMARK_SNAP_MS: u64 = 2_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MarkerKind {
+    Section,
+    Chapter,
+    Bookmark,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Marker {
+    pub ms: u64,
+    pub kind: MarkerKind,
+    /// Chapter title, first lyric line of the section, or empty
+    pub label: String,
+}
+
+/// Song sections in synced lyrics, shifted by the sync offset
+pub fn sections(lines: &[LyricLine], sync_ms: i64) -> Vec<Marker> {
+    let mut prev: Option<&LyricLine> = None;
+    let mut out = Vec::new();
+    for line in lines {
+        let starts = match prev {
+            _ if line.text.is_empty() => false,
+            None => true,
+            Some(p) => {
+                p.text.is_empty()
+                    || line.timestamp_ms.saturating_sub(p.timestamp_ms) >= SECTION_GAP_MS
+            }
+        };
+        if starts {
+            out.push(Marker {
+                ms: (line.timestamp_ms as i64 + sync_ms).max(0) as u64,
+                kind: MarkerKind::Section,
+                label: line.text.clone(),
+            });
+        }
+        prev = Some(line);
+    }
+    out
+}
+
+/// Every marker of a track, sorted by position
+pub fn collect(bookmarks: &[u64], chapters: &[Chapter], sections: Vec<Marker>) -> Vec<Marker> {
+    let mut markers = sections;
+    markers.extend(chapters.iter().map(|c| Marker {
+        ms: c.start_ms,
+        kind: MarkerKind::Chapter,
+        label: c.title.clone(),
+    }));
+    markers.extend(bookmarks.iter().map(|&ms| Marker {
+        ms,
+        kind: MarkerKind::Bookmark,
+        label: String::new(),
+    }));
+    markers.sort_by_key(|m| m.ms);
+    markers
+}
+
+/// First marker after `position_ms`
+pub fn next(markers: &[Marker], position_ms: u64) -> Option<&Marker> {
+    markers.iter().find(|m| m.ms > position_ms + 500)
+}
+
+/// Last marker before `position_ms`; just past one, the one before that
+pub fn prev(markers: &[Marker], position_ms: u64) -> Option<&Marker> {
+    markers
+        .iter()
+        .rev()
+        .find(|m| m.ms + BACK_GRACE_MS <= position_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(timestamp_ms: u64, text: &str) -> LyricLine {
+        LyricLine {
+            timestamp_ms,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sections_and_jumps() {
+        let lines = [
+            line(12_000, "First verse"),
+            line(15_000, "goes on"),
+            line(18_000, ""),
+            line(20_000, "Chorus"),
+            line(23_000, "again"),
+            line(40_000, "Bridge after a solo"),
+        ];
+        let found = sections(&lines, -1_000);
+        let starts: Vec<_> = found.iter().map(|m| (m.ms, m.label.as_str())).collect();
+        assert_eq!(
+            starts,
+            [
+                (11_000, "First verse"),
+                (19_000, "Chorus"),
+                (39_000, "Bridge after a solo")
+            ]
+        );
+
+        let chapters = [Chapter {
+            title: "Part 2".into(),
+            start_ms: 30_000,
+        }];
+        let markers = collect(&[25_000], &chapters, found);
+        let kinds: Vec<_> = markers.iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                MarkerKind::Section,
+                MarkerKind::Section,
+                MarkerKind::Bookmark,
+                MarkerKind::Chapter,
+                MarkerKind::Section
+            ]
+        );
+
+        assert_eq!(next(&markers, 19_000).map(|m| m.ms), Some(25_000));
+        assert_eq!(next(&markers, 39_500), None);
+        assert_eq!(prev(&markers, 26_000).map(|m| m.ms), Some(19_000));
+        assert_eq!(prev(&markers, 28_000).map(|m| m.ms), Some(25_000));
+        assert_eq!(prev(&markers, 5_000), None);
+    }
+}
@@ -0,0 +1,175 @@
+//! Missing and broken files 🩹
+//!
+//! `:missing` cross-checks MPD's database against the music folder and the
+//! stored playlists against the database, and lists what can't be played:
+//! songs MPD still lists after they were deleted or moved, and playlist
+//! entries pointing at them. Purging drops those entries from their
+//! playlists and has MPD rescan so its database forgets the rest.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Where an unreachable entry was found
+#[derive(Debug, Clone, PartialEq)]
+pub enum Place {
+    /// In MPD's database, but not in the music folder any more
+    Library,
+    /// In a stored playlist, at this position
+    Playlist(String, u32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingEntry {
+    /// Path relative to the music directory
+    pub file: String,
+    pub place: Place,
+}
+
+/// Radio streams and other URLs aren't files
+fn is_stream(file: &str) -> bool {
+    file.contains("://")
+}
+
+/// Songs in `library` that aren't in `music_dir`, or None when the folder
+/// isn't here (MPD on another machine)
+pub fn gone_from_disk(music_dir: &Path, library: &[String]) -> Option<Vec<String>> {
+    if !music_dir.is_dir() {
+        return None;
+    }
+    Some(
+        library
+            .iter()
+            .filter(|file| !is_stream(file) && !music_dir.join(file).exists())
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Everything unreachable: library songs gone from disk, then playlist
+/// entries that aren't in the library or are gone from disk
+pub fn check(
+    library: &[String],
+    gone: &[String],
+    playlists: &[(String, Vec<String>)],
+) -> Vec<MissingEntry> {
+    let gone_set: HashSet<&str> = gone.iter().map(String::as_str).collect();
+    let known: HashSet<&str> = library
+        .iter()
+        .map(String::as_str)
+        .filter(|file| !gone_set.contains(file))
+        .collect();
+
+    let mut entries: Vec<MissingEntry> = gone
+        .iter()
+        .map(|file| MissingEntry {
+            file: file.clone(),
+            place: Place::Library,
+        })
+        .collect();
+    for (name, files) in playlists {
+        for (pos, file) in files.iter().enumerate() {
+            if !is_stream(file) && !known.contains(file.as_str()) {
+                entries.push(MissingEntry {
+                    file: file.clone(),
+                    place: Place::Playlist(name.clone(), pos as u32),
+                });
+            }
+        }
+    }
+    entries
+}
+
+/// Scan the library and every stored playlist. The flag says whether the
+/// music folder could be checked too.
+#[cfg(feature = "mpd")]
+pub fn scan(
+    mpd: &mut mpd::Client,
+    music_dir: &str,
+) -> Result<(Vec<MissingEntry>, bool), mpd::error::Error> {
+    let library: Vec<String> = mpd.listall()?.into_iter().map(|s| s.file).collect();
+    let gone = gone_from_disk(Path::new(music_dir), &library);
+    let mut playlists = Vec::new();
+    for playlist in mpd.playlists()? {
+        let files = mpd
+            .playlist(playlist.name.as_str())?
+            .into_iter()
+            .map(|s| s.file)
+            .collect();
+        playlists.push((playlist.name, files));
+    }
+    let entries = check(&library, gone.as_deref().unwrap_or_default(), &playlists);
+    Ok((entries, gone.is_some()))
+}
+
+/// Drop the playlist entries in `entries`, and rescan if the library has
+/// songs that are gone. Returns how many playlist entries went.
+#[cfg(feature = "mpd")]
+pub fn purge(mpd: &mut mpd::Client, entries: &[MissingEntry]) -> Result<usize, mpd::error::Error> {
+    let mut removed = 0;
+    // Last position first, so the ones before it don't move
+    let mut in_playlists: Vec<(&str, u32)> = entries
+        .iter()
+        .filter_map(|e| match &e.place {
+            Place::Playlist(name, pos) => Some((name.as_str(), *pos)),
+            Place::Library => None,
+        })
+        .collect();
+    in_playlists.sort_by(|a, b| a.0.cmp(b.0).then(b.1.cmp(&a.1)));
+    for (name, pos) in in_playlists {
+        mpd.pl_delete(name, pos)?;
+        removed += 1;
+    }
+    if entries.iter().any(|e| e.place == Place::Library) {
+        mpd.update()?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_missing_entries() {
+        let dir = std::env::temp_dir().join(format!("vyom-missing-{}", std::process::id()));
+        fs::create_dir_all(dir.join("a")).unwrap();
+        fs::write(dir.join("a/1.flac"), b"").unwrap();
+        fs::write(dir.join("a/2.flac"), b"").unwrap();
+
+        let library: Vec<String> = vec!["a/1.flac".into(), "a/2.flac".into(), "a/3.flac".into()];
+        let gone = gone_from_disk(&dir, &library).unwrap();
+        assert_eq!(gone, vec!["a/3.flac"]);
+        assert_eq!(gone_from_disk(&dir.join("nowhere"), &library), None);
+
+        let playlists = vec![(
+            "mix".to_string(),
+            vec![
+                "a/2.flac".to_string(),
+                "old/9.flac".to_string(),
+                "https://radio.example/stream".to_string(),
+                "a/3.flac".to_string(),
+            ],
+        )];
+        let entries = check(&library, &gone, &playlists);
+        assert_eq!(
+            entries,
+            vec![
+                MissingEntry {
+                    file: "a/3.flac".into(),
+                    place: Place::Library,
+                },
+                MissingEntry {
+                    file: "old/9.flac".into(),
+                    place: Place::Playlist("mix".into(), 1),
+                },
+                MissingEntry {
+                    file: "a/3.flac".into(),
+                    place: Place::Playlist("mix".into(), 3),
+                },
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
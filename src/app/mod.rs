@@ -1,38 +1,83 @@
+pub mod about;
+pub mod bandcamp;
+pub mod cd;
 pub mod config;
+pub mod connection;
+pub mod doctor;
+pub mod downloads;
 pub mod lyrics;
 pub mod state;
 
+pub mod chapters;
 pub mod cli;
 pub mod events;
+pub mod export;
+pub mod history;
 pub mod inputs;
 pub mod keys;
+pub mod library_cache;
 pub mod library_helpers;
 pub mod lock;
+pub mod markers;
+pub mod missing;
+#[cfg(feature = "mpd")]
+pub mod mpd_actor;
+pub mod palette;
+pub mod playlist_files;
+pub mod related;
+pub mod remote;
+pub mod requests;
 pub mod runner;
+pub mod shuffle;
+pub mod smart_playlists;
+pub mod snapshots;
+pub mod spotify;
+pub mod stream_search;
+pub mod sync;
+pub mod tag_lookup;
+pub mod tag_patterns;
+#[cfg(feature = "mpd")]
+pub mod tags;
 pub mod tmux;
+pub mod wizard;
+pub mod wrapped;
+pub mod ytdlp;
+pub mod zellij;
 pub use state::*;
 
+/// The MPD thread, started on first use
+#[cfg(feature = "mpd")]
+fn mpd_actor<'a>(app: &'a mut App, args: &cli::Args) -> &'a mpd_actor::MpdActor {
+    app.mpd.get_or_insert_with(|| {
+        mpd_actor::MpdActor::spawn(
+            format!("{}:{}", args.mpd_host, args.mpd_port),
+            mpd_actor::REPLY_TIMEOUT,
+        )
+    })
+}
+
+/// Run `f` on the MPD thread and await (briefly) its result. `None`
+/// when MPD can't be reached or is too slow; that is reported as a toast.
 #[cfg(feature = "mpd")]
-pub fn with_mpd<F, R>(app: &mut App, args: &cli::Args, f: F) -> Option<R>
+pub async fn with_mpd<F, R>(app: &mut App, args: &cli::Args, f: F) -> Option<R>
 where
-    F: FnOnce(&mut mpd::Client) -> R,
+    F: FnOnce(&mut mpd::Client) -> R + Send + 'static,
+    R: Send + 'static,
 {
-    let client = app
-        .mpd_client
-        .take()
-        .or_else(|| mpd::Client::connect(format!("{}:{}", args.mpd_host, args.mpd_port)).ok());
-
-    if let Some(mut mpd) = client {
-        if mpd.ping().is_err() {
-            if let Ok(c) = mpd::Client::connect(format!("{}:{}", args.mpd_host, args.mpd_port)) {
-                mpd = c;
-            }
+    match mpd_actor(app, args).call(f).await {
+        Ok(result) => Some(result),
+        Err(e) => {
+            app.report(&crate::error::VyomError::MpdConnection(e), None);
+            None
         }
-
-        let result = f(&mut mpd);
-        app.mpd_client = Some(mpd);
-        Some(result)
-    } else {
-        None
     }
 }
+
+/// Run `f` on the MPD thread without waiting for it
+#[cfg(feature = "mpd")]
+pub fn send_mpd<F>(app: &mut App, args: &cli::Args, f: F)
+where
+    F: FnOnce(&mut mpd::Client) + Send + 'static,
+{
+    mpd_actor(app, args).send(f);
+}
@@ -0,0 +1,187 @@
+//! One thread owns the MPD connection 🔌
+//!
+//! Key handlers hand it jobs instead of connecting themselves, so a slow or
+//! unreachable MPD can't freeze the screen. [`MpdActor::call`] awaits the
+//! answer at most the actor's timeout, [`MpdActor::send`] doesn't wait.
+//! Jobs run one after another in the order they were sent. A call that gave
+//! up is never run afterwards, so what the UI reports is what happened.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long a key handler waits for MPD before giving up
+pub const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Gets the connection, or why there is none
+type Run = Box<dyn FnOnce(Result<&mut mpd::Client, String>) + Send>;
+
+struct Job {
+    /// Skipped once this has passed: the caller stopped waiting
+    deadline: Option<Instant>,
+    /// Taken by the thread as it starts the job, or by a caller giving up
+    claimed: Arc<AtomicBool>,
+    run: Run,
+}
+
+/// Connect with `timeout` for the connection and for every reply
+fn connect(addr: &str, timeout: Duration) -> Result<mpd::Client, String> {
+    let addr = addr
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("{} does not resolve", addr))?;
+    let stream = TcpStream::connect_timeout(&addr, timeout).map_err(|e| e.to_string())?;
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|_| stream.set_write_timeout(Some(timeout)))
+        .map_err(|e| e.to_string())?;
+    mpd::Client::new(stream).map_err(|e| e.to_string())
+}
+
+pub struct MpdActor {
+    jobs: tokio::sync::mpsc::UnboundedSender<Job>,
+    timeout: Duration,
+}
+
+impl MpdActor {
+    /// Start the thread; it connects to `addr` ("host:port") on the first
+    /// job and again whenever the connection stops answering
+    pub fn spawn(addr: String, timeout: Duration) -> Self {
+        let (jobs, mut rx) = tokio::sync::mpsc::unbounded_channel::<Job>();
+        let spawned = std::thread::Builder::new()
+            .name("vyom-mpd".into())
+            .spawn(move || {
+                let mut client: Option<mpd::Client> = None;
+                while let Some(job) = rx.blocking_recv() {
+                    let late = job.deadline.is_some_and(|d| Instant::now() >= d);
+                    if late || job.claimed.swap(true, Ordering::SeqCst) {
+                        tracing::debug!("MPD job skipped: its caller gave up");
+                        continue;
+                    }
+                    if client.as_mut().is_none_or(|c| c.ping().is_err()) {
+                        client = None;
+                        match connect(&addr, timeout) {
+                            Ok(c) => client = Some(c),
+                            Err(e) => {
+                                (job.run)(Err(e));
+                                continue;
+                            }
+                        }
+                    }
+                    if let Some(mpd) = client.as_mut() {
+                        (job.run)(Ok(mpd));
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            tracing::error!("Could not start the MPD thread: {}", e);
+        }
+        Self { jobs, timeout }
+    }
+
+    /// Run `f` on the connection and await what it returns. `Err` when MPD
+    /// can't be reached or the job didn't start in time; it is then dropped,
+    /// never run later. A job already running is awaited to the end (the
+    /// socket timeouts bound it).
+    pub async fn call<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut mpd::Client) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (reply, mut answer) = tokio::sync::oneshot::channel();
+        let claimed = Arc::new(AtomicBool::new(false));
+        let job = Job {
+            deadline: Some(Instant::now() + self.timeout),
+            claimed: claimed.clone(),
+            run: Box::new(move |mpd| {
+                let _ = reply.send(mpd.map(f));
+            }),
+        };
+        let gone = || crate::t!("error.mpd_gone");
+        self.jobs.send(job).map_err(|_| gone())?;
+        match tokio::time::timeout(self.timeout, &mut answer).await {
+            Ok(result) => result.map_err(|_| gone())?,
+            // Not started yet: it never will be
+            Err(_) if !claimed.swap(true, Ordering::SeqCst) => Err(crate::t!(
+                "error.mpd_no_reply",
+                secs = self.timeout.as_secs_f32()
+            )),
+            Err(_) => answer.await.map_err(|_| gone())?,
+        }
+    }
+
+    /// Run `f` on the connection without waiting; failures are only logged
+    pub fn send<F>(&self, f: F)
+    where
+        F: FnOnce(&mut mpd::Client) + Send + 'static,
+    {
+        let job = Job {
+            deadline: None,
+            claimed: Arc::new(AtomicBool::new(false)),
+            run: Box::new(move |mpd| match mpd {
+                Ok(mpd) => f(mpd),
+                Err(e) => tracing::warn!("MPD command dropped: {}", e),
+            }),
+        };
+        if self.jobs.send(job).is_err() {
+            tracing::warn!("MPD thread is gone");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Greets like MPD and answers every command with `OK`, or never says
+    /// anything when `silent`
+    fn fake_mpd(silent: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            if silent {
+                std::thread::sleep(Duration::from_secs(5));
+                return;
+            }
+            let mut writer = stream.try_clone().unwrap();
+            writer.write_all(b"OK MPD 0.23.5\n").unwrap();
+            for line in BufReader::new(stream).lines() {
+                if line.is_err() || writer.write_all(b"OK\n").is_err() {
+                    return;
+                }
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_actor_calls_and_times_out() {
+        let actor = MpdActor::spawn(fake_mpd(false), Duration::from_secs(5));
+        assert!(actor.call(|mpd| mpd.ping().is_ok()).await.unwrap());
+        // Fire-and-forget jobs run in order before the next call
+        let (tx, rx) = std::sync::mpsc::channel();
+        actor.send(move |mpd| tx.send(mpd.ping().is_ok()).unwrap());
+        assert_eq!(actor.call(|_| 7).await, Ok(7));
+        assert_eq!(rx.try_recv(), Ok(true));
+
+        // The greeting never comes: the connect gives up
+        let silent = MpdActor::spawn(fake_mpd(true), Duration::from_millis(100));
+        assert!(silent.call(|mpd| mpd.ping()).await.is_err());
+
+        // Stuck behind a slow job, a call gives up and is dropped, not run late
+        let slow = MpdActor::spawn(fake_mpd(false), Duration::from_millis(100));
+        slow.send(|_| std::thread::sleep(Duration::from_millis(300)));
+        let (tx, rx) = std::sync::mpsc::channel();
+        assert!(slow.call(move |_| tx.send(()).unwrap()).await.is_err());
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(rx.try_recv().is_err(), "a call that timed out must not run");
+
+        let refused = MpdActor::spawn("127.0.0.1:1".into(), Duration::from_secs(5));
+        assert!(refused.call(|_| ()).await.is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Jump to anything 🧭
+//!
+//! `Ctrl+p` fuzzy-searches songs, albums, artists, playlists, views and `:`
+//! commands in one list. `Enter` does the obvious thing with the pick:
+//! plays a song, opens an album, artist or playlist in the library, shows a
+//! view or runs a command.
+
+use crate::app::library_cache::CachedSong;
+use crate::app::{AlbumKey, ViewMode};
+use crate::t;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+/// Matches shown at most
+pub const MAX_MATCHES: usize = 50;
+
+/// What picking an entry does
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    /// Play this file
+    Song(String),
+    /// List the album's tracks
+    Album(AlbumKey),
+    /// List the artist's songs
+    Artist(String),
+    /// Show it in the Playlists tab
+    Playlist(String),
+    View(ViewMode),
+    /// Run `:command`. A trailing space means it takes an argument, so the
+    /// command line opens with it typed instead.
+    Command(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub label: String,
+    /// Artist, album or what a command does; searched too
+    pub detail: String,
+    pub target: Target,
+}
+
+impl Entry {
+    pub fn icon(&self) -> &'static str {
+        match self.target {
+            Target::Song(_) => "🎵",
+            Target::Album(_) => "💿",
+            Target::Artist(_) => "🎤",
+            Target::Playlist(_) => "📜",
+            Target::View(_) => "🪟",
+            Target::Command(_) => "💬",
+        }
+    }
+}
+
+/// Views and `:` commands, listed before the library
+pub fn commands() -> Vec<Entry> {
+    let view = |label: String, mode| Entry {
+        label,
+        detail: t!("palette.view"),
+        target: Target::View(mode),
+    };
+    let command = |command: &str, detail: String| Entry {
+        label: format!(":{}", command.trim_end()),
+        detail,
+        target: Target::Command(command.to_string()),
+    };
+    vec![
+        view(t!("palette.view_lyrics"), ViewMode::Lyrics),
+        view(t!("palette.view_visualizer"), ViewMode::Visualizer),
+        view(t!("palette.view_library"), ViewMode::Library),
+        view(t!("palette.view_eq"), ViewMode::EQ),
+        view(t!("palette.view_info"), ViewMode::Info),
+        view(t!("palette.view_related"), ViewMode::Related),
+        command("clear", t!("palette.cmd_clear")),
+        command("missing", t!("palette.cmd_missing")),
+        command("messages", t!("palette.cmd_messages")),
+        command("messages clear", t!("palette.cmd_messages_clear")),
+        command("snapshot", t!("palette.cmd_snapshot_list")),
+        command("snapshot save ", t!("palette.cmd_snapshot_save")),
+        command("snapshot load ", t!("palette.cmd_snapshot_load")),
+        command("name2tag ", t!("palette.cmd_name2tag")),
+        command("tag2name ", t!("palette.cmd_tag2name")),
+        command("party", t!("palette.cmd_party")),
+        command("wrapped", t!("palette.cmd_wrapped")),
+        command("open ", t!("palette.cmd_open")),
+        command("stream", t!("palette.cmd_stream")),
+        command("download", t!("palette.cmd_download")),
+        command("download ", t!("palette.cmd_download_link")),
+        command("record", t!("palette.cmd_record")),
+        command("downloads", t!("palette.cmd_downloads")),
+        command("cd", t!("palette.cmd_cd")),
+        command("dsp", t!("palette.cmd_dsp")),
+    ]
+}
+
+/// Indices of the entries matching `query`, best first. Equal scores keep
+/// list order, so commands come before playlists, artists, albums and songs.
+pub fn filter(entries: &[Entry], query: &str) -> Vec<usize> {
+    let query = query.trim();
+    if query.is_empty() {
+        return (0..entries.len().min(MAX_MATCHES)).collect();
+    }
+    let matcher = SkimMatcherV2::default();
+    let mut scored: Vec<(i64, usize)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let text = format!("{} {}", entry.label, entry.detail);
+            matcher.fuzzy_match(&text, query).map(|score| (score, i))
+        })
+        .collect();
+    scored.sort_by_key(|&(score, i)| (std::cmp::Reverse(score), i));
+    scored.truncate(MAX_MATCHES);
+    scored.into_iter().map(|(_, i)| i).collect()
+}
+
+/// Everything to search: commands, `playlists`, then the library's
+/// artists, albums and songs
+pub fn collect(songs: &[CachedSong], playlists: &[String]) -> Vec<Entry> {
+    use std::collections::BTreeSet;
+
+    let mut entries = commands();
+    entries.extend(playlists.iter().map(|name| Entry {
+        label: name.clone(),
+        detail: t!("palette.playlist"),
+        target: Target::Playlist(name.clone()),
+    }));
+
+    let artists: BTreeSet<&str> = songs
+        .iter()
+        .filter_map(|s| s.artist.as_deref())
+        .filter(|a| !a.trim().is_empty())
+        .collect();
+    entries.extend(artists.into_iter().map(|artist| Entry {
+        label: artist.to_string(),
+        detail: t!("palette.artist"),
+        target: Target::Artist(artist.to_string()),
+    }));
+
+    // One entry per album and album artist, by title
+    let mut albums: Vec<AlbumKey> = songs.iter().filter_map(CachedSong::album_key).collect();
+    albums.sort_by(|a, b| (&a.name, &a.artist).cmp(&(&b.name, &b.artist)));
+    albums.dedup();
+    entries.extend(albums.into_iter().map(|album| Entry {
+        label: album.name.clone(),
+        detail: album.artist.clone(),
+        target: Target::Album(album),
+    }));
+
+    entries.extend(songs.iter().map(|song| Entry {
+        label: song.name().to_string(),
+        detail: song.artist.clone().unwrap_or_default(),
+        target: Target::Song(song.file.clone()),
+    }));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_ranks_and_keeps_order() {
+        let entry = |label: &str, target| Entry {
+            label: label.to_string(),
+            detail: String::new(),
+            target,
+        };
+        let entries = vec![
+            entry(":missing", Target::Command("missing".into())),
+            entry("Miles Davis", Target::Artist("Miles Davis".into())),
+            entry(
+                "Kind of Blue",
+                Target::Album(AlbumKey {
+                    artist: "Miles Davis".into(),
+                    name: "Kind of Blue".into(),
+                }),
+            ),
+            entry("Milestones", Target::Song("m/milestones.flac".into())),
+        ];
+        assert_eq!(filter(&entries, ""), vec![0, 1, 2, 3]);
+        assert_eq!(filter(&entries, "kind blue"), vec![2]);
+        let miles = filter(&entries, "miles");
+        assert_eq!(miles.len(), 2);
+        assert!(miles.contains(&1) && miles.contains(&3));
+        assert!(filter(&entries, "zzz").is_empty());
+    }
+}
@@ -0,0 +1,317 @@
+//! Playlist files from other players 📃
+//!
+//! M3U/M3U8, PLS and XSPF files are read into MPD's names for their songs,
+//! so `P` in the Library can queue them: relative paths are looked up next
+//! to the playlist first and otherwise taken as relative to the music
+//! directory, the way MPD's own playlists are written. `E` writes a saved
+//! playlist out as M3U8, with paths relative to the file (portable along
+//! with the music) or absolute (`playlist_paths = "absolute"`).
+
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+/// How exported playlists point at songs
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    /// From the playlist file's folder (`../Jazz/...`)
+    #[default]
+    Relative,
+    /// Full paths under the music directory
+    Absolute,
+}
+
+/// A song of a playlist being exported
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Entry {
+    /// MPD's name: relative to the music directory, or a stream URL
+    pub file: String,
+    pub artist: Option<String>,
+    pub title: Option<String>,
+    pub secs: Option<u64>,
+}
+
+/// `~/` expanded
+pub fn expand(input: &str) -> PathBuf {
+    match input.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| PathBuf::from(input)),
+        None => PathBuf::from(input),
+    }
+}
+
+/// The songs of a playlist file, as MPD names them
+pub fn read(path: &Path, music_dir: &Path) -> std::io::Result<Vec<String>> {
+    let text = std::fs::read_to_string(path)?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    Ok(parse(&text)
+        .iter()
+        .map(|entry| resolve(entry, dir, music_dir))
+        .collect())
+}
+
+/// The entries of an M3U, PLS or XSPF playlist as written in it
+pub fn parse(text: &str) -> Vec<String> {
+    let text = text.trim_start_matches('\u{feff}');
+    let head = text.trim_start();
+    if head.starts_with("[playlist]") {
+        parse_pls(text)
+    } else if head.starts_with("<?xml") || head.starts_with("<playlist") {
+        parse_xspf(text)
+    } else {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// `File1=...`, `File2=...`, in their numbered order
+fn parse_pls(text: &str) -> Vec<String> {
+    let mut files: Vec<(u32, String)> = text
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once('=')?;
+            let n = key.trim().strip_prefix("File")?.parse().ok()?;
+            Some((n, value.trim().to_string()))
+        })
+        .collect();
+    files.sort_by_key(|(n, _)| *n);
+    files.into_iter().map(|(_, file)| file).collect()
+}
+
+/// Every `<location>` (XSPF locations are URIs, percent-encoded)
+fn parse_xspf(text: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("<location>") {
+        rest = &rest[start + "<location>".len()..];
+        let Some(end) = rest.find("</location>") else {
+            break;
+        };
+        let location = unescape_xml(rest[..end].trim());
+        if is_url(&location) {
+            entries.push(location);
+        } else {
+            entries.push(percent_decode(&location));
+        }
+        rest = &rest[end..];
+    }
+    entries
+}
+
+/// MPD's name for an entry: streams stay URLs, paths under the music
+/// directory lose that prefix
+pub fn resolve(entry: &str, playlist_dir: &Path, music_dir: &Path) -> String {
+    let path = match entry.strip_prefix("file://") {
+        Some(rest) => percent_decode(rest),
+        None if is_url(entry) => return entry.to_string(),
+        None => entry.replace('\\', "/"),
+    };
+    let path = Path::new(&path);
+    if path.is_absolute() {
+        return match path.strip_prefix(music_dir) {
+            Ok(inside) => inside.to_string_lossy().to_string(),
+            Err(_) => crate::app::remote::library_path(path, music_dir),
+        };
+    }
+    let beside = playlist_dir.join(path);
+    if beside.exists() {
+        return crate::app::remote::library_path(&beside, music_dir);
+    }
+    // `../Jazz/...` from a playlist inside the music directory, on a
+    // machine where the songs aren't (mounted) yet
+    if path.starts_with("..") {
+        if let Ok(inside) = normalize(&beside).strip_prefix(music_dir) {
+            return inside.to_string_lossy().to_string();
+        }
+    }
+    path.to_string_lossy().to_string()
+}
+
+/// `a/b/../c` -> `a/c`, without touching the disk
+fn normalize(path: &Path) -> PathBuf {
+    let mut normal = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normal.pop();
+            }
+            Component::CurDir => {}
+            other => normal.push(other),
+        }
+    }
+    normal
+}
+
+/// An M3U8 playlist of `entries`, to be written into `out_dir`
+pub fn to_m3u8(entries: &[Entry], style: PathStyle, music_dir: &Path, out_dir: &Path) -> String {
+    let mut out = String::from("#EXTM3U\n");
+    for entry in entries {
+        let name = match (&entry.artist, &entry.title) {
+            (Some(artist), Some(title)) => format!("{} - {}", artist, title),
+            (None, Some(title)) => title.clone(),
+            _ => entry
+                .file
+                .rsplit('/')
+                .next()
+                .unwrap_or(&entry.file)
+                .to_string(),
+        };
+        let secs = entry.secs.map_or(-1, |s| s as i64);
+        out.push_str(&format!("#EXTINF:{},{}\n", secs, name));
+
+        let location = if is_url(&entry.file) {
+            entry.file.clone()
+        } else {
+            let full = music_dir.join(&entry.file);
+            match style {
+                PathStyle::Absolute => full.to_string_lossy().to_string(),
+                PathStyle::Relative => relative_to(&full, out_dir).to_string_lossy().to_string(),
+            }
+        };
+        out.push_str(&location);
+        out.push('\n');
+    }
+    out
+}
+
+/// `target` as seen from `base` (both absolute)
+fn relative_to(target: &Path, base: &Path) -> PathBuf {
+    let target: Vec<Component> = target.components().collect();
+    let base: Vec<Component> = base.components().collect();
+    let common = target.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    for component in &target[common..] {
+        relative.push(component);
+    }
+    relative
+}
+
+fn is_url(entry: &str) -> bool {
+    entry.split_once("://").is_some_and(|(scheme, _)| {
+        !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+/// `%20` -> ` `; broken escapes are kept as written
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_three_formats_parse() {
+        let m3u = "\u{feff}#EXTM3U\n#EXTINF:245,Radiohead - Airbag\nRadiohead/01 Airbag.flac\n\nhttp://radio.example/stream\n";
+        assert_eq!(
+            parse(m3u),
+            vec!["Radiohead/01 Airbag.flac", "http://radio.example/stream"]
+        );
+
+        let pls = "[playlist]\nFile2=b.mp3\nTitle2=B\nFile1=a.mp3\nNumberOfEntries=2\nVersion=2\n";
+        assert_eq!(parse(pls), vec!["a.mp3", "b.mp3"]);
+
+        let xspf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<playlist version="1" xmlns="http://xspf.org/ns/0/">
+  <trackList>
+    <track><location>file:///music/Bj%C3%B6rk/J%C3%B3ga.flac</location></track>
+    <track><location>Simon%20&amp;%20Garfunkel/America.mp3</location></track>
+  </trackList>
+</playlist>"#;
+        assert_eq!(
+            parse(xspf),
+            vec![
+                "file:///music/Bj%C3%B6rk/J%C3%B3ga.flac",
+                "Simon & Garfunkel/America.mp3"
+            ]
+        );
+
+        let music = Path::new("/no/such/music");
+        let here = Path::new("/no/such/playlists");
+        assert_eq!(
+            resolve(&parse(xspf)[0], here, Path::new("/music")),
+            "Björk/Jóga.flac"
+        );
+        assert_eq!(
+            resolve("Jazz\\So What.flac", here, music),
+            "Jazz/So What.flac"
+        );
+        assert_eq!(
+            resolve("http://radio.example/stream", here, music),
+            "http://radio.example/stream"
+        );
+    }
+
+    #[test]
+    fn test_m3u8_paths() {
+        let entries = vec![
+            Entry {
+                file: "Radiohead/OK Computer/01 Airbag.flac".into(),
+                artist: Some("Radiohead".into()),
+                title: Some("Airbag".into()),
+                secs: Some(284),
+            },
+            Entry {
+                file: "https://radio.example/stream".into(),
+                ..Default::default()
+            },
+        ];
+        let music = Path::new("/home/me/Music");
+        let relative = to_m3u8(
+            &entries,
+            PathStyle::Relative,
+            music,
+            Path::new("/home/me/Music/Playlists"),
+        );
+        assert_eq!(
+            relative,
+            "#EXTM3U\n#EXTINF:284,Radiohead - Airbag\n../Radiohead/OK Computer/01 Airbag.flac\n#EXTINF:-1,stream\nhttps://radio.example/stream\n"
+        );
+        let absolute = to_m3u8(&entries[..1], PathStyle::Absolute, music, Path::new("/tmp"));
+        assert!(absolute.ends_with("\n/home/me/Music/Radiohead/OK Computer/01 Airbag.flac\n"));
+        // And back again
+        assert_eq!(
+            resolve(
+                &parse(&relative)[0],
+                Path::new("/home/me/Music/Playlists"),
+                music
+            ),
+            "Radiohead/OK Computer/01 Airbag.flac"
+        );
+    }
+}
@@ -0,0 +1,390 @@
+//! Related artists for the Discover view 🧭
+//!
+//! Artists similar to the one playing, from Last.fm (with `lastfm_api_key`
+//! set) or ListenBrainz, checked against the library so the ones already in
+//! it come first. Looked up only while the view is showing, once per artist,
+//! and only once the library listing is there to check against.
+
+use crate::app::events::AppEvent;
+use crate::app::library_cache::LibraryCache;
+use crate::app::App;
+use crate::artwork::{lucene_escape, ArtworkRenderer, MUSICBRAINZ_USER_AGENT};
+use anyhow::Result;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Similar artists asked for
+const LIMIT: usize = 100;
+/// MusicBrainz artist matches below this are usually someone else
+const MIN_ARTIST_SCORE: u32 = 90;
+const LISTENBRAINZ_ALGORITHM: &str =
+    "session_based_days_7500_session_300_contribution_5_threshold_10_limit_100_filter_True_skip_30";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelatedArtist {
+    /// As the library spells it when it's there
+    pub name: String,
+    /// How similar, 0-1
+    pub score: f32,
+    /// Albums of theirs in the library; 0 when they aren't in it
+    pub albums: usize,
+    pub in_library: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum RelatedState {
+    #[default]
+    Idle,
+    Loading,
+    /// The artists and where they came from ("Last.fm" / "ListenBrainz")
+    Loaded(Vec<RelatedArtist>, &'static str),
+    NotFound,
+    Failed(String),
+}
+
+/// What the lookup is keyed on: one per artist
+pub fn related_key(artist: &str) -> String {
+    normalize(artist)
+}
+
+/// Look up the playing artist if that hasn't been done yet
+pub fn ensure(app: &mut App, tx: &mpsc::Sender<AppEvent>, client: &Client) {
+    let Some(artist) = app.track.as_ref().map(|t| t.artist.clone()) else {
+        return;
+    };
+    let Some(cache) = app.library_cache.clone() else {
+        return;
+    };
+    if artist.trim().is_empty() {
+        return;
+    }
+    let key = related_key(&artist);
+    if app.related_request.wants(&key) {
+        return;
+    }
+    let api_key = app.lastfm_api_key.clone();
+    let (tx, client) = (tx.clone(), client.clone());
+    app.related = RelatedState::Loading;
+    app.related_selected = 0;
+    app.related_request.start(key.clone(), async move {
+        let state = match fetch(&client, &artist, api_key.as_deref()).await {
+            Ok((similar, _)) if similar.is_empty() => RelatedState::NotFound,
+            Ok((similar, source)) => {
+                RelatedState::Loaded(in_library(&artist, similar, &cache), source)
+            }
+            Err(e) => RelatedState::Failed(e.to_string()),
+        };
+        if let Err(e) = tx.send(AppEvent::RelatedUpdate(key, state)).await {
+            tracing::debug!("Channel closed: {}", e);
+        }
+    });
+}
+
+/// Similar artists with a 0-1 score, best first, and the source
+pub async fn fetch(
+    client: &Client,
+    artist: &str,
+    lastfm_api_key: Option<&str>,
+) -> Result<(Vec<(String, f32)>, &'static str)> {
+    if let Some(api_key) = lastfm_api_key {
+        match lastfm(client, api_key, artist).await {
+            Ok(similar) if !similar.is_empty() => return Ok((similar, "Last.fm")),
+            Ok(_) => {}
+            Err(e) => tracing::debug!("Last.fm lookup failed: {}", e),
+        }
+    }
+    Ok((listenbrainz(client, artist).await?, "ListenBrainz"))
+}
+
+/// Match `similar` against the library's artists: those in it first, each
+/// group still by score
+pub fn in_library(
+    artist: &str,
+    similar: Vec<(String, f32)>,
+    cache: &LibraryCache,
+) -> Vec<RelatedArtist> {
+    // Normalized name -> (library spelling, its albums)
+    let mut library: HashMap<String, (&str, BTreeSet<&str>)> = HashMap::new();
+    for song in &cache.songs {
+        let names = song.artist.iter().chain(song.album_artist.iter());
+        for name in names.filter(|n| !n.trim().is_empty()) {
+            let entry = library
+                .entry(normalize(name))
+                .or_insert_with(|| (name.as_str(), BTreeSet::new()));
+            if let Some(album) = song.album.as_deref().filter(|a| !a.trim().is_empty()) {
+                entry.1.insert(album);
+            }
+        }
+    }
+
+    let own = normalize(artist);
+    let mut seen = BTreeSet::new();
+    let mut related: Vec<RelatedArtist> = similar
+        .into_iter()
+        .filter_map(|(name, score)| {
+            let key = normalize(&name);
+            if key.is_empty() || key == own || !seen.insert(key.clone()) {
+                return None;
+            }
+            Some(match library.get(&key) {
+                Some((spelling, albums)) => RelatedArtist {
+                    name: spelling.to_string(),
+                    score,
+                    albums: albums.len(),
+                    in_library: true,
+                },
+                None => RelatedArtist {
+                    name,
+                    score,
+                    albums: 0,
+                    in_library: false,
+                },
+            })
+        })
+        .collect();
+    related.sort_by_key(|a| !a.in_library);
+    related
+}
+
+/// Compare artist names loosely: case, "The", "&" and punctuation aside
+pub fn normalize(name: &str) -> String {
+    let lower = name.trim().to_lowercase().replace('&', "and");
+    let lower = lower.strip_prefix("the ").unwrap_or(&lower);
+    lower.chars().filter(|c| c.is_alphanumeric()).collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct LastfmResponse {
+    similarartists: Option<LastfmSimilar>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LastfmSimilar {
+    #[serde(default)]
+    artist: Vec<LastfmArtist>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LastfmArtist {
+    name: String,
+    /// A number in a string, "0.93"
+    #[serde(rename = "match", default)]
+    score: String,
+}
+
+async fn lastfm(client: &Client, api_key: &str, artist: &str) -> Result<Vec<(String, f32)>> {
+    let limit = LIMIT.to_string();
+    let response: LastfmResponse = client
+        .get("https://ws.audioscrobbler.com/2.0/")
+        .query(&[
+            ("method", "artist.getsimilar"),
+            ("artist", artist),
+            ("api_key", api_key),
+            ("autocorrect", "1"),
+            ("limit", limit.as_str()),
+            ("format", "json"),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(lastfm_artists(response))
+}
+
+/// An unknown artist comes back as an error object: nothing similar
+fn lastfm_artists(response: LastfmResponse) -> Vec<(String, f32)> {
+    response
+        .similarartists
+        .map(|s| s.artist)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|a| (a.name, a.score.parse().unwrap_or(0.0)))
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct MbArtistSearch {
+    #[serde(default)]
+    artists: Vec<MbArtist>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbArtist {
+    id: String,
+    name: String,
+    #[serde(default)]
+    score: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct LbArtist {
+    name: String,
+    #[serde(default)]
+    score: f64,
+}
+
+/// ListenBrainz goes by MusicBrainz id, so find the artist there first
+async fn listenbrainz(client: &Client, artist: &str) -> Result<Vec<(String, f32)>> {
+    ArtworkRenderer::musicbrainz_throttle().await;
+    let query = format!("artist:\"{}\"", lucene_escape(artist));
+    let search: MbArtistSearch = client
+        .get("https://musicbrainz.org/ws/2/artist/")
+        .header(reqwest::header::USER_AGENT, MUSICBRAINZ_USER_AGENT)
+        .query(&[("query", query.as_str()), ("fmt", "json"), ("limit", "5")])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let Some(mbid) = artist_mbid(&search, artist) else {
+        return Ok(Vec::new());
+    };
+
+    let similar: Vec<LbArtist> = client
+        .get("https://labs.api.listenbrainz.org/similar-artists/json")
+        .query(&[
+            ("artist_mbids", mbid.as_str()),
+            ("algorithm", LISTENBRAINZ_ALGORITHM),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(listenbrainz_artists(similar))
+}
+
+/// The best match spelled like `artist`, else the best match that's sure
+fn artist_mbid(search: &MbArtistSearch, artist: &str) -> Option<String> {
+    let wanted = normalize(artist);
+    let candidates = search
+        .artists
+        .iter()
+        .filter(|a| a.score >= MIN_ARTIST_SCORE);
+    candidates
+        .clone()
+        .find(|a| normalize(&a.name) == wanted)
+        .or_else(|| candidates.max_by_key(|a| a.score))
+        .map(|a| a.id.clone())
+}
+
+/// Scores are counts; scale them to 0-1 against the best
+fn listenbrainz_artists(similar: Vec<LbArtist>) -> Vec<(String, f32)> {
+    let best = similar.iter().map(|a| a.score).fold(0.0, f64::max);
+    let mut artists: Vec<(String, f32)> = similar
+        .into_iter()
+        .map(|a| {
+            let score = if best > 0.0 { a.score / best } else { 0.0 };
+            (a.name, score as f32)
+        })
+        .collect();
+    artists.sort_by(|a, b| b.1.total_cmp(&a.1));
+    artists.truncate(LIMIT);
+    artists
+}
+
+/// Keep a loaded list when the library listing is refreshed
+pub fn refresh(state: &mut RelatedState, artist: &str, cache: &Arc<LibraryCache>) {
+    if let RelatedState::Loaded(artists, _) = state {
+        let similar = artists
+            .drain(..)
+            .map(|a| (a.name, a.score))
+            .collect::<Vec<_>>();
+        *artists = in_library(artist, similar, cache);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::library_cache::CachedSong;
+
+    fn song(artist: &str, album_artist: Option<&str>, album: &str) -> CachedSong {
+        CachedSong {
+            file: format!("{}/{}.flac", artist, album),
+            artist: Some(artist.into()),
+            album_artist: album_artist.map(str::to_string),
+            album: Some(album.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_library_artists_come_first() {
+        let cache = LibraryCache {
+            songs: vec![
+                song("Portishead", None, "Dummy"),
+                song("Portishead", None, "Third"),
+                song("Massive Attack", None, "Mezzanine"),
+                song("John Lennon", Some("The Beatles"), "Abbey Road"),
+                song("Radiohead", None, "OK Computer"),
+            ],
+            ..Default::default()
+        };
+        let similar = vec![
+            ("Björk".to_string(), 1.0),
+            ("portishead".to_string(), 0.9),
+            ("Beatles".to_string(), 0.8),
+            ("Massive Attack".to_string(), 0.7),
+            ("Radiohead".to_string(), 0.6),
+        ];
+        let related = in_library("Radiohead", similar, &cache);
+        let names: Vec<(&str, usize, bool)> = related
+            .iter()
+            .map(|a| (a.name.as_str(), a.albums, a.in_library))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Portishead", 2, true),
+                ("The Beatles", 1, true),
+                ("Massive Attack", 1, true),
+                ("Björk", 0, false),
+            ]
+        );
+        assert_eq!(normalize("The Mamas & The Papas"), "mamasandthepapas");
+    }
+
+    #[test]
+    fn test_api_responses() {
+        let lastfm: LastfmResponse = serde_json::from_str(
+            r#"{"similarartists":{"artist":[
+                {"name":"Thom Yorke","match":"1"},
+                {"name":"Muse","match":"0.25"}
+            ]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            lastfm_artists(lastfm),
+            vec![("Thom Yorke".to_string(), 1.0), ("Muse".to_string(), 0.25)]
+        );
+        let unknown: LastfmResponse =
+            serde_json::from_str(r#"{"error":6,"message":"The artist could not be found"}"#)
+                .unwrap();
+        assert!(lastfm_artists(unknown).is_empty());
+
+        let search: MbArtistSearch = serde_json::from_str(
+            r#"{"artists":[
+                {"id":"a","name":"Radiohead Tribute","score":95},
+                {"id":"b","name":"Radiohead","score":93},
+                {"id":"c","name":"Radiohead","score":40}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(artist_mbid(&search, "radiohead").as_deref(), Some("b"));
+        assert_eq!(artist_mbid(&search, "Muse").as_deref(), Some("a"));
+
+        let similar: Vec<LbArtist> = serde_json::from_str(
+            r#"[{"name":"Muse","score":50},{"name":"Thom Yorke","score":200,"type":"Person"}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            listenbrainz_artists(similar),
+            vec![("Thom Yorke".to_string(), 1.0), ("Muse".to_string(), 0.25)]
+        );
+    }
+}
@@ -0,0 +1,548 @@
+//! Control a running Vyom from the command line 🛰️
+//!
+//! `vyom next`, `vyom volume +5` and friends connect to the player's Unix
+//! socket (`$XDG_RUNTIME_DIR/vyom.sock`) and send one line; the player does
+//! what a key press would and answers with one line, `ok <message>` or
+//! `err <message>`. With no player running they go straight to MPD, so
+//! window-manager hotkeys work whether the TUI is open or not.
+
+use crate::app::events::AppEvent;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+/// What the player answers: a message to print, or why it didn't work
+pub type Reply = Result<String, String>;
+
+/// How long a command may take before the client gives up
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where to seek: `90` / `1:30` from the start, `+10` / `-10` from here
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Seek {
+    To(f64),
+    By(f64),
+}
+
+impl Seek {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        let (sign, body) = match s.strip_prefix('+') {
+            Some(rest) => (Some(1.0), rest),
+            None => match s.strip_prefix('-') {
+                Some(rest) => (Some(-1.0), rest),
+                None => (None, s),
+            },
+        };
+        let secs = parse_time(body).ok_or_else(|| format!("Not a time: {}", s))?;
+        Ok(match sign {
+            Some(sign) => Seek::By(sign * secs),
+            None => Seek::To(secs),
+        })
+    }
+
+    /// Seconds to seek to, kept inside the track
+    pub fn target(&self, position: f64, duration: f64) -> f64 {
+        let target = match self {
+            Seek::To(secs) => *secs,
+            Seek::By(delta) => position + delta,
+        };
+        target.min(duration).max(0.0)
+    }
+}
+
+/// `90`, `1:30` or `1:02:03`, in seconds
+fn parse_time(s: &str) -> Option<f64> {
+    let mut secs = 0.0;
+    for part in s.split(':') {
+        let value: f64 = part.parse().ok()?;
+        if !value.is_finite() || value < 0.0 {
+            return None;
+        }
+        secs = secs * 60.0 + value;
+    }
+    Some(secs)
+}
+
+/// New volume: `40` or a step like `+5` / `-5`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Volume {
+    To(u8),
+    By(i16),
+}
+
+impl Volume {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim().trim_end_matches('%');
+        let bad = || format!("Not a volume (0-100, +N or -N): {}", s);
+        if s.starts_with(['+', '-']) {
+            s.parse::<i16>().map(Volume::By).map_err(|_| bad())
+        } else {
+            match s.parse::<u8>() {
+                Ok(v) if v <= 100 => Ok(Volume::To(v)),
+                _ => Err(bad()),
+            }
+        }
+    }
+
+    /// Apply to the current volume, clamped to 0-100
+    pub fn apply(&self, current: u8) -> u8 {
+        match self {
+            Volume::To(v) => (*v).min(100),
+            Volume::By(delta) => (current as i16 + delta).clamp(0, 100) as u8,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteCommand {
+    Play,
+    Pause,
+    Toggle,
+    Next,
+    Prev,
+    Seek(Seek),
+    Volume(Volume),
+    /// A file or folder, as MPD names it (relative to the music directory)
+    QueueAdd(String),
+}
+
+impl RemoteCommand {
+    /// The line sent over the socket
+    pub fn to_line(&self) -> String {
+        match self {
+            RemoteCommand::Play => "play".to_string(),
+            RemoteCommand::Pause => "pause".to_string(),
+            RemoteCommand::Toggle => "toggle".to_string(),
+            RemoteCommand::Next => "next".to_string(),
+            RemoteCommand::Prev => "prev".to_string(),
+            RemoteCommand::Seek(Seek::To(secs)) => format!("seek {}", secs),
+            RemoteCommand::Seek(Seek::By(delta)) => format!("seek {:+}", delta),
+            RemoteCommand::Volume(Volume::To(v)) => format!("volume {}", v),
+            RemoteCommand::Volume(Volume::By(delta)) => format!("volume {:+}", delta),
+            RemoteCommand::QueueAdd(path) => format!("queue-add {}", path),
+        }
+    }
+
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let (word, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match word {
+            "play" => Ok(RemoteCommand::Play),
+            "pause" => Ok(RemoteCommand::Pause),
+            "toggle" => Ok(RemoteCommand::Toggle),
+            "next" => Ok(RemoteCommand::Next),
+            "prev" => Ok(RemoteCommand::Prev),
+            "seek" => Seek::parse(rest).map(RemoteCommand::Seek),
+            "volume" => Volume::parse(rest).map(RemoteCommand::Volume),
+            "queue-add" if !rest.is_empty() => Ok(RemoteCommand::QueueAdd(rest.to_string())),
+            _ => Err(format!("Unknown command: {}", line)),
+        }
+    }
+}
+
+/// MPD's name for a path given on the command line: files under the music
+/// directory lose that prefix, anything else is passed on as written
+pub fn library_path(path: &std::path::Path, music_dir: &std::path::Path) -> String {
+    let full = std::fs::canonicalize(path).ok();
+    let root = std::fs::canonicalize(music_dir).unwrap_or_else(|_| music_dir.to_path_buf());
+    full.as_deref()
+        .and_then(|full| full.strip_prefix(&root).ok())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// `$XDG_RUNTIME_DIR/vyom.sock`, else a socket in a private per-user
+/// directory under `/tmp`
+pub fn socket_path() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join("vyom.sock"),
+        #[cfg(unix)]
+        // SAFETY: getuid has no preconditions
+        None => PathBuf::from(format!("/tmp/vyom-{}", unsafe { libc::getuid() })).join("vyom.sock"),
+        #[cfg(not(unix))]
+        None => std::env::temp_dir().join("vyom.sock"),
+    }
+}
+
+/// Whether `path` belongs to this user, so commands sent there don't
+/// reach anyone else
+#[cfg(unix)]
+pub fn owned_by_us(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    // SAFETY: getuid has no preconditions
+    std::fs::symlink_metadata(path).is_ok_and(|meta| meta.uid() == unsafe { libc::getuid() })
+}
+
+/// Create `dir` readable by us alone, or check that an existing one is
+#[cfg(unix)]
+fn private_dir(dir: &std::path::Path) -> std::io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Err(e) if e.kind() != std::io::ErrorKind::AlreadyExists => return Err(e),
+        _ => {}
+    }
+    let meta = std::fs::symlink_metadata(dir)?;
+    if !meta.is_dir() || !owned_by_us(dir) || meta.permissions().mode() & 0o077 != 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "not a private directory of ours",
+        ));
+    }
+    Ok(())
+}
+
+// --- Player side ---
+
+/// Removes the socket when the player quits
+pub struct Listener {
+    path: PathBuf,
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            tracing::debug!("Failed to remove control socket: {}", e);
+        }
+    }
+}
+
+/// Accept commands on the default socket
+pub fn listen(tx: mpsc::Sender<AppEvent>) -> Option<Listener> {
+    listen_at(socket_path(), tx)
+}
+
+/// Accept commands on `path` and hand them to the event loop as
+/// `AppEvent::Remote`. `None` when another player already answers there
+/// (the first one keeps control) or the socket can't be created.
+///
+/// The socket lives in a directory only we can enter and is itself
+/// `0600`, so other users can't send commands or stand in for us.
+#[cfg(unix)]
+pub fn listen_at(path: PathBuf, tx: mpsc::Sender<AppEvent>) -> Option<Listener> {
+    use std::os::unix::fs::PermissionsExt;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    if let Some(dir) = path.parent() {
+        if let Err(e) = private_dir(dir) {
+            tracing::warn!("Refusing control socket in {}: {}", dir.display(), e);
+            return None;
+        }
+    }
+    if path.exists() {
+        if !owned_by_us(&path) {
+            tracing::warn!("Control socket {} belongs to another user", path.display());
+            return None;
+        }
+        if std::os::unix::net::UnixStream::connect(&path).is_ok() {
+            tracing::info!("Another instance owns {}", path.display());
+            return None;
+        }
+        // Left over from a crash
+        let _ = std::fs::remove_file(&path);
+    }
+    let listener = match tokio::net::UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::warn!("Failed to open control socket {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    if let Err(e) = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)) {
+        tracing::warn!(
+            "Failed to restrict control socket {}: {}",
+            path.display(),
+            e
+        );
+        return None;
+    }
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut line = String::new();
+                // Nothing sent: someone checking whether we're alive
+                if BufReader::new(read).read_line(&mut line).await.is_err() || line.is_empty() {
+                    return;
+                }
+                if line.trim_end() == "subscribe" {
+                    return stream_state(tx, write).await;
+                }
+                let reply = match RemoteCommand::parse(&line) {
+                    Ok(command) => {
+                        let (reply_tx, reply_rx) = oneshot::channel();
+                        if tx.send(AppEvent::Remote(command, reply_tx)).await.is_err() {
+                            return;
+                        }
+                        reply_rx
+                            .await
+                            .unwrap_or_else(|_| Err("Player is shutting down".to_string()))
+                    }
+                    Err(e) => Err(e),
+                };
+                let out = match reply {
+                    Ok(msg) => format!("ok {}\n", msg),
+                    Err(e) => format!("err {}\n", e),
+                };
+                if let Err(e) = write.write_all(out.as_bytes()).await {
+                    tracing::debug!("Failed to answer remote command: {}", e);
+                }
+            });
+        }
+    });
+    Some(Listener { path })
+}
+
+/// Feed a Shared Audio Mode instance until it or we go away
+#[cfg(unix)]
+async fn stream_state(tx: mpsc::Sender<AppEvent>, mut write: tokio::net::unix::OwnedWriteHalf) {
+    use tokio::io::AsyncWriteExt;
+    use tokio::sync::broadcast::error::RecvError;
+
+    let (reply_tx, reply_rx) = oneshot::channel();
+    if tx.send(AppEvent::Subscribe(reply_tx)).await.is_err() {
+        return;
+    }
+    let mut updates = match reply_rx.await {
+        Ok(Ok(updates)) => updates,
+        Ok(Err(e)) => {
+            let _ = write.write_all(format!("err {}\n", e).as_bytes()).await;
+            return;
+        }
+        Err(_) => return,
+    };
+    if write.write_all(b"ok subscribed\n").await.is_err() {
+        return;
+    }
+    loop {
+        let line = match updates.recv().await {
+            Ok(line) => line,
+            // Too slow: skip the frames it missed
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return,
+        };
+        if write
+            .write_all(format!("{}\n", line).as_bytes())
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn listen_at(_path: PathBuf, _tx: mpsc::Sender<AppEvent>) -> Option<Listener> {
+    None
+}
+
+// --- Command line side ---
+
+/// Send to the running player; `None` when there is none
+pub fn send(command: &RemoteCommand) -> Option<Reply> {
+    send_to(&socket_path(), command)
+}
+
+#[cfg(unix)]
+pub fn send_to(path: &std::path::Path, command: &RemoteCommand) -> Option<Reply> {
+    use std::io::{BufRead, BufReader, Write};
+
+    if !owned_by_us(path) {
+        return None;
+    }
+    let mut stream = std::os::unix::net::UnixStream::connect(path).ok()?;
+    let mut exchange = || -> std::io::Result<String> {
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        writeln!(stream, "{}", command.to_line())?;
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        Ok(line)
+    };
+    let line = match exchange() {
+        Ok(line) => line,
+        Err(e) => return Some(Err(e.to_string())),
+    };
+    let line = line.trim_end();
+    Some(match line.split_once(' ').unwrap_or((line, "")) {
+        ("ok", msg) => Ok(msg.to_string()),
+        ("err", msg) => Err(msg.to_string()),
+        _ => Err(format!("Unexpected reply: {}", line)),
+    })
+}
+
+#[cfg(not(unix))]
+pub fn send_to(_path: &std::path::Path, _command: &RemoteCommand) -> Option<Reply> {
+    None
+}
+
+/// No player running: do it on MPD directly
+#[cfg(feature = "mpd")]
+pub fn run_on_mpd(command: &RemoteCommand, host: &str, port: u16) -> Reply {
+    use crate::t;
+
+    let mut mpd = mpd::Client::connect(format!("{}:{}", host, port))
+        .map_err(|e| format!("MPD at {}:{}: {}", host, port, e))?;
+    let err = |e: mpd::error::Error| e.to_string();
+    match command {
+        RemoteCommand::Play => mpd.play().map(|_| t!("toast.play")).map_err(err),
+        RemoteCommand::Pause => mpd.pause(true).map(|_| t!("toast.pause")).map_err(err),
+        RemoteCommand::Toggle => {
+            let playing = mpd.status().map_err(err)?.state == mpd::State::Play;
+            if playing {
+                mpd.pause(true).map(|_| t!("toast.pause")).map_err(err)
+            } else {
+                mpd.play().map(|_| t!("toast.play")).map_err(err)
+            }
+        }
+        RemoteCommand::Next => mpd.next().map(|_| t!("toast.next_track")).map_err(err),
+        RemoteCommand::Prev => mpd.prev().map(|_| t!("toast.prev_track")).map_err(err),
+        RemoteCommand::Seek(seek) => {
+            let status = mpd.status().map_err(err)?;
+            let (Some(elapsed), Some(duration)) = (status.elapsed, status.duration) else {
+                return Err(t!("remote.nothing_playing"));
+            };
+            let target = seek.target(elapsed.as_secs_f64(), duration.as_secs_f64());
+            mpd.rewind(target).map_err(err)?;
+            Ok(t!("toast.seek_to", time = format_secs(target)))
+        }
+        RemoteCommand::Volume(volume) => {
+            let current = mpd.status().map_err(err)?.volume;
+            if current < 0 {
+                return Err(t!("remote.no_mixer"));
+            }
+            let new_vol = volume.apply(current as u8);
+            mpd.volume(new_vol as i8).map_err(err)?;
+            Ok(t!("toast.volume", percent = new_vol))
+        }
+        RemoteCommand::QueueAdd(path) => {
+            queue_add(&mut mpd, path)?;
+            Ok(t!("remote.queued", path = path))
+        }
+    }
+}
+
+/// Queue a song, or everything under a folder
+#[cfg(feature = "mpd")]
+pub fn queue_add(mpd: &mut mpd::Client, path: &str) -> Result<(), String> {
+    let song = mpd::Song {
+        file: path.to_string(),
+        ..Default::default()
+    };
+    if mpd.push(&song).is_ok() {
+        return Ok(());
+    }
+    // Not a song: maybe a folder
+    match mpd.listfiles(path) {
+        Ok(entries) if !entries.is_empty() => {
+            crate::app::library_helpers::queue_folder_recursive(mpd, path)
+                .map_err(|e| e.to_string())
+        }
+        _ => Err(crate::t!("remote.not_found", path = path)),
+    }
+}
+
+/// `01:05` for toasts and replies, like the player card
+pub fn format_secs(secs: f64) -> String {
+    let secs = secs as u64;
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_round_trip_and_parse_values() {
+        for command in [
+            RemoteCommand::Play,
+            RemoteCommand::Toggle,
+            RemoteCommand::Prev,
+            RemoteCommand::Seek(Seek::To(90.0)),
+            RemoteCommand::Seek(Seek::By(-10.0)),
+            RemoteCommand::Volume(Volume::By(5)),
+            RemoteCommand::Volume(Volume::To(40)),
+            RemoteCommand::QueueAdd("Jazz/Kind of Blue".into()),
+        ] {
+            assert_eq!(RemoteCommand::parse(&command.to_line()), Ok(command));
+        }
+        assert!(RemoteCommand::parse("rm -rf").is_err());
+        assert!(RemoteCommand::parse("queue-add ").is_err());
+
+        assert_eq!(Seek::parse("1:30"), Ok(Seek::To(90.0)));
+        assert_eq!(Seek::parse("1:02:03"), Ok(Seek::To(3723.0)));
+        assert_eq!(Seek::parse("+10"), Ok(Seek::By(10.0)));
+        assert_eq!(Seek::parse("-0:05"), Ok(Seek::By(-5.0)));
+        assert!(Seek::parse("soon").is_err());
+        assert_eq!(Seek::By(-30.0).target(10.0, 200.0), 0.0);
+        assert_eq!(Seek::To(500.0).target(10.0, 200.0), 200.0);
+
+        assert_eq!(Volume::parse("40%"), Ok(Volume::To(40)));
+        assert!(Volume::parse("101").is_err());
+        assert_eq!(Volume::parse("-5").unwrap().apply(3), 0);
+        assert_eq!(Volume::parse("+5").unwrap().apply(98), 100);
+
+        let music = std::env::temp_dir().join(format!("vyom-music-{}", std::process::id()));
+        std::fs::create_dir_all(music.join("Jazz")).unwrap();
+        assert_eq!(library_path(&music.join("Jazz"), &music), "Jazz");
+        assert_eq!(library_path("Rock/x.flac".as_ref(), &music), "Rock/x.flac");
+        std::fs::remove_dir_all(&music).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_socket_hands_commands_to_the_event_loop() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("vyom-test-{}", std::process::id()));
+        let path = dir.join("vyom.sock");
+        let (tx, mut rx) = mpsc::channel(4);
+        let listener = listen_at(path.clone(), tx.clone()).unwrap();
+        // A second player leaves the live socket alone
+        assert!(listen_at(path.clone(), tx.clone()).is_none());
+        // Only we can get at it
+        let mode = |p: &std::path::Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&dir), 0o700);
+        assert_eq!(mode(&path), 0o600);
+        // Nor will it settle in a directory others can write to
+        let shared = std::env::temp_dir().join(format!("vyom-shared-{}", std::process::id()));
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(listen_at(shared.join("vyom.sock"), tx).is_none());
+        std::fs::remove_dir(&shared).unwrap();
+
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                if let AppEvent::Remote(command, reply) = event {
+                    let _ = reply.send(match command {
+                        RemoteCommand::Next => Ok("skipped".to_string()),
+                        _ => Err("nope".to_string()),
+                    });
+                }
+            }
+        });
+
+        let client_path = path.clone();
+        let replies = tokio::task::spawn_blocking(move || {
+            (
+                send_to(&client_path, &RemoteCommand::Next),
+                send_to(&client_path, &RemoteCommand::Play),
+            )
+        })
+        .await
+        .unwrap();
+        assert_eq!(replies.0, Some(Ok("skipped".to_string())));
+        assert_eq!(replies.1, Some(Err("nope".to_string())));
+
+        drop(listener);
+        assert!(!path.exists());
+        assert_eq!(send_to(&path, &RemoteCommand::Next), None);
+        std::fs::remove_dir(&dir).unwrap();
+    }
+}
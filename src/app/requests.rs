@@ -0,0 +1,100 @@
+//! Background fetches for the playing track 🧵
+//!
+//! Skipping through a queue used to start a lyrics lookup and an artwork
+//! fetch per song, and whichever finished last won, so an old album's cover
+//! could land after the new song started. Each kind now keeps one request:
+//! a new key aborts the task still running for the old one, the same key
+//! (the next song of the same album) reuses it, and results are only
+//! applied while their key is still the one wanted.
+
+use crate::player::TrackInfo;
+use std::future::Future;
+use tokio::task::AbortHandle;
+
+#[derive(Debug, Default)]
+pub struct Request {
+    /// What the screen wants, kept after the task finished
+    key: Option<String>,
+    task: Option<AbortHandle>,
+}
+
+impl Request {
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Whether results for `key` are still wanted
+    pub fn wants(&self, key: &str) -> bool {
+        self.key.as_deref() == Some(key)
+    }
+
+    /// Still working on `key`
+    pub fn is_running(&self, key: &str) -> bool {
+        self.wants(key) && self.task.as_ref().is_some_and(|t| !t.is_finished())
+    }
+
+    /// Run `fetch` for `key`, dropping whatever ran for another key
+    pub fn start<F>(&mut self, key: String, fetch: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.cancel();
+        self.key = Some(key);
+        self.task = Some(tokio::spawn(fetch).abort_handle());
+    }
+
+    /// Nothing wanted any more
+    pub fn clear(&mut self) {
+        self.cancel();
+        self.key = None;
+    }
+
+    fn cancel(&mut self) {
+        if let Some(task) = self.task.take() {
+            if !task.is_finished() {
+                tracing::debug!("Cancelled fetch for {:?}", self.key);
+                task.abort();
+            }
+        }
+    }
+}
+
+/// Artwork is per album: songs of one album share a request
+pub fn art_key(track: &TrackInfo) -> String {
+    if track.album.is_empty() {
+        format!("{}{}", track.name, track.artist)
+    } else {
+        format!("{}\u{1f}{}", track.artist, track.album)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_new_key_cancels_and_same_key_is_kept() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut request = Request::default();
+
+        let slow = tx.clone();
+        request.start("a".into(), async move {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            let _ = slow.send("a");
+        });
+        assert!(request.is_running("a"));
+        assert!(!request.is_running("b"));
+
+        request.start("b".into(), async move {
+            let _ = tx.send("b");
+        });
+        assert_eq!(rx.recv().await, Some("b"));
+        // "a" was aborted: its sender is gone with it
+        assert_eq!(rx.recv().await, None);
+        assert!(request.wants("b") && !request.wants("a"));
+
+        request.clear();
+        assert_eq!(request.key(), None);
+    }
+}
@@ -1,10 +1,14 @@
 use crate::app::cli::Args;
-use crate::app::events::AppEvent;
+use crate::app::events::{report, AppEvent, Retry};
 use crate::app::lyrics::LyricsFetcher;
 use crate::app::{App, ArtworkState, LyricsState};
+#[cfg(feature = "mpd")]
+use crate::app::{FinishAlbumMode, TrimAction};
 use crate::artwork::ArtworkRenderer;
 use crate::audio::pipeline::AudioPipeline;
-use crate::player::PlayerTrait;
+use crate::error::VyomError;
+use crate::player::{PlayerTrait, TrackInfo};
+use crate::t;
 use crate::ui;
 
 use crossterm::event::Event;
@@ -27,8 +31,11 @@ where
     <B as Backend>::Error: std::error::Error + Send + Sync + 'static,
 {
     let mut last_track_id = String::new();
-    let mut last_artwork_url = None;
     let mut last_view_mode = app.view_mode;
+    let mut last_fullscreen_art = app.fullscreen_art;
+    let mut last_layout = app.layout;
+    let mut last_split = app.split_view;
+    let mut last_bar = app.bar_active;
 
     loop {
         // Auto-Reset Lyrics Scroll Logic
@@ -39,32 +46,180 @@ where
         }
 
         // Update visualizer bars 60fps (called before draw)
-        if app.view_mode == crate::app::ViewMode::Visualizer {
+        if app.is_audio_master && app.is_visible(crate::app::ViewMode::Visualizer) {
             app.visualizer_bars = app.visualizer.get_bars(64);
         }
+        crate::app::sync::publish(app, std::time::Instant::now());
+
+        // Level meters only tick while someone is looking at them
+        if app.show_audio_info || app.show_level_meter {
+            let frame = app.level_meter.take();
+            app.meters.update(frame, std::time::Instant::now());
+        }
+
+        // Library thumbnail for the selected folder/song 🖼️
+        #[cfg(feature = "mpd")]
+        if app.is_visible(crate::app::ViewMode::Library)
+            && app.library_mode == crate::app::LibraryMode::Directory
+        {
+            let selected = app
+                .library_items
+                .get(app.library_selected)
+                .and_then(|item| item.path.clone());
+            if let Some(path) = selected {
+                if app.library_art.as_ref().map(|(key, _)| key) != Some(&path) {
+                    app.library_art = Some((path.clone(), ArtworkState::Loading));
+                    let full_path = std::path::PathBuf::from(&app.music_directory).join(&path);
+                    let names = app.artwork_filenames.clone();
+                    let tx_art = tx.clone();
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            ArtworkRenderer::load_library_art(&full_path, &names)
+                        })
+                        .await;
+                        let state = match result {
+                            Ok(Ok(img)) => ArtworkState::Loaded(Arc::new(img)),
+                            _ => ArtworkState::Failed,
+                        };
+                        if let Err(e) = tx_art.send(AppEvent::LibraryArtUpdate(path, state)).await {
+                            tracing::debug!("Channel closed: {}", e);
+                        }
+                    });
+                }
+            }
+        }
+
+        // Album grid covers for the tiles on screen, and for the queue's
+        // album headers 💿
+        #[cfg(feature = "mpd")]
+        if app.is_visible(crate::app::ViewMode::Library) {
+            let shown: Vec<String> = match app.library_mode {
+                crate::app::LibraryMode::Albums if app.album_open.is_none() => app
+                    .albums
+                    .iter()
+                    .skip(app.album_top_row * app.album_cols)
+                    .take(app.library_page)
+                    .map(|album| album.file.clone())
+                    .collect(),
+                crate::app::LibraryMode::Queue if app.group_queue => app
+                    .queue_rows()
+                    .iter()
+                    .skip(app.library_list.offset())
+                    .take(app.library_page)
+                    .filter_map(|row| match row {
+                        crate::app::QueueRow::Album(group) => {
+                            app.queue.get(group.start).map(|q| q.file_path.clone())
+                        }
+                        crate::app::QueueRow::Song(_) => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            let missing: Vec<String> = shown
+                .into_iter()
+                .filter(|file| !app.album_art.contains_key(file))
+                .collect();
+            for file in missing {
+                app.album_art.insert(file.clone(), ArtworkState::Loading);
+                let full_path = std::path::PathBuf::from(&app.music_directory).join(&file);
+                let names = app.artwork_filenames.clone();
+                let tx_art = tx.clone();
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        ArtworkRenderer::load_library_art(&full_path, &names).map(|img| {
+                            let px = crate::ui::widgets::library::albums::THUMB_PX;
+                            img.thumbnail(px, px)
+                        })
+                    })
+                    .await;
+                    let state = match result {
+                        Ok(Ok(img)) => ArtworkState::Loaded(Arc::new(img)),
+                        _ => ArtworkState::Failed,
+                    };
+                    if let Err(e) = tx_art.send(AppEvent::AlbumArtUpdate(file, state)).await {
+                        tracing::debug!("Channel closed: {}", e);
+                    }
+                });
+            }
+        }
 
         // --- SEAMLESS POPUP OVERLAY FIX ---
         let has_popup = app.show_keyhints
             || app.show_audio_info
             || app.input_state.is_some()
-            || app.tag_edit.is_some();
+            || app.tag_edit.is_some()
+            || app.tag_tool.is_some()
+            || app.missing.is_some()
+            || app.palette.is_some()
+            || app.lyrics_lookup.is_some()
+            || app.stream_search.is_some()
+            || app.wrapped.is_some()
+            || app.theme_picker.is_some()
+            || app.messages_view.is_some()
+            || app.downloads_view.is_some()
+            || app.cd.is_some()
+            || app.dsp_view.is_some()
+            || app.log_view.is_some()
+            || app.track_details.is_some()
+            || app.console.open;
 
         let popup_closed = !has_popup && app.had_popup_last_frame;
-        let view_changed = app.view_mode != last_view_mode;
+        let view_changed = app.view_mode != last_view_mode
+            || app.fullscreen_art != last_fullscreen_art
+            || app.layout != last_layout
+            || app.split_view != last_split
+            || app.bar_active != last_bar;
 
         if popup_closed || view_changed {
             terminal.clear()?;
         }
 
         last_view_mode = app.view_mode;
+        last_fullscreen_art = app.fullscreen_art;
+        last_layout = app.layout;
+        last_split = app.split_view;
+        last_bar = app.bar_active;
         app.had_popup_last_frame = has_popup;
 
         // Reactive Rendering: Only draw if state was actually mutated
         if app.needs_redraw {
             terminal.draw(|f| ui::ui(f, app))?;
             app.needs_redraw = false; // Reset flag after a successful draw
+            app.last_frame = std::time::Instant::now();
         }
 
+        // Text artwork for a new cover, style or size: resize it off the
+        // render thread, the old buffer stays up meanwhile 🖼️
+        if let (Some(key), ArtworkState::Loaded(img)) = (app.art_cells_wanted, &app.artwork) {
+            let built = app.art_cells.as_ref().map(|cells| cells.key);
+            if key.generation == app.art_generation
+                && built != Some(key)
+                && app.art_cells_pending != Some(key)
+            {
+                app.art_cells_pending = Some(key);
+                let img = img.clone();
+                let tx_art = tx.clone();
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        crate::ui::widgets::player::art::build_cells(&img, key)
+                    })
+                    .await;
+                    if let Ok(cells) = result {
+                        if let Err(e) = tx_art.send(AppEvent::ArtCellsReady(cells)).await {
+                            tracing::debug!("Channel closed: {}", e);
+                        }
+                    }
+                });
+            }
+        }
+
+        // Tick only as fast as what's on screen needs 🎞️
+        let rate = app.frame_rate(std::time::Instant::now());
+        app.frame_ms.store(
+            rate.interval().as_millis() as u64,
+            std::sync::atomic::Ordering::Relaxed,
+        );
+
         tokio::select! {
             _ = tokio::signal::ctrl_c() => {
                 app.is_running = false;
@@ -86,9 +241,26 @@ where
                 },
                 AppEvent::Input(_) => {},
 
-                AppEvent::TrackUpdate(info) => {
+                AppEvent::TrackUpdate(mut info) => {
+                    if let Some(track) = info.as_mut() {
+                        crate::app::ytdlp::describe(track, &app.streams, &app.music_directory);
+                    }
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs())
+                        .unwrap_or(0);
+                    let ended = match &info {
+                        Some(track) => app.listening.update(track, now),
+                        None => app.listening.finish(),
+                    };
+                    if let Some(play) = ended {
+                        crate::app::history::record(&play);
+                    }
                     app.track = info.clone();
                     app.last_track_update = Some(std::time::Instant::now());
+                    if let Some(pane) = app.tmux.as_mut() {
+                        pane.update_status(app.track.as_ref());
+                    }
                     if let Some(track) = info {
                         let mpd_volume_bug = track.source == "MPD" && track.volume.unwrap_or(0) == 0 && app.app_volume > 0;
 
@@ -99,7 +271,7 @@ where
 
                         if !ignore_sync {
                             if let Some(vol) = track.volume {
-                                let new_vol = (vol as u8).min(100);
+                                let new_vol = (vol as u8).min(app.output.max_volume());
                                 if (app.app_volume as i16 - new_vol as i16).abs() > 1 {
                                     app.app_volume = new_vol;
                                     audio_pipeline.set_volume(new_vol);
@@ -107,8 +279,11 @@ where
                             }
                         }
 
-                        let id = format!("{}{}", track.name, track.artist);
+                        if let Some(file) = app.playing_file().map(str::to_string) {
+                            app.resume.record(&file, track.position_ms, track.duration_ms);
+                        }
 
+                        let id = format!("{}{}", track.name, track.artist);
                         if !track.album.is_empty() && !app.last_album.is_empty() {
                             app.gapless_mode = track.album == app.last_album;
                         } else {
@@ -116,11 +291,129 @@ where
                         }
                         app.last_album = track.album.clone();
 
+                        // Finish Album 💿: MPD is set up while the album's last track
+                        // plays (single to stop after it, random to shuffle past it),
+                        // so the next album never starts
+                        #[cfg(feature = "mpd")]
+                        if !args.controller {
+                            let moved_on = app.finish_album_checked.as_deref() != Some(id.as_str());
+                            match app.finish_album_armed {
+                                Some(FinishAlbumMode::Stop) if track.state == crate::player::PlayerState::Stopped => {
+                                    app.finish_album = FinishAlbumMode::Off;
+                                    app.finish_album_armed = None;
+                                    crate::app::send_mpd(app, args, |mpd| {
+                                        if let Err(e) = mpd.single(false) {
+                                            tracing::warn!("Failed to turn off single mode: {}", e);
+                                        }
+                                    });
+                                    app.show_toast(&t!("toast.album_finished_stop"));
+                                }
+                                Some(FinishAlbumMode::Shuffle) if moved_on => {
+                                    app.finish_album = FinishAlbumMode::Off;
+                                    app.finish_album_armed = None;
+                                    app.shuffle = true;
+                                    app.show_toast(&t!("toast.album_finished_shuffle"));
+                                }
+                                // Skipped off the last track: look again from here
+                                Some(FinishAlbumMode::Stop) if moved_on => {
+                                    app.finish_album_armed = None;
+                                    crate::app::send_mpd(app, args, |mpd| {
+                                        if let Err(e) = mpd.single(false) {
+                                            tracing::warn!("Failed to turn off single mode: {}", e);
+                                        }
+                                    });
+                                }
+                                _ => {}
+                            }
+                            if app.finish_album != FinishAlbumMode::Off && app.finish_album_armed.is_none() && moved_on {
+                                app.finish_album_checked = Some(id.clone());
+                                let mode = app.finish_album;
+                                let armed = crate::app::with_mpd(app, args, move |mpd| {
+                                    if !crate::app::library_helpers::album_ends_after_current(mpd)? {
+                                        return Ok(false);
+                                    }
+                                    match mode {
+                                        FinishAlbumMode::Stop => mpd.single(true)?,
+                                        _ => mpd.random(true)?,
+                                    }
+                                    Ok::<_, mpd::error::Error>(true)
+                                }).await;
+                                match armed {
+                                    Some(Ok(true)) => app.finish_album_armed = Some(mode),
+                                    Some(Ok(false)) => {}
+                                    Some(Err(e)) => tracing::warn!("Failed to apply finish-album action: {}", e),
+                                    None => tracing::warn!("Finish-album action skipped: MPD unavailable"),
+                                }
+                            }
+                        }
+
                         if id != last_track_id {
                             last_track_id = id.clone();
+                            app.on_track_changed();
+
+                            app.silence_trim.on_track_change();
+                            app.ab_loop.clear();
+
+                            // Chapters 📖, read off the UI thread
+                            app.chapters.clear();
+                            app.chapter_list = None;
+                            if let Some(file) = app.playing_file().filter(|_| !args.controller).map(str::to_string) {
+                                let path = std::path::Path::new(&app.music_directory).join(&file);
+                                let tx = tx.clone();
+                                tokio::task::spawn_blocking(move || {
+                                    let chapters = crate::app::chapters::load(&path);
+                                    if !chapters.is_empty() {
+                                        let _ = tx.blocking_send(AppEvent::Chapters(file, chapters));
+                                    }
+                                });
+                            }
+
+                            // Long tracks pick up where they were left off ⏯️
+                            let left_off = app.playing_file().map(str::to_string).and_then(|file| {
+                                app.resume.on_track_change(&file, track.position_ms, track.duration_ms)
+                            });
+                            if let Some(ms) = left_off {
+                                let time = format!("{:02}:{:02}", ms / 60000, (ms % 60000) / 1000);
+                                if app.resume.auto {
+                                    audio_pipeline.flush();
+                                    crate::app::inputs::player::seek_to(app, player, &tx, ms as f64 / 1000.0);
+                                    app.show_toast(&t!("toast.resumed", time = time));
+                                } else {
+                                    app.resume.offer = Some(ms);
+                                    let key = app.keys.display(&app.keys.resume);
+                                    app.show_toast(&t!("toast.resume_offer", time = time, key = key));
+                                }
+                            }
+
+                            // Volume set by hand for this album or track 🔊
+                            let key = app.volume_memory.key(app.playing_file(), &track.artist, &track.album);
+                            let remembered = app.volume_memory.on_track_change(key.clone());
+                            // With ReplayGain on, MPD evens out tagged files itself: look first
+                            #[cfg(feature = "mpd")]
+                            let checking = match app.playing_file().filter(|_| key.is_some() && app.replay_gain_mode != 0 && !args.controller) {
+                                Some(file) => {
+                                    let file = file.to_string();
+                                    let path = std::path::Path::new(&app.music_directory).join(&file);
+                                    let tx = tx.clone();
+                                    tokio::task::spawn_blocking(move || {
+                                        let tagged = crate::app::tags::has_replay_gain(&path);
+                                        let _ = tx.blocking_send(AppEvent::RememberedVolume(file, remembered, tagged));
+                                    });
+                                    true
+                                }
+                                None => false,
+                            };
+                            #[cfg(not(feature = "mpd"))]
+                            let checking = false;
+                            if let Some(volume) = remembered.filter(|_| !checking) {
+                                crate::app::inputs::player::restore_volume(app, player, audio_pipeline, &tx, volume);
+                            }
                             app.lyrics = LyricsState::Loading;
 
                             app.lyrics_offset = None;
+                            app.lyrics_scroll = 0;
+                            app.lyrics_editor = None; // Edits belong to the old track
+                            app.lyrics_search = None;
                             app.last_scroll_time = None;
                             app.seek_accumulator = 0.0;
                             app.seek_initial_pos = None;
@@ -128,71 +421,88 @@ where
                             app.needs_redraw = true;
 
                             if let Some(cached) = app.lyrics_cache.get(&id) {
-                                app.lyrics = LyricsState::Loaded(cached.clone(), "Memory Cache".to_string());
+                                app.lyrics = match cached {
+                                    LyricsState::Plain(lines, _) => LyricsState::Plain(lines.clone(), "Memory Cache".to_string()),
+                                    LyricsState::Loaded(lines, _) => LyricsState::Loaded(lines.clone(), "Memory Cache".to_string()),
+                                    other => other.clone(),
+                                };
                             } else {
-                                let tx_lyrics = tx.clone();
-                                let (artist, name, dur) = (track.artist.clone(), track.name.clone(), track.duration_ms);
-                                let fetch_id = id.clone();
-                                let file_path = track.file_path.clone();
-
-                                let client = client.clone();
-                                tokio::spawn(async move {
-                                    let fetcher = LyricsFetcher::new(client);
-                                    use crate::app::lyrics::LyricsFetchResult;
-                                    match fetcher.fetch(&artist, &name, dur, file_path.as_ref()).await {
-                                        Ok(LyricsFetchResult::Found(lyrics, source)) => {
-                                            if let Err(e) = tx_lyrics.send(AppEvent::LyricsUpdate(fetch_id, LyricsState::Loaded(lyrics, source))).await { tracing::debug!("Channel closed: {}", e); }
-                                        },
-                                        Ok(LyricsFetchResult::Instrumental) => {
-                                             if let Err(e) = tx_lyrics.send(AppEvent::LyricsUpdate(fetch_id, LyricsState::Instrumental)).await { tracing::debug!("Channel closed: {}", e); }
-                                        },
-                                        Ok(LyricsFetchResult::None) => {
-                                             if let Err(e) = tx_lyrics.send(AppEvent::LyricsUpdate(fetch_id, LyricsState::NotFound)).await { tracing::debug!("Channel closed: {}", e); }
-                                        }
-                                        Err(e) => {
-                                            if let Err(err) = tx_lyrics.send(AppEvent::LyricsUpdate(fetch_id, LyricsState::Failed(e.to_string()))).await { tracing::debug!("Channel closed: {}", err); }
-                                        }
-                                    }
-                                });
+                                spawn_lyrics_fetch(app, &track, &tx, &client);
                             }
 
                             app.needs_redraw = true;
 
-                            if track.source == "Music" && track.artwork_url.is_none() {
+                            // Next song of the same album: keep the cover (or its fetch)
+                            let art_key = crate::app::requests::art_key(&track);
+                            let new_art = !app.art_request.wants(&art_key);
+
+                            if track.source == "Music" && track.artwork_url.is_none() && new_art {
                                 app.artwork = ArtworkState::Loading;
                                 let tx_art = tx.clone();
                                 let (artist, album) = (track.artist.clone(), track.album.clone());
                                 let client = client.clone();
-                                let fetch_id = id.clone();
-                                tokio::spawn(async move {
+                                let fetch_id = art_key.clone();
+                                app.art_request.start(art_key.clone(), async move {
+                                    if let Some(img) = ArtworkRenderer::cached(&artist, &album).await {
+                                        if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Loaded(Arc::new(img)))).await { tracing::debug!("Channel closed: {}", e); }
+                                        return;
+                                    }
                                     let renderer = ArtworkRenderer::new(client);
-                                    match renderer.fetch_itunes_artwork(&artist, &album).await {
-                                        Ok(url) => {
-                                             match renderer.fetch_image(&url).await {
-                                                 Ok(img) => { if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Loaded(img))).await { tracing::debug!("Channel closed: {}", e); } },
-                                                 Err(_) => { if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id.clone(), ArtworkState::Failed)).await { tracing::debug!("Channel closed: {}", e); } }
-                                             }
+                                    let itunes = match renderer.fetch_itunes_artwork(&artist, &album).await {
+                                        Ok(url) => renderer.fetch_image(&url).await.inspect_err(|e| tracing::debug!("iTunes artwork download failed: {}", e)).ok(),
+                                        Err(e) => { tracing::debug!("No iTunes artwork for {} - {}: {}", artist, album, e); None },
+                                    };
+                                    let img = match itunes {
+                                        Some(img) => Some(img),
+                                        None => renderer.fetch_cover_art_archive(&artist, &album).await.inspect_err(|e| tracing::debug!("No Cover Art Archive artwork for {} - {}: {}", artist, album, e)).ok(),
+                                    };
+                                    match img {
+                                        Some(img) => {
+                                            ArtworkRenderer::remember(&artist, &album, &img);
+                                            if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Loaded(Arc::new(img)))).await { tracing::debug!("Channel closed: {}", e); }
                                         },
-                                        Err(_) => { if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Failed)).await { tracing::debug!("Channel closed: {}", e); } }
+                                        None => { if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Failed)).await { tracing::debug!("Channel closed: {}", e); } }
                                     }
                                 });
                             }
 
                             #[cfg(feature = "mpd")]
-                            if track.source == "MPD" {
+                            if track.source == "MPD" && new_art {
                                 if let Some(file_path) = &track.file_path {
                                     app.artwork = ArtworkState::Loading;
                                     let tx_art = tx.clone();
                                     let fp = file_path.clone();
-                                    let fetch_id = id.clone();
-                                    tokio::spawn(async move {
-                                        let result = tokio::task::spawn_blocking(move || {
-                                            ArtworkRenderer::extract_embedded_art(&fp)
+                                    let (artist, album) = (track.artist.clone(), track.album.clone());
+                                    let art_names = app.artwork_filenames.clone();
+                                    let client = client.clone();
+                                    let fetch_id = art_key.clone();
+                                    app.art_request.start(art_key, async move {
+                                        let (mb_artist, mb_album) = (artist.clone(), album.clone());
+                                        let result = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+                                            if let Some(img) = ArtworkRenderer::cache_load(&artist, &album) {
+                                                return Ok(img);
+                                            }
+                                            let img = ArtworkRenderer::load_folder_art(std::path::Path::new(&fp), &art_names)
+                                                .or_else(|_| ArtworkRenderer::extract_embedded_art(&fp))?;
+                                            ArtworkRenderer::cache_store(&artist, &album, &img);
+                                            Ok(img)
                                         }).await;
 
-                                        match result {
-                                            Ok(Ok(img)) => { if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Loaded(img))).await { tracing::debug!("Channel closed: {}", e); } },
-                                            _ => { if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Failed)).await { tracing::debug!("Channel closed: {}", e); } }
+                                        // Nothing local: try the Cover Art Archive
+                                        let img = match result {
+                                            Ok(Ok(img)) => Some(img),
+                                            _ => {
+                                                let renderer = ArtworkRenderer::new(client);
+                                                let img = renderer.fetch_cover_art_archive(&mb_artist, &mb_album).await.inspect_err(|e| tracing::debug!("No Cover Art Archive artwork for {} - {}: {}", mb_artist, mb_album, e)).ok();
+                                                if let Some(img) = &img {
+                                                    ArtworkRenderer::remember(&mb_artist, &mb_album, img);
+                                                }
+                                                img
+                                            }
+                                        };
+                                        match img {
+                                            Some(img) => { if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Loaded(Arc::new(img)))).await { tracing::debug!("Channel closed: {}", e); } },
+                                            None => { if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Failed)).await { tracing::debug!("Channel closed: {}", e); } }
                                         }
                                     });
                                 }
@@ -200,36 +510,47 @@ where
                         }
 
                         if let Some(url) = track.artwork_url.clone() {
-                            if Some(url.clone()) != last_artwork_url {
-                                last_artwork_url = Some(url.clone());
+                            if !app.art_request.wants(&url) {
                                 app.artwork = ArtworkState::Loading;
                                 let tx_art = tx.clone();
                                 let client = client.clone();
-                                let fetch_id = id.clone();
-                                tokio::spawn(async move {
+                                let fetch_id = url.clone();
+                                let (artist, album) = (track.artist.clone(), track.album.clone());
+                                app.art_request.start(url.clone(), async move {
+                                    if let Some(img) = ArtworkRenderer::cached(&artist, &album).await {
+                                        if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Loaded(Arc::new(img)))).await { tracing::debug!("Channel closed: {}", e); }
+                                        return;
+                                    }
                                     let renderer = ArtworkRenderer::new(client);
                                     match renderer.fetch_image(&url).await {
-                                         Ok(img) => { if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Loaded(img))).await { tracing::debug!("Channel closed: {}", e); } },
-                                         Err(_) => { if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Failed)).await { tracing::debug!("Channel closed: {}", e); } }
+                                         Ok(img) => {
+                                             ArtworkRenderer::remember(&artist, &album, &img);
+                                             if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Loaded(Arc::new(img)))).await { tracing::debug!("Channel closed: {}", e); }
+                                         },
+                                         Err(err) => {
+                                             tracing::debug!("Artwork download failed: {}", err);
+                                             if let Err(e) = tx_art.send(AppEvent::ArtworkUpdate(fetch_id, ArtworkState::Failed)).await { tracing::debug!("Channel closed: {}", e); }
+                                         }
                                     }
                                 });
                             }
                         }
                     } else {
                         last_track_id.clear();
-                        last_artwork_url = None;
+                        app.art_request.clear();
+                        app.lyrics_request.clear();
                         app.artwork = ArtworkState::Idle;
                     }
                     app.needs_redraw = true;
                 },
                 AppEvent::LyricsUpdate(id, state) => {
-                    if let LyricsState::Loaded(ref l, _) = state {
+                    if matches!(state, LyricsState::Loaded(..) | LyricsState::Plain(..)) {
                          if app.lyrics_cache.len() > 50 {
                              if let Some(oldest_key) = app.lyrics_cache.keys().next().cloned() {
                                  app.lyrics_cache.remove(&oldest_key);
                              }
                          }
-                         app.lyrics_cache.insert(id.clone(), l.clone());
+                         app.lyrics_cache.insert(id.clone(), state.clone());
                     }
 
                     if id == last_track_id {
@@ -237,27 +558,208 @@ where
                          app.needs_redraw = true;
                     }
                 },
-                AppEvent::ArtworkUpdate(id, data) => {
-                    if id == last_track_id {
+                AppEvent::TagCandidates(files, res) => {
+                    if let Some(lookup) = app
+                        .tag_edit
+                        .as_mut()
+                        .filter(|edit| edit.files == files)
+                        .and_then(|edit| edit.lookup.as_mut())
+                    {
+                        lookup.set_results(res);
+                        app.needs_redraw = true;
+                    }
+                },
+                AppEvent::LibraryCacheUpdate(cache) => {
+                    // Refresh the album grid in place; the rest reads it on demand
+                    if app.library_mode == crate::app::LibraryMode::Albums
+                        && app.album_open.is_none()
+                    {
+                        app.albums = cache.albums();
+                    }
+                    if let Some(track) = &app.track {
+                        crate::app::related::refresh(&mut app.related, &track.artist, &cache);
+                    }
+                    app.library_cache = Some(cache);
+                    app.needs_redraw = true;
+                },
+                AppEvent::LyricsCandidates(id, res) => {
+                    if let Some(lookup) = app.lyrics_lookup.as_mut().filter(|l| l.track_id == id) {
+                        match res {
+                            Ok(results) => lookup.set_results(results),
+                            Err(e) => lookup.status = crate::app::LookupStatus::Failed(e),
+                        }
+                        app.needs_redraw = true;
+                    }
+                },
+                AppEvent::ArtworkUpdate(key, data) => {
+                    if app.art_request.wants(&key) {
+                        if app.dynamic_theme {
+                            let palette = match &data {
+                                ArtworkState::Loaded(img) => Some(crate::ui::theme::dominant_palette(img, 5)),
+                                ArtworkState::Failed | ArtworkState::Idle => None,
+                                ArtworkState::Loading => app.art_palette.clone(),
+                            };
+                            app.apply_art_palette(palette);
+                        }
                         app.artwork = data;
+                        app.art_generation += 1;
+                        app.art_cells = None;
                         app.image_protocol = None;
+                        app.art_backdrop = None;
+                        app.needs_redraw = true;
+                    }
+                },
+                AppEvent::ArtCellsReady(cells) => {
+                    if app.art_cells_pending == Some(cells.key) {
+                        app.art_cells_pending = None;
+                    }
+                    // Anything for the current cover beats an older size
+                    if cells.key.generation == app.art_generation {
+                        app.art_cells = Some(cells);
                         app.needs_redraw = true;
                     }
                 },
-                AppEvent::ThemeUpdate(new_theme) => {
-                    app.theme = new_theme;
+                AppEvent::LibraryArtUpdate(path, data) => {
+                    // Drop results for entries the cursor has already left
+                    if let Some((key, state)) = &mut app.library_art {
+                        if *key == path {
+                            *state = data;
+                            app.needs_redraw = true;
+                        }
+                    }
+                },
+                AppEvent::AlbumArtUpdate(file, data) => {
+                    if let Some(state) = app.album_art.get_mut(&file) {
+                        *state = data;
+                        app.needs_redraw = true;
+                    }
+                },
+                AppEvent::ThemeFilesChanged => {
+                    // Don't disturb a preview in progress
+                    if app.theme_picker.is_none() {
+                        app.apply_theme(app.theme_name.clone());
+                        app.needs_redraw = true;
+                    }
+                },
+                AppEvent::ThemeSelect(name) => {
+                    // An explicit config edit wins over the last picker choice
+                    app.theme_picker = None;
+                    app.picked_theme = None;
+                    app.config_theme = name.clone();
+                    app.apply_theme(name);
+                    app.save_state();
                     app.needs_redraw = true;
                 },
                 AppEvent::KeyConfigUpdate(new_keys) => {
                     app.keys = *new_keys;
-                    app.show_toast("🔧 Config Reloaded");
+                    app.show_toast(&t!("toast.config_reloaded"));
                 },
                 AppEvent::QueueUpdate(queue_data) => {
-                    app.queue = queue_data.into_iter().map(|(title, artist, duration_ms, is_current, file_path)| {
-                        crate::app::QueueItem { title, artist, duration_ms, is_current, file_path }
-                    }).collect();
+                    app.queue = queue_data.into_iter().map(crate::app::QueueItem::from).collect();
+                    app.needs_redraw = true;
+                },
+
+                AppEvent::QueueChanged(delta) => {
+                    crate::app::apply_queue_delta(&mut app.queue, delta);
+                    app.needs_redraw = true;
+                },
+                AppEvent::AboutUpdate(key, state) => {
+                    if app.about_request.wants(&key) {
+                        app.about = state;
+                        app.needs_redraw = true;
+                    }
+                },
+                AppEvent::RelatedUpdate(key, state) => {
+                    if app.related_request.wants(&key) {
+                        app.related = state;
+                        app.needs_redraw = true;
+                    }
+                },
+                AppEvent::StreamResults(query, res) => {
+                    if let Some(search) = app.stream_search.as_mut().filter(|s| s.searched == query) {
+                        match res {
+                            Ok(results) => search.set_results(results),
+                            Err(e) => search.status = crate::app::LookupStatus::Failed(e),
+                        }
+                        app.needs_redraw = true;
+                    }
+                },
+                AppEvent::StreamResolved(result) => match result {
+                    #[cfg(feature = "mpd")]
+                    Ok(stream) => crate::app::ytdlp::play(app, args, stream).await,
+                    #[cfg(not(feature = "mpd"))]
+                    Ok(_) => {}
+                    Err(e) => app.show_error(&t!("stream.failed", error = e)),
+                },
+                AppEvent::BandcampCollection(state) => {
+                    if app.bandcamp_request.wants(crate::app::bandcamp::COLLECTION_KEY) {
+                        app.bandcamp = state;
+                        app.needs_redraw = true;
+                    }
+                },
+                AppEvent::BandcampStreams(title, play, result) => match result {
+                    #[cfg(feature = "mpd")]
+                    Ok(streams) => crate::app::ytdlp::queue(app, args, &title, streams, play.then_some(0)).await,
+                    #[cfg(not(feature = "mpd"))]
+                    Ok(_) => {
+                        let _ = (title, play);
+                    }
+                    Err(e) => app.show_error(&t!("bandcamp.stream_failed", error = e)),
+                },
+                AppEvent::Transfer(id, update) => {
+                    use crate::app::downloads::{TransferState, TransferUpdate};
+                    // New files for MPD's database
+                    let landed = matches!(update, TransferUpdate::Song | TransferUpdate::Done(_));
+                    match app.downloads.update(id, update).map(|t| (t.title.clone(), t.state.clone(), t.path.clone())) {
+                        Some((title, TransferState::Done, path)) => {
+                            app.show_toast(&t!("downloads.done", title = title, path = path.unwrap_or_default()));
+                        }
+                        Some((title, TransferState::Failed(e), _)) => {
+                            app.show_error(&t!("downloads.failed", title = title, error = e));
+                        }
+                        _ => {}
+                    }
+                    #[cfg(feature = "mpd")]
+                    if landed && app.is_mpd {
+                        crate::app::downloads::update_mpd(app, args);
+                    }
+                    #[cfg(not(feature = "mpd"))]
+                    let _ = landed;
                     app.needs_redraw = true;
                 },
+                AppEvent::CdRead(result) => {
+                    use crate::app::cd::CdState;
+                    // Closed while reading: nothing to show it in
+                    if let Some(view) = app.cd.as_mut() {
+                        view.state = match result {
+                            Ok(disc) => CdState::Ready(disc),
+                            Err(e) => CdState::Failed(e),
+                        };
+                        app.needs_redraw = true;
+                    }
+                },
+                #[cfg(feature = "mpd")]
+                AppEvent::ConsoleReply(cmd, reply) => {
+                    crate::app::inputs::console::show_reply(app, &cmd, reply);
+                    app.needs_redraw = true;
+                },
+                #[cfg(feature = "mpd")]
+                AppEvent::RememberedVolume(file, volume, tagged) => {
+                    if app.playing_file() == Some(file.as_str()) {
+                        if tagged {
+                            app.volume_memory.skip();
+                        } else if let Some(volume) = volume {
+                            crate::app::inputs::player::restore_volume(app, player, audio_pipeline, &tx, volume);
+                            app.needs_redraw = true;
+                        }
+                    }
+                },
+                AppEvent::Chapters(file, chapters) => {
+                    if app.playing_file() == Some(file.as_str()) {
+                        app.chapters = chapters;
+                        app.needs_redraw = true;
+                    }
+                },
 
                 AppEvent::StatusUpdate(shuffle, repeat) => {
                     app.shuffle = shuffle;
@@ -265,32 +767,179 @@ where
                     app.needs_redraw = true;
                 },
 
-                AppEvent::ToastUpdate(msg) => {
-                    app.show_toast(&msg);
+                AppEvent::ConnectionUpdate(state) => {
+                    if state == crate::app::connection::ConnectionState::Connected
+                        && matches!(app.connection, crate::app::connection::ConnectionState::Down { .. })
+                    {
+                        app.show_toast(&crate::t!("connection.back"));
+                    }
+                    app.connection = state;
+                    app.needs_redraw = true;
+                },
+
+                AppEvent::ToastUpdate(level, msg) => {
+                    app.notify(level, &msg);
+                    app.needs_redraw = true;
+                },
+
+                AppEvent::TagProgress(done) => {
+                    if let Some(edit) = app.tag_edit.as_mut() {
+                        edit.progress = Some(done);
+                    }
+                    if let Some(tool) = app.tag_tool.as_mut() {
+                        tool.progress = Some(done);
+                    }
+                    app.needs_redraw = true;
+                },
+
+                #[cfg(feature = "mpd")]
+                AppEvent::TagsWritten(report) => {
+                    crate::app::tags::finished(app, report);
+                    app.needs_redraw = true;
+                },
+
+                AppEvent::Error(error, retry) => {
+                    app.report(&error, retry);
+                    app.needs_redraw = true;
+                },
+
+                AppEvent::Remote(command, reply) => {
+                    let result = crate::app::inputs::player::handle_remote(command, app, player, audio_pipeline, args, &tx).await;
+                    if reply.send(result).is_err() {
+                        tracing::debug!("Remote client hung up before the reply");
+                    }
+                    app.needs_redraw = true;
+                },
+
+                AppEvent::Subscribe(reply) => {
+                    let receiver = app
+                        .sync
+                        .as_mut()
+                        .map(|publisher| publisher.subscribe())
+                        .ok_or_else(|| crate::t!("remote.not_master"));
+                    if reply.send(receiver).is_err() {
+                        tracing::debug!("Sync client hung up before the reply");
+                    }
+                },
+
+                AppEvent::Sync(update) => {
+                    crate::app::sync::apply(app, update);
                     app.needs_redraw = true;
                 },
 
                 AppEvent::Tick => {
+                    let had_toasts = !app.toasts.is_empty();
                     app.on_tick();
-                    app.tick_count = app.tick_count.wrapping_add(1);
+                    app.tick_theme_fade(std::time::Instant::now());
 
-                    let mut is_playing = false;
-                    if app.track.is_some() {
-                        is_playing = true;
+                    // Info view 📰: look the song up once it's showing
+                    if app.is_visible(crate::app::ViewMode::Info) {
+                        crate::app::about::ensure(app, &tx, &client);
+                    }
+                    if app.is_visible(crate::app::ViewMode::Related) {
+                        crate::app::related::ensure(app, &tx, &client);
+                    }
+                    if app.is_visible(crate::app::ViewMode::Library)
+                        && app.library_mode == crate::app::LibraryMode::Bandcamp
+                    {
+                        crate::app::bandcamp::ensure(app, &tx, &client);
                     }
 
-                    let is_animating_lyrics = app.last_scroll_time.is_none() && (app.lyrics_offset.is_some() || app.lyrics_selected.is_some());
-                    let has_active_toast = app.toast.is_some();
-                    let needs_high_fps = app.view_mode == crate::app::ViewMode::Visualizer || is_animating_lyrics || has_active_toast;
+                    if let Some(e) = audio_pipeline.take_error() {
+                        app.report(&VyomError::AudioPipeline(e), Some(Retry::Pipeline));
+                        app.needs_redraw = true;
+                    }
+                    // Vyom's slider moved in pavucontrol & co.
+                    if let Some(volume) = app.output.take_volume_report() {
+                        crate::app::inputs::player::set_volume(app, player, audio_pipeline, &tx, volume);
+                        app.needs_redraw = true;
+                    }
+
+                    let is_playing = app.is_playing();
+                    let is_animating_lyrics = app.lyrics_animating();
+                    // Moving things redraw every tick; the rest only when
+                    // something changed (input and events set needs_redraw)
+                    let moving = app.frame_rate(std::time::Instant::now()) != crate::app::FrameRate::Idle;
+                    // Idle: the clock, the bar's marquee, and visualizer bars settling after a pause
+                    let clock = is_playing && app.last_frame.elapsed() >= crate::app::frames::CLOCK_REDRAW;
+                    let bar_step = app.bar_active && is_playing;
+                    let settling = app.is_visible(crate::app::ViewMode::Visualizer);
+                    let toast_gone = had_toasts && app.toasts.is_empty();
+
+                    // New log lines while the viewer is open
+                    if let Some(view) = app.log_view.as_mut() {
+                        let logged = crate::logging::logged();
+                        if view.seen != logged {
+                            view.seen = logged;
+                            app.needs_redraw = true;
+                        }
+                    }
 
-                    if needs_high_fps || (is_playing && app.tick_count.is_multiple_of(30)) {
+                    if moving || clock || bar_step || settling || toast_gone {
                         app.needs_redraw = true;
                     }
 
+                    // Silence trimming ✂️ (skips happen on the server; the stream is real time)
+                    #[cfg(feature = "mpd")]
+                    if !args.controller {
+                        if let Some(track) = &app.track {
+                            let action = app.silence_trim.check(
+                                app.get_current_position_ms(),
+                                track.duration_ms,
+                                track.state == crate::player::PlayerState::Playing,
+                                app.silence.silent_for(),
+                                std::time::Instant::now(),
+                            );
+                            if let Some(action) = action {
+                                audio_pipeline.flush();
+                                let p = player.clone();
+                                tokio::task::spawn_blocking(move || {
+                                    let result = match action {
+                                        TrimAction::SkipAhead(secs) => p.seek(secs),
+                                        TrimAction::Next => p.next(),
+                                    };
+                                    if let Err(e) = result {
+                                        tracing::warn!("Silence trim failed: {}", e);
+                                    }
+                                });
+                                if action == TrimAction::Next {
+                                    app.show_toast(&t!("toast.silence_skipped"));
+                                }
+                            }
+                        }
+                    }
+
+                    let now = std::time::Instant::now();
+                    if app.resume.should_save(now) | app.volume_memory.should_save(now) {
+                        app.save_state();
+                    }
+
+                    // A-B loop 🔁: back to A once playback reaches B
+                    #[cfg(feature = "mpd")]
+                    if !args.controller {
+                        let playing = app.is_playing();
+                        let now = std::time::Instant::now();
+                        if let Some(a) = app.ab_loop.check(app.get_current_position_ms(), playing, now) {
+                            audio_pipeline.flush();
+                            let p = player.clone();
+                            tokio::task::spawn_blocking(move || {
+                                if let Err(e) = p.seek(a as f64 / 1000.0) {
+                                    tracing::warn!("A-B loop seek failed: {}", e);
+                                }
+                            });
+                            // Don't wait for the next poll to show it
+                            if let Some(track) = app.track.as_mut() {
+                                track.position_ms = a;
+                            }
+                            app.last_track_update = Some(now);
+                            app.needs_redraw = true;
+                        }
+                    }
+
                     if is_animating_lyrics {
                         if let (LyricsState::Loaded(lyrics, _), Some(_track)) = (&app.lyrics, &app.track) {
                             let target_idx = lyrics.iter()
-                               .position(|l| l.timestamp_ms > app.get_current_position_ms())
+                               .position(|l| l.timestamp_ms > app.lyrics_position_ms())
                                .map(|i| i.saturating_sub(1))
                                .unwrap_or(lyrics.len().saturating_sub(1));
 
@@ -344,5 +993,44 @@ where
         }
     }
 
+    if let Some(play) = app.listening.finish() {
+        crate::app::history::record(&play);
+    }
     Ok(())
 }
+
+/// Look up lyrics for `track` in the background. The result arrives as a
+/// `LyricsUpdate`; a failed lookup also raises an error toast with a retry.
+pub(crate) fn spawn_lyrics_fetch(
+    app: &mut App,
+    track: &TrackInfo,
+    tx: &mpsc::Sender<AppEvent>,
+    client: &reqwest::Client,
+) {
+    use crate::app::lyrics::LyricsFetchResult;
+
+    let tx = tx.clone();
+    let fetch_id = format!("{}{}", track.name, track.artist);
+    let (artist, name, dur) = (track.artist.clone(), track.name.clone(), track.duration_ms);
+    let file_path = track.file_path.clone();
+    let fetcher = LyricsFetcher::new(client.clone()).with_lyrics_dir(app.lyrics_dir.clone());
+    app.lyrics_request.start(fetch_id.clone(), async move {
+        let state = match fetcher.fetch(&artist, &name, dur, file_path.as_ref()).await {
+            Ok(LyricsFetchResult::Found(lyrics, source)) => LyricsState::Loaded(lyrics, source),
+            Ok(LyricsFetchResult::Plain(lines, source)) => LyricsState::Plain(lines, source),
+            Ok(LyricsFetchResult::Instrumental) => LyricsState::Instrumental,
+            Ok(LyricsFetchResult::None) => LyricsState::NotFound,
+            Err(e) => {
+                report(
+                    &tx,
+                    VyomError::LyricsFetch(e.to_string()),
+                    Some(Retry::Lyrics),
+                );
+                LyricsState::Failed(e.to_string())
+            }
+        };
+        if let Err(e) = tx.send(AppEvent::LyricsUpdate(fetch_id, state)).await {
+            tracing::debug!("Channel closed: {}", e);
+        }
+    });
+}
@@ -0,0 +1,289 @@
+//! Smarter shuffles 🔀
+//!
+//! MPD's random mode picks any song next. These reorder the queue itself
+//! instead, once, so the order is visible in the queue and survives Vyom
+//! quitting. The playing song moves to the top and everything after it is
+//! shuffled.
+
+use crate::app::ShuffleMode;
+use std::collections::HashMap;
+
+/// What a shuffle needs to know about a queued song
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Entry {
+    pub artist: String,
+    pub album: String,
+    pub disc: u32,
+    pub track: u32,
+    /// Higher goes earlier more often (weighted shuffle only)
+    pub weight: f64,
+}
+
+/// A new order for `entries`, as indices into it. `after_artist` is the
+/// artist playing right before the first one (the current song).
+pub fn order(
+    entries: &[Entry],
+    mode: ShuffleMode,
+    after_artist: Option<&str>,
+    rng: &mut fastrand::Rng,
+) -> Vec<usize> {
+    match mode {
+        ShuffleMode::Albums => by_album(entries, rng),
+        ShuffleMode::Weighted => weighted(entries, rng),
+        ShuffleMode::ArtistSpread => artist_spread(entries, after_artist, rng),
+    }
+}
+
+/// Albums in random order, each album's tracks in disc/track order
+fn by_album(entries: &[Entry], rng: &mut fastrand::Rng) -> Vec<usize> {
+    let mut albums: Vec<Vec<usize>> = Vec::new();
+    let mut index: HashMap<(&str, &str), usize> = HashMap::new();
+    for (i, e) in entries.iter().enumerate() {
+        let slot = *index
+            .entry((e.artist.as_str(), e.album.as_str()))
+            .or_insert_with(|| {
+                albums.push(Vec::new());
+                albums.len() - 1
+            });
+        albums[slot].push(i);
+    }
+    rng.shuffle(&mut albums);
+    albums
+        .into_iter()
+        .flat_map(|mut tracks| {
+            // Stable: untagged tracks keep their queue order
+            tracks.sort_by_key(|&i| (entries[i].disc, entries[i].track));
+            tracks
+        })
+        .collect()
+}
+
+/// Weighted random order (Efraimidis-Spirakis): a song with twice the
+/// weight tends to come twice as early
+fn weighted(entries: &[Entry], rng: &mut fastrand::Rng) -> Vec<usize> {
+    let mut keyed: Vec<(f64, usize)> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (rng.f64().powf(1.0 / e.weight.max(0.01)), i))
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+    keyed.into_iter().map(|(_, i)| i).collect()
+}
+
+/// Random order where the same artist never plays twice in a row, unless
+/// there is nothing else left
+fn artist_spread(
+    entries: &[Entry],
+    after_artist: Option<&str>,
+    rng: &mut fastrand::Rng,
+) -> Vec<usize> {
+    let mut by_artist: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, e) in entries.iter().enumerate() {
+        by_artist.entry(e.artist.as_str()).or_default().push(i);
+    }
+    let mut artists: Vec<(&str, Vec<usize>)> = by_artist.into_iter().collect();
+    // HashMap order isn't random enough to break ties fairly
+    artists.sort_by_key(|(artist, _)| *artist);
+    rng.shuffle(&mut artists);
+    for (_, songs) in artists.iter_mut() {
+        rng.shuffle(songs);
+    }
+
+    // Always take from the artist with the most songs left (other than the
+    // last one): that is what keeps a big artist from bunching up at the end
+    let mut out = Vec::with_capacity(entries.len());
+    let mut last = after_artist;
+    while out.len() < entries.len() {
+        let pick = artists
+            .iter()
+            .enumerate()
+            .filter(|(_, (artist, songs))| !songs.is_empty() && Some(*artist) != last)
+            .max_by_key(|(i, (_, songs))| (songs.len(), std::cmp::Reverse(*i)))
+            .map(|(i, _)| i)
+            .or_else(|| artists.iter().position(|(_, songs)| !songs.is_empty()));
+        let Some(pick) = pick else {
+            break;
+        };
+        let (artist, songs) = &mut artists[pick];
+        out.extend(songs.pop());
+        last = Some(*artist);
+    }
+    out
+}
+
+/// Rewrite MPD's queue in `mode` order. Turns MPD's random mode off, or it
+/// would ignore the new order. Returns how many songs were shuffled.
+#[cfg(feature = "mpd")]
+pub fn reorder(mpd: &mut mpd::Client, mode: ShuffleMode) -> Result<usize, mpd::error::Error> {
+    let songs = mpd.queue()?;
+    let status = mpd.status()?;
+    let current = status.song.map(|s| s.pos as usize);
+
+    let rest: Vec<usize> = (0..songs.len()).filter(|&i| Some(i) != current).collect();
+    let entries: Vec<Entry> = rest
+        .iter()
+        .map(|&i| {
+            let mut entry = entry(&songs[i]);
+            if mode == ShuffleMode::Weighted {
+                entry.weight = weight(mpd, &songs[i].file);
+            }
+            entry
+        })
+        .collect();
+    let after = current.map(|i| entry(&songs[i]).artist);
+    let mut rng = fastrand::Rng::new();
+    let mut target: Vec<usize> = current.into_iter().collect();
+    target.extend(
+        order(&entries, mode, after.as_deref(), &mut rng)
+            .into_iter()
+            .map(|i| rest[i]),
+    );
+
+    // Move songs into place front to back; `live` mirrors MPD's queue
+    let mut live: Vec<usize> = (0..songs.len()).collect();
+    for (pos, want) in target.iter().enumerate() {
+        let Some(at) = live.iter().position(|i| i == want) else {
+            continue;
+        };
+        if at != pos {
+            mpd.shift(at as u32, pos)?;
+            let moved = live.remove(at);
+            live.insert(pos, moved);
+        }
+    }
+    if status.random {
+        mpd.random(false)?;
+    }
+    Ok(rest.len())
+}
+
+#[cfg(feature = "mpd")]
+fn entry(song: &mpd::Song) -> Entry {
+    let tag = |key: &str| {
+        song.tags
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    };
+    // "3/12" and "03" both count as 3
+    let number = |key: &str| {
+        tag(key)
+            .and_then(|v| v.split('/').next())
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0)
+    };
+    Entry {
+        artist: tag("AlbumArtist")
+            .or(song.artist.as_deref())
+            .unwrap_or_default()
+            .to_string(),
+        album: tag("Album").unwrap_or_default().to_string(),
+        disc: number("Disc"),
+        track: number("Track"),
+        weight: 1.0,
+    }
+}
+
+/// From the `rating` (0-10) and `playCount` stickers other clients set;
+/// songs without them weigh 1
+#[cfg(feature = "mpd")]
+fn weight(mpd: &mut mpd::Client, file: &str) -> f64 {
+    let mut sticker = |name: &str| {
+        mpd.sticker("song", file, name)
+            .ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .unwrap_or(0.0)
+            .max(0.0)
+    };
+    let rating = sticker("rating");
+    let plays = sticker("playCount");
+    (1.0 + rating) * (1.0 + plays.ln_1p())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(artist: &str, album: &str, track: u32) -> Entry {
+        Entry {
+            artist: artist.into(),
+            album: album.into(),
+            track,
+            weight: 1.0,
+            ..Default::default()
+        }
+    }
+
+    fn is_permutation(order: &[usize], len: usize) -> bool {
+        let mut sorted = order.to_vec();
+        sorted.sort();
+        sorted == (0..len).collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn test_album_shuffle_keeps_track_order() {
+        let entries = vec![
+            song("A", "One", 2),
+            song("B", "Two", 1),
+            song("A", "One", 1),
+            song("B", "Two", 2),
+            song("C", "Three", 1),
+        ];
+        let mut rng = fastrand::Rng::with_seed(7);
+        for _ in 0..20 {
+            let order = order(&entries, ShuffleMode::Albums, None, &mut rng);
+            assert!(is_permutation(&order, entries.len()));
+            let a: Vec<usize> = order
+                .iter()
+                .copied()
+                .filter(|&i| entries[i].album == "One")
+                .collect();
+            assert_eq!(a, vec![2, 0]);
+            // Each album stays together
+            let first = order.iter().position(|&i| i == 2).unwrap();
+            assert_eq!(order[first + 1], 0);
+        }
+    }
+
+    #[test]
+    fn test_artist_spread_avoids_repeats() {
+        // 3 x A, 2 x B, 1 x C: A-B-A-B-A with C somewhere works out
+        let mut entries: Vec<Entry> = (0..3).map(|i| song("A", "x", i)).collect();
+        entries.extend((0..2).map(|i| song("B", "y", i)));
+        entries.push(song("C", "z", 0));
+        let mut rng = fastrand::Rng::with_seed(1);
+        for _ in 0..50 {
+            let order = order(&entries, ShuffleMode::ArtistSpread, Some("B"), &mut rng);
+            assert!(is_permutation(&order, entries.len()));
+            assert_ne!(entries[order[0]].artist, "B");
+            for pair in order.windows(2) {
+                assert_ne!(entries[pair[0]].artist, entries[pair[1]].artist);
+            }
+        }
+
+        // Only one artist: nothing to spread, still every song once
+        let solo: Vec<Entry> = (0..4).map(|i| song("A", "x", i)).collect();
+        let order = order(&solo, ShuffleMode::ArtistSpread, Some("A"), &mut rng);
+        assert!(is_permutation(&order, 4));
+    }
+
+    #[test]
+    fn test_weighted_shuffle_favors_heavy_songs() {
+        let mut entries: Vec<Entry> = (0..10).map(|i| song("A", "x", i)).collect();
+        entries[3].weight = 50.0;
+        let mut rng = fastrand::Rng::with_seed(3);
+        let mut first_half = 0;
+        for _ in 0..200 {
+            let order = order(&entries, ShuffleMode::Weighted, None, &mut rng);
+            assert!(is_permutation(&order, entries.len()));
+            if order.iter().position(|&i| i == 3).unwrap() < 5 {
+                first_half += 1;
+            }
+        }
+        assert!(
+            first_half > 180,
+            "heavy song in the first half {} times",
+            first_half
+        );
+    }
+}
@@ -0,0 +1,449 @@
+//! Smart playlists ✨
+//!
+//! `[[smart_playlists]]` in config.toml pairs a name with a rule such as
+//! `genre is Jazz AND year >= 1990 AND rating >= 4`. Loading one from the
+//! Playlists tab rebuilds the MPD playlist of that name from the rule first,
+//! so it always reflects the library (and ratings) as they are now.
+//!
+//! A rule is conditions joined by `AND` / `OR` (`AND` binds tighter; no
+//! parentheses). A condition is `<field> <op> <value>`:
+//! - fields: any MPD tag, `year` (from Date), `duration` (seconds), `file`,
+//!   and the `rating` / `playCount` stickers other clients set
+//! - ops: `is`, `is not`, `contains`, `=`, `!=`, `<`, `<=`, `>`, `>=`
+//! - values: the rest of the condition; quote it when it contains `AND`,
+//!   `OR` or one of `<>=!`
+//!
+//! Numbers compare as numbers, everything else case-insensitively.
+
+use crate::t;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SmartPlaylist {
+    pub name: String,
+    pub rule: String,
+    /// Stop after this many songs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    Tag(String),
+    Year,
+    Duration,
+    Rating,
+    PlayCount,
+    File,
+}
+
+impl Field {
+    fn parse(word: &str) -> Self {
+        match word.to_lowercase().as_str() {
+            "year" => Field::Year,
+            "duration" | "length" => Field::Duration,
+            "rating" => Field::Rating,
+            "playcount" | "plays" => Field::PlayCount,
+            "file" | "path" => Field::File,
+            _ => Field::Tag(word.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Is,
+    IsNot,
+    Contains,
+    Less,
+    LessEq,
+    Greater,
+    GreaterEq,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub field: Field,
+    pub op: Op,
+    pub value: String,
+}
+
+impl Condition {
+    /// Against every value the song has for the field (tags can repeat):
+    /// one match is enough, and `is not` means none is equal
+    fn holds(&self, values: &[String]) -> bool {
+        match self.op {
+            Op::IsNot => !values
+                .iter()
+                .any(|v| self.compare(v) == Some(Ordering::Equal)),
+            Op::Contains => {
+                let needle = self.value.to_lowercase();
+                values.iter().any(|v| v.to_lowercase().contains(&needle))
+            }
+            op => values.iter().any(|v| {
+                let ord = self.compare(v);
+                match op {
+                    Op::Is => ord == Some(Ordering::Equal),
+                    Op::Less => ord == Some(Ordering::Less),
+                    Op::LessEq => matches!(ord, Some(Ordering::Less | Ordering::Equal)),
+                    Op::Greater => ord == Some(Ordering::Greater),
+                    _ => matches!(ord, Some(Ordering::Greater | Ordering::Equal)),
+                }
+            }),
+        }
+    }
+
+    fn compare(&self, actual: &str) -> Option<Ordering> {
+        match (actual.trim().parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b),
+            _ => Some(actual.to_lowercase().cmp(&self.value.to_lowercase())),
+        }
+    }
+}
+
+/// Conditions in OR-ed groups of AND-ed ones
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    any_of: Vec<Vec<Condition>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    text: String,
+    quoted: bool,
+}
+
+impl Token {
+    fn is_op(&self) -> bool {
+        !self.quoted && self.text.starts_with(OP_CHARS)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        !self.quoted && self.text.eq_ignore_ascii_case(keyword)
+    }
+}
+
+const OP_CHARS: [char; 4] = ['<', '>', '=', '!'];
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some(q) if q == c => break,
+                    Some(ch) => text.push(ch),
+                    None => return Err(t!("smart.unterminated")),
+                }
+            }
+            tokens.push(Token { text, quoted: true });
+        } else {
+            let op = OP_CHARS.contains(&c);
+            let mut text = String::new();
+            while let Some(&ch) = chars.peek() {
+                let boundary = ch.is_whitespace() || ch == '"' || ch == '\'';
+                if boundary || OP_CHARS.contains(&ch) != op {
+                    break;
+                }
+                text.push(ch);
+                chars.next();
+            }
+            tokens.push(Token {
+                text,
+                quoted: false,
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+impl Rule {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let tokens = tokenize(text)?;
+        let mut tokens = tokens.iter().peekable();
+        let mut any_of = vec![Vec::new()];
+
+        loop {
+            let field = match tokens.next() {
+                Some(token) if !token.is_op() => token.text.as_str(),
+                Some(token) => return Err(t!("smart.expected_field", word = token.text)),
+                None => return Err(t!("smart.empty")),
+            };
+
+            let op = match tokens.next() {
+                Some(token) if token.is_op() => match token.text.as_str() {
+                    "=" | "==" => Op::Is,
+                    "!=" => Op::IsNot,
+                    "<" => Op::Less,
+                    "<=" => Op::LessEq,
+                    ">" => Op::Greater,
+                    ">=" => Op::GreaterEq,
+                    _ => return Err(t!("smart.bad_operator", op = token.text)),
+                },
+                Some(token) if token.is_keyword("is") => {
+                    if tokens.next_if(|t| t.is_keyword("not")).is_some() {
+                        Op::IsNot
+                    } else {
+                        Op::Is
+                    }
+                }
+                Some(token) if token.is_keyword("contains") => Op::Contains,
+                _ => return Err(t!("smart.expected_operator", field = field)),
+            };
+
+            let mut value = Vec::new();
+            while let Some(token) = tokens.next_if(|t| !t.is_keyword("and") && !t.is_keyword("or"))
+            {
+                value.push(token.text.as_str());
+            }
+            if value.is_empty() {
+                return Err(t!("smart.expected_value", field = field));
+            }
+
+            if let Some(group) = any_of.last_mut() {
+                group.push(Condition {
+                    field: Field::parse(field),
+                    op,
+                    value: value.join(" "),
+                });
+            }
+            match tokens.next() {
+                Some(token) if token.is_keyword("or") => any_of.push(Vec::new()),
+                Some(_) => {}
+                None => break,
+            }
+        }
+        Ok(Rule { any_of })
+    }
+
+    /// Whether a song matches, given its values for a field. Each field is
+    /// looked up at most once, and only if a condition needs it.
+    pub fn matches(&self, mut lookup: impl FnMut(&Field) -> Vec<String>) -> bool {
+        let mut seen: Vec<(&Field, Vec<String>)> = Vec::new();
+        self.any_of.iter().any(|group| {
+            group.iter().all(|cond| {
+                let at = match seen.iter().position(|(f, _)| *f == &cond.field) {
+                    Some(at) => at,
+                    None => {
+                        seen.push((&cond.field, lookup(&cond.field)));
+                        seen.len() - 1
+                    }
+                };
+                cond.holds(&seen[at].1)
+            })
+        })
+    }
+
+    /// `is` / `contains` tag conditions every match meets, so MPD's
+    /// (case-insensitive, substring) search can narrow things down first
+    pub fn search_terms(&self) -> Vec<(&str, &str)> {
+        match self.any_of.as_slice() {
+            [only] => only
+                .iter()
+                .filter_map(|cond| match &cond.field {
+                    Field::Tag(tag) if matches!(cond.op, Op::Is | Op::Contains) => {
+                        Some((tag.as_str(), cond.value.as_str()))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Rebuild the MPD playlist `smart.name` from its rule. Returns how many
+/// songs matched.
+#[cfg(feature = "mpd")]
+pub fn materialize(mpd: &mut mpd::Client, smart: &SmartPlaylist) -> Result<usize, String> {
+    use crate::app::library_helpers::song_tag;
+    use std::collections::HashMap;
+
+    let rule =
+        Rule::parse(&smart.rule).map_err(|e| t!("smart.bad_rule", name = smart.name, error = e))?;
+
+    let mut query = mpd::Query::new();
+    query.and(mpd::Term::Base, "");
+    for (tag, value) in rule.search_terms() {
+        query.and(mpd::Term::Tag(tag.into()), value);
+    }
+    let songs = mpd.search(&query, None).map_err(|e| e.to_string())?;
+
+    // Stickers for the whole library in one request each, when first needed
+    let mut stickers: HashMap<&str, HashMap<String, String>> = HashMap::new();
+    let mut files = Vec::new();
+    for song in &songs {
+        let matched = rule.matches(|field| {
+            let one = |v: Option<String>| -> Vec<String> { v.into_iter().collect() };
+            match field {
+                Field::Tag(name) => song
+                    .tags
+                    .iter()
+                    .filter(|(k, _)| k.eq_ignore_ascii_case(name))
+                    .map(|(_, v)| v.clone())
+                    .chain(match name.to_lowercase().as_str() {
+                        "title" => song.title.clone(),
+                        "artist" => song.artist.clone(),
+                        _ => None,
+                    })
+                    .collect(),
+                Field::Year => one(song_tag(song, "Date").and_then(|d| {
+                    let year: String = d.chars().take_while(char::is_ascii_digit).collect();
+                    (!year.is_empty()).then_some(year)
+                })),
+                Field::Duration => one(song.duration.map(|d| d.as_secs().to_string())),
+                Field::File => vec![song.file.clone()],
+                Field::Rating | Field::PlayCount => {
+                    let name = if *field == Field::Rating {
+                        "rating"
+                    } else {
+                        "playCount"
+                    };
+                    let values = stickers.entry(name).or_insert_with(|| {
+                        mpd.find_sticker("song", "", name)
+                            .map(|found| found.into_iter().collect())
+                            .unwrap_or_default()
+                    });
+                    one(values.get(&song.file).cloned())
+                }
+            }
+        });
+        if matched {
+            files.push(song.file.clone());
+            if smart.limit == Some(files.len()) {
+                break;
+            }
+        }
+    }
+
+    let exists = mpd
+        .playlists()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .any(|p| p.name == smart.name);
+    if exists {
+        mpd.pl_clear(&smart.name).map_err(|e| e.to_string())?;
+    }
+    for file in &files {
+        let song = mpd::Song {
+            file: file.clone(),
+            ..Default::default()
+        };
+        mpd.pl_push(&smart.name, &song).map_err(|e| e.to_string())?;
+    }
+    Ok(files.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song<'a>(fields: &'a [(&'a str, &'a str)]) -> impl FnMut(&Field) -> Vec<String> + 'a {
+        move |field| {
+            let key = match field {
+                Field::Tag(tag) => tag.to_lowercase(),
+                Field::Year => "year".into(),
+                Field::Duration => "duration".into(),
+                Field::Rating => "rating".into(),
+                Field::PlayCount => "playcount".into(),
+                Field::File => "file".into(),
+            };
+            fields
+                .iter()
+                .filter(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        let rule = Rule::parse("genre is Jazz AND year>=1990 and Rating >= 4").unwrap();
+        assert_eq!(
+            rule.any_of,
+            vec![vec![
+                Condition {
+                    field: Field::Tag("genre".into()),
+                    op: Op::Is,
+                    value: "Jazz".into(),
+                },
+                Condition {
+                    field: Field::Year,
+                    op: Op::GreaterEq,
+                    value: "1990".into(),
+                },
+                Condition {
+                    field: Field::Rating,
+                    op: Op::GreaterEq,
+                    value: "4".into(),
+                },
+            ]]
+        );
+        assert_eq!(rule.search_terms(), vec![("genre", "Jazz")]);
+
+        // Multi-word and quoted values, OR groups
+        let rule =
+            Rule::parse("artist is not Miles Davis OR album contains \"Love AND Theft\"").unwrap();
+        assert_eq!(rule.any_of.len(), 2);
+        assert_eq!(rule.any_of[0][0].op, Op::IsNot);
+        assert_eq!(rule.any_of[0][0].value, "Miles Davis");
+        assert_eq!(rule.any_of[1][0].value, "Love AND Theft");
+        assert!(rule.search_terms().is_empty());
+
+        for bad in [
+            "",
+            "genre Jazz",
+            "genre is",
+            "genre is Jazz AND",
+            "year => 1990",
+            "title is \"open",
+        ] {
+            assert!(Rule::parse(bad).is_err(), "{:?} should not parse", bad);
+        }
+    }
+
+    #[test]
+    fn test_rule_matches() {
+        let rule = Rule::parse("genre is jazz AND year >= 1990 AND rating >= 4").unwrap();
+        let hit = [("genre", "Jazz"), ("year", "1998"), ("rating", "8")];
+        assert!(rule.matches(song(&hit)));
+        let old = [("genre", "Jazz"), ("year", "1959"), ("rating", "8")];
+        assert!(!rule.matches(song(&old)));
+        // No rating sticker: can't be >= 4
+        let unrated = [("genre", "Jazz"), ("year", "1998")];
+        assert!(!rule.matches(song(&unrated)));
+        // Any of several genre tags will do
+        let tagged = [
+            ("genre", "Fusion"),
+            ("genre", "Jazz"),
+            ("year", "2001"),
+            ("rating", "4"),
+        ];
+        assert!(rule.matches(song(&tagged)));
+
+        // Numbers compare as numbers, not text ("10" > "9")
+        let rule = Rule::parse("rating > 9").unwrap();
+        assert!(rule.matches(song(&[("rating", "10")])));
+
+        let rule = Rule::parse("genre is not Rock OR playcount = 0").unwrap();
+        assert!(rule.matches(song(&[("genre", "Pop")])));
+        assert!(rule.matches(song(&[])));
+        assert!(!rule.matches(song(&[("genre", "rock"), ("playcount", "3")])));
+        assert!(rule.matches(song(&[("genre", "rock"), ("playcount", "0")])));
+
+        // Lookups only happen for fields a condition reaches
+        let rule = Rule::parse("genre is Jazz AND rating >= 4").unwrap();
+        let mut asked = Vec::new();
+        rule.matches(|field| {
+            asked.push(field.clone());
+            vec!["Rock".to_string()]
+        });
+        assert_eq!(asked, vec![Field::Tag("genre".into())]);
+    }
+}
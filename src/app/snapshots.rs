@@ -0,0 +1,149 @@
+//! Named queue snapshots 📸
+//!
+//! `:snapshot save <name>` keeps the queue together with the playing song
+//! and how far into it you were, in `~/.config/vyom/snapshots/<name>.toml`.
+//! `:snapshot load <name>` puts all of it back. MPD playlists only keep the
+//! songs.
+
+use crate::app::config::AppConfig;
+use crate::t;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Snapshot {
+    /// Queue, in order (paths relative to the music directory)
+    pub files: Vec<String>,
+    /// Queue position of the song that was playing or paused
+    pub current: Option<usize>,
+    /// Seconds into that song
+    pub elapsed: f64,
+    /// Playing rather than paused
+    pub playing: bool,
+}
+
+/// `~/.config/vyom/snapshots`
+pub fn dir() -> PathBuf {
+    AppConfig::get_config_dir().join("snapshots")
+}
+
+/// The file for `name`. Names are plain file names: no paths, no dot files.
+fn path_in(dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let name = name.trim();
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(t!("snapshot.bad_name", name = name));
+    }
+    Ok(dir.join(format!("{}.toml", name)))
+}
+
+pub fn save_in(dir: &Path, name: &str, snapshot: &Snapshot) -> Result<(), String> {
+    let path = path_in(dir, name)?;
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let content = toml::to_string_pretty(snapshot).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+pub fn load_in(dir: &Path, name: &str) -> Result<Snapshot, String> {
+    let path = path_in(dir, name)?;
+    let content = fs::read_to_string(&path).map_err(|_| t!("snapshot.not_found", name = name))?;
+    toml::from_str(&content).map_err(|e| e.to_string())
+}
+
+pub fn delete_in(dir: &Path, name: &str) -> Result<(), String> {
+    let path = path_in(dir, name)?;
+    fs::remove_file(path).map_err(|_| t!("snapshot.not_found", name = name))
+}
+
+/// Saved snapshot names, sorted
+pub fn list_in(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            (path.extension()? == "toml")
+                .then(|| path.file_stem()?.to_str().map(str::to_string))
+                .flatten()
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// The queue and playback position right now
+#[cfg(feature = "mpd")]
+pub fn capture(mpd: &mut mpd::Client) -> Result<Snapshot, mpd::error::Error> {
+    let files = mpd.queue()?.into_iter().map(|s| s.file).collect();
+    let status = mpd.status()?;
+    Ok(Snapshot {
+        files,
+        current: status.song.map(|s| s.pos as usize),
+        elapsed: status.elapsed.map(|d| d.as_secs_f64()).unwrap_or(0.0),
+        playing: status.state == mpd::State::Play,
+    })
+}
+
+/// Replace the queue with `snapshot` and go back to where it was. Songs no
+/// longer in the library are left out; returns how many.
+#[cfg(feature = "mpd")]
+pub fn restore(mpd: &mut mpd::Client, snapshot: &Snapshot) -> Result<usize, mpd::error::Error> {
+    mpd.clear()?;
+    let mut missing = 0;
+    let mut current = snapshot.current;
+    for (i, file) in snapshot.files.iter().enumerate() {
+        let song = mpd::Song {
+            file: file.clone(),
+            ..Default::default()
+        };
+        if mpd.push(&song).is_err() {
+            missing += 1;
+            current = match current {
+                Some(c) if c == i => None,
+                Some(c) if c > i => Some(c - 1),
+                other => other,
+            };
+        }
+    }
+
+    if let Some(pos) = current {
+        mpd.seek(pos as u32, snapshot.elapsed)?;
+        if !snapshot.playing {
+            mpd.pause(true)?;
+        }
+    }
+    Ok(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_files_round_trip() {
+        let dir = std::env::temp_dir().join(format!("vyom-snapshots-{}", std::process::id()));
+        let snapshot = Snapshot {
+            files: vec!["a/1.flac".into(), "b/2.flac".into()],
+            current: Some(1),
+            elapsed: 42.5,
+            playing: true,
+        };
+        save_in(&dir, "evening", &snapshot).unwrap();
+        save_in(&dir, "morning", &Snapshot::default()).unwrap();
+        assert_eq!(load_in(&dir, "evening").unwrap(), snapshot);
+        assert_eq!(list_in(&dir), vec!["evening", "morning"]);
+
+        delete_in(&dir, "morning").unwrap();
+        assert_eq!(list_in(&dir), vec!["evening"]);
+        assert!(load_in(&dir, "morning").is_err());
+
+        // Names can't leave the directory
+        assert!(save_in(&dir, "../escape", &snapshot).is_err());
+        assert!(save_in(&dir, ".hidden", &snapshot).is_err());
+        assert!(save_in(&dir, "  ", &snapshot).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
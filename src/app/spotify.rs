@@ -0,0 +1,326 @@
+//! `vyom spotify <playlist link>`: a Spotify playlist, from your library 🟢
+//!
+//! The track list comes from the Spotify Web API (an app's client
+//! credentials from `spotify_client_id` / `spotify_client_secret`). Each
+//! track is looked up in the MPD library by artist and title, loosely: case,
+//! punctuation, "The", "feat." and "- Remastered" style suffixes don't
+//! matter, and when several songs fit the one closest in length wins. The
+//! matches become an MPD playlist; the rest are listed as missing.
+
+use crate::app::library_cache::CachedSong;
+use crate::app::related::normalize;
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+
+const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
+const API_URL: &str = "https://api.spotify.com/v1";
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpotifyTrack {
+    pub title: String,
+    pub artists: Vec<String>,
+    pub duration_ms: u64,
+}
+
+impl SpotifyTrack {
+    /// "Artist, Artist — Title"
+    pub fn label(&self) -> String {
+        format!("{} — {}", self.artists.join(", "), self.title)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpotifyPlaylist {
+    pub name: String,
+    pub tracks: Vec<SpotifyTrack>,
+}
+
+/// The playlist ID in an `open.spotify.com` link, a `spotify:playlist:`
+/// URI or on its own
+pub fn playlist_id(link: &str) -> Option<String> {
+    let link = link.trim();
+    let id = if let Some(rest) = link.strip_prefix("spotify:playlist:") {
+        rest
+    } else if link.contains("open.spotify.com") {
+        let path = link.split(['?', '#']).next().unwrap_or(link);
+        let mut parts = path.split('/').skip_while(|part| *part != "playlist");
+        parts.next()?;
+        parts.next()?
+    } else {
+        link
+    };
+    (id.len() == 22 && id.chars().all(|c| c.is_ascii_alphanumeric())).then(|| id.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+struct Token {
+    access_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlaylistResponse {
+    name: String,
+    tracks: Page,
+}
+
+#[derive(Debug, Deserialize)]
+struct Page {
+    #[serde(default)]
+    items: Vec<Item>,
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Item {
+    /// null for tracks no longer on Spotify
+    track: Option<ApiTrack>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiTrack {
+    name: String,
+    #[serde(default)]
+    duration_ms: u64,
+    #[serde(default)]
+    artists: Vec<ApiArtist>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiArtist {
+    name: String,
+}
+
+/// The playlist's name and every track on it
+pub async fn fetch(
+    client: &Client,
+    id: &str,
+    client_id: &str,
+    client_secret: &str,
+) -> Result<SpotifyPlaylist> {
+    let token: Token = client
+        .post(TOKEN_URL)
+        .basic_auth(client_id, Some(client_secret))
+        .form(&[("grant_type", "client_credentials")])
+        .send()
+        .await?
+        .error_for_status()
+        .context("Spotify didn't accept the client ID and secret")?
+        .json()
+        .await?;
+
+    let fields = "name,tracks(next,items(track(name,duration_ms,artists(name))))";
+    let first: PlaylistResponse = client
+        .get(format!("{}/playlists/{}", API_URL, id))
+        .bearer_auth(&token.access_token)
+        .query(&[("fields", fields)])
+        .send()
+        .await?
+        .error_for_status()
+        .context("Spotify has no playlist with that link (or it's private)")?
+        .json()
+        .await?;
+
+    let mut playlist = SpotifyPlaylist {
+        name: first.name,
+        tracks: Vec::new(),
+    };
+    let mut page = first.tracks;
+    loop {
+        playlist
+            .tracks
+            .extend(page.items.into_iter().filter_map(|item| {
+                let track = item.track?;
+                Some(SpotifyTrack {
+                    title: track.name,
+                    artists: track.artists.into_iter().map(|a| a.name).collect(),
+                    duration_ms: track.duration_ms,
+                })
+            }));
+        let Some(next) = page.next else {
+            break;
+        };
+        page = client
+            .get(next)
+            .bearer_auth(&token.access_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+    }
+    Ok(playlist)
+}
+
+/// How alike two cleaned titles (or artists) must be to count as the same
+const MIN_SIMILARITY: f32 = 0.8;
+
+/// The library song that is `track`, if there is one
+pub fn best_match<'a>(track: &SpotifyTrack, library: &'a [CachedSong]) -> Option<&'a CachedSong> {
+    let title = clean_title(&track.title);
+    if title.is_empty() {
+        return None;
+    }
+    let artists: Vec<String> = track
+        .artists
+        .iter()
+        .map(|a| normalize(a))
+        .filter(|a| !a.is_empty())
+        .collect();
+
+    library
+        .iter()
+        .filter_map(|song| {
+            let names = song.artist.iter().chain(song.album_artist.iter());
+            let by_artist = names.map(|n| normalize(n)).any(|name| {
+                !name.is_empty()
+                    && artists.iter().any(|a| {
+                        name.contains(a.as_str())
+                            || a.contains(name.as_str())
+                            || similarity(&name, a) >= MIN_SIMILARITY
+                    })
+            });
+            if !by_artist {
+                return None;
+            }
+            let own = clean_title(song.title.as_deref()?);
+            let score = similarity(&own, &title);
+            if score < MIN_SIMILARITY {
+                return None;
+            }
+            let off = song
+                .duration_ms
+                .map_or(u64::MAX, |d| d.abs_diff(track.duration_ms));
+            Some((score, off, song))
+        })
+        // The closest title, then the closest length
+        .min_by(|(a, a_off, _), (b, b_off, _)| b.total_cmp(a).then(a_off.cmp(b_off)))
+        .map(|(_, _, song)| song)
+}
+
+/// 1.0 for equal strings down to 0.0: edit distance (a swap of two
+/// neighbours counts once) over the longer length
+fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    // Three rows of the optimal string alignment table
+    let mut before: Vec<usize> = Vec::new();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (prev[j] + 1).min(row[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(before[j - 2] + 1);
+            }
+        }
+        before = std::mem::replace(&mut prev, row);
+    }
+    1.0 - prev[b.len()] as f32 / longest as f32
+}
+
+/// Lowercase letters and digits of a title, without "(feat. ...)",
+/// "[Live]" or " - Remastered 2011"
+fn clean_title(title: &str) -> String {
+    let title = title.split(" - ").next().unwrap_or(title);
+    let title = title.split(['(', '[']).next().unwrap_or(title);
+    let title = title.to_lowercase();
+    normalize(title.split(" feat").next().unwrap_or(&title))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(file: &str, artist: &str, title: &str, duration_ms: u64) -> CachedSong {
+        CachedSong {
+            file: file.into(),
+            title: Some(title.into()),
+            artist: Some(artist.into()),
+            duration_ms: Some(duration_ms),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_playlist_links() {
+        let id = "37i9dQZF1DXcBWIGoYBM5M";
+        for link in [
+            "https://open.spotify.com/playlist/37i9dQZF1DXcBWIGoYBM5M?si=abc123",
+            "https://open.spotify.com/intl-de/playlist/37i9dQZF1DXcBWIGoYBM5M",
+            "spotify:playlist:37i9dQZF1DXcBWIGoYBM5M",
+            "37i9dQZF1DXcBWIGoYBM5M",
+        ] {
+            assert_eq!(playlist_id(link).as_deref(), Some(id), "{}", link);
+        }
+        assert_eq!(
+            playlist_id("https://open.spotify.com/album/1DFixLWuPkv3KT3TnV35m3"),
+            None
+        );
+        assert_eq!(playlist_id("not a link"), None);
+    }
+
+    #[test]
+    fn test_tracks_match_loosely() {
+        let library = vec![
+            song("live.flac", "The Beatles", "Let It Be (Live)", 250_000),
+            song("lib.flac", "The Beatles", "Let It Be", 243_000),
+            song("cover.flac", "Aretha Franklin", "Let It Be", 210_000),
+            song("daft.flac", "Daft Punk", "Get Lucky", 369_000),
+        ];
+        let track = |title: &str, artists: &[&str], duration_ms| SpotifyTrack {
+            title: title.into(),
+            artists: artists.iter().map(|a| a.to_string()).collect(),
+            duration_ms,
+        };
+        let found = |t: &SpotifyTrack| best_match(t, &library).map(|s| s.file.as_str());
+
+        assert_eq!(
+            found(&track("Let It Be - Remastered 2009", &["Beatles"], 243_026)),
+            Some("lib.flac")
+        );
+        assert_eq!(
+            found(&track(
+                "Get Lucky (feat. Pharrell Williams and Nile Rodgers)",
+                &["Daft Punk", "Pharrell Williams", "Nile Rodgers"],
+                248_413
+            )),
+            Some("daft.flac")
+        );
+        assert_eq!(found(&track("Yesterday", &["The Beatles"], 125_000)), None);
+        assert_eq!(found(&track("Let It Be", &["Nina Simone"], 240_000)), None);
+    }
+
+    #[test]
+    fn test_titles_match_by_similarity() {
+        let library = vec![
+            song("gone.flac", "U2", "Gone", 266_000),
+            song("someone.flac", "U2", "Someone", 232_000),
+            song("yesterday.flac", "The Beatles", "Yesterday", 125_000),
+        ];
+        let track = |title: &str, artist: &str| SpotifyTrack {
+            title: title.into(),
+            artists: vec![artist.into()],
+            duration_ms: 200_000,
+        };
+        let found = |t: &SpotifyTrack| best_match(t, &library).map(|s| s.file.as_str());
+
+        // Same artist, a title that only shares letters
+        assert_eq!(found(&track("One", "U2")), None);
+        // A typo in the title, or in the artist
+        assert_eq!(
+            found(&track("Yesterdya", "The Beatles")),
+            Some("yesterday.flac")
+        );
+        assert_eq!(
+            found(&track("Yesterday", "The Beatels")),
+            Some("yesterday.flac")
+        );
+        assert!((similarity("one", "gone") - 0.75).abs() < 1e-6);
+    }
+}
@@ -1,19 +1,129 @@
-use image::DynamicImage;
+use image::{imageops::FilterType, DynamicImage};
+use ratatui::text::Line;
+use ratatui_image::picker::ProtocolType;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub enum ArtStyle {
     #[default]
-    Block, // Half-block truecolor
+    Auto, // Image if the terminal supports graphics, else Block
+    Block,   // Half-block truecolor
     Ascii,   // Character based on luminance
     Braille, // 2x4 dot pattern
     Image,   // ratatui-image (Sixel/Kitty)
     Off,     // Hidden
 }
 
+/// Graphics protocol override (`art_protocol` in config.toml)
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphicsProtocol {
+    #[default]
+    Auto,
+    Kitty,
+    Iterm2,
+    Sixel,
+    Halfblocks,
+}
+
+impl GraphicsProtocol {
+    /// Forced protocol, or `None` to detect
+    pub fn protocol_type(self) -> Option<ProtocolType> {
+        match self {
+            GraphicsProtocol::Auto => None,
+            GraphicsProtocol::Kitty => Some(ProtocolType::Kitty),
+            GraphicsProtocol::Iterm2 => Some(ProtocolType::Iterm2),
+            GraphicsProtocol::Sixel => Some(ProtocolType::Sixel),
+            GraphicsProtocol::Halfblocks => Some(ProtocolType::Halfblocks),
+        }
+    }
+}
+
+/// Resampling filter for text artwork (`art_filter` in config.toml)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtFilter {
+    /// Blocky, fastest
+    Nearest,
+    /// Bilinear: soft and quick
+    #[default]
+    Triangle,
+    CatmullRom,
+    Gaussian,
+    /// Sharpest, slowest
+    Lanczos,
+}
+
+impl ArtFilter {
+    pub fn filter_type(self) -> FilterType {
+        match self {
+            ArtFilter::Nearest => FilterType::Nearest,
+            ArtFilter::Triangle => FilterType::Triangle,
+            ArtFilter::CatmullRom => FilterType::CatmullRom,
+            ArtFilter::Gaussian => FilterType::Gaussian,
+            ArtFilter::Lanczos => FilterType::Lanczos3,
+        }
+    }
+}
+
+/// Color reduction for text artwork (`art_dither` in config.toml): map the
+/// colors onto the 256-color palette so limited terminals don't band
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtDither {
+    /// Truecolor as is
+    #[default]
+    Off,
+    /// Floyd–Steinberg when the terminal doesn't report truecolor
+    Auto,
+    /// Bayer pattern: stable between frames, a fine grid
+    Ordered,
+    /// Error diffusion: smoothest gradients
+    FloydSteinberg,
+}
+
+impl ArtDither {
+    /// `Auto` decided for this terminal (`COLORTERM`)
+    pub fn resolve(self, truecolor: bool) -> Self {
+        match self {
+            ArtDither::Auto if truecolor => ArtDither::Off,
+            ArtDither::Auto => ArtDither::FloydSteinberg,
+            other => other,
+        }
+    }
+}
+
+/// How text artwork is scaled and colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ArtQuality {
+    pub filter: ArtFilter,
+    /// Never `Auto` here
+    pub dither: ArtDither,
+}
+
 pub enum ArtworkState {
     Idle,
     Loading,
-    Loaded(DynamicImage),
+    /// Shared so resizing can happen on another thread without a copy
+    Loaded(Arc<DynamicImage>),
     Failed,
 }
+
+/// Which image, style and size a text-art buffer was built for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArtCellsKey {
+    /// `App::art_generation` of the image
+    pub generation: u64,
+    pub style: ArtStyle,
+    pub quality: ArtQuality,
+    pub width: u16,
+    pub height: u16,
+}
+
+/// Block/ASCII/Braille artwork already resized to cells, built in the
+/// background so big covers don't stall a frame
+pub struct ArtCells {
+    pub key: ArtCellsKey,
+    pub lines: Vec<Line<'static>>,
+}
@@ -0,0 +1,75 @@
+/// Max lines kept in the console scrollback
+const MAX_OUTPUT: usize = 500;
+/// Max commands kept in history
+const MAX_HISTORY: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConsoleLineKind {
+    Command,
+    Response,
+    Error,
+    Info,
+}
+
+/// Raw MPD command console 🖥️ (hidden, advanced)
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleState {
+    pub open: bool,
+    pub input: String,
+    pub output: Vec<(ConsoleLineKind, String)>,
+    pub history: Vec<String>,
+    /// Position while browsing history with Up/Down (None = editing a new line)
+    pub history_pos: Option<usize>,
+    /// Command waiting for y/n (anything not read-only)
+    pub pending_confirm: Option<String>,
+    /// Lines scrolled up from the bottom
+    pub scroll: usize,
+}
+
+impl ConsoleState {
+    pub fn push(&mut self, kind: ConsoleLineKind, text: impl Into<String>) {
+        self.output.push((kind, text.into()));
+        if self.output.len() > MAX_OUTPUT {
+            let excess = self.output.len() - MAX_OUTPUT;
+            self.output.drain(..excess);
+        }
+        self.scroll = 0;
+    }
+
+    /// Record a submitted command (consecutive duplicates are collapsed)
+    pub fn remember(&mut self, command: &str) {
+        if self.history.last().map(String::as_str) != Some(command) {
+            self.history.push(command.to_string());
+            if self.history.len() > MAX_HISTORY {
+                self.history.remove(0);
+            }
+        }
+        self.history_pos = None;
+    }
+
+    pub fn history_prev(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let pos = match self.history_pos {
+            Some(p) => p.saturating_sub(1),
+            None => self.history.len() - 1,
+        };
+        self.history_pos = Some(pos);
+        self.input = self.history[pos].clone();
+    }
+
+    pub fn history_next(&mut self) {
+        match self.history_pos {
+            Some(p) if p + 1 < self.history.len() => {
+                self.history_pos = Some(p + 1);
+                self.input = self.history[p + 1].clone();
+            }
+            Some(_) => {
+                self.history_pos = None;
+                self.input.clear();
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+/// How often the tick task wakes the app 🎞️
+///
+/// Input and background events redraw right away through `needs_redraw`;
+/// ticks only drive what moves on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameRate {
+    /// Something is sliding or fading: 60 FPS
+    Animation,
+    /// Visualizer, spectrum or meters fed by playing audio: 30 FPS
+    Live,
+    /// Nothing moves but the clock: 10 FPS
+    Idle,
+}
+
+impl FrameRate {
+    pub fn interval(self) -> Duration {
+        match self {
+            FrameRate::Animation => Duration::from_millis(16),
+            FrameRate::Live => Duration::from_millis(33),
+            FrameRate::Idle => Duration::from_millis(100),
+        }
+    }
+}
+
+/// While idle and playing, redraw this often for the clock and progress bar
+pub const CLOCK_REDRAW: Duration = Duration::from_millis(500);
+
+/// How long the seek preview stays up after an `h` / `l` seek
+pub const SEEK_FLASH: Duration = Duration::from_millis(1500);
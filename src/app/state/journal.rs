@@ -0,0 +1,148 @@
+//! Undo history for queue and playlist edits ↩️
+//!
+//! Each entry keeps enough to redo or reverse it with plain MPD commands
+//! (`library_helpers::replay`), so nothing depends on Vyom's own copy of the
+//! queue still being current.
+
+use crate::t;
+
+/// Entries kept; the oldest are forgotten first
+const CAPACITY: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    /// `file` removed from queue position `pos`
+    QueueDelete { pos: u32, file: String },
+    /// `file` moved from queue position `from` to `to`
+    QueueMove { from: u32, to: u32, file: String },
+    /// Whole queue cleared; its files in order
+    QueueClear { files: Vec<String> },
+    /// Saved playlist deleted; its files in order
+    PlaylistDelete { name: String, files: Vec<String> },
+}
+
+impl Operation {
+    /// For the undo / redo toasts
+    pub fn describe(&self) -> String {
+        match self {
+            Operation::QueueDelete { file, .. } => {
+                let name = file.rsplit('/').next().unwrap_or(file);
+                t!("journal.queue_delete", song = name)
+            }
+            Operation::QueueMove { .. } => t!("journal.queue_move"),
+            Operation::QueueClear { files } => t!("journal.queue_clear", count = files.len()),
+            Operation::PlaylistDelete { name, .. } => t!("journal.playlist_delete", name = name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Journal {
+    undo: Vec<Operation>,
+    redo: Vec<Operation>,
+}
+
+impl Journal {
+    /// A new edit: it can be undone, and whatever was undone before is gone
+    pub fn record(&mut self, op: Operation) {
+        if self.undo.len() == CAPACITY {
+            self.undo.remove(0);
+        }
+        self.undo.push(op);
+        self.redo.clear();
+    }
+
+    /// The edit `u` reverses next
+    pub fn take_undo(&mut self) -> Option<Operation> {
+        self.undo.pop()
+    }
+
+    /// The edit redo applies again next
+    pub fn take_redo(&mut self) -> Option<Operation> {
+        self.redo.pop()
+    }
+
+    /// `op` was undone: it can be redone
+    pub fn undone(&mut self, op: Operation) {
+        self.redo.push(op);
+    }
+
+    /// `op` was redone: it can be undone again, without dropping the rest
+    /// of the redo stack
+    pub fn redone(&mut self, op: Operation) {
+        self.undo.push(op);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delete(pos: u32) -> Operation {
+        Operation::QueueDelete {
+            pos,
+            file: format!("song{}.flac", pos),
+        }
+    }
+
+    #[test]
+    fn test_undo_redo_order() {
+        let mut journal = Journal::default();
+        journal.record(delete(1));
+        journal.record(Operation::QueueMove {
+            from: 2,
+            to: 3,
+            file: "song2.flac".into(),
+        });
+
+        let op = journal.take_undo().unwrap();
+        assert_eq!(
+            op,
+            Operation::QueueMove {
+                from: 2,
+                to: 3,
+                file: "song2.flac".into(),
+            }
+        );
+        journal.undone(op);
+        let op = journal.take_undo().unwrap();
+        journal.undone(op);
+        assert!(!journal.can_undo());
+
+        // Redo runs them again in the original order
+        let op = journal.take_redo().unwrap();
+        assert_eq!(op, delete(1));
+        journal.redone(op);
+        assert!(journal.can_redo());
+
+        // A new edit drops what was left to redo
+        journal.record(delete(5));
+        assert!(!journal.can_redo());
+        assert_eq!(journal.take_undo(), Some(delete(5)));
+        assert_eq!(journal.take_undo(), Some(delete(1)));
+    }
+
+    #[test]
+    fn test_journal_forgets_oldest() {
+        let mut journal = Journal::default();
+        for pos in 0..CAPACITY as u32 + 5 {
+            journal.record(delete(pos));
+        }
+        let mut count = 0;
+        let mut last = None;
+        while let Some(op) = journal.take_undo() {
+            count += 1;
+            last = Some(op);
+        }
+        assert_eq!(count, CAPACITY);
+        assert_eq!(last, Some(delete(5)));
+    }
+}
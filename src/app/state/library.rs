@@ -1,11 +1,13 @@
 /// Library panel sub-mode 📚
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize)]
 pub enum LibraryMode {
     #[default]
     Queue, // Current queue
     Directory, // Neo-tree style music folder browser
+    Albums,    // Cover grid
     Search,    // Search library
     Playlists, // Saved playlists
+    Bandcamp,  // Purchases, with `bandcamp_identity` set
 }
 
 /// Library browser item type
@@ -30,11 +32,188 @@ pub struct LibraryItem {
 }
 
 /// Queue item for MPD playlist display 📋
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct QueueItem {
     pub title: String,
     pub artist: String,
     pub duration_ms: u64,
     pub is_current: bool,
     pub file_path: String, // For tag editing
+    pub album: String,
+    pub year: String,
+}
+
+impl From<crate::player::QueueItem> for QueueItem {
+    fn from(
+        (title, artist, duration_ms, is_current, file_path, album, year): crate::player::QueueItem,
+    ) -> Self {
+        Self {
+            title,
+            artist,
+            duration_ms,
+            is_current,
+            file_path,
+            album,
+            year,
+        }
+    }
+}
+
+/// Consecutive queue songs of one album, listed under a header row
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueGroup {
+    /// Queue index of its first song
+    pub start: usize,
+    pub len: usize,
+    pub album: String,
+    pub year: String,
+    pub duration_ms: u64,
+    /// Only the header is shown
+    pub folded: bool,
+}
+
+impl QueueGroup {
+    pub fn contains(&self, idx: usize) -> bool {
+        (self.start..self.start + self.len).contains(&idx)
+    }
+}
+
+/// A row of the queue view
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueueRow {
+    Album(QueueGroup),
+    /// Queue index
+    Song(usize),
+}
+
+/// The queue with a header above every run of two or more songs from one
+/// album; the songs of `folded` albums are left out
+pub fn queue_rows(
+    queue: &[QueueItem],
+    folded: &std::collections::HashSet<String>,
+) -> Vec<QueueRow> {
+    let mut rows = Vec::with_capacity(queue.len());
+    let mut start = 0;
+    while start < queue.len() {
+        let album = &queue[start].album;
+        let len = if album.is_empty() {
+            1
+        } else {
+            queue[start..]
+                .iter()
+                .take_while(|q| &q.album == album)
+                .count()
+        };
+        if len < 2 {
+            rows.push(QueueRow::Song(start));
+            start += 1;
+            continue;
+        }
+
+        let group = QueueGroup {
+            start,
+            len,
+            album: album.clone(),
+            year: queue[start].year.clone(),
+            duration_ms: queue[start..start + len]
+                .iter()
+                .map(|q| q.duration_ms)
+                .sum(),
+            folded: folded.contains(album),
+        };
+        let open = !group.folded;
+        rows.push(QueueRow::Album(group));
+        if open {
+            rows.extend((start..start + len).map(QueueRow::Song));
+        }
+        start += len;
+    }
+    rows
+}
+
+/// Row showing queue song `selected`: the header when its album is folded
+pub fn queue_selected_row(rows: &[QueueRow], selected: usize) -> usize {
+    rows.iter()
+        .position(|row| match row {
+            QueueRow::Song(idx) => *idx == selected,
+            QueueRow::Album(group) => group.folded && group.contains(selected),
+        })
+        .unwrap_or(0)
+}
+
+/// Bring `queue` up to date with the edits MPD reported
+pub fn apply_queue_delta(queue: &mut Vec<QueueItem>, delta: crate::player::QueueDelta) {
+    queue.truncate(delta.len);
+    for (pos, item) in delta.changed {
+        let len = queue.len();
+        match queue.get_mut(pos) {
+            Some(entry) => *entry = item.into(),
+            None if pos == len => queue.push(item.into()),
+            None => tracing::debug!("Queue change at {} past the end ({})", pos, len),
+        }
+    }
+    for (i, item) in queue.iter_mut().enumerate() {
+        item.is_current = delta.current == Some(i);
+    }
+}
+
+/// The CUE sheet a virtual track belongs to: MPD lists the tracks of a
+/// single-file rip's `Album.cue` as `Album.cue/track0001` and so on
+pub fn cue_sheet(file: &str) -> Option<&str> {
+    let (sheet, track) = file.rsplit_once('/')?;
+    (sheet.to_lowercase().ends_with(".cue") && track.starts_with("track")).then_some(sheet)
+}
+
+/// Order a folder listing: folders, songs by name, then CUE tracks in
+/// sheet order. The audio file a sheet splits up (`Album.flac` next to
+/// `Album.cue`) is left out, its tracks stand in for it.
+pub fn sort_directory(items: &mut Vec<LibraryItem>) {
+    let sheets: std::collections::HashSet<String> = items
+        .iter()
+        .filter_map(|i| cue_sheet(i.path.as_deref()?))
+        .map(|sheet| sheet[..sheet.len() - 4].to_lowercase())
+        .collect();
+    items.retain(|item| {
+        let Some(path) = item.path.as_deref() else {
+            return true;
+        };
+        let stem = path.rsplit_once('.').map_or(path, |(stem, _)| stem);
+        item.item_type != LibraryItemType::Song
+            || cue_sheet(path).is_some()
+            || !sheets.contains(&stem.to_lowercase())
+    });
+    items.sort_by_cached_key(|item| {
+        let path = item.path.as_deref().unwrap_or("");
+        match (&item.item_type, cue_sheet(path)) {
+            (LibraryItemType::Folder, _) => (0, String::new(), item.name.to_lowercase()),
+            (_, None) => (1, String::new(), item.name.to_lowercase()),
+            (_, Some(sheet)) => (2, sheet.to_lowercase(), path.to_string()),
+        }
+    });
+}
+
+/// Which album: one title under two album artists is two albums
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AlbumKey {
+    /// The album artist, or the artist where that tag is missing, the
+    /// same fallback MPD applies when filtering on `AlbumArtist`
+    pub artist: String,
+    pub name: String,
+}
+
+/// Album tile in the cover grid 💿
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlbumEntry {
+    pub name: String,
+    pub artist: String,
+    pub file: String, // First track, for the cover
+}
+
+impl AlbumEntry {
+    pub fn key(&self) -> AlbumKey {
+        AlbumKey {
+            artist: self.artist.clone(),
+            name: self.name.clone(),
+        }
+    }
 }
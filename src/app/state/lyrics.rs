@@ -1,11 +1,305 @@
-use crate::app::lyrics::LyricLine;
+use crate::app::lyrics::{LyricLine, LyricsCandidate};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum LyricsState {
     Idle,
     Loading,
     Loaded(Vec<LyricLine>, String),
+    /// Unsynced lyrics (no timestamps), shown as a scrollable paragraph
+    Plain(Vec<String>, String),
     Instrumental,
     Failed(String),
     NotFound,
 }
+
+/// Text search inside the current song's lyrics 🔎
+#[derive(Debug, Clone, PartialEq)]
+pub struct LyricsSearch {
+    pub query: String,
+    /// Indices of matching lines
+    pub matches: Vec<usize>,
+    /// Position in `matches`
+    pub current: usize,
+}
+
+impl LyricsSearch {
+    /// Case-insensitive match over `lines`; the first hit at or after `from` comes first
+    pub fn new<'a>(query: &str, lines: impl Iterator<Item = &'a str>, from: usize) -> Self {
+        let needle = query.to_lowercase();
+        let matches: Vec<usize> = lines
+            .enumerate()
+            .filter(|(_, l)| !needle.is_empty() && l.to_lowercase().contains(&needle))
+            .map(|(i, _)| i)
+            .collect();
+        let current = matches.iter().position(|&i| i >= from).unwrap_or(0);
+        Self {
+            query: query.to_string(),
+            matches,
+            current,
+        }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.matches.get(self.current).copied()
+    }
+
+    pub fn next_match(&mut self) -> Option<usize> {
+        if !self.matches.is_empty() {
+            self.current = (self.current + 1) % self.matches.len();
+        }
+        self.line()
+    }
+
+    pub fn prev_match(&mut self) -> Option<usize> {
+        if !self.matches.is_empty() {
+            self.current = (self.current + self.matches.len() - 1) % self.matches.len();
+        }
+        self.line()
+    }
+
+    pub fn is_match(&self, line: usize) -> bool {
+        self.matches.binary_search(&line).is_ok()
+    }
+}
+
+/// Manual lyrics lookup popup 🔍 (fix the query, pick a result, bind it to the track)
+#[derive(Debug, Clone)]
+pub struct LyricsLookup {
+    /// Track the lookup was opened for (`name + artist`)
+    pub track_id: String,
+    /// Track metadata the picked lyrics get cached under
+    pub track_artist: String,
+    pub track_title: String,
+    /// Editable query
+    pub artist: String,
+    pub title: String,
+    pub active_field: usize, // 0=artist, 1=title, 2=results
+    pub results: Vec<LyricsCandidate>,
+    pub selected: usize,
+    pub status: LookupStatus,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LookupStatus {
+    Editing,
+    Searching,
+    Done,
+    Failed(String),
+}
+
+impl LyricsLookup {
+    pub fn new(track_id: &str, artist: &str, title: &str) -> Self {
+        Self {
+            track_id: track_id.to_string(),
+            track_artist: artist.to_string(),
+            track_title: title.to_string(),
+            artist: artist.to_string(),
+            title: title.to_string(),
+            active_field: 1,
+            results: Vec::new(),
+            selected: 0,
+            status: LookupStatus::Editing,
+        }
+    }
+
+    /// Text field under the cursor (None while the result list is focused)
+    pub fn active_value(&mut self) -> Option<&mut String> {
+        match self.active_field {
+            0 => Some(&mut self.artist),
+            1 => Some(&mut self.title),
+            _ => None,
+        }
+    }
+
+    /// Tab order: artist → title → results (skipped while empty)
+    pub fn next_field(&mut self) {
+        let fields = if self.results.is_empty() { 2 } else { 3 };
+        self.active_field = (self.active_field + 1) % fields;
+    }
+
+    pub fn set_results(&mut self, results: Vec<LyricsCandidate>) {
+        self.results = results;
+        self.selected = 0;
+        self.status = LookupStatus::Done;
+        if !self.results.is_empty() {
+            self.active_field = 2;
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn chosen(&self) -> Option<&LyricsCandidate> {
+        if self.active_field == 2 {
+            self.results.get(self.selected)
+        } else {
+            None
+        }
+    }
+}
+
+/// In-place lyrics editor ✍️ (fix text, tap to re-time, save as .lrc)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LyricsEditor {
+    pub lines: Vec<LyricLine>,
+    pub selected: usize,
+    pub dirty: bool,
+}
+
+impl LyricsEditor {
+    /// Start editing the loaded lyrics (unsynced lines start at 0:00)
+    pub fn from_state(state: &LyricsState) -> Option<Self> {
+        let lines = match state {
+            LyricsState::Loaded(lines, _) => lines.clone(),
+            LyricsState::Plain(text, _) => text
+                .iter()
+                .map(|t| LyricLine {
+                    timestamp_ms: 0,
+                    text: t.clone(),
+                })
+                .collect(),
+            _ => return None,
+        };
+        if lines.is_empty() {
+            return None;
+        }
+        Some(Self {
+            lines,
+            selected: 0,
+            dirty: false,
+        })
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.lines.len().saturating_sub(1));
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Stamp the selected line with `position_ms` and move on, so timing a
+    /// song is just tapping along as each line starts
+    pub fn retime(&mut self, position_ms: u64) {
+        if let Some(line) = self.lines.get_mut(self.selected) {
+            line.timestamp_ms = position_ms;
+            self.dirty = true;
+            self.select_next();
+        }
+    }
+
+    pub fn selected_text(&self) -> &str {
+        self.lines
+            .get(self.selected)
+            .map(|l| l.text.as_str())
+            .unwrap_or("")
+    }
+
+    pub fn set_text(&mut self, index: usize, text: &str) {
+        if let Some(line) = self.lines.get_mut(index) {
+            if line.text != text {
+                line.text = text.to_string();
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Lines in playback order, ready to save
+    pub fn finish(&self) -> Vec<LyricLine> {
+        let mut lines = self.lines.clone();
+        lines.sort_by_key(|l| l.timestamp_ms);
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(ms: u64, text: &str) -> LyricLine {
+        LyricLine {
+            timestamp_ms: ms,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_retime_advances_and_sorts_on_finish() {
+        let state = LyricsState::Loaded(vec![line(1000, "a"), line(2000, "b")], "x".into());
+        let mut editor = LyricsEditor::from_state(&state).unwrap();
+        editor.retime(5000);
+        assert_eq!(editor.selected, 1);
+        assert!(editor.dirty);
+        editor.retime(6000);
+        // Last line: selection stays put
+        assert_eq!(editor.selected, 1);
+
+        editor.select_prev();
+        editor.retime(7000);
+        let done = editor.finish();
+        assert_eq!(done[0], line(6000, "b"));
+        assert_eq!(done[1], line(7000, "a"));
+    }
+
+    #[test]
+    fn test_search_wraps_and_starts_near_cursor() {
+        let lines = ["Hello", "world", "hello again", "bye"];
+        let mut search = LyricsSearch::new("HELLO", lines.iter().copied(), 1);
+        assert_eq!(search.matches, vec![0, 2]);
+        assert_eq!(search.line(), Some(2));
+        assert_eq!(search.next_match(), Some(0));
+        assert_eq!(search.prev_match(), Some(2));
+        assert!(search.is_match(0) && !search.is_match(1));
+
+        let mut none = LyricsSearch::new("zzz", lines.iter().copied(), 0);
+        assert_eq!(none.next_match(), None);
+    }
+
+    #[test]
+    fn test_lookup_focus_moves_to_results() {
+        use crate::app::lyrics::LyricsFetchResult;
+
+        let mut lookup = LyricsLookup::new("SongArtist", "Artist", "Song");
+        lookup.active_value().unwrap().push('!');
+        assert_eq!(lookup.title, "Song!");
+        // No results yet: Tab only cycles the two fields
+        lookup.next_field();
+        lookup.next_field();
+        assert_eq!(lookup.active_field, 1);
+        assert!(lookup.chosen().is_none());
+
+        let candidate = LyricsCandidate {
+            artist: "Artist".into(),
+            title: "Song".into(),
+            album: String::new(),
+            duration_secs: None,
+            lyrics: LyricsFetchResult::Plain(vec!["la".into()], "LRCLIB".into()),
+        };
+        lookup.set_results(vec![candidate.clone(), candidate]);
+        assert_eq!(lookup.active_field, 2);
+        assert!(lookup.active_value().is_none());
+        lookup.select_next();
+        lookup.select_next();
+        assert_eq!(lookup.selected, 1);
+        assert!(lookup.chosen().is_some());
+        // Original metadata is kept for the cache key
+        assert_eq!(lookup.track_title, "Song");
+    }
+
+    #[test]
+    fn test_plain_lyrics_start_untimed() {
+        let state = LyricsState::Plain(vec!["one".into(), "two".into()], "x".into());
+        let mut editor = LyricsEditor::from_state(&state).unwrap();
+        assert!(editor.lines.iter().all(|l| l.timestamp_ms == 0));
+        editor.set_text(1, "two!");
+        assert_eq!(editor.lines[1].text, "two!");
+        assert!(LyricsEditor::from_state(&LyricsState::NotFound).is_none());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::audio::dsp::meter::{to_dbfs, MeterFrame};
+use std::time::{Duration, Instant};
+
+/// Peak marker stays put this long before falling
+const PEAK_HOLD: Duration = Duration::from_millis(1500);
+/// Clip light stays on this long after the last over
+const CLIP_HOLD: Duration = Duration::from_secs(3);
+/// Bar fall rate (dB per second)
+const DECAY_DB_PER_SEC: f32 = 24.0;
+/// Anything at or above this true-peak level counts as a clip
+const CLIP_DBFS: f32 = -0.1;
+
+/// Display state for one channel, in dBFS
+#[derive(Debug, Clone, Copy)]
+pub struct ChannelMeter {
+    pub peak_db: f32,
+    pub rms_db: f32,
+    pub hold_db: f32,
+    hold_since: Option<Instant>,
+    clip_until: Option<Instant>,
+}
+
+impl Default for ChannelMeter {
+    fn default() -> Self {
+        Self {
+            peak_db: -90.0,
+            rms_db: -90.0,
+            hold_db: -90.0,
+            hold_since: None,
+            clip_until: None,
+        }
+    }
+}
+
+impl ChannelMeter {
+    pub fn is_clipping(&self, now: Instant) -> bool {
+        self.clip_until.is_some_and(|t| now < t)
+    }
+}
+
+/// Stereo level meters 📶 (UI-side ballistics)
+#[derive(Debug, Clone, Default)]
+pub struct MeterState {
+    pub channels: [ChannelMeter; 2],
+    last_update: Option<Instant>,
+}
+
+impl MeterState {
+    /// Apply a fresh reading (or silence when the stream is idle)
+    pub fn update(&mut self, frame: Option<MeterFrame>, now: Instant) {
+        let dt = self
+            .last_update
+            .map(|t| now.duration_since(t).as_secs_f32())
+            .unwrap_or(0.0)
+            .min(0.25);
+        self.last_update = Some(now);
+        let fall = DECAY_DB_PER_SEC * dt;
+        let frame = frame.unwrap_or_default();
+
+        for (ch, meter) in self.channels.iter_mut().enumerate() {
+            let peak = to_dbfs(frame.peak[ch]);
+            let rms = to_dbfs(frame.rms[ch]);
+
+            meter.peak_db = peak.max(meter.peak_db - fall);
+            meter.rms_db = rms.max(meter.rms_db - fall);
+
+            if peak >= meter.hold_db {
+                meter.hold_db = peak;
+                meter.hold_since = Some(now);
+            } else if meter
+                .hold_since
+                .is_none_or(|t| now.duration_since(t) > PEAK_HOLD)
+            {
+                meter.hold_db = (meter.hold_db - fall).max(peak);
+            }
+
+            if peak >= CLIP_DBFS {
+                meter.clip_until = Some(now + CLIP_HOLD);
+            }
+        }
+    }
+}
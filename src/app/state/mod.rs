@@ -1,35 +1,85 @@
-use crate::app::config::{get_default_presets, EqPreset, PersistentState, UserConfig};
+use crate::app::config::{
+    get_default_presets, EqPreset, PaneState, PersistentState, SessionState, UserConfig,
+};
+use crate::app::events::Retry;
 use crate::app::keys::KeyConfig;
+use crate::app::markers::{self, Marker};
+use crate::app::smart_playlists::SmartPlaylist;
 use crate::audio::device as audio_device;
-use crate::audio::dsp::EqGains;
+use crate::audio::dsp::{DspSetting, EqGains, LevelMeter, PitchSetting, SilenceMonitor};
+use crate::audio::output::OutputSetting;
 use crate::audio::visualizer::Visualizer;
+use crate::error::VyomError;
 use crate::player::{RepeatMode, TrackInfo};
-use crate::ui::theme::Theme;
-use std::collections::HashMap;
-use std::sync::atomic::AtomicUsize;
+use crate::t;
+use crate::ui::theme::{Rgb, Theme, ThemeFade};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, AtomicUsize};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 pub mod artwork;
+pub mod console;
+pub mod frames;
+pub mod journal;
 pub mod library;
 pub mod lyrics;
+pub mod meters;
+pub mod playback;
+pub mod toasts;
 pub mod ui;
 
-pub use artwork::ArtworkState;
-pub use library::{LibraryItem, LibraryItemType, LibraryMode, QueueItem};
-pub use lyrics::LyricsState;
-
-pub use artwork::ArtStyle;
+pub use artwork::{ArtCells, ArtCellsKey, ArtDither, ArtFilter, ArtQuality, ArtworkState};
+pub use console::{ConsoleLineKind, ConsoleState};
+pub use frames::FrameRate;
+pub use journal::{Journal, Operation};
+pub use library::{
+    apply_queue_delta, cue_sheet, queue_rows, queue_selected_row, sort_directory, AlbumEntry,
+    AlbumKey, LibraryItem, LibraryItemType, LibraryMode, QueueGroup, QueueItem, QueueRow,
+};
+pub use lyrics::{LookupStatus, LyricsEditor, LyricsLookup, LyricsSearch, LyricsState};
+pub use meters::{ChannelMeter, MeterState};
+pub use playback::{
+    AbLoop, FinishAlbumMode, Resume, ShuffleMode, SilenceTrim, TrimAction, VolumeHold,
+    VolumeMemory, VolumeMemoryMode,
+};
+pub use toasts::{LoggedToast, Toast, ToastLevel, Toasts};
+
+pub use artwork::{ArtStyle, GraphicsProtocol};
 use ratatui_image::picker::Picker;
 use ratatui_image::protocol::StatefulProtocol;
-pub use ui::{InputMode, InputState, TagEditState, Toast, ViewMode};
+pub use ui::{
+    DetailSection, HelpState, InputMode, InputState, LogView, MissingReport, Palette,
+    SpectrumOverlay, TagEditState, TagField, TagLookup, TagToolAction, TagToolPreview, TagToolRow,
+    TagValue, ThemePicker, TrackDetails, ViewMode, Workspace, WORKSPACES,
+};
 
 pub struct App {
     pub theme: Theme,
+    /// Theme as loaded from its file (before album accents)
+    pub base_theme: Theme,
+    /// Active theme name (None = legacy theme.toml or the default)
+    pub theme_name: Option<String>,
+    /// Choice made in the theme picker, remembered in state.toml
+    pub picked_theme: Option<String>,
+    /// `theme` in config.toml, which a pick is made over
+    pub config_theme: Option<String>,
+    pub theme_picker: Option<ThemePicker>,
+    pub dynamic_theme: bool,
+    pub art_palette: Option<Vec<Rgb>>,
+    pub theme_fade: Option<ThemeFade>,
+    pub show_art_backdrop: bool,
+    /// Scaling and dithering of text artwork
+    pub art_quality: ArtQuality,
+    /// Draw with ASCII only (`ascii_only`, or detected)
+    pub ascii_only: bool,
+    /// Blurred art for the player card, rebuilt on art/size/theme change
+    pub art_backdrop: Option<crate::ui::widgets::player::backdrop::ArtBackdrop>,
     pub keys: KeyConfig, // Store keys for runtime lookup
 
     pub is_running: bool,
-    pub tick_count: u64,
+    /// The bar's marquee steps by time since this
+    pub started: Instant,
     pub track: Option<TrackInfo>,
     pub lyrics: LyricsState, // changed from Option<Vec<LyricLine>>
     pub artwork: ArtworkState,
@@ -37,7 +87,15 @@ pub struct App {
     // Manual Scroll State (None = Auto-sync)
     pub lyrics_offset: Option<usize>,
     pub lyrics_selected: Option<usize>, // Manual selection for j/k navigation
-    pub lyrics_cache: HashMap<String, Vec<crate::app::lyrics::LyricLine>>,
+    pub lyrics_cache: HashMap<String, LyricsState>, // Loaded / Plain only
+    pub lyrics_scroll: u16,             // Scroll row for unsynced lyrics
+    /// Per-track lyrics sync offsets (ms, positive = lyrics later)
+    pub lyrics_offsets: std::collections::BTreeMap<String, i64>,
+    pub lyrics_editor: Option<LyricsEditor>,
+    pub show_romanization: bool,
+    pub lyrics_search: Option<LyricsSearch>,
+    pub lyrics_lookup: Option<LyricsLookup>,
+    pub stream_search: Option<crate::app::stream_search::StreamSearch>,
     pub last_scroll_time: Option<Instant>,
 
     // Seek Accumulation State ⏩
@@ -45,6 +103,10 @@ pub struct App {
     pub last_seek_time: Option<Instant>,
     pub seek_initial_pos: Option<f64>,
     pub seek_id: Arc<AtomicUsize>, // Generation counter for seek requests
+    /// Seek mode: ghost playhead (ms) waiting for Enter
+    pub seek_preview: Option<u64>,
+    /// Where the last `h` / `l` seek went (ms), shown until then
+    pub seek_flash: Option<(u64, Instant)>,
 
     // Animation State 🌊
     pub smooth_scroll_accum: f64,
@@ -54,12 +116,33 @@ pub struct App {
     pub last_volume_action: Option<std::time::Instant>, // Grace period for volume sync 🛡️
 
     pub app_show_lyrics: bool,
-    pub is_tmux: bool,      // Layout logic
+    pub layout: crate::ui::layout::LayoutSpec,
+    /// Pane changes made at runtime (persisted)
+    pub panes: Option<PaneState>,
+    /// Whether the last frame put the panes side by side
+    pub panes_horizontal: bool,
+    /// `--bar`: always the one-line now playing bar
+    pub bar_mode: bool,
+    /// Whether the last frame was the bar (forced, or the terminal is tiny)
+    pub bar_active: bool,
+    pub is_tmux: bool, // Layout logic
+    /// The tmux pane we run in, once claimed
+    pub tmux: Option<crate::app::tmux::TmuxPane>,
     pub is_mpd: bool,       // MPD backend mode
     pub source_app: String, // "MPD", "Spotify", "Apple Music"
+    /// Whether the MPD backend answers (header dot and banner)
+    pub connection: crate::app::connection::ConnectionState,
 
     /// Current panel view mode (Lyrics/Cava/Queue/EQ) 🎛️
     pub view_mode: ViewMode,
+    /// Second panel view in split view (the one without focus)
+    pub split_view: Option<ViewMode>,
+    /// Focused pane is the second one (right / bottom)
+    pub split_focus_second: bool,
+
+    /// Workspaces (Alt+1..4): the others' saved state; the active slot is live in App
+    pub workspaces: [Option<Workspace>; WORKSPACES],
+    pub workspace: usize,
 
     /// MPD Queue (playlist) 📋
     pub queue: Vec<QueueItem>,
@@ -69,14 +152,41 @@ pub struct App {
     pub previous_library_mode: Option<LibraryMode>, // Track previous mode for search exit
     pub library_items: Vec<LibraryItem>,
     pub library_selected: usize,
+    /// Move the queue cursor to each new track as playback advances
+    pub follow_playback: bool,
+    /// Destructive keys and commands are locked (`:party`)
+    pub party_mode: bool,
+    /// First quit press in party mode
+    pub quit_armed: Option<Instant>,
+    /// Album headers in the queue (`group_queue`)
+    pub group_queue: bool,
+    /// Albums whose queue songs are folded under their header
+    pub queue_folded: HashSet<String>,
+    /// Scroll position of the library list, kept between frames
+    pub library_list: ratatui::widgets::ListState,
+    /// List rows on screen last frame (page size for PageUp / PageDown)
+    pub library_page: usize,
+    /// First `g` of `gg` pressed
+    pub pending_g: bool,
     pub browse_path: Vec<String>, // Breadcrumb navigation
+    pub library_art: Option<(String, ArtworkState)>, // Thumbnail for the selected entry
+    pub albums: Vec<AlbumEntry>,
+    pub album_art: HashMap<String, ArtworkState>, // Grid covers by first-track path
+    pub album_open: Option<AlbumKey>,             // Album whose tracks are listed
+    pub album_cols: usize,                        // Grid tiles per row last frame
+    pub album_top_row: usize,                     // First grid row on screen
     pub search_query: String,
     pub search_active: bool,    // Is search input active
-    pub playlists: Vec<String>, // Available playlists
+    pub playlists: Vec<String>, // Available playlists, smart ones first
+    pub smart_playlists: Vec<SmartPlaylist>,
 
     /// Visualizer bars (0.0-1.0 heights) 📊
     pub visualizer_bars: Vec<f32>,
     pub visualizer: Visualizer,
+    /// Holds the audio lock; otherwise the bars come from the master
+    pub is_audio_master: bool,
+    /// Sends EQ, volume and bars to Shared Audio Mode instances
+    pub sync: Option<crate::app::sync::SyncPublisher>,
 
     /// EQ State 🎛️
     /// 10-band EQ: 32Hz, 64Hz, 125Hz, 250Hz, 500Hz, 1kHz, 2kHz, 4kHz, 8kHz, 16kHz
@@ -85,25 +195,76 @@ pub struct App {
     pub eq_selected: usize,
     pub eq_enabled: bool,
     pub eq_preset: usize, // Index into EQ_PRESETS
+    /// A/B Comparison 🅰️🅱️
+    /// The curve that is NOT currently audible (reference B while on A, your EQ while on B)
+    pub eq_ab_other: [f32; 10],
+    pub eq_ab_on_b: bool,
+    pub spectrum_overlay: SpectrumOverlay,
 
     /// Audiophile Controls 🎚️
     /// Internal Volume State (0-100)
     pub app_volume: u8,
-    pub preamp_db: f32,       // -12 to +12 dB
-    pub balance: f32,         // -1.0 (L) to +1.0 (R)
-    pub crossfade_secs: u32,  // MPD crossfade in seconds
-    pub replay_gain_mode: u8, // 0=Off, 1=Track, 2=Album, 3=Auto
+    /// Muted or ducked, with the volume to go back to
+    pub volume_hold: Option<(VolumeHold, u8)>,
+    /// Ducking turns the volume down to this (0-100)
+    pub duck_volume: u8,
+    pub preamp_db: f32,        // -12 to +12 dB
+    pub balance: f32,          // -1.0 (L) to +1.0 (R)
+    pub crossfade_secs: u32,   // MPD crossfade in seconds
+    pub replay_gain_mode: u8,  // 0=Off, 1=Track, 2=Album, 3=Auto
+    pub pitch: PitchSetting,   // Pitch shift in semitones (local pipeline)
+    pub dsp: DspSetting,       // DSP chain order and switches (local pipeline)
+    pub output: OutputSetting, // Output bit depth, dither and rate (local pipeline)
 
     /// UI State
     pub show_keyhints: bool, // WhichKey popup visible
+    pub help: HelpState,
+    pub track_details: Option<TrackDetails>,
+    /// Chapters of the playing file (audiobooks, CUE albums)
+    pub chapters: Vec<crate::app::chapters::Chapter>,
+    /// Chapter list popup: the selected chapter
+    pub chapter_list: Option<usize>,
+    /// Bookmarks in ms, per file (`Ctrl+b`)
+    pub bookmarks: BTreeMap<String, Vec<u64>>,
     pub show_audio_info: bool, // Audio Info popup visible (like Poweramp)
+    pub fullscreen_art: bool,  // Artwork fills the terminal ('f')
     pub tag_edit: Option<TagEditState>,
+    /// `:name2tag` / `:tag2name` dry run
+    pub tag_tool: Option<TagToolPreview>,
+    /// `:missing` report
+    pub missing: Option<MissingReport>,
+    /// `Ctrl+p` jump-to-anything list
+    pub palette: Option<Palette>,
+    /// The whole library listing, so browsing doesn't ask MPD each time
+    pub library_cache: Option<std::sync::Arc<crate::app::library_cache::LibraryCache>>,
+    /// Songs picked with `v` for editing together (paths, in pick order)
+    pub marked: Vec<String>,
     pub input_state: Option<InputState>,
-    pub toast: Option<Toast>,
+    pub console: ConsoleState, // Raw MPD console (advanced)
+    /// Queue and playlist edits `u` can undo
+    pub journal: Journal,
+    pub toasts: Toasts,
+    /// `:messages` popup (scroll offset while open)
+    pub messages_view: Option<usize>,
+    /// `:wrapped` popup
+    pub wrapped: Option<crate::app::wrapped::Wrapped>,
+    /// Log viewer (`F12`)
+    pub log_view: Option<LogView>,
+    /// What `Ctrl+t` re-runs after an error, until when
+    pub retry: Option<(Retry, Instant)>,
     pub gapless_mode: bool, // True when current+next song are from same album
     pub last_album: String, // Track album changes
     pub shuffle: bool,      // MPD random mode
+    pub shuffle_mode: Option<ShuffleMode>, // Last queue shuffle (`Z`)
     pub repeat: RepeatMode, // MPD repeat mode
+    pub finish_album: FinishAlbumMode, // One-shot action at the album boundary
+    pub finish_album_armed: Option<FinishAlbumMode>, // Already set on MPD for the last track
+    pub finish_album_checked: Option<String>, // Track last checked for being the album's last
+    pub silence_trim: SilenceTrim, // Skip dead air at track edges
+    pub ab_loop: AbLoop,    // Repeat part of the track
+    pub resume: Resume,     // Where long tracks were left off
+    pub volume_memory: VolumeMemory, // Volume set by hand per album or track
+    pub listening: crate::app::history::Listening, // The play to add to the history
 
     /// Audio output devices 🔊
     pub output_device: String,
@@ -112,6 +273,12 @@ pub struct App {
 
     /// Shared EQ gains for DSP engine
     pub eq_gains: EqGains,
+    /// Output level meter 📶 (fed by the audio callback) and its display state
+    pub level_meter: LevelMeter,
+    pub meters: MeterState,
+    pub show_level_meter: bool,
+    /// Silence tracker fed by the decode loop ✂️
+    pub silence: SilenceMonitor,
     /// DSP EQ is always available (built-in)
     pub dsp_available: bool,
     // Persistence & Custom Presets
@@ -120,14 +287,57 @@ pub struct App {
 
     /// Music directory for local file operations 📂
     pub music_directory: String,
-
-    /// Persistent MPD Connection 🔌
+    pub artwork_filenames: Vec<String>, // Folder art names, tried before embedded art
+    pub lyrics_dir: Option<std::path::PathBuf>, // Extra .lrc folder
+    pub acoustid_key: Option<String>,   // Fingerprint lookups in the tag editor
+    pub genius_token: Option<String>,   // Song annotations in the Info view
+    pub lastfm_api_key: Option<String>, // Similar artists in the Related view
+    pub playlist_paths: crate::app::playlist_files::PathStyle, // Exported M3U8 paths
+    pub invidious_instance: Option<String>, // Stream search without yt-dlp
+    pub bandcamp_identity: Option<String>, // Bandcamp tab sign-in cookie
+    pub bandcamp_format: String,        // Bandcamp download format
+
+    /// The thread holding the MPD connection 🔌
     #[cfg(feature = "mpd")]
-    pub mpd_client: Option<mpd::Client>,
+    pub mpd: Option<crate::app::mpd_actor::MpdActor>,
 
     /// Image Artwork Rendering 🎨
     pub image_picker: Picker,
     pub image_protocol: Option<StatefulProtocol>,
+    /// The cover being fetched or shown, by album
+    pub art_request: crate::app::requests::Request,
+    /// Lyrics being fetched, by track id
+    pub lyrics_request: crate::app::requests::Request,
+    /// Info view: what's known about the song, and its lookup by song
+    pub about: crate::app::about::AboutState,
+    pub about_scroll: u16,
+    pub about_request: crate::app::requests::Request,
+    /// Related view: artists like the playing one, looked up by artist
+    pub related: crate::app::related::RelatedState,
+    pub related_selected: usize,
+    pub related_request: crate::app::requests::Request,
+    /// Bandcamp tab: the fan's purchases, listed once
+    pub bandcamp: crate::app::bandcamp::CollectionState,
+    pub bandcamp_request: crate::app::requests::Request,
+    /// Streams opened with `:open`, by URL, for their titles
+    pub streams: std::collections::HashMap<String, crate::app::ytdlp::Stream>,
+    /// Downloads and recordings, and the `:downloads` popup's selection
+    pub downloads: crate::app::downloads::Transfers,
+    pub downloads_view: Option<usize>,
+    /// The `:cd` popup
+    pub cd: Option<crate::app::cd::CdView>,
+    /// The `:dsp` popup: the selected stage
+    pub dsp_view: Option<usize>,
+    /// Sliders for a plugin in the chain: plugin index, selected control
+    pub plugin_controls: Option<(usize, usize)>,
+    /// Bumped whenever `artwork` changes, so old cell buffers are ignored
+    pub art_generation: u64,
+    /// Text artwork ready to draw (Block / ASCII / Braille)
+    pub art_cells: Option<ArtCells>,
+    /// What the last frame wanted drawn; the runner builds it
+    pub art_cells_wanted: Option<ArtCellsKey>,
+    /// Being built right now
+    pub art_cells_pending: Option<ArtCellsKey>,
 
     /// Internal standard to track if a popup was just closed
     /// so we can force re-transmit the kitty graphic.
@@ -135,6 +345,10 @@ pub struct App {
 
     /// Reactive Render Flag: Only invoke ratatui terminal.draw() when true.
     pub needs_redraw: bool,
+    /// Tick interval in ms, read by the tick task (see `frame_rate`)
+    pub frame_ms: Arc<AtomicU64>,
+    /// When the last frame was drawn
+    pub last_frame: Instant,
 }
 
 impl App {
@@ -149,6 +363,7 @@ impl App {
     ) -> Self {
         // Merge defaults with user saved presets (from state)
         // If state.presets is empty, it means we don't have custom ones yet, but we should always have defaults available.
+        let lyrics_dir = user_config.lyrics_dir();
         let mut presets = get_default_presets();
         presets.extend(state.presets.clone());
 
@@ -170,12 +385,37 @@ impl App {
             .position(|p| p.name == state.last_preset_name)
             .unwrap_or(0); // Default to first (Custom or Flat)
 
-        let app = Self {
-            theme: crate::ui::theme::load_current_theme(),
+        let picked_theme = state.picked_theme(user_config.theme.as_deref());
+        let theme_name = picked_theme.clone().or(user_config.theme.clone());
+        let (theme, theme_err) = crate::ui::themes::resolve(theme_name.as_deref());
+
+        let mut app = Self {
+            theme: theme.clone(),
+            base_theme: theme,
+            theme_name,
+            picked_theme,
+            config_theme: user_config.theme.clone(),
+            theme_picker: None,
+            dynamic_theme: user_config.dynamic_theme,
+            art_palette: None,
+            theme_fade: None,
+            show_art_backdrop: user_config.art_backdrop,
+            art_quality: ArtQuality {
+                filter: user_config.art_filter,
+                dither: user_config.art_dither.resolve(
+                    std::env::var("COLORTERM").is_ok_and(|c| c == "truecolor" || c == "24bit"),
+                ),
+            },
+            ascii_only: user_config
+                .ascii_only
+                .unwrap_or_else(|| !is_test && crate::ui::ascii::detect(|k| std::env::var(k).ok())),
+            art_backdrop: None,
             keys: user_config.keys.clone(), // Clone keys from user config
             is_running: true,
             needs_redraw: true,
-            tick_count: 0,
+            frame_ms: Arc::new(AtomicU64::new(FrameRate::Idle.interval().as_millis() as u64)),
+            last_frame: Instant::now(),
+            started: Instant::now(),
             track: None,
             lyrics: LyricsState::Idle,
             artwork: ArtworkState::Idle,
@@ -183,30 +423,75 @@ impl App {
             lyrics_offset: None,
             lyrics_selected: None,
             lyrics_cache: HashMap::new(),
+            lyrics_scroll: 0,
+            lyrics_offsets: state.lyrics_offsets.clone(),
+            lyrics_editor: None,
+            show_romanization: user_config.lyrics_romanization,
+            lyrics_search: None,
+            lyrics_lookup: None,
+            stream_search: None,
             last_scroll_time: None,
             seek_accumulator: 0.0,
             last_seek_time: None,
             seek_initial_pos: None,
             seek_id: Arc::new(AtomicUsize::new(0)),
+            seek_preview: None,
+            seek_flash: None,
             smooth_scroll_accum: 0.0,
             last_track_update: None,
             last_volume_action: None,
             app_show_lyrics,
+            layout: {
+                let mut spec = user_config.layout.resolve();
+                if let Some(p) = state.panes {
+                    spec.side_split = p.side_split;
+                    spec.stack_split = p.stack_split;
+                    spec.swapped = p.swapped;
+                }
+                spec
+            },
+            panes: state.panes,
+            panes_horizontal: false,
+            bar_mode: false,
+            bar_active: false,
             is_tmux,
+            tmux: None,
             is_mpd,
             source_app: source_app.to_string(),
+            connection: Default::default(),
             view_mode: ViewMode::default(),
+            split_view: None,
+            split_focus_second: false,
+            workspaces: Default::default(),
+            workspace: 0,
             queue: Vec::new(),
 
             library_mode: LibraryMode::default(),
             previous_library_mode: None,
             library_items: Vec::new(),
             library_selected: 0,
+            follow_playback: false,
+            party_mode: user_config.party_mode,
+            quit_armed: None,
+            group_queue: user_config.group_queue,
+            queue_folded: HashSet::new(),
+            library_list: ratatui::widgets::ListState::default(),
+            library_page: 0,
+            pending_g: false,
             browse_path: Vec::new(),
+            library_art: None,
+            albums: Vec::new(),
+            album_art: HashMap::new(),
+            album_open: None,
+            album_cols: 1,
+            album_top_row: 0,
             search_query: String::new(),
             search_active: false,
             playlists: Vec::new(),
+            smart_playlists: user_config.smart_playlists,
             visualizer_bars: vec![0.0; 60],
+            is_audio_master: true,
+            sync: None,
             visualizer: Visualizer::new(44100), // Default 44.1k, will adapt? Or fixed for vis?
 
             // Persistence loading from STATE
@@ -214,21 +499,64 @@ impl App {
             eq_selected: 0,
             eq_enabled: state.eq_enabled,
             eq_preset: eq_preset_idx,
-            app_volume: state.volume,
+            eq_ab_other: [0.5; 10], // Reference starts flat
+            eq_ab_on_b: false,
+            spectrum_overlay: SpectrumOverlay::default(),
+            app_volume: state.volume.min(user_config.output.max_volume),
+            volume_hold: None,
+            duck_volume: user_config.duck_volume.min(100),
             preamp_db: state.preamp_db,
             balance: state.balance,
             crossfade_secs: state.crossfade,
             replay_gain_mode: state.replay_gain_mode,
-
-            show_keyhints: false,   // Hidden by default
+            pitch: PitchSetting::default(), // Not persisted: always start at concert pitch
+            dsp: DspSetting::new(user_config.dsp.clone()),
+            output: OutputSetting::new(&user_config.output),
+
+            show_keyhints: false, // Hidden by default
+            help: HelpState::default(),
+            track_details: None,
+            chapters: Vec::new(),
+            chapter_list: None,
+            bookmarks: state.bookmarks.clone(),
             show_audio_info: false, // Hidden by default
+            fullscreen_art: false,
             tag_edit: None,
-            input_state: None,   // No input popup active
-            toast: None,         // No toast notification
+            marked: Vec::new(),
+            tag_tool: None,
+            missing: None,
+            palette: None,
+            library_cache: None,
+            input_state: None, // No input popup active
+            console: ConsoleState::default(),
+            journal: Journal::default(),
+            toasts: Toasts::new(std::time::Duration::from_millis(
+                user_config.toast_duration_ms.max(500),
+            )),
+            messages_view: None,
+            wrapped: None,
+            log_view: None,
+            retry: None,
             gapless_mode: false, // No gapless detected initially
             last_album: String::new(),
-            shuffle: false,          // Will be updated from MPD
+            shuffle: false, // Will be updated from MPD
+            shuffle_mode: None,
             repeat: RepeatMode::Off, // Will be updated from MPD
+            finish_album: FinishAlbumMode::Off,
+            finish_album_armed: None,
+            finish_album_checked: None,
+            silence_trim: SilenceTrim::new(user_config.silence_trim),
+            ab_loop: AbLoop::default(),
+            resume: Resume::new(
+                user_config.resume_min_minutes,
+                user_config.resume_auto,
+                state.resume_positions.clone(),
+            ),
+            volume_memory: VolumeMemory::new(
+                user_config.volume_memory,
+                state.track_volumes.clone(),
+            ),
+            listening: crate::app::history::Listening::new(user_config.listening_history),
             output_device: if is_test {
                 "Mock Audio Device".to_string()
             } else {
@@ -253,6 +581,14 @@ impl App {
 
             // Initialization for Persistence fields
             eq_gains: EqGains::default(),
+            level_meter: LevelMeter::new(),
+            meters: MeterState::default(),
+            show_level_meter: user_config.show_level_meter,
+            silence: {
+                let silence = SilenceMonitor::new();
+                silence.set_threshold_db(user_config.silence_threshold_db);
+                silence
+            },
 
             dsp_available: true, // Built-in DSP is always available
 
@@ -261,18 +597,46 @@ impl App {
             eq_preset_name: state.last_preset_name,
 
             music_directory: user_config.music_directory,
+            lyrics_dir,
+            acoustid_key: user_config.acoustid_key,
+            genius_token: user_config.genius_token,
+            lastfm_api_key: user_config.lastfm_api_key,
+            playlist_paths: user_config.playlist_paths,
+            invidious_instance: user_config.invidious_instance,
+            bandcamp_identity: user_config.bandcamp_identity,
+            bandcamp_format: user_config.bandcamp_format,
+            artwork_filenames: user_config.artwork_filenames,
 
             #[cfg(feature = "mpd")]
-            mpd_client: None,
+            mpd: None,
 
             // CRITICAL: We skip from_query_stdio() during tests as it panics in headless CI
             image_picker: if is_test {
                 ratatui_image::picker::Picker::halfblocks()
             } else {
-                ratatui_image::picker::Picker::from_query_stdio()
-                    .unwrap_or_else(|_| ratatui_image::picker::Picker::halfblocks())
+                crate::artwork::ArtworkRenderer::graphics_picker(user_config.art_protocol)
             },
             image_protocol: None,
+            art_request: Default::default(),
+            lyrics_request: Default::default(),
+            about: Default::default(),
+            about_scroll: 0,
+            about_request: Default::default(),
+            related: Default::default(),
+            streams: Default::default(),
+            downloads: Default::default(),
+            downloads_view: None,
+            cd: None,
+            dsp_view: None,
+            plugin_controls: None,
+            related_selected: 0,
+            related_request: Default::default(),
+            bandcamp: Default::default(),
+            bandcamp_request: Default::default(),
+            art_generation: 0,
+            art_cells: None,
+            art_cells_wanted: None,
+            art_cells_pending: None,
             had_popup_last_frame: false,
         };
 
@@ -280,9 +644,46 @@ impl App {
         // Otherwise, we launch with flat EQ despite UI showing "Bass Boost".
         app.sync_eq_to_dsp();
 
+        if let Some(err) = theme_err {
+            app.show_warning(&format!("🎨 {}", err));
+        }
+        if let Some(session) = state.session {
+            app.restore_session(session);
+        }
+
         app
     }
 
+    pub fn is_playing(&self) -> bool {
+        self.track
+            .as_ref()
+            .is_some_and(|t| t.state == crate::player::PlayerState::Playing)
+    }
+
+    /// Lyrics gliding back to the playing line after a manual scroll
+    pub fn lyrics_animating(&self) -> bool {
+        self.last_scroll_time.is_none()
+            && (self.lyrics_offset.is_some() || self.lyrics_selected.is_some())
+    }
+
+    /// How fast to tick for what is on screen right now
+    pub fn frame_rate(&self, now: Instant) -> FrameRate {
+        let live = self.is_visible(ViewMode::Visualizer)
+            || (self.is_visible(ViewMode::EQ) && self.spectrum_overlay != SpectrumOverlay::Off)
+            || self.show_audio_info
+            || self.show_level_meter
+            // Catch the loop end on time
+            || self.ab_loop.range().is_some()
+            || self.sync.as_ref().is_some_and(|s| s.has_subscribers());
+        if self.theme_fade.is_some() || self.lyrics_animating() || self.toasts.animating(now) {
+            FrameRate::Animation
+        } else if live && self.is_playing() {
+            FrameRate::Live
+        } else {
+            FrameRate::Idle
+        }
+    }
+
     pub fn get_current_position_ms(&self) -> u64 {
         if let Some(track) = &self.track {
             if track.state == crate::player::PlayerState::Playing {
@@ -298,6 +699,75 @@ impl App {
         }
     }
 
+    /// Seek mode: park a ghost playhead at the current position
+    pub fn start_seek_mode(&mut self) {
+        if self.track.as_ref().is_some_and(|t| t.duration_ms > 0) {
+            self.seek_preview = Some(self.get_current_position_ms());
+        }
+    }
+
+    /// Where a seek is headed, while seek mode or a recent seek shows it
+    pub fn seek_target(&self) -> Option<u64> {
+        self.seek_preview.or(self.seek_flash.map(|(ms, _)| ms))
+    }
+
+    /// The synced lyric line sung at `ms` (sync offset applied), and the
+    /// one after it
+    pub fn lyrics_at(&self, ms: u64) -> Option<(&str, Option<&str>)> {
+        let LyricsState::Loaded(lines, _) = &self.lyrics else {
+            return None;
+        };
+        let pos = (ms as i64 - self.lyrics_sync_ms()).max(0) as u64;
+        let idx = lines
+            .partition_point(|l| l.timestamp_ms <= pos)
+            .checked_sub(1)?;
+        Some((
+            lines[idx].text.as_str(),
+            lines.get(idx + 1).map(|l| l.text.as_str()),
+        ))
+    }
+
+    /// Move the ghost playhead, clamped to the track
+    pub fn nudge_seek_preview(&mut self, delta_ms: i64) {
+        let duration = self.track.as_ref().map_or(0, |t| t.duration_ms);
+        if let Some(pos) = self.seek_preview.as_mut() {
+            *pos = pos.saturating_add_signed(delta_ms).min(duration);
+        }
+    }
+
+    fn lyrics_sync_key(&self) -> Option<String> {
+        self.track
+            .as_ref()
+            .map(|t| format!("{} - {}", t.artist, t.name))
+    }
+
+    /// Lyrics sync offset for the current track (ms)
+    pub fn lyrics_sync_ms(&self) -> i64 {
+        self.lyrics_sync_key()
+            .and_then(|k| self.lyrics_offsets.get(&k).copied())
+            .unwrap_or(0)
+    }
+
+    /// Shift this track's lyrics by `delta_ms` and remember it. Returns the new offset.
+    pub fn shift_lyrics_sync(&mut self, delta_ms: i64) -> i64 {
+        let Some(key) = self.lyrics_sync_key() else {
+            return 0;
+        };
+        let offset = self.lyrics_sync_ms() + delta_ms;
+        if offset == 0 {
+            self.lyrics_offsets.remove(&key);
+        } else {
+            self.lyrics_offsets.insert(key, offset);
+        }
+        self.save_state();
+        offset
+    }
+
+    /// Playback position as the lyrics see it (sync offset applied)
+    pub fn lyrics_position_ms(&self) -> u64 {
+        (self.get_current_position_ms() as i64 - self.lyrics_sync_ms()).max(0) as u64
+    }
+
     /// Sync EQ bands to DSP engine
     pub fn sync_eq_to_dsp(&self) {
         self.eq_gains.set_all_from_values(&self.eq_bands);
@@ -343,48 +813,601 @@ impl App {
         self.eq_gains.set_enabled(self.eq_enabled);
     }
 
-    /// Cycle through artwork styles (Block -> Ascii -> Braille -> Off) 🎨
+    /// Swap between A (your EQ) and B (stored reference) 🅰️🅱️
+    /// The DSP crossfades between the two curves, so the switch is click-free.
+    pub fn toggle_ab(&mut self) {
+        std::mem::swap(&mut self.eq_bands, &mut self.eq_ab_other);
+        self.eq_ab_on_b = !self.eq_ab_on_b;
+        self.eq_gains.set_all_from_values(&self.eq_bands);
+    }
+
+    /// Store the current A curve as the B reference
+    pub fn store_ab_reference(&mut self) {
+        if self.eq_ab_on_b {
+            self.toggle_ab();
+        }
+        self.eq_ab_other = self.eq_bands;
+    }
+
+    /// Bands of the A side, regardless of which side is audible
+    pub fn eq_bands_a(&self) -> [f32; 10] {
+        if self.eq_ab_on_b {
+            self.eq_ab_other
+        } else {
+            self.eq_bands
+        }
+    }
+
+    /// Expand the artwork to the whole terminal (or back) 🖼️
+    pub fn toggle_fullscreen_art(&mut self) {
+        self.fullscreen_art = !self.fullscreen_art;
+        // Graphics protocols encode for a fixed size; rebuild for the new area
+        self.image_protocol = None;
+    }
+
+    /// Cycle through artwork styles (Auto -> Block -> Ascii -> Braille -> Image -> Off) 🎨
     pub fn cycle_art_style(&mut self) {
         self.art_style = match self.art_style {
+            ArtStyle::Auto => ArtStyle::Block,
             ArtStyle::Block => ArtStyle::Ascii,
             ArtStyle::Ascii => ArtStyle::Braille,
             ArtStyle::Braille => ArtStyle::Image,
             ArtStyle::Image => ArtStyle::Off,
-            ArtStyle::Off => ArtStyle::Block,
+            ArtStyle::Off => ArtStyle::Auto,
         };
+        self.image_protocol = None;
         self.save_state();
-        self.show_toast(&format!("🎨 Art Style: {:?}", self.art_style));
+        let msg = match self.art_style {
+            ArtStyle::Auto | ArtStyle::Image => t!(
+                "toast.art_style",
+                style = format!(
+                    "{:?} ({:?})",
+                    self.art_style,
+                    self.image_picker.protocol_type()
+                )
+            ),
+            style => t!("toast.art_style", style = format!("{:?}", style)),
+        };
+        self.show_toast(&msg);
+    }
+
+    /// Whether the terminal can show real pixels (kitty / iTerm2 / sixel)
+    pub fn has_graphics(&self) -> bool {
+        self.image_picker.protocol_type() != ratatui_image::picker::ProtocolType::Halfblocks
+    }
+
+    /// Art style after resolving `Auto`; `Image` falls back to half-blocks
+    /// on terminals without a graphics protocol, everything to ASCII art in
+    /// ASCII-only mode
+    pub fn effective_art_style(&self) -> ArtStyle {
+        match self.art_style {
+            ArtStyle::Off => ArtStyle::Off,
+            _ if self.ascii_only => ArtStyle::Ascii,
+            ArtStyle::Auto | ArtStyle::Image if self.has_graphics() => ArtStyle::Image,
+            ArtStyle::Auto | ArtStyle::Image => ArtStyle::Block,
+            style => style,
+        }
+    }
+
+    /// The Playlists tab: smart playlists first, then the saved ones that
+    /// aren't one of them (a smart playlist is saved under its own name)
+    pub fn set_playlists(&mut self, saved: impl IntoIterator<Item = String>) {
+        let mut names: Vec<String> = self
+            .smart_playlists
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+        names.extend(
+            saved
+                .into_iter()
+                .filter(|name| self.smart_playlist(name).is_none()),
+        );
+        self.playlists = names;
+    }
+
+    /// Pick or unpick a song for batch tag editing
+    pub fn toggle_mark(&mut self, path: &str) {
+        match self.marked.iter().position(|p| p == path) {
+            Some(at) => {
+                self.marked.remove(at);
+            }
+            None => self.marked.push(path.to_string()),
+        }
+    }
+
+    pub fn is_marked(&self, path: &str) -> bool {
+        self.marked.iter().any(|p| p == path)
+    }
+
+    /// What the library tools work on: the marked songs, or else every
+    /// song in the list on screen
+    pub fn target_songs(&self) -> Vec<String> {
+        if !self.marked.is_empty() {
+            return self.marked.clone();
+        }
+        match self.library_mode {
+            LibraryMode::Queue => self.queue.iter().map(|q| q.file_path.clone()).collect(),
+            _ => self
+                .library_items
+                .iter()
+                .filter(|item| item.item_type == LibraryItemType::Song)
+                .filter_map(|item| item.path.clone())
+                .collect(),
+        }
+    }
+
+    pub fn smart_playlist(&self, name: &str) -> Option<&SmartPlaylist> {
+        self.smart_playlists.iter().find(|s| s.name == name)
+    }
+
+    /// In party mode: say the action is locked and return true
+    pub fn party_locked(&mut self) -> bool {
+        if self.party_mode {
+            self.show_warning(&t!("party.locked"));
+        }
+        self.party_mode
+    }
+
+    /// Stop, or in party mode only on a second press shortly after the first
+    pub fn request_quit(&mut self, now: Instant) {
+        const CONFIRM_WITHIN: Duration = Duration::from_secs(3);
+        let confirmed = self
+            .quit_armed
+            .is_some_and(|at| now.duration_since(at) < CONFIRM_WITHIN);
+        if self.party_mode && !confirmed {
+            self.quit_armed = Some(now);
+            let key = self.keys.display(&self.keys.quit);
+            self.show_warning(&t!("party.quit_again", key = key));
+            return;
+        }
+        self.is_running = false;
     }
 
     pub fn show_toast(&mut self, message: &str) {
-        let now = Instant::now();
-        let duration = std::time::Duration::from_millis(2000); // 2s display time
-        let deadline = now + duration;
-
-        if let Some(ref mut current) = self.toast {
-            // Intelligent Update:
-            // If previous toast is recent (less than 500ms old) OR still active,
-            // update message and extend deadline, but keep start_time to preserve "Entrance" state.
-            // This prevents the "flashing" animation on rapid updates.
-            current.message = message.to_string();
-            current.deadline = deadline;
-            // start_time is INTENTIONALLY left alone!
-        } else {
-            // New Toast
-            self.toast = Some(Toast {
-                message: message.to_string(),
-                start_time: now,
-                deadline,
-            });
+        self.notify(ToastLevel::Info, message);
+    }
+
+    pub fn show_warning(&mut self, message: &str) {
+        self.notify(ToastLevel::Warn, message);
+    }
+
+    pub fn show_error(&mut self, message: &str) {
+        self.notify(ToastLevel::Error, message);
+    }
+
+    /// Error toast for `error`; a retry stays armed while the toast is up
+    pub fn report(&mut self, error: &VyomError, retry: Option<Retry>) {
+        let message = error.user_message();
+        match retry {
+            Some(retry) => {
+                let key = self.keys.display(&self.keys.retry);
+                self.show_error(&t!("toast.retry_hint", message = message, key = key));
+                self.retry = Some((retry, Instant::now() + self.toasts.duration * 2));
+            }
+            None => {
+                self.show_error(&message);
+                self.retry = None;
+            }
         }
     }
 
+    /// The armed retry, if its toast is still showing
+    pub fn take_retry(&mut self) -> Option<Retry> {
+        self.retry
+            .take()
+            .filter(|(_, until)| Instant::now() <= *until)
+            .map(|(retry, _)| retry)
+    }
+
+    pub fn notify(&mut self, level: ToastLevel, message: &str) {
+        self.toasts.push(level, message, Instant::now());
+    }
+
     /// Called every tick to update state
     pub fn on_tick(&mut self) {
-        // Handle Toast Expiry
-        if let Some(ref toast) = self.toast {
-            if Instant::now() > toast.deadline {
-                self.toast = None;
+        let now = Instant::now();
+        self.toasts.expire(now);
+        if self.seek_flash.is_some_and(|(_, until)| now >= until) {
+            self.seek_flash = None;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Fade the accents to a new album palette (`None` = back to the base theme)
+    pub fn apply_art_palette(&mut self, palette: Option<Vec<Rgb>>) {
+        if !self.dynamic_theme || palette == self.art_palette {
+            return;
+        }
+        let target = match &palette {
+            Some(colors) => self.base_theme.with_accents(colors),
+            None => self.base_theme.clone(),
+        };
+        self.art_palette = palette;
+        self.theme_fade = Some(ThemeFade::new(self.theme.clone(), target, Instant::now()));
+    }
+
+    /// theme.toml changed: keep album accents on top of the new base
+    pub fn set_base_theme(&mut self, theme: Theme) {
+        self.theme_fade = None;
+        self.theme = match (&self.art_palette, self.dynamic_theme) {
+            (Some(colors), true) => theme.with_accents(colors),
+            _ => theme.clone(),
+        };
+        self.base_theme = theme;
+    }
+
+    /// Switch to a theme by name (None = legacy theme.toml / default)
+    pub fn apply_theme(&mut self, name: Option<String>) {
+        let (theme, err) = crate::ui::themes::resolve(name.as_deref());
+        if let Some(err) = err {
+            self.show_warning(&format!("🎨 {}", err));
+        }
+        self.theme_name = name;
+        self.set_base_theme(theme);
+    }
+
+    /// Whether a view is on screen (focused or in the other split pane)
+    pub fn is_visible(&self, view: ViewMode) -> bool {
+        self.view_mode == view || self.split_view == Some(view)
+    }
+
+    /// Panel views in screen order (first, second)
+    pub fn panel_views(&self) -> (ViewMode, Option<ViewMode>) {
+        match self.split_view {
+            Some(other) if self.split_focus_second => (other, Some(self.view_mode)),
+            other => (self.view_mode, other),
+        }
+    }
+
+    /// Switch the focused pane to `view` (or just focus it if the other pane shows it)
+    pub fn show_view(&mut self, view: ViewMode) {
+        if self.split_view == Some(view) {
+            self.focus_other_pane();
+        } else {
+            self.view_mode = view;
+        }
+    }
+
+    pub fn focus_other_pane(&mut self) {
+        if let Some(other) = self.split_view {
+            self.split_view = Some(self.view_mode);
+            self.view_mode = other;
+            self.split_focus_second = !self.split_focus_second;
+        }
+    }
+
+    /// Open split view with the first of `candidates` that isn't focused, or close it
+    pub fn toggle_split_view(&mut self, candidates: &[ViewMode]) {
+        if self.split_view.take().is_some() {
+            self.split_focus_second = false;
+            return;
+        }
+        match candidates.iter().find(|&&v| v != self.view_mode) {
+            Some(&other) => {
+                self.split_view = Some(other);
+                self.split_focus_second = false;
+            }
+            None => self.show_toast(&t!("toast.split_needs_view")),
+        }
+    }
+
+    /// Park the current views in their workspace and bring up workspace `n`.
+    /// A workspace that was never used starts as a copy of the current one.
+    pub fn switch_workspace(&mut self, n: usize) {
+        if n == self.workspace || n >= WORKSPACES {
+            return;
+        }
+        let current = Workspace {
+            view_mode: self.view_mode,
+            library_mode: self.library_mode,
+            previous_library_mode: self.previous_library_mode,
+            library_items: std::mem::take(&mut self.library_items),
+            library_selected: self.library_selected,
+            browse_path: std::mem::take(&mut self.browse_path),
+            search_query: std::mem::take(&mut self.search_query),
+            album_open: self.album_open.take(),
+        };
+        let next = self.workspaces[n].take().unwrap_or_else(|| current.clone());
+        self.workspaces[self.workspace] = Some(current);
+        self.workspace = n;
+
+        // The split pane can't show what the new focus already shows
+        if self.split_view == Some(next.view_mode) {
+            self.split_view = Some(self.view_mode);
+        }
+        self.view_mode = next.view_mode;
+        self.library_mode = next.library_mode;
+        self.previous_library_mode = next.previous_library_mode;
+        self.library_items = next.library_items;
+        self.library_selected = next.library_selected;
+        self.browse_path = next.browse_path;
+        self.search_query = next.search_query;
+        self.album_open = next.album_open;
+        self.search_active = false;
+        self.pending_g = false;
+    }
+
+    /// What `state.toml` keeps of the views for the next start
+    pub fn session(&self) -> SessionState {
+        SessionState {
+            view_mode: self.view_mode,
+            split_view: self.split_view,
+            library_mode: self.library_mode,
+            browse_path: self.browse_path.clone(),
+            library_selected: self.library_selected,
+            search_query: self.search_query.clone(),
+            album_open: self.album_open.as_ref().map(|a| a.name.clone()),
+            album_open_artist: self
+                .album_open
+                .as_ref()
+                .map(|a| a.artist.clone())
+                .unwrap_or_default(),
+            eq_selected: self.eq_selected,
+        }
+    }
+
+    /// Go back to a saved session. The library lists themselves are
+    /// fetched again once MPD is up (`inputs::library::restore_library`).
+    pub fn restore_session(&mut self, session: SessionState) {
+        // Library, Visualizer and EQ only exist with MPD
+        let available = |view: ViewMode| self.is_mpd || view == ViewMode::Lyrics;
+        if available(session.view_mode) {
+            self.view_mode = session.view_mode;
+        }
+        self.split_view = session
+            .split_view
+            .filter(|v| available(*v) && *v != self.view_mode);
+        self.library_mode = session.library_mode;
+        self.browse_path = session.browse_path;
+        self.library_selected = session.library_selected;
+        self.search_query = session.search_query;
+        self.album_open = session.album_open.map(|name| AlbumKey {
+            artist: session.album_open_artist,
+            name,
+        });
+        self.eq_selected = session.eq_selected.min(9);
+    }
+
+    /// Playing track's MPD path (relative to the music directory)
+    pub fn playing_file(&self) -> Option<&str> {
+        let path = self.track.as_ref()?.file_path.as_deref()?;
+        Some(
+            path.strip_prefix(self.music_directory.as_str())
+                .map(|p| p.trim_start_matches('/'))
+                .unwrap_or(path),
+        )
+    }
+
+    /// Chapter playing now
+    pub fn current_chapter(&self) -> Option<usize> {
+        crate::app::chapters::current(&self.chapters, self.get_current_position_ms())
+    }
+
+    /// Bookmarks, chapters and lyric sections of the playing track
+    pub fn markers(&self) -> Vec<Marker> {
+        let sections = match &self.lyrics {
+            LyricsState::Loaded(lines, _) => markers::sections(lines, self.lyrics_sync_ms()),
+            _ => Vec::new(),
+        };
+        let bookmarks = self
+            .track
+            .as_ref()
+            .and_then(|t| t.file_path.as_ref())
+            .and_then(|file| self.bookmarks.get(file));
+        markers::collect(
+            bookmarks.map_or(&[][..], |b| b.as_slice()),
+            &self.chapters,
+            sections,
+        )
+    }
+
+    /// Bookmark the playhead, or remove the bookmark right there. Some(true)
+    /// when one was added, None without a playing file.
+    pub fn toggle_bookmark(&mut self) -> Option<bool> {
+        let pos = self.get_current_position_ms();
+        let file = self.track.as_ref()?.file_path.clone()?;
+        let list = self.bookmarks.entry(file.clone()).or_default();
+        let added = match list
+            .iter()
+            .position(|&ms| ms.abs_diff(pos) <= markers::BOOKMARK_SNAP_MS)
+        {
+            Some(i) => {
+                list.remove(i);
+                false
+            }
+            None => {
+                let at = list.partition_point(|&ms| ms < pos);
+                list.insert(at, pos);
+                true
+            }
+        };
+        if list.is_empty() {
+            self.bookmarks.remove(&file);
+        }
+        Some(added)
+    }
+
+    /// Queue position of the playing track
+    pub fn playing_queue_index(&self) -> Option<usize> {
+        let file = self.playing_file();
+        // MPD's flag is exact (duplicates), but only refreshed when the queue changes
+        self.queue
+            .iter()
+            .position(|q| q.is_current && Some(q.file_path.as_str()) == file)
+            .or_else(|| file.and_then(|f| self.queue.iter().position(|q| q.file_path == f)))
+            .or_else(|| self.queue.iter().position(|q| q.is_current))
+    }
+
+    /// Playing queue position, time left to the end of the queue and its
+    /// length (ms); everything is left when nothing from it plays
+    pub fn queue_time(&self) -> (Option<usize>, u64, u64) {
+        let total: u64 = self.queue.iter().map(|q| q.duration_ms).sum();
+        let Some(idx) = self.track.as_ref().and(self.playing_queue_index()) else {
+            return (None, total, total);
+        };
+        let played = self.queue[..idx].iter().map(|q| q.duration_ms).sum::<u64>()
+            + self
+                .get_current_position_ms()
+                .min(self.queue[idx].duration_ms);
+        (Some(idx), total.saturating_sub(played), total)
+    }
+
+    /// A new track started: move the queue marker, and the cursor when following
+    pub fn on_track_changed(&mut self) {
+        // A ghost playhead from the last song means nothing now
+        self.seek_preview = None;
+        self.seek_flash = None;
+        let Some(idx) = self.playing_queue_index() else {
+            return;
+        };
+        for (i, item) in self.queue.iter_mut().enumerate() {
+            item.is_current = i == idx;
+        }
+        if self.follow_playback && self.library_mode == LibraryMode::Queue {
+            self.library_selected = idx;
+            self.center_library_selection();
+        }
+    }
+
+    /// Scroll the library list so the selection sits in the middle
+    pub fn center_library_selection(&mut self) {
+        let row = if self.library_mode == LibraryMode::Queue {
+            queue_selected_row(&self.queue_rows(), self.library_selected)
+        } else {
+            self.library_selected
+        };
+        *self.library_list.offset_mut() = row.saturating_sub(self.library_page / 2);
+    }
+
+    /// Queue rows as listed: with album headers if `group_queue` is on
+    pub fn queue_rows(&self) -> Vec<QueueRow> {
+        if self.group_queue {
+            queue_rows(&self.queue, &self.queue_folded)
+        } else {
+            (0..self.queue.len()).map(QueueRow::Song).collect()
+        }
+    }
+
+    /// Fold or unfold the album of the selected queue song. False when it
+    /// isn't under a header.
+    pub fn toggle_queue_fold(&mut self) -> bool {
+        let selected = self.library_selected;
+        let group = self.queue_rows().into_iter().find_map(|row| match row {
+            QueueRow::Album(group) if group.contains(selected) => Some(group),
+            _ => None,
+        });
+        let Some(group) = group else {
+            return false;
+        };
+        if !self.queue_folded.remove(&group.album) {
+            self.queue_folded.insert(group.album);
+            self.library_selected = group.start;
+        }
+        true
+    }
+
+    /// Entries in the current library tab
+    pub fn library_len(&self) -> usize {
+        match self.library_mode {
+            LibraryMode::Queue => self.queue.len(),
+            LibraryMode::Playlists => self.playlists.len(),
+            LibraryMode::Bandcamp => self.bandcamp.purchases().len(),
+            LibraryMode::Albums if self.album_open.is_none() => self.albums.len(),
+            LibraryMode::Directory | LibraryMode::Albums | LibraryMode::Search => {
+                self.library_items.len()
+            }
+        }
+    }
+
+    /// Move the library selection by `delta` rows, stopping at either end
+    pub fn move_library_selection(&mut self, delta: isize) {
+        if self.library_mode == LibraryMode::Queue && !self.queue_folded.is_empty() {
+            // Folded albums are one stop, at their first song
+            let stops: Vec<usize> = self
+                .queue_rows()
+                .iter()
+                .filter_map(|row| match row {
+                    QueueRow::Song(idx) => Some(*idx),
+                    QueueRow::Album(group) if group.folded => Some(group.start),
+                    QueueRow::Album(_) => None,
+                })
+                .collect();
+            let at = stops
+                .iter()
+                .rposition(|&stop| stop <= self.library_selected)
+                .unwrap_or(0);
+            let to = at
+                .saturating_add_signed(delta)
+                .min(stops.len().saturating_sub(1));
+            if let Some(&stop) = stops.get(to) {
+                self.library_selected = stop;
+            }
+            return;
+        }
+        let last = self.library_len().saturating_sub(1);
+        self.library_selected = self.library_selected.saturating_add_signed(delta).min(last);
+    }
+
+    /// Move the card / panel divider (percent, towards right / bottom)
+    pub fn nudge_panes(&mut self, delta: i16) {
+        let share = self.layout.nudge_split(self.panes_horizontal, delta);
+        self.remember_panes();
+        self.show_toast(&t!("toast.player_share", percent = share));
+    }
+
+    pub fn swap_panes(&mut self) {
+        self.layout.swapped = !self.layout.swapped;
+        self.remember_panes();
+    }
+
+    fn remember_panes(&mut self) {
+        self.panes = Some(PaneState {
+            side_split: self.layout.side_split,
+            stack_split: self.layout.stack_split,
+            swapped: self.layout.swapped,
+        });
+        self.save_state();
+    }
+
+    /// Open the theme gallery on the active theme
+    pub fn open_theme_picker(&mut self) {
+        let entries = crate::ui::themes::available()
+            .into_iter()
+            .filter_map(|name| {
+                let theme = crate::ui::themes::load(&name).ok()?;
+                Some((name, theme))
+            })
+            .collect();
+        let current = self
+            .theme_name
+            .as_deref()
+            .unwrap_or(crate::ui::themes::DEFAULT_THEME);
+        self.theme_picker = Some(ThemePicker::new(entries, current, self.base_theme.clone()));
+    }
+
+    /// Close the gallery, keeping the previewed theme or restoring the old one
+    pub fn close_theme_picker(&mut self, keep: bool) {
+        let Some(picker) = self.theme_picker.take() else {
+            return;
+        };
+        match picker.current() {
+            Some((name, _)) if keep => {
+                self.theme_name = Some(name.clone());
+                self.picked_theme = Some(name.clone());
+                self.save_state();
+                self.show_toast(&t!("toast.theme", name = name));
+            }
+            _ => self.set_base_theme(picker.original),
+        }
+    }
+
+    /// Advance an in-progress theme fade
+    pub fn tick_theme_fade(&mut self, now: Instant) {
+        if let Some(fade) = &self.theme_fade {
+            let (theme, done) = fade.sample(now);
+            self.theme = theme;
+            if done {
+                self.theme_fade = None;
             }
         }
     }
@@ -452,6 +1475,23 @@ impl App {
         self.save_state();
     }
 
+    /// Add an imported preset, replacing one with the same name, and switch
+    /// to it
+    pub fn import_preset(&mut self, preset: EqPreset) {
+        match self.presets.iter().position(|p| p.name == preset.name) {
+            Some(pos) => {
+                self.presets[pos] = preset;
+                self.eq_preset = pos;
+            }
+            None => {
+                self.presets.push(preset);
+                self.eq_preset = self.presets.len() - 1;
+            }
+        }
+        self.apply_preset();
+        self.save_state();
+    }
+
     /// Delete current preset (if not a builtin)
     pub fn delete_preset(&mut self) -> Result<(), String> {
         if self.eq_preset < self.presets.len() {
@@ -460,7 +1500,7 @@ impl App {
             // Prevent deleting defaults
             let defaults = get_default_presets();
             if defaults.iter().any(|d| d.name == name) {
-                return Err("Cannot delete built-in preset".to_string());
+                return Err(t!("error.builtin_preset"));
             }
 
             self.presets.remove(self.eq_preset);
@@ -472,7 +1512,7 @@ impl App {
             self.save_state();
             return Ok(());
         }
-        Err("No preset selected".to_string())
+        Err(t!("error.no_preset"))
     }
 
     pub fn save_state(&self) {
@@ -491,14 +1531,23 @@ impl App {
         let state = PersistentState {
             last_preset_name: self.eq_preset_name.clone(),
             eq_enabled: self.eq_enabled,
-            eq_bands: self.eq_bands,
+            eq_bands: self.eq_bands_a(),
             preamp_db: self.preamp_db,
             balance: self.balance,
             crossfade: self.crossfade_secs,
             replay_gain_mode: self.replay_gain_mode,
-            volume: self.app_volume,
+            // Muted or ducked, the volume it comes back to
+            volume: self.volume_hold.map_or(self.app_volume, |(_, v)| v),
             presets: clean_presets,
             art_style: self.art_style,
+            lyrics_offsets: self.lyrics_offsets.clone(),
+            resume_positions: self.resume.positions.clone(),
+            track_volumes: self.volume_memory.volumes.clone(),
+            bookmarks: self.bookmarks.clone(),
+            theme: self.picked_theme.clone(),
+            theme_config: self.config_theme.clone(),
+            panes: self.panes,
+            session: Some(self.session()),
         };
         state.save();
     }
@@ -515,7 +1564,7 @@ impl App {
             // Actually switch the system audio output
             if audio_device::switch_audio_device(&device_name) {
                 self.output_device = device_name.clone();
-                self.show_toast(&format!("🎧 Device: {}", device_name));
+                self.show_toast(&t!("toast.device", name = device_name));
             }
         }
     }
@@ -535,7 +1584,7 @@ impl App {
             // Actually switch the system audio output
             if audio_device::switch_audio_device(&device_name) {
                 self.output_device = device_name.clone();
-                self.show_toast(&format!("🎧 Device: {}", device_name));
+                self.show_toast(&t!("toast.device", name = device_name));
             }
         }
     }
@@ -623,4 +1672,9 @@ impl App {
             _ => 0,
         };
     }
+
+    /// Shift pitch by `delta` semitones (tempo unchanged)
+    pub fn shift_pitch(&mut self, delta: i32) {
+        self.pitch.set(self.pitch.get() + delta);
+    }
 }
@@ -0,0 +1,529 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// What to do once the current album finishes 💿
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FinishAlbumMode {
+    #[default]
+    Off,
+    /// Stop after the album's last track
+    Stop,
+    /// Turn on shuffle before the next album would start
+    Shuffle,
+}
+
+impl FinishAlbumMode {
+    pub fn next(self) -> Self {
+        match self {
+            FinishAlbumMode::Off => FinishAlbumMode::Stop,
+            FinishAlbumMode::Stop => FinishAlbumMode::Shuffle,
+            FinishAlbumMode::Shuffle => FinishAlbumMode::Off,
+        }
+    }
+}
+
+/// Queue shuffles beyond MPD's random mode (`Z` cycles them) 🔀
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShuffleMode {
+    /// Albums in random order, tracks in album order
+    Albums,
+    /// Higher rated / more played songs tend to come first
+    Weighted,
+    /// Never the same artist twice in a row
+    ArtistSpread,
+}
+
+impl ShuffleMode {
+    /// The mode after `current` (the first one when none was used yet)
+    pub fn after(current: Option<Self>) -> Self {
+        match current {
+            None | Some(ShuffleMode::ArtistSpread) => ShuffleMode::Albums,
+            Some(ShuffleMode::Albums) => ShuffleMode::Weighted,
+            Some(ShuffleMode::Weighted) => ShuffleMode::ArtistSpread,
+        }
+    }
+}
+
+/// Consecutive silence needed before skipping a track's tail
+const TRAIL_MIN_SILENCE: Duration = Duration::from_secs(2);
+/// Silence at the start of a track that triggers a skip ahead
+const LEAD_MIN_SILENCE: Duration = Duration::from_millis(500);
+/// Only look for leading silence this far into a track
+const LEAD_WINDOW_MS: u64 = 30_000;
+/// Each leading-silence skip moves this far forward
+const LEAD_STEP_MS: u64 = 1000;
+/// Give MPD and the pipeline time to settle between actions
+const ACTION_COOLDOWN: Duration = Duration::from_secs(1);
+
+/// What the silence trimmer wants MPD to do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrimAction {
+    /// Seek forward past leading silence (absolute position in seconds)
+    SkipAhead(f64),
+    /// Trailing silence: go to the next track
+    Next,
+}
+
+/// Leading/trailing silence trimming ✂️
+#[derive(Debug, Clone, Default)]
+pub struct SilenceTrim {
+    /// Global setting (`silence_trim` in config.toml)
+    pub enabled: bool,
+    /// Per-track toggle; cleared when the track changes
+    pub track_override: bool,
+    /// Still waiting for the first sound of the current track
+    lead_armed: bool,
+    last_action: Option<Instant>,
+}
+
+impl SilenceTrim {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    /// Whether trimming applies to the current track
+    pub fn is_active(&self) -> bool {
+        self.enabled != self.track_override
+    }
+
+    /// Flip trimming for the current track only; returns the new state
+    pub fn toggle_track(&mut self) -> bool {
+        self.track_override = !self.track_override;
+        self.is_active()
+    }
+
+    pub fn on_track_change(&mut self) {
+        self.track_override = false;
+        self.lead_armed = true;
+    }
+
+    /// Decide whether to skip, given the playback position and the current
+    /// silence run reported by the pipeline
+    pub fn check(
+        &mut self,
+        position_ms: u64,
+        duration_ms: u64,
+        playing: bool,
+        silent_for: Duration,
+        now: Instant,
+    ) -> Option<TrimAction> {
+        if !self.is_active() || !playing || duration_ms == 0 {
+            return None;
+        }
+        if self
+            .last_action
+            .is_some_and(|t| now.duration_since(t) < ACTION_COOLDOWN)
+        {
+            return None;
+        }
+
+        let mut action = None;
+        if self.lead_armed {
+            if position_ms > LEAD_WINDOW_MS {
+                self.lead_armed = false;
+            } else if silent_for >= LEAD_MIN_SILENCE {
+                let target = (position_ms + LEAD_STEP_MS).min(duration_ms);
+                action = Some(TrimAction::SkipAhead(target as f64 / 1000.0));
+            } else if silent_for < Duration::from_millis(50) && position_ms >= 500 {
+                // The music has started
+                self.lead_armed = false;
+            }
+        } else if position_ms * 2 >= duration_ms && silent_for >= TRAIL_MIN_SILENCE {
+            // Only the second half, so quiet passages early on are left alone
+            action = Some(TrimAction::Next);
+        }
+
+        if action.is_some() {
+            self.last_action = Some(now);
+        }
+        action
+    }
+}
+
+/// Time between loop jumps, so a position report from before the seek
+/// doesn't jump again
+const LOOP_COOLDOWN: Duration = Duration::from_millis(500);
+
+/// A-B repeat for practicing a part 🔁
+#[derive(Debug, Clone, Default)]
+pub struct AbLoop {
+    /// Loop start (ms)
+    pub a: Option<u64>,
+    /// Loop end (ms); looping starts once it's set
+    pub b: Option<u64>,
+    last_jump: Option<Instant>,
+}
+
+impl AbLoop {
+    /// One key does it all: set A, then B, then clear
+    pub fn mark(&mut self, position_ms: u64) {
+        match (self.a, self.b) {
+            (None, _) => self.a = Some(position_ms),
+            (Some(a), None) => {
+                // B set before A: loop what lies between them anyway
+                self.a = Some(a.min(position_ms));
+                self.b = Some(a.max(position_ms));
+            }
+            (Some(_), Some(_)) => self.clear(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Start and end while looping
+    pub fn range(&self) -> Option<(u64, u64)> {
+        Some((self.a?, self.b?)).filter(|(a, b)| b > a)
+    }
+
+    /// Where to seek back to (ms) once playback reaches B
+    pub fn check(&mut self, position_ms: u64, playing: bool, now: Instant) -> Option<u64> {
+        let (a, b) = self.range()?;
+        if !playing || position_ms < b {
+            return None;
+        }
+        if self
+            .last_jump
+            .is_some_and(|t| now.duration_since(t) < LOOP_COOLDOWN)
+        {
+            return None;
+        }
+        self.last_jump = Some(now);
+        Some(a)
+    }
+}
+
+/// Positions near the start aren't worth resuming, and neither are ones
+/// this close to the end (the track was finished)
+const RESUME_MARGIN_MS: u64 = 30_000;
+/// How often remembered positions are written to `state.toml`
+const RESUME_SAVE_EVERY: Duration = Duration::from_secs(30);
+
+/// Where long tracks (audiobooks, DJ mixes) were left off ⏯️
+#[derive(Debug, Clone, Default)]
+pub struct Resume {
+    /// Tracks at least this long are remembered (ms); 0 = off
+    pub min_ms: u64,
+    /// Seek there on start instead of offering it
+    pub auto: bool,
+    /// File -> position (ms), saved in `state.toml`
+    pub positions: BTreeMap<String, u64>,
+    /// Position offered for the playing track (`auto` off)
+    pub offer: Option<u64>,
+    dirty: bool,
+    saved_at: Option<Instant>,
+}
+
+impl Resume {
+    pub fn new(min_minutes: u32, auto: bool, positions: BTreeMap<String, u64>) -> Self {
+        Self {
+            min_ms: min_minutes as u64 * 60_000,
+            auto,
+            positions,
+            ..Default::default()
+        }
+    }
+
+    fn is_long(&self, duration_ms: u64) -> bool {
+        self.min_ms > 0 && duration_ms >= self.min_ms
+    }
+
+    /// Note the position of the playing track (every track update)
+    pub fn record(&mut self, file: &str, position_ms: u64, duration_ms: u64) {
+        if !self.is_long(duration_ms) || position_ms < RESUME_MARGIN_MS {
+            return;
+        }
+        if position_ms + RESUME_MARGIN_MS >= duration_ms {
+            self.dirty |= self.positions.remove(file).is_some();
+        } else if self.positions.insert(file.to_string(), position_ms) != Some(position_ms) {
+            self.dirty = true;
+        }
+    }
+
+    /// A track started at `position_ms`: where it was left off, if that's
+    /// further in
+    pub fn on_track_change(
+        &mut self,
+        file: &str,
+        position_ms: u64,
+        duration_ms: u64,
+    ) -> Option<u64> {
+        self.offer = None;
+        if !self.is_long(duration_ms) || position_ms >= RESUME_MARGIN_MS {
+            // Already somewhere in the middle (the server kept its place)
+            return None;
+        }
+        self.positions.get(file).copied()
+    }
+
+    /// Whether it's time to write the positions out
+    pub fn should_save(&mut self, now: Instant) -> bool {
+        if !self.dirty
+            || self
+                .saved_at
+                .is_some_and(|t| now.duration_since(t) < RESUME_SAVE_EVERY)
+        {
+            return false;
+        }
+        self.dirty = false;
+        self.saved_at = Some(now);
+        true
+    }
+}
+
+/// Volume held down for a while, to come back to 🔇
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeHold {
+    Muted,
+    /// Down to `duck_volume`, for a conversation
+    Ducked,
+}
+
+/// What a remembered volume belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VolumeMemoryMode {
+    #[default]
+    Off,
+    Album,
+    Track,
+}
+
+/// Volume set by hand for an album or track, put back when it plays again
+/// 🔊, for libraries mastered all over the place without ReplayGain tags
+#[derive(Debug, Clone, Default)]
+pub struct VolumeMemory {
+    pub mode: VolumeMemoryMode,
+    /// Album or track key -> volume (0-100), saved in `state.toml`
+    pub volumes: BTreeMap<String, u8>,
+    /// Key of the playing track, while its volume is being remembered
+    current: Option<String>,
+    dirty: bool,
+    saved_at: Option<Instant>,
+}
+
+impl VolumeMemory {
+    pub fn new(mode: VolumeMemoryMode, volumes: BTreeMap<String, u8>) -> Self {
+        Self {
+            mode,
+            volumes,
+            ..Default::default()
+        }
+    }
+
+    /// What the volume is remembered under: the album (its folder when
+    /// the file is known) or the file itself
+    pub fn key(&self, file: Option<&str>, artist: &str, album: &str) -> Option<String> {
+        match self.mode {
+            VolumeMemoryMode::Off => None,
+            VolumeMemoryMode::Album => match file.and_then(|f| f.rsplit_once('/')) {
+                Some((folder, _)) => Some(format!("album:{}", folder)),
+                None if !album.is_empty() => Some(format!("album:{} - {}", artist, album)),
+                None => None,
+            },
+            VolumeMemoryMode::Track => match file {
+                Some(file) => Some(format!("track:{}", file)),
+                None if !artist.is_empty() => Some(format!("track:{} - {}", artist, album)),
+                None => None,
+            },
+        }
+    }
+
+    /// A track under `key` started: its remembered volume, if any
+    pub fn on_track_change(&mut self, key: Option<String>) -> Option<u8> {
+        self.current = key;
+        self.current
+            .as_ref()
+            .and_then(|key| self.volumes.get(key))
+            .copied()
+    }
+
+    /// ReplayGain evens this one out; leave its volume alone
+    pub fn skip(&mut self) {
+        self.current = None;
+    }
+
+    /// The volume was set by hand
+    pub fn record(&mut self, volume: u8) {
+        if let Some(key) = &self.current {
+            if self.volumes.insert(key.clone(), volume) != Some(volume) {
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Whether it's time to write the volumes out
+    pub fn should_save(&mut self, now: Instant) -> bool {
+        if !self.dirty
+            || self
+                .saved_at
+                .is_some_and(|t| now.duration_since(t) < RESUME_SAVE_EVERY)
+        {
+            return false;
+        }
+        self.dirty = false;
+        self.saved_at = Some(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_memory_per_album() {
+        let mut memory = VolumeMemory::new(VolumeMemoryMode::Album, BTreeMap::new());
+        let key = memory.key(Some("Loud/Album/01.flac"), "Loud", "Album");
+        assert_eq!(key.as_deref(), Some("album:Loud/Album"));
+        // Another track of the album shares it; without a file, the tags
+        assert_eq!(memory.key(Some("Loud/Album/02.flac"), "", ""), key);
+        assert_eq!(
+            memory.key(None, "Loud", "Album").as_deref(),
+            Some("album:Loud - Album")
+        );
+
+        assert_eq!(memory.on_track_change(key.clone()), None);
+        memory.record(40);
+        assert!(memory.should_save(Instant::now()));
+        assert_eq!(memory.on_track_change(key.clone()), Some(40));
+
+        // ReplayGain-tagged tracks aren't remembered
+        let quiet = memory.key(Some("Quiet/Album/01.flac"), "Quiet", "Album");
+        assert_eq!(memory.on_track_change(quiet.clone()), None);
+        memory.skip();
+        memory.record(90);
+        assert_eq!(memory.on_track_change(quiet), None);
+
+        let mut off = VolumeMemory::new(VolumeMemoryMode::Off, BTreeMap::new());
+        assert_eq!(off.key(Some("Loud/Album/01.flac"), "Loud", "Album"), None);
+        assert_eq!(off.on_track_change(None), None);
+        off.record(40);
+        assert!(off.volumes.is_empty());
+    }
+
+    #[test]
+    fn test_resume_remembers_long_tracks_only() {
+        const MIN: u64 = 60_000;
+        let mut resume = Resume::new(20, true, BTreeMap::new());
+        let t0 = Instant::now();
+
+        // A four minute song is never remembered
+        resume.record("song.flac", 120_000, 4 * MIN);
+        assert!(resume.positions.is_empty());
+
+        resume.record("book.m4b", 10_000, 300 * MIN);
+        assert!(resume.positions.is_empty());
+        resume.record("book.m4b", 95 * MIN, 300 * MIN);
+        assert!(resume.should_save(t0));
+        assert!(!resume.should_save(t0 + Duration::from_secs(60)));
+
+        // Started again from the top: back to where it was
+        assert_eq!(
+            resume.on_track_change("book.m4b", 0, 300 * MIN),
+            Some(95 * MIN)
+        );
+        // The server already kept the place
+        assert_eq!(
+            resume.on_track_change("book.m4b", 95 * MIN, 300 * MIN),
+            None
+        );
+
+        // Listened to the end: forgotten
+        resume.record("book.m4b", 300 * MIN - 5_000, 300 * MIN);
+        assert_eq!(resume.on_track_change("book.m4b", 0, 300 * MIN), None);
+        assert!(!resume.should_save(t0 + Duration::from_secs(10)));
+        assert!(resume.should_save(t0 + Duration::from_secs(30)));
+
+        // 0 turns it off
+        let mut off = Resume::new(0, true, BTreeMap::new());
+        off.record("book.m4b", 95 * MIN, 300 * MIN);
+        assert!(off.positions.is_empty());
+    }
+
+    #[test]
+    fn test_ab_loop_marks_and_jumps_back() {
+        let mut ab = AbLoop::default();
+        let t0 = Instant::now();
+        ab.mark(45_000);
+        assert_eq!(ab.range(), None);
+        assert_eq!(ab.check(200_000, true, t0), None);
+
+        // B before A swaps them
+        ab.mark(30_000);
+        assert_eq!(ab.range(), Some((30_000, 45_000)));
+        assert_eq!(ab.check(44_900, true, t0), None);
+        assert_eq!(ab.check(45_010, false, t0), None);
+        assert_eq!(ab.check(45_010, true, t0), Some(30_000));
+        // A late report from before the seek
+        assert_eq!(
+            ab.check(45_100, true, t0 + Duration::from_millis(100)),
+            None
+        );
+        assert_eq!(
+            ab.check(45_000, true, t0 + Duration::from_secs(15)),
+            Some(30_000)
+        );
+
+        ab.mark(50_000);
+        assert_eq!((ab.a, ab.b), (None, None));
+    }
+
+    #[test]
+    fn test_leading_silence_skips_until_sound() {
+        let mut trim = SilenceTrim::new(true);
+        trim.on_track_change();
+        let t0 = Instant::now();
+
+        let action = trim.check(200, 180_000, true, Duration::from_millis(600), t0);
+        assert_eq!(action, Some(TrimAction::SkipAhead(1.2)));
+        // Cooldown
+        assert_eq!(
+            trim.check(300, 180_000, true, Duration::from_secs(1), t0),
+            None
+        );
+
+        // Sound arrives: leading trim disarms, and the first half is never cut
+        let t1 = t0 + Duration::from_secs(2);
+        assert_eq!(trim.check(1500, 180_000, true, Duration::ZERO, t1), None);
+        let t2 = t1 + Duration::from_secs(2);
+        assert_eq!(
+            trim.check(20_000, 180_000, true, Duration::from_secs(5), t2),
+            None
+        );
+    }
+
+    #[test]
+    fn test_trailing_silence_skips_to_next() {
+        let mut trim = SilenceTrim::new(true);
+        let now = Instant::now();
+        assert_eq!(
+            trim.check(170_000, 180_000, true, Duration::from_secs(3), now),
+            Some(TrimAction::Next)
+        );
+    }
+
+    #[test]
+    fn test_per_track_toggle() {
+        let mut trim = SilenceTrim::new(true);
+        assert!(!trim.toggle_track());
+        let now = Instant::now();
+        assert_eq!(
+            trim.check(170_000, 180_000, true, Duration::from_secs(3), now),
+            None
+        );
+
+        // The override only lasts for the current track
+        trim.on_track_change();
+        assert!(trim.is_active());
+
+        // With trimming off globally the toggle turns it on for one track
+        let mut off = SilenceTrim::new(false);
+        assert!(off.toggle_track());
+    }
+}
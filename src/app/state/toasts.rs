@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Toasts on screen at once; older ones are dropped first
+const MAX_VISIBLE: usize = 4;
+/// Messages kept for `:messages`
+const HISTORY_LEN: usize = 100;
+/// Updates closer together than this replace the newest toast (volume, seeking)
+const COALESCE: Duration = Duration::from_millis(800);
+/// Length of the slide in and out
+pub const SLIDE: Duration = Duration::from_millis(300);
+
+/// How loud a toast is 🔔
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastLevel {
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub level: ToastLevel,
+    pub start_time: Instant,
+    pub deadline: Instant,
+    /// Last time the message changed (for coalescing)
+    pub updated: Instant,
+}
+
+/// A past toast, for the `:messages` popup
+#[derive(Debug, Clone)]
+pub struct LoggedToast {
+    pub message: String,
+    pub level: ToastLevel,
+    pub at: Instant,
+}
+
+/// Stacked notifications plus their history 📬
+#[derive(Debug, Clone)]
+pub struct Toasts {
+    /// On screen, oldest first
+    pub active: Vec<Toast>,
+    /// Newest first
+    pub history: VecDeque<LoggedToast>,
+    /// How long an info toast stays up (warnings and errors get longer)
+    pub duration: Duration,
+}
+
+impl Toasts {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            active: Vec::new(),
+            history: VecDeque::new(),
+            duration,
+        }
+    }
+
+    pub fn push(&mut self, level: ToastLevel, message: &str, now: Instant) {
+        let duration = match level {
+            ToastLevel::Info => self.duration,
+            ToastLevel::Warn | ToastLevel::Error => self.duration * 2,
+        };
+
+        // Rapid updates of one kind rewrite the newest toast in place, keeping
+        // start_time so the entrance animation doesn't replay
+        let newest = self
+            .active
+            .last_mut()
+            .filter(|t| t.level == level && now.saturating_duration_since(t.updated) < COALESCE);
+        match newest {
+            Some(current) => {
+                current.message = message.to_string();
+                current.deadline = now + duration;
+                current.updated = now;
+            }
+            None => {
+                self.active.push(Toast {
+                    message: message.to_string(),
+                    level,
+                    start_time: now,
+                    deadline: now + duration,
+                    updated: now,
+                });
+                if self.active.len() > MAX_VISIBLE {
+                    self.active.remove(0);
+                }
+            }
+        }
+
+        // History keeps the last word of a burst only
+        match self.history.front_mut() {
+            Some(last)
+                if now.saturating_duration_since(last.at) < COALESCE && last.level == level =>
+            {
+                last.message = message.to_string();
+                last.at = now;
+            }
+            _ => {
+                self.history.push_front(LoggedToast {
+                    message: message.to_string(),
+                    level,
+                    at: now,
+                });
+                self.history.truncate(HISTORY_LEN);
+            }
+        }
+    }
+
+    /// Drop toasts past their deadline
+    pub fn expire(&mut self, now: Instant) {
+        self.active.retain(|t| now <= t.deadline);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// A toast is sliding in or out (needs full frame rate). The exit
+    /// counts one idle tick early so the slide isn't joined halfway.
+    pub fn animating(&self, now: Instant) -> bool {
+        let exit = SLIDE + super::FrameRate::Idle.interval();
+        self.active.iter().any(|t| {
+            now.saturating_duration_since(t.start_time) < SLIDE
+                || t.deadline.saturating_duration_since(now) < exit
+        })
+    }
+
+    /// Most recent toast on screen
+    pub fn latest(&self) -> Option<&Toast> {
+        self.active.last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_stack_coalesce_and_log() {
+        let mut toasts = Toasts::new(Duration::from_secs(2));
+        let t0 = Instant::now();
+        toasts.push(ToastLevel::Info, "Volume 50%", t0);
+        toasts.push(
+            ToastLevel::Info,
+            "Volume 55%",
+            t0 + Duration::from_millis(100),
+        );
+        assert_eq!(toasts.active.len(), 1);
+        assert_eq!(toasts.latest().unwrap().message, "Volume 55%");
+
+        // A different level, or a later message, stacks
+        toasts.push(
+            ToastLevel::Error,
+            "Save failed",
+            t0 + Duration::from_millis(200),
+        );
+        toasts.push(ToastLevel::Info, "Saved", t0 + Duration::from_secs(1));
+        assert_eq!(toasts.active.len(), 3);
+        assert_eq!(toasts.history.len(), 3);
+        assert_eq!(toasts.history[1].message, "Save failed");
+
+        // Errors outlast info toasts
+        toasts.expire(t0 + Duration::from_millis(3500));
+        assert_eq!(toasts.active.len(), 1);
+        assert_eq!(toasts.latest().unwrap().level, ToastLevel::Error);
+        // History survives expiry
+        assert_eq!(toasts.history.len(), 3);
+
+        for i in 0..10 {
+            toasts.push(
+                ToastLevel::Warn,
+                &i.to_string(),
+                t0 + Duration::from_secs(10 + i),
+            );
+        }
+        assert_eq!(toasts.active.len(), MAX_VISIBLE);
+    }
+}
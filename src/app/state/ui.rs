@@ -1,63 +1,491 @@
-use std::time::Instant;
+use crate::app::tag_lookup::{TagCandidate, TagQuery};
 
 /// View mode for the right panel 🎛️
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize)]
 pub enum ViewMode {
     #[default]
     Lyrics,
     Visualizer,
     Library, // Renamed from Queue → Library
     EQ,
+    Info,    // About the song
+    Related, // Artists like the playing one
+}
+
+/// Number of workspaces (Alt+1..4)
+pub const WORKSPACES: usize = 4;
+
+/// What a workspace remembers while another one is shown 🗂️
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    pub view_mode: ViewMode,
+    pub library_mode: super::LibraryMode,
+    pub previous_library_mode: Option<super::LibraryMode>,
+    pub library_items: Vec<super::LibraryItem>,
+    pub library_selected: usize,
+    pub browse_path: Vec<String>,
+    pub search_query: String,
+    pub album_open: Option<super::AlbumKey>,
+}
+
+/// Live spectrum drawn behind the EQ curve 📈
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SpectrumOverlay {
+    /// What you hear (after the EQ)
+    #[default]
+    Post,
+    /// The source, with the current curve backed out
+    Pre,
+    Off,
+}
+
+impl SpectrumOverlay {
+    pub fn next(self) -> Self {
+        match self {
+            SpectrumOverlay::Post => SpectrumOverlay::Pre,
+            SpectrumOverlay::Pre => SpectrumOverlay::Off,
+            SpectrumOverlay::Off => SpectrumOverlay::Post,
+        }
+    }
+}
+
+/// Theme gallery popup 🎨 (previews live, Esc restores the old theme)
+#[derive(Debug, Clone)]
+pub struct ThemePicker {
+    pub entries: Vec<(String, crate::ui::Theme)>,
+    pub selected: usize,
+    /// Base theme to restore on cancel
+    pub original: crate::ui::Theme,
+}
+
+impl ThemePicker {
+    pub fn new(
+        entries: Vec<(String, crate::ui::Theme)>,
+        current: &str,
+        original: crate::ui::Theme,
+    ) -> Self {
+        let selected = entries.iter().position(|(n, _)| n == current).unwrap_or(0);
+        Self {
+            entries,
+            selected,
+            original,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.entries.is_empty() {
+            self.selected = (self.selected + 1) % self.entries.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.entries.is_empty() {
+            self.selected = (self.selected + self.entries.len() - 1) % self.entries.len();
+        }
+    }
+
+    pub fn current(&self) -> Option<&(String, crate::ui::Theme)> {
+        self.entries.get(self.selected)
+    }
+}
+
+/// A tag the editor can change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagField {
+    Title,
+    Artist,
+    Album,
+    AlbumArtist,
+    Genre,
+    Year,
+    Track,
+    Disc,
+    Comment,
+}
+
+impl TagField {
+    pub fn label(self) -> String {
+        match self {
+            TagField::Title => crate::t!("field.title"),
+            TagField::Artist => crate::t!("field.artist"),
+            TagField::Album => crate::t!("field.album"),
+            TagField::AlbumArtist => crate::t!("field.album_artist"),
+            TagField::Genre => crate::t!("field.genre"),
+            TagField::Year => crate::t!("field.year"),
+            TagField::Track => crate::t!("field.track"),
+            TagField::Disc => crate::t!("field.disc"),
+            TagField::Comment => crate::t!("field.comment"),
+        }
+    }
+}
+
+/// One row of the tag editor
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagValue {
+    pub field: TagField,
+    pub value: String,
+    /// `value` when the editor opened
+    pub original: String,
+    /// Saving leaves this tag alone. Cleared by typing, back on with `Ctrl+k`.
+    pub keep: bool,
+    /// The files don't agree on a value (batch mode)
+    pub mixed: bool,
+}
+
+impl TagValue {
+    /// The value every file has, or empty and `mixed` when they differ
+    pub fn shared(field: TagField, values: &[Option<String>]) -> Self {
+        let first = values.first().cloned().flatten();
+        let mixed = values.iter().any(|v| *v != first);
+        let value = if mixed {
+            String::new()
+        } else {
+            first.unwrap_or_default()
+        };
+        Self {
+            field,
+            original: value.clone(),
+            value,
+            keep: true,
+            mixed,
+        }
+    }
 }
 
 /// Tag editing state 🏷️
 #[derive(Debug, Clone)]
 pub struct TagEditState {
-    pub file_path: String,
-    pub title: String,
-    pub artist: String,
-    pub album: String,
-    pub active_field: usize, // 0=title, 1=artist, 2=album
+    /// Paths relative to the music directory; more than one in batch mode
+    pub files: Vec<String>,
+    pub fields: Vec<TagValue>,
+    pub active_field: usize,
+    /// Files written so far while saving
+    pub progress: Option<usize>,
+    /// "Fetch tags" (Ctrl+f) results, shown over the fields
+    pub lookup: Option<TagLookup>,
+}
+
+/// MusicBrainz matches offered by the tag editor
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagLookup {
+    pub status: super::LookupStatus,
+    pub results: Vec<TagCandidate>,
+    pub selected: usize,
+}
+
+impl TagLookup {
+    pub fn searching() -> Self {
+        Self {
+            status: super::LookupStatus::Searching,
+            results: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn set_results(&mut self, results: Result<Vec<TagCandidate>, String>) {
+        self.selected = 0;
+        match results {
+            Ok(results) => {
+                self.results = results;
+                self.status = super::LookupStatus::Done;
+            }
+            Err(e) => self.status = super::LookupStatus::Failed(e),
+        }
+    }
 }
 
 impl TagEditState {
+    /// Fields for a single song, in Tab order
+    pub const SINGLE: [TagField; 9] = [
+        TagField::Title,
+        TagField::Artist,
+        TagField::Album,
+        TagField::AlbumArtist,
+        TagField::Genre,
+        TagField::Year,
+        TagField::Track,
+        TagField::Disc,
+        TagField::Comment,
+    ];
+    /// Fields an album or a set of songs usually share
+    pub const BATCH: [TagField; 7] = [
+        TagField::Album,
+        TagField::AlbumArtist,
+        TagField::Artist,
+        TagField::Genre,
+        TagField::Year,
+        TagField::Disc,
+        TagField::Comment,
+    ];
+
+    /// One song, knowing only its title, artist and album
     pub fn new(path: &str, title: &str, artist: &str, album: &str) -> Self {
+        let fields = Self::SINGLE
+            .iter()
+            .map(|&field| {
+                let value = match field {
+                    TagField::Title => title,
+                    TagField::Artist => artist,
+                    TagField::Album => album,
+                    _ => "",
+                };
+                TagValue::shared(field, &[Some(value.to_string())])
+            })
+            .collect();
+        Self::with_fields(vec![path.to_string()], fields)
+    }
+
+    /// Edit `files` (together, if more than one); `fields` from
+    /// `TagValue::shared`
+    pub fn with_fields(files: Vec<String>, fields: Vec<TagValue>) -> Self {
         Self {
-            file_path: path.to_string(),
-            title: title.to_string(),
-            artist: artist.to_string(),
-            album: album.to_string(),
+            files,
+            fields,
             active_field: 0,
+            progress: None,
+            lookup: None,
+        }
+    }
+
+    pub fn is_batch(&self) -> bool {
+        self.files.len() > 1
+    }
+
+    pub fn active(&mut self) -> Option<&mut TagValue> {
+        self.fields.get_mut(self.active_field)
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        if let Some(row) = self.active() {
+            row.value.push(c);
+            row.keep = false;
         }
     }
 
-    pub fn active_value(&mut self) -> &mut String {
-        match self.active_field {
-            0 => &mut self.title,
-            1 => &mut self.artist,
-            _ => &mut self.album,
+    pub fn pop_char(&mut self) {
+        if let Some(row) = self.active() {
+            row.value.pop();
+            row.keep = false;
+        }
+    }
+
+    /// Back to "keep existing": the field is left as it is in every file
+    pub fn keep_active(&mut self) {
+        if let Some(row) = self.active() {
+            row.keep = true;
+            row.value = row.original.clone();
+        }
+    }
+
+    /// Fields to write: everything not kept
+    pub fn changes(&self) -> Vec<(TagField, String)> {
+        self.fields
+            .iter()
+            .filter(|row| !row.keep)
+            .map(|row| (row.field, row.value.clone()))
+            .collect()
+    }
+
+    /// Fill in what `candidate` knows; fields it has nothing for stay as
+    /// they are
+    pub fn apply(&mut self, candidate: &TagCandidate) {
+        for row in &mut self.fields {
+            if let Some(value) = candidate.value(row.field) {
+                if row.value != value {
+                    row.value = value.to_string();
+                    row.keep = false;
+                }
+            }
+        }
+    }
+
+    /// Title, artist and album as they stand, for "fetch tags"
+    pub fn query(&self, music_dir: &str) -> TagQuery {
+        let value = |field| {
+            self.fields
+                .iter()
+                .find(|row| row.field == field)
+                .map(|row| row.value.clone())
+                .unwrap_or_default()
+        };
+        TagQuery {
+            title: value(TagField::Title),
+            artist: value(TagField::Artist),
+            album: value(TagField::Album),
+            path: match self.files.as_slice() {
+                [file] => Some(std::path::Path::new(music_dir).join(file)),
+                _ => None,
+            },
         }
     }
 
     pub fn next_field(&mut self) {
-        self.active_field = (self.active_field + 1) % 3;
+        self.active_field = (self.active_field + 1) % self.fields.len().max(1);
     }
 
     pub fn prev_field(&mut self) {
         self.active_field = if self.active_field == 0 {
-            2
+            self.fields.len().saturating_sub(1)
         } else {
             self.active_field - 1
         };
     }
 }
 
+/// What a filename ↔ tag preview does when confirmed
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagToolAction {
+    /// Write these tags (`:name2tag`)
+    Tags(Vec<(TagField, String)>),
+    /// Rename to this path, relative to the music directory (`:tag2name`)
+    Rename(String),
+}
+
+/// One file in the preview: what would happen, or why it can't
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagToolRow {
+    pub file: String,
+    pub action: Result<TagToolAction, String>,
+}
+
+/// Dry run of `:name2tag` / `:tag2name`; `Enter` applies it 🔤
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagToolPreview {
+    pub pattern: String,
+    pub rows: Vec<TagToolRow>,
+    pub scroll: usize,
+    /// Files written so far while applying
+    pub progress: Option<usize>,
+}
+
+impl TagToolPreview {
+    /// Rows that will change something
+    pub fn ready(&self) -> usize {
+        self.rows.iter().filter(|r| r.action.is_ok()).count()
+    }
+}
+
+/// `:missing` results; `d` purges them 🩹
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingReport {
+    pub entries: Vec<crate::app::missing::MissingEntry>,
+    pub scroll: usize,
+    /// The music folder was there to check; else only playlists were
+    pub disk_checked: bool,
+}
+
+/// Jump-to-anything palette (`Ctrl+p`) 🧭
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    pub query: String,
+    pub entries: Vec<crate::app::palette::Entry>,
+    /// Indices into `entries`, best match first
+    pub matches: Vec<usize>,
+    pub selected: usize,
+}
+
+impl Palette {
+    pub fn new(entries: Vec<crate::app::palette::Entry>) -> Self {
+        let mut palette = Self {
+            query: String::new(),
+            entries,
+            matches: Vec::new(),
+            selected: 0,
+        };
+        palette.refilter();
+        palette
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.refilter();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.refilter();
+    }
+
+    fn refilter(&mut self) {
+        self.matches = crate::app::palette::filter(&self.entries, &self.query);
+        self.selected = 0;
+    }
+
+    pub fn current(&self) -> Option<&crate::app::palette::Entry> {
+        self.entries.get(*self.matches.get(self.selected)?)
+    }
+}
+
+/// A titled block of (label, value) rows in the track info popup
+pub type DetailSection = (String, Vec<(String, String)>);
+
+/// Track info popup (`I`) 🏷️
+#[derive(Debug, Clone, Default)]
+pub struct TrackDetails {
+    pub title: String,
+    pub sections: Vec<DetailSection>,
+    pub scroll: usize,
+}
+
+/// `?` help browser 🎹
+#[derive(Debug, Clone, Default)]
+pub struct HelpState {
+    pub page: usize,
+    /// Fuzzy filter across every page
+    pub query: String,
+    /// Keys go into the filter
+    pub searching: bool,
+    pub scroll: usize,
+}
+
+/// Log viewer (`F12`) 📝
+#[derive(Debug, Clone)]
+pub struct LogView {
+    /// First line shown; `None` follows the newest lines
+    pub scroll: Option<usize>,
+    /// Least severe level shown
+    pub min_level: tracing::Level,
+    /// `logging::logged()` at the last draw
+    pub seen: u64,
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        Self {
+            scroll: None,
+            min_level: tracing::Level::DEBUG,
+            seen: 0,
+        }
+    }
+}
+
+impl LogView {
+    /// debug -> info -> warn -> error -> trace -> debug
+    pub fn cycle_level(&mut self) {
+        use tracing::Level;
+        self.min_level = match self.min_level {
+            Level::TRACE => Level::DEBUG,
+            Level::DEBUG => Level::INFO,
+            Level::INFO => Level::WARN,
+            Level::WARN => Level::ERROR,
+            _ => Level::TRACE,
+        };
+        self.scroll = None;
+    }
+}
+
 /// Generic Input Popup Mode 📝
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputMode {
     PlaylistSave,
     EqSave,
+    EqImport,               // Path of a preset file (ours or AutoEq's)
     PlaylistRename(String), // Carries old name
+    PlaylistImport,         // Path of an M3U/PLS/XSPF file
+    PlaylistExport(String), // Carries the playlist name
+    LyricsLine(usize),      // Index of the line being edited
+    LyricsSearch,
+    Command, // `:` command line
 }
 
 /// Generic Input Popup State 📝
@@ -77,10 +505,3 @@ impl InputState {
         }
     }
 }
-
-#[derive(Debug, Clone)]
-pub struct Toast {
-    pub message: String,
-    pub start_time: Instant,
-    pub deadline: Instant,
-}
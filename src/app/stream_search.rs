@@ -0,0 +1,217 @@
+//! `:stream [query]`: find a song on YouTube Music and play it 🔎
+//!
+//! Searches go to an Invidious instance when `invidious_instance` is set in
+//! `config.toml`, else to YouTube Music through yt-dlp. The pick is played
+//! like `:open` (see [`super::ytdlp`]), so songs that aren't in the library
+//! are a search away.
+
+use crate::app::LookupStatus;
+use anyhow::{anyhow, bail, Result};
+use reqwest::Client;
+use serde::Deserialize;
+
+/// Results asked for
+const LIMIT: usize = 15;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamHit {
+    pub title: String,
+    pub artist: String,
+    pub duration_secs: Option<u64>,
+    /// The page to hand yt-dlp
+    pub url: String,
+}
+
+/// The stream search popup
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamSearch {
+    pub query: String,
+    /// What `results` were found for
+    pub searched: String,
+    pub results: Vec<StreamHit>,
+    pub selected: usize,
+    pub status: LookupStatus,
+}
+
+impl StreamSearch {
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_string(),
+            searched: String::new(),
+            results: Vec::new(),
+            selected: 0,
+            status: LookupStatus::Editing,
+        }
+    }
+
+    /// The result `Enter` plays: only while the list is for the query typed
+    pub fn chosen(&self) -> Option<&StreamHit> {
+        if self.query.trim() == self.searched && self.status == LookupStatus::Done {
+            self.results.get(self.selected)
+        } else {
+            None
+        }
+    }
+
+    pub fn set_results(&mut self, results: Vec<StreamHit>) {
+        self.results = results;
+        self.selected = 0;
+        self.status = LookupStatus::Done;
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+/// Songs matching `query`
+pub async fn search(
+    client: &Client,
+    query: &str,
+    invidious: Option<&str>,
+) -> Result<Vec<StreamHit>> {
+    match invidious {
+        Some(instance) => search_invidious(client, instance, query).await,
+        None => search_ytdlp(query).await,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InvidiousVideo {
+    title: String,
+    video_id: String,
+    #[serde(default)]
+    author: String,
+    length_seconds: Option<u64>,
+}
+
+async fn search_invidious(client: &Client, instance: &str, query: &str) -> Result<Vec<StreamHit>> {
+    let videos: Vec<InvidiousVideo> = client
+        .get(format!("{}/api/v1/search", instance.trim_end_matches('/')))
+        .query(&[("q", query), ("type", "video")])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(videos
+        .into_iter()
+        .take(LIMIT)
+        .map(|video| StreamHit {
+            url: format!("https://www.youtube.com/watch?v={}", video.video_id),
+            title: video.title,
+            artist: video.author,
+            duration_secs: video.length_seconds.filter(|&s| s > 0),
+        })
+        .collect())
+}
+
+async fn search_ytdlp(query: &str) -> Result<Vec<StreamHit>> {
+    let mut url =
+        reqwest::Url::parse_with_params("https://music.youtube.com/search", &[("q", query)])?;
+    // The "Songs" shelf, not videos and albums
+    url.set_fragment(Some("songs"));
+    let output = tokio::process::Command::new("yt-dlp")
+        .args(["--flat-playlist", "--dump-json", "--no-warnings"])
+        .args(["--playlist-end", &LIMIT.to_string(), "--", url.as_str()])
+        .output()
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => anyhow!(crate::t!("stream.no_ytdlp")),
+            _ => e.into(),
+        })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last = stderr.lines().rev().find(|l| !l.trim().is_empty());
+        bail!(
+            "{}",
+            last.map(|l| l.trim_start_matches("ERROR: ").to_string())
+                .unwrap_or_else(|| format!("yt-dlp exited with {}", output.status))
+        );
+    }
+    Ok(parse_ytdlp(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[derive(Debug, Deserialize)]
+struct FlatEntry {
+    id: Option<String>,
+    url: Option<String>,
+    title: Option<String>,
+    #[serde(default)]
+    artists: Vec<String>,
+    channel: Option<String>,
+    uploader: Option<String>,
+    duration: Option<f64>,
+}
+
+/// yt-dlp's `--flat-playlist --dump-json` lines; entries it can't play are
+/// left out
+fn parse_ytdlp(output: &str) -> Vec<StreamHit> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<FlatEntry>(line).ok())
+        .filter_map(|entry| {
+            let url = entry.url.or_else(|| {
+                entry
+                    .id
+                    .map(|id| format!("https://music.youtube.com/watch?v={}", id))
+            })?;
+            let artist = if entry.artists.is_empty() {
+                entry.channel.or(entry.uploader).unwrap_or_default()
+            } else {
+                entry.artists.join(", ")
+            };
+            Some(StreamHit {
+                title: entry.title?,
+                artist,
+                duration_secs: entry.duration.map(|d| d as u64),
+                url,
+            })
+        })
+        .take(LIMIT)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ytdlp_results() {
+        let output = r#"{"_type": "url", "ie_key": "Youtube", "id": "lYBUbBu4W08", "url": "https://music.youtube.com/watch?v=lYBUbBu4W08", "title": "Airbag", "artists": ["Radiohead"], "duration": 284.0}
+{"_type": "url", "id": "fHiGbolFFGw", "title": "Karma Police", "channel": "Radiohead - Topic"}
+{"_type": "url", "id": "x", "url": "https://music.youtube.com/playlist?list=x"}
+not json"#;
+        let hits = parse_ytdlp(output);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(
+            hits[0],
+            StreamHit {
+                title: "Airbag".into(),
+                artist: "Radiohead".into(),
+                duration_secs: Some(284),
+                url: "https://music.youtube.com/watch?v=lYBUbBu4W08".into(),
+            }
+        );
+        assert_eq!(hits[1].artist, "Radiohead - Topic");
+        assert_eq!(hits[1].url, "https://music.youtube.com/watch?v=fHiGbolFFGw");
+
+        let mut search = StreamSearch::new("airbag");
+        search.searched = "airbag".into();
+        search.set_results(hits);
+        search.select_next();
+        assert_eq!(
+            search.chosen().map(|h| h.title.as_str()),
+            Some("Karma Police")
+        );
+        search.query.push('s');
+        assert_eq!(search.chosen(), None);
+    }
+}
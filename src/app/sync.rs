@@ -0,0 +1,278 @@
+//! Shared Audio Mode instances see what the master hears 🔗
+//!
+//! Only the instance holding the audio lock processes sound. Others
+//! subscribe over the control socket (`subscribe`) and get one line per
+//! change: `eq`, `volume`, and `bars` for every visualizer frame. The
+//! master only computes and sends while someone listens.
+
+use crate::app::events::AppEvent;
+use crate::app::App;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+
+/// Lines a slow subscriber may fall behind before frames are dropped
+const BACKLOG: usize = 64;
+/// Visualizer frames sent at most this often
+const BARS_EVERY: Duration = Duration::from_millis(33);
+/// Wait before subscribing again when there's no master to talk to
+const RETRY: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncUpdate {
+    Eq {
+        enabled: bool,
+        preamp_db: f32,
+        balance: f32,
+        bands: [f32; 10],
+    },
+    Volume(u8),
+    Bars(Vec<f32>),
+}
+
+impl SyncUpdate {
+    pub fn to_line(&self) -> String {
+        let list = |values: &[f32]| {
+            values
+                .iter()
+                .map(|v| format!("{:.2}", v))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        match self {
+            SyncUpdate::Eq {
+                enabled,
+                preamp_db,
+                balance,
+                bands,
+            } => format!(
+                "eq {} {:.1} {:.2} {}",
+                u8::from(*enabled),
+                preamp_db,
+                balance,
+                list(bands)
+            ),
+            SyncUpdate::Volume(v) => format!("volume {}", v),
+            SyncUpdate::Bars(bars) => format!("bars {}", list(bars)),
+        }
+    }
+
+    pub fn parse(line: &str) -> Option<Self> {
+        let list = |s: &str| -> Option<Vec<f32>> {
+            if s.is_empty() {
+                return Some(Vec::new());
+            }
+            s.split(',').map(|v| v.parse().ok()).collect()
+        };
+        let mut words = line.trim_end().split(' ');
+        match words.next()? {
+            "eq" => Some(SyncUpdate::Eq {
+                enabled: words.next()? == "1",
+                preamp_db: words.next()?.parse().ok()?,
+                balance: words.next()?.parse().ok()?,
+                bands: list(words.next()?)?.try_into().ok()?,
+            }),
+            "volume" => Some(SyncUpdate::Volume(words.next()?.parse().ok()?)),
+            "bars" => Some(SyncUpdate::Bars(list(words.next().unwrap_or(""))?)),
+            _ => None,
+        }
+    }
+}
+
+/// Master side: what was sent last, so only changes go out
+pub struct SyncPublisher {
+    tx: broadcast::Sender<String>,
+    eq: Option<SyncUpdate>,
+    volume: Option<u8>,
+    bars_at: Option<Instant>,
+}
+
+impl Default for SyncPublisher {
+    fn default() -> Self {
+        Self {
+            tx: broadcast::channel(BACKLOG).0,
+            eq: None,
+            volume: None,
+            bars_at: None,
+        }
+    }
+}
+
+impl SyncPublisher {
+    pub fn has_subscribers(&self) -> bool {
+        self.tx.receiver_count() > 0
+    }
+
+    /// A new listener: everything is sent again on the next publish
+    pub fn subscribe(&mut self) -> broadcast::Receiver<String> {
+        self.eq = None;
+        self.volume = None;
+        self.tx.subscribe()
+    }
+
+    fn send(&self, update: &SyncUpdate) {
+        // Nobody listening is fine
+        let _ = self.tx.send(update.to_line());
+    }
+}
+
+/// Send what changed since the last call (called every loop turn)
+pub fn publish(app: &mut App, now: Instant) {
+    let Some(publisher) = app.sync.as_mut() else {
+        return;
+    };
+    if !publisher.has_subscribers() {
+        return;
+    }
+    let eq = SyncUpdate::Eq {
+        enabled: app.eq_enabled,
+        preamp_db: app.preamp_db,
+        balance: app.balance,
+        bands: app.eq_bands,
+    };
+    if publisher.eq.as_ref() != Some(&eq) {
+        publisher.send(&eq);
+        publisher.eq = Some(eq);
+    }
+    if publisher.volume != Some(app.app_volume) {
+        publisher.send(&SyncUpdate::Volume(app.app_volume));
+        publisher.volume = Some(app.app_volume);
+    }
+    if publisher
+        .bars_at
+        .is_none_or(|at| now.duration_since(at) >= BARS_EVERY)
+    {
+        publisher.bars_at = Some(now);
+        // Already fresh when our own visualizer is showing
+        let bars = if app.is_visible(crate::app::ViewMode::Visualizer) {
+            app.visualizer_bars.clone()
+        } else {
+            app.visualizer.get_bars(64)
+        };
+        if let Some(publisher) = app.sync.as_ref() {
+            publisher.send(&SyncUpdate::Bars(bars));
+        }
+    }
+}
+
+/// Secondary side: show the master's state
+pub fn apply(app: &mut App, update: SyncUpdate) {
+    match update {
+        SyncUpdate::Eq {
+            enabled,
+            preamp_db,
+            balance,
+            bands,
+        } => {
+            app.eq_enabled = enabled;
+            app.preamp_db = preamp_db;
+            app.balance = balance;
+            app.eq_bands = bands;
+        }
+        SyncUpdate::Volume(v) => app.app_volume = v,
+        SyncUpdate::Bars(bars) => app.visualizer_bars = bars,
+    }
+}
+
+/// Keep a subscription to the master open, passing its lines on as
+/// `AppEvent::Sync`; reconnects whenever the master goes away
+#[cfg(unix)]
+pub fn spawn_subscriber(path: std::path::PathBuf, tx: mpsc::Sender<AppEvent>) {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    tokio::spawn(async move {
+        loop {
+            let stream = match crate::app::remote::owned_by_us(&path) {
+                true => tokio::net::UnixStream::connect(&path).await.ok(),
+                false => None,
+            };
+            if let Some(mut stream) = stream {
+                if stream.write_all(b"subscribe\n").await.is_ok() {
+                    let mut lines = BufReader::new(stream).lines();
+                    match lines.next_line().await {
+                        Ok(Some(reply)) if reply.starts_with("ok") => {
+                            tracing::info!("Following the audio master's state");
+                            while let Ok(Some(line)) = lines.next_line().await {
+                                let Some(update) = SyncUpdate::parse(&line) else {
+                                    continue;
+                                };
+                                if tx.send(AppEvent::Sync(update)).await.is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                        Ok(Some(reply)) => tracing::debug!("Sync refused: {}", reply),
+                        _ => {}
+                    }
+                }
+            }
+            if tx.is_closed() {
+                return;
+            }
+            tokio::time::sleep(RETRY).await;
+        }
+    });
+}
+
+#[cfg(not(unix))]
+pub fn spawn_subscriber(_path: std::path::PathBuf, _tx: mpsc::Sender<AppEvent>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_updates_round_trip() {
+        for update in [
+            SyncUpdate::Eq {
+                enabled: true,
+                preamp_db: -3.5,
+                balance: 0.25,
+                bands: [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, -1.0, -2.0, -3.0, 0.5],
+            },
+            SyncUpdate::Volume(40),
+            SyncUpdate::Bars(vec![0.0, 0.5, 1.0]),
+            SyncUpdate::Bars(Vec::new()),
+        ] {
+            assert_eq!(SyncUpdate::parse(&update.to_line()), Some(update));
+        }
+        assert_eq!(SyncUpdate::parse("eq 1 0 0 1,2"), None);
+        assert_eq!(SyncUpdate::parse("ok subscribed"), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_secondary_follows_the_master() {
+        let dir = std::env::temp_dir().join(format!("vyom-sync-{}", std::process::id()));
+        let path = dir.join("vyom.sock");
+        let (master_tx, mut master_rx) = mpsc::channel(4);
+        let _listener = crate::app::remote::listen_at(path.clone(), master_tx).unwrap();
+        let (tx, mut rx) = mpsc::channel(4);
+        spawn_subscriber(path, tx);
+
+        // The master's event loop hands out a subscription
+        let mut publisher = SyncPublisher::default();
+        let Some(AppEvent::Subscribe(reply)) = master_rx.recv().await else {
+            panic!("expected a subscription");
+        };
+        reply.send(Ok(publisher.subscribe())).unwrap();
+        while !publisher.has_subscribers() {
+            tokio::task::yield_now().await;
+        }
+        publisher.send(&SyncUpdate::Volume(55));
+        publisher.send(&SyncUpdate::Bars(vec![0.25; 4]));
+
+        let mut got = Vec::new();
+        while got.len() < 2 {
+            match tokio::time::timeout(Duration::from_secs(5), rx.recv()).await {
+                Ok(Some(AppEvent::Sync(update))) => got.push(update),
+                _ => panic!("no update from the master"),
+            }
+        }
+        assert_eq!(
+            got,
+            vec![SyncUpdate::Volume(55), SyncUpdate::Bars(vec![0.25; 4])]
+        );
+        drop(_listener);
+        std::fs::remove_dir(&dir).unwrap();
+    }
+}
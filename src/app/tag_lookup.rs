@@ -0,0 +1,447 @@
+//! "Fetch tags" for the tag editor 🔎
+//!
+//! Ctrl+f looks the song up on MusicBrainz and offers the matches. With
+//! `acoustid_key` set and Chromaprint's `fpcalc` on the PATH, a single song
+//! is fingerprinted and looked up on AcoustID first, which finds it even
+//! when its tags are wrong; otherwise (or when that finds nothing) the
+//! recording search uses whatever title, artist and album are filled in.
+
+use crate::app::TagField;
+use crate::artwork::{lucene_escape, ArtworkRenderer, MUSICBRAINZ_USER_AGENT};
+use anyhow::Result;
+use reqwest::Client;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Results offered at most
+const MAX_CANDIDATES: usize = 8;
+/// AcoustID matches below this are usually a different recording
+const ACOUSTID_MIN_SCORE: f64 = 0.5;
+
+/// A match offered by "fetch tags"
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagCandidate {
+    pub title: String,
+    pub artist: String,
+    pub album: Option<String>,
+    pub year: Option<String>,
+    /// "3/12" when MusicBrainz knows the track count
+    pub track: Option<String>,
+    /// How sure the source is, 0-100
+    pub score: u32,
+}
+
+impl TagCandidate {
+    /// The value this match has for `field`, if any
+    pub fn value(&self, field: TagField) -> Option<&str> {
+        match field {
+            TagField::Title => Some(self.title.as_str()),
+            TagField::Artist => Some(self.artist.as_str()),
+            TagField::Album => self.album.as_deref(),
+            TagField::Year => self.year.as_deref(),
+            TagField::Track => self.track.as_deref(),
+            _ => None,
+        }
+        .filter(|v| !v.is_empty())
+    }
+}
+
+/// What to look up
+#[derive(Debug, Clone, Default)]
+pub struct TagQuery {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    /// The song itself, for fingerprinting (single song only)
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbRecordingSearch {
+    #[serde(default)]
+    recordings: Vec<MbRecording>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbRecording {
+    title: String,
+    #[serde(default)]
+    score: u32,
+    #[serde(rename = "artist-credit", default)]
+    artist_credit: Vec<MbArtistCredit>,
+    #[serde(default)]
+    releases: Vec<MbRelease>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbArtistCredit {
+    name: String,
+    #[serde(default)]
+    joinphrase: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbRelease {
+    title: String,
+    date: Option<String>,
+    #[serde(default)]
+    media: Vec<MbMedium>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbMedium {
+    #[serde(rename = "track-count")]
+    track_count: Option<u32>,
+    #[serde(default)]
+    track: Vec<MbTrack>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbTrack {
+    number: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdResponse {
+    #[serde(default)]
+    results: Vec<AcoustIdResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdResult {
+    score: f64,
+    #[serde(default)]
+    recordings: Vec<AcoustIdRecording>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdRecording {
+    title: Option<String>,
+    #[serde(default)]
+    artists: Vec<AcoustIdArtist>,
+    #[serde(default)]
+    releases: Vec<AcoustIdRelease>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdArtist {
+    name: String,
+    #[serde(default)]
+    joinphrase: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdRelease {
+    title: Option<String>,
+    date: Option<AcoustIdDate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdDate {
+    year: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Fingerprint {
+    duration: f64,
+    fingerprint: String,
+}
+
+/// Candidates for `query`, best first
+pub async fn lookup(
+    client: &Client,
+    query: &TagQuery,
+    acoustid_key: Option<&str>,
+) -> Result<Vec<TagCandidate>> {
+    if let (Some(key), Some(path)) = (acoustid_key, query.path.as_deref()) {
+        match acoustid(client, key, path).await {
+            Ok(found) if !found.is_empty() => return Ok(found),
+            Ok(_) => tracing::debug!("No AcoustID match for {}", path.display()),
+            Err(e) => tracing::debug!("Fingerprint lookup failed: {}", e),
+        }
+    }
+    search(client, query).await
+}
+
+/// MusicBrainz recording search on the known tags, or the file name when
+/// there are none
+async fn search(client: &Client, query: &TagQuery) -> Result<Vec<TagCandidate>> {
+    let text = search_query(query)
+        .ok_or_else(|| anyhow::anyhow!(crate::t!("tag_lookup.nothing_to_search")))?;
+    ArtworkRenderer::musicbrainz_throttle().await;
+    let data: MbRecordingSearch = client
+        .get("https://musicbrainz.org/ws/2/recording/")
+        .header(reqwest::header::USER_AGENT, MUSICBRAINZ_USER_AGENT)
+        .query(&[("query", text.as_str()), ("fmt", "json"), ("limit", "15")])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(from_recordings(data.recordings))
+}
+
+/// Lucene query for the recording search
+fn search_query(query: &TagQuery) -> Option<String> {
+    let mut terms = Vec::new();
+    for (name, value) in [
+        ("recording", &query.title),
+        ("artist", &query.artist),
+        ("release", &query.album),
+    ] {
+        let value = value.trim();
+        if !value.is_empty() {
+            terms.push(format!("{}:\"{}\"", name, lucene_escape(value)));
+        }
+    }
+    if terms.is_empty() {
+        // Untagged: the file name is the best hint there is
+        let stem = query.path.as_deref()?.file_stem()?.to_str()?;
+        let words: String = stem
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+            .collect();
+        let words = words.split_whitespace().collect::<Vec<_>>().join(" ");
+        return (!words.is_empty()).then_some(words);
+    }
+    Some(terms.join(" AND "))
+}
+
+/// One candidate per recording and release, duplicates dropped
+fn from_recordings(recordings: Vec<MbRecording>) -> Vec<TagCandidate> {
+    let mut candidates = Vec::new();
+    for recording in recordings {
+        let artist = credit(
+            recording
+                .artist_credit
+                .iter()
+                .map(|c| (c.name.as_str(), c.joinphrase.as_str())),
+        );
+        let releases = recording.releases.iter().map(|release| {
+            let medium = release.media.first();
+            let track = medium.and_then(|m| {
+                let number = &m.track.first()?.number;
+                Some(match m.track_count {
+                    Some(count) => format!("{}/{}", number, count),
+                    None => number.clone(),
+                })
+            });
+            (
+                Some(release.title.clone()),
+                year_of(release.date.as_deref()),
+                track,
+            )
+        });
+        let releases: Vec<_> = if recording.releases.is_empty() {
+            vec![(None, None, None)]
+        } else {
+            releases.collect()
+        };
+        for (album, year, track) in releases {
+            push(
+                &mut candidates,
+                TagCandidate {
+                    title: recording.title.clone(),
+                    artist: artist.clone(),
+                    album,
+                    year,
+                    track,
+                    score: recording.score,
+                },
+            );
+        }
+    }
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.score));
+    candidates.truncate(MAX_CANDIDATES);
+    candidates
+}
+
+/// Fingerprint `path` with `fpcalc` and look it up on AcoustID
+async fn acoustid(client: &Client, key: &str, path: &Path) -> Result<Vec<TagCandidate>> {
+    let output = tokio::process::Command::new("fpcalc")
+        .arg("-json")
+        .arg(path)
+        .output()
+        .await?;
+    if !output.status.success() {
+        anyhow::bail!("fpcalc: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    let print: Fingerprint = serde_json::from_slice(&output.stdout)?;
+    let duration = (print.duration.round() as u64).to_string();
+    let data: AcoustIdResponse = client
+        .post("https://api.acoustid.org/v2/lookup")
+        .header(reqwest::header::USER_AGENT, MUSICBRAINZ_USER_AGENT)
+        .form(&[
+            ("client", key),
+            ("meta", "recordings releases"),
+            ("duration", duration.as_str()),
+            ("fingerprint", print.fingerprint.as_str()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(from_acoustid(data))
+}
+
+fn from_acoustid(data: AcoustIdResponse) -> Vec<TagCandidate> {
+    let mut candidates = Vec::new();
+    for result in data.results {
+        if result.score < ACOUSTID_MIN_SCORE {
+            continue;
+        }
+        let score = (result.score * 100.0).round() as u32;
+        for recording in result.recordings {
+            // Recordings without metadata are just ids
+            let Some(title) = recording.title else {
+                continue;
+            };
+            let artist = credit(
+                recording
+                    .artists
+                    .iter()
+                    .map(|a| (a.name.as_str(), a.joinphrase.as_str())),
+            );
+            let releases: Vec<_> = if recording.releases.is_empty() {
+                vec![(None, None)]
+            } else {
+                recording
+                    .releases
+                    .iter()
+                    .map(|r| {
+                        let year = r.date.as_ref().and_then(|d| d.year);
+                        (r.title.clone(), year.map(|y| y.to_string()))
+                    })
+                    .collect()
+            };
+            for (album, year) in releases {
+                push(
+                    &mut candidates,
+                    TagCandidate {
+                        title: title.clone(),
+                        artist: artist.clone(),
+                        album,
+                        year,
+                        track: None,
+                        score,
+                    },
+                );
+            }
+        }
+    }
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.score));
+    candidates.truncate(MAX_CANDIDATES);
+    candidates
+}
+
+/// "Artist feat. Other" from the credited names and what joins them
+pub(crate) fn credit<'a>(parts: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    parts
+        .map(|(name, join)| format!("{}{}", name, join))
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// "1959-08-17" -> "1959"
+pub(crate) fn year_of(date: Option<&str>) -> Option<String> {
+    let year = date?.get(..4)?;
+    year.chars()
+        .all(|c| c.is_ascii_digit())
+        .then(|| year.to_string())
+}
+
+/// Add `candidate` unless the same match is already there
+fn push(candidates: &mut Vec<TagCandidate>, candidate: TagCandidate) {
+    let same = |c: &TagCandidate| {
+        c.title == candidate.title
+            && c.artist == candidate.artist
+            && c.album == candidate.album
+            && c.year == candidate.year
+    };
+    if !candidates.iter().any(same) {
+        candidates.push(candidate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_search_results() {
+        let json = r#"{"recordings": [
+            {"title": "So What", "score": 100,
+             "artist-credit": [{"name": "Miles Davis", "joinphrase": " & "},
+                               {"name": "John Coltrane"}],
+             "releases": [
+                {"title": "Kind of Blue", "date": "1959-08-17",
+                 "media": [{"track-count": 5, "track": [{"number": "1"}]}]},
+                {"title": "Kind of Blue", "date": "1959",
+                 "media": [{"track-count": 5, "track": [{"number": "A1"}]}]},
+                {"title": "Jazz Hits", "date": "",
+                 "media": [{"track": [{"number": "7"}]}]}
+             ]},
+            {"title": "So What (live)", "score": 85}
+        ]}"#;
+        let data: MbRecordingSearch = serde_json::from_str(json).unwrap();
+        let found = from_recordings(data.recordings);
+        assert_eq!(found.len(), 3);
+        assert_eq!(
+            found[0],
+            TagCandidate {
+                title: "So What".into(),
+                artist: "Miles Davis & John Coltrane".into(),
+                album: Some("Kind of Blue".into()),
+                year: Some("1959".into()),
+                track: Some("1/5".into()),
+                score: 100,
+            }
+        );
+        // Same album and year twice is one choice
+        assert_eq!(found[1].album.as_deref(), Some("Jazz Hits"));
+        assert_eq!(found[1].year, None);
+        assert_eq!(found[1].track.as_deref(), Some("7"));
+        assert_eq!(found[2].album, None);
+        assert_eq!(found[2].value(TagField::Album), None);
+        assert_eq!(found[2].value(TagField::Title), Some("So What (live)"));
+    }
+
+    #[test]
+    fn test_acoustid_results() {
+        let json = r#"{"status": "ok", "results": [
+            {"score": 0.3, "recordings": [{"title": "Wrong", "artists": [{"name": "X"}]}]},
+            {"score": 0.97, "recordings": [
+                {"id": "bare"},
+                {"title": "Blue in Green", "artists": [{"name": "Miles Davis"}],
+                 "releases": [{"title": "Kind of Blue", "date": {"year": 1959, "month": 8}}]}
+            ]}
+        ]}"#;
+        let data: AcoustIdResponse = serde_json::from_str(json).unwrap();
+        let found = from_acoustid(data);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].title, "Blue in Green");
+        assert_eq!(found[0].year.as_deref(), Some("1959"));
+        assert_eq!(found[0].score, 97);
+    }
+
+    #[test]
+    fn test_search_query() {
+        let query = TagQuery {
+            title: "So \"What\"".into(),
+            artist: "Miles Davis".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            search_query(&query).unwrap(),
+            r#"recording:"So \"What\"" AND artist:"Miles Davis""#
+        );
+        let untagged = TagQuery {
+            path: Some("jazz/03_miles-davis_so-what.flac".into()),
+            ..Default::default()
+        };
+        assert_eq!(search_query(&untagged).unwrap(), "03 miles davis so what");
+        assert_eq!(search_query(&TagQuery::default()), None);
+    }
+}
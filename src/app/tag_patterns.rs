@@ -0,0 +1,234 @@
+//! Filename ↔ tag patterns 🔤
+//!
+//! `:name2tag %artist% - %title%` reads tags out of file names and
+//! `:tag2name %track% %title%` renames files from their tags. A pattern is
+//! text with `%field%` placeholders (`%_%` skips a part when reading); it
+//! applies to the file name without folder or extension.
+
+use crate::app::TagField;
+use crate::t;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    Text(String),
+    /// None: `%_%`, matched but not kept
+    Field(Option<TagField>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pieces: Vec<Piece>,
+}
+
+/// The field a placeholder names
+fn field(name: &str) -> Option<TagField> {
+    let name: String = name
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    Some(match name.as_str() {
+        "title" => TagField::Title,
+        "artist" => TagField::Artist,
+        "album" => TagField::Album,
+        "albumartist" => TagField::AlbumArtist,
+        "genre" => TagField::Genre,
+        "year" | "date" => TagField::Year,
+        "track" | "tracknumber" => TagField::Track,
+        "disc" | "disk" | "discnumber" => TagField::Disc,
+        "comment" => TagField::Comment,
+        _ => return None,
+    })
+}
+
+/// Characters file systems (or MPD) don't like in a name
+fn sanitize(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+impl Pattern {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut pieces = Vec::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            match rest.find('%') {
+                Some(0) => {
+                    let end = rest[1..].find('%').ok_or_else(|| t!("tag_tool.unclosed"))?;
+                    let name = &rest[1..=end];
+                    let piece = if name == "_" {
+                        Piece::Field(None)
+                    } else {
+                        Piece::Field(Some(
+                            field(name).ok_or_else(|| t!("tag_tool.unknown_field", name = name))?,
+                        ))
+                    };
+                    pieces.push(piece);
+                    rest = &rest[end + 2..];
+                }
+                Some(at) => {
+                    pieces.push(Piece::Text(rest[..at].to_string()));
+                    rest = &rest[at..];
+                }
+                None => {
+                    pieces.push(Piece::Text(rest.to_string()));
+                    rest = "";
+                }
+            }
+        }
+        if !pieces.iter().any(|p| matches!(p, Piece::Field(_))) {
+            return Err(t!("tag_tool.no_fields"));
+        }
+        Ok(Pattern { pieces })
+    }
+
+    /// Tags read from the file name `stem`, or None if it doesn't fit the
+    /// pattern. Each field ends where the text after it first appears.
+    pub fn read(&self, stem: &str) -> Result<Option<Vec<(TagField, String)>>, String> {
+        let mut fields = Vec::new();
+        let mut rest = stem;
+        let mut pieces = self.pieces.iter().peekable();
+        while let Some(piece) = pieces.next() {
+            match piece {
+                Piece::Text(text) => match rest.strip_prefix(text.as_str()) {
+                    Some(after) => rest = after,
+                    None => return Ok(None),
+                },
+                Piece::Field(field) => {
+                    let value = match pieces.peek() {
+                        Some(Piece::Text(next)) => match rest.find(next.as_str()) {
+                            Some(at) => &rest[..at],
+                            None => return Ok(None),
+                        },
+                        Some(Piece::Field(_)) => return Err(t!("tag_tool.adjacent")),
+                        None => rest,
+                    };
+                    rest = &rest[value.len()..];
+                    let value = value.trim();
+                    if value.is_empty() {
+                        return Ok(None);
+                    }
+                    if let Some(field) = field {
+                        fields.push((*field, value.to_string()));
+                    }
+                }
+            }
+        }
+        Ok(rest.is_empty().then_some(fields))
+    }
+
+    /// A file name (without extension) from the song's tags
+    pub fn fill(&self, value: impl Fn(TagField) -> Option<String>) -> Result<String, String> {
+        let mut name = String::new();
+        for piece in &self.pieces {
+            match piece {
+                Piece::Text(text) => name.push_str(text),
+                Piece::Field(None) => {}
+                Piece::Field(Some(field)) => {
+                    let v = value(*field)
+                        .filter(|v| !v.trim().is_empty())
+                        .ok_or_else(|| t!("tag_tool.missing", field = field.label()))?;
+                    // "3/12" -> "03"
+                    let v = match field {
+                        TagField::Track | TagField::Disc => {
+                            let n = v.split('/').next().unwrap_or(&v).trim();
+                            match n.parse::<u32>() {
+                                Ok(n) if *field == TagField::Track => format!("{:02}", n),
+                                Ok(n) => n.to_string(),
+                                Err(_) => n.to_string(),
+                            }
+                        }
+                        _ => v,
+                    };
+                    name.push_str(&sanitize(&v));
+                }
+            }
+        }
+        let name = name.trim().to_string();
+        if name.is_empty() || name.starts_with('.') {
+            return Err(t!("tag_tool.bad_name", name = name));
+        }
+        Ok(name)
+    }
+
+    /// Every field the pattern mentions
+    pub fn fields(&self) -> Vec<TagField> {
+        self.pieces
+            .iter()
+            .filter_map(|p| match p {
+                Piece::Field(field) => *field,
+                Piece::Text(_) => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_tags_from_names() {
+        let pattern = Pattern::parse("%track% - %artist% - %title%").unwrap();
+        assert_eq!(
+            pattern.read("03 - Miles Davis - So What").unwrap(),
+            Some(vec![
+                (TagField::Track, "03".into()),
+                (TagField::Artist, "Miles Davis".into()),
+                (TagField::Title, "So What".into()),
+            ])
+        );
+        // The title may contain the separator; earlier fields may not
+        assert_eq!(
+            pattern.read("04 - Miles Davis - Blue - Green").unwrap(),
+            Some(vec![
+                (TagField::Track, "04".into()),
+                (TagField::Artist, "Miles Davis".into()),
+                (TagField::Title, "Blue - Green".into()),
+            ])
+        );
+        assert_eq!(pattern.read("Untitled").unwrap(), None);
+        assert_eq!(pattern.read("01 -  - Empty").unwrap(), None);
+
+        let skip = Pattern::parse("%_%. %title% [%year%]").unwrap();
+        assert_eq!(
+            skip.read("7. Freddie Freeloader [1959]").unwrap(),
+            Some(vec![
+                (TagField::Title, "Freddie Freeloader".into()),
+                (TagField::Year, "1959".into()),
+            ])
+        );
+        assert_eq!(skip.read("7. Freddie [1959] extra").unwrap(), None);
+
+        assert!(Pattern::parse("%artist%%title%")
+            .unwrap()
+            .read("ab")
+            .is_err());
+        assert!(Pattern::parse("%artist - %title%").is_err());
+        assert!(Pattern::parse("%mood% - %title%").is_err());
+        assert!(Pattern::parse("no fields").is_err());
+    }
+
+    #[test]
+    fn test_names_from_tags() {
+        let pattern = Pattern::parse("%track% %artist% - %title%").unwrap();
+        let tags = |field| match field {
+            TagField::Track => Some("3/9".to_string()),
+            TagField::Artist => Some("AC/DC".to_string()),
+            TagField::Title => Some("What? ".to_string()),
+            _ => None,
+        };
+        assert_eq!(pattern.fill(tags).unwrap(), "03 AC_DC - What_");
+
+        let missing = Pattern::parse("%album% - %title%").unwrap();
+        assert!(missing.fill(tags).is_err());
+        assert_eq!(missing.fields(), vec![TagField::Album, TagField::Title]);
+    }
+}
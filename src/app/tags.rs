@@ -0,0 +1,328 @@
+//! Reading and writing tags for the tag editor (lofty) 🏷️
+//!
+//! Saving runs on a blocking thread, one file at a time, so a whole album
+//! doesn't freeze the UI; `AppEvent::TagProgress` drives the editor's
+//! progress line and `AppEvent::TagsWritten` closes it.
+
+use crate::app::events::AppEvent;
+use crate::app::tag_patterns::Pattern;
+use crate::app::{App, TagField, TagToolAction, TagToolRow};
+use crate::t;
+use lofty::file::{AudioFile, TaggedFileExt};
+use lofty::tag::{Accessor, ItemKey, Tag};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+
+/// What a save did
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WriteReport {
+    pub written: usize,
+    /// (file, error) for each file that couldn't be written
+    pub failed: Vec<(String, String)>,
+}
+
+/// `field` in `tag`, if it is set. Track and disc read "3/12" when the
+/// total is known.
+pub fn get(tag: &Tag, field: TagField) -> Option<String> {
+    let of = |n: Option<u32>, total: Option<u32>| {
+        n.map(|n| match total {
+            Some(total) => format!("{}/{}", n, total),
+            None => n.to_string(),
+        })
+    };
+    match field {
+        TagField::Title => tag.title().map(|v| v.to_string()),
+        TagField::Artist => tag.artist().map(|v| v.to_string()),
+        TagField::Album => tag.album().map(|v| v.to_string()),
+        TagField::AlbumArtist => tag.get_string(&ItemKey::AlbumArtist).map(str::to_string),
+        TagField::Genre => tag.genre().map(|v| v.to_string()),
+        TagField::Year => tag.year().map(|v| v.to_string()),
+        TagField::Track => of(tag.track(), tag.track_total()),
+        TagField::Disc => of(tag.disk(), tag.disk_total()),
+        TagField::Comment => tag.comment().map(|v| v.to_string()),
+    }
+}
+
+/// Set `field`, or remove it when `value` is empty
+fn set(tag: &mut Tag, field: TagField, value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        match field {
+            TagField::Title => tag.remove_title(),
+            TagField::Artist => tag.remove_artist(),
+            TagField::Album => tag.remove_album(),
+            TagField::AlbumArtist => tag.remove_key(&ItemKey::AlbumArtist),
+            TagField::Genre => tag.remove_genre(),
+            TagField::Year => tag.remove_year(),
+            TagField::Track => {
+                tag.remove_track();
+                tag.remove_track_total();
+            }
+            TagField::Disc => {
+                tag.remove_disk();
+                tag.remove_disk_total();
+            }
+            TagField::Comment => tag.remove_comment(),
+        }
+        return Ok(());
+    }
+    let (n, total) = number(field, value)?.unwrap_or_default();
+    match field {
+        TagField::Title => tag.set_title(value.to_string()),
+        TagField::Artist => tag.set_artist(value.to_string()),
+        TagField::Album => tag.set_album(value.to_string()),
+        TagField::AlbumArtist => {
+            tag.insert_text(ItemKey::AlbumArtist, value.to_string());
+        }
+        TagField::Genre => tag.set_genre(value.to_string()),
+        TagField::Year => tag.set_year(n),
+        TagField::Track => {
+            tag.set_track(n);
+            match total {
+                Some(total) => tag.set_track_total(total),
+                None => tag.remove_track_total(),
+            }
+        }
+        TagField::Disc => {
+            tag.set_disk(n);
+            match total {
+                Some(total) => tag.set_disk_total(total),
+                None => tag.remove_disk_total(),
+            }
+        }
+        TagField::Comment => tag.set_comment(value.to_string()),
+    }
+    Ok(())
+}
+
+/// Catch values that can't be written (a year that isn't a number) before
+/// touching any file
+pub fn check(changes: &[(TagField, String)]) -> Result<(), String> {
+    for (field, value) in changes {
+        if !value.trim().is_empty() {
+            number(*field, value)?;
+        }
+    }
+    Ok(())
+}
+
+/// The number in `value` for fields that hold one: the year, or a track
+/// or disc number with an optional total ("3" or "3/12")
+fn number(field: TagField, value: &str) -> Result<Option<(u32, Option<u32>)>, String> {
+    let bad = || t!("error.tag_number", field = field.label(), value = value);
+    let parse = |v: &str| v.trim().parse::<u32>().map_err(|_| bad());
+    match field {
+        TagField::Year => Ok(Some((parse(value)?, None))),
+        TagField::Track | TagField::Disc => match value.split_once('/') {
+            Some((n, total)) => Ok(Some((parse(n)?, Some(parse(total)?)))),
+            None => Ok(Some((parse(value)?, None))),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// `fields` of the file at `path` (None for a tag that isn't set, or a
+/// file lofty can't read)
+pub fn read(path: &Path, fields: &[TagField]) -> Vec<Option<String>> {
+    let tagged = lofty::read_from_path(path).ok();
+    let tag = tagged
+        .as_ref()
+        .and_then(|t| t.primary_tag().or_else(|| t.first_tag()));
+    fields
+        .iter()
+        .map(|&field| tag.and_then(|tag| get(tag, field)))
+        .collect()
+}
+
+/// Whether the file at `path` has ReplayGain track or album gain tags
+pub fn has_replay_gain(path: &Path) -> bool {
+    lofty::read_from_path(path).is_ok_and(|tagged| {
+        tagged.tags().iter().any(|tag| {
+            tag.get_string(&ItemKey::ReplayGainTrackGain).is_some()
+                || tag.get_string(&ItemKey::ReplayGainAlbumGain).is_some()
+        })
+    })
+}
+
+/// Apply `changes` to one file, creating a tag if it has none
+pub fn write(path: &Path, changes: &[(TagField, String)]) -> Result<(), String> {
+    let mut tagged = lofty::read_from_path(path).map_err(|e| e.to_string())?;
+    // The primary tag if there is one, else whatever the file has
+    let primary = tagged.primary_tag_type();
+    let kind = match tagged.tag(primary) {
+        Some(_) => primary,
+        None => tagged.first_tag().map_or(primary, |t| t.tag_type()),
+    };
+    if tagged.tag(kind).is_none() {
+        tagged.insert_tag(Tag::new(kind));
+    }
+    let tag = tagged.tag_mut(kind).ok_or_else(|| t!("error.no_tag"))?;
+    for (field, value) in changes {
+        set(tag, *field, value)?;
+    }
+    tagged
+        .save_to_path(path, lofty::config::WriteOptions::default())
+        .map_err(|e| e.to_string())
+}
+
+/// Set the `wanted` fields the file doesn't have yet; the rest are left
+/// as they are
+pub fn fill(path: &Path, wanted: &[(TagField, Option<String>)]) -> Result<(), String> {
+    let fields: Vec<TagField> = wanted.iter().map(|(field, _)| *field).collect();
+    let changes: Vec<(TagField, String)> = wanted
+        .iter()
+        .zip(read(path, &fields))
+        .filter_map(|((field, value), have)| match have {
+            Some(_) => None,
+            None => Some((*field, value.clone()?)),
+        })
+        .collect();
+    if changes.is_empty() {
+        return Ok(());
+    }
+    write(path, &changes)
+}
+
+/// Write each file's changes in the background, reporting progress
+pub fn spawn_write(
+    music_dir: &str,
+    jobs: Vec<(String, Vec<(TagField, String)>)>,
+    tx: mpsc::Sender<AppEvent>,
+) {
+    let music_dir = PathBuf::from(music_dir);
+    tokio::task::spawn_blocking(move || {
+        let mut report = WriteReport::default();
+        for (done, (file, changes)) in jobs.iter().enumerate() {
+            match write(&music_dir.join(file), changes) {
+                Ok(()) => report.written += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to write tags to {}: {}", file, e);
+                    report.failed.push((file.clone(), e));
+                }
+            }
+            // Progress is best effort; the final report must arrive
+            let _ = tx.try_send(AppEvent::TagProgress(done + 1));
+        }
+        if let Err(e) = tx.blocking_send(AppEvent::TagsWritten(report)) {
+            tracing::debug!("Channel closed: {}", e);
+        }
+    });
+}
+
+/// Dry run of `:name2tag` (`to_tags`) or `:tag2name` over `files`
+pub fn preview(
+    music_dir: &str,
+    files: &[String],
+    pattern: &Pattern,
+    to_tags: bool,
+) -> Vec<TagToolRow> {
+    let music_dir = Path::new(music_dir);
+    let mut targets = HashSet::new();
+    files
+        .iter()
+        .map(|file| {
+            let path = Path::new(file);
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(file);
+            let action = if to_tags {
+                pattern.read(stem).and_then(|fields| {
+                    let fields = fields.ok_or_else(|| t!("tag_tool.no_match"))?;
+                    check(&fields)?;
+                    Ok(TagToolAction::Tags(fields))
+                })
+            } else {
+                let fields = pattern.fields();
+                let values = read(&music_dir.join(file), &fields);
+                pattern
+                    .fill(|field| {
+                        let at = fields.iter().position(|f| *f == field)?;
+                        values[at].clone()
+                    })
+                    .and_then(|name| {
+                        let mut target = path.with_file_name(name);
+                        if let Some(ext) = path.extension() {
+                            target.set_extension(ext);
+                        }
+                        let target = target.to_string_lossy().to_string();
+                        if target == *file {
+                            Err(t!("tag_tool.unchanged"))
+                        } else if music_dir.join(&target).exists()
+                            || !targets.insert(target.clone())
+                        {
+                            Err(t!("tag_tool.exists"))
+                        } else {
+                            Ok(TagToolAction::Rename(target))
+                        }
+                    })
+            };
+            TagToolRow {
+                file: file.clone(),
+                action,
+            }
+        })
+        .collect()
+}
+
+/// Rename files as `:tag2name` planned. Quick enough to do right away.
+pub fn rename(music_dir: &str, rows: &[TagToolRow]) -> WriteReport {
+    let music_dir = Path::new(music_dir);
+    let mut report = WriteReport::default();
+    for row in rows {
+        if let Ok(TagToolAction::Rename(target)) = &row.action {
+            match std::fs::rename(music_dir.join(&row.file), music_dir.join(target)) {
+                Ok(()) => report.written += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to rename {}: {}", row.file, e);
+                    report.failed.push((row.file.clone(), e.to_string()));
+                }
+            }
+        }
+    }
+    report
+}
+
+/// Close the editor (or preview) once its files are written, and say how
+/// it went
+pub fn finished(app: &mut App, report: WriteReport) {
+    let total = report.written + report.failed.len();
+    app.tag_edit = None;
+    app.tag_tool = None;
+    match report.failed.first() {
+        None if total > 1 => {
+            app.marked.clear();
+            app.show_toast(&t!("toast.tags_saved_batch", count = total));
+        }
+        None => app.show_toast(&t!("toast.tags_saved")),
+        Some((_, error)) if total == 1 => app.show_error(&t!("error.tag_save", error = error)),
+        Some((file, error)) => app.show_error(&t!(
+            "error.tags_failed",
+            failed = report.failed.len(),
+            total = total,
+            file = file,
+            error = error
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_fields() {
+        assert_eq!(number(TagField::Track, "3/12"), Ok(Some((3, Some(12)))));
+        assert_eq!(number(TagField::Disc, " 2 "), Ok(Some((2, None))));
+        assert_eq!(number(TagField::Year, "1959"), Ok(Some((1959, None))));
+        assert_eq!(number(TagField::Title, "3/12"), Ok(None));
+        assert!(number(TagField::Year, "1959/60").is_err());
+        assert!(number(TagField::Track, "three").is_err());
+
+        // Empty clears the tag, so it is always fine
+        let changes = vec![
+            (TagField::Year, String::new()),
+            (TagField::Track, "4/10".to_string()),
+        ];
+        assert!(check(&changes).is_ok());
+        assert!(check(&[(TagField::Disc, "1/".to_string())]).is_err());
+    }
+}
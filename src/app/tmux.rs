@@ -1,46 +1,294 @@
+//! Living in a tmux pane 🪟
+//!
+//! Started inside tmux, Vyom opens itself in a split next to the current
+//! pane and the launching process exits. The pane it runs in is marked with
+//! the `@vyom` pane option, so launching again jumps to that pane instead
+//! of opening a second one. A split opened this way is closed again on
+//! quit, and with `status = true` the playing track is kept in the global
+//! `@vyom_status` option for `#{@vyom_status}` in `status-right`.
+
 use crate::app::cli::Args;
+use crate::player::{PlayerState, TrackInfo};
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Pane option marking the pane Vyom runs in
+const PANE_OPTION: &str = "@vyom";
+/// Global option holding the status-line text
+const STATUS_OPTION: &str = "@vyom_status";
+/// Set in the environment of a split opened by [`handle_tmux_split`]
+const SPLIT_ENV: &str = "VYOM_TMUX_SPLIT";
+const DEFAULT_SIZE: &str = "22%";
+
+/// Where the Vyom pane opens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TmuxSplit {
+    #[default]
+    Right,
+    Left,
+    Below,
+    Above,
+    /// Run in the current pane
+    Off,
+}
+
+impl TmuxSplit {
+    /// `split-window` flags, `None` for no split
+    fn flags(self) -> Option<&'static [&'static str]> {
+        match self {
+            TmuxSplit::Right => Some(&["-h"]),
+            TmuxSplit::Left => Some(&["-h", "-b"]),
+            TmuxSplit::Below => Some(&["-v"]),
+            TmuxSplit::Above => Some(&["-v", "-b"]),
+            TmuxSplit::Off => None,
+        }
+    }
+}
 
+/// `[tmux]` in config.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TmuxConfig {
+    pub split: TmuxSplit,
+    /// Pane size: a share like "22%" or a number of cells
+    pub size: String,
+    /// Keep `@vyom_status` up to date for the status line
+    pub status: bool,
+}
+
+impl Default for TmuxConfig {
+    fn default() -> Self {
+        Self {
+            split: TmuxSplit::default(),
+            size: DEFAULT_SIZE.to_string(),
+            status: false,
+        }
+    }
+}
+
+impl TmuxConfig {
+    /// The size for `split-window -l`, the default when it doesn't parse
+    pub fn size_arg(&self) -> String {
+        let size = self.size.trim();
+        let valid = match size.strip_suffix('%') {
+            Some(percent) => percent.parse::<u8>().is_ok_and(|p| (1..100).contains(&p)),
+            None => size.parse::<u16>().is_ok_and(|cells| cells > 0),
+        };
+        if valid {
+            size.to_string()
+        } else {
+            tracing::warn!("Ignoring tmux size {:?}, using {}", self.size, DEFAULT_SIZE);
+            DEFAULT_SIZE.to_string()
+        }
+    }
+}
+
+/// Run tmux, its output when it succeeded
+fn tmux(args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new("tmux")
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        tracing::debug!(
+            "tmux {:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// A pane in `list-panes` output (`id<TAB>@vyom<TAB>command`) still running
+/// Vyom, other than `own`
+pub fn find_vyom_pane(listing: &str, own: Option<&str>) -> Option<String> {
+    listing.lines().find_map(|line| {
+        let mut fields = line.split('\t');
+        let (id, mark, command) = (fields.next()?, fields.next()?, fields.next()?);
+        (mark == "1" && command.starts_with("vyom") && Some(id) != own).then(|| id.to_string())
+    })
+}
+
+/// Arguments for the Vyom started in the new pane
+pub fn child_args(args: &Args) -> Vec<String> {
+    let mut child = vec![
+        "--standalone".to_string(),
+        "--log-level".to_string(),
+        args.log_level.as_str().to_string(),
+    ];
+    // Pass controller flag if present
+    if args.controller {
+        child.push("--controller".to_string());
+    } else {
+        // Default is MPD, pass args if needed
+        #[cfg(feature = "mpd")]
+        child.extend([
+            "--mpd-host".to_string(),
+            args.mpd_host.clone(),
+            "--mpd-port".to_string(),
+            args.mpd_port.to_string(),
+        ]);
+    }
+    child
+}
+
+/// Returns true when another pane now shows Vyom and this process should exit
 pub fn handle_tmux_split(
     args: &Args,
     exe_path: &str,
     is_tmux: bool,
     is_standalone: bool,
     want_lyrics: bool,
+    config: &TmuxConfig,
 ) -> Result<bool> {
-    // Returns true if split occurred and we should exit
-    // Only auto-split if we WANT full UI (default) and aren't already the child process
-    if is_tmux && !is_standalone && want_lyrics {
-        // Auto-split logic (Tmux)
-        let mut cmd = std::process::Command::new("tmux");
-        cmd.arg("split-window")
-            .arg("-h")
-            .arg("-p")
-            .arg("22")
-            .arg(exe_path)
-            .arg("--standalone");
-
-        // Pass controller flag if present
-        if args.controller {
-            cmd.arg("--controller");
-        } else {
-            // Default is MPD, pass args if needed
-            #[cfg(feature = "mpd")]
-            {
-                cmd.arg("--mpd-host").arg(&args.mpd_host);
-                cmd.arg("--mpd-port").arg(args.mpd_port.to_string());
+    // Only for the full UI, and not in the pane we opened ourselves
+    if !is_tmux || is_standalone || !want_lyrics {
+        return Ok(false);
+    }
+
+    // Already open in this session: go there instead of opening another
+    let own = std::env::var("TMUX_PANE").ok();
+    let listing = tmux(&[
+        "list-panes",
+        "-s",
+        "-F",
+        "#{pane_id}\t#{@vyom}\t#{pane_current_command}",
+    ]);
+    if let Some(pane) = listing.and_then(|l| find_vyom_pane(&l, own.as_deref())) {
+        tmux(&["select-window", "-t", &pane]);
+        tmux(&["select-pane", "-t", &pane]);
+        return Ok(true);
+    }
+
+    let Some(flags) = config.split.flags() else {
+        return Ok(false);
+    };
+    let mut cmd = std::process::Command::new("tmux");
+    cmd.arg("split-window")
+        .args(flags)
+        .arg("-l")
+        .arg(config.size_arg())
+        .arg("-e")
+        .arg(format!("{}=1", SPLIT_ENV))
+        .arg(exe_path)
+        .args(child_args(args));
+
+    match cmd.status() {
+        Ok(status) if status.success() => Ok(true), // Split successful, parent should exit
+        Ok(status) => {
+            tracing::error!("tmux split-window failed: {}", status);
+            Ok(false)
+        }
+        Err(e) => {
+            tracing::error!("Failed to create tmux split: {}", e);
+            // Continue as single pane if split fails
+            Ok(false)
+        }
+    }
+}
+
+/// What the status line shows for `track`
+pub fn status_text(track: Option<&TrackInfo>) -> String {
+    match track {
+        Some(t) if !t.name.is_empty() => {
+            let icon = if t.state == PlayerState::Playing {
+                "▶"
+            } else {
+                "⏸"
+            };
+            if t.artist.is_empty() {
+                format!("{} {}", icon, t.name)
+            } else {
+                format!("{} {} - {}", icon, t.name, t.artist)
             }
         }
+        _ => String::new(),
+    }
+}
 
-        let status = cmd.status();
+/// The pane this Vyom runs in
+#[derive(Debug)]
+pub struct TmuxPane {
+    /// `%12`, from `$TMUX_PANE`
+    id: String,
+    /// Opened by [`handle_tmux_split`]: closed on quit
+    split: bool,
+    status: bool,
+    last_status: Option<String>,
+}
 
-        match status {
-            Ok(_) => return Ok(true), // Split successful, parent should exit
-            Err(e) => {
-                tracing::error!("Failed to create tmux split: {}", e);
-                // Continue as single pane if split fails
-            }
+impl TmuxPane {
+    /// Mark the current pane as Vyom's
+    pub fn claim(config: &TmuxConfig) -> Option<Self> {
+        let id = std::env::var("TMUX_PANE").ok().filter(|p| !p.is_empty())?;
+        tmux(&["set-option", "-p", "-t", &id, PANE_OPTION, "1"]);
+        Some(Self {
+            id,
+            split: std::env::var(SPLIT_ENV).is_ok_and(|v| v == "1"),
+            status: config.status,
+            last_status: None,
+        })
+    }
+
+    /// Push the track to `@vyom_status` when it changed
+    pub fn update_status(&mut self, track: Option<&TrackInfo>) {
+        if !self.status {
+            return;
+        }
+        let text = status_text(track);
+        if self.last_status.as_ref() == Some(&text) {
+            return;
+        }
+        self.last_status = Some(text.clone());
+        tokio::task::spawn_blocking(move || {
+            tmux(&["set-option", "-g", STATUS_OPTION, &text]);
+            tmux(&["refresh-client", "-S"]);
+        });
+    }
+
+    /// Undo the marks; close the pane if we opened it
+    pub fn release(&self) {
+        if self.status {
+            tmux(&["set-option", "-g", "-u", STATUS_OPTION]);
+            tmux(&["refresh-client", "-S"]);
+        }
+        if self.split {
+            tmux(&["kill-pane", "-t", &self.id]);
+        } else {
+            tmux(&["set-option", "-p", "-u", "-t", &self.id, PANE_OPTION]);
         }
     }
-    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_config_and_pane_lookup() {
+        let config: TmuxConfig = toml::from_str("split = \"below\"\nsize = \"12\"").unwrap();
+        assert_eq!(config.split.flags(), Some(&["-v"][..]));
+        assert_eq!(config.size_arg(), "12");
+        assert!(!config.status);
+        for bad in ["0%", "150%", "half", ""] {
+            let config = TmuxConfig {
+                size: bad.to_string(),
+                ..Default::default()
+            };
+            assert_eq!(config.size_arg(), DEFAULT_SIZE);
+        }
+
+        let listing = "%1\t\tzsh\n%2\t1\tzsh\n%3\t1\tvyom\n%4\t1\tvyom\n";
+        // A marked pane that went back to the shell doesn't count
+        assert_eq!(find_vyom_pane(listing, None).as_deref(), Some("%3"));
+        assert_eq!(find_vyom_pane(listing, Some("%3")).as_deref(), Some("%4"));
+        assert_eq!(find_vyom_pane("%1\t\tvyom\n", None), None);
+
+        use clap::Parser;
+        let child = child_args(&Args::parse_from(["vyom", "--controller"]));
+        assert_eq!(child[0], "--standalone");
+        assert!(child.contains(&"--controller".to_string()));
+    }
 }
@@ -0,0 +1,352 @@
+//! First-run setup 🧭
+//!
+//! Shown once, when there is no `config.toml` yet: where MPD lives (tested
+//! before moving on), the music folder and a theme. Finishing or skipping
+//! writes `config.toml`, so the next start goes straight to the player.
+
+use crate::app::config::{AppConfig, UserConfig};
+use crate::t;
+use crate::ui::themes;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{backend::Backend, Terminal};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    Mpd,
+    MusicDir,
+    Theme,
+    Summary,
+}
+
+/// Result of the last MPD connection test
+#[derive(Debug, Clone, PartialEq)]
+pub enum MpdCheck {
+    Untested,
+    /// Server version and number of songs in its database
+    Connected(String, u32),
+    Failed(String),
+}
+
+/// What a key did to the wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardAction {
+    None,
+    /// Test the MPD address now (the caller does it, it blocks)
+    TestMpd,
+    Finish,
+    Skip,
+}
+
+pub struct Wizard {
+    pub step: WizardStep,
+    /// No MPD step in controller mode
+    pub use_mpd: bool,
+    pub host: String,
+    pub port: String,
+    /// 0 = host, 1 = port
+    pub active_field: usize,
+    pub mpd: MpdCheck,
+    pub music_dir: String,
+    pub themes: Vec<String>,
+    pub theme_selected: usize,
+}
+
+impl Wizard {
+    pub fn new(use_mpd: bool, host: &str, port: u16) -> Self {
+        let themes = themes::available();
+        let theme_selected = themes
+            .iter()
+            .position(|t| t == themes::DEFAULT_THEME)
+            .unwrap_or(0);
+        Self {
+            step: if use_mpd {
+                WizardStep::Mpd
+            } else {
+                WizardStep::MusicDir
+            },
+            use_mpd,
+            host: host.to_string(),
+            port: port.to_string(),
+            active_field: 0,
+            mpd: MpdCheck::Untested,
+            music_dir: UserConfig::default().music_directory,
+            themes,
+            theme_selected,
+        }
+    }
+
+    pub fn steps(&self) -> Vec<WizardStep> {
+        let mut steps = vec![WizardStep::MusicDir, WizardStep::Theme, WizardStep::Summary];
+        if self.use_mpd {
+            steps.insert(0, WizardStep::Mpd);
+        }
+        steps
+    }
+
+    fn go(&mut self, delta: isize) {
+        let steps = self.steps();
+        let at = steps.iter().position(|s| *s == self.step).unwrap_or(0);
+        let to = (at as isize + delta).clamp(0, steps.len() as isize - 1);
+        self.step = steps[to as usize];
+    }
+
+    pub fn selected_theme(&self) -> Option<&str> {
+        self.themes.get(self.theme_selected).map(String::as_str)
+    }
+
+    /// The music folder with `~` expanded
+    pub fn music_path(&self) -> PathBuf {
+        let dir = self.music_dir.trim();
+        match dir.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+            None => PathBuf::from(dir),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> WizardAction {
+        match key.code {
+            KeyCode::Esc => return WizardAction::Skip,
+            KeyCode::BackTab => {
+                self.go(-1);
+                return WizardAction::None;
+            }
+            _ => {}
+        }
+
+        match self.step {
+            WizardStep::Mpd => match key.code {
+                KeyCode::Tab | KeyCode::Up | KeyCode::Down => {
+                    self.active_field = 1 - self.active_field;
+                }
+                KeyCode::Enter => {
+                    // A failed test doesn't block: Enter again goes on without MPD
+                    if matches!(self.mpd, MpdCheck::Failed(_)) {
+                        self.go(1);
+                    } else {
+                        return WizardAction::TestMpd;
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.field_mut().pop();
+                    self.mpd = MpdCheck::Untested;
+                }
+                KeyCode::Char(c) if self.active_field == 0 || c.is_ascii_digit() => {
+                    self.field_mut().push(c);
+                    self.mpd = MpdCheck::Untested;
+                }
+                _ => {}
+            },
+            WizardStep::MusicDir => match key.code {
+                KeyCode::Enter | KeyCode::Tab => self.go(1),
+                KeyCode::Backspace => {
+                    self.music_dir.pop();
+                }
+                KeyCode::Char(c) => self.music_dir.push(c),
+                _ => {}
+            },
+            WizardStep::Theme => match key.code {
+                KeyCode::Enter | KeyCode::Tab => self.go(1),
+                KeyCode::Down | KeyCode::Char('j')
+                    if self.theme_selected + 1 < self.themes.len() =>
+                {
+                    self.theme_selected += 1;
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.theme_selected = self.theme_selected.saturating_sub(1);
+                }
+                _ => {}
+            },
+            WizardStep::Summary => {
+                if key.code == KeyCode::Enter {
+                    return WizardAction::Finish;
+                }
+            }
+        }
+        WizardAction::None
+    }
+
+    fn field_mut(&mut self) -> &mut String {
+        if self.active_field == 0 {
+            &mut self.host
+        } else {
+            &mut self.port
+        }
+    }
+
+    /// Record a connection test; a working server moves on
+    pub fn set_mpd_result(&mut self, result: MpdCheck) {
+        let ok = matches!(result, MpdCheck::Connected(..));
+        self.mpd = result;
+        if ok {
+            self.go(1);
+        }
+    }
+
+    /// The config to write: defaults plus whatever was chosen
+    pub fn to_config(&self) -> UserConfig {
+        let mut config = UserConfig::default();
+        if self.use_mpd {
+            let host = self.host.trim();
+            if !host.is_empty() && host != "localhost" {
+                config.mpd_host = Some(host.to_string());
+            }
+            match self.port.parse::<u16>() {
+                Ok(port) if port != 6600 => config.mpd_port = Some(port),
+                _ => {}
+            }
+        }
+        if !self.music_dir.trim().is_empty() {
+            config.music_directory = self.music_path().to_string_lossy().to_string();
+        }
+        config.theme = self
+            .selected_theme()
+            .filter(|t| *t != themes::DEFAULT_THEME)
+            .map(str::to_string);
+        config
+    }
+}
+
+/// Connect to MPD with a short timeout and ask what it has
+#[cfg(feature = "mpd")]
+pub fn check_mpd(host: &str, port: &str) -> MpdCheck {
+    use std::net::{TcpStream, ToSocketAddrs};
+
+    let Ok(port) = port.trim().parse::<u16>() else {
+        return MpdCheck::Failed(t!("wizard.bad_port"));
+    };
+    let addr = match (host.trim(), port).to_socket_addrs() {
+        Ok(mut addrs) => match addrs.next() {
+            Some(addr) => addr,
+            None => return MpdCheck::Failed(t!("wizard.no_address")),
+        },
+        Err(e) => return MpdCheck::Failed(e.to_string()),
+    };
+    let stream = match TcpStream::connect_timeout(&addr, Duration::from_secs(2)) {
+        Ok(stream) => stream,
+        Err(e) => return MpdCheck::Failed(e.to_string()),
+    };
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+    match mpd::Client::new(stream) {
+        Ok(mut client) => {
+            let mpd::Version(major, minor, patch) = client.version;
+            let songs = client.stats().map(|s| s.songs).unwrap_or(0);
+            MpdCheck::Connected(format!("{}.{}.{}", major, minor, patch), songs)
+        }
+        Err(e) => MpdCheck::Failed(e.to_string()),
+    }
+}
+
+#[cfg(not(feature = "mpd"))]
+pub fn check_mpd(_host: &str, _port: &str) -> MpdCheck {
+    MpdCheck::Failed(t!("wizard.no_mpd_support"))
+}
+
+/// First launch: no config file yet
+pub fn is_first_run() -> bool {
+    !AppConfig::get_config_path().exists()
+}
+
+/// Run the wizard on an already set up terminal and write `config.toml`.
+/// Skipping still writes the defaults so it isn't shown again.
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, mut wizard: Wizard) -> std::io::Result<()> {
+    let config = loop {
+        let theme = themes::resolve(wizard.selected_theme()).0;
+        terminal
+            .draw(|f| crate::ui::widgets::wizard::render(f, &wizard, &theme))
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match wizard.handle_key(key) {
+            WizardAction::None => {}
+            WizardAction::TestMpd => {
+                let result = check_mpd(&wizard.host, &wizard.port);
+                wizard.set_mpd_result(result);
+            }
+            WizardAction::Finish => break wizard.to_config(),
+            WizardAction::Skip => break UserConfig::default(),
+        }
+    };
+
+    if let Err(e) = AppConfig::write_user_config(&config) {
+        tracing::warn!("Failed to write config from setup: {}", e);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(wizard: &mut Wizard, code: KeyCode) -> WizardAction {
+        wizard.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_wizard_steps_and_config() {
+        let mut wizard = Wizard::new(true, "localhost", 6600);
+        assert_eq!(wizard.step, WizardStep::Mpd);
+
+        // Editing the port only takes digits and resets the test
+        press(&mut wizard, KeyCode::Tab);
+        press(&mut wizard, KeyCode::Backspace);
+        press(&mut wizard, KeyCode::Char('x'));
+        press(&mut wizard, KeyCode::Char('1'));
+        assert_eq!(wizard.port, "6601");
+        assert_eq!(press(&mut wizard, KeyCode::Enter), WizardAction::TestMpd);
+
+        // A failed test stays, Enter again continues without MPD
+        wizard.set_mpd_result(MpdCheck::Failed("refused".into()));
+        assert_eq!(wizard.step, WizardStep::Mpd);
+        press(&mut wizard, KeyCode::Enter);
+        assert_eq!(wizard.step, WizardStep::MusicDir);
+        press(&mut wizard, KeyCode::BackTab);
+        assert_eq!(wizard.step, WizardStep::Mpd);
+        wizard.set_mpd_result(MpdCheck::Connected("0.23.5".into(), 10));
+        assert_eq!(wizard.step, WizardStep::MusicDir);
+
+        wizard.music_dir = "~/Tunes".into();
+        press(&mut wizard, KeyCode::Enter);
+        assert_eq!(wizard.step, WizardStep::Theme);
+        wizard.theme_selected = wizard.themes.iter().position(|t| t == "nord").unwrap();
+        press(&mut wizard, KeyCode::Enter);
+        assert_eq!(press(&mut wizard, KeyCode::Enter), WizardAction::Finish);
+
+        let config = wizard.to_config();
+        assert_eq!(config.mpd_host, None);
+        assert_eq!(config.mpd_port, Some(6601));
+        assert!(config.music_directory.ends_with("/Tunes"));
+        assert_eq!(config.theme.as_deref(), Some("nord"));
+
+        // Without MPD the wizard starts at the music folder
+        let wizard = Wizard::new(false, "localhost", 6600);
+        assert_eq!(wizard.step, WizardStep::MusicDir);
+        assert_eq!(wizard.steps().len(), 3);
+    }
+
+    #[cfg(feature = "mpd")]
+    #[test]
+    fn test_mpd_check_reports_refused_connection() {
+        // A port that was free a moment ago
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        assert!(matches!(
+            check_mpd("127.0.0.1", &port.to_string()),
+            MpdCheck::Failed(_)
+        ));
+        assert!(matches!(check_mpd("127.0.0.1", "x"), MpdCheck::Failed(_)));
+    }
+}
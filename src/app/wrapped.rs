@@ -0,0 +1,277 @@
+//! Vyom Wrapped: a year of listening 🎁
+//!
+//! `:wrapped [year]` sums up the listening history (see [`super::history`]):
+//! the top artists, songs and genres of the year and a heat map of the hours
+//! you listened at, by weekday. `e` in the popup saves the same as Markdown
+//! to `~/vyom-wrapped-<year>.md`.
+
+use super::history::Play;
+use crate::t;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Entries kept per top list
+const TOP: usize = 10;
+/// Heat map shades, nothing to most
+pub const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// When a play happened, as the heat map needs it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stamp {
+    pub year: i32,
+    /// Monday = 0
+    pub weekday: usize,
+    pub hour: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Wrapped {
+    pub year: i32,
+    /// Every year with history, oldest first
+    pub years: Vec<i32>,
+    pub plays: usize,
+    pub listened_ms: u64,
+    /// Different artists heard
+    pub artists: usize,
+    /// (name, plays), most first
+    pub top_artists: Vec<(String, usize)>,
+    /// ("Title — Artist", plays)
+    pub top_songs: Vec<(String, usize)>,
+    pub top_genres: Vec<(String, usize)>,
+    /// Time listened (ms) by weekday and hour
+    pub heat: [[u64; 24]; 7],
+}
+
+impl Wrapped {
+    /// Sum up the plays of `year`; `stamp` turns unix seconds into a date
+    pub fn build(plays: &[Play], year: i32, stamp: impl Fn(u64) -> Stamp) -> Self {
+        let mut wrapped = Wrapped {
+            year,
+            ..Default::default()
+        };
+        let mut years = BTreeSet::new();
+        let (mut artists, mut songs, mut genres) = (HashMap::new(), HashMap::new(), HashMap::new());
+        for play in plays {
+            let when = stamp(play.at);
+            years.insert(when.year);
+            if when.year != year {
+                continue;
+            }
+            wrapped.plays += 1;
+            wrapped.listened_ms += play.listened_ms;
+            wrapped.heat[when.weekday.min(6)][when.hour.min(23)] += play.listened_ms;
+            *artists.entry(play.artist.clone()).or_insert(0) += 1;
+            *songs
+                .entry(format!("{} — {}", play.title, play.artist))
+                .or_insert(0) += 1;
+            if let Some(genre) = play.genre.as_deref().filter(|g| !g.trim().is_empty()) {
+                *genres.entry(genre.to_string()).or_insert(0) += 1;
+            }
+        }
+        wrapped.years = years.into_iter().collect();
+        wrapped.artists = artists.len();
+        wrapped.top_artists = top(artists);
+        wrapped.top_songs = top(songs);
+        wrapped.top_genres = top(genres);
+        wrapped
+    }
+
+    pub fn hours(&self) -> f64 {
+        self.listened_ms as f64 / 3_600_000.0
+    }
+
+    /// The heat map shade of one hour cell
+    pub fn shade(&self, weekday: usize, hour: usize) -> char {
+        let most = self.heat.iter().flatten().copied().max().unwrap_or(0);
+        let value = self.heat[weekday][hour];
+        if most == 0 || value == 0 {
+            return SHADES[0];
+        }
+        let steps = (SHADES.len() - 1) as u64;
+        SHADES[(value * steps).div_ceil(most) as usize]
+    }
+
+    /// The heat map as text rows: an hour axis, then one row per weekday
+    pub fn heat_rows(&self, cell: usize) -> Vec<String> {
+        let days = t!("wrapped.weekdays");
+        let days: Vec<&str> = days.split_whitespace().collect();
+        let mut axis = "    ".to_string();
+        for hour in (0..24).step_by(6) {
+            axis.push_str(&format!("{:<width$}", hour, width = 6 * cell));
+        }
+        let mut rows = vec![axis.trim_end().to_string()];
+        for (weekday, day) in days.iter().enumerate().take(7) {
+            let mut row = format!("{:<4}", day);
+            for hour in 0..24 {
+                let shade = self.shade(weekday, hour);
+                row.extend(std::iter::repeat_n(shade, cell));
+            }
+            rows.push(row.trim_end().to_string());
+        }
+        rows
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", t!("wrapped.heading", year = self.year));
+        out.push_str(&format!("**{}**\n", self.summary()));
+        for (heading, list) in [
+            (t!("wrapped.top_artists"), &self.top_artists),
+            (t!("wrapped.top_songs"), &self.top_songs),
+            (t!("wrapped.top_genres"), &self.top_genres),
+        ] {
+            if list.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {}\n\n", heading));
+            for (i, (name, count)) in list.iter().enumerate() {
+                out.push_str(&format!(
+                    "{}. {} ({})\n",
+                    i + 1,
+                    name,
+                    t!("wrapped.plays", count = count)
+                ));
+            }
+        }
+        out.push_str(&format!("\n## {}\n\n```\n", t!("wrapped.when")));
+        for row in self.heat_rows(2) {
+            out.push_str(&row);
+            out.push('\n');
+        }
+        out.push_str("```\n");
+        out
+    }
+
+    /// "1234 plays · 85.2 hours · 120 artists"
+    pub fn summary(&self) -> String {
+        t!(
+            "wrapped.summary",
+            plays = self.plays,
+            hours = format!("{:.1}", self.hours()),
+            artists = self.artists
+        )
+    }
+
+    /// Write the Markdown report into `dir`
+    pub fn save_in(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        let path = dir.join(format!("vyom-wrapped-{}.md", self.year));
+        std::fs::write(&path, self.to_markdown())?;
+        Ok(path)
+    }
+}
+
+/// Most first, then by name
+fn top(counts: HashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut list: Vec<(String, usize)> = counts.into_iter().collect();
+    list.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    list.truncate(TOP);
+    list
+}
+
+/// Unix seconds in the local time zone
+#[cfg(unix)]
+pub fn local(secs: u64) -> Stamp {
+    // SAFETY: localtime_r only writes to the struct passed in
+    unsafe {
+        let time = secs as libc::time_t;
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&time, &mut tm).is_null() {
+            return utc(secs);
+        }
+        Stamp {
+            year: tm.tm_year + 1900,
+            weekday: ((tm.tm_wday + 6) % 7) as usize,
+            hour: tm.tm_hour as usize,
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn local(secs: u64) -> Stamp {
+    utc(secs)
+}
+
+/// Unix seconds in UTC
+pub fn utc(secs: u64) -> Stamp {
+    let days = (secs / 86_400) as i64;
+    // 1970-01-01 was a Thursday
+    let weekday = ((days + 3) % 7) as usize;
+    // Days to a civil date (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    Stamp {
+        year: (yoe + era * 400 + i64::from(month <= 2)) as i32,
+        weekday,
+        hour: (secs % 86_400 / 3600) as usize,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(at: u64, artist: &str, title: &str, genre: Option<&str>) -> Play {
+        Play {
+            at,
+            artist: artist.into(),
+            title: title.into(),
+            album: String::new(),
+            genre: genre.map(str::to_string),
+            listened_ms: 1_800_000,
+        }
+    }
+
+    #[test]
+    fn test_year_summary() {
+        // 2024-01-01 was a Monday
+        const NEW_YEAR_2024: u64 = 1_704_067_200;
+        assert_eq!(
+            utc(NEW_YEAR_2024 + 21 * 3600),
+            Stamp {
+                year: 2024,
+                weekday: 0,
+                hour: 21
+            }
+        );
+        assert_eq!(utc(NEW_YEAR_2024 - 1).year, 2023);
+
+        let plays = vec![
+            play(NEW_YEAR_2024 - 60, "Muse", "Uprising", None),
+            play(
+                NEW_YEAR_2024 + 21 * 3600,
+                "Radiohead",
+                "Airbag",
+                Some("Rock"),
+            ),
+            play(
+                NEW_YEAR_2024 + 22 * 3600,
+                "Radiohead",
+                "Airbag",
+                Some("Rock"),
+            ),
+            play(NEW_YEAR_2024 + 86_400, "Björk", "Jóga", Some("Electronic")),
+        ];
+        let wrapped = Wrapped::build(&plays, 2024, utc);
+        assert_eq!(wrapped.years, vec![2023, 2024]);
+        assert_eq!((wrapped.plays, wrapped.artists), (3, 2));
+        assert_eq!(wrapped.hours(), 1.5);
+        assert_eq!(wrapped.top_artists[0], ("Radiohead".to_string(), 2));
+        assert_eq!(wrapped.top_songs[0], ("Airbag — Radiohead".to_string(), 2));
+        assert_eq!(
+            wrapped.top_genres,
+            vec![("Rock".to_string(), 2), ("Electronic".to_string(), 1)]
+        );
+        assert_eq!(wrapped.shade(0, 21), '█');
+        assert_eq!(wrapped.shade(1, 0), '█');
+        assert_eq!(wrapped.shade(3, 12), ' ');
+
+        let markdown = wrapped.to_markdown();
+        assert!(markdown.starts_with("# Vyom Wrapped 2024\n"));
+        assert!(markdown.contains("1. Radiohead (2 plays)"));
+        assert!(markdown.contains("```\n    0"));
+    }
+}
@@ -0,0 +1,237 @@
+//! `:open <link>`: YouTube, SoundCloud & co. through yt-dlp ▶️
+//!
+//! [yt-dlp](https://github.com/yt-dlp/yt-dlp) turns the page link into the
+//! URL of its best audio stream, which MPD plays like any other stream (so
+//! it goes through the EQ and visualizer too). MPD only knows the stream's
+//! URL, so the title and uploader yt-dlp found are kept here and put on the
+//! track while it plays.
+
+use crate::app::events::AppEvent;
+use crate::app::App;
+use crate::player::TrackInfo;
+use crate::t;
+use anyhow::{anyhow, bail, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+/// A resolved link
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stream {
+    /// The audio itself (these expire after a few hours)
+    pub url: String,
+    pub title: String,
+    pub uploader: Option<String>,
+    /// Shown as the album, else the site
+    pub album: Option<String>,
+    /// The page it came from, for yt-dlp to download from
+    pub page: Option<String>,
+    /// "Youtube", "Soundcloud", ...
+    pub site: String,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Info {
+    url: String,
+    title: String,
+    uploader: Option<String>,
+    channel: Option<String>,
+    album: Option<String>,
+    webpage_url: Option<String>,
+    #[serde(default)]
+    extractor_key: String,
+    duration: Option<f64>,
+}
+
+/// Resolve `link` in the background; `play` takes it from there
+pub fn open(app: &mut App, tx: &mpsc::Sender<AppEvent>, link: &str) {
+    if !app.is_mpd {
+        app.show_warning(&t!("stream.needs_mpd"));
+        return;
+    }
+    if !(link.starts_with("https://") || link.starts_with("http://")) {
+        app.show_error(&t!("stream.not_a_link", link = link));
+        return;
+    }
+    app.show_toast(&t!("stream.resolving"));
+    let (tx, link) = (tx.clone(), link.to_string());
+    tokio::spawn(async move {
+        let result = resolve(&link).await.map_err(|e| e.to_string());
+        if let Err(e) = tx.send(AppEvent::StreamResolved(result)).await {
+            tracing::debug!("Channel closed: {}", e);
+        }
+    });
+}
+
+/// Queue the stream and start it
+#[cfg(feature = "mpd")]
+pub async fn play(app: &mut App, args: &crate::app::cli::Args, stream: Stream) {
+    let url = stream.url.clone();
+    let played = crate::app::with_mpd(app, args, move |mpd| {
+        let id = mpd.push(mpd::Song {
+            file: url,
+            ..Default::default()
+        })?;
+        mpd.switch(id)
+    })
+    .await;
+    match played {
+        Some(Ok(())) => {
+            app.show_toast(&t!("stream.playing", title = stream.title));
+            app.streams.insert(stream.url.clone(), stream);
+        }
+        Some(Err(e)) => app.show_error(&t!("error.generic", error = e)),
+        None => {}
+    }
+}
+
+/// Queue `streams` (an album, a CD), starting the one at `play`
+#[cfg(feature = "mpd")]
+pub async fn queue(
+    app: &mut App,
+    args: &crate::app::cli::Args,
+    title: &str,
+    streams: Vec<Stream>,
+    play: Option<usize>,
+) {
+    let urls: Vec<String> = streams.iter().map(|s| s.url.clone()).collect();
+    let queued = crate::app::with_mpd(app, args, move |mpd| {
+        let mut start = None;
+        for (i, url) in urls.into_iter().enumerate() {
+            let id = mpd.push(mpd::Song {
+                file: url,
+                ..Default::default()
+            })?;
+            if play == Some(i) {
+                start = Some(id);
+            }
+        }
+        match start {
+            Some(id) => mpd.switch(id),
+            None => Ok(()),
+        }
+    })
+    .await;
+    match queued {
+        Some(Ok(())) => {
+            for stream in streams {
+                app.streams.insert(stream.url.clone(), stream);
+            }
+            app.show_toast(&if play.is_some() {
+                t!("stream.playing", title = title)
+            } else {
+                t!("toast.added", name = title)
+            });
+        }
+        Some(Err(e)) => app.show_error(&t!("error.generic", error = e)),
+        None => {}
+    }
+}
+
+/// Title, uploader and length for a stream opened with `:open` or from the
+/// Bandcamp tab
+pub fn describe(track: &mut TrackInfo, streams: &HashMap<String, Stream>, music_dir: &str) {
+    let Some(path) = track.file_path.as_deref() else {
+        return;
+    };
+    let file = path
+        .strip_prefix(music_dir)
+        .map(|p| p.trim_start_matches('/'))
+        .unwrap_or(path);
+    let Some(stream) = streams.get(file) else {
+        return;
+    };
+    track.name = stream.title.clone();
+    if let Some(uploader) = &stream.uploader {
+        track.artist = uploader.clone();
+    }
+    track.album = stream.album.clone().unwrap_or_else(|| stream.site.clone());
+    if track.duration_ms == 0 {
+        track.duration_ms = stream.duration_ms;
+    }
+}
+
+/// Ask yt-dlp for the best audio of `link`
+pub async fn resolve(link: &str) -> Result<Stream> {
+    let output = tokio::process::Command::new("yt-dlp")
+        .args(["--format", "bestaudio/best", "--dump-json", "--no-playlist"])
+        .args(["--no-warnings", "--", link])
+        .output()
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => anyhow!(t!("stream.no_ytdlp")),
+            _ => e.into(),
+        })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last = stderr.lines().rev().find(|l| !l.trim().is_empty());
+        bail!(
+            "{}",
+            last.map(|l| l.trim_start_matches("ERROR: ").to_string())
+                .unwrap_or_else(|| format!("yt-dlp exited with {}", output.status))
+        );
+    }
+    parse(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// yt-dlp's `--dump-json` output
+pub fn parse(json: &str) -> Result<Stream> {
+    let info: Info = serde_json::from_str(json.trim())?;
+    Ok(Stream {
+        url: info.url,
+        title: info.title,
+        uploader: info.uploader.or(info.channel),
+        album: info.album,
+        page: info.webpage_url,
+        site: info.extractor_key,
+        duration_ms: info.duration.map_or(0, |d| (d * 1000.0) as u64),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_info() {
+        let json = r#"{"id": "dQw4w9WgXcQ", "title": "Never Gonna Give You Up",
+            "channel": "Rick Astley", "duration": 212.0, "extractor_key": "Youtube",
+            "url": "https://rr1.example.googlevideo.com/videoplayback?id=1", "ext": "webm"}"#;
+        let stream = parse(json).unwrap();
+        assert_eq!(stream.uploader.as_deref(), Some("Rick Astley"));
+        assert_eq!(stream.duration_ms, 212_000);
+        assert!(parse("{\"title\": \"no url\"}").is_err());
+
+        let mut track = TrackInfo {
+            name: stream.url.clone(),
+            artist: String::new(),
+            album: String::new(),
+            artwork_url: None,
+            duration_ms: 0,
+            position_ms: 0,
+            state: crate::player::PlayerState::Playing,
+            source: "MPD".into(),
+            codec: None,
+            bitrate: None,
+            sample_rate: None,
+            bit_depth: None,
+            file_path: Some(format!("/music/{}", stream.url)),
+            volume: None,
+            track_number: None,
+            track_total: None,
+            genre: None,
+        };
+        let streams = HashMap::from([(stream.url.clone(), stream)]);
+        describe(&mut track, &streams, "/music");
+        assert_eq!(
+            (
+                track.name.as_str(),
+                track.artist.as_str(),
+                track.album.as_str()
+            ),
+            ("Never Gonna Give You Up", "Rick Astley", "Youtube")
+        );
+        assert_eq!(track.duration_ms, 212_000);
+    }
+}
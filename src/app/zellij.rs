@@ -0,0 +1,56 @@
+//! Living in a Zellij pane 🪟
+//!
+//! The same dance as in tmux: the launching process opens a pane running
+//! `vyom --standalone` and exits. Zellij closes the pane when Vyom quits.
+//! `split` from `[tmux]` picks the side; Zellij sizes the pane itself.
+
+use crate::app::cli::Args;
+use crate::app::tmux::{child_args, TmuxConfig, TmuxSplit};
+use anyhow::Result;
+
+/// `zellij action new-pane --direction` for a split, `None` for no split
+pub fn direction(split: TmuxSplit) -> Option<&'static str> {
+    match split {
+        TmuxSplit::Right => Some("right"),
+        TmuxSplit::Left => Some("left"),
+        TmuxSplit::Below => Some("down"),
+        TmuxSplit::Above => Some("up"),
+        TmuxSplit::Off => None,
+    }
+}
+
+/// Returns true if the pane was opened and this process should exit
+pub fn handle_zellij_split(
+    args: &Args,
+    exe_path: &str,
+    is_zellij: bool,
+    is_standalone: bool,
+    want_lyrics: bool,
+    config: &TmuxConfig,
+) -> Result<bool> {
+    if !is_zellij || is_standalone || !want_lyrics {
+        return Ok(false);
+    }
+    let Some(direction) = direction(config.split) else {
+        return Ok(false);
+    };
+
+    let status = std::process::Command::new("zellij")
+        .args(["action", "new-pane", "--direction", direction])
+        .args(["--name", "Vyom", "--close-on-exit", "--"])
+        .arg(exe_path)
+        .args(child_args(args))
+        .status();
+    match status {
+        Ok(status) if status.success() => Ok(true),
+        Ok(status) => {
+            tracing::error!("zellij new-pane failed: {}", status);
+            Ok(false)
+        }
+        Err(e) => {
+            tracing::error!("Failed to open a zellij pane: {}", e);
+            // Continue in this pane
+            Ok(false)
+        }
+    }
+}
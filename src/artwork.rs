@@ -1,7 +1,19 @@
+use crate::app::GraphicsProtocol;
 use anyhow::Result;
 use image::DynamicImage;
+use ratatui_image::picker::{Picker, ProtocolType};
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Image files we'll pick up from an album folder
+const FOLDER_ART_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "bmp", "gif"];
+
+/// Longest edge kept in the disk cache (embedded art can be 3000px+)
+const CACHE_MAX_EDGE: u32 = 1200;
 
 #[derive(Debug, Deserialize)]
 struct ItunesResponse {
@@ -18,6 +30,89 @@ struct ItunesResult {
     artist_name: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct MbReleaseGroupSearch {
+    #[serde(rename = "release-groups", default)]
+    release_groups: Vec<MbReleaseGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbReleaseGroup {
+    id: String,
+    #[serde(default)]
+    score: u32,
+    title: String,
+}
+
+/// MusicBrainz asks clients to stay at or below one request per second
+const MUSICBRAINZ_INTERVAL: Duration = Duration::from_millis(1100);
+/// Search results scoring below this are probably a different album
+const MUSICBRAINZ_MIN_SCORE: u32 = 80;
+pub(crate) const MUSICBRAINZ_USER_AGENT: &str = concat!(
+    "Vyom/",
+    env!("CARGO_PKG_VERSION"),
+    " ( https://github.com/MrSyr3x/Vyom )"
+);
+
+/// Albums MusicBrainz / Cover Art Archive had nothing for, and when
+static COVER_ART_MISSES: Mutex<Option<HashMap<String, Instant>>> = Mutex::new(None);
+/// An album with no Cover Art Archive cover is looked up again after this
+const COVER_ART_MISS_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// iTunes answers per album: the artwork URL, or `None` when it had nothing
+static ITUNES_LOOKUPS: Mutex<Option<HashMap<String, ItunesLookup>>> = Mutex::new(None);
+/// An album iTunes had nothing for is searched again after this
+const ITUNES_MISS_TTL: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone)]
+struct ItunesLookup {
+    url: Option<String>,
+    at: Instant,
+}
+
+/// Lookup key for an album, ignoring case and padding
+fn album_key(artist: &str, album: &str) -> String {
+    format!(
+        "{}\u{1f}{}",
+        artist.trim().to_lowercase(),
+        album.trim().to_lowercase()
+    )
+}
+
+/// What iTunes said about `key` before: `Some(None)` for a recent miss
+fn itunes_known(key: &str, now: Instant) -> Option<Option<String>> {
+    let lookups = ITUNES_LOOKUPS.lock().ok()?;
+    let lookup = lookups.as_ref()?.get(key)?;
+    match &lookup.url {
+        Some(url) => Some(Some(url.clone())),
+        None if now.duration_since(lookup.at) < ITUNES_MISS_TTL => Some(None),
+        None => None,
+    }
+}
+
+/// Whether the Cover Art Archive recently had nothing for `key`
+fn cover_art_missed(key: &str, now: Instant) -> bool {
+    COVER_ART_MISSES
+        .lock()
+        .ok()
+        .and_then(|misses| misses.as_ref()?.get(key).copied())
+        .is_some_and(|at| now.duration_since(at) < COVER_ART_MISS_TTL)
+}
+
+fn cover_art_remember_miss(key: String, now: Instant) {
+    if let Ok(mut misses) = COVER_ART_MISSES.lock() {
+        misses.get_or_insert_with(HashMap::new).insert(key, now);
+    }
+}
+
+fn itunes_remember(key: String, url: Option<String>, now: Instant) {
+    if let Ok(mut lookups) = ITUNES_LOOKUPS.lock() {
+        lookups
+            .get_or_insert_with(HashMap::new)
+            .insert(key, ItunesLookup { url, at: now });
+    }
+}
+
 pub struct ArtworkRenderer {
     client: Client,
 }
@@ -30,12 +125,260 @@ impl ArtworkRenderer {
         Self { client }
     }
 
+    /// Pick the best image protocol the terminal supports 🖼️
+    ///
+    /// The terminal is queried first (kitty graphics, sixel, iTerm2 inline
+    /// images). Terminals that don't answer, which is common inside tmux, fall
+    /// back to environment hints. A non-`Auto` preference skips detection.
+    /// Call after entering the alternate screen.
+    pub fn graphics_picker(preferred: GraphicsProtocol) -> Picker {
+        let mut picker = Picker::from_query_stdio().unwrap_or_else(|_| Picker::halfblocks());
+        let protocol = match preferred.protocol_type() {
+            Some(forced) => Some(forced),
+            None if picker.protocol_type() == ProtocolType::Halfblocks => {
+                protocol_from_env(|key| std::env::var(key).ok())
+            }
+            None => None,
+        };
+        if let Some(protocol) = protocol {
+            tracing::info!("🖼️ Artwork protocol: {:?}", protocol);
+            picker.set_protocol_type(protocol);
+        }
+        picker
+    }
+
+    /// `~/.cache/vyom/artwork/<hash>.jpg`, keyed by artist + album.
+    /// Tracks without an album aren't cached (the key would be ambiguous).
+    fn get_cache_path(artist: &str, album: &str) -> Option<PathBuf> {
+        if album.trim().is_empty() {
+            return None;
+        }
+        let cache_root = dirs::cache_dir().unwrap_or_else(|| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            PathBuf::from(home).join(".cache")
+        });
+        Some(
+            cache_root
+                .join("vyom")
+                .join("artwork")
+                .join(Self::cache_file_name(artist, album)),
+        )
+    }
+
+    fn cache_file_name(artist: &str, album: &str) -> String {
+        // Hashed so odd characters in tags can't escape the cache dir, with
+        // a hash that stays the same across builds and Rust versions
+        use sha2::{Digest, Sha256};
+
+        let digest = Sha256::digest(album_key(artist, album).as_bytes());
+        let hex: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+        format!("{}.jpg", hex)
+    }
+
+    fn load_from_cache(path: &Path) -> Option<DynamicImage> {
+        image::open(path).ok()
+    }
+
+    fn save_to_cache(path: &Path, img: &DynamicImage) {
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                tracing::debug!("Failed to create artwork cache dir: {}", e);
+                return;
+            }
+        }
+        let img = if img.width().max(img.height()) > CACHE_MAX_EDGE {
+            img.thumbnail(CACHE_MAX_EDGE, CACHE_MAX_EDGE)
+        } else {
+            img.clone()
+        };
+        // JPEG has no alpha channel
+        if let Err(e) = img.to_rgb8().save(path) {
+            tracing::debug!("Failed to write artwork cache: {}", e);
+        }
+    }
+
+    /// Cached artwork for an album, if any (blocking)
+    pub fn cache_load(artist: &str, album: &str) -> Option<DynamicImage> {
+        Self::load_from_cache(&Self::get_cache_path(artist, album)?)
+    }
+
+    /// Store artwork for an album (blocking; failures are only logged)
+    pub fn cache_store(artist: &str, album: &str, img: &DynamicImage) {
+        if let Some(path) = Self::get_cache_path(artist, album) {
+            Self::save_to_cache(&path, img);
+        }
+    }
+
+    /// Check the disk cache without blocking the runtime
+    pub async fn cached(artist: &str, album: &str) -> Option<DynamicImage> {
+        let (artist, album) = (artist.to_string(), album.to_string());
+        tokio::task::spawn_blocking(move || Self::cache_load(&artist, &album))
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Write to the disk cache in the background
+    pub fn remember(artist: &str, album: &str, img: &DynamicImage) {
+        let (artist, album, img) = (artist.to_string(), album.to_string(), img.clone());
+        tokio::task::spawn_blocking(move || Self::cache_store(&artist, &album, &img));
+    }
+
+    /// Fallback source: MusicBrainz release-group search + Cover Art Archive.
+    /// Rate limited, and albums with no match aren't looked up again for a
+    /// while.
+    pub async fn fetch_cover_art_archive(&self, artist: &str, album: &str) -> Result<DynamicImage> {
+        let key = album_key(artist, album);
+        if cover_art_missed(&key, Instant::now()) || album.trim().is_empty() {
+            anyhow::bail!("No Cover Art Archive artwork for {} - {}", artist, album);
+        }
+
+        // Only a real miss (no match, 404) is remembered, not network
+        // trouble, a busy server or a broken image
+        let mut missing = false;
+        let result: Result<DynamicImage> = async {
+            let Some(mbid) = self.musicbrainz_release_group(artist, album).await? else {
+                missing = true;
+                anyhow::bail!("No MusicBrainz release group for {}", album);
+            };
+            let url = format!(
+                "https://coverartarchive.org/release-group/{}/front-500",
+                mbid
+            );
+            let resp = self
+                .client
+                .get(&url)
+                .header(reqwest::header::USER_AGENT, MUSICBRAINZ_USER_AGENT)
+                .send()
+                .await?;
+            missing = resp.status() == reqwest::StatusCode::NOT_FOUND;
+            let resp = resp.error_for_status()?;
+            Ok(image::load_from_memory(&resp.bytes().await?)?)
+        }
+        .await;
+
+        if missing {
+            cover_art_remember_miss(key, Instant::now());
+        }
+        result
+    }
+
+    /// The best matching release group, `None` when MusicBrainz has none
+    async fn musicbrainz_release_group(&self, artist: &str, album: &str) -> Result<Option<String>> {
+        Self::musicbrainz_throttle().await;
+
+        let query = format!(
+            "releasegroup:\"{}\" AND artist:\"{}\"",
+            lucene_escape(album),
+            lucene_escape(&Self::clean_string(artist))
+        );
+        let data: MbReleaseGroupSearch = self
+            .client
+            .get("https://musicbrainz.org/ws/2/release-group/")
+            .header(reqwest::header::USER_AGENT, MUSICBRAINZ_USER_AGENT)
+            .query(&[("query", query.as_str()), ("fmt", "json"), ("limit", "5")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(best_release_group(&data.release_groups, album))
+    }
+
+    /// Space MusicBrainz requests out across all concurrent fetches
+    pub(crate) async fn musicbrainz_throttle() {
+        static LAST_REQUEST: tokio::sync::Mutex<Option<Instant>> =
+            tokio::sync::Mutex::const_new(None);
+
+        let mut last = LAST_REQUEST.lock().await;
+        if let Some(t) = *last {
+            let wait = MUSICBRAINZ_INTERVAL.saturating_sub(t.elapsed());
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+        }
+        *last = Some(Instant::now());
+    }
+
     pub async fn fetch_image(&self, url: &str) -> Result<DynamicImage> {
         let bytes = self.client.get(url).send().await?.bytes().await?;
         let img = image::load_from_memory(&bytes)?;
         Ok(img)
     }
 
+    /// Find `cover.jpg` / `folder.png` / `front.*` next to the track.
+    /// `names` are tried in order, case-insensitively; a bare name ("cover")
+    /// matches any image extension, a full name ("cover.jpg") only itself.
+    pub fn find_folder_art(track_path: &Path, names: &[String]) -> Option<PathBuf> {
+        // Tracks of a CUE sheet (`Album.cue/track0001`) sit one level deeper
+        let dir = track_path.ancestors().skip(1).find(|p| p.is_dir())?;
+        Self::find_art_in_dir(dir, names)
+    }
+
+    fn find_art_in_dir(dir: &Path, names: &[String]) -> Option<PathBuf> {
+        let images: Vec<PathBuf> = std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| FOLDER_ART_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+            })
+            .collect();
+
+        names.iter().find_map(|name| {
+            let name = name.to_lowercase();
+            images
+                .iter()
+                .find(|path| {
+                    let lower = |s: Option<&std::ffi::OsStr>| {
+                        s.and_then(|s| s.to_str()).map(str::to_lowercase)
+                    };
+                    lower(path.file_name()).as_deref() == Some(name.as_str())
+                        || lower(path.file_stem()).as_deref() == Some(name.as_str())
+                })
+                .cloned()
+        })
+    }
+
+    /// Load folder artwork for a track (cheaper than parsing the audio file)
+    pub fn load_folder_art(track_path: &Path, names: &[String]) -> Result<DynamicImage> {
+        match Self::find_folder_art(track_path, names) {
+            Some(path) => Ok(image::open(path)?),
+            None => anyhow::bail!("No folder artwork next to {}", track_path.display()),
+        }
+    }
+
+    /// Thumbnail for a library entry: folder art, then the first track's
+    /// embedded art. Artist folders use their first album.
+    #[cfg(feature = "mpd")]
+    pub fn load_library_art(path: &Path, names: &[String]) -> Result<DynamicImage> {
+        if path.is_file() {
+            return Self::load_folder_art(path, names)
+                .or_else(|_| Self::extract_embedded_art(&path.to_string_lossy()));
+        }
+        if let Some(art) = Self::find_art_in_dir(path, names) {
+            return Ok(image::open(art)?);
+        }
+
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        entries.sort();
+        for entry in entries.iter().filter(|p| p.is_file()) {
+            if let Ok(img) = Self::extract_embedded_art(&entry.to_string_lossy()) {
+                return Ok(img);
+            }
+        }
+        for dir in entries.iter().filter(|p| p.is_dir()) {
+            if let Some(art) = Self::find_art_in_dir(dir, names) {
+                return Ok(image::open(art)?);
+            }
+        }
+        anyhow::bail!("No artwork for {}", path.display())
+    }
+
     /// Extract embedded album art from audio file (FLAC, MP3, etc.)
     #[cfg(feature = "mpd")]
     pub fn extract_embedded_art(file_path: &str) -> Result<DynamicImage> {
@@ -69,7 +412,31 @@ impl ArtworkRenderer {
         s.trim().to_string()
     }
 
+    /// Artwork URL from the iTunes search API. Answers are kept per album
+    /// (misses for a while), so going back and forth between songs of one
+    /// album doesn't run into iTunes' rate limit.
     pub async fn fetch_itunes_artwork(&self, artist: &str, album: &str) -> Result<String> {
+        let key = album_key(artist, album);
+        match itunes_known(&key, Instant::now()) {
+            Some(Some(url)) => return Ok(url),
+            Some(None) => anyhow::bail!("No results found on iTunes (cached)"),
+            None => {}
+        }
+        match self.search_itunes(artist, album).await? {
+            Some(url) => {
+                itunes_remember(key, Some(url.clone()), Instant::now());
+                Ok(url)
+            }
+            None => {
+                itunes_remember(key, None, Instant::now());
+                anyhow::bail!("No results found on iTunes")
+            }
+        }
+    }
+
+    /// `Ok(None)` when iTunes answered without a match, `Err` when it
+    /// couldn't be asked (offline, rate limited), which isn't remembered
+    async fn search_itunes(&self, artist: &str, album: &str) -> Result<Option<String>> {
         let clean_artist = Self::clean_string(artist);
         let clean_album = Self::clean_string(album);
         let term = format!("{} {}", clean_artist, clean_album);
@@ -77,6 +444,7 @@ impl ArtworkRenderer {
         // Try US first (global default), then IN (for regional content)
         // We can add more regions if needed, or make it configurable later.
         let countries = ["US", "IN"];
+        let mut last_error = None;
 
         for country in countries {
             let params = [
@@ -91,52 +459,62 @@ impl ArtworkRenderer {
                 .get("https://itunes.apple.com/search")
                 .query(&params)
                 .send()
-                .await;
-
-            // If request failed entirely (network), probably fails for all. But let's proceed.
-            if let Ok(resp) = resp_result {
-                if let Ok(data) = resp.json::<ItunesResponse>().await {
-                    // 2. Filter Candidates (Strict Artist Check)
-                    let candidates: Vec<&ItunesResult> = data
-                        .results
-                        .iter()
-                        .filter(|r| {
-                            if let Some(r_artist) = &r.artist_name {
-                                let r_clean = Self::clean_string(r_artist);
-                                r_clean.contains(&clean_artist) || clean_artist.contains(&r_clean)
+                .await
+                .and_then(|resp| resp.error_for_status());
+
+            // Offline or rate limited (403/429): try the next one, but don't
+            // take the silence for a miss
+            match resp_result {
+                Ok(resp) => match resp.json::<ItunesResponse>().await {
+                    Ok(data) => {
+                        // 2. Filter Candidates (Strict Artist Check)
+                        let candidates: Vec<&ItunesResult> = data
+                            .results
+                            .iter()
+                            .filter(|r| {
+                                if let Some(r_artist) = &r.artist_name {
+                                    let r_clean = Self::clean_string(r_artist);
+                                    r_clean.contains(&clean_artist)
+                                        || clean_artist.contains(&r_clean)
+                                } else {
+                                    true
+                                }
+                            })
+                            .collect();
+
+                        if candidates.is_empty() {
+                            continue; // Try next country
+                        }
+
+                        // 3. Find Best Match
+                        let best_match = candidates.iter().find(|r| {
+                            if let Some(name) = &r.collection_name {
+                                let r_clean = Self::clean_string(name);
+                                r_clean == clean_album
+                                    || r_clean.contains(&clean_album)
+                                    || clean_album.contains(&r_clean)
                             } else {
-                                true
+                                false
                             }
-                        })
-                        .collect();
+                        });
 
-                    if candidates.is_empty() {
-                        continue; // Try next country
-                    }
+                        let result = best_match.or(candidates.first()).copied();
 
-                    // 3. Find Best Match
-                    let best_match = candidates.iter().find(|r| {
-                        if let Some(name) = &r.collection_name {
-                            let r_clean = Self::clean_string(name);
-                            r_clean == clean_album
-                                || r_clean.contains(&clean_album)
-                                || clean_album.contains(&r_clean)
-                        } else {
-                            false
+                        if let Some(result) = result {
+                            let high_res = result.artwork_url.replace("100x100bb", "600x600bb");
+                            return Ok(Some(high_res));
                         }
-                    });
-
-                    let result = best_match.or(candidates.first()).copied();
-
-                    if let Some(result) = result {
-                        let high_res = result.artwork_url.replace("100x100bb", "600x600bb");
-                        return Ok(high_res);
                     }
-                }
+                    Err(e) => last_error = Some(e),
+                },
+                Err(e) => last_error = Some(e),
             }
         }
 
-        anyhow::bail!("No results found on iTunes")
+        match last_error {
+            Some(e) => Err(e.into()),
+            None => Ok(None),
+        }
     }
 
     pub fn render_to_lines(
@@ -255,3 +633,193 @@ impl ArtworkRenderer {
         lines
     }
 }
+
+/// Escape Lucene query syntax for a quoted MusicBrainz search term
+pub(crate) fn lucene_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Pick the best-scoring release group, preferring an exact title match
+fn best_release_group(groups: &[MbReleaseGroup], album: &str) -> Option<String> {
+    let wanted = ArtworkRenderer::clean_string(album);
+    let candidates = groups.iter().filter(|g| g.score >= MUSICBRAINZ_MIN_SCORE);
+    candidates
+        .clone()
+        .find(|g| ArtworkRenderer::clean_string(&g.title) == wanted)
+        .or_else(|| candidates.max_by_key(|g| g.score))
+        .map(|g| g.id.clone())
+}
+
+/// Guess a graphics protocol from well-known terminal environment variables
+pub fn protocol_from_env(var: impl Fn(&str) -> Option<String>) -> Option<ProtocolType> {
+    let is_set = |key: &str| var(key).is_some_and(|v| !v.is_empty());
+    let term = var("TERM").unwrap_or_default();
+    let program = var("TERM_PROGRAM").unwrap_or_default();
+
+    if is_set("KITTY_WINDOW_ID") || term == "xterm-kitty" || term == "xterm-ghostty" {
+        Some(ProtocolType::Kitty)
+    } else if program == "iTerm.app"
+        || program == "WezTerm"
+        || is_set("ITERM_SESSION_ID")
+        || var("LC_TERMINAL").is_some_and(|t| t.contains("iTerm"))
+    {
+        Some(ProtocolType::Iterm2)
+    } else if term.starts_with("foot") || term.starts_with("mlterm") || term == "contour" {
+        Some(ProtocolType::Sixel)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| map.get(key).cloned()
+    }
+
+    #[test]
+    fn test_best_release_group() {
+        let json = r#"{"release-groups": [
+            {"id": "a", "score": 100, "title": "Discovery: Remixes"},
+            {"id": "b", "score": 95, "title": "Discovery"},
+            {"id": "c", "score": 40, "title": "Discovery Live"}
+        ]}"#;
+        let data: MbReleaseGroupSearch = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            best_release_group(&data.release_groups, "Discovery"),
+            Some("b".to_string())
+        );
+        assert_eq!(
+            best_release_group(&data.release_groups, "Homework"),
+            Some("a".to_string())
+        );
+
+        let weak = &data.release_groups[2..];
+        assert_eq!(best_release_group(weak, "Discovery Live"), None);
+        assert_eq!(lucene_escape(r#"Say "Hi""#), r#"Say \"Hi\""#);
+    }
+
+    #[test]
+    fn test_itunes_lookups_are_kept_per_album() {
+        let now = Instant::now();
+        let hit = album_key("Vyom Test Artist", "Hit");
+        let miss = album_key("Vyom Test Artist", "Miss");
+        assert_eq!(itunes_known(&hit, now), None);
+
+        itunes_remember(hit.clone(), Some("https://art/600x600bb.jpg".into()), now);
+        itunes_remember(miss.clone(), None, now);
+        let later = now + Duration::from_secs(60);
+        assert_eq!(
+            itunes_known(&album_key(" vyom test artist", "HIT "), later),
+            Some(Some("https://art/600x600bb.jpg".into()))
+        );
+        assert_eq!(itunes_known(&miss, later), Some(None));
+
+        // Misses are asked about again after a while, found artwork isn't
+        let much_later = now + ITUNES_MISS_TTL;
+        assert_eq!(itunes_known(&miss, much_later), None);
+        assert!(itunes_known(&hit, much_later).is_some());
+
+        // Cover Art Archive misses expire the same way
+        let gone = album_key("vyom test artist", "no cover");
+        assert!(!cover_art_missed(&gone, now));
+        cover_art_remember_miss(gone.clone(), now);
+        assert!(cover_art_missed(&gone, later));
+        assert!(!cover_art_missed(&gone, now + COVER_ART_MISS_TTL));
+    }
+
+    #[test]
+    fn test_find_folder_art_order_and_case() {
+        let dir = std::env::temp_dir().join(format!("vyom-folder-art-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for file in ["01 - Intro.flac", "Folder.PNG", "front.jpg", "notes.txt"] {
+            std::fs::write(dir.join(file), b"").unwrap();
+        }
+        let track = dir.join("01 - Intro.flac");
+        let names = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let found = ArtworkRenderer::find_folder_art(&track, &names(&["cover", "folder", "front"]));
+        assert_eq!(found, Some(dir.join("Folder.PNG")));
+        let found = ArtworkRenderer::find_folder_art(&track, &names(&["front.jpg", "folder"]));
+        assert_eq!(found, Some(dir.join("front.jpg")));
+        assert_eq!(
+            ArtworkRenderer::find_folder_art(&track, &names(&["cover", "notes"])),
+            None
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(feature = "mpd")]
+    #[test]
+    fn test_library_art_uses_first_album_for_artist() {
+        let root = std::env::temp_dir().join(format!("vyom-lib-art-{}", std::process::id()));
+        let album = root.join("Artist").join("2001 - Album");
+        std::fs::create_dir_all(&album).unwrap();
+        DynamicImage::new_rgb8(8, 8)
+            .save(album.join("cover.png"))
+            .unwrap();
+        let names = vec!["cover".to_string()];
+
+        let img = ArtworkRenderer::load_library_art(&root.join("Artist"), &names).unwrap();
+        assert_eq!(img.width(), 8);
+        assert!(ArtworkRenderer::load_library_art(&root.join("missing"), &names).is_err());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_cache_key_ignores_case_and_padding() {
+        let a = ArtworkRenderer::cache_file_name("Daft Punk", "Discovery");
+        let b = ArtworkRenderer::cache_file_name(" daft punk", "DISCOVERY ");
+        assert_eq!(a, b);
+        assert!(a.ends_with(".jpg") && !a.contains('/'));
+        assert_ne!(a, ArtworkRenderer::cache_file_name("Daft Punk", "Homework"));
+        // Pinned: files cached by earlier runs must still be found
+        assert_eq!(a, "e748a2c0f0765bf1.jpg");
+        assert!(ArtworkRenderer::get_cache_path("Daft Punk", "").is_none());
+    }
+
+    #[test]
+    fn test_cache_roundtrip_downscales() {
+        let dir = std::env::temp_dir().join(format!("vyom-art-test-{}", std::process::id()));
+        let path = dir.join("cover.jpg");
+        let img = DynamicImage::new_rgba8(2400, 1200);
+
+        ArtworkRenderer::save_to_cache(&path, &img);
+        let loaded = ArtworkRenderer::load_from_cache(&path).unwrap();
+        assert_eq!((loaded.width(), loaded.height()), (1200, 600));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_protocol_from_env() {
+        assert_eq!(
+            protocol_from_env(env(&[("TERM", "xterm-kitty")])),
+            Some(ProtocolType::Kitty)
+        );
+        assert_eq!(
+            protocol_from_env(env(&[("TERM", "tmux-256color"), ("KITTY_WINDOW_ID", "3")])),
+            Some(ProtocolType::Kitty)
+        );
+        assert_eq!(
+            protocol_from_env(env(&[("TERM_PROGRAM", "iTerm.app")])),
+            Some(ProtocolType::Iterm2)
+        );
+        assert_eq!(
+            protocol_from_env(env(&[("TERM", "foot")])),
+            Some(ProtocolType::Sixel)
+        );
+        assert_eq!(
+            protocol_from_env(env(&[("TERM", "xterm-256color"), ("KITTY_WINDOW_ID", "")])),
+            None
+        );
+    }
+}
@@ -1,11 +1,51 @@
 //! Audio device information module
 //!
 //! Uses cpal to list audio output devices and SwitchAudioSource (macOS) to switch.
+//! On Linux, output can go straight to PipeWire instead of through cpal.
+
+#[cfg(target_os = "linux")]
+pub mod pipewire;
 
 #[cfg(feature = "eq")]
 use cpal::traits::{DeviceTrait, HostTrait};
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "macos")]
 use std::process::Command;
 
+/// How Vyom's streams are named to the sound server
+pub const APP_NAME: &str = "Vyom Music Player";
+
+/// Where local playback is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioBackend {
+    /// PipeWire when it's running, cpal otherwise
+    #[default]
+    Auto,
+    Pipewire,
+    Cpal,
+}
+
+impl AudioBackend {
+    /// Whether streams should be opened on PipeWire directly
+    #[cfg(target_os = "linux")]
+    pub fn use_pipewire(self) -> bool {
+        if self == AudioBackend::Cpal {
+            return false;
+        }
+        let available = pipewire::available();
+        if !available && self == AudioBackend::Pipewire {
+            tracing::warn!("PipeWire backend requested but not available, using cpal");
+        }
+        available
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn use_pipewire(self) -> bool {
+        false
+    }
+}
+
 /// Audio device with name
 #[derive(Clone, Debug)]
 pub struct AudioDevice {
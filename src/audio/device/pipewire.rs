@@ -0,0 +1,678 @@
+//! Native PipeWire output and capture 🎛️
+//!
+//! libpipewire is loaded at runtime, the way LADSPA plugins are, so Vyom
+//! builds without its headers and still starts where it isn't installed.
+//! Streams carry Vyom's own node name, which is what pavucontrol and helvum
+//! show instead of "ALSA plug-in [vyom]". Playback volume is the stream's
+//! own: the per-app slider there and Vyom's volume are one control. Capture
+//! taps the default output's monitor, for the visualizer when the music
+//! doesn't go through Vyom's pipeline.
+
+use super::APP_NAME;
+use crate::audio::output::{BitDepth, OutputSetting, Quantizer};
+use crate::audio::visualizer::Visualizer;
+use std::collections::VecDeque;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::ptr::{null, null_mut};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+type Ptr = *mut c_void;
+/// Fills interleaved float samples for the next buffer
+pub type Render = Box<dyn FnMut(&mut [f32]) + Send>;
+
+// spa/utils/type.h, spa/param/*.h, pipewire/stream.h
+const TYPE_ID: u32 = 3;
+const TYPE_INT: u32 = 4;
+const TYPE_ARRAY: u32 = 13;
+const TYPE_OBJECT: u32 = 15;
+const TYPE_OBJECT_FORMAT: u32 = 0x40003;
+const PARAM_ENUM_FORMAT: u32 = 3;
+const FORMAT_MEDIA_TYPE: u32 = 1;
+const FORMAT_MEDIA_SUBTYPE: u32 = 2;
+const FORMAT_AUDIO_FORMAT: u32 = 0x10001;
+const FORMAT_AUDIO_RATE: u32 = 0x10003;
+const FORMAT_AUDIO_CHANNELS: u32 = 0x10004;
+const FORMAT_AUDIO_POSITION: u32 = 0x10005;
+const MEDIA_TYPE_AUDIO: u32 = 1;
+const MEDIA_SUBTYPE_RAW: u32 = 1;
+const AUDIO_FORMAT_S16_LE: u32 = 0x103;
+const AUDIO_FORMAT_S32_LE: u32 = 0x10b;
+const AUDIO_FORMAT_F32_LE: u32 = 0x11b;
+const CHANNEL_MONO: u32 = 2;
+const CHANNEL_FL: u32 = 3;
+const CHANNEL_FR: u32 = 4;
+const CHANNEL_FC: u32 = 5;
+const CHANNEL_LFE: u32 = 6;
+const CHANNEL_SL: u32 = 7;
+const CHANNEL_SR: u32 = 8;
+const CHANNEL_RL: u32 = 12;
+const CHANNEL_RR: u32 = 13;
+const PROP_CHANNEL_VOLUMES: u32 = 0x10008;
+const DIRECTION_INPUT: u32 = 0;
+const DIRECTION_OUTPUT: u32 = 1;
+const ID_ANY: u32 = 0xffff_ffff;
+const FLAG_AUTOCONNECT: u32 = 1 << 0;
+const FLAG_MAP_BUFFERS: u32 = 1 << 2;
+const STATE_ERROR: c_int = -1;
+
+/// Capture runs at the rate the visualizer assumes
+const CAPTURE_RATE: u32 = 44100;
+
+#[repr(C)]
+struct Chunk {
+    offset: u32,
+    size: u32,
+    stride: i32,
+    flags: i32,
+}
+
+#[repr(C)]
+struct Data {
+    kind: u32,
+    flags: u32,
+    fd: i64,
+    map_offset: u32,
+    max_size: u32,
+    data: *mut c_void,
+    chunk: *mut Chunk,
+}
+
+#[repr(C)]
+struct SpaBuffer {
+    n_metas: u32,
+    n_datas: u32,
+    metas: *mut c_void,
+    datas: *mut Data,
+}
+
+#[repr(C)]
+struct PwBuffer {
+    buffer: *mut SpaBuffer,
+    user_data: *mut c_void,
+    size: u64,
+    /// Frames the graph wants this cycle (0 = unknown)
+    requested: u64,
+}
+
+#[repr(C)]
+struct StreamControl {
+    name: *const c_char,
+    flags: u32,
+    default: f32,
+    min: f32,
+    max: f32,
+    values: *mut f32,
+    n_values: u32,
+    max_values: u32,
+}
+
+/// `struct pw_stream_events`, version 0
+#[repr(C)]
+struct StreamEvents {
+    version: u32,
+    destroy: Option<unsafe extern "C" fn(Ptr)>,
+    state_changed: Option<unsafe extern "C" fn(Ptr, c_int, c_int, *const c_char)>,
+    control_info: Option<unsafe extern "C" fn(Ptr, u32, *const StreamControl)>,
+    io_changed: Option<unsafe extern "C" fn(Ptr, u32, Ptr, u32)>,
+    param_changed: Option<unsafe extern "C" fn(Ptr, u32, *const c_void)>,
+    add_buffer: Option<unsafe extern "C" fn(Ptr, *mut PwBuffer)>,
+    remove_buffer: Option<unsafe extern "C" fn(Ptr, *mut PwBuffer)>,
+    process: Option<unsafe extern "C" fn(Ptr)>,
+    drained: Option<unsafe extern "C" fn(Ptr)>,
+}
+
+static PLAYBACK_EVENTS: StreamEvents = StreamEvents {
+    version: 0,
+    destroy: None,
+    state_changed: Some(on_state_changed),
+    control_info: Some(on_playback_control),
+    io_changed: None,
+    param_changed: None,
+    add_buffer: None,
+    remove_buffer: None,
+    process: Some(on_playback_process),
+    drained: None,
+};
+
+static CAPTURE_EVENTS: StreamEvents = StreamEvents {
+    version: 0,
+    destroy: None,
+    state_changed: Some(on_state_changed),
+    control_info: None,
+    io_changed: None,
+    param_changed: None,
+    add_buffer: None,
+    remove_buffer: None,
+    process: Some(on_capture_process),
+    drained: None,
+};
+
+/// The parts of libpipewire-0.3 used here
+struct Api {
+    thread_loop_new: unsafe extern "C" fn(*const c_char, *const c_void) -> Ptr,
+    thread_loop_get_loop: unsafe extern "C" fn(Ptr) -> Ptr,
+    thread_loop_start: unsafe extern "C" fn(Ptr) -> c_int,
+    thread_loop_stop: unsafe extern "C" fn(Ptr),
+    thread_loop_destroy: unsafe extern "C" fn(Ptr),
+    thread_loop_lock: unsafe extern "C" fn(Ptr),
+    thread_loop_unlock: unsafe extern "C" fn(Ptr),
+    properties_new_string: unsafe extern "C" fn(*const c_char) -> Ptr,
+    stream_new_simple:
+        unsafe extern "C" fn(Ptr, *const c_char, Ptr, *const StreamEvents, Ptr) -> Ptr,
+    stream_connect: unsafe extern "C" fn(Ptr, u32, u32, u32, *mut *const c_void, u32) -> c_int,
+    stream_destroy: unsafe extern "C" fn(Ptr),
+    stream_dequeue_buffer: unsafe extern "C" fn(Ptr) -> *mut PwBuffer,
+    stream_queue_buffer: unsafe extern "C" fn(Ptr, *mut PwBuffer) -> c_int,
+    stream_set_control: unsafe extern "C" fn(Ptr, u32, u32, *mut f32, ...) -> c_int,
+}
+
+impl Api {
+    fn load() -> Result<Self, String> {
+        // SAFETY: a constant C string; the library stays loaded for good
+        let handle = unsafe {
+            libc::dlopen(
+                c"libpipewire-0.3.so.0".as_ptr(),
+                libc::RTLD_NOW | libc::RTLD_LOCAL,
+            )
+        };
+        if handle.is_null() {
+            return Err("libpipewire-0.3 isn't installed".to_string());
+        }
+        // SAFETY: each type is the C prototype of that symbol
+        unsafe {
+            let init: unsafe extern "C" fn(*mut c_int, *mut *mut *mut c_char) =
+                symbol(handle, c"pw_init")?;
+            init(null_mut(), null_mut());
+            Ok(Self {
+                thread_loop_new: symbol(handle, c"pw_thread_loop_new")?,
+                thread_loop_get_loop: symbol(handle, c"pw_thread_loop_get_loop")?,
+                thread_loop_start: symbol(handle, c"pw_thread_loop_start")?,
+                thread_loop_stop: symbol(handle, c"pw_thread_loop_stop")?,
+                thread_loop_destroy: symbol(handle, c"pw_thread_loop_destroy")?,
+                thread_loop_lock: symbol(handle, c"pw_thread_loop_lock")?,
+                thread_loop_unlock: symbol(handle, c"pw_thread_loop_unlock")?,
+                properties_new_string: symbol(handle, c"pw_properties_new_string")?,
+                stream_new_simple: symbol(handle, c"pw_stream_new_simple")?,
+                stream_connect: symbol(handle, c"pw_stream_connect")?,
+                stream_destroy: symbol(handle, c"pw_stream_destroy")?,
+                stream_dequeue_buffer: symbol(handle, c"pw_stream_dequeue_buffer")?,
+                stream_queue_buffer: symbol(handle, c"pw_stream_queue_buffer")?,
+                stream_set_control: symbol(handle, c"pw_stream_set_control")?,
+            })
+        }
+    }
+}
+
+/// # Safety
+/// `T` must be the function pointer type of `name`
+unsafe fn symbol<T: Copy>(handle: Ptr, name: &CStr) -> Result<T, String> {
+    let symbol = libc::dlsym(handle, name.as_ptr());
+    if symbol.is_null() {
+        return Err(format!("libpipewire has no {}", name.to_string_lossy()));
+    }
+    Ok(std::mem::transmute_copy::<*mut c_void, T>(&symbol))
+}
+
+fn api() -> Result<&'static Api, String> {
+    static API: OnceLock<Result<Api, String>> = OnceLock::new();
+    API.get_or_init(Api::load).as_ref().map_err(Clone::clone)
+}
+
+/// Whether there's a PipeWire server for this session to talk to
+pub fn available() -> bool {
+    let remote = std::env::var("PIPEWIRE_REMOTE").unwrap_or_else(|_| "pipewire-0".to_string());
+    let socket = if remote.starts_with('/') {
+        std::path::PathBuf::from(remote)
+    } else {
+        let Some(dir) = ["PIPEWIRE_RUNTIME_DIR", "XDG_RUNTIME_DIR"]
+            .iter()
+            .find_map(std::env::var_os)
+        else {
+            return false;
+        };
+        std::path::Path::new(&dir).join(remote)
+    };
+    socket.exists() && api().is_ok()
+}
+
+/// An `EnumFormat` param for raw interleaved audio, as SPA pod words
+fn audio_format(format: u32, rate: u32, channels: u32) -> Vec<u64> {
+    let mut props: Vec<u32> = vec![
+        FORMAT_MEDIA_TYPE,
+        0,
+        4,
+        TYPE_ID,
+        MEDIA_TYPE_AUDIO,
+        0,
+        FORMAT_MEDIA_SUBTYPE,
+        0,
+        4,
+        TYPE_ID,
+        MEDIA_SUBTYPE_RAW,
+        0,
+        FORMAT_AUDIO_FORMAT,
+        0,
+        4,
+        TYPE_ID,
+        format,
+        0,
+        FORMAT_AUDIO_RATE,
+        0,
+        4,
+        TYPE_INT,
+        rate,
+        0,
+        FORMAT_AUDIO_CHANNELS,
+        0,
+        4,
+        TYPE_INT,
+        channels,
+        0,
+    ];
+    let positions: &[u32] = match channels {
+        1 => &[CHANNEL_MONO],
+        2 => &[CHANNEL_FL, CHANNEL_FR],
+        4 => &[CHANNEL_FL, CHANNEL_FR, CHANNEL_RL, CHANNEL_RR],
+        6 => &[
+            CHANNEL_FL,
+            CHANNEL_FR,
+            CHANNEL_FC,
+            CHANNEL_LFE,
+            CHANNEL_RL,
+            CHANNEL_RR,
+        ],
+        8 => &[
+            CHANNEL_FL,
+            CHANNEL_FR,
+            CHANNEL_FC,
+            CHANNEL_LFE,
+            CHANNEL_RL,
+            CHANNEL_RR,
+            CHANNEL_SL,
+            CHANNEL_SR,
+        ],
+        // Left for the server to place
+        _ => &[],
+    };
+    if !positions.is_empty() {
+        let n = positions.len() as u32;
+        // An array of ids: child header, then the values, padded to 8 bytes
+        props.extend([FORMAT_AUDIO_POSITION, 0, 8 + 4 * n, TYPE_ARRAY, 4, TYPE_ID]);
+        props.extend(positions);
+        if n % 2 == 1 {
+            props.push(0);
+        }
+    }
+
+    let mut words = vec![
+        8 + 4 * props.len() as u32,
+        TYPE_OBJECT,
+        TYPE_OBJECT_FORMAT,
+        PARAM_ENUM_FORMAT,
+    ];
+    words.extend(props);
+    // In u64s: pods are read 8-byte aligned
+    words
+        .chunks(2)
+        .map(|pair| {
+            let mut bytes = [0u8; 8];
+            bytes[..4].copy_from_slice(&pair[0].to_ne_bytes());
+            bytes[4..].copy_from_slice(&pair.get(1).copied().unwrap_or(0).to_ne_bytes());
+            u64::from_ne_bytes(bytes)
+        })
+        .collect()
+}
+
+/// A connected PipeWire stream with its own loop thread; dropping it
+/// disconnects
+pub struct Stream {
+    api: &'static Api,
+    thread_loop: Ptr,
+    stream: Ptr,
+    /// What the callbacks get, boxed so its address stays put
+    _state: Box<dyn std::any::Any>,
+}
+
+impl Stream {
+    /// Playback at `sample_rate`: `render` fills interleaved float samples,
+    /// rounded to `depth` on the way out. Vyom's volume (0-100 in `volume`)
+    /// becomes the stream's, so it isn't applied in `render` as well.
+    pub fn playback(
+        sample_rate: u32,
+        channels: u16,
+        depth: BitDepth,
+        render: Render,
+        volume: Arc<AtomicU8>,
+        output: OutputSetting,
+    ) -> Result<Self, String> {
+        let format = spa_format(depth);
+        let props = format!(
+            "media.type=Audio media.category=Playback media.role=Music \
+             media.name=\"{name}\" node.name=vyom node.description=\"{name}\" \
+             application.name=\"{name}\" application.id=vyom \
+             application.icon-name=audio-x-generic node.latency=1024/{rate}",
+            name = APP_NAME,
+            rate = sample_rate
+        );
+        let state = Playback {
+            api: api()?,
+            stream: null_mut(),
+            render,
+            channels: channels.max(1) as usize,
+            depth,
+            output,
+            quantizer: Quantizer::default(),
+            scratch: Vec::new(),
+            volume,
+            applied: None,
+        };
+        Self::connect(
+            &props,
+            &PLAYBACK_EVENTS,
+            state,
+            |state, stream| state.stream = stream,
+            DIRECTION_OUTPUT,
+            &audio_format(format, sample_rate, channels as u32),
+        )
+    }
+
+    /// The default output's monitor, mixed to mono into `buffer`
+    pub fn capture(buffer: Arc<Mutex<VecDeque<f32>>>) -> Result<Self, String> {
+        let props = format!(
+            "media.type=Audio media.category=Capture media.role=Music \
+             stream.capture.sink=true node.passive=true node.name=vyom-visualizer \
+             node.description=\"{name} (visualizer)\" application.name=\"{name}\" \
+             application.id=vyom application.icon-name=audio-x-generic",
+            name = APP_NAME
+        );
+        let state = Capture {
+            api: api()?,
+            stream: null_mut(),
+            buffer,
+        };
+        Self::connect(
+            &props,
+            &CAPTURE_EVENTS,
+            state,
+            |state, stream| state.stream = stream,
+            DIRECTION_INPUT,
+            &audio_format(AUDIO_FORMAT_F32_LE, CAPTURE_RATE, 2),
+        )
+    }
+
+    fn connect<S: 'static>(
+        props: &str,
+        events: &'static StreamEvents,
+        state: S,
+        set_stream: fn(&mut S, Ptr),
+        direction: u32,
+        format: &[u64],
+    ) -> Result<Self, String> {
+        let api = api()?;
+        let props = CString::new(props).map_err(|e| e.to_string())?;
+        let mut state = Box::new(state);
+        // SAFETY: the loop isn't running until everything is set up, and
+        // `state` (boxed) outlives the stream, which Drop destroys first
+        unsafe {
+            let thread_loop = (api.thread_loop_new)(c"vyom-pipewire".as_ptr(), null());
+            if thread_loop.is_null() {
+                return Err("Can't start a PipeWire loop".to_string());
+            }
+            let stream = (api.stream_new_simple)(
+                (api.thread_loop_get_loop)(thread_loop),
+                c"Vyom Music Player".as_ptr(),
+                (api.properties_new_string)(props.as_ptr()),
+                events,
+                &mut *state as *mut S as Ptr,
+            );
+            if stream.is_null() {
+                (api.thread_loop_destroy)(thread_loop);
+                return Err("Can't reach the PipeWire server".to_string());
+            }
+            set_stream(&mut state, stream);
+
+            let mut params = [format.as_ptr() as *const c_void];
+            let result = (api.stream_connect)(
+                stream,
+                direction,
+                ID_ANY,
+                FLAG_AUTOCONNECT | FLAG_MAP_BUFFERS,
+                params.as_mut_ptr(),
+                1,
+            );
+            if result < 0 || (api.thread_loop_start)(thread_loop) < 0 {
+                (api.stream_destroy)(stream);
+                (api.thread_loop_destroy)(thread_loop);
+                return Err(format!("Can't connect a PipeWire stream ({})", result));
+            }
+            Ok(Self {
+                api,
+                thread_loop,
+                stream,
+                _state: state,
+            })
+        }
+    }
+}
+
+impl Drop for Stream {
+    fn drop(&mut self) {
+        // SAFETY: created in `connect`; the loop is locked while the stream
+        // goes, so no callback is running
+        unsafe {
+            (self.api.thread_loop_lock)(self.thread_loop);
+            (self.api.stream_destroy)(self.stream);
+            (self.api.thread_loop_unlock)(self.thread_loop);
+            (self.api.thread_loop_stop)(self.thread_loop);
+            (self.api.thread_loop_destroy)(self.thread_loop);
+        }
+    }
+}
+
+struct Playback {
+    api: &'static Api,
+    stream: Ptr,
+    render: Render,
+    channels: usize,
+    depth: BitDepth,
+    output: OutputSetting,
+    quantizer: Quantizer,
+    scratch: Vec<f32>,
+    volume: Arc<AtomicU8>,
+    /// Volume the stream was last set to or reported at; None until Vyom
+    /// has set it once, so the server's default doesn't override Vyom's
+    applied: Option<u8>,
+}
+
+impl Playback {
+    /// Vyom's volume changed: set the stream's, on the same curve the
+    /// software gain uses
+    fn push_volume(&mut self) {
+        let volume = self.volume.load(Ordering::Relaxed);
+        if self.applied == Some(volume) {
+            return;
+        }
+        self.applied = Some(volume);
+        let mut values = vec![self.output.volume_gain(volume); self.channels];
+        // SAFETY: on the loop thread; the list of controls ends with id 0
+        unsafe {
+            (self.api.stream_set_control)(
+                self.stream,
+                PROP_CHANNEL_VOLUMES,
+                values.len() as u32,
+                values.as_mut_ptr(),
+                0u32,
+            );
+        }
+    }
+}
+
+unsafe extern "C" fn on_state_changed(_: Ptr, _old: c_int, state: c_int, error: *const c_char) {
+    if state == STATE_ERROR && !error.is_null() {
+        tracing::warn!(
+            "PipeWire stream error: {}",
+            CStr::from_ptr(error).to_string_lossy()
+        );
+    }
+}
+
+unsafe extern "C" fn on_playback_control(data: Ptr, id: u32, control: *const StreamControl) {
+    let state = &mut *(data as *mut Playback);
+    if id != PROP_CHANNEL_VOLUMES || control.is_null() || state.applied.is_none() {
+        return;
+    }
+    let control = &*control;
+    if control.values.is_null() || control.n_values == 0 {
+        return;
+    }
+    let values = std::slice::from_raw_parts(control.values, control.n_values as usize);
+    let loudest = values.iter().copied().fold(0.0f32, f32::max);
+    let volume = state.output.volume_percent(loudest);
+    // Our own change coming back, give or take rounding
+    if state.applied.is_some_and(|v| v.abs_diff(volume) <= 1) {
+        return;
+    }
+    state.applied = Some(volume);
+    // Past the cap, the next cycle turns the stream back down to it
+    let capped = volume.min(state.output.max_volume());
+    state.volume.store(capped, Ordering::Relaxed);
+    state.output.report_volume(volume);
+}
+
+/// SPA sample format for an output depth. 24-bit samples are written
+/// left-aligned in 32-bit words (see `int24_word`), which is S32: S24_32
+/// keeps them in the low bits, and PipeWire would shift them up again.
+fn spa_format(depth: BitDepth) -> u32 {
+    match depth {
+        BitDepth::Float32 => AUDIO_FORMAT_F32_LE,
+        BitDepth::Int24 => AUDIO_FORMAT_S32_LE,
+        BitDepth::Int16 => AUDIO_FORMAT_S16_LE,
+    }
+}
+
+/// A quantized 24-bit sample in the top bits of its 32-bit word
+fn int24_word(value: i32) -> i32 {
+    value << 8
+}
+
+unsafe extern "C" fn on_playback_process(data: Ptr) {
+    let state = &mut *(data as *mut Playback);
+    state.push_volume();
+    let buffer = (state.api.stream_dequeue_buffer)(state.stream);
+    if buffer.is_null() {
+        return;
+    }
+    let spa = &*(*buffer).buffer;
+    if spa.n_datas > 0 && !(*spa.datas).data.is_null() {
+        let target = &mut *spa.datas;
+        let sample_bytes = match state.depth {
+            BitDepth::Int16 => 2,
+            BitDepth::Float32 | BitDepth::Int24 => 4,
+        };
+        let stride = sample_bytes * state.channels;
+        let mut frames = target.max_size as usize / stride;
+        if (*buffer).requested > 0 {
+            frames = frames.min((*buffer).requested as usize);
+        }
+        let samples = frames * state.channels;
+        state.scratch.resize(samples, 0.0);
+        (state.render)(&mut state.scratch);
+
+        let dither = state.output.dither();
+        match state.depth {
+            BitDepth::Float32 => {
+                std::slice::from_raw_parts_mut(target.data as *mut f32, samples)
+                    .copy_from_slice(&state.scratch);
+            }
+            BitDepth::Int24 => {
+                let out = std::slice::from_raw_parts_mut(target.data as *mut i32, samples);
+                for (out, &sample) in out.iter_mut().zip(state.scratch.iter()) {
+                    *out = int24_word(state.quantizer.quantize(sample, 8_388_608.0, dither));
+                }
+            }
+            BitDepth::Int16 => {
+                let out = std::slice::from_raw_parts_mut(target.data as *mut i16, samples);
+                for (out, &sample) in out.iter_mut().zip(state.scratch.iter()) {
+                    *out = state.quantizer.quantize(sample, 32_768.0, dither) as i16;
+                }
+            }
+        }
+        let chunk = &mut *target.chunk;
+        chunk.offset = 0;
+        chunk.stride = stride as i32;
+        chunk.size = (frames * stride) as u32;
+    }
+    (state.api.stream_queue_buffer)(state.stream, buffer);
+}
+
+struct Capture {
+    api: &'static Api,
+    stream: Ptr,
+    buffer: Arc<Mutex<VecDeque<f32>>>,
+}
+
+unsafe extern "C" fn on_capture_process(data: Ptr) {
+    let state = &mut *(data as *mut Capture);
+    let buffer = (state.api.stream_dequeue_buffer)(state.stream);
+    if buffer.is_null() {
+        return;
+    }
+    let spa = &*(*buffer).buffer;
+    if spa.n_datas > 0 && !(*spa.datas).data.is_null() {
+        let source = &*spa.datas;
+        let chunk = &*source.chunk;
+        let offset = chunk.offset.min(source.max_size) as usize;
+        let size = (chunk.size as usize).min(source.max_size as usize - offset);
+        let samples = std::slice::from_raw_parts(
+            (source.data as *const u8).add(offset) as *const f32,
+            size / 4,
+        );
+        Visualizer::push_samples(&state.buffer, samples, 2);
+    }
+    (state.api.stream_queue_buffer)(state.stream, buffer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_format_pod() {
+        let pod = audio_format(AUDIO_FORMAT_F32_LE, 48000, 2);
+        let words: Vec<u32> = pod
+            .iter()
+            .flat_map(|w| {
+                let bytes = w.to_ne_bytes();
+                [
+                    u32::from_ne_bytes(bytes[..4].try_into().unwrap()),
+                    u32::from_ne_bytes(bytes[4..].try_into().unwrap()),
+                ]
+            })
+            .collect();
+        // The object's size covers everything after its 8-byte header
+        assert_eq!(words[0] as usize, words.len() * 4 - 8);
+        assert_eq!(words[1], TYPE_OBJECT);
+        assert_eq!(&words[4..10], &[FORMAT_MEDIA_TYPE, 0, 4, TYPE_ID, 1, 0]);
+        // Rate and channels
+        assert_eq!(words[22..28], [FORMAT_AUDIO_RATE, 0, 4, TYPE_INT, 48000, 0]);
+        assert_eq!(words[28..34], [FORMAT_AUDIO_CHANNELS, 0, 4, TYPE_INT, 2, 0]);
+        // Stereo positions: array of two ids, already 8-byte aligned
+        assert_eq!(
+            &words[34..],
+            &[FORMAT_AUDIO_POSITION, 0, 16, TYPE_ARRAY, 4, TYPE_ID, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_int24_format_matches_sample_layout() {
+        // The declared format and the words written for it go together
+        assert_eq!(spa_format(BitDepth::Int24), AUDIO_FORMAT_S32_LE);
+        let mut quantizer = Quantizer::default();
+        let mut word = |sample: f32| int24_word(quantizer.quantize(sample, 8_388_608.0, false));
+        assert_eq!(word(1.0), 0x7fff_ff00);
+        assert_eq!(word(-1.0), i32::MIN);
+        assert_eq!(word(0.5), 0x4000_0000);
+        assert_eq!(word(-0.25), -0x2000_0000);
+    }
+}
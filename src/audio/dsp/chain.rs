@@ -0,0 +1,812 @@
+//! The DSP chain 🔗
+//!
+//! Everything between the decoder and the output device runs as an ordered
+//! list of [`DspStage`]s, set in config.toml:
+//!
+//! ```toml
+//! [dsp]
+//! chain = ["eq", "compressor", "crossfeed", "limiter"]
+//!
+//! [dsp.compressor]
+//! enabled = true
+//! threshold_db = -20.0
+//! ratio = 3.0
+//!
+//! [dsp.crossfeed]
+//! enabled = true
+//! level_db = -6.0
+//! ```
+//!
+//! Stages can be switched on and off while playing (`:dsp`); each one fades
+//! in and out over a few ms so a toggle doesn't click. The limiter only
+//! guards what the stages before it changed: with all of those off, the
+//! output stays bit-perfect. LADSPA plugins join the chain by name (see
+//! [`super::ladspa`]).
+
+use super::eq::{DspEqualizer, EqGains};
+use super::ladspa::{Plugin, PluginStage};
+use super::limiter::limiter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Toggle fade length
+const TOGGLE_MS: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageKind {
+    Eq,
+    Compressor,
+    Crossfeed,
+    Limiter,
+    /// Index in `[[dsp.plugins]]`
+    Plugin(usize),
+}
+
+impl StageKind {
+    /// The built-in stages, in their default order
+    pub const BUILT_IN: [StageKind; 4] = [
+        StageKind::Eq,
+        StageKind::Compressor,
+        StageKind::Crossfeed,
+        StageKind::Limiter,
+    ];
+
+    /// Name of a built-in stage as written in config.toml
+    pub fn name(self) -> Option<&'static str> {
+        match self {
+            StageKind::Eq => Some("eq"),
+            StageKind::Compressor => Some("compressor"),
+            StageKind::Crossfeed => Some("crossfeed"),
+            StageKind::Limiter => Some("limiter"),
+            StageKind::Plugin(_) => None,
+        }
+    }
+}
+
+/// One step of the chain. Buffers are interleaved stereo.
+pub trait DspStage: Send {
+    fn kind(&self) -> StageKind;
+    fn process(&mut self, buffer: &mut [f32]);
+    /// Forget filter state (after silence, a seek or a flush)
+    fn reset(&mut self) {}
+    /// Frames the stage delays its output by
+    fn latency_frames(&self) -> usize {
+        0
+    }
+    /// Whether the stage changes the signal right now (fading counts)
+    fn is_active(&self) -> bool;
+}
+
+/// `[dsp.compressor]` in config.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompressorConfig {
+    pub enabled: bool,
+    pub threshold_db: f32,
+    pub ratio: f32,
+    pub attack_ms: f32,
+    pub release_ms: f32,
+    /// Gain after compression, to win back the level it took
+    pub makeup_db: f32,
+}
+
+impl Default for CompressorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_db: -20.0,
+            ratio: 3.0,
+            attack_ms: 10.0,
+            release_ms: 200.0,
+            makeup_db: 0.0,
+        }
+    }
+}
+
+/// `[dsp.crossfeed]` in config.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrossfeedConfig {
+    pub enabled: bool,
+    /// How much of the other channel's lows is mixed in
+    pub level_db: f32,
+    /// Above this, channels stay apart
+    pub cutoff_hz: f32,
+}
+
+impl Default for CrossfeedConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            level_db: -6.0,
+            cutoff_hz: 700.0,
+        }
+    }
+}
+
+/// `[dsp.limiter]` in config.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LimiterConfig {
+    pub enabled: bool,
+}
+
+impl Default for LimiterConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// `[[dsp.plugins]]` in config.toml: a LADSPA plugin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginConfig {
+    /// What `chain` calls it
+    pub name: String,
+    /// Library file on the LADSPA path, or a full path
+    pub path: String,
+    /// Plugin in the library (unset = the first)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default = "default_plugin_enabled")]
+    pub enabled: bool,
+    /// Starting values by port name; the rest start at the plugin's defaults
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub controls: BTreeMap<String, f32>,
+}
+
+fn default_plugin_enabled() -> bool {
+    true
+}
+
+/// `[dsp]` in config.toml. The EQ is switched with `e` and remembered in
+/// state.toml, so it has no `enabled` here.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DspConfig {
+    /// Stage order by name; a stage left out doesn't run at all
+    pub chain: Vec<String>,
+    pub compressor: CompressorConfig,
+    pub crossfeed: CrossfeedConfig,
+    pub limiter: LimiterConfig,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<PluginConfig>,
+}
+
+impl Default for DspConfig {
+    fn default() -> Self {
+        Self {
+            chain: StageKind::BUILT_IN
+                .iter()
+                .filter_map(|kind| kind.name())
+                .map(String::from)
+                .collect(),
+            compressor: CompressorConfig::default(),
+            crossfeed: CrossfeedConfig::default(),
+            limiter: LimiterConfig::default(),
+            plugins: Vec::new(),
+        }
+    }
+}
+
+impl DspConfig {
+    /// The stage `chain` calls `name`
+    fn stage(&self, name: &str) -> Option<StageKind> {
+        StageKind::BUILT_IN
+            .into_iter()
+            .find(|kind| kind.name() == Some(name))
+            .or_else(|| {
+                self.plugins
+                    .iter()
+                    .position(|p| p.name == name)
+                    .map(StageKind::Plugin)
+            })
+    }
+
+    /// The chain without repeats or unknown names
+    pub fn stages(&self) -> Vec<StageKind> {
+        let mut stages: Vec<StageKind> = Vec::new();
+        for kind in self.chain.iter().filter_map(|name| self.stage(name)) {
+            if !stages.contains(&kind) {
+                stages.push(kind);
+            }
+        }
+        stages
+    }
+
+    /// Names in `chain` that are neither a built-in stage nor a plugin
+    pub fn unknown_stages(&self) -> Vec<&str> {
+        self.chain
+            .iter()
+            .filter(|name| self.stage(name).is_none())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// A running stage as the audio thread last built it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StageReport {
+    pub kind: StageKind,
+    pub latency_frames: usize,
+}
+
+struct Shared {
+    config: DspConfig,
+    /// Compressor, crossfeed and limiter switches (the EQ has its own),
+    /// then one per plugin
+    enabled: Vec<AtomicBool>,
+    /// Plugins as loaded, by `[[dsp.plugins]]` index
+    plugins: Vec<Result<Arc<Plugin>, String>>,
+    /// Plugin control values (f32 bits), by plugin and control
+    controls: Vec<Vec<AtomicU32>>,
+    /// What the audio thread runs, and at which rate, by chain
+    report: Mutex<Option<(u64, u32, Vec<StageReport>)>>,
+    /// Last chain built, so a replaced chain doesn't clear its successor's report
+    chains: AtomicU64,
+}
+
+/// The chain's settings, shared between the UI and the audio thread
+#[derive(Clone)]
+pub struct DspSetting(Arc<Shared>);
+
+impl Default for DspSetting {
+    fn default() -> Self {
+        Self::new(DspConfig::default())
+    }
+}
+
+impl DspSetting {
+    /// Loads the plugins the chain uses
+    pub fn new(config: DspConfig) -> Self {
+        let mut enabled = vec![
+            AtomicBool::new(config.compressor.enabled),
+            AtomicBool::new(config.crossfeed.enabled),
+            AtomicBool::new(config.limiter.enabled),
+        ];
+        enabled.extend(config.plugins.iter().map(|p| AtomicBool::new(p.enabled)));
+
+        let stages = config.stages();
+        let plugins: Vec<Result<Arc<Plugin>, String>> = config
+            .plugins
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                if !stages.contains(&StageKind::Plugin(i)) {
+                    return Err(format!("{} isn't in the chain", p.name));
+                }
+                Plugin::load(&p.path, p.label.as_deref())
+                    .map(Arc::new)
+                    .map_err(|e| format!("{}: {}", p.name, e))
+            })
+            .collect();
+        let controls = config
+            .plugins
+            .iter()
+            .zip(&plugins)
+            .map(|(p, plugin)| match plugin {
+                Ok(plugin) => plugin
+                    .info
+                    .controls
+                    .iter()
+                    .map(|c| {
+                        let value = p.controls.get(&c.name).copied().unwrap_or(c.default);
+                        AtomicU32::new(value.to_bits())
+                    })
+                    .collect(),
+                Err(_) => Vec::new(),
+            })
+            .collect();
+
+        Self(Arc::new(Shared {
+            config,
+            enabled,
+            plugins,
+            controls,
+            report: Mutex::new(None),
+            chains: AtomicU64::new(0),
+        }))
+    }
+
+    pub fn config(&self) -> &DspConfig {
+        &self.0.config
+    }
+
+    fn switch(&self, kind: StageKind) -> Option<&AtomicBool> {
+        match kind {
+            StageKind::Eq => None,
+            StageKind::Compressor => self.0.enabled.first(),
+            StageKind::Crossfeed => self.0.enabled.get(1),
+            StageKind::Limiter => self.0.enabled.get(2),
+            StageKind::Plugin(i) => self.0.enabled.get(3 + i),
+        }
+    }
+
+    /// Display name for plugins, config name for the rest
+    pub fn stage_name(&self, kind: StageKind) -> String {
+        match kind {
+            StageKind::Plugin(i) => self.0.config.plugins[i].name.clone(),
+            _ => kind.name().unwrap_or_default().to_string(),
+        }
+    }
+
+    /// The loaded plugin, or why it didn't load
+    pub fn plugin(&self, index: usize) -> Option<&Result<Arc<Plugin>, String>> {
+        self.0.plugins.get(index)
+    }
+
+    /// Problems with the configured chain: unknown names, plugins that
+    /// didn't load
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems: Vec<String> = self
+            .0
+            .config
+            .unknown_stages()
+            .iter()
+            .map(|name| format!("No DSP stage or plugin called \"{}\"", name))
+            .collect();
+        let stages = self.0.config.stages();
+        for (i, plugin) in self.0.plugins.iter().enumerate() {
+            if let (Err(e), true) = (plugin, stages.contains(&StageKind::Plugin(i))) {
+                problems.push(e.clone());
+            }
+        }
+        problems
+    }
+
+    /// Value of control `control` of plugin `plugin`
+    pub fn control(&self, plugin: usize, control: usize) -> f32 {
+        self.0
+            .controls
+            .get(plugin)
+            .and_then(|c| c.get(control))
+            .map_or(0.0, |v| f32::from_bits(v.load(Ordering::Relaxed)))
+    }
+
+    pub fn set_control(&self, plugin: usize, control: usize, value: f32) {
+        if let Some(slot) = self.0.controls.get(plugin).and_then(|c| c.get(control)) {
+            slot.store(value.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Switch state of a stage; always true for the EQ, which asks [`EqGains`]
+    pub fn is_enabled(&self, kind: StageKind) -> bool {
+        self.switch(kind)
+            .is_none_or(|enabled| enabled.load(Ordering::Relaxed))
+    }
+
+    pub fn set_enabled(&self, kind: StageKind, enabled: bool) {
+        if let Some(switch) = self.switch(kind) {
+            switch.store(enabled, Ordering::Relaxed);
+        }
+    }
+
+    /// Sample rate and stages of the running chain, `None` while no audio
+    /// runs through Vyom
+    pub fn report(&self) -> Option<(u32, Vec<StageReport>)> {
+        let report = self.0.report.lock().ok()?.clone()?;
+        Some((report.1, report.2))
+    }
+}
+
+/// Fades a stage in and out when it is switched
+pub(super) struct Switch {
+    mix: f32,
+    step: f32,
+}
+
+impl Switch {
+    pub(super) fn new(sample_rate: u32, on: bool) -> Self {
+        Self {
+            mix: if on { 1.0 } else { 0.0 },
+            step: 1.0 / (sample_rate as f32 * TOGGLE_MS / 1000.0).max(1.0),
+        }
+    }
+
+    /// Wet share for the next frame
+    pub(super) fn next(&mut self, on: bool) -> f32 {
+        self.mix = if on {
+            (self.mix + self.step).min(1.0)
+        } else {
+            (self.mix - self.step).max(0.0)
+        };
+        self.mix
+    }
+
+    pub(super) fn is_active(&self, on: bool) -> bool {
+        on || self.mix > 0.0
+    }
+}
+
+impl DspStage for DspEqualizer {
+    fn kind(&self) -> StageKind {
+        StageKind::Eq
+    }
+
+    fn process(&mut self, buffer: &mut [f32]) {
+        self.process_buffer(buffer);
+    }
+
+    fn reset(&mut self) {
+        self.reset_filters();
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_wet()
+    }
+}
+
+/// Stereo-linked feed-forward compressor, gain smoothed in dB
+pub struct Compressor {
+    setting: DspSetting,
+    switch: Switch,
+    threshold_db: f32,
+    /// dB of reduction per dB over the threshold
+    slope: f32,
+    attack: f32,
+    release: f32,
+    makeup_db: f32,
+    /// Current gain change in dB (0 or less)
+    envelope_db: f32,
+}
+
+impl Compressor {
+    pub fn new(sample_rate: u32, setting: DspSetting) -> Self {
+        let config = setting.config().compressor.clone();
+        let coeff = |ms: f32| (-1.0 / (ms.max(0.1) / 1000.0 * sample_rate as f32)).exp();
+        Self {
+            switch: Switch::new(sample_rate, setting.is_enabled(StageKind::Compressor)),
+            setting,
+            threshold_db: config.threshold_db,
+            slope: 1.0 - 1.0 / config.ratio.max(1.0),
+            attack: coeff(config.attack_ms),
+            release: coeff(config.release_ms),
+            makeup_db: config.makeup_db,
+            envelope_db: 0.0,
+        }
+    }
+}
+
+impl DspStage for Compressor {
+    fn kind(&self) -> StageKind {
+        StageKind::Compressor
+    }
+
+    fn process(&mut self, buffer: &mut [f32]) {
+        let on = self.setting.is_enabled(StageKind::Compressor);
+        if !self.switch.is_active(on) {
+            return;
+        }
+        for frame in buffer.chunks_exact_mut(2) {
+            let peak = frame[0].abs().max(frame[1].abs()).max(1e-9);
+            let over = 20.0 * peak.log10() - self.threshold_db;
+            let target = if over > 0.0 { -over * self.slope } else { 0.0 };
+            // More reduction follows the attack, less the release
+            let coeff = if target < self.envelope_db {
+                self.attack
+            } else {
+                self.release
+            };
+            self.envelope_db = target + coeff * (self.envelope_db - target);
+
+            let gain = 10f32.powf((self.envelope_db + self.makeup_db) / 20.0);
+            let mix = self.switch.next(on);
+            let wet = 1.0 + (gain - 1.0) * mix;
+            frame[0] *= wet;
+            frame[1] *= wet;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.envelope_db = 0.0;
+    }
+
+    fn is_active(&self) -> bool {
+        self.switch
+            .is_active(self.setting.is_enabled(StageKind::Compressor))
+    }
+}
+
+/// Headphone crossfeed: each ear gets the other channel's lows, minus its
+/// own, so a mono signal passes untouched and hard-panned bass stops
+/// sitting in one ear
+pub struct Crossfeed {
+    setting: DspSetting,
+    switch: Switch,
+    level: f32,
+    /// One-pole lowpass coefficient
+    alpha: f32,
+    low_left: f32,
+    low_right: f32,
+}
+
+impl Crossfeed {
+    pub fn new(sample_rate: u32, setting: DspSetting) -> Self {
+        let config = setting.config().crossfeed.clone();
+        let cutoff = config.cutoff_hz.clamp(20.0, sample_rate as f32 / 2.0 - 1.0);
+        Self {
+            switch: Switch::new(sample_rate, setting.is_enabled(StageKind::Crossfeed)),
+            setting,
+            level: 10f32.powf(config.level_db.min(0.0) / 20.0),
+            alpha: 1.0 - (-2.0 * std::f32::consts::PI * cutoff / sample_rate as f32).exp(),
+            low_left: 0.0,
+            low_right: 0.0,
+        }
+    }
+}
+
+impl DspStage for Crossfeed {
+    fn kind(&self) -> StageKind {
+        StageKind::Crossfeed
+    }
+
+    fn process(&mut self, buffer: &mut [f32]) {
+        let on = self.setting.is_enabled(StageKind::Crossfeed);
+        if !self.switch.is_active(on) {
+            return;
+        }
+        for frame in buffer.chunks_exact_mut(2) {
+            self.low_left += self.alpha * (frame[0] - self.low_left);
+            self.low_right += self.alpha * (frame[1] - self.low_right);
+            let amount = self.level * self.switch.next(on);
+            let shift = amount * (self.low_right - self.low_left);
+            frame[0] += shift;
+            frame[1] -= shift;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.low_left = 0.0;
+        self.low_right = 0.0;
+    }
+
+    fn is_active(&self) -> bool {
+        self.switch
+            .is_active(self.setting.is_enabled(StageKind::Crossfeed))
+    }
+}
+
+/// The soft-knee [`limiter`] as a stage
+pub struct Limiter {
+    setting: DspSetting,
+    switch: Switch,
+}
+
+impl Limiter {
+    pub fn new(sample_rate: u32, setting: DspSetting) -> Self {
+        Self {
+            switch: Switch::new(sample_rate, setting.is_enabled(StageKind::Limiter)),
+            setting,
+        }
+    }
+}
+
+impl DspStage for Limiter {
+    fn kind(&self) -> StageKind {
+        StageKind::Limiter
+    }
+
+    fn process(&mut self, buffer: &mut [f32]) {
+        let on = self.setting.is_enabled(StageKind::Limiter);
+        if !self.switch.is_active(on) {
+            return;
+        }
+        for frame in buffer.chunks_exact_mut(2) {
+            let mix = self.switch.next(on);
+            for sample in frame.iter_mut() {
+                *sample += (limiter(*sample) - *sample) * mix;
+            }
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.switch
+            .is_active(self.setting.is_enabled(StageKind::Limiter))
+    }
+}
+
+/// The stages in config order, built for one sample rate
+pub struct DspChain {
+    stages: Vec<Box<dyn DspStage>>,
+    setting: DspSetting,
+    id: u64,
+}
+
+impl DspChain {
+    pub fn new(sample_rate: u32, eq_gains: EqGains, setting: DspSetting) -> Self {
+        let stages: Vec<Box<dyn DspStage>> = setting
+            .config()
+            .stages()
+            .into_iter()
+            .filter_map(|kind| -> Option<Box<dyn DspStage>> {
+                Some(match kind {
+                    StageKind::Eq => {
+                        Box::new(DspEqualizer::new(sample_rate as f32, eq_gains.clone()))
+                    }
+                    StageKind::Compressor => {
+                        Box::new(Compressor::new(sample_rate, setting.clone()))
+                    }
+                    StageKind::Crossfeed => Box::new(Crossfeed::new(sample_rate, setting.clone())),
+                    StageKind::Limiter => Box::new(Limiter::new(sample_rate, setting.clone())),
+                    StageKind::Plugin(i) => {
+                        // Load errors were reported when the setting was made
+                        let plugin = setting.plugin(i)?.as_ref().ok()?.clone();
+                        match PluginStage::new(plugin, i, sample_rate, setting.clone()) {
+                            Ok(stage) => Box::new(stage),
+                            Err(e) => {
+                                tracing::warn!("DSP plugin skipped: {}", e);
+                                return None;
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+        let id = setting.0.chains.fetch_add(1, Ordering::Relaxed) + 1;
+        let report = stages
+            .iter()
+            .map(|stage| StageReport {
+                kind: stage.kind(),
+                latency_frames: stage.latency_frames(),
+            })
+            .collect();
+        if let Ok(mut slot) = setting.0.report.lock() {
+            *slot = Some((id, sample_rate, report));
+        }
+        Self {
+            stages,
+            setting,
+            id,
+        }
+    }
+
+    /// Run a buffer of interleaved stereo through every stage in turn
+    pub fn process_buffer(&mut self, buffer: &mut [f32]) {
+        let mut changed = false;
+        for stage in &mut self.stages {
+            // Nothing to guard: leave the samples exactly as they came
+            if stage.kind() == StageKind::Limiter && !changed {
+                continue;
+            }
+            let active = stage.is_active();
+            stage.process(buffer);
+            changed |= active;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for stage in &mut self.stages {
+            stage.reset();
+        }
+    }
+}
+
+impl Drop for DspChain {
+    fn drop(&mut self) {
+        if let Ok(mut slot) = self.setting.0.report.lock() {
+            if slot.as_ref().is_some_and(|r| r.0 == self.id) {
+                *slot = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setting(toml: &str) -> DspSetting {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            dsp: DspConfig,
+        }
+        DspSetting::new(toml::from_str::<Wrapper>(toml).unwrap().dsp)
+    }
+
+    #[test]
+    fn test_chain_order_from_config() {
+        let setting = setting(
+            r#"
+            [dsp]
+            chain = ["limiter", "eq", "limiter", "crossfeed"]
+            [dsp.crossfeed]
+            enabled = true
+            "#,
+        );
+        assert_eq!(
+            setting.config().stages(),
+            vec![StageKind::Limiter, StageKind::Eq, StageKind::Crossfeed]
+        );
+        assert!(setting.is_enabled(StageKind::Crossfeed));
+        assert!(!setting.is_enabled(StageKind::Compressor));
+        // Defaults fill in what isn't given
+        assert_eq!(setting.config().compressor.ratio, 3.0);
+
+        let chain = DspChain::new(48000, EqGains::new(), setting.clone());
+        let (rate, stages) = setting.report().unwrap();
+        assert_eq!(rate, 48000);
+        assert_eq!(stages.len(), 3);
+        assert!(stages.iter().all(|s| s.latency_frames == 0));
+        // Rebuilt for a new rate: the old chain goes after the new one came
+        let rebuilt = DspChain::new(44100, EqGains::new(), setting.clone());
+        drop(chain);
+        assert_eq!(setting.report().unwrap().0, 44100);
+        drop(rebuilt);
+        assert!(setting.report().is_none());
+    }
+
+    #[test]
+    fn test_missing_plugin_is_reported_and_skipped() {
+        let setting = setting(
+            r#"
+            [dsp]
+            chain = ["eq", "room", "reverb", "limiter"]
+            [[dsp.plugins]]
+            name = "room"
+            path = "/nonexistent/room.so"
+            "#,
+        );
+        assert_eq!(
+            setting.config().stages(),
+            vec![StageKind::Eq, StageKind::Plugin(0), StageKind::Limiter]
+        );
+        assert_eq!(setting.stage_name(StageKind::Plugin(0)), "room");
+        let problems = setting.problems();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("reverb"));
+        assert!(problems[1].starts_with("room: "));
+
+        let _chain = DspChain::new(48000, EqGains::new(), setting.clone());
+        let (_, stages) = setting.report().unwrap();
+        assert_eq!(
+            stages.iter().map(|s| s.kind).collect::<Vec<_>>(),
+            vec![StageKind::Eq, StageKind::Limiter]
+        );
+    }
+
+    #[test]
+    fn test_idle_chain_is_bit_perfect() {
+        let gains = EqGains::new();
+        gains.set_enabled(false);
+        let setting = DspSetting::default();
+        let mut chain = DspChain::new(44100, gains, setting.clone());
+
+        // Hot enough that the limiter would touch it
+        let input: Vec<f32> = (0..512).map(|i| ((i as f32) * 0.05).sin() * 0.99).collect();
+        let mut buffer = input.clone();
+        chain.process_buffer(&mut buffer);
+        assert_eq!(buffer, input);
+
+        // The compressor turns loud audio down, after its fade in
+        setting.set_enabled(StageKind::Compressor, true);
+        for _ in 0..20 {
+            buffer.clone_from(&input);
+            chain.process_buffer(&mut buffer);
+        }
+        let peak = buffer.iter().fold(0f32, |peak, s| peak.max(s.abs()));
+        assert!(peak < 0.5, "peak {}", peak);
+    }
+
+    #[test]
+    fn test_crossfeed_leaves_mono_alone() {
+        let setting = DspSetting::default();
+        setting.set_enabled(StageKind::Crossfeed, true);
+        let mut crossfeed = Crossfeed::new(44100, setting);
+        let mut buffer: Vec<f32> = (0..256)
+            .flat_map(|i| {
+                let s = ((i as f32) * 0.1).sin() * 0.5;
+                [s, s]
+            })
+            .collect();
+        let input = buffer.clone();
+        crossfeed.process(&mut buffer);
+        assert_eq!(buffer, input);
+
+        // Left only: some of it reaches the right
+        let mut left: Vec<f32> = (0..256).flat_map(|_| [0.5, 0.0]).collect();
+        crossfeed.process(&mut left);
+        assert!(left[511] > 0.1);
+        assert!(left[510] < 0.5);
+    }
+}
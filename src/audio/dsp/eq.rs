@@ -3,7 +3,6 @@
 #[cfg(feature = "eq")]
 use biquad::{Biquad, Coefficients, DirectForm1, ToHertz, Type, Q_BUTTERWORTH_F32};
 
-use super::limiter::limiter;
 use std::sync::{Arc, RwLock};
 
 /// 10-band EQ center frequencies in Hz
@@ -28,7 +27,6 @@ pub fn value_to_db(value: f32) -> f32 {
 
 /// Convert dB gain (-12 to +12) to app EQ value (0.0-1.0)
 #[must_use]
-#[allow(dead_code)]
 pub fn db_to_value(db: f32) -> f32 {
     (db / 24.0) + 0.5
 }
@@ -134,6 +132,10 @@ impl EqGains {
     }
 }
 
+/// Outgoing (left, right, preamp) chain during a curve crossfade
+#[cfg(feature = "eq")]
+type FadeChain = (Vec<DirectForm1<f32>>, Vec<DirectForm1<f32>>, f32);
+
 /// 10-Band Parametric Equalizer using biquad filters
 #[cfg(feature = "eq")]
 pub struct DspEqualizer {
@@ -154,6 +156,12 @@ pub struct DspEqualizer {
     target_mix: f32,
     /// Crossfade speed (samples to transition)
     crossfade_speed: f32,
+    /// Previous filter chain, kept alive briefly after a curve change (A/B swap, preset)
+    fade_from: Option<FadeChain>,
+    /// Progress of the curve crossfade (0.0 = old curve, 1.0 = new curve)
+    fade_pos: f32,
+    /// Curve crossfade speed (~30ms)
+    curve_fade_speed: f32,
 }
 
 #[cfg(feature = "eq")]
@@ -172,6 +180,8 @@ impl DspEqualizer {
 
         // Crossfade speed: ~10ms at sample rate
         let crossfade_speed = 1.0 / (sample_rate * 0.010);
+        // Start where the switch is, so a disabled EQ never touches a sample
+        let mix = if gains.is_enabled() { 1.0 } else { 0.0 };
 
         Self {
             filters_left,
@@ -180,9 +190,12 @@ impl DspEqualizer {
             gains,
             last_gains: [0.0; 10],
             preamp: 1.0, // No reduction initially (flat EQ)
-            mix: 1.0,    // Start with EQ active
-            target_mix: 1.0,
+            mix,
+            target_mix: mix,
             crossfade_speed,
+            fade_from: None,
+            fade_pos: 1.0,
+            curve_fade_speed: 1.0 / (sample_rate * 0.030),
         }
     }
 
@@ -221,6 +234,15 @@ impl DspEqualizer {
         }
 
         if needs_update {
+            // Keep the outgoing chain running so the curve change blends in
+            // instead of jumping (audible as a click on large A/B swaps)
+            self.fade_from = Some((
+                self.filters_left.clone(),
+                self.filters_right.clone(),
+                self.preamp,
+            ));
+            self.fade_pos = 0.0;
+
             // Calculate preamp: reduce by the maximum boost to prevent clipping
             let max_boost = current_gains.iter().cloned().fold(0.0f32, f32::max);
             // Convert dB to linear gain reduction
@@ -270,9 +292,23 @@ impl DspEqualizer {
             r = self.filters_right[i].run(r);
         }
 
-        // Apply limiter
-        let wet_l = limiter(l);
-        let wet_r = limiter(r);
+        // Blend in from the previous curve after a change 🅰️🅱️
+        if let Some((old_left, old_right, old_preamp)) = &mut self.fade_from {
+            let mut ol = left * *old_preamp;
+            let mut or = right * *old_preamp;
+            for i in 0..10 {
+                ol = old_left[i].run(ol);
+                or = old_right[i].run(or);
+            }
+
+            self.fade_pos = (self.fade_pos + self.curve_fade_speed).min(1.0);
+            l = ol * (1.0 - self.fade_pos) + l * self.fade_pos;
+            r = or * (1.0 - self.fade_pos) + r * self.fade_pos;
+
+            if self.fade_pos >= 1.0 {
+                self.fade_from = None;
+            }
+        }
 
         // Get user preamp and balance from EqGains
         let user_preamp_db = self.gains.get_preamp_db();
@@ -288,8 +324,8 @@ impl DspEqualizer {
         let right_gain = if balance < 0.0 { 1.0 + balance } else { 1.0 };
 
         // Apply user preamp and balance
-        let final_l = wet_l * user_preamp_linear * left_gain;
-        let final_r = wet_r * user_preamp_linear * right_gain;
+        let final_l = l * user_preamp_linear * left_gain;
+        let final_r = r * user_preamp_linear * right_gain;
 
         // Crossfade between dry and wet (smooth transition on toggle)
         if self.mix >= 0.9999 {
@@ -305,6 +341,11 @@ impl DspEqualizer {
         }
     }
 
+    /// Whether the EQ changes the signal (switched on, or still fading out)
+    pub fn is_wet(&self) -> bool {
+        self.gains.is_enabled() || self.mix > 0.0001
+    }
+
     /// Process a buffer of interleaved stereo samples
     pub fn process_buffer(&mut self, buffer: &mut [f32]) {
         // Process interleaved stereo (L, R, L, R, ...)
@@ -326,6 +367,8 @@ impl DspEqualizer {
             self.filters_left[i] = biquad::DirectForm1::<f32>::new(coeffs);
             self.filters_right[i] = biquad::DirectForm1::<f32>::new(coeffs);
         }
+        self.fade_from = None;
+        self.fade_pos = 1.0;
     }
 }
 
@@ -345,6 +388,10 @@ impl DspEqualizer {
 
     pub fn process_buffer(&mut self, _buffer: &mut [f32]) {}
     pub fn reset_filters(&mut self) {}
+
+    pub fn is_wet(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
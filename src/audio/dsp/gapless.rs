@@ -0,0 +1,69 @@
+//! Gapless joins across stream restarts 🔗
+//!
+//! MPD crossfades and joins songs gaplessly on its own, but only while the
+//! audio format stays the same. When the sample rate or channel count changes
+//! the HTTP stream restarts and the pipeline used to drop whatever was still
+//! buffered. Instead, the unplayed tail of the old stream is kept, converted to
+//! the new format and played out ahead of the new stream.
+//!
+//! There is no crossfade at that join: the pipeline only sees the old track
+//! end when the new stream arrives, so overlapping them would mean holding the
+//! whole fade length back on every track.
+
+/// Convert interleaved samples to another rate/channel count (linear interpolation).
+/// Only used for the short tail at a format change, so quality is not critical.
+pub fn convert_tail(
+    samples: &[f32],
+    from_rate: u32,
+    from_channels: usize,
+    to_rate: u32,
+    to_channels: usize,
+) -> Vec<f32> {
+    let from_channels = from_channels.max(1);
+    let to_channels = to_channels.max(1);
+    let frames = samples.len() / from_channels;
+    if frames == 0 {
+        return Vec::new();
+    }
+
+    let channel = |frame: usize, ch: usize| -> f32 {
+        let base = frame * from_channels;
+        match (from_channels, to_channels) {
+            (1, _) => samples[base],
+            (_, 1) => {
+                samples[base..base + from_channels].iter().sum::<f32>() / from_channels as f32
+            }
+            _ => samples[base + ch.min(from_channels - 1)],
+        }
+    };
+
+    let out_frames = (frames as u64 * to_rate as u64 / from_rate.max(1) as u64) as usize;
+    let step = from_rate as f64 / to_rate.max(1) as f64;
+    let mut out = Vec::with_capacity(out_frames * to_channels);
+    for i in 0..out_frames {
+        let src = i as f64 * step;
+        let i0 = (src as usize).min(frames - 1);
+        let i1 = (i0 + 1).min(frames - 1);
+        let frac = (src - i0 as f64) as f32;
+        for ch in 0..to_channels {
+            out.push(channel(i0, ch) * (1.0 - frac) + channel(i1, ch) * frac);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_tail_rate_and_channels() {
+        let stereo_44k = vec![0.25; 441 * 2];
+        let mono_88k = convert_tail(&stereo_44k, 44100, 2, 88200, 1);
+        assert_eq!(mono_88k.len(), 882);
+        assert!(mono_88k.iter().all(|s| (s - 0.25).abs() < 1e-6));
+
+        let stereo = convert_tail(&[0.1, 0.2], 48000, 1, 48000, 2);
+        assert_eq!(stereo, vec![0.1, 0.1, 0.2, 0.2]);
+    }
+}
@@ -0,0 +1,662 @@
+//! LADSPA plugins as DSP stages 🔌
+//!
+//! A plugin is named in `[[dsp.plugins]]` and placed in `[dsp] chain` by
+//! that name:
+//!
+//! ```toml
+//! [dsp]
+//! chain = ["eq", "room", "limiter"]
+//!
+//! [[dsp.plugins]]
+//! name = "room"
+//! path = "caps.so"          # a file on the LADSPA path, or a full path
+//! label = "Eq10X2"          # which plugin in the file (unset = the first)
+//! controls = { "31 Hz" = -3.0 }
+//! ```
+//!
+//! Libraries are found on `LADSPA_PATH`, or in the usual folders when that
+//! isn't set (`vyom plugins` lists what is there). Mono plugins run once per
+//! channel, stereo ones once. A plugin's `latency` output port, when it has
+//! one, is what `:dsp` shows as its latency. LV2 isn't hosted (yet).
+
+use super::chain::{DspSetting, DspStage, StageKind};
+use std::ffi::{c_char, c_int, c_ulong, c_void, CStr, CString};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Frames handed to a plugin per `run` call
+const BLOCK: usize = 1024;
+
+const PORT_INPUT: c_int = 0x1;
+const PORT_OUTPUT: c_int = 0x2;
+const PORT_CONTROL: c_int = 0x4;
+const PORT_AUDIO: c_int = 0x8;
+
+const HINT_BOUNDED_BELOW: c_int = 0x1;
+const HINT_BOUNDED_ABOVE: c_int = 0x2;
+const HINT_TOGGLED: c_int = 0x4;
+const HINT_SAMPLE_RATE: c_int = 0x8;
+const HINT_LOGARITHMIC: c_int = 0x10;
+const HINT_INTEGER: c_int = 0x20;
+const HINT_DEFAULT_MASK: c_int = 0x3C0;
+
+type Handle = *mut c_void;
+
+/// `LADSPA_PortRangeHint`
+#[repr(C)]
+struct RangeHint {
+    hint: c_int,
+    lower: f32,
+    upper: f32,
+}
+
+/// `LADSPA_Descriptor` (ladspa.h, version 1.1)
+#[repr(C)]
+#[allow(dead_code)] // Laid out as in C, read or not
+struct Descriptor {
+    unique_id: c_ulong,
+    label: *const c_char,
+    properties: c_int,
+    name: *const c_char,
+    maker: *const c_char,
+    copyright: *const c_char,
+    port_count: c_ulong,
+    port_descriptors: *const c_int,
+    port_names: *const *const c_char,
+    port_range_hints: *const RangeHint,
+    implementation_data: *mut c_void,
+    instantiate: Option<unsafe extern "C" fn(*const Descriptor, c_ulong) -> Handle>,
+    connect_port: Option<unsafe extern "C" fn(Handle, c_ulong, *mut f32)>,
+    activate: Option<unsafe extern "C" fn(Handle)>,
+    run: Option<unsafe extern "C" fn(Handle, c_ulong)>,
+    run_adding: Option<unsafe extern "C" fn(Handle, c_ulong)>,
+    set_run_adding_gain: Option<unsafe extern "C" fn(Handle, f32)>,
+    deactivate: Option<unsafe extern "C" fn(Handle)>,
+    cleanup: Option<unsafe extern "C" fn(Handle)>,
+}
+
+type DescriptorFn = unsafe extern "C" fn(c_ulong) -> *const Descriptor;
+
+/// A control port: what the sliders popup edits
+#[derive(Debug, Clone, PartialEq)]
+pub struct Control {
+    pub port: usize,
+    pub name: String,
+    pub min: f32,
+    pub max: f32,
+    pub default: f32,
+    pub toggled: bool,
+    pub integer: bool,
+    pub logarithmic: bool,
+    /// Bounds are fractions of the sample rate
+    pub per_sample_rate: bool,
+}
+
+impl Control {
+    fn from_hint(port: usize, name: String, hint: &RangeHint) -> Self {
+        let flags = hint.hint;
+        let toggled = flags & HINT_TOGGLED != 0;
+        // Ports without bounds slide over 0-1, or one unit past the bound given
+        let (min, max) = match (
+            flags & HINT_BOUNDED_BELOW != 0 && !toggled,
+            flags & HINT_BOUNDED_ABOVE != 0 && !toggled,
+        ) {
+            (true, true) => (hint.lower, hint.upper.max(hint.lower)),
+            (true, false) => (hint.lower, hint.lower + hint.lower.abs().max(1.0)),
+            (false, true) => (hint.upper - hint.upper.abs().max(1.0), hint.upper),
+            (false, false) => (0.0, 1.0),
+        };
+        let logarithmic = flags & HINT_LOGARITHMIC != 0 && min > 0.0;
+        // Between the bounds, on the scale the port uses
+        let between = |share: f32| {
+            if logarithmic {
+                (min.ln() * (1.0 - share) + max.ln() * share).exp()
+            } else {
+                min * (1.0 - share) + max * share
+            }
+        };
+        let default = match flags & HINT_DEFAULT_MASK {
+            0x40 => min,
+            0x80 => between(0.25),
+            0xC0 => between(0.5),
+            0x100 => between(0.75),
+            0x140 => max,
+            0x200 => 0.0,
+            0x240 => 1.0,
+            0x280 => 100.0,
+            0x2C0 => 440.0,
+            _ => 0.0f32.clamp(min, max),
+        };
+        let integer = flags & HINT_INTEGER != 0;
+        Self {
+            port,
+            name,
+            min,
+            max,
+            default: if integer { default.round() } else { default },
+            toggled,
+            integer,
+            logarithmic,
+            per_sample_rate: flags & HINT_SAMPLE_RATE != 0,
+        }
+    }
+
+    /// Bounds at `sample_rate`
+    pub fn range(&self, sample_rate: u32) -> (f32, f32) {
+        if self.per_sample_rate {
+            let rate = sample_rate as f32;
+            (self.min * rate, self.max * rate)
+        } else {
+            (self.min, self.max)
+        }
+    }
+
+    /// `value` moved `steps` fortieths of the range, on the port's own scale
+    pub fn step(&self, value: f32, steps: i32, sample_rate: u32) -> f32 {
+        let (min, max) = self.range(sample_rate);
+        if self.toggled {
+            return if value > 0.0 { 0.0 } else { 1.0 };
+        }
+        let next = if self.logarithmic {
+            let factor = (max / min).powf(1.0 / 40.0);
+            value.max(min) * factor.powi(steps)
+        } else if self.integer {
+            value.round() + (((max - min) / 40.0).round().max(1.0)) * steps as f32
+        } else {
+            value + (max - min) / 40.0 * steps as f32
+        };
+        next.clamp(min, max)
+    }
+}
+
+/// One plugin in a library, as far as the host needs to know it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginInfo {
+    pub path: PathBuf,
+    pub label: String,
+    pub name: String,
+    pub maker: String,
+    pub controls: Vec<Control>,
+    audio_in: Vec<usize>,
+    audio_out: Vec<usize>,
+    /// Control output ports (`latency` among them, maybe)
+    outputs: Vec<(usize, String)>,
+    port_count: usize,
+}
+
+impl PluginInfo {
+    /// Instances needed for stereo: one per channel for a mono plugin
+    fn instances(&self) -> Option<usize> {
+        match (self.audio_in.len(), self.audio_out.len()) {
+            (1, 1) => Some(2),
+            (2, 2) => Some(1),
+            _ => None,
+        }
+    }
+
+    fn latency_port(&self) -> Option<usize> {
+        self.outputs
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case("latency"))
+            .map(|(port, _)| *port)
+    }
+}
+
+/// A `dlopen`ed library, closed when the last plugin from it goes
+struct Library(Handle);
+
+// SAFETY: a dlopen handle may be used and closed from any thread
+unsafe impl Send for Library {}
+unsafe impl Sync for Library {}
+
+impl Library {
+    fn open(path: &Path) -> Result<Self, String> {
+        let c_path = CString::new(path.as_os_str().as_encoded_bytes())
+            .map_err(|_| format!("Bad path: {}", path.display()))?;
+        // SAFETY: a valid C string; RTLD_NOW so missing symbols fail here
+        let handle = unsafe { libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+        if handle.is_null() {
+            return Err(dl_error().unwrap_or_else(|| format!("Can't load {}", path.display())));
+        }
+        Ok(Self(handle))
+    }
+
+    fn descriptor_fn(&self) -> Option<DescriptorFn> {
+        // SAFETY: the handle is open; the symbol has this type per ladspa.h
+        unsafe {
+            let symbol = libc::dlsym(self.0, c"ladspa_descriptor".as_ptr());
+            (!symbol.is_null()).then(|| std::mem::transmute::<*mut c_void, DescriptorFn>(symbol))
+        }
+    }
+
+    /// Every plugin in the library, with its descriptor
+    fn plugins(&self, path: &Path) -> Vec<(*const Descriptor, PluginInfo)> {
+        let Some(descriptor_fn) = self.descriptor_fn() else {
+            return Vec::new();
+        };
+        let mut plugins = Vec::new();
+        for index in 0.. {
+            // SAFETY: the library returns null past its last plugin
+            let descriptor = unsafe { descriptor_fn(index) };
+            if descriptor.is_null() {
+                break;
+            }
+            // SAFETY: non-null descriptors stay valid while the library is open
+            if let Some(info) = unsafe { describe(&*descriptor, path) } {
+                plugins.push((descriptor, info));
+            }
+        }
+        plugins
+    }
+}
+
+impl Drop for Library {
+    fn drop(&mut self) {
+        // SAFETY: opened by us, and nothing from it outlives this
+        unsafe {
+            libc::dlclose(self.0);
+        }
+    }
+}
+
+fn dl_error() -> Option<String> {
+    // SAFETY: dlerror returns null or a C string valid until the next call
+    unsafe {
+        let error = libc::dlerror();
+        (!error.is_null()).then(|| CStr::from_ptr(error).to_string_lossy().into_owned())
+    }
+}
+
+/// # Safety
+/// `ptr` is null or a C string
+unsafe fn text(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+}
+
+/// # Safety
+/// `descriptor` is a descriptor from an open library
+unsafe fn describe(descriptor: &Descriptor, path: &Path) -> Option<PluginInfo> {
+    let count = descriptor.port_count as usize;
+    if descriptor.port_descriptors.is_null() || descriptor.port_names.is_null() {
+        return None;
+    }
+    let kinds = std::slice::from_raw_parts(descriptor.port_descriptors, count);
+    let names = std::slice::from_raw_parts(descriptor.port_names, count);
+    let hints = (!descriptor.port_range_hints.is_null())
+        .then(|| std::slice::from_raw_parts(descriptor.port_range_hints, count));
+
+    let mut info = PluginInfo {
+        path: path.to_path_buf(),
+        label: text(descriptor.label),
+        name: text(descriptor.name),
+        maker: text(descriptor.maker),
+        controls: Vec::new(),
+        audio_in: Vec::new(),
+        audio_out: Vec::new(),
+        outputs: Vec::new(),
+        port_count: count,
+    };
+    for port in 0..count {
+        let kind = kinds[port];
+        let name = text(names[port]);
+        match (kind & PORT_AUDIO != 0, kind & PORT_INPUT != 0) {
+            (true, true) => info.audio_in.push(port),
+            (true, false) => info.audio_out.push(port),
+            (false, true) => {
+                let none = RangeHint {
+                    hint: 0,
+                    lower: 0.0,
+                    upper: 0.0,
+                };
+                let hint = hints.map_or(&none, |h| &h[port]);
+                info.controls.push(Control::from_hint(port, name, hint));
+            }
+            (false, false) if kind & PORT_OUTPUT != 0 && kind & PORT_CONTROL != 0 => {
+                info.outputs.push((port, name))
+            }
+            _ => {}
+        }
+    }
+    Some(info)
+}
+
+/// Where LADSPA libraries are looked for: `LADSPA_PATH`, or the usual places
+pub fn search_paths() -> Vec<PathBuf> {
+    if let Some(path) = std::env::var_os("LADSPA_PATH").filter(|p| !p.is_empty()) {
+        return std::env::split_paths(&path).collect();
+    }
+    let mut paths = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        paths.push(home.join(".ladspa"));
+        paths.push(home.join("Library/Audio/Plug-Ins/LADSPA"));
+    }
+    for dir in [
+        "/usr/local/lib/ladspa",
+        "/usr/lib/ladspa",
+        "/usr/lib64/ladspa",
+        "/Library/Audio/Plug-Ins/LADSPA",
+    ] {
+        paths.push(PathBuf::from(dir));
+    }
+    paths
+}
+
+fn is_library(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "so" || ext == "dylib")
+}
+
+/// Every plugin on the search path, by file
+pub fn discover() -> Vec<PluginInfo> {
+    let mut files: Vec<PathBuf> = search_paths()
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten().map(|e| e.path()))
+        .filter(|path| is_library(path))
+        .collect();
+    files.sort();
+    files
+        .iter()
+        .filter_map(|path| Library::open(path).ok().map(|lib| lib.plugins(path)))
+        .flatten()
+        .map(|(_, info)| info)
+        .collect()
+}
+
+/// `path` as given: a full path, or a file somewhere on the search path
+fn resolve(path: &str) -> Result<PathBuf, String> {
+    let given = match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => PathBuf::from(path),
+    };
+    if given.is_absolute() || given.components().count() > 1 {
+        return Ok(given);
+    }
+    search_paths()
+        .iter()
+        .map(|dir| dir.join(&given))
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| format!("{} isn't on the LADSPA path", path))
+}
+
+/// A plugin ready to be instantiated on the audio thread
+pub struct Plugin {
+    pub info: PluginInfo,
+    descriptor: *const Descriptor,
+    _library: Arc<Library>,
+}
+
+// SAFETY: the descriptor is immutable data that lives as long as the library,
+// which the plugin keeps open
+unsafe impl Send for Plugin {}
+unsafe impl Sync for Plugin {}
+
+impl Plugin {
+    /// The plugin called `label` in the library at `path` (the first one
+    /// without a label)
+    pub fn load(path: &str, label: Option<&str>) -> Result<Self, String> {
+        let path = resolve(path)?;
+        let library = Arc::new(Library::open(&path)?);
+        let mut plugins = library.plugins(&path);
+        if plugins.is_empty() {
+            return Err(format!("{} has no LADSPA plugins", path.display()));
+        }
+        let index = match label {
+            Some(label) => plugins
+                .iter()
+                .position(|(_, info)| info.label == label)
+                .ok_or_else(|| {
+                    let labels: Vec<&str> = plugins.iter().map(|(_, i)| i.label.as_str()).collect();
+                    format!(
+                        "No plugin \"{}\" in {} (there's {})",
+                        label,
+                        path.display(),
+                        labels.join(", ")
+                    )
+                })?,
+            None => 0,
+        };
+        let (descriptor, info) = plugins.swap_remove(index);
+        if info.instances().is_none() {
+            return Err(format!(
+                "{} has {} inputs and {} outputs; only mono and stereo plugins work",
+                info.name,
+                info.audio_in.len(),
+                info.audio_out.len()
+            ));
+        }
+        Ok(Self {
+            info,
+            descriptor,
+            _library: library,
+        })
+    }
+
+    fn descriptor(&self) -> &Descriptor {
+        // SAFETY: valid while `_library` is open
+        unsafe { &*self.descriptor }
+    }
+}
+
+/// A plugin running in the chain
+pub struct PluginStage {
+    plugin: Arc<Plugin>,
+    /// Index in `[[dsp.plugins]]`
+    index: usize,
+    setting: DspSetting,
+    switch: super::chain::Switch,
+    handles: Vec<Handle>,
+    /// Control values, one slot per port so the addresses stay put
+    ports: Box<[f32]>,
+    /// Per instance: input and output buffers
+    inputs: Vec<Vec<f32>>,
+    outputs: Vec<Vec<f32>>,
+    latency: usize,
+}
+
+// SAFETY: instances are only ever used by the thread that owns the stage
+unsafe impl Send for PluginStage {}
+
+impl PluginStage {
+    pub fn new(
+        plugin: Arc<Plugin>,
+        index: usize,
+        sample_rate: u32,
+        setting: DspSetting,
+    ) -> Result<Self, String> {
+        let descriptor = plugin.descriptor();
+        let (Some(instantiate), Some(connect), Some(_run)) = (
+            descriptor.instantiate,
+            descriptor.connect_port,
+            descriptor.run,
+        ) else {
+            return Err(format!("{} can't be run", plugin.info.name));
+        };
+        let count = plugin.info.instances().unwrap_or(1);
+        let channels = plugin.info.audio_in.len();
+
+        let mut stage = Self {
+            switch: super::chain::Switch::new(
+                sample_rate,
+                setting.is_enabled(StageKind::Plugin(index)),
+            ),
+            ports: vec![0.0; plugin.info.port_count].into_boxed_slice(),
+            inputs: vec![vec![0.0; BLOCK]; count * channels],
+            outputs: vec![vec![0.0; BLOCK]; count * channels],
+            handles: Vec::new(),
+            latency: 0,
+            plugin: plugin.clone(),
+            index,
+            setting,
+        };
+        stage.read_controls();
+        for instance in 0..count {
+            // SAFETY: instantiate/connect_port/activate as ladspa.h lays out;
+            // every port is connected to memory the stage owns and keeps in place
+            unsafe {
+                let handle = instantiate(plugin.descriptor, sample_rate as c_ulong);
+                if handle.is_null() {
+                    return Err(format!("{} failed to start", plugin.info.name));
+                }
+                stage.handles.push(handle);
+                for port in 0..plugin.info.port_count {
+                    let slot = stage.ports.as_mut_ptr().add(port);
+                    connect(handle, port as c_ulong, slot);
+                }
+                for (channel, &port) in plugin.info.audio_in.iter().enumerate() {
+                    let buffer = stage.inputs[instance * channels + channel].as_mut_ptr();
+                    connect(handle, port as c_ulong, buffer);
+                }
+                for (channel, &port) in plugin.info.audio_out.iter().enumerate() {
+                    let buffer = stage.outputs[instance * channels + channel].as_mut_ptr();
+                    connect(handle, port as c_ulong, buffer);
+                }
+                if let Some(activate) = descriptor.activate {
+                    activate(handle);
+                }
+            }
+        }
+
+        // Plugins fill in their latency port when they run
+        if let Some(port) = plugin.info.latency_port() {
+            stage.run(BLOCK);
+            stage.latency = stage.ports[port].max(0.0) as usize;
+        }
+        Ok(stage)
+    }
+
+    /// Copy the current slider values into the control ports
+    fn read_controls(&mut self) {
+        for (i, control) in self.plugin.info.controls.iter().enumerate() {
+            self.ports[control.port] = self.setting.control(self.index, i);
+        }
+    }
+
+    fn run(&mut self, frames: usize) {
+        let Some(run) = self.plugin.descriptor().run else {
+            return;
+        };
+        for &handle in &self.handles {
+            // SAFETY: an activated instance with all ports connected
+            unsafe { run(handle, frames as c_ulong) };
+        }
+    }
+}
+
+impl DspStage for PluginStage {
+    fn kind(&self) -> StageKind {
+        StageKind::Plugin(self.index)
+    }
+
+    fn process(&mut self, buffer: &mut [f32]) {
+        let on = self.setting.is_enabled(StageKind::Plugin(self.index));
+        if !self.switch.is_active(on) {
+            return;
+        }
+        self.read_controls();
+        for block in buffer.chunks_mut(BLOCK * 2) {
+            let frames = block.len() / 2;
+            // Buffers 0 and 1: the two mono instances, or the stereo one's
+            // left and right
+            for (i, frame) in block.chunks_exact(2).enumerate() {
+                self.inputs[0][i] = frame[0];
+                self.inputs[1][i] = frame[1];
+            }
+            self.run(frames);
+            for (i, frame) in block.chunks_exact_mut(2).enumerate() {
+                let mix = self.switch.next(on);
+                frame[0] += (self.outputs[0][i] - frame[0]) * mix;
+                frame[1] += (self.outputs[1][i] - frame[1]) * mix;
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        let descriptor = self.plugin.descriptor();
+        if let (Some(deactivate), Some(activate)) = (descriptor.deactivate, descriptor.activate) {
+            for &handle in &self.handles {
+                // SAFETY: deactivate/activate pairs reset an instance's state
+                unsafe {
+                    deactivate(handle);
+                    activate(handle);
+                }
+            }
+        }
+    }
+
+    fn latency_frames(&self) -> usize {
+        self.latency
+    }
+
+    fn is_active(&self) -> bool {
+        self.switch
+            .is_active(self.setting.is_enabled(StageKind::Plugin(self.index)))
+    }
+}
+
+impl Drop for PluginStage {
+    fn drop(&mut self) {
+        let descriptor = self.plugin.descriptor();
+        for &handle in &self.handles {
+            // SAFETY: each handle came from instantiate and is dropped once
+            unsafe {
+                if let Some(deactivate) = descriptor.deactivate {
+                    deactivate(handle);
+                }
+                if let Some(cleanup) = descriptor.cleanup {
+                    cleanup(handle);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_defaults_from_hints() {
+        let hint = |hint, lower, upper| RangeHint { hint, lower, upper };
+        let bounded = HINT_BOUNDED_BELOW | HINT_BOUNDED_ABOVE;
+
+        let gain = Control::from_hint(0, "Gain".into(), &hint(bounded | 0xC0, -12.0, 12.0));
+        assert_eq!((gain.min, gain.max, gain.default), (-12.0, 12.0, 0.0));
+        assert!((gain.step(0.0, 1, 48000) - 0.6).abs() < 1e-6);
+        assert_eq!(gain.step(11.9, 5, 48000), 12.0);
+
+        // Logarithmic: the middle is the geometric mean
+        let freq = Control::from_hint(
+            1,
+            "Frequency".into(),
+            &hint(bounded | HINT_LOGARITHMIC | 0xC0, 20.0, 20000.0),
+        );
+        assert!((freq.default - 632.455).abs() < 0.01);
+        assert!((freq.step(20000.0, -40, 48000) - 20.0).abs() < 0.01);
+
+        // Fractions of the sample rate
+        let cutoff = Control::from_hint(
+            2,
+            "Cutoff".into(),
+            &hint(bounded | HINT_SAMPLE_RATE | 0x40, 0.0, 0.5),
+        );
+        assert_eq!(cutoff.range(44100), (0.0, 22050.0));
+
+        let bypass = Control::from_hint(3, "Bypass".into(), &hint(HINT_TOGGLED | 0x240, 0.0, 0.0));
+        assert!(bypass.toggled);
+        assert_eq!(bypass.default, 1.0);
+        assert_eq!(bypass.step(1.0, 1, 48000), 0.0);
+
+        let taps = Control::from_hint(
+            4,
+            "Taps".into(),
+            &hint(bounded | HINT_INTEGER | 0x80, 1.0, 8.0),
+        );
+        assert_eq!(taps.default, 3.0);
+        assert_eq!(taps.step(3.0, 1, 48000), 4.0);
+    }
+}
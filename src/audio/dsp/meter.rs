@@ -0,0 +1,140 @@
+//! Stereo peak / RMS level metering 📶
+//!
+//! The audio callback accumulates raw stats; the UI drains them once per
+//! frame and applies its own ballistics (decay, hold, clip latch).
+
+use std::sync::{Arc, Mutex};
+
+/// Peak and RMS (linear, 0.0 = silence, 1.0 = full scale) per channel
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MeterFrame {
+    pub peak: [f32; 2],
+    pub rms: [f32; 2],
+}
+
+#[derive(Debug, Default)]
+struct MeterAccum {
+    peak: [f32; 2],
+    sum_sq: [f64; 2],
+    count: usize,
+    /// Last 3 samples per channel, for inter-sample peak estimation across buffers
+    history: [[f32; 3]; 2],
+}
+
+/// Shared meter fed from the output stream
+#[derive(Clone, Default)]
+pub struct LevelMeter {
+    inner: Arc<Mutex<MeterAccum>>,
+}
+
+impl LevelMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed interleaved output samples (audio thread; never blocks)
+    pub fn process(&self, data: &[f32], channels: usize) {
+        let Ok(mut acc) = self.inner.try_lock() else {
+            return;
+        };
+        let channels = channels.max(1);
+        for frame in data.chunks(channels) {
+            for ch in 0..2 {
+                // Mono feeds both sides
+                let s = frame[ch.min(frame.len() - 1)];
+                let [p0, p1, p2] = acc.history[ch];
+                let peak = true_peak(p0, p1, p2, s).max(s.abs());
+                acc.history[ch] = [p1, p2, s];
+                acc.peak[ch] = acc.peak[ch].max(peak);
+                acc.sum_sq[ch] += (s as f64) * (s as f64);
+            }
+            acc.count += 1;
+        }
+    }
+
+    /// Drain everything accumulated since the last call
+    pub fn take(&self) -> Option<MeterFrame> {
+        let mut acc = self.inner.lock().ok()?;
+        if acc.count == 0 {
+            return None;
+        }
+        let n = acc.count as f64;
+        let frame = MeterFrame {
+            peak: acc.peak,
+            rms: [
+                (acc.sum_sq[0] / n).sqrt() as f32,
+                (acc.sum_sq[1] / n).sqrt() as f32,
+            ],
+        };
+        acc.peak = [0.0; 2];
+        acc.sum_sq = [0.0; 2];
+        acc.count = 0;
+        Some(frame)
+    }
+}
+
+/// Estimate the inter-sample peak between `p1` and `p2` (4x oversampled
+/// Catmull-Rom), which is what a DAC's reconstruction filter will hit
+fn true_peak(p0: f32, p1: f32, p2: f32, p3: f32) -> f32 {
+    let mut peak = 0.0f32;
+    for t in [0.25f32, 0.5, 0.75] {
+        let t2 = t * t;
+        let t3 = t2 * t;
+        let v = 0.5
+            * (2.0 * p1
+                + (-p0 + p2) * t
+                + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+                + (-p0 + 3.0 * p1 - 3.0 * p2 + p3) * t3);
+        peak = peak.max(v.abs());
+    }
+    peak
+}
+
+/// Linear amplitude to dBFS (floored at -90)
+#[must_use]
+pub fn to_dbfs(linear: f32) -> f32 {
+    (20.0 * linear.max(1e-9).log10()).max(-90.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_scale_sine_levels() {
+        let meter = LevelMeter::new();
+        let samples: Vec<f32> = (0..4800)
+            .flat_map(|i| {
+                let s = (2.0 * std::f32::consts::PI * 997.0 * i as f32 / 48000.0).sin();
+                [s, s * 0.5]
+            })
+            .collect();
+        meter.process(&samples, 2);
+        let frame = meter.take().unwrap();
+
+        assert!((to_dbfs(frame.peak[0])).abs() < 0.1);
+        // Sine RMS is -3dB below peak
+        assert!((to_dbfs(frame.rms[0]) + 3.01).abs() < 0.1);
+        assert!((to_dbfs(frame.peak[1]) + 6.02).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_take_resets() {
+        let meter = LevelMeter::new();
+        meter.process(&[0.5, 0.5], 2);
+        assert!(meter.take().is_some());
+        assert!(meter.take().is_none());
+    }
+
+    #[test]
+    fn test_true_peak_exceeds_sample_peak() {
+        // fs/4 sine sampled at 45° offsets: samples sit at ±0.707, real peak is 1.0
+        let meter = LevelMeter::new();
+        let samples: Vec<f32> = (0..64)
+            .map(|i| (std::f32::consts::FRAC_PI_2 * i as f32 + std::f32::consts::FRAC_PI_4).sin())
+            .collect();
+        meter.process(&samples, 1);
+        let frame = meter.take().unwrap();
+        assert!(frame.peak[0] > 0.75);
+    }
+}
@@ -1,4 +1,15 @@
+pub mod chain;
 pub mod eq;
+pub mod gapless;
+pub mod ladspa;
 pub mod limiter;
+pub mod meter;
+pub mod pitch;
+pub mod resample;
+pub mod silence;
 
-pub use eq::{value_to_db, DspEqualizer, EqGains, EQ_FREQUENCIES};
+pub use chain::DspSetting;
+pub use eq::{db_to_value, value_to_db, DspEqualizer, EqGains, EQ_FREQUENCIES};
+pub use meter::LevelMeter;
+pub use pitch::PitchSetting;
+pub use silence::SilenceMonitor;
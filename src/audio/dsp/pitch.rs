@@ -0,0 +1,164 @@
+//! Tempo-preserving pitch shift 🎼
+//!
+//! A two-tap granular delay line: each tap slides through a short window at
+//! `1 - ratio` samples per sample and the taps are Hann-crossfaded half a window
+//! apart, so the output is transposed while the duration stays the same.
+//! Cheap enough for the audio thread and fine for practice / transcription.
+//!
+//! Playback speed can't be changed here: MPD paces both the HTTP stream and the
+//! FIFO in real time, so the pipeline never has more audio than it plays.
+
+use std::f32::consts::PI;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+/// Allowed shift in semitones (either direction)
+pub const MAX_SEMITONES: i32 = 12;
+
+/// Grain length; long enough for bass, short enough to keep smearing low
+const WINDOW_MS: f32 = 60.0;
+
+/// Pitch shift in semitones, shared between the UI and the audio thread
+#[derive(Clone, Default)]
+pub struct PitchSetting(Arc<AtomicI32>);
+
+impl PitchSetting {
+    pub fn set(&self, semitones: i32) {
+        self.0.store(
+            semitones.clamp(-MAX_SEMITONES, MAX_SEMITONES),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn get(&self) -> i32 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Frequency ratio for the current setting
+    pub fn ratio(&self) -> f32 {
+        2f32.powf(self.get() as f32 / 12.0)
+    }
+}
+
+pub struct PitchShifter {
+    /// Grain window in frames
+    window: usize,
+    /// Per-channel circular history
+    history: Vec<Vec<f32>>,
+    write: usize,
+    /// Delay of the first tap in frames, within `[0, window)`
+    delay: f32,
+}
+
+impl PitchShifter {
+    pub fn new(sample_rate: u32) -> Self {
+        let window = ((sample_rate as f32 * WINDOW_MS / 1000.0) as usize).max(64);
+        Self {
+            window,
+            history: Vec::new(),
+            write: 0,
+            delay: 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.write = 0;
+        self.delay = 0.0;
+    }
+
+    /// Shift interleaved samples in place by `ratio` (1.0 = bypass)
+    pub fn process(&mut self, buffer: &mut [f32], channels: usize, ratio: f32) {
+        if (ratio - 1.0).abs() < 1e-4 {
+            // Drop stale grains so re-enabling doesn't replay old audio
+            if !self.history.is_empty() {
+                self.reset();
+            }
+            return;
+        }
+
+        let channels = channels.max(1);
+        let len = self.window + 2;
+        if self.history.len() != channels {
+            self.history = vec![vec![0.0; len]; channels];
+            self.write = 0;
+        }
+
+        let window = self.window as f32;
+        let half = window / 2.0;
+        for frame in buffer.chunks_mut(channels) {
+            let delay_a = self.delay;
+            let delay_b = (self.delay + half) % window;
+            // sin² + cos² = 1, so the two taps always sum to unity gain
+            let gain_a = (PI * delay_a / window).sin().powi(2);
+            let gain_b = 1.0 - gain_a;
+
+            for (ch, sample) in frame.iter_mut().enumerate() {
+                let line = &mut self.history[ch];
+                line[self.write] = *sample;
+                *sample = tap(line, self.write, delay_a) * gain_a
+                    + tap(line, self.write, delay_b) * gain_b;
+            }
+
+            self.write = (self.write + 1) % len;
+            self.delay = (self.delay + 1.0 - ratio).rem_euclid(window);
+        }
+    }
+}
+
+/// Read `delay` frames behind `write` (linear interpolation)
+fn tap(line: &[f32], write: usize, delay: f32) -> f32 {
+    let len = line.len();
+    let whole = delay as usize;
+    let frac = delay - whole as f32;
+    let i0 = (write + len - whole) % len;
+    let i1 = (i0 + len - 1) % len;
+    line[i0] * (1.0 - frac) + line[i1] * frac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Count upward zero crossings (≈ frequency × seconds)
+    fn crossings(samples: &[f32]) -> usize {
+        samples
+            .windows(2)
+            .filter(|w| w[0] < 0.0 && w[1] >= 0.0)
+            .count()
+    }
+
+    #[test]
+    fn test_octave_up_doubles_frequency() {
+        let rate = 48000;
+        let mut tone: Vec<f32> = (0..rate)
+            .map(|i| (2.0 * PI * 220.0 * i as f32 / rate as f32).sin() * 0.5)
+            .collect();
+        let setting = PitchSetting::default();
+        setting.set(12);
+
+        let mut shifter = PitchShifter::new(rate as u32);
+        shifter.process(&mut tone, 1, setting.ratio());
+
+        // Skip the first window while the delay line fills
+        let n = crossings(&tone[4800..]) as f32 / 0.9;
+        assert!((n - 440.0).abs() < 20.0, "Expected ~440 Hz, got {}", n);
+    }
+
+    #[test]
+    fn test_unity_ratio_is_bypass() {
+        let mut shifter = PitchShifter::new(44100);
+        let mut buf = vec![0.1, -0.2, 0.3, -0.4];
+        shifter.process(&mut buf, 2, 1.0);
+        assert_eq!(buf, vec![0.1, -0.2, 0.3, -0.4]);
+    }
+
+    #[test]
+    fn test_setting_is_clamped() {
+        let setting = PitchSetting::default();
+        setting.set(40);
+        assert_eq!(setting.get(), MAX_SEMITONES);
+        setting.set(-40);
+        assert_eq!(setting.get(), -MAX_SEMITONES);
+    }
+}
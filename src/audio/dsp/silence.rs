@@ -0,0 +1,124 @@
+//! Silence detection for gap trimming ✂️
+//!
+//! The decode loop feeds every buffer through here; the UI reads how long the
+//! stream has been silent and decides whether to skip (MPD paces the stream in
+//! real time, so dead air can only be skipped on the server, not dropped here).
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default threshold: anything quieter counts as silence
+pub const DEFAULT_THRESHOLD_DB: f32 = -60.0;
+
+struct Inner {
+    /// Linear amplitude, stored as f32 bits
+    threshold: AtomicU32,
+    /// Consecutive silent frames at the end of the decoded stream
+    silent_frames: AtomicU64,
+    sample_rate: AtomicU32,
+}
+
+/// Shared silence tracker fed from the decode loop
+#[derive(Clone)]
+pub struct SilenceMonitor {
+    inner: Arc<Inner>,
+}
+
+impl Default for SilenceMonitor {
+    fn default() -> Self {
+        let monitor = Self {
+            inner: Arc::new(Inner {
+                threshold: AtomicU32::new(0),
+                silent_frames: AtomicU64::new(0),
+                sample_rate: AtomicU32::new(44100),
+            }),
+        };
+        monitor.set_threshold_db(DEFAULT_THRESHOLD_DB);
+        monitor
+    }
+}
+
+impl SilenceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_threshold_db(&self, db: f32) {
+        let linear = 10f32.powf(db.min(0.0) / 20.0);
+        self.inner
+            .threshold
+            .store(linear.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Feed decoded interleaved samples (audio thread; lock-free)
+    pub fn process(&self, data: &[f32], channels: usize, sample_rate: u32) {
+        let channels = channels.max(1);
+        let threshold = f32::from_bits(self.inner.threshold.load(Ordering::Relaxed));
+        self.inner.sample_rate.store(sample_rate, Ordering::Relaxed);
+
+        let frames = data.len() / channels;
+        let last_loud = data
+            .chunks(channels)
+            .rposition(|frame| frame.iter().any(|s| s.abs() > threshold));
+        match last_loud {
+            Some(i) => self
+                .inner
+                .silent_frames
+                .store((frames - i - 1) as u64, Ordering::Relaxed),
+            None => {
+                self.inner
+                    .silent_frames
+                    .fetch_add(frames as u64, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// How long the stream has been silent so far
+    pub fn silent_for(&self) -> Duration {
+        let frames = self.inner.silent_frames.load(Ordering::Relaxed);
+        let rate = self.inner.sample_rate.load(Ordering::Relaxed).max(1);
+        Duration::from_secs_f64(frames as f64 / rate as f64)
+    }
+
+    /// Forget the current run (after a flush/seek)
+    pub fn reset(&self) {
+        self.inner.silent_frames.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silence_accumulates_until_signal() {
+        let monitor = SilenceMonitor::new();
+        monitor.process(&[0.0; 48000 * 2], 2, 48000);
+        assert_eq!(monitor.silent_for(), Duration::from_secs(1));
+
+        // Noise floor below the threshold still counts as silence
+        monitor.process(&[1e-4; 4800 * 2], 2, 48000);
+        assert_eq!(monitor.silent_for(), Duration::from_millis(1100));
+
+        // A loud frame restarts the run from that point
+        let mut buf = vec![0.0; 4800 * 2];
+        buf[(4800 - 480) * 2] = 0.5;
+        monitor.process(&buf, 2, 48000);
+        let ms = monitor.silent_for().as_millis();
+        assert!((9..=10).contains(&ms), "Expected ~10ms, got {}", ms);
+    }
+
+    #[test]
+    fn test_threshold_is_configurable() {
+        let monitor = SilenceMonitor::new();
+        monitor.set_threshold_db(-20.0);
+        monitor.process(&[0.05; 44100], 1, 44100);
+        assert_eq!(monitor.silent_for(), Duration::from_secs(1));
+
+        monitor.reset();
+        monitor.set_threshold_db(-40.0);
+        monitor.process(&[0.05; 44100], 1, 44100);
+        assert_eq!(monitor.silent_for(), Duration::ZERO);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod device;
 pub mod dsp;
+pub mod output;
 pub mod pipeline;
 pub mod sources;
 pub mod types;
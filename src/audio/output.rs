@@ -0,0 +1,438 @@
+//! Output format 🔌
+//!
+//! What the output stream asks the device for: 32-bit float, or 24/16-bit
+//! integers with TPDF dither where samples get cut short, at each track's
+//! own sample rate or a fixed one everything is resampled to. `[output]` in
+//! config.toml sets the defaults and the EQ view changes them for the
+//! session. A device that doesn't list the format gets float instead, so
+//! the stream records what it was actually opened with.
+//!
+//! `backend` picks what the stream is opened on: cpal, or on Linux PipeWire
+//! natively (see `audio::device::pipewire`), where the stream's volume is
+//! Vyom's and changes made in the mixer are reported back here.
+//!
+//! The volume percentage becomes gain through `volume_curve` (logarithmic
+//! by default: equal steps sound equally loud), and `max_volume` caps how
+//! far it can be turned up.
+//!
+//! Pausing, resuming, stopping and seeking fade over `fade_ms` instead of
+//! cutting in and out with a click; 0 turns every fade off.
+
+use crate::audio::device::AudioBackend;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicI16, AtomicU32, AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Rates the EQ view steps through after following the source
+pub const FIXED_RATES: [u32; 5] = [44100, 48000, 88200, 96000, 192000];
+
+/// Sample format of the output stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BitDepth {
+    #[default]
+    #[serde(rename = "32f")]
+    Float32,
+    #[serde(rename = "24")]
+    Int24,
+    #[serde(rename = "16")]
+    Int16,
+}
+
+impl BitDepth {
+    const ALL: [BitDepth; 3] = [BitDepth::Float32, BitDepth::Int24, BitDepth::Int16];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BitDepth::Float32 => "32f",
+            BitDepth::Int24 => "24",
+            BitDepth::Int16 => "16",
+        }
+    }
+
+    /// Integer full scale; float isn't truncated
+    pub fn full_scale(self) -> Option<f32> {
+        match self {
+            BitDepth::Float32 => None,
+            BitDepth::Int24 => Some(8_388_608.0),
+            BitDepth::Int16 => Some(32_768.0),
+        }
+    }
+
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+}
+
+/// How the volume percentage maps to gain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VolumeCurve {
+    /// `volume_range_db` spread evenly over 1-100%
+    #[default]
+    Log,
+    Cubic,
+    Linear,
+}
+
+impl VolumeCurve {
+    /// Gain at `percent`; 0% is silence on every curve
+    pub fn gain(self, percent: u8, range_db: f32) -> f32 {
+        let x = percent.min(100) as f32 / 100.0;
+        if x <= 0.0 {
+            return 0.0;
+        }
+        match self {
+            VolumeCurve::Log => 10f32.powf(range_db * (x - 1.0) / 20.0),
+            VolumeCurve::Cubic => x.powi(3),
+            VolumeCurve::Linear => x,
+        }
+    }
+
+    /// The percentage giving `gain`, the other way round
+    pub fn percent(self, gain: f32, range_db: f32) -> u8 {
+        if gain <= 0.0 {
+            return 0;
+        }
+        let x = match self {
+            VolumeCurve::Log => 1.0 + 20.0 * gain.log10() / range_db,
+            VolumeCurve::Cubic => gain.cbrt(),
+            VolumeCurve::Linear => gain,
+        };
+        (x.clamp(0.0, 1.0) * 100.0).round() as u8
+    }
+}
+
+/// `[output]` in config.toml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputConfig {
+    /// Sample format: "32f", "24" or "16"
+    pub bit_depth: BitDepth,
+    /// TPDF dither when "24" or "16" cuts samples short
+    pub dither: bool,
+    /// Fixed output rate in Hz, everything resampled to it; unset follows
+    /// each track
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+    /// "auto", "pipewire" or "cpal"
+    pub backend: AudioBackend,
+    /// "log", "cubic" or "linear"
+    pub volume_curve: VolumeCurve,
+    /// How far down 1% is on the log curve, in dB
+    pub volume_range_db: f32,
+    /// Volume can't be turned up past this (0-100)
+    pub max_volume: u8,
+    /// Fade length on pause, resume, stop and seek; 0 = none (bit-perfect)
+    pub fade_ms: u32,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            bit_depth: BitDepth::Float32,
+            dither: true,
+            sample_rate: None,
+            backend: AudioBackend::Auto,
+            volume_curve: VolumeCurve::Log,
+            volume_range_db: 60.0,
+            max_volume: 100,
+            fade_ms: 150,
+        }
+    }
+}
+
+/// What the output stream was opened with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NegotiatedFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub depth: BitDepth,
+    /// Rate of the audio coming in, before resampling
+    pub source_rate: u32,
+    /// Opened on PipeWire directly rather than through cpal
+    pub pipewire: bool,
+}
+
+struct Shared {
+    depth: AtomicU8,
+    dither: AtomicBool,
+    /// 0 = follow the source
+    sample_rate: AtomicU32,
+    /// Bumped when the stream has to be reopened
+    changes: AtomicU64,
+    negotiated: Mutex<Option<NegotiatedFormat>>,
+    backend: AudioBackend,
+    /// Volume set from outside (the PipeWire mixer), -1 = none pending
+    external_volume: AtomicI16,
+    volume_curve: VolumeCurve,
+    volume_range_db: f32,
+    max_volume: u8,
+    fade_ms: u32,
+    /// A flush is waiting for the output to fade out, asked for then
+    fading_out: AtomicBool,
+    fade_started: Mutex<Option<Instant>>,
+}
+
+/// The output format, shared between the UI and the audio thread
+#[derive(Clone)]
+pub struct OutputSetting(Arc<Shared>);
+
+impl Default for OutputSetting {
+    fn default() -> Self {
+        Self::new(&OutputConfig::default())
+    }
+}
+
+impl OutputSetting {
+    pub fn new(config: &OutputConfig) -> Self {
+        Self(Arc::new(Shared {
+            depth: AtomicU8::new(config.bit_depth as u8),
+            dither: AtomicBool::new(config.dither),
+            sample_rate: AtomicU32::new(config.sample_rate.unwrap_or(0)),
+            changes: AtomicU64::new(0),
+            negotiated: Mutex::new(None),
+            backend: config.backend,
+            external_volume: AtomicI16::new(-1),
+            volume_curve: config.volume_curve,
+            volume_range_db: config.volume_range_db.clamp(10.0, 120.0),
+            max_volume: config.max_volume.min(100),
+            fade_ms: config.fade_ms.min(2000),
+            fading_out: AtomicBool::new(false),
+            fade_started: Mutex::new(None),
+        }))
+    }
+
+    pub fn depth(&self) -> BitDepth {
+        BitDepth::ALL[self.0.depth.load(Ordering::Relaxed) as usize % BitDepth::ALL.len()]
+    }
+
+    pub fn set_depth(&self, depth: BitDepth) {
+        if self.0.depth.swap(depth as u8, Ordering::Relaxed) != depth as u8 {
+            self.0.changes.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn dither(&self) -> bool {
+        self.0.dither.load(Ordering::Relaxed)
+    }
+
+    /// Takes effect on the next buffer, without reopening the stream
+    pub fn set_dither(&self, on: bool) {
+        self.0.dither.store(on, Ordering::Relaxed);
+    }
+
+    /// The fixed rate, if there is one
+    pub fn sample_rate(&self) -> Option<u32> {
+        Some(self.0.sample_rate.load(Ordering::Relaxed)).filter(|&rate| rate > 0)
+    }
+
+    pub fn set_sample_rate(&self, rate: Option<u32>) {
+        let rate = rate.unwrap_or(0);
+        if self.0.sample_rate.swap(rate, Ordering::Relaxed) != rate {
+            self.0.changes.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Source, then each of `FIXED_RATES`
+    pub fn next_sample_rate(&self) -> Option<u32> {
+        match self.sample_rate() {
+            None => Some(FIXED_RATES[0]),
+            Some(rate) => FIXED_RATES.iter().copied().find(|&r| r > rate),
+        }
+    }
+
+    /// The rate the stream runs at for a source at `source_rate`
+    pub fn rate_for(&self, source_rate: u32) -> u32 {
+        self.sample_rate().unwrap_or(source_rate)
+    }
+
+    /// Counts changes that need the stream reopened
+    pub fn changes(&self) -> u64 {
+        self.0.changes.load(Ordering::Relaxed)
+    }
+
+    pub fn negotiated(&self) -> Option<NegotiatedFormat> {
+        self.0.negotiated.lock().ok().and_then(|format| *format)
+    }
+
+    pub fn set_negotiated(&self, format: Option<NegotiatedFormat>) {
+        if let Ok(mut slot) = self.0.negotiated.lock() {
+            *slot = format;
+        }
+    }
+
+    pub fn backend(&self) -> AudioBackend {
+        self.0.backend
+    }
+
+    /// Gain for a volume percentage, on the configured curve
+    pub fn volume_gain(&self, percent: u8) -> f32 {
+        self.0.volume_curve.gain(percent, self.0.volume_range_db)
+    }
+
+    /// The volume percentage a gain stands for
+    pub fn volume_percent(&self, gain: f32) -> u8 {
+        self.0.volume_curve.percent(gain, self.0.volume_range_db)
+    }
+
+    /// The safety cap on the volume
+    pub fn max_volume(&self) -> u8 {
+        self.0.max_volume
+    }
+
+    pub fn fade_ms(&self) -> u32 {
+        self.0.fade_ms
+    }
+
+    /// Ask the output to fade out and then flush. False when a fade asked
+    /// for earlier still hasn't finished after `timeout` (nothing is
+    /// playing it), so the flush has to happen right away.
+    pub fn fade_out(&self, timeout: Duration) -> bool {
+        let Ok(mut started) = self.0.fade_started.lock() else {
+            return false;
+        };
+        if self.fading_out() {
+            return started.is_some_and(|t| t.elapsed() < timeout);
+        }
+        *started = Some(Instant::now());
+        self.0.fading_out.store(true, Ordering::Relaxed);
+        true
+    }
+
+    /// Whether the output should be fading out for a flush
+    pub fn fading_out(&self) -> bool {
+        self.0.fading_out.load(Ordering::Relaxed)
+    }
+
+    /// Faded out (or flushed without waiting)
+    pub fn fade_out_done(&self) {
+        self.0.fading_out.store(false, Ordering::Relaxed);
+    }
+
+    /// The output's volume was changed outside Vyom (0-100)
+    pub fn report_volume(&self, volume: u8) {
+        self.0
+            .external_volume
+            .store(volume as i16, Ordering::Relaxed);
+    }
+
+    /// A volume reported since the last call, for the UI to follow
+    pub fn take_volume_report(&self) -> Option<u8> {
+        let volume = self.0.external_volume.swap(-1, Ordering::Relaxed);
+        u8::try_from(volume).ok()
+    }
+}
+
+/// Rounds float samples to integers, with triangular (TPDF) dither: the
+/// difference of two uniform values one step wide, which keeps the
+/// rounding error from following the signal. Samples that already fit
+/// (silence, a 16-bit source on a 24-bit stream) are left alone.
+pub struct Quantizer {
+    /// xorshift32 state
+    seed: u32,
+}
+
+impl Default for Quantizer {
+    fn default() -> Self {
+        Self { seed: 0x9E37_79B9 }
+    }
+}
+
+impl Quantizer {
+    fn uniform(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        (self.seed >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    pub fn quantize(&mut self, sample: f32, full_scale: f32, dither: bool) -> i32 {
+        let mut value = sample * full_scale;
+        if dither && value.fract() != 0.0 {
+            value += self.uniform() - self.uniform();
+        }
+        value.round().clamp(-full_scale, full_scale - 1.0) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_config_parses() {
+        let config: OutputConfig =
+            toml::from_str("bit_depth = \"16\"\nsample_rate = 48000").unwrap();
+        assert_eq!(config.bit_depth, BitDepth::Int16);
+        assert!(config.dither);
+        assert_eq!(config.sample_rate, Some(48000));
+        assert_eq!(config.backend, AudioBackend::Auto);
+
+        let setting = OutputSetting::new(&config);
+        assert_eq!(setting.rate_for(44100), 48000);
+        setting.set_sample_rate(None);
+        assert_eq!(setting.rate_for(44100), 44100);
+        assert_eq!(setting.changes(), 1);
+        setting.set_depth(BitDepth::Int16);
+        assert_eq!(setting.changes(), 1);
+
+        // A fade nothing plays out can't hold a flush up for good
+        assert_eq!(setting.fade_ms(), 150);
+        assert!(setting.fade_out(Duration::from_secs(1)));
+        assert!(setting.fade_out(Duration::from_secs(1)));
+        assert!(!setting.fade_out(Duration::ZERO));
+        setting.fade_out_done();
+        assert!(!setting.fading_out());
+    }
+
+    #[test]
+    fn test_volume_curves() {
+        let config: OutputConfig = toml::from_str("max_volume = 80").unwrap();
+        assert_eq!(config.volume_curve, VolumeCurve::Log);
+        let setting = OutputSetting::new(&config);
+        assert_eq!(setting.max_volume(), 80);
+
+        // Log: 60 dB over the range, so half way is -30 dB
+        assert_eq!(setting.volume_gain(0), 0.0);
+        assert!((setting.volume_gain(100) - 1.0).abs() < 1e-6);
+        let half_db = 20.0 * setting.volume_gain(50).log10();
+        assert!((half_db + 30.0).abs() < 0.01, "{} dB", half_db);
+        assert!((VolumeCurve::Cubic.gain(50, 60.0) - 0.125).abs() < 1e-6);
+
+        // Every percentage survives the round trip
+        for curve in [VolumeCurve::Log, VolumeCurve::Cubic, VolumeCurve::Linear] {
+            for percent in 0..=100 {
+                let gain = curve.gain(percent, 60.0);
+                assert_eq!(curve.percent(gain, 60.0), percent, "{:?}", curve);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quantizer_dither() {
+        let mut quantizer = Quantizer::default();
+        // Without dither, 16-bit samples come back exactly
+        for value in [-32768, -1, 0, 1, 12345, 32767] {
+            let sample = value as f32 / 32768.0;
+            assert_eq!(quantizer.quantize(sample, 32768.0, false), value);
+        }
+        assert_eq!(quantizer.quantize(1.5, 32768.0, false), 32767);
+        // ...and with it, since nothing is cut short
+        for value in [-32768, -1, 0, 1, 12345] {
+            let sample = value as f32 / 32768.0;
+            assert_eq!(quantizer.quantize(sample, 8_388_608.0, true), value << 8);
+        }
+
+        // A level under one step rounds away to nothing without dither,
+        // but survives on average with it
+        let quiet = 0.3 / 32768.0;
+        assert_eq!(quantizer.quantize(quiet, 32768.0, false), 0);
+        let n = 100_000;
+        let sum: i64 = (0..n)
+            .map(|_| quantizer.quantize(quiet, 32768.0, true) as i64)
+            .sum();
+        let mean = sum as f32 / n as f32;
+        assert!((mean - 0.3).abs() < 0.02, "mean {}", mean);
+    }
+}
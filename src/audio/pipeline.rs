@@ -1,22 +1,41 @@
-use super::dsp::EqGains;
+use super::dsp::{DspSetting, EqGains, LevelMeter, PitchSetting, SilenceMonitor};
+use super::output::OutputSetting;
 use super::sources::{run_fifo_audio_loop, run_http_audio_loop};
 use super::types::{AudioInputFormat, AudioPipelineConfig, AudioSource};
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
+
+/// How long past its length a fade-out may hold a flush up
+const FADE_GRACE: Duration = Duration::from_millis(250);
 
 /// Audio pipeline with Hi-Res support
 pub struct AudioPipeline {
     config: AudioPipelineConfig,
     eq_gains: EqGains,
+    /// Stage order and switches of the DSP chain
+    dsp: DspSetting,
+    /// Bit depth, dither and sample rate of the output stream
+    output: OutputSetting,
     running: Arc<AtomicBool>,
     pub global_volume: Arc<std::sync::atomic::AtomicU8>,
     thread_handle: Option<thread::JoinHandle<()>>,
     /// Shared buffer for visualizer
     pub vis_buffer: Option<Arc<Mutex<VecDeque<f32>>>>,
+    /// Visualizer buffer fed before the DSP chain
+    pub pre_vis_buffer: Option<Arc<Mutex<VecDeque<f32>>>>,
+    /// Output level meter
+    pub meter: Option<LevelMeter>,
+    /// Pitch shift in semitones
+    pitch: PitchSetting,
+    /// Silence tracker for gap trimming
+    silence: SilenceMonitor,
     /// Trigger to instantly flush buffers on seek/pause
     pub flush_signal: Arc<AtomicBool>,
+    /// Why the pipeline thread stopped, until the UI picks it up
+    last_error: Arc<Mutex<Option<String>>>,
 }
 
 impl AudioPipeline {
@@ -25,11 +44,18 @@ impl AudioPipeline {
         Self {
             config: AudioPipelineConfig::default(),
             eq_gains,
+            dsp: DspSetting::default(),
+            output: OutputSetting::default(),
             running: Arc::new(AtomicBool::new(false)),
             global_volume: Arc::new(std::sync::atomic::AtomicU8::new(100)),
             thread_handle: None,
             vis_buffer: None,
+            pre_vis_buffer: None,
+            meter: None,
+            pitch: PitchSetting::default(),
+            silence: SilenceMonitor::default(),
             flush_signal: Arc::new(AtomicBool::new(false)),
+            last_error: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -44,11 +70,18 @@ impl AudioPipeline {
                 format,
             },
             eq_gains,
+            dsp: DspSetting::default(),
+            output: OutputSetting::default(),
             running: Arc::new(AtomicBool::new(false)),
             global_volume: Arc::new(std::sync::atomic::AtomicU8::new(100)),
             thread_handle: None,
             vis_buffer: None,
+            pre_vis_buffer: None,
+            meter: None,
+            pitch: PitchSetting::default(),
+            silence: SilenceMonitor::default(),
             flush_signal: Arc::new(AtomicBool::new(false)),
+            last_error: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -57,14 +90,49 @@ impl AudioPipeline {
         self.vis_buffer = Some(buffer);
     }
 
+    /// Attach the visualizer buffer for the signal going into the DSP chain
+    pub fn attach_pre_visualizer(&mut self, buffer: Arc<Mutex<VecDeque<f32>>>) {
+        self.pre_vis_buffer = Some(buffer);
+    }
+
+    /// Attach output level meter
+    pub fn attach_meter(&mut self, meter: LevelMeter) {
+        self.meter = Some(meter);
+    }
+
+    /// Share the DSP chain setting with the UI
+    pub fn attach_dsp(&mut self, dsp: DspSetting) {
+        self.dsp = dsp;
+    }
+
+    /// Share the output format setting with the UI
+    pub fn attach_output(&mut self, output: OutputSetting) {
+        self.output = output;
+    }
+
+    /// Share the pitch setting with the UI
+    pub fn attach_pitch(&mut self, pitch: PitchSetting) {
+        self.pitch = pitch;
+    }
+
+    /// Share the silence tracker with the UI
+    pub fn attach_silence_monitor(&mut self, silence: SilenceMonitor) {
+        self.silence = silence;
+    }
+
     /// Set global volume (0-100)
     pub fn set_volume(&self, volume: u8) {
         self.global_volume.store(volume.min(100), Ordering::SeqCst);
     }
 
-    /// Ask the pipeline to flush its software buffers immediately
+    /// Ask the pipeline to flush its software buffers: once the output
+    /// has faded out when fades are on, immediately otherwise
     pub fn flush(&self) {
-        self.flush_signal.store(true, Ordering::SeqCst);
+        let fade = Duration::from_millis(self.output.fade_ms() as u64);
+        if fade.is_zero() || !self.is_running() || !self.output.fade_out(fade + FADE_GRACE) {
+            self.output.fade_out_done();
+            self.flush_signal.store(true, Ordering::SeqCst);
+        }
     }
 
     /// Start the audio pipeline
@@ -76,11 +144,18 @@ impl AudioPipeline {
 
         let running = self.running.clone();
         let eq_gains = self.eq_gains.clone();
+        let dsp = self.dsp.clone();
+        let output = self.output.clone();
         let global_volume = self.global_volume.clone();
         let source = self.config.source.clone();
         let format = self.config.format.clone();
         let vis_buffer = self.vis_buffer.clone();
+        let pre_vis_buffer = self.pre_vis_buffer.clone();
+        let meter = self.meter.clone();
+        let pitch = self.pitch.clone();
+        let silence = self.silence.clone();
         let flush_signal = Arc::clone(&self.flush_signal);
+        let last_error = Arc::clone(&self.last_error);
 
         running.store(true, Ordering::SeqCst);
 
@@ -91,24 +166,39 @@ impl AudioPipeline {
                     port,
                     &format,
                     eq_gains,
+                    dsp,
+                    output,
                     Arc::clone(&running),
                     Arc::clone(&global_volume),
                     vis_buffer.clone(),
+                    pre_vis_buffer.clone(),
+                    meter.clone(),
+                    pitch.clone(),
+                    silence.clone(),
                     Arc::clone(&flush_signal),
                 ),
                 AudioSource::Fifo { path } => run_fifo_audio_loop(
                     &path,
                     &format,
                     eq_gains,
+                    dsp,
+                    output,
                     Arc::clone(&running),
                     Arc::clone(&global_volume),
                     vis_buffer,
+                    pre_vis_buffer,
+                    meter,
+                    pitch,
+                    silence,
                     Arc::clone(&flush_signal),
                 ),
             };
 
             if let Err(e) = result {
                 tracing::error!("Audio pipeline error: {}", e);
+                if let Ok(mut slot) = last_error.lock() {
+                    *slot = Some(e);
+                }
             }
             running.store(false, Ordering::SeqCst);
         });
@@ -127,12 +217,18 @@ impl AudioPipeline {
     pub fn stop(&mut self) {
         self.running.store(false, Ordering::SeqCst);
         if let Some(handle) = self.thread_handle.take() {
-            let _ = handle.join();
+            if handle.join().is_err() {
+                tracing::error!("Audio pipeline thread panicked");
+            }
         }
     }
 
+    /// The error the pipeline thread died with, once
+    pub fn take_error(&self) -> Option<String> {
+        self.last_error.lock().ok().and_then(|mut slot| slot.take())
+    }
+
     /// Check if running
-    #[allow(dead_code)]
     pub fn is_running(&self) -> bool {
         self.running.load(Ordering::SeqCst)
     }
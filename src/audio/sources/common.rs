@@ -1,6 +1,8 @@
+use crate::audio::dsp::LevelMeter;
+use crate::audio::output::{BitDepth, NegotiatedFormat, OutputSetting, Quantizer};
 use crate::audio::visualizer::Visualizer;
 use cpal::traits::{DeviceTrait, StreamTrait};
-use cpal::StreamConfig;
+use cpal::{SampleFormat, SizedSample, StreamConfig};
 use std::collections::VecDeque;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::net::TcpStream;
@@ -60,6 +62,13 @@ fn parse_mpd_status(response: &str) -> Option<(u32, u16, u16)> {
     None
 }
 
+/// An open output stream; playback stops when it's dropped
+pub enum OutputStream {
+    Cpal(cpal::Stream),
+    #[cfg(target_os = "linux")]
+    PipeWire(crate::audio::device::pipewire::Stream),
+}
+
 /// Helper to build audio output stream with consistent volume/fade/visualizer logic
 #[allow(clippy::too_many_arguments)]
 pub fn build_audio_stream(
@@ -69,15 +78,24 @@ pub fn build_audio_stream(
     fade_level: Arc<AtomicU32>,
     global_volume: Arc<std::sync::atomic::AtomicU8>,
     vis_buffer: Option<Arc<Mutex<VecDeque<f32>>>>,
-    fade_speed: f32,
+    meter: Option<LevelMeter>,
     flush_signal: Arc<std::sync::atomic::AtomicBool>,
-) -> Result<cpal::Stream, String> {
+    output: &OutputSetting,
+    source_rate: u32,
+) -> Result<OutputStream, String> {
     let rb_clone = Arc::clone(&ring_buffer);
     let fl_clone = Arc::clone(&fade_level);
     let gv_clone = Arc::clone(&global_volume);
     let vb_clone = vis_buffer.clone();
     let channels = config.channels as usize;
     let sample_rate = config.sample_rate.0;
+    // On PipeWire the volume is the stream's own, applied by the server
+    let native = output.backend().use_pipewire();
+    // Per interleaved sample; fades off means full level straight away
+    let fade_speed = match output.fade_ms() {
+        0 => 1.0,
+        ms => 1000.0 / (sample_rate as f32 * channels as f32 * ms as f32),
+    };
 
     // ═══════════════════════════════════════════════════════════════════
     // PRE-FILL: Seed the ring buffer with silence BEFORE the stream
@@ -105,82 +123,189 @@ pub fn build_audio_stream(
     let mute_remaining = Arc::new(std::sync::atomic::AtomicU32::new(0));
     let mute_clone = Arc::clone(&mute_remaining);
     let flush_clone = Arc::clone(&flush_signal);
+    let out = output.clone();
 
-    let stream = device
-        .build_output_stream(
-            config,
-            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                // Audio Thread: Must run fast! ⚡
-
-                // Check if a flush just happened — if so, activate mute window
-                if flush_clone.load(Ordering::Relaxed) {
-                    // Don't clear the flag here (reader thread does that),
-                    // just set the mute window
-                    let mute_samples = sample_rate / 10; // ~100ms of silence
-                    mute_clone.store(mute_samples, Ordering::Relaxed);
-                }
+    // Audio Thread: Must run fast! ⚡
+    let render = move |data: &mut [f32]| {
+        // Check if a flush just happened — if so, activate mute window
+        if flush_clone.load(Ordering::Relaxed) {
+            // Don't clear the flag here (reader thread does that),
+            // just set the mute window
+            let mute_samples = sample_rate / 10; // ~100ms of silence
+            mute_clone.store(mute_samples, Ordering::Relaxed);
+        }
 
-                let mute_left = mute_clone.load(Ordering::Relaxed);
+        let mute_left = mute_clone.load(Ordering::Relaxed);
 
-                if mute_left > 0 {
-                    // POST-FLUSH MUTE: Output pure silence and drain ring buffer
-                    // This eliminates buzz from stale data that was already in the
-                    // ring buffer when flush fired
-                    if let Ok(mut buffer) = rb_clone.lock() {
-                        // Drain stale samples so they don't play after mute ends
-                        let drain_count = data.len().min(buffer.len());
-                        buffer.drain(..drain_count);
-                    }
-                    for sample in data.iter_mut() {
-                        *sample = 0.0;
-                    }
-                    // Reset fade to 0 during mute so we get a clean fade-in after
-                    fl_clone.store(0f32.to_bits(), Ordering::Relaxed);
-                    let consumed = (data.len() as u32).min(mute_left);
-                    mute_clone.store(mute_left - consumed, Ordering::Relaxed);
-                    return;
-                }
+        if mute_left > 0 {
+            // POST-FLUSH MUTE: Output pure silence and drain ring buffer
+            // This eliminates buzz from stale data that was already in the
+            // ring buffer when flush fired
+            if let Ok(mut buffer) = rb_clone.lock() {
+                // Drain stale samples so they don't play after mute ends
+                let drain_count = data.len().min(buffer.len());
+                buffer.drain(..drain_count);
+            }
+            for sample in data.iter_mut() {
+                *sample = 0.0;
+            }
+            // Reset fade to 0 during mute so we get a clean fade-in after
+            fl_clone.store(0f32.to_bits(), Ordering::Relaxed);
+            let consumed = (data.len() as u32).min(mute_left);
+            mute_clone.store(mute_left - consumed, Ordering::Relaxed);
+            return;
+        }
 
-                if let Ok(mut buffer) = rb_clone.lock() {
-                    let mut fade = f32::from_bits(fl_clone.load(Ordering::Relaxed));
-                    let vol = gv_clone.load(Ordering::Relaxed);
-                    let gain = (vol as f32 / 100.0).powf(3.0); // Cubic volume curve
-
-                    for sample in data.iter_mut() {
-                        if let Some(s) = buffer.pop_front() {
-                            if fade < 1.0 {
-                                fade = (fade + fade_speed).min(1.0);
-                            }
-                            *sample = s * fade * gain;
-                        } else {
-                            // Buffer underrun: output silence, fade down
-                            if fade > 0.0 {
-                                fade = (fade - fade_speed).max(0.0);
-                            }
-                            *sample = 0.0;
-                        }
+        if let Ok(mut buffer) = rb_clone.lock() {
+            let mut fade = f32::from_bits(fl_clone.load(Ordering::Relaxed));
+            let vol = gv_clone.load(Ordering::Relaxed);
+            let gain = if native { 1.0 } else { out.volume_gain(vol) };
+            // Pause, seek & co.: play what's buffered down to silence, then
+            // let the flush happen
+            let fading_out = out.fading_out();
+
+            for sample in data.iter_mut() {
+                if let Some(s) = buffer.pop_front() {
+                    if fading_out {
+                        fade = (fade - fade_speed).max(0.0);
+                    } else if fade < 1.0 {
+                        fade = (fade + fade_speed).min(1.0);
                     }
-                    // Save fade state
-                    fl_clone.store(fade.to_bits(), Ordering::Relaxed);
+                    *sample = s * fade * gain;
                 } else {
-                    // CRITICAL FIX: If lock fails, output silence instead of garbage/repeat
-                    for sample in data.iter_mut() {
-                        *sample = 0.0;
+                    // Buffer underrun: output silence, fade down
+                    if fade > 0.0 {
+                        fade = (fade - fade_speed).max(0.0);
                     }
+                    *sample = 0.0;
                 }
+            }
+            // Save fade state
+            fl_clone.store(fade.to_bits(), Ordering::Relaxed);
+            if fading_out && fade <= 0.0 {
+                out.fade_out_done();
+                flush_clone.store(true, Ordering::SeqCst);
+            }
+        } else {
+            // CRITICAL FIX: If lock fails, output silence instead of garbage/repeat
+            for sample in data.iter_mut() {
+                *sample = 0.0;
+            }
+        }
 
-                // Visualize (Post-fill)
-                if let Some(vis) = &vb_clone {
-                    Visualizer::push_samples(vis, data, channels);
-                }
-            },
+        // Visualize (Post-fill)
+        if let Some(vis) = &vb_clone {
+            Visualizer::push_samples(vis, data, channels);
+        }
+        // Meter exactly what goes to the DAC
+        if let Some(m) = &meter {
+            m.process(data, channels);
+        }
+    };
+
+    #[cfg(target_os = "linux")]
+    if native {
+        let depth = output.depth();
+        let stream = crate::audio::device::pipewire::Stream::playback(
+            sample_rate,
+            config.channels,
+            depth,
+            Box::new(render),
+            global_volume,
+            output.clone(),
+        )?;
+        output.set_negotiated(Some(NegotiatedFormat {
+            sample_rate,
+            channels: config.channels,
+            depth,
+            source_rate,
+            pipewire: true,
+        }));
+        return Ok(OutputStream::PipeWire(stream));
+    }
+
+    let depth = negotiate(device, config, output.depth());
+    let stream = match depth {
+        BitDepth::Float32 => device.build_output_stream(
+            config,
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| render(data),
             |err| tracing::error!("Audio stream error: {}", err),
             None,
-        )
-        .map_err(|e| format!("Failed to build output stream: {}", e))?;
+        ),
+        // 24 significant bits in a 32-bit container
+        BitDepth::Int24 => {
+            build_integer_stream(device, config, render, output.clone(), depth, |v| v << 8)
+        }
+        BitDepth::Int16 => {
+            build_integer_stream(device, config, render, output.clone(), depth, |v| v as i16)
+        }
+    }
+    .map_err(|e| format!("Failed to build output stream: {}", e))?;
+    output.set_negotiated(Some(NegotiatedFormat {
+        sample_rate,
+        channels: config.channels,
+        depth,
+        source_rate,
+        pipewire: false,
+    }));
 
     stream
         .play()
         .map_err(|e| format!("Failed to play stream: {}", e))?;
-    Ok(stream)
+    Ok(OutputStream::Cpal(stream))
+}
+
+/// The bit depth to open `config` with: `wanted` if the device lists it at
+/// this rate and channel count, float otherwise
+fn negotiate(device: &cpal::Device, config: &StreamConfig, wanted: BitDepth) -> BitDepth {
+    let format = match wanted {
+        BitDepth::Float32 => return wanted,
+        BitDepth::Int24 => SampleFormat::I32,
+        BitDepth::Int16 => SampleFormat::I16,
+    };
+    let supported = device.supported_output_configs().is_ok_and(|mut configs| {
+        configs.any(|c| {
+            c.sample_format() == format
+                && c.channels() == config.channels
+                && c.min_sample_rate() <= config.sample_rate
+                && config.sample_rate <= c.max_sample_rate()
+        })
+    });
+    if !supported {
+        tracing::warn!(
+            "Output device doesn't take {}-bit at {}Hz; using 32-bit float",
+            wanted.label(),
+            config.sample_rate.0
+        );
+        return BitDepth::Float32;
+    }
+    wanted
+}
+
+/// An integer stream: `render` fills float samples, which are rounded (and
+/// dithered, while that's on) to `depth`
+fn build_integer_stream<T: SizedSample + Send + 'static>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    mut render: impl FnMut(&mut [f32]) + Send + 'static,
+    output: OutputSetting,
+    depth: BitDepth,
+    to_sample: fn(i32) -> T,
+) -> Result<cpal::Stream, cpal::BuildStreamError> {
+    let full_scale = depth.full_scale().unwrap_or(1.0);
+    let mut quantizer = Quantizer::default();
+    let mut scratch: Vec<f32> = Vec::new();
+    device.build_output_stream(
+        config,
+        move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+            scratch.resize(data.len(), 0.0);
+            render(&mut scratch);
+            let dither = output.dither();
+            for (out, &sample) in data.iter_mut().zip(scratch.iter()) {
+                *out = to_sample(quantizer.quantize(sample, full_scale, dither));
+            }
+        },
+        |err| tracing::error!("Audio stream error: {}", err),
+        None,
+    )
 }
@@ -1,6 +1,12 @@
 use super::common::build_audio_stream;
-use crate::audio::dsp::{DspEqualizer, EqGains};
+use crate::audio::dsp::chain::{DspChain, DspSetting};
+use crate::audio::dsp::pitch::{PitchSetting, PitchShifter};
+use crate::audio::dsp::resample::Resampler;
+use crate::audio::dsp::silence::SilenceMonitor;
+use crate::audio::dsp::{EqGains, LevelMeter};
+use crate::audio::output::OutputSetting;
 use crate::audio::types::AudioInputFormat;
+use crate::audio::visualizer::Visualizer;
 use cpal::traits::HostTrait;
 use cpal::StreamConfig;
 use std::collections::VecDeque;
@@ -18,9 +24,15 @@ pub fn run_fifo_audio_loop(
     fifo_path: &str,
     format: &AudioInputFormat,
     eq_gains: EqGains,
+    dsp: DspSetting,
+    output: OutputSetting,
     running: Arc<AtomicBool>,
     global_volume: Arc<std::sync::atomic::AtomicU8>,
     vis_buffer: Option<Arc<Mutex<VecDeque<f32>>>>,
+    pre_vis_buffer: Option<Arc<Mutex<VecDeque<f32>>>>,
+    meter: Option<LevelMeter>,
+    pitch: PitchSetting,
+    silence: SilenceMonitor,
     flush_signal: Arc<AtomicBool>,
 ) -> Result<(), String> {
     // Get output device
@@ -40,16 +52,21 @@ pub fn run_fifo_audio_loop(
         current_channels
     );
 
-    // Use detected sample rate for bit-perfect output
-    let stream_config = StreamConfig {
+    // Use detected sample rate for bit-perfect output, unless the output
+    // has a fixed one
+    let stream_config = |output_rate: u32| StreamConfig {
         channels: current_channels,
-        sample_rate: cpal::SampleRate(current_sample_rate),
+        sample_rate: cpal::SampleRate(output_rate),
         buffer_size: cpal::BufferSize::Fixed(1024),
     };
+    let mut output_rate = output.rate_for(current_sample_rate);
+    let mut output_changes = output.changes();
 
-    // Create EQ at correct sample rate
+    // Create the DSP chain at correct sample rate
     // Clone eq_gains because we might need it again later for dynamic updates
-    let mut equalizer = DspEqualizer::new(current_sample_rate as f32, eq_gains.clone());
+    let mut dsp_chain = DspChain::new(current_sample_rate, eq_gains.clone(), dsp.clone());
+    let mut pitch_shifter = PitchShifter::new(current_sample_rate);
+    let mut resampler = Resampler::new(current_sample_rate, output_rate, current_channels as usize);
 
     let ring_buffer = Arc::new(std::sync::Mutex::new(
         std::collections::VecDeque::<f32>::with_capacity(65536), // Larger for Hi-Res
@@ -57,17 +74,23 @@ pub fn run_fifo_audio_loop(
 
     let fade_level = Arc::new(std::sync::atomic::AtomicU32::new(0));
 
+    let build_stream = |output_rate: u32| {
+        build_audio_stream(
+            &device,
+            &stream_config(output_rate),
+            ring_buffer.clone(),
+            fade_level.clone(),
+            global_volume.clone(),
+            vis_buffer.clone(),
+            meter.clone(),
+            flush_signal.clone(),
+            &output,
+            current_sample_rate,
+        )
+    };
+
     // Initial Stream
-    let stream = build_audio_stream(
-        &device,
-        &stream_config,
-        ring_buffer.clone(),
-        fade_level.clone(),
-        global_volume.clone(),
-        vis_buffer.clone(),
-        0.001, // FADE_SPEED for FIFO (~30ms fade-in at 44100Hz)
-        flush_signal.clone(),
-    )?;
+    let stream = build_stream(output_rate)?;
 
     // Calculate bytes per sample based on detected bit depth
     let bytes_per_sample_val = (current_bits_per_sample / 8) as usize;
@@ -125,11 +148,36 @@ pub fn run_fifo_audio_loop(
                 // Reset fade to 0 so audio fades in smoothly on resume
                 // instead of popping at full volume
                 fade_level.store(0f32.to_bits(), Ordering::SeqCst);
-                equalizer.reset_filters();
+                dsp_chain.reset();
+                pitch_shifter.reset();
+                resampler.reset();
+                silence.reset();
                 // Break to reopen FIFO and drop OS kernel buffer
                 break;
             }
 
+            // Bit depth or rate changed in the EQ view: reopen the stream,
+            // dropping what's buffered for the old one
+            if output.changes() != output_changes {
+                output_changes = output.changes();
+                let rate = output.rate_for(current_sample_rate);
+                if let Ok(mut buffer) = ring_buffer.lock() {
+                    buffer.clear();
+                }
+                match build_stream(rate) {
+                    Ok(stream) => {
+                        _active_stream = stream;
+                        output_rate = rate;
+                        resampler = Resampler::new(
+                            current_sample_rate,
+                            output_rate,
+                            current_channels as usize,
+                        );
+                    }
+                    Err(e) => tracing::error!("Failed to rebuild cpal stream: {}", e),
+                }
+            }
+
             match reader.read(&mut read_buffer) {
                 Ok(0) => {
                     thread::sleep(Duration::from_millis(10));
@@ -199,7 +247,22 @@ pub fn run_fifo_audio_loop(
                         }
                     }
 
-                    equalizer.process_buffer(&mut float_buffer);
+                    silence.process(
+                        &float_buffer,
+                        current_channels as usize,
+                        current_sample_rate,
+                    );
+                    // Spectrum "pre" tap, ahead of the EQ and the rest of the chain
+                    if let Some(pre) = &pre_vis_buffer {
+                        Visualizer::push_samples(pre, &float_buffer, current_channels as usize);
+                    }
+                    dsp_chain.process_buffer(&mut float_buffer);
+                    pitch_shifter.process(
+                        &mut float_buffer,
+                        current_channels as usize,
+                        pitch.ratio(),
+                    );
+                    resampler.process(&mut float_buffer);
 
                     // Backpressure: Wait for space 🛑
                     let max_size = 65536;
@@ -213,7 +276,7 @@ pub fn run_fifo_audio_loop(
                         }
                         thread::sleep(Duration::from_millis(5));
                         if !running.load(Ordering::SeqCst) {
-                            equalizer.reset_filters();
+                            dsp_chain.reset();
                             return Ok(());
                         }
                     }
@@ -232,7 +295,7 @@ pub fn run_fifo_audio_loop(
                     continue;
                 }
                 Err(_) => {
-                    equalizer.reset_filters();
+                    dsp_chain.reset();
                     break;
                 }
             }
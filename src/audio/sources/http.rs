@@ -1,6 +1,13 @@
-use super::common::build_audio_stream;
-use crate::audio::dsp::{DspEqualizer, EqGains};
+use super::common::{build_audio_stream, OutputStream};
+use crate::audio::dsp::chain::{DspChain, DspSetting};
+use crate::audio::dsp::gapless::convert_tail;
+use crate::audio::dsp::pitch::{PitchSetting, PitchShifter};
+use crate::audio::dsp::resample::Resampler;
+use crate::audio::dsp::silence::SilenceMonitor;
+use crate::audio::dsp::{EqGains, LevelMeter};
+use crate::audio::output::OutputSetting;
 use crate::audio::types::AudioInputFormat;
+use crate::audio::visualizer::Visualizer;
 use cpal::traits::HostTrait;
 use cpal::StreamConfig;
 use std::collections::VecDeque;
@@ -70,9 +77,15 @@ pub fn run_http_audio_loop(
     port: u16,
     initial_format: &AudioInputFormat,
     eq_gains: EqGains,
+    dsp: DspSetting,
+    output: OutputSetting,
     running: Arc<AtomicBool>,
     global_volume: Arc<std::sync::atomic::AtomicU8>,
     vis_buffer: Option<Arc<Mutex<VecDeque<f32>>>>,
+    pre_vis_buffer: Option<Arc<Mutex<VecDeque<f32>>>>,
+    meter: Option<LevelMeter>,
+    pitch: PitchSetting,
+    silence: SilenceMonitor,
     flush_signal: Arc<AtomicBool>,
 ) -> Result<(), String> {
     // Get output device
@@ -81,9 +94,12 @@ pub fn run_http_audio_loop(
         .default_output_device()
         .ok_or("No output device available")?;
 
-    let mut _current_stream: Option<cpal::Stream> = None;
+    let mut _current_stream: Option<OutputStream> = None;
     let mut current_sample_rate = initial_format.sample_rate;
     let mut current_channels = initial_format.channels;
+    // The stream's rate: the source's, or the fixed one everything is resampled to
+    let mut output_rate = output.rate_for(current_sample_rate);
+    let mut output_changes = output.changes();
 
     let ring_buffer = Arc::new(std::sync::Mutex::new(
         std::collections::VecDeque::<f32>::with_capacity(32768),
@@ -96,10 +112,10 @@ pub fn run_http_audio_loop(
 
     // Helper to build stream with correct params for HTTP loop
     let flush_sig_orig = flush_signal.clone();
-    let build_stream = |sample_rate: u32, channels: u16| -> Result<cpal::Stream, String> {
+    let build_stream = |source_rate: u32, channels: u16| -> Result<OutputStream, String> {
         let stream_config = StreamConfig {
             channels,
-            sample_rate: cpal::SampleRate(sample_rate),
+            sample_rate: cpal::SampleRate(output.rate_for(source_rate)),
             buffer_size: cpal::BufferSize::Fixed(1024),
         };
 
@@ -110,17 +126,21 @@ pub fn run_http_audio_loop(
             fade_level.clone(),
             global_volume.clone(),
             vis_buffer_orig.clone(),
-            0.001, // FADE_SPEED for HTTP (~30ms fade-in at 44100Hz)
+            meter.clone(),
             flush_sig_orig.clone(),
+            &output,
+            source_rate,
         )
     };
 
     // Initial stream build (fallback)
     _current_stream = Some(build_stream(current_sample_rate, current_channels)?);
 
-    // EQ instance for processing loop (needs to match sample rate too!)
+    // DSP chain for processing loop (needs to match sample rate too!)
     // We'll recreate it if rate changes.
-    let mut processing_eq = DspEqualizer::new(current_sample_rate as f32, eq_gains.clone());
+    let mut dsp_chain = DspChain::new(current_sample_rate, eq_gains.clone(), dsp.clone());
+    let mut pitch_shifter = PitchShifter::new(current_sample_rate);
+    let mut resampler = Resampler::new(current_sample_rate, output_rate, current_channels as usize);
 
     while running.load(Ordering::SeqCst) {
         let reader = match connect_to_http_stream(host, port) {
@@ -143,7 +163,10 @@ pub fn run_http_audio_loop(
                 buffer.clear();
             }
             fade_level.store(0f32.to_bits(), Ordering::SeqCst);
-            processing_eq.reset_filters();
+            dsp_chain.reset();
+            pitch_shifter.reset();
+            resampler.reset();
+            silence.reset();
         }
 
         let hint = Hint::new();
@@ -202,8 +225,16 @@ pub fn run_http_audio_loop(
                 }
             };
 
+        // Keep whatever the old stream hadn't played yet (empty after a flush)
+        let (old_rate, old_channels) = (output_rate, current_channels);
+        let tail: Vec<f32> = ring_buffer
+            .lock()
+            .map(|mut b| b.drain(..).collect())
+            .unwrap_or_default();
+
         // Stream Reconfiguration
-        if p_sample_rate != current_sample_rate || p_channels != current_channels {
+        let source_changed = p_sample_rate != current_sample_rate || p_channels != current_channels;
+        if source_changed {
             tracing::info!(
                 "⟳ Audio Format Changed: {}Hz / {}ch",
                 p_sample_rate,
@@ -212,8 +243,19 @@ pub fn run_http_audio_loop(
             current_sample_rate = p_sample_rate;
             current_channels = p_channels;
 
-            processing_eq = DspEqualizer::new(current_sample_rate as f32, eq_gains.clone());
-
+            dsp_chain = DspChain::new(current_sample_rate, eq_gains.clone(), dsp.clone());
+            pitch_shifter = PitchShifter::new(current_sample_rate);
+        }
+        // A fixed output rate keeps the stream when only the source rate moves
+        let stream_changed = output.rate_for(current_sample_rate) != output_rate
+            || current_channels != old_channels
+            || output.changes() != output_changes;
+        if source_changed || stream_changed {
+            output_rate = output.rate_for(current_sample_rate);
+            resampler = Resampler::new(current_sample_rate, output_rate, current_channels as usize);
+        }
+        if stream_changed {
+            output_changes = output.changes();
             _current_stream = match build_stream(current_sample_rate, current_channels) {
                 Ok(s) => Some(s),
                 Err(e) => {
@@ -224,21 +266,62 @@ pub fn run_http_audio_loop(
             };
         }
 
+        // Join the old and new streams gaplessly: the tail plays out first
+        let tail = convert_tail(
+            &tail,
+            old_rate,
+            old_channels as usize,
+            output_rate,
+            current_channels as usize,
+        );
         if let Ok(mut buffer) = ring_buffer.lock() {
             buffer.clear();
+            buffer.extend(tail);
         }
 
         let mut sample_buf: Option<symphonia::core::audio::SampleBuffer<f32>> = None;
 
         // Packet decode loop
         while running.load(Ordering::SeqCst) {
+            // Bit depth or rate changed in the EQ view: reopen the stream in
+            // place, keeping what's buffered
+            if output.changes() != output_changes {
+                output_changes = output.changes();
+                let old_rate = output_rate;
+                output_rate = output.rate_for(current_sample_rate);
+                let tail: Vec<f32> = ring_buffer
+                    .lock()
+                    .map(|mut b| b.drain(..).collect())
+                    .unwrap_or_default();
+                _current_stream = match build_stream(current_sample_rate, current_channels) {
+                    Ok(s) => Some(s),
+                    Err(e) => {
+                        tracing::error!("Failed to rebuild cpal stream: {}", e);
+                        break;
+                    }
+                };
+                if output_rate != old_rate {
+                    resampler =
+                        Resampler::new(current_sample_rate, output_rate, current_channels as usize);
+                }
+                let channels = current_channels as usize;
+                let tail = convert_tail(&tail, old_rate, channels, output_rate, channels);
+                if let Ok(mut buffer) = ring_buffer.lock() {
+                    buffer.clear();
+                    buffer.extend(tail);
+                }
+            }
+
             if flush_signal.load(Ordering::SeqCst) {
                 flush_signal.store(false, Ordering::SeqCst);
                 if let Ok(mut buffer) = ring_buffer.lock() {
                     buffer.clear();
                 }
                 fade_level.store(0f32.to_bits(), Ordering::SeqCst);
-                processing_eq.reset_filters();
+                dsp_chain.reset();
+                pitch_shifter.reset();
+                resampler.reset();
+                silence.reset();
                 break; // Break the internal decode loop to reconnect the HTTP socket
             }
 
@@ -253,7 +336,7 @@ pub fn run_http_audio_loop(
                 }
                 Err(e) => {
                     tracing::debug!("Stream ended or Error: {:?} - Reconnecting...", e);
-                    processing_eq.reset_filters();
+                    dsp_chain.reset();
                     break;
                 }
             };
@@ -280,8 +363,23 @@ pub fn run_http_audio_loop(
                         let samples = buf.samples();
 
                         let mut float_buffer = samples.to_vec();
-
-                        processing_eq.process_buffer(&mut float_buffer);
+                        silence.process(
+                            &float_buffer,
+                            current_channels as usize,
+                            current_sample_rate,
+                        );
+
+                        // Spectrum "pre" tap, ahead of the EQ and the rest of the chain
+                        if let Some(pre) = &pre_vis_buffer {
+                            Visualizer::push_samples(pre, &float_buffer, current_channels as usize);
+                        }
+                        dsp_chain.process_buffer(&mut float_buffer);
+                        pitch_shifter.process(
+                            &mut float_buffer,
+                            current_channels as usize,
+                            pitch.ratio(),
+                        );
+                        resampler.process(&mut float_buffer);
 
                         // Backpressure: Wait for space 🛑
                         let max_buffer_size = 32768;
@@ -295,7 +393,7 @@ pub fn run_http_audio_loop(
                             }
                             thread::sleep(Duration::from_millis(5));
                             if !running.load(Ordering::SeqCst) {
-                                processing_eq.reset_filters();
+                                dsp_chain.reset();
                                 return Ok(());
                             }
                         }
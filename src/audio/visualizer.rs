@@ -3,12 +3,18 @@ use spectrum_analyzer::{samples_fft_to_spectrum, scaling::divide_by_N_sqrt, Freq
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
+/// Lowest level reported by `spectrum_db` (treated as silence)
+pub const SPECTRUM_FLOOR_DB: f32 = -96.0;
+
 /// Native FFT Visualizer State
 pub struct Visualizer {
     /// Lock-protected buffer of incoming audio samples
     /// We keep a rolling window of 2048 or 4096 samples for FFT
     audio_buffer: Arc<Mutex<VecDeque<f32>>>,
 
+    /// Same, but fed before the DSP chain (the EQ spectrum's "pre" tap)
+    pre_buffer: Arc<Mutex<VecDeque<f32>>>,
+
     /// Sample rate (typically 44100 or 48000)
     sample_rate: u32,
 
@@ -26,18 +32,23 @@ pub struct Visualizer {
 
     /// Last update timestamp for Framerate-Independent Physics ⏱️
     last_update: Option<std::time::Instant>,
+
+    /// Smoothed dBFS levels for the EQ spectrum overlay, output and pre-DSP tap
+    spectrum_prev: [Vec<f32>; 2],
 }
 
 impl Visualizer {
     pub fn new(sample_rate: u32) -> Self {
         Self {
             audio_buffer: Arc::new(Mutex::new(VecDeque::with_capacity(8192))),
+            pre_buffer: Arc::new(Mutex::new(VecDeque::with_capacity(8192))),
             sample_rate,
             fft_size: 4096, // High Res FFT for stable bass
             prev_bars: vec![0.0; 200],
             velocities: vec![0.0; 200],
             max_val: 0.001,
             last_update: None,
+            spectrum_prev: [Vec::new(), Vec::new()],
         }
     }
 
@@ -46,6 +57,11 @@ impl Visualizer {
         self.audio_buffer.clone()
     }
 
+    /// Handle for the audio thread to push samples before the DSP chain
+    pub fn get_pre_buffer(&self) -> Arc<Mutex<VecDeque<f32>>> {
+        self.pre_buffer.clone()
+    }
+
     /// Push raw audio samples into the buffer via shared handle
     pub fn push_samples(buffer: &Arc<Mutex<VecDeque<f32>>>, new_samples: &[f32], channels: usize) {
         if let Ok(mut buf) = buffer.lock() {
@@ -68,24 +84,84 @@ impl Visualizer {
         }
     }
 
+    /// Most recent `fft_size` samples of the output (or `pre` DSP), oldest first
+    fn latest_window(&self, pre: bool) -> Option<Vec<f32>> {
+        let buffer = if pre {
+            &self.pre_buffer
+        } else {
+            &self.audio_buffer
+        };
+        let buf = buffer.lock().ok()?;
+        if buf.len() < self.fft_size {
+            return None;
+        }
+        Some(
+            buf.iter()
+                .skip(buf.len() - self.fft_size)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Spectrum level in dBFS at each requested frequency (EQ overlay 📈),
+    /// of the output or of the signal going into the DSP chain (`pre`)
+    ///
+    /// Unlike `get_bars` there is no auto-gain, so levels stay comparable
+    /// while the EQ changes. Fast attack, ~90dB/s release.
+    pub fn spectrum_db(&mut self, freqs: &[f32], pre: bool) -> Vec<f32> {
+        let mut smoothed = std::mem::take(&mut self.spectrum_prev[pre as usize]);
+        if smoothed.len() != freqs.len() {
+            smoothed = vec![SPECTRUM_FLOOR_DB; freqs.len()];
+        }
+
+        let spectrum = self.latest_window(pre).and_then(|input| {
+            samples_fft_to_spectrum(
+                &hann_window(&input),
+                self.sample_rate,
+                FrequencyLimit::Range(20.0, 20_000.0),
+                None,
+            )
+            .ok()
+        });
+        let data = spectrum.as_ref().map(|s| s.data()).unwrap_or(&[]);
+
+        // Hann window (coherent gain 0.5) on a one-sided spectrum:
+        // a full-scale sine peaks at N/4
+        let full_scale = self.fft_size as f32 / 4.0;
+
+        for (prev, &freq) in smoothed.iter_mut().zip(freqs) {
+            // Peak bin within ±1/12 octave, falling back to the nearest bin
+            let lo = freq * 2f32.powf(-1.0 / 12.0);
+            let hi = freq * 2f32.powf(1.0 / 12.0);
+            let start = data.partition_point(|(f, _)| f.val() < lo);
+            let peak = data[start..]
+                .iter()
+                .take_while(|(f, _)| f.val() <= hi)
+                .map(|(_, v)| v.val())
+                .reduce(f32::max)
+                .or_else(|| {
+                    data.get(start.min(data.len().saturating_sub(1)))
+                        .map(|(_, v)| v.val())
+                })
+                .unwrap_or(0.0);
+
+            let db = (20.0 * (peak / full_scale).max(1e-6).log10()).max(SPECTRUM_FLOOR_DB);
+            *prev = if db > *prev {
+                db
+            } else {
+                (*prev - 1.5).max(db)
+            };
+        }
+
+        self.spectrum_prev[pre as usize] = smoothed.clone();
+        smoothed
+    }
+
     /// Process FFT and return normalized bar heights (0.0 - 1.0)
     pub fn get_bars(&mut self, count: usize) -> Vec<f32> {
-        let samples = {
-            if let Ok(buf) = self.audio_buffer.lock() {
-                if buf.len() < self.fft_size {
-                    return vec![0.0; count];
-                }
-                buf.iter()
-                    .rev()
-                    .take(self.fft_size)
-                    .cloned()
-                    .collect::<Vec<_>>()
-            } else {
-                return vec![0.0; count];
-            }
+        let Some(input) = self.latest_window(false) else {
+            return vec![0.0; count];
         };
-
-        let input: Vec<f32> = samples.into_iter().rev().collect();
         let windowed_input = hann_window(&input);
 
         // FFT
@@ -0,0 +1,159 @@
+//! Leave the terminal usable, whatever happens 🚨
+//!
+//! A panic on the UI thread used to leave the shell in raw mode on the
+//! alternate screen. The panic hook puts the terminal back before anything
+//! is printed, [`TerminalGuard`] does the same when `main` returns early
+//! with an error, and a crash report with the backtrace and the last log
+//! lines goes to `~/.cache/vyom/crash-<time>.txt` (`crash_reports = false`
+//! turns that off). Panics in background tasks don't take the UI down, so
+//! they are only reported.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether a report file is written (`crash_reports` in config.toml)
+static REPORTS: AtomicBool = AtomicBool::new(true);
+/// Whether raw mode / the alternate screen are on
+static TERMINAL_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Log lines included in a report
+const REPORT_LOG_LINES: usize = 50;
+
+pub fn set_reports(enabled: bool) {
+    REPORTS.store(enabled, Ordering::Relaxed);
+}
+
+/// Raw mode off, main screen back, cursor shown; only if we took them
+pub fn restore_terminal() {
+    use crossterm::event::PopKeyboardEnhancementFlags;
+    use crossterm::{cursor::Show, execute, terminal};
+
+    if !TERMINAL_TAKEN.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(
+        std::io::stdout(),
+        terminal::LeaveAlternateScreen,
+        Show,
+        PopKeyboardEnhancementFlags
+    );
+}
+
+/// Restores the terminal when dropped; create it right after taking it over
+pub struct TerminalGuard(());
+
+impl TerminalGuard {
+    pub fn new() -> Self {
+        TERMINAL_TAKEN.store(true, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Default for TerminalGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Chain our hook in front of the one already installed (human-panic's
+/// friendly message)
+pub fn install_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let thread = std::thread::current();
+        let name = thread.name().unwrap_or("unnamed").to_string();
+        let message = panic_message(info);
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}", l.file(), l.line()))
+            .unwrap_or_default();
+        tracing::error!("Panic in {} at {}: {}", name, location, message);
+
+        let report = REPORTS.load(Ordering::Relaxed).then(|| {
+            let content = report(&name, &message, &location);
+            let path = report_path(&crate::logging::log_dir());
+            std::fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))
+                .and_then(|_| std::fs::write(&path, content))
+                .map(|_| path)
+        });
+
+        if name == "main" {
+            restore_terminal();
+            previous(info);
+            match report {
+                Some(Ok(path)) => eprintln!("Crash report: {}", path.display()),
+                Some(Err(e)) => eprintln!("Could not write the crash report: {}", e),
+                None => {}
+            }
+        }
+    }));
+}
+
+fn panic_message(info: &std::panic::PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+/// `crash-<unix seconds>.txt` in `dir`
+fn report_path(dir: &Path) -> PathBuf {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    dir.join(format!("crash-{}.txt", secs))
+}
+
+/// What goes into the report file
+pub fn report(thread: &str, message: &str, location: &str) -> String {
+    let mut out = format!(
+        "Vyom {} crashed\n\nThread: {}\nAt: {}\nMessage: {}\nOS: {} {}\n\nBacktrace:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        thread,
+        location,
+        message,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::backtrace::Backtrace::force_capture()
+    );
+    let lines = crate::logging::recent(tracing::Level::TRACE);
+    if !lines.is_empty() {
+        out.push_str("\nLast log lines:\n");
+        for line in &lines[lines.len().saturating_sub(REPORT_LOG_LINES)..] {
+            out.push_str(&format!(
+                "{} {:>5} {}: {}\n",
+                line.time, line.level, line.target, line.message
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_contents() {
+        let report = report("main", "index out of bounds", "src/app/runner.rs:10");
+        assert!(report.starts_with(&format!("Vyom {} crashed", env!("CARGO_PKG_VERSION"))));
+        assert!(report.contains("Message: index out of bounds"));
+        assert!(report.contains("At: src/app/runner.rs:10"));
+        assert!(report.contains("Backtrace:"));
+        assert!(report_path(Path::new("/tmp"))
+            .to_string_lossy()
+            .starts_with("/tmp/crash-"));
+        // Nothing was taken over, so there is nothing to undo
+        restore_terminal();
+    }
+}
@@ -25,6 +25,20 @@ pub enum VyomError {
     PlayerCommand(String),
 }
 
+impl VyomError {
+    /// The toast text, in the user's language
+    pub fn user_message(&self) -> String {
+        match self {
+            VyomError::MpdConnection(e) => crate::t!("error.mpd_connection", error = e),
+            VyomError::ConfigParse(e) => crate::t!("error.config_parse", error = e),
+            VyomError::AudioPipeline(e) => crate::t!("error.audio", error = e),
+            VyomError::LyricsFetch(e) => crate::t!("error.lyrics_fetch", error = e),
+            VyomError::ArtworkFetch(e) => crate::t!("error.artwork_fetch", error = e),
+            VyomError::PlayerCommand(e) => crate::t!("error.player_command", error = e),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
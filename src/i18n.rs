@@ -0,0 +1,239 @@
+//! Translations for everything Vyom says 🌐
+//!
+//! Each language is a TOML catalog in `assets/locales/<lang>.toml`, compiled
+//! in. Tables only group keys: `[toast] paused = "..."` is `toast.paused`.
+//! English is complete and fills in whatever a catalog leaves out. A file at
+//! `~/.config/vyom/locales/<lang>.toml` adds a language or overrides keys of
+//! a bundled one. Look text up with `t!("toast.paused")`; `{name}`
+//! placeholders are filled with `t!("toast.volume", percent = 40)`.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// Used when nothing is configured and the environment doesn't say
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Catalogs compiled into the binary
+const BUILTIN: &[(&str, &str)] = &[
+    ("en", include_str!("../assets/locales/en.toml")),
+    ("de", include_str!("../assets/locales/de.toml")),
+    ("es", include_str!("../assets/locales/es.toml")),
+];
+
+/// Active strings, English already merged underneath
+static ACTIVE: RwLock<Option<HashMap<String, String>>> = RwLock::new(None);
+
+/// Look up `key` in the active language
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::text($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format(
+            $key,
+            &[$((stringify!($name), $value.to_string())),+],
+        )
+    };
+}
+
+/// Translated text for `key` (the key itself if no catalog has it)
+pub fn text(key: &str) -> String {
+    if let Ok(guard) = ACTIVE.read() {
+        if let Some(strings) = guard.as_ref() {
+            return strings.get(key).cloned().unwrap_or_else(|| key.to_string());
+        }
+    }
+    // Nothing chosen yet (tests, early errors): English
+    if let Ok(mut guard) = ACTIVE.write() {
+        let strings = guard.get_or_insert_with(|| builtin("en").unwrap_or_default());
+        return strings.get(key).cloned().unwrap_or_else(|| key.to_string());
+    }
+    key.to_string()
+}
+
+/// Translated text with `{name}` placeholders filled in
+pub fn format(key: &str, args: &[(&str, String)]) -> String {
+    let mut out = text(key);
+    for (name, value) in args {
+        out = out.replace(&format!("{{{}}}", name), value);
+    }
+    out
+}
+
+/// Switch language: the configured one, else the locale from the environment.
+/// Returns a warning when the language is unknown (English is used then).
+pub fn set_language(configured: Option<&str>) -> Option<String> {
+    let lang = configured
+        .map(|l| l.trim().to_lowercase())
+        .unwrap_or_else(detect_language);
+
+    let mut strings = builtin("en").unwrap_or_default();
+    let mut known = lang == "en";
+    if let Some(bundled) = builtin(&lang) {
+        strings.extend(bundled);
+        known = true;
+    }
+    let mut warning = None;
+    if let Ok(content) = std::fs::read_to_string(user_catalog_path(&lang)) {
+        match parse(&content) {
+            Ok(user) => {
+                strings.extend(user);
+                known = true;
+            }
+            Err(e) => warning = Some(format!("🌐 {}.toml: {}", lang, e)),
+        }
+    }
+    if !known && configured.is_some() {
+        warning = Some(format!("🌐 Unknown language: {}", lang));
+    }
+
+    if let Ok(mut guard) = ACTIVE.write() {
+        *guard = Some(strings);
+    }
+    warning
+}
+
+/// `de_DE.UTF-8` -> `de`, from the usual locale variables
+fn detect_language() -> String {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.is_empty())
+        .and_then(|v| language_of(&v))
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string())
+}
+
+fn language_of(locale: &str) -> Option<String> {
+    let lang = locale.split(['_', '.', '@', '-']).next()?.to_lowercase();
+    match lang.as_str() {
+        "" | "c" | "posix" => None,
+        _ => Some(lang),
+    }
+}
+
+/// `~/.config/vyom/locales/<lang>.toml`
+pub fn user_catalog_path(lang: &str) -> PathBuf {
+    crate::app::config::AppConfig::get_config_dir()
+        .join("locales")
+        .join(format!("{}.toml", lang))
+}
+
+fn builtin(lang: &str) -> Option<HashMap<String, String>> {
+    BUILTIN
+        .iter()
+        .find(|(name, _)| *name == lang)
+        .and_then(|(_, content)| parse(content).ok())
+}
+
+/// Flatten a catalog's tables into dotted keys
+pub fn parse(content: &str) -> Result<HashMap<String, String>, String> {
+    let table: toml::Table = toml::from_str(content).map_err(|e| e.message().to_string())?;
+    let mut strings = HashMap::new();
+    flatten("", &table, &mut strings);
+    Ok(strings)
+}
+
+fn flatten(prefix: &str, table: &toml::Table, out: &mut HashMap<String, String>) {
+    for (key, value) in table {
+        let full = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            toml::Value::String(s) => {
+                out.insert(full, s.clone());
+            }
+            toml::Value::Table(t) => flatten(&full, t, out),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(s: &str) -> BTreeSet<&str> {
+        s.split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogs_match_english() {
+        let en = builtin("en").unwrap();
+        for (lang, content) in BUILTIN {
+            let strings = parse(content).unwrap_or_else(|e| panic!("{}: {}", lang, e));
+            for (key, value) in &strings {
+                let english = en
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{}: {} is not in en.toml", lang, key));
+                assert_eq!(
+                    placeholders(value),
+                    placeholders(english),
+                    "{}: placeholders of {}",
+                    lang,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_used_key_exists() {
+        let en = builtin("en").unwrap();
+        let mut stack = vec![PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src")];
+        let mut missing = Vec::new();
+        while let Some(path) = stack.pop() {
+            if path.is_dir() {
+                stack.extend(
+                    std::fs::read_dir(&path)
+                        .unwrap()
+                        .flatten()
+                        .map(|e| e.path()),
+                );
+                continue;
+            }
+            if path.extension().is_none_or(|e| e != "rs") || path.ends_with("i18n.rs") {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap();
+            for (at, _) in source.match_indices("t!(\"") {
+                // Not print!(" and friends
+                if source[..at].ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                    continue;
+                }
+                let key = source[at + 4..].split('"').next().unwrap_or_default();
+                if !en.contains_key(key) {
+                    missing.push(format!("{}: {}", path.display(), key));
+                }
+            }
+        }
+        assert!(
+            missing.is_empty(),
+            "Keys missing from en.toml: {:#?}",
+            missing
+        );
+    }
+
+    #[test]
+    fn test_lookup_and_placeholders() {
+        let strings = parse("[a]\nb = \"Hi {name}, {name}!\"\n[a.c]\nd = \"x\"").unwrap();
+        assert_eq!(strings["a.b"], "Hi {name}, {name}!");
+        assert_eq!(strings["a.c.d"], "x");
+        assert!(parse("a = ").is_err());
+
+        assert_eq!(language_of("de_DE.UTF-8").as_deref(), Some("de"));
+        assert_eq!(language_of("pt-BR").as_deref(), Some("pt"));
+        assert_eq!(language_of("C.UTF-8"), None);
+        assert_eq!(language_of("POSIX"), None);
+
+        // Unknown keys show up as themselves rather than vanishing
+        assert_eq!(text("no.such.key"), "no.such.key");
+    }
+}
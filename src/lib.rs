@@ -1,7 +1,10 @@
 pub mod app;
 pub mod artwork;
 pub mod audio;
+pub mod crash;
 pub mod error;
+pub mod i18n;
+pub mod logging;
 
 pub mod player;
 pub mod ui;
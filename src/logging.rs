@@ -0,0 +1,214 @@
+//! Logging: `~/.cache/vyom/vyom.log` plus the last lines in memory 📝
+//!
+//! Everything goes through `tracing`. `--log-level` sets how much; our own
+//! modules log at that level, dependencies at most at `info` so `debug`
+//! isn't buried under HTTP internals. The in-memory copy is what the log
+//! viewer (`F12`) shows.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Lines kept for the viewer
+const CAPACITY: usize = 1000;
+
+static RECENT: Mutex<VecDeque<LogLine>> = Mutex::new(VecDeque::new());
+/// Lines logged so far, so the viewer knows when to redraw
+static LOGGED: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, clap::ValueEnum)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+impl LogLevel {
+    /// The `--log-level` value
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogLine {
+    /// Local wall-clock time, `HH:MM:SS`
+    pub time: String,
+    pub level: Level,
+    /// Module that logged it (`vyom::app::lyrics`)
+    pub target: String,
+    pub message: String,
+}
+
+/// `~/.cache/vyom`
+pub fn log_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("vyom")
+}
+
+/// Install the subscriber. Keep the guard alive: it flushes the file on drop.
+pub fn init(level: LogLevel) -> tracing_appender::non_blocking::WorkerGuard {
+    let dir = log_dir();
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        eprintln!("Warning: failed to create log directory: {}", e);
+    }
+    // Use a daily rolling log file to prevent infinite growth
+    let file_appender = tracing_appender::rolling::daily(dir, "vyom.log");
+    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+
+    let level = LevelFilter::from(level);
+    let filter = Targets::new()
+        .with_target("vyom", level)
+        .with_default(level.min(LevelFilter::INFO));
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(non_blocking)
+                .with_ansi(false),
+        )
+        .with(RecentLayer)
+        .with(filter)
+        .init();
+    guard
+}
+
+/// The kept lines at `min` or more severe, oldest first
+pub fn recent(min: Level) -> Vec<LogLine> {
+    RECENT
+        .lock()
+        .map(|lines| lines.iter().filter(|l| l.level <= min).cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Lines logged since startup
+pub fn logged() -> u64 {
+    LOGGED.load(Ordering::Relaxed)
+}
+
+fn push(line: LogLine) {
+    if let Ok(mut lines) = RECENT.lock() {
+        if lines.len() == CAPACITY {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+    LOGGED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Copies every event that passes the filter into `RECENT`
+struct RecentLayer;
+
+impl<S: Subscriber> Layer<S> for RecentLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut message = MessageVisitor(String::new());
+        event.record(&mut message);
+        push(LogLine {
+            time: local_time(),
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message: message.0,
+        });
+    }
+}
+
+/// The message, then any other fields as `key=value`
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0.insert_str(0, value);
+        } else {
+            self.0.push_str(&format!(" {}={}", field.name(), value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{:?}", value));
+        } else {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+}
+
+#[cfg(unix)]
+fn local_time() -> String {
+    // SAFETY: time and localtime_r only write to the structs passed in
+    unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&now, &mut tm).is_null() {
+            return String::new();
+        }
+        format!("{:02}:{:02}:{:02}", tm.tm_hour, tm.tm_min, tm.tm_sec)
+    }
+}
+
+#[cfg(not(unix))]
+fn local_time() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_lines_capture_message_and_fields() {
+        let subscriber = tracing_subscriber::registry().with(RecentLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(attempt = 2, "Lyrics request failed: {}", "timeout");
+            tracing::debug!("chatter");
+        });
+
+        let warnings = recent(Level::WARN);
+        let line = warnings
+            .iter()
+            .rev()
+            .find(|l| l.message.starts_with("Lyrics request failed"))
+            .unwrap();
+        assert_eq!(line.message, "Lyrics request failed: timeout attempt=2");
+        assert_eq!(line.level, Level::WARN);
+        assert!(line.target.starts_with("vyom"));
+        assert!(!warnings.iter().any(|l| l.message == "chatter"));
+        assert!(recent(Level::TRACE).iter().any(|l| l.message == "chatter"));
+    }
+}
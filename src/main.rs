@@ -1,9 +1,9 @@
 use anyhow::Result;
 use crossterm::{
-    cursor::{Hide, Show},
+    cursor::Hide,
     event::EventStream,
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{enable_raw_mode, EnterAlternateScreen},
 };
 use futures::StreamExt;
 
@@ -13,21 +13,26 @@ use tokio::sync::mpsc;
 use vyom::app::config::AppConfig;
 
 use vyom::app;
+use vyom::audio;
 use vyom::audio::pipeline as audio_pipeline;
 
 use vyom::player;
-use vyom::ui::theme;
+use vyom::t;
+use vyom::ui::themes;
 
 use app::cli::Args;
 use app::events::AppEvent;
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // 0. Set up beautiful panic handler to intercept unrecoverable crashes 🚨
     human_panic::setup_panic!();
+    // ...after putting the terminal back and writing a crash report
+    vyom::crash::install_hook();
 
-    let args = Args::parse();
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches)?;
 
     if args.generate_config {
         let default_config = app::config::UserConfig::default();
@@ -35,12 +40,117 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(app::cli::Command::Eq {
+        action: app::cli::EqCommand::Import { file, name },
+    }) = &args.command
+    {
+        let (preset, preamp_db) = app::config::sharing::import_file(file, name.clone())?;
+        println!("Imported preset \"{}\"", preset.name);
+        for (freq, value) in audio::dsp::EQ_FREQUENCIES.iter().zip(preset.bands.iter()) {
+            println!(
+                "  {:>6} Hz  {:+5.1} dB",
+                freq,
+                audio::dsp::value_to_db(*value)
+            );
+        }
+        if preamp_db < 0.0 {
+            println!("Suggested preamp: {:.1} dB", preamp_db);
+        }
+        return Ok(());
+    }
+
+    if let Some(app::cli::Command::Eq {
+        action: app::cli::EqCommand::Export { name, output },
+    }) = &args.command
+    {
+        let json = app::config::sharing::export(name)?;
+        match output {
+            Some(path) => {
+                std::fs::write(path, json + "\n")?;
+                println!("Exported preset \"{}\" to {}", name, path.display());
+            }
+            None => println!("{}", json),
+        }
+        return Ok(());
+    }
+
+    if let Some(app::cli::Command::Plugins) = &args.command {
+        let plugins = audio::dsp::ladspa::discover();
+        if plugins.is_empty() {
+            let paths: Vec<String> = audio::dsp::ladspa::search_paths()
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            println!("No LADSPA plugins in {}", paths.join(", "));
+        }
+        for plugin in plugins {
+            println!(
+                "{}  {} ({})  [{}]",
+                plugin.label,
+                plugin.name,
+                plugin.maker,
+                plugin.path.display()
+            );
+            for control in &plugin.controls {
+                println!(
+                    "    {} = {} ({} to {})",
+                    control.name, control.default, control.min, control.max
+                );
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(app::cli::Command::Doctor) = &args.command {
+        let config = load_config_quietly();
+        args.apply_config(&config, &matches);
+        let client = reqwest::Client::builder()
+            .user_agent(format!("vyom-rs/{}", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        let checks = app::doctor::run(&args, &config, &client).await;
+        if !app::doctor::print(&checks) {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(app::cli::Command::Export { format, output }) = &args.command {
+        let (format, output) = (*format, output.clone());
+        args.apply_config(&load_config_quietly(), &matches);
+        return export_data(&args, format, output);
+    }
+    if let Some(app::cli::Command::Import { file }) = &args.command {
+        let file = file.clone();
+        args.apply_config(&load_config_quietly(), &matches);
+        return import_data(&args, &file);
+    }
+
+    if let Some(app::cli::Command::Spotify { url, name, replace }) = &args.command {
+        let (url, name, replace) = (url.clone(), name.clone(), *replace);
+        let config = load_config_quietly();
+        args.apply_config(&config, &matches);
+        return spotify_import(&args, &config, &url, name, replace).await;
+    }
+
+    // Playback control for window-manager hotkeys: no TUI
+    if let Some(command) = args.command.as_ref().and_then(|c| c.remote()) {
+        return match command {
+            Ok(command) => remote_control(command, &mut args, &matches),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        };
+    }
+
     let is_standalone = args.standalone;
     let is_tmux = std::env::var("TMUX").is_ok();
+    let is_zellij = std::env::var("ZELLIJ").is_ok();
 
     // Smart Window Logic
     // Default is Full UI (!mini).
-    let want_lyrics = !args.mini;
+    let want_lyrics = !args.mini && !args.bar;
 
     let current_exe = std::env::current_exe()?;
     let exe_path_cow = current_exe.to_string_lossy();
@@ -50,36 +160,59 @@ async fn main() -> Result<()> {
     print!("\x1b]2;Vyom\x07");
 
     // 3. LOGGER INITIALIZATION 📝
-    let log_dir = dirs::cache_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join("vyom");
-    if let Err(e) = std::fs::create_dir_all(&log_dir) {
-        eprintln!("Warning: failed to create log directory: {}", e);
-    }
-    // Use a daily rolling log file to prevent infinite growth
-    let file_appender = tracing_appender::rolling::daily(log_dir, "vyom.log");
-    let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
-
-    // We only set this up if we are not generating config
-    tracing_subscriber::fmt()
-        .with_writer(non_blocking)
-        .with_ansi(false)
-        .init();
+    let _log_guard = vyom::logging::init(args.log_level);
+
+    // First launch: ask for the basics and write config.toml
+    if !is_standalone && app::wizard::is_first_run() && io::IsTerminal::is_terminal(&io::stdin()) {
+        run_wizard(&args)?;
+    }
+
+    // Load persisted state (Split into UserConfig and PersistentState)
+    let (user_config, persistent_state, config_err) = AppConfig::load();
+    // Before anything is shown, so the first toasts are translated too
+    let language_warning = vyom::i18n::set_language(user_config.language.as_deref());
+    vyom::crash::set_reports(user_config.crash_reports);
+    args.apply_config(&user_config, &matches);
 
     // 4. TMUX LOGIC
-    if app::tmux::handle_tmux_split(&args, exe_path, is_tmux, is_standalone, want_lyrics)? {
+    if app::tmux::handle_tmux_split(
+        &args,
+        exe_path,
+        is_tmux,
+        is_standalone,
+        want_lyrics,
+        &user_config.tmux,
+    )? || app::zellij::handle_zellij_split(
+        &args,
+        exe_path,
+        is_zellij,
+        is_standalone,
+        want_lyrics,
+        &user_config.tmux,
+    )? {
         return Ok(());
     }
     // No else block for Standalone Resize - User manages window size manually.
 
     // Setup terminal
     enable_raw_mode()?;
+    // Undone on every way out: errors, panics and the normal exit below
+    let _terminal_guard = vyom::crash::TerminalGuard::new();
+    // Ask for the terminal palette while nothing else is reading stdin, and
+    // only for the themes made from outside colors
+    let start_theme = persistent_state
+        .picked_theme(user_config.theme.as_deref())
+        .or_else(|| user_config.theme.clone());
+    if matches!(
+        start_theme.as_deref(),
+        Some(themes::TERMINAL_THEME | themes::PYWAL_THEME)
+    ) {
+        vyom::ui::termcolors::query_terminal(Duration::from_millis(300));
+    }
     let mut stdout = io::stdout();
     // Enable Kitty Keyboard Protocol (DisambiguateEscapeCodes | ReportAllKeysAsEscapeCodes)
     // This often stops terminals from "peeking" at modifiers for local shortcuts
-    use crossterm::event::{
-        KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
-    };
+    use crossterm::event::{KeyboardEnhancementFlags, PushKeyboardEnhancementFlags};
     execute!(
         stdout,
         EnterAlternateScreen,
@@ -96,12 +229,16 @@ async fn main() -> Result<()> {
     // Determine backend mode and source app name
     #[cfg(feature = "mpd")]
     let (is_mpd_mode, source_app) = if args.controller {
-        // Auto-Pause MPD to prevent concurrent audio ⏸️
-        if let Ok(mut mpd) = mpd::Client::connect(format!("{}:{}", args.mpd_host, args.mpd_port)) {
-            if let Err(e) = mpd.pause(true) {
-                tracing::debug!("Failed to pause MPD on controller init: {}", e);
+        // Auto-Pause MPD to prevent concurrent audio ⏸️ (in the background,
+        // an unreachable MPD mustn't hold up the first frame)
+        let addr = format!("{}:{}", args.mpd_host, args.mpd_port);
+        tokio::task::spawn_blocking(move || {
+            if let Ok(mut mpd) = mpd::Client::connect(addr) {
+                if let Err(e) = mpd.pause(true) {
+                    tracing::debug!("Failed to pause MPD on controller init: {}", e);
+                }
             }
-        }
+        });
         (false, "Spotify / Apple Music")
     } else {
         // Default mode - MPD
@@ -116,16 +253,13 @@ async fn main() -> Result<()> {
     let audio_lock = app::lock::try_acquire_audio_lock();
     let is_audio_master = audio_lock.is_some();
 
-    // Load persisted state (Split into UserConfig and PersistentState)
-    let (user_config, persistent_state, config_err) = AppConfig::load();
-
     if persistent_state.eq_enabled && !is_audio_master {
         // Maybe log that EQ is visual only?
     }
 
     let mut app = app::App::new(
         app_show_lyrics,
-        is_tmux,
+        is_tmux || is_zellij, // Both give us a narrow pane
         is_mpd_mode,
         source_app,
         false, // is_test
@@ -133,35 +267,92 @@ async fn main() -> Result<()> {
         persistent_state,
     );
 
+    app.bar_mode = args.bar;
+    if is_tmux && want_lyrics {
+        app.tmux = app::tmux::TmuxPane::claim(&user_config.tmux);
+    }
+    // Last run's library listing, until the watcher has checked it 📚
+    #[cfg(feature = "mpd")]
+    let library_server = format!("{}:{}", args.mpd_host, args.mpd_port);
+    #[cfg(feature = "mpd")]
+    if !args.controller {
+        app.library_cache = app::library_cache::LibraryCache::load_from(
+            &app::library_cache::LibraryCache::path(),
+            &library_server,
+        )
+        .map(std::sync::Arc::new);
+    }
+    #[cfg(feature = "mpd")]
+    app::inputs::library::restore_library(&mut app, &args).await;
     if let Some(msg) = config_err {
-        app.show_toast(&msg);
+        app.show_error(&msg);
+    }
+    if let Some(msg) = language_warning {
+        app.show_warning(&msg);
+    }
+    for problem in app.dsp.problems() {
+        app.show_warning(&problem);
     }
 
     let mut audio_pipeline = audio_pipeline::AudioPipeline::new(app.eq_gains.clone());
 
     // Attach Visualizer 📊
     audio_pipeline.attach_visualizer(app.visualizer.get_audio_buffer());
+    audio_pipeline.attach_pre_visualizer(app.visualizer.get_pre_buffer());
+    audio_pipeline.attach_meter(app.level_meter.clone());
+    audio_pipeline.attach_pitch(app.pitch.clone());
+    audio_pipeline.attach_dsp(app.dsp.clone());
+    audio_pipeline.attach_output(app.output.clone());
+    audio_pipeline.attach_silence_monitor(app.silence.clone());
 
     if is_audio_master {
         if let Err(e) = audio_pipeline.start() {
-            let msg = format!("Audio Error: {} (Visuals Only)", e);
-            tracing::error!("{}", msg);
-            app.show_toast(&msg);
+            let error = vyom::error::VyomError::AudioPipeline(e);
+            tracing::error!("{}", error);
+            app.report(&error, Some(app::events::Retry::Pipeline));
         }
         // CRITICAL: Apply persisted volume immediately 🔊
         audio_pipeline.set_volume(app.app_volume);
+        app.sync = Some(Default::default());
     } else {
-        // We are secondary. No audio output.
-        // Visuals might still work if we tap into the same FIFO?
-        // For now, secondary = no audio processing = no visualizer (unless we share data, which is complex).
-        app.show_toast("🔇 Shared Audio Mode (UI Only)");
+        // We are secondary. No audio output: EQ, volume and the visualizer
+        // follow the master over the control socket (subscribed below)
+        app.is_audio_master = false;
+        app.show_toast(&t!("toast.shared_audio"));
     }
 
+    // Controlling another player, nothing goes through the pipeline: on
+    // PipeWire the visualizer listens to what the speakers play instead
+    #[cfg(target_os = "linux")]
+    let _capture = (args.controller && app.output.backend().use_pipewire())
+        .then(|| audio::device::pipewire::Stream::capture(app.visualizer.get_audio_buffer()))
+        .and_then(|capture| {
+            capture
+                .inspect_err(|e| tracing::warn!("Visualizer capture unavailable: {}", e))
+                .ok()
+        });
+
     // Player Backend Selection 🎛️
     let player: std::sync::Arc<dyn player::PlayerTrait> =
         player::PlayerFactory::create(&args, &user_config);
 
     let (tx, rx) = mpsc::channel(100);
+    if !is_audio_master {
+        // The master wrote its socket into the lock file
+        let socket = app::lock::holder()
+            .map(|holder| holder.socket)
+            .unwrap_or_else(app::remote::socket_path);
+        app::sync::spawn_subscriber(socket, tx.clone());
+    }
+
+    #[cfg(feature = "mpd")]
+    if !args.controller {
+        app::library_cache::spawn_watcher(
+            library_server,
+            app.library_cache.as_ref().map(|c| c.db_update),
+            tx.clone(),
+        );
+    }
 
     // Performance Optimization: Global HTTP Client (Reused)
     let client = reqwest::Client::builder()
@@ -188,6 +379,9 @@ async fn main() -> Result<()> {
         // Track last status poll time to run it less frequently (e.g., 1s)
         let mut last_status_poll = std::time::Instant::now();
         let mut last_queue_version: Option<u64> = None;
+        // MPD off: back off instead of polling it every 250ms 🔌
+        let mut reconnector = app::connection::Reconnector::default();
+        let mut connection = app::connection::ConnectionState::Connected;
 
         loop {
             // Use shared player reference
@@ -204,10 +398,22 @@ async fn main() -> Result<()> {
 
             let result = tokio::task::spawn_blocking(move || {
                 let track = player_ref.get_current_track();
+                if track.is_err() {
+                    // No point asking for the rest
+                    return (track, None, None, None, None, None);
+                }
                 let q_vers = player_ref.get_queue_version();
 
+                // Edited since last time: ask for what changed, not the
+                // whole queue (10k songs are a lot to send every skip)
+                let mut delta = None;
                 let queue = if q_vers.is_some() && q_vers == last_q_vers_clone {
                     None // Queue hasn't changed, skip heavy allocation
+                } else if let Some(changes) = last_q_vers_clone
+                    .and_then(|since| player_ref.get_queue_changes(since).ok().flatten())
+                {
+                    delta = Some(changes);
+                    None
                 } else {
                     Some(player_ref.get_queue())
                 };
@@ -218,12 +424,36 @@ async fn main() -> Result<()> {
                     (None, None)
                 };
 
-                (track, queue, q_vers, shuffle, repeat)
+                (track, queue, delta, q_vers, shuffle, repeat)
             })
             .await;
 
-            if let Ok((track_res, queue_opt, new_q_vers, shuffle_opt, repeat_opt)) = result {
-                if new_q_vers != last_queue_version {
+            let mut wait = Duration::from_millis(250);
+            if let Ok((track_res, queue_opt, delta_opt, new_q_vers, shuffle_opt, repeat_opt)) =
+                result
+            {
+                if is_mpd_mode {
+                    let state = match &track_res {
+                        Ok(_) => reconnector.succeeded(),
+                        Err(e) => {
+                            let (state, retry_in) = reconnector.failed(&e.to_string());
+                            wait = retry_in;
+                            state
+                        }
+                    };
+                    if state != connection {
+                        connection = state.clone();
+                        if tx_spotify
+                            .send(AppEvent::ConnectionUpdate(state))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                }
+                // A failed fetch is retried next time
+                if !matches!(queue_opt, Some(Err(_))) {
                     last_queue_version = new_q_vers;
                 }
 
@@ -237,6 +467,11 @@ async fn main() -> Result<()> {
                         tracing::debug!("Channel closed during queue update: {}", e);
                     }
                 }
+                if let Some(delta) = delta_opt {
+                    if let Err(e) = tx_spotify.send(AppEvent::QueueChanged(delta)).await {
+                        tracing::debug!("Channel closed during queue update: {}", e);
+                    }
+                }
                 // Send status update if we polled it successfully
                 if let (Some(s), Some(r)) = (shuffle_opt, repeat_opt) {
                     if let Err(e) = tx_spotify.send(AppEvent::StatusUpdate(s, r)).await {
@@ -244,56 +479,15 @@ async fn main() -> Result<()> {
                     }
                 }
             }
-            tokio::time::sleep(Duration::from_millis(250)).await;
+            tokio::time::sleep(wait).await;
         }
     });
 
-    // 3. Theme Watcher Task 🎨
-    let tx_theme = tx.clone();
-    tokio::spawn(async move {
-        // Use file modification time for efficient hot reloading 🌶️
-        let theme_path = theme::get_theme_path();
-        let path_clone = theme_path.clone();
-
-        let mut last_modified = tokio::task::spawn_blocking(move || {
-            std::fs::metadata(&path_clone)
-                .and_then(|m| m.modified())
-                .ok()
-        })
-        .await
-        .unwrap_or(None);
-
-        loop {
-            tokio::time::sleep(Duration::from_millis(250)).await;
-
-            // Check file modification time (blocking I/O wrapped)
-            let check_path = theme_path.clone(); // Clone for closure
-            let metadata_result = tokio::task::spawn_blocking(move || {
-                std::fs::metadata(&check_path).and_then(|m| m.modified())
-            })
-            .await;
-
-            if let Ok(Ok(modified)) = metadata_result {
-                // If file modified or we never saw it before (and it exists)
-                if last_modified.is_none_or(|last| modified > last) {
-                    last_modified = Some(modified);
-
-                    // Load and broadcast new theme
-                    let new_theme = theme::load_current_theme();
-                    if tx_theme
-                        .send(AppEvent::ThemeUpdate(new_theme))
-                        .await
-                        .is_err()
-                    {
-                        break;
-                    }
-                }
-            }
-        }
-    });
-
-    // 4. Config Watcher Task (Hot Reloading) 🔧
+    // 3. Config Watcher Task (Hot Reloading) 🔧
+    // Also looks at the theme files; the app decides what to reload 🎨
     let tx_config = tx.clone();
+    let mut last_theme = user_config.theme.clone();
+    let mut last_language = user_config.language.clone();
     tokio::spawn(async move {
         let config_path = AppConfig::get_config_path();
         let path_clone = config_path.clone();
@@ -305,10 +499,19 @@ async fn main() -> Result<()> {
         })
         .await
         .unwrap_or(None);
+        let mut last_themes = tokio::task::spawn_blocking(theme_files_stamp).await.ok();
 
         loop {
             tokio::time::sleep(Duration::from_millis(500)).await;
 
+            let themes_now = tokio::task::spawn_blocking(theme_files_stamp).await.ok();
+            if themes_now.is_some() && themes_now != last_themes {
+                last_themes = themes_now;
+                if tx_config.send(AppEvent::ThemeFilesChanged).await.is_err() {
+                    break;
+                }
+            }
+
             let check_path = config_path.clone();
             let metadata_result = tokio::task::spawn_blocking(move || {
                 std::fs::metadata(&check_path).and_then(|m| m.modified())
@@ -323,36 +526,64 @@ async fn main() -> Result<()> {
                     // Hot Reload: Reload config, verify keys changed
                     let (new_user_config, _, reload_err) = AppConfig::load();
                     if let Some(err_msg) = reload_err {
-                        if let Err(e) = tx_config.send(AppEvent::ToastUpdate(err_msg)).await {
+                        if let Err(e) = tx_config
+                            .send(AppEvent::ToastUpdate(app::ToastLevel::Error, err_msg))
+                            .await
+                        {
                             tracing::debug!("Channel closed during config toast: {}", e);
                         }
-                    } else if tx_config
-                        .send(AppEvent::KeyConfigUpdate(Box::new(new_user_config.keys)))
-                        .await
-                        .is_err()
-                    {
-                        break;
+                    } else {
+                        if new_user_config.language != last_language {
+                            last_language = new_user_config.language.clone();
+                            if let Some(msg) = vyom::i18n::set_language(last_language.as_deref()) {
+                                let warning = AppEvent::ToastUpdate(app::ToastLevel::Warn, msg);
+                                if tx_config.send(warning).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                        if new_user_config.theme != last_theme {
+                            last_theme = new_user_config.theme.clone();
+                            if tx_config
+                                .send(AppEvent::ThemeSelect(new_user_config.theme.clone()))
+                                .await
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                        if tx_config
+                            .send(AppEvent::KeyConfigUpdate(Box::new(new_user_config.keys)))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
                     }
                 }
             }
         }
     });
 
-    // 5. Animation / Status Tick Task ⚡
+    // 4. Animation / Status Tick Task ⚡
     let tx_tick = tx.clone();
+    let frame_ms = app.frame_ms.clone();
     tokio::spawn(async move {
-        // Fast animations (like Seeking and Cava Visualizer) need 16ms to render correctly.
-        // We handle conditional drawing inside the event loop so this doesn't burn CPU unnecessarily.
-        let mut interval = tokio::time::interval(Duration::from_millis(16));
+        // 60 FPS only while something animates; the runner picks the rate
+        // (App::frame_rate) so an idle Vyom barely wakes up
         loop {
-            interval.tick().await;
+            let ms = frame_ms.load(std::sync::atomic::Ordering::Relaxed);
+            tokio::time::sleep(Duration::from_millis(ms)).await;
             if tx_tick.send(AppEvent::Tick).await.is_err() {
                 break;
             }
         }
     });
 
-    // 6. Launch Runner Core Orchestrator 🚀
+    // `vyom next` & co. talk to this instance
+    let remote = app::remote::listen(tx.clone());
+
+    // 5. Launch Runner Core Orchestrator 🚀
     if let Err(e) = vyom::app::runner::run_app(
         &mut app,
         &mut terminal,
@@ -371,23 +602,274 @@ async fn main() -> Result<()> {
     // Stop Audio Pipeline 🛑
     audio_pipeline.stop();
 
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        Show,
-        PopKeyboardEnhancementFlags
-    )?;
-    terminal.show_cursor()?;
+    vyom::crash::restore_terminal();
 
     // Save state on exit
     app.save_state();
+    if let Some(pane) = &app.tmux {
+        pane.release();
+    }
 
     // Cleanup Lock File (if we own it)
-    if is_audio_master {
-        app::lock::release_audio_lock();
+    if let Some(lock) = audio_lock {
+        lock.release();
     }
 
+    // Exit below skips destructors
+    drop(remote);
+
     // Force Exit to bypass slow Tokio unwind of blocking tasks (AppleScript/MPD) 🚀
     std::process::exit(0);
 }
+
+/// Newest mtime across the theme files, and how many there are
+fn theme_files_stamp() -> (Option<std::time::SystemTime>, usize) {
+    let mut paths = vec![
+        themes::legacy_theme_path(),
+        vyom::ui::termcolors::wal_colors_path(),
+    ];
+    if let Ok(entries) = std::fs::read_dir(themes::themes_dir()) {
+        paths.extend(entries.flatten().map(|e| e.path()));
+    }
+    let newest = paths
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .max();
+    (newest, paths.len())
+}
+
+/// The config for one-shot commands. Doesn't create config.toml, so the
+/// first-run wizard still gets its turn.
+fn load_config_quietly() -> app::config::UserConfig {
+    if app::wizard::is_first_run() {
+        app::config::UserConfig::default()
+    } else {
+        AppConfig::load().0
+    }
+}
+
+/// `vyom export`: the history file, plus what MPD keeps when it answers
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+fn export_data(
+    args: &Args,
+    format: app::export::Format,
+    output: Option<std::path::PathBuf>,
+) -> Result<()> {
+    #[cfg_attr(not(feature = "mpd"), allow(unused_mut))]
+    let mut export = app::export::Export::collect();
+    #[cfg(feature = "mpd")]
+    match mpd::Client::connect(format!("{}:{}", args.mpd_host, args.mpd_port)) {
+        Ok(mut mpd) => export.add_from_mpd(&mut mpd)?,
+        Err(e) => eprintln!(
+            "MPD at {}:{}: {} (exporting the history only)",
+            args.mpd_host, args.mpd_port, e
+        ),
+    }
+    let text = export.render(format);
+    match output {
+        Some(path) => {
+            std::fs::write(&path, text)?;
+            println!(
+                "Exported {} plays, {} ratings, {} favorites and {} playlists to {}",
+                export.history.len(),
+                export.ratings.len(),
+                export.favorites.len(),
+                export.playlists.len(),
+                path.display()
+            );
+        }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+/// `vyom import`: history into the history file, the rest into MPD
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+fn import_data(args: &Args, file: &std::path::Path) -> Result<()> {
+    let text = std::fs::read_to_string(file)?;
+    let data = app::export::Export::parse(&text).map_err(anyhow::Error::msg)?;
+    #[cfg_attr(not(feature = "mpd"), allow(unused_mut))]
+    let mut imported = data.import_history()?;
+    let for_mpd =
+        !(data.ratings.is_empty() && data.favorites.is_empty() && data.playlists.is_empty());
+    #[cfg(feature = "mpd")]
+    if for_mpd {
+        match mpd::Client::connect(format!("{}:{}", args.mpd_host, args.mpd_port)) {
+            Ok(mut mpd) => data.import_to_mpd(&mut mpd, &mut imported),
+            Err(e) => eprintln!(
+                "MPD at {}:{}: {} (ratings, favorites and playlists skipped)",
+                args.mpd_host, args.mpd_port, e
+            ),
+        }
+    }
+    #[cfg(not(feature = "mpd"))]
+    if for_mpd {
+        eprintln!("Built without MPD support: ratings, favorites and playlists skipped");
+    }
+    println!(
+        "Imported {} plays ({} already there), {} ratings, {} favorites and {} playlists",
+        imported.plays,
+        imported.plays_known,
+        imported.ratings,
+        imported.favorites,
+        imported.playlists
+    );
+    if imported.failed > 0 {
+        println!(
+            "{} songs aren't in MPD's database and were left out",
+            imported.failed
+        );
+    }
+    Ok(())
+}
+
+/// `vyom spotify`: the songs of a Spotify playlist that are in the library,
+/// saved as an MPD playlist, and a list of the rest. An existing playlist
+/// of that name is only overwritten with `replace`.
+#[cfg(feature = "mpd")]
+async fn spotify_import(
+    args: &Args,
+    config: &app::config::UserConfig,
+    url: &str,
+    name: Option<String>,
+    replace: bool,
+) -> Result<()> {
+    let Some(id) = app::spotify::playlist_id(url) else {
+        anyhow::bail!("Not a Spotify playlist link: {}", url);
+    };
+    let (Some(client_id), Some(client_secret)) = (
+        config.spotify_client_id.as_deref(),
+        config.spotify_client_secret.as_deref(),
+    ) else {
+        anyhow::bail!(
+            "Set spotify_client_id and spotify_client_secret in config.toml \
+             (create an app at https://developer.spotify.com/dashboard)"
+        );
+    };
+    let client = reqwest::Client::builder()
+        .user_agent(format!("vyom-rs/{}", env!("CARGO_PKG_VERSION")))
+        .build()
+        .unwrap_or_default();
+    let playlist = app::spotify::fetch(&client, &id, client_id, client_secret).await?;
+
+    let mut mpd = mpd::Client::connect(format!("{}:{}", args.mpd_host, args.mpd_port))?;
+    let library = app::library_cache::songs(&mut mpd)?;
+    let mut matched = Vec::new();
+    let mut missed = Vec::new();
+    for track in &playlist.tracks {
+        match app::spotify::best_match(track, &library) {
+            Some(song) => matched.push(song.file.clone()),
+            None => missed.push(track.label()),
+        }
+    }
+
+    let name = name.unwrap_or(playlist.name);
+    let exists = mpd.playlists()?.iter().any(|p| p.name == name);
+    if exists && !replace {
+        anyhow::bail!(
+            "There is already a playlist \"{}\" (--replace overwrites it, --name picks another)",
+            name
+        );
+    }
+    if matched.is_empty() {
+        println!(
+            "None of the {} songs are in your library",
+            playlist.tracks.len()
+        );
+    } else {
+        // Replaced, so running it again picks up songs added since
+        if exists {
+            mpd.pl_remove(&name)
+                .map_err(|e| anyhow::anyhow!("Couldn't replace \"{}\": {}", name, e))?;
+        }
+        for file in &matched {
+            let song = mpd::Song {
+                file: file.clone(),
+                ..Default::default()
+            };
+            mpd.pl_push(&name, &song)?;
+        }
+        println!(
+            "Saved \"{}\": {} of {} songs",
+            name,
+            matched.len(),
+            playlist.tracks.len()
+        );
+    }
+    if !missed.is_empty() {
+        println!("\nNot in your library:");
+        for label in &missed {
+            println!("  {}", label);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "mpd"))]
+async fn spotify_import(
+    _args: &Args,
+    _config: &app::config::UserConfig,
+    _url: &str,
+    _name: Option<String>,
+    _replace: bool,
+) -> Result<()> {
+    anyhow::bail!("vyom spotify needs MPD support (the mpd feature)")
+}
+
+/// `vyom play` & co.: ask the running player, or MPD when there is none
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+fn remote_control(
+    command: app::remote::RemoteCommand,
+    args: &mut Args,
+    matches: &clap::ArgMatches,
+) -> Result<()> {
+    use app::remote::RemoteCommand;
+
+    let config = load_config_quietly();
+    vyom::i18n::set_language(config.language.as_deref());
+    args.apply_config(&config, matches);
+
+    let command = match command {
+        RemoteCommand::QueueAdd(path) => RemoteCommand::QueueAdd(app::remote::library_path(
+            path.as_ref(),
+            config.music_directory.as_ref(),
+        )),
+        command => command,
+    };
+
+    let reply = match app::remote::send(&command) {
+        Some(reply) => reply,
+        #[cfg(feature = "mpd")]
+        None => app::remote::run_on_mpd(&command, &args.mpd_host, args.mpd_port),
+        #[cfg(not(feature = "mpd"))]
+        None => Err("Vyom is not running".to_string()),
+    };
+    match reply {
+        Ok(msg) => {
+            println!("{}", msg);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The first-run wizard, in a terminal of its own before the player starts
+#[cfg_attr(not(feature = "mpd"), allow(unused_variables))]
+fn run_wizard(args: &Args) -> Result<()> {
+    #[cfg(feature = "mpd")]
+    let wizard = app::wizard::Wizard::new(!args.controller, &args.mpd_host, args.mpd_port);
+    #[cfg(not(feature = "mpd"))]
+    let wizard = app::wizard::Wizard::new(false, "localhost", 6600);
+
+    enable_raw_mode()?;
+    let guard = vyom::crash::TerminalGuard::new();
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, Hide)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+    let result = app::wizard::run(&mut terminal, wizard);
+    drop(guard);
+    Ok(result?)
+}
@@ -1,4 +1,4 @@
-use crate::player::traits::{PlayerState, PlayerTrait, QueueItem, RepeatMode, TrackInfo};
+use crate::player::traits::{PlayerTrait, QueueItem, RepeatMode, TrackInfo};
 use anyhow::{bail, Result};
 
 pub struct DummyPlayer;
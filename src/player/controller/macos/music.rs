@@ -165,5 +165,8 @@ fn parse_music_output(output: &str) -> Option<TrackInfo> {
         bit_depth: None,
         file_path: None,
         volume: Some(volume),
+        track_number: None,
+        track_total: None,
+        genre: None,
     })
 }
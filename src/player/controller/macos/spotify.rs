@@ -166,5 +166,8 @@ fn parse_spotify_output(output: &str) -> Option<TrackInfo> {
         bit_depth: None,
         file_path: None,
         volume: Some(volume),
+        track_number: None,
+        track_total: None,
+        genre: None,
     })
 }
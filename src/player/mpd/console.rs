@@ -0,0 +1,227 @@
+//! Raw MPD protocol access for the in-app console 🖥️
+//!
+//! Each command runs on its own short-lived connection so a typo or a
+//! stuck response can never wedge the main `mpd::Client`.
+
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Commands that only read server state. Everything else needs a y/n
+/// confirmation.
+const READ_ONLY: &[&str] = &[
+    "channels",
+    "commands",
+    "config",
+    "count",
+    "currentsong",
+    "decoders",
+    "find",
+    "getfingerprint",
+    "list",
+    "listall",
+    "listallinfo",
+    "listfiles",
+    "listmounts",
+    "listneighbors",
+    "listpartitions",
+    "listplaylist",
+    "listplaylistinfo",
+    "listplaylists",
+    "lsinfo",
+    "notcommands",
+    "outputs",
+    "ping",
+    "playlist",
+    "playlistfind",
+    "playlistid",
+    "playlistinfo",
+    "playlistsearch",
+    "plchanges",
+    "plchangesposid",
+    "readcomments",
+    "readmessages",
+    "replay_gain_status",
+    "search",
+    "stats",
+    "status",
+    "tagtypes",
+    "urlhandlers",
+];
+
+/// `sticker` subcommands that only read
+const STICKER_READ_ONLY: &[&str] = &["get", "list", "find"];
+
+/// Commands that would block forever or return binary payloads
+const UNSUPPORTED: &[&str] = &["idle", "noidle", "albumart", "readpicture", "close"];
+
+/// Server reply to a single command
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsoleReply {
+    /// `OK` with the response lines before it
+    Ok(Vec<String>),
+    /// `ACK [error@command_listNum] {command} message`
+    Ack(String),
+}
+
+/// Lowercased first word of a command line
+pub fn command_name(line: &str) -> String {
+    line.split_whitespace()
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+/// Anything not known to be read-only asks first
+pub fn needs_confirm(line: &str) -> bool {
+    match command_name(line).as_str() {
+        "" => false,
+        "sticker" => {
+            let sub = line.split_whitespace().nth(1).unwrap_or("");
+            !STICKER_READ_ONLY.contains(&sub.to_ascii_lowercase().as_str())
+        }
+        name => !READ_ONLY.contains(&name),
+    }
+}
+
+pub fn is_unsupported(line: &str) -> bool {
+    UNSUPPORTED.contains(&command_name(line).as_str())
+}
+
+/// Read lines until the terminating `OK` / `ACK`
+pub fn read_response<R: BufRead>(reader: &mut R) -> Result<ConsoleReply> {
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            bail!("Connection closed by MPD");
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line == "OK" {
+            return Ok(ConsoleReply::Ok(lines));
+        }
+        if let Some(err) = line.strip_prefix("ACK ") {
+            return Ok(ConsoleReply::Ack(err.to_string()));
+        }
+        lines.push(line.to_string());
+    }
+}
+
+/// Send one raw command to MPD and wait for the reply
+pub fn send(host: &str, port: u16, command: &str) -> Result<ConsoleReply> {
+    let addr = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .context("Could not resolve MPD host")?;
+    let mut stream = TcpStream::connect_timeout(&addr, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut greeting = String::new();
+    reader.read_line(&mut greeting)?;
+    if !greeting.starts_with("OK MPD") {
+        bail!("Unexpected greeting: {}", greeting.trim());
+    }
+
+    // One command per line; strip anything that would smuggle in a second one
+    let command = command.replace(['\r', '\n'], " ");
+    writeln!(stream, "{}", command.trim())?;
+    read_response(&mut reader)
+}
+
+/// Align `key: value` pairs and separate records (each new `file:`/`directory:`
+/// starts one) with a blank line
+pub fn pretty_lines(lines: &[String]) -> Vec<String> {
+    let key_width = lines
+        .iter()
+        .filter_map(|l| l.split_once(": ").map(|(k, _)| k.len()))
+        .max()
+        .unwrap_or(0)
+        .min(24);
+
+    let mut out = Vec::with_capacity(lines.len());
+    for line in lines {
+        match line.split_once(": ") {
+            Some((key, value)) => {
+                let starts_record = matches!(key, "file" | "directory" | "playlist");
+                if starts_record && !out.is_empty() {
+                    out.push(String::new());
+                }
+                out.push(format!("{:>width$}  {}", key, value, width = key_width));
+            }
+            None => out.push(line.clone()),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_response_ok() {
+        let mut input = Cursor::new("volume: 50\nrepeat: 0\nOK\n");
+        let reply = read_response(&mut input).unwrap();
+        assert_eq!(
+            reply,
+            ConsoleReply::Ok(vec!["volume: 50".to_string(), "repeat: 0".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_read_response_ack() {
+        let mut input = Cursor::new("ACK [5@0] {} unknown command \"foo\"\n");
+        let reply = read_response(&mut input).unwrap();
+        assert_eq!(
+            reply,
+            ConsoleReply::Ack("[5@0] {} unknown command \"foo\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_read_response_truncated_is_error() {
+        let mut input = Cursor::new("volume: 50\n");
+        assert!(read_response(&mut input).is_err());
+    }
+
+    #[test]
+    fn test_dangerous_commands() {
+        assert!(needs_confirm("clear"));
+        assert!(needs_confirm("  KILL "));
+        assert!(needs_confirm("rm \"My Playlist\""));
+        for cmd in [
+            "update", "rescan", "save x", "load x", "shuffle", "move 1 2",
+        ] {
+            assert!(needs_confirm(cmd), "{}", cmd);
+        }
+        for cmd in ["setvol 50", "stop", "consume 1", "enableoutput 0"] {
+            assert!(needs_confirm(cmd), "{}", cmd);
+        }
+        assert!(needs_confirm("sticker delete song a.flac rating"));
+        assert!(needs_confirm("someday_new_command"));
+        assert!(!needs_confirm("sticker get song a.flac rating"));
+        assert!(!needs_confirm("status"));
+        assert!(!needs_confirm("  LsInfo \"Rock\""));
+        assert!(!needs_confirm(""));
+        assert!(is_unsupported("idle player"));
+    }
+
+    #[test]
+    fn test_pretty_lines_groups_records() {
+        let lines = vec![
+            "file: a.flac".to_string(),
+            "Title: A".to_string(),
+            "file: b.flac".to_string(),
+        ];
+        let pretty = pretty_lines(&lines);
+        assert_eq!(pretty.len(), 4);
+        assert_eq!(pretty[0], " file  a.flac");
+        assert_eq!(pretty[2], "");
+    }
+}
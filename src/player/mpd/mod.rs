@@ -1,4 +1,7 @@
-use super::traits::{PlayerState, PlayerTrait, QueueItem, RepeatMode, TrackInfo};
+#[cfg(feature = "mpd")]
+pub mod console;
+
+use super::traits::{PlayerState, PlayerTrait, QueueDelta, QueueItem, RepeatMode, TrackInfo};
 use anyhow::{Context, Result};
 #[cfg(feature = "mpd")]
 use mpd::{Client, Song, State};
@@ -83,6 +86,17 @@ impl MpdPlayer {
     }
 }
 
+/// Parse an MPD `Track` tag ("4" or "4/11") into (number, total)
+pub fn parse_track_tag(tag: Option<&str>) -> (Option<u32>, Option<u32>) {
+    let Some(tag) = tag else {
+        return (None, None);
+    };
+    let mut parts = tag.split('/');
+    let number = parts.next().and_then(|n| n.trim().parse().ok());
+    let total = parts.next().and_then(|t| t.trim().parse().ok());
+    (number, total)
+}
+
 #[cfg(feature = "mpd")]
 impl Default for MpdPlayer {
     fn default() -> Self {
@@ -131,6 +145,15 @@ impl PlayerTrait for MpdPlayer {
 
                 let album = find_tag(&song.tags, "Album").unwrap_or_else(|| "Unknown".to_string());
 
+                // Track tag is either "4" or "4/11"
+                let track_tag = find_tag(&song.tags, "Track");
+                let (track_number, track_total) = parse_track_tag(track_tag.as_deref());
+                let track_total = track_total.or_else(|| {
+                    find_tag(&song.tags, "TrackTotal")
+                        .or_else(|| find_tag(&song.tags, "TotalTracks"))
+                        .and_then(|t| t.trim().parse().ok())
+                });
+
                 Ok(Some(TrackInfo {
                     name: song.title.unwrap_or_else(|| "Unknown".to_string()),
                     artist: song.artist.unwrap_or_else(|| "Unknown".to_string()),
@@ -154,6 +177,9 @@ impl PlayerTrait for MpdPlayer {
                     bit_depth: audio_format.map(|(_, b, _)| b),
                     file_path: Some(file_path),
                     volume: Some(status.volume.unsigned_abs() as u32),
+                    track_number,
+                    track_total,
+                    genre: find_tag(&song.tags, "Genre"),
                 }))
             } else {
                 Ok(None)
@@ -226,45 +252,41 @@ impl PlayerTrait for MpdPlayer {
         self.with_client(|client| {
             let queue = client.queue()?;
             let current_song = client.currentsong().ok().flatten();
-            let current_id = current_song.and_then(|s| s.place).map(|p| p.id);
-
-            // Helper closure to find tag value (case-insensitive)
-            let find_tag = |tags: &[(String, String)], key: &str| -> Option<String> {
-                let key_lower = key.to_lowercase();
-                tags.iter()
-                    .find(|(k, _)| k.to_lowercase() == key_lower)
-                    .map(|(_, v)| v.clone())
-            };
+            let current_id = current_song.and_then(|s| s.place).map(|p| p.id.0);
 
             Ok(queue
-                .into_iter()
-                .map(|song| {
-                    let id = song.place.map(|p| p.id).unwrap_or_default();
-                    let title = song.title.clone().unwrap_or_else(|| song.file.clone());
-                    let artist = song
-                        .artist
-                        .clone()
-                        .or_else(|| find_tag(&song.tags, "Artist"))
-                        .or_else(|| find_tag(&song.tags, "AlbumArtist"))
-                        .or_else(|| find_tag(&song.tags, "Composer"))
-                        .unwrap_or_else(|| "Unknown Artist".to_string());
-                    let duration_ms = song
-                        .duration
-                        .map(|d| d.as_secs() * 1000 + d.subsec_millis() as u64)
-                        .unwrap_or(0);
-
-                    (
-                        title,
-                        artist,
-                        duration_ms,
-                        Some(id.0) == current_id.map(|i| i.0),
-                        song.file.clone(),
-                    )
-                })
+                .iter()
+                .map(|song| Self::queue_item(song, current_id))
                 .collect())
         })
     }
 
+    fn get_queue_changes(&self, since: u64) -> Result<Option<QueueDelta>> {
+        self.with_client(|client| {
+            let status = client.status()?;
+            // `get_queue_version` keeps the playlist version in the high half
+            let since = (since >> 32) as u32;
+            let changed = if status.queue_version == since {
+                Vec::new()
+            } else {
+                client.changes(since)?
+            };
+            let current_id = status.song.map(|s| s.id.0);
+
+            Ok(Some(QueueDelta {
+                len: status.queue_len as usize,
+                changed: changed
+                    .iter()
+                    .filter_map(|song| {
+                        let pos = song.place?.pos as usize;
+                        Some((pos, Self::queue_item(song, current_id)))
+                    })
+                    .collect(),
+                current: status.song.map(|s| s.pos as usize),
+            }))
+        })
+    }
+
     fn get_queue_version(&self) -> Option<u64> {
         self.with_client(|client| {
             let status = client.status()?;
@@ -332,6 +354,47 @@ impl PlayerTrait for MpdPlayer {
 
 #[cfg(feature = "mpd")]
 impl MpdPlayer {
+    fn queue_item(song: &Song, current_id: Option<u32>) -> QueueItem {
+        // Tag lookup by name, case-insensitive
+        let find_tag = |key: &str| -> Option<String> {
+            let key_lower = key.to_lowercase();
+            song.tags
+                .iter()
+                .find(|(k, _)| k.to_lowercase() == key_lower)
+                .map(|(_, v)| v.clone())
+        };
+
+        let id = song.place.map(|p| p.id.0);
+        let title = song.title.clone().unwrap_or_else(|| song.file.clone());
+        let artist = song
+            .artist
+            .clone()
+            .or_else(|| find_tag("Artist"))
+            .or_else(|| find_tag("AlbumArtist"))
+            .or_else(|| find_tag("Composer"))
+            .unwrap_or_else(|| "Unknown Artist".to_string());
+        let duration_ms = song
+            .duration
+            .map(|d| d.as_secs() * 1000 + d.subsec_millis() as u64)
+            .unwrap_or(0);
+
+        let album = find_tag("Album").unwrap_or_default();
+        // "2001-05-14" or "2001"
+        let year = find_tag("Date")
+            .map(|d| d.chars().take(4).collect())
+            .unwrap_or_default();
+
+        (
+            title,
+            artist,
+            duration_ms,
+            id.is_some() && id == current_id,
+            song.file.clone(),
+            album,
+            year,
+        )
+    }
+
     /// Set crossfade duration in seconds (0 to disable)
     pub fn set_crossfade(&self, seconds: u32) -> Result<()> {
         self.with_client(|client| {
@@ -480,3 +543,16 @@ impl MpdPlayer {
         self.with_client(|client| client.switch(pos).context("Failed to switch to position"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_track_tag() {
+        assert_eq!(parse_track_tag(Some("4/11")), (Some(4), Some(11)));
+        assert_eq!(parse_track_tag(Some("7")), (Some(7), None));
+        assert_eq!(parse_track_tag(Some("x")), (None, None));
+        assert_eq!(parse_track_tag(None), (None, None));
+    }
+}
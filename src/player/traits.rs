@@ -30,6 +30,14 @@ pub struct TrackInfo {
 
     /// Current Volume (0-100)
     pub volume: Option<u32>,
+
+    /// Album position 💿 (track 4 of 11)
+    pub track_number: Option<u32>,
+    pub track_total: Option<u32>,
+
+    /// Genre tag, for the listening history
+    #[serde(default)]
+    pub genre: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
@@ -52,7 +60,7 @@ pub trait PlayerTrait: Send + Sync {
     fn set_volume(&self, volume: u8) -> Result<()>;
 
     /// Get current queue/playlist (MPD only, returns empty for controller mode)
-    /// Returns: (title, artist, duration_ms, is_current, file_path)
+    /// Returns: (title, artist, duration_ms, is_current, file_path, album, year)
     fn get_queue(&self) -> Result<Vec<QueueItem>> {
         Ok(Vec::new())
     }
@@ -64,6 +72,13 @@ pub trait PlayerTrait: Send + Sync {
         None
     }
 
+    /// What changed in the queue since `since` (a `get_queue_version`
+    /// value), so a big queue isn't fetched whole for every edit.
+    /// `None` when the player can't tell: fetch the whole queue instead.
+    fn get_queue_changes(&self, _since: u64) -> Result<Option<QueueDelta>> {
+        Ok(None)
+    }
+
     // Extended methods for MPD features (defaults for non-MPD players)
     fn shuffle(&self, _enable: bool) -> Result<()> {
         Ok(())
@@ -85,5 +100,16 @@ pub trait PlayerTrait: Send + Sync {
     }
 }
 
-/// (title, artist, duration_ms, is_current, file_path)
-pub type QueueItem = (String, String, u64, bool, String);
+/// (title, artist, duration_ms, is_current, file_path, album, year)
+pub type QueueItem = (String, String, u64, bool, String, String, String);
+
+/// Queue edits since a known version
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QueueDelta {
+    /// Length of the queue now; entries past it are gone
+    pub len: usize,
+    /// Entries that changed or were added, by position
+    pub changed: Vec<(usize, QueueItem)>,
+    /// Position of the current song
+    pub current: Option<usize>,
+}
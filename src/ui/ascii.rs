@@ -0,0 +1,164 @@
+//! Plain ASCII for consoles without emoji 🔤
+//!
+//! The Linux console, serial terminals and fonts without emoji coverage
+//! show boxes or garbage for 📁, ♪, ● and the rounded borders. With
+//! `ascii_only` on (or detected), every finished frame is rewritten cell by
+//! cell: symbols and box drawing become ASCII, letters in titles and lyrics
+//! (accents, CJK) are left alone.
+
+use ratatui::buffer::Buffer;
+use unicode_width::UnicodeWidthStr;
+
+/// Whether the environment looks like it can't draw emoji: the Linux
+/// console, an old terminal type or a locale that isn't UTF-8
+pub fn detect(var: impl Fn(&str) -> Option<String>) -> bool {
+    let term = var("TERM").unwrap_or_default();
+    if matches!(
+        term.as_str(),
+        "linux" | "vt100" | "vt102" | "vt220" | "dumb"
+    ) {
+        return true;
+    }
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|key| var(key))
+        .find(|v| !v.is_empty())
+        .is_some_and(|locale| {
+            let locale = locale.to_lowercase();
+            !locale.contains("utf-8") && !locale.contains("utf8")
+        })
+}
+
+/// Replace the fancy symbols of a drawn frame
+pub fn downgrade(buf: &mut Buffer) {
+    let area = buf.area;
+    for y in area.top()..area.bottom() {
+        let mut x = area.left();
+        while x < area.right() {
+            let cell = &mut buf[(x, y)];
+            let width = cell.symbol().width().max(1) as u16;
+            if let Some(plain) = cell.symbol().chars().next().and_then(replacement) {
+                // Keep the grapheme's width so nothing after it shifts
+                let mut chars = plain.chars();
+                for dx in 0..width.min(area.right() - x) {
+                    let c = chars.next().unwrap_or(' ');
+                    buf[(x + dx, y)].set_char(c);
+                }
+            }
+            x += width;
+        }
+    }
+}
+
+/// ASCII for one symbol, `None` for ASCII and for letters
+pub fn replacement(c: char) -> Option<&'static str> {
+    if c.is_ascii() {
+        return None;
+    }
+    let plain = match c {
+        // Punctuation
+        '–' | '—' => "-",
+        '…' => ".",
+        '•' => "*",
+        '›' => ">",
+        '‹' => "<",
+        'ℹ' => "i",
+        '∓' => "+",
+        // Arrows
+        '←' => "<",
+        '→' => ">",
+        '↑' => "^",
+        '↓' => "v",
+        '↔' => "-",
+        '↕' => "|",
+        '↩' | '↪' | '↺' | '↻' | '⟳' => "@",
+        '\u{2190}'..='\u{21FF}' => "-",
+        // Transport
+        '⏮' => "|<",
+        '⏭' => ">|",
+        '⏪' => "<<",
+        '⏩' => ">>",
+        '⏸' => "||",
+        '⏹' => "[]",
+        '⏱' => "t",
+        '\u{2300}'..='\u{23FF}' => "*",
+        // Box drawing
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '═' => "-",
+        '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '║' => "|",
+        '\u{2500}'..='\u{257F}' => "+",
+        // Blocks, by how much of the cell they fill
+        '▁' | '▂' => "_",
+        '▃' | '▄' | '▅' => "=",
+        '▆' | '▇' | '█' | '▓' => "#",
+        '▀' | '▔' => "\"",
+        '▒' => ":",
+        '░' => ".",
+        '\u{2580}'..='\u{259F}' => "|",
+        // Shapes
+        '▶' | '►' | '▸' => ">",
+        '◀' | '◄' | '◂' => "<",
+        '▲' | '▴' => "^",
+        '▼' | '▾' => "v",
+        '○' | '◌' | '◯' | '▱' | '◇' | '□' => "o",
+        '\u{25A0}'..='\u{25FF}' => "*",
+        // Symbols and dingbats
+        '♪' | '♫' | '♩' | '♬' => "~",
+        '⚠' => "!",
+        '✔' | '✓' | '➕' => "+",
+        '✖' | '✗' | '❌' => "x",
+        '✂' => "%",
+        '\u{2600}'..='\u{27BF}' => "*",
+        // Braille, by how many dots are raised
+        '\u{2800}'..='\u{28FF}' => match (c as u32 - 0x2800).count_ones() {
+            0 => " ",
+            1..=2 => ".",
+            3..=5 => ":",
+            _ => "#",
+        },
+        '🅰' => "A",
+        '🅱' => "B",
+        '🔀' => "SH",
+        '🔁' => "RP",
+        '🔂' => "R1",
+        '🔇' => "MU",
+        '🔊' => "VO",
+        '🔍' | '🔎' => "/",
+        '📁' | '🗂' => "[]",
+        '💿' | '📀' => "()",
+        '🎵' | '🎶' | '🎼' => "~",
+        '\u{1F000}'..='\u{1FAFF}' => "*",
+        _ => return None,
+    };
+    Some(plain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_detect_and_downgrade() {
+        let env = |pairs: &'static [(&str, &str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert!(detect(env(&[("TERM", "linux"), ("LANG", "en_US.UTF-8")])));
+        assert!(detect(env(&[
+            ("TERM", "xterm"),
+            ("LANG", "en_US.ISO-8859-1")
+        ])));
+        assert!(!detect(env(&[("TERM", "xterm"), ("LC_ALL", "de_DE.utf8")])));
+        assert!(!detect(env(&[("TERM", "xterm-kitty")])));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 12, 1));
+        buf.set_string(0, 0, "╭📁 Café ♪╮", ratatui::style::Style::default());
+        downgrade(&mut buf);
+        let line: String = (0..12).map(|x| buf[(x, 0)].symbol().to_string()).collect();
+        assert_eq!(line, "+[] Café ~+ ");
+    }
+}
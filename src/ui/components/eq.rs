@@ -1,4 +1,5 @@
 use crate::app::App;
+use crate::audio::output::BitDepth;
 use ratatui::{
     layout::Alignment,
     layout::Rect,
@@ -8,6 +9,10 @@ use ratatui::{
     Frame,
 };
 
+/// dBFS mapped to the top/bottom of the graph for the spectrum overlay
+const SPECTRUM_TOP_DB: f32 = -12.0;
+const SPECTRUM_BOTTOM_DB: f32 = -78.0;
+
 pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
     let theme = &app.theme;
 
@@ -124,7 +129,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
 
         // ━━━ EQ GRAPH with High Resolution ━━━
         // Scale graph height based on available space (7-25 rows)
-        let available_rows = h.saturating_sub(14); // Reserve space for other elements
+        let available_rows = h.saturating_sub(15); // Reserve space for other elements
                                                    // Smart scaling: compact for tmux (7-13), expanded for fullscreen (up to 25)
         let max_graph_h = if h >= 40 { 25 } else { 13 };
         let graph_h = available_rows.max(7).min(max_graph_h);
@@ -177,6 +182,43 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         // Generate dB labels based on graph height
         let db_step = 24.0 / (graph_h - 1) as f32; // dB per row
 
+        // ━━━ SPECTRUM OVERLAY (rows filled from the bottom, per column) ━━━
+        let spectrum_rows: Option<Vec<usize>> =
+            if app.spectrum_overlay != crate::app::SpectrumOverlay::Off {
+                // Columns are octave-spaced: band i sits at (2i+1)/20 of the width
+                let col_freqs: Vec<f32> = (0..graph_w)
+                    .map(|col| {
+                        let octave = (col as f32 + 0.5) / graph_w as f32 * bands as f32 - 0.5;
+                        crate::audio::dsp::EQ_FREQUENCIES[0] * 2f32.powf(octave)
+                    })
+                    .collect();
+                // "Pre" reads the pipeline's tap in front of the DSP chain
+                let pre = app.spectrum_overlay == crate::app::SpectrumOverlay::Pre;
+                let levels = app.visualizer.spectrum_db(&col_freqs, pre);
+
+                // The output tap is post-volume; back the volume out so the overlay doesn't shrink
+                let volume_db = if pre {
+                    0.0
+                } else {
+                    20.0 * app.output.volume_gain(app.app_volume.max(1)).log10()
+                };
+
+                Some(
+                    levels
+                        .iter()
+                        .map(|&db| {
+                            let db = db - volume_db;
+                            let level = ((db - SPECTRUM_BOTTOM_DB)
+                                / (SPECTRUM_TOP_DB - SPECTRUM_BOTTOM_DB))
+                                .clamp(0.0, 1.0);
+                            (level * graph_h as f32).round() as usize
+                        })
+                        .collect(),
+                )
+            } else {
+                None
+            };
+
         for row in 0..graph_h {
             let mut spans: Vec<Span> = Vec::new();
 
@@ -233,6 +275,15 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
                 } else if is_cut_fill {
                     // Solid fill for cut
                     spans.push(Span::styled("░", Style::default().fg(pink)));
+                } else if spectrum_rows
+                    .as_ref()
+                    .is_some_and(|rows| row + rows[col] >= graph_h)
+                {
+                    // Live spectrum sits behind the curve and fills
+                    spans.push(Span::styled(
+                        "▒",
+                        Style::default().fg(muted).add_modifier(Modifier::DIM),
+                    ));
                 } else if is_band_col {
                     // Dotted vertical grid line at band positions
                     spans.push(Span::styled("┊", Style::default().fg(grid_dim)));
@@ -268,16 +319,42 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
 
         // ━━━ EQUALISER + PRESET ━━━
         lines.push(Line::from(""));
+        let spectrum_label = match app.spectrum_overlay {
+            crate::app::SpectrumOverlay::Post => " · POST",
+            crate::app::SpectrumOverlay::Pre => " · PRE",
+            crate::app::SpectrumOverlay::Off => "",
+        };
         lines.push(
-            Line::from(Span::styled("EQUALISER", Style::default().fg(muted)))
-                .alignment(Alignment::Center),
+            Line::from(vec![
+                Span::styled("EQUALISER", Style::default().fg(muted)),
+                Span::styled(spectrum_label, Style::default().fg(grid_dim)),
+            ])
+            .alignment(Alignment::Center),
         );
         let preset = format!("PRESET: {}", app.get_preset_name());
+        // A/B indicator: highlight whichever curve is audible
+        let (a_style, b_style) = if app.eq_ab_on_b {
+            (
+                Style::default().fg(grid_dim),
+                Style::default().fg(cream).add_modifier(Modifier::BOLD),
+            )
+        } else {
+            (
+                Style::default().fg(green).add_modifier(Modifier::BOLD),
+                Style::default().fg(grid_dim),
+            )
+        };
         lines.push(
-            Line::from(Span::styled(
-                preset,
-                Style::default().fg(if app.eq_enabled { green } else { muted }),
-            ))
+            Line::from(vec![
+                Span::styled(
+                    preset,
+                    Style::default().fg(if app.eq_enabled { green } else { muted }),
+                ),
+                Span::raw("   "),
+                Span::styled("A", a_style),
+                Span::styled("│", Style::default().fg(grid_dim)),
+                Span::styled("B", b_style),
+            ])
             .alignment(Alignment::Center),
         );
 
@@ -382,6 +459,33 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         }
         lines.push(Line::from(rg_line).alignment(Alignment::Center));
 
+        // ━━━ OUTPUT FORMAT (own line) ━━━
+        let depth = app.output.depth();
+        let mut out_line: Vec<Span> = Vec::new();
+        out_line.push(Span::styled("OUTPUT:  ", Style::default().fg(muted)));
+        for option in [BitDepth::Float32, BitDepth::Int24, BitDepth::Int16] {
+            let s = if option == depth {
+                Style::default().fg(green)
+            } else {
+                Style::default().fg(grid_dim)
+            };
+            out_line.push(Span::styled(option.label(), s));
+            out_line.push(Span::raw("  "));
+        }
+        // Dither only matters when samples get cut short
+        let dither_color = if app.output.dither() && depth.full_scale().is_some() {
+            green
+        } else {
+            grid_dim
+        };
+        out_line.push(Span::styled("DITHER  ", Style::default().fg(dither_color)));
+        let rate = match app.output.sample_rate() {
+            Some(rate) => format!("{:.1}k", rate as f32 / 1000.0),
+            None => "SOURCE".to_string(),
+        };
+        out_line.push(Span::styled(rate, Style::default().fg(green)));
+        lines.push(Line::from(out_line).alignment(Alignment::Center));
+
         // ━━━ DEVICE PILL ━━━
         lines.push(Line::from(""));
         let status = if app.dsp_available {
@@ -0,0 +1,82 @@
+use crate::app::about::AboutState;
+use crate::app::App;
+use crate::t;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+    let theme = &app.theme;
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(Span::styled(
+            t!("about.title"),
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ))
+        .title_alignment(Alignment::Left)
+        .border_style(Style::default().fg(theme.blue))
+        .style(Style::default().bg(Color::Reset));
+
+    let message = |text: String| {
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.overlay))
+            .wrap(Wrap { trim: true })
+    };
+
+    let content = match (&app.about, app.track.is_some()) {
+        (_, false) => message(t!("about.no_track")),
+        (AboutState::Idle | AboutState::Loading, _) => message(t!("about.loading")),
+        (AboutState::NotFound, _) => message(t!("about.not_found")),
+        (AboutState::Failed(e), _) => message(t!("about.failed", error = e)),
+        (AboutState::Loaded(sections), _) => {
+            let mut lines: Vec<Line> = Vec::new();
+            let mut sources: Vec<&str> = Vec::new();
+            for section in sections {
+                if !lines.is_empty() {
+                    lines.push(Line::from(""));
+                }
+                lines.push(Line::from(Span::styled(
+                    section.heading.clone(),
+                    Style::default()
+                        .fg(theme.yellow)
+                        .add_modifier(Modifier::BOLD),
+                )));
+                for paragraph in section.text.lines().filter(|l| !l.trim().is_empty()) {
+                    lines.push(Line::from(Span::styled(
+                        paragraph.to_string(),
+                        Style::default().fg(theme.text),
+                    )));
+                }
+                if !sources.contains(&section.source) {
+                    sources.push(section.source);
+                }
+            }
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {} ", sources.join(" · ")),
+                    Style::default().fg(theme.overlay),
+                ))
+                .alignment(Alignment::Right),
+            );
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .scroll((app.about_scroll, 0))
+        }
+    };
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let inner = Rect {
+        x: inner.x + 1,
+        width: inner.width.saturating_sub(2),
+        ..inner
+    };
+    f.render_widget(content, inner);
+}
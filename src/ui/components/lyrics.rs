@@ -1,4 +1,6 @@
-use crate::app::{App, LyricsState, ViewMode};
+use crate::app::lyrics::romanize;
+use crate::app::{App, LyricsEditor, LyricsState};
+use crate::t;
 use ratatui::{
     layout::Alignment,
     layout::Rect,
@@ -11,11 +13,31 @@ use ratatui::{
 pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
     let theme = &app.theme;
 
-    let mode_title = match app.view_mode {
-        ViewMode::Lyrics => " Lyrics ".to_string(),
-        ViewMode::Visualizer => " Visualizer ".to_string(),
-        ViewMode::Library => " Library ".to_string(),
-        ViewMode::EQ => " Sound ".to_string(),
+    let mode_title = match &app.lyrics_editor {
+        Some(editor) => {
+            t!(
+                "lyrics.title_editing",
+                dirty = if editor.dirty { " *" } else { "" }
+            )
+        }
+        None => {
+            let mut title = match app.lyrics_sync_ms() {
+                0 => t!("lyrics.title"),
+                ms => t!(
+                    "lyrics.title_offset",
+                    offset = format!("{:+.1}", ms as f64 / 1000.0)
+                ),
+            };
+            if let Some(search) = &app.lyrics_search {
+                title.push_str(&format!(
+                    "· /{} {}/{} ",
+                    search.query,
+                    search.current + 1,
+                    search.matches.len()
+                ));
+            }
+            title
+        }
     };
 
     let lyrics_title = Line::from(vec![Span::styled(
@@ -82,10 +104,15 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
     // Let's make `rect_container.rs` or similar? No, I'll just put the container logic in `ui/mod.rs`
     // and pass the `inner` rect to these specific components.
 
+    if let Some(editor) = &app.lyrics_editor {
+        render_editor(f, inner_lyrics_area, app, editor);
+        return;
+    }
+
     match &app.lyrics {
         LyricsState::Loaded(lyrics, _) => {
             let height = inner_lyrics_area.height as usize;
-            let track_ms = app.track.as_ref().map(|t| t.position_ms).unwrap_or(0);
+            let track_ms = app.lyrics_position_ms();
 
             let current_idx = lyrics
                 .iter()
@@ -94,10 +121,13 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
                 .unwrap_or(lyrics.len().saturating_sub(1));
 
             let mut lines = Vec::new();
-            let half_height = height / 2;
+            // Romanization adds a second row under every lyric
+            let rows_per_line = if app.show_romanization { 2 } else { 1 };
+            let slots = height / rows_per_line;
+            let half_height = slots / 2;
             let center_idx = app.lyrics_offset.unwrap_or(current_idx);
 
-            for row in 0..height {
+            for row in 0..slots {
                 let dist_from_center: isize = (row as isize - half_height as isize).abs();
                 let target_idx_isize =
                     (center_idx as isize) - (half_height as isize) + (row as isize);
@@ -121,6 +151,8 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
                         Style::default()
                             .add_modifier(Modifier::BOLD)
                             .fg(theme.green)
+                    } else if app.lyrics_search.as_ref().is_some_and(|s| s.is_match(idx)) {
+                        Style::default().fg(theme.cyan)
                     } else {
                         match dist_from_center {
                             1 => Style::default().fg(theme.text),
@@ -150,8 +182,17 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
                         prefix_span,
                         Span::styled(line.text.clone(), style),
                     ]));
+                    if rows_per_line == 2 {
+                        let romaji = romanize(&line.text).unwrap_or_default();
+                        lines.push(Line::from(vec![
+                            Span::raw("  "),
+                            Span::styled(romaji, style.add_modifier(Modifier::ITALIC)),
+                        ]));
+                    }
                 } else {
-                    lines.push(Line::from(""));
+                    for _ in 0..rows_per_line {
+                        lines.push(Line::from(""));
+                    }
                 }
             }
 
@@ -162,6 +203,33 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
 
             f.render_widget(lyrics_widget, inner_lyrics_area);
         }
+        LyricsState::Plain(text, _) => {
+            // No timestamps: a static paragraph the user scrolls with j/k.
+            // Rows as `plain_rows` counts them, which the search jumps by.
+            let mut lines: Vec<Line> = Vec::new();
+            for (i, l) in text.iter().enumerate() {
+                let matched = app.lyrics_search.as_ref().is_some_and(|s| s.is_match(i));
+                let color = if matched { theme.cyan } else { theme.text };
+                lines.push(Line::from(Span::styled(
+                    l.clone(),
+                    Style::default().fg(color),
+                )));
+                if let Some(romaji) = romanize(l).filter(|_| app.show_romanization) {
+                    lines.push(Line::from(Span::styled(
+                        romaji,
+                        Style::default()
+                            .fg(theme.overlay)
+                            .add_modifier(Modifier::ITALIC),
+                    )));
+                }
+            }
+            let lyrics_widget = Paragraph::new(lines)
+                .alignment(Alignment::Center)
+                .wrap(ratatui::widgets::Wrap { trim: true })
+                .scroll((app.lyrics_scroll, 0))
+                .block(Block::default().style(Style::default().bg(Color::Reset)));
+            f.render_widget(lyrics_widget, inner_lyrics_area);
+        }
         LyricsState::Loading => {
             let text = Paragraph::new(Text::styled(
                 "\nFetching Lyrics...",
@@ -200,3 +268,50 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         }
     }
 }
+
+/// Editor list: `[mm:ss.xx] text`, selection kept in the middle
+fn render_editor(f: &mut Frame, area: Rect, app: &App, editor: &LyricsEditor) {
+    let theme = &app.theme;
+    let height = area.height as usize;
+    let first = editor.selected.saturating_sub(height / 2);
+    let pos = app.lyrics_position_ms();
+
+    let lines: Vec<Line> = editor
+        .lines
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(height)
+        .map(|(i, line)| {
+            let ms = line.timestamp_ms;
+            let stamp = format!(
+                "[{:02}:{:02}.{:02}] ",
+                ms / 60000,
+                (ms % 60000) / 1000,
+                (ms % 1000) / 10
+            );
+            let selected = i == editor.selected;
+            let stamp_style = if ms > pos {
+                Style::default().fg(theme.overlay)
+            } else {
+                Style::default().fg(theme.green)
+            };
+            let text_style = if selected {
+                Style::default()
+                    .fg(theme.yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            Line::from(vec![
+                Span::styled(if selected { "▶ " } else { "  " }, text_style),
+                Span::styled(stamp, stamp_style),
+                Span::styled(line.text.clone(), text_style),
+            ])
+        })
+        .collect();
+
+    let widget =
+        Paragraph::new(lines).block(Block::default().style(Style::default().bg(Color::Reset)));
+    f.render_widget(widget, area);
+}
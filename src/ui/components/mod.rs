@@ -1,5 +1,8 @@
 pub mod eq;
 
+pub mod info;
+pub mod related;
+
 pub mod lyrics;
 
 pub mod visualizer;
@@ -0,0 +1,100 @@
+use crate::app::related::RelatedState;
+use crate::app::App;
+use crate::t;
+use crate::ui::utils::fit;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+    let theme = &app.theme;
+    let artist = app
+        .track
+        .as_ref()
+        .map(|t| t.artist.clone())
+        .unwrap_or_default();
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(Span::styled(
+            t!("related.title", artist = artist),
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ))
+        .title_alignment(Alignment::Left)
+        .border_style(Style::default().fg(theme.blue))
+        .style(Style::default().bg(Color::Reset));
+
+    let message = |text: String| {
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.overlay))
+            .wrap(Wrap { trim: true })
+    };
+
+    let inner = block.inner(area);
+    let inner = Rect {
+        x: inner.x + 1,
+        width: inner.width.saturating_sub(2),
+        ..inner
+    };
+
+    let content = match &app.related {
+        _ if app.track.is_none() => message(t!("related.no_track")),
+        RelatedState::Idle if app.library_cache.is_none() => message(t!("related.no_library")),
+        RelatedState::Idle | RelatedState::Loading => message(t!("related.loading")),
+        RelatedState::NotFound => message(t!("related.not_found")),
+        RelatedState::Failed(e) => message(t!("related.failed", error = e)),
+        RelatedState::Loaded(artists, source) => {
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {} ", source),
+                    Style::default().fg(theme.overlay),
+                ))
+                .alignment(Alignment::Right),
+            );
+
+            // Keep the selection in view
+            let rows = inner.height as usize;
+            let selected = app.related_selected.min(artists.len().saturating_sub(1));
+            let start = selected
+                .saturating_sub(rows.saturating_sub(1))
+                .min(artists.len().saturating_sub(rows));
+
+            let mut lines: Vec<Line> = Vec::new();
+            for (i, related) in artists.iter().enumerate().skip(start).take(rows) {
+                let style = match (i == selected, related.in_library) {
+                    (true, _) => Style::default()
+                        .fg(theme.magenta)
+                        .add_modifier(Modifier::BOLD),
+                    (false, true) => Style::default().fg(theme.text),
+                    (false, false) => Style::default().fg(theme.overlay),
+                };
+                let marker = if i == selected { "▶ " } else { "  " };
+                let (detail, detail_color) = match (related.in_library, related.albums) {
+                    (true, 0) => (t!("related.in_library"), theme.green),
+                    (true, n) => (t!("related.albums", count = n), theme.green),
+                    (false, _) => (t!("related.not_in_library"), theme.overlay),
+                };
+                let score = format!("{:>3.0}%", related.score * 100.0);
+                let name_width =
+                    (inner.width as usize).saturating_sub(2 + detail.width() + score.len() + 4);
+                lines.push(Line::from(vec![
+                    Span::styled(marker, style),
+                    Span::styled(fit(&related.name, name_width), style),
+                    Span::styled(format!("  {}", detail), Style::default().fg(detail_color)),
+                    Span::styled(format!("  {}", score), Style::default().fg(theme.overlay)),
+                ]));
+            }
+            Paragraph::new(lines)
+        }
+    };
+
+    f.render_widget(block, area);
+    f.render_widget(content, inner);
+}
@@ -1,4 +1,5 @@
 use crate::app::App;
+use crate::t;
 use ratatui::{
     layout::Alignment,
     layout::Rect,
@@ -16,7 +17,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         .borders(ratatui::widgets::Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded)
         .title(Span::styled(
-            " Visualizer ",
+            t!("visualizer.title"),
             Style::default()
                 .fg(theme.cyan)
                 .add_modifier(ratatui::style::Modifier::BOLD),
@@ -0,0 +1,132 @@
+//! Artwork colors for 256-color terminals 🎨
+//!
+//! Truecolor art sent to a terminal with a limited palette gets rounded by
+//! the terminal, which bands smooth covers into blotches. These map pixels
+//! onto the xterm palette ourselves, spreading the rounding error so
+//! gradients survive.
+
+use crate::app::ArtDither;
+use image::RgbImage;
+use ratatui::style::Color;
+
+/// Channel levels of the 6×6×6 cube (indices 16–231)
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// 4×4 Bayer matrix, thresholds 0–15
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Nearest palette entry (16–255) and its color
+pub fn nearest(rgb: [u8; 3]) -> (u8, [u8; 3]) {
+    let level = |c: u8| {
+        CUBE.iter()
+            .enumerate()
+            .min_by_key(|(_, &l)| (l as i32 - c as i32).abs())
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    };
+    let (r, g, b) = (level(rgb[0]), level(rgb[1]), level(rgb[2]));
+    let cube = [CUBE[r], CUBE[g], CUBE[b]];
+
+    // Grays 232–255 run 8, 18, ... 238
+    let avg = (rgb[0] as u32 + rgb[1] as u32 + rgb[2] as u32) / 3;
+    let step = ((avg.saturating_sub(3)) / 10).min(23) as u8;
+    let gray_level = 8 + step * 10;
+    let gray = [gray_level; 3];
+
+    let dist = |c: [u8; 3]| -> u32 {
+        c.iter()
+            .zip(rgb)
+            .map(|(&a, b)| (a as i32 - b as i32).pow(2) as u32)
+            .sum()
+    };
+    if dist(gray) < dist(cube) {
+        (232 + step, gray)
+    } else {
+        (16 + 36 * r as u8 + 6 * g as u8 + b as u8, cube)
+    }
+}
+
+/// One color per pixel, row-major. `Off` keeps truecolor.
+pub fn colors(img: &RgbImage, dither: ArtDither) -> Vec<Color> {
+    let (w, h) = (img.width() as usize, img.height() as usize);
+    match dither {
+        ArtDither::Off | ArtDither::Auto => {
+            img.pixels().map(|p| Color::Rgb(p[0], p[1], p[2])).collect()
+        }
+        ArtDither::Ordered => img
+            .enumerate_pixels()
+            .map(|(x, y, p)| {
+                // Nudge by up to ±half a cube step before rounding
+                let t = BAYER[y as usize % 4][x as usize % 4] as i32 * 40 / 16 - 20;
+                let nudge = |c: u8| (c as i32 + t).clamp(0, 255) as u8;
+                Color::Indexed(nearest([nudge(p[0]), nudge(p[1]), nudge(p[2])]).0)
+            })
+            .collect(),
+        ArtDither::FloydSteinberg => {
+            let mut buf: Vec<[f32; 3]> = img
+                .pixels()
+                .map(|p| [p[0] as f32, p[1] as f32, p[2] as f32])
+                .collect();
+            let mut out = Vec::with_capacity(w * h);
+            for y in 0..h {
+                for x in 0..w {
+                    let old = buf[y * w + x];
+                    let clamped = old.map(|c| c.round().clamp(0.0, 255.0) as u8);
+                    let (index, new) = nearest(clamped);
+                    out.push(Color::Indexed(index));
+                    let err = [0, 1, 2].map(|i| old[i] - new[i] as f32);
+                    let mut spread = |dx: isize, dy: usize, k: f32| {
+                        let nx = x as isize + dx;
+                        if nx >= 0 && (nx as usize) < w && y + dy < h {
+                            let cell = &mut buf[(y + dy) * w + nx as usize];
+                            for i in 0..3 {
+                                cell[i] += err[i] * k;
+                            }
+                        }
+                    };
+                    spread(1, 0, 7.0 / 16.0);
+                    spread(-1, 1, 3.0 / 16.0);
+                    spread(0, 1, 5.0 / 16.0);
+                    spread(1, 1, 1.0 / 16.0);
+                }
+            }
+            out
+        }
+    }
+}
+
+/// A single averaged color (Braille cells): rounded, nothing to spread to
+pub fn color(rgb: [u8; 3], dither: ArtDither) -> Color {
+    match dither {
+        ArtDither::Off | ArtDither::Auto => Color::Rgb(rgb[0], rgb[1], rgb[2]),
+        ArtDither::Ordered | ArtDither::FloydSteinberg => Color::Indexed(nearest(rgb).0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_palette_entries() {
+        assert_eq!(nearest([0, 0, 0]), (16, [0, 0, 0]));
+        assert_eq!(nearest([255, 255, 255]).0, 231);
+        assert_eq!(nearest([255, 0, 0]).0, 196);
+        // Mid grays land on the gray ramp, not the cube
+        assert_eq!(nearest([128, 128, 128]), (244, [128, 128, 128]));
+    }
+
+    #[test]
+    fn test_dithering_mixes_a_gradient() {
+        // A flat color between two palette entries
+        let img = RgbImage::from_pixel(8, 8, image::Rgb([115, 0, 0]));
+        let plain: std::collections::HashSet<_> =
+            colors(&img, ArtDither::Off).into_iter().collect();
+        assert_eq!(plain.len(), 1);
+        for dither in [ArtDither::Ordered, ArtDither::FloydSteinberg] {
+            let mixed: std::collections::HashSet<_> = colors(&img, dither).into_iter().collect();
+            assert!(mixed.len() > 1, "{:?} should mix two reds", dither);
+            assert!(mixed.iter().all(|c| matches!(c, Color::Indexed(_))));
+        }
+    }
+}
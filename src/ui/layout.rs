@@ -1,18 +1,19 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use serde::{Deserialize, Serialize};
 
 pub struct MainLayout {
     pub body_area: Rect,
     pub footer_area: Rect,
 }
 
-pub fn get_main_layout(area: Rect) -> MainLayout {
+pub fn get_main_layout(area: Rect, footer: bool) -> MainLayout {
     // Responsive Logic 🧠
-    // 1. Footer needs 1 line at the bottom always.
+    // 1. Footer takes 1 line at the bottom (unless the layout hides it).
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Min(0),    // Body
-            Constraint::Length(1), // Footer
+            Constraint::Min(0),                             // Body
+            Constraint::Length(if footer { 1 } else { 0 }), // Footer
         ])
         .split(area);
 
@@ -22,6 +23,152 @@ pub fn get_main_layout(area: Rect) -> MainLayout {
     }
 }
 
+/// Where the player card goes relative to the panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlayerPlacement {
+    /// Side by side on wide terminals, stacked otherwise (and always in tmux)
+    Auto,
+    Left,
+    Top,
+}
+
+/// Named starting points for `[layout]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutPreset {
+    /// The original responsive layout
+    #[default]
+    Classic,
+    /// Card on the left at any width, panel gets more room
+    Side,
+    /// Card on top, panel below
+    Stacked,
+    /// Small card, big lyrics / library
+    Focus,
+    /// Player card only, no footer
+    Minimal,
+}
+
+/// `[layout]` in config.toml: a preset, with any field overridden
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    pub preset: LayoutPreset,
+    pub player: Option<PlayerPlacement>,
+    /// Player card share (%) when side by side
+    pub side_split: Option<u16>,
+    /// Player card share (%) when stacked
+    pub stack_split: Option<u16>,
+    /// Show the lyrics / visualizer / library / EQ panel
+    pub panel: Option<bool>,
+    /// Show the `? keys` hint row
+    pub footer: Option<bool>,
+    /// Narrowest terminal that goes side by side in `auto`
+    pub wide_width: Option<u16>,
+    /// Shortest terminal (rows) that still stacks the panel under the card
+    pub stack_height: Option<u16>,
+    pub min_player_width: Option<u16>,
+    pub min_panel_width: Option<u16>,
+    /// Put the panel before the card
+    pub swap: Option<bool>,
+}
+
+/// Resolved layout settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutSpec {
+    pub player: PlayerPlacement,
+    pub side_split: u16,
+    pub stack_split: u16,
+    pub panel: bool,
+    pub footer: bool,
+    pub wide_width: u16,
+    pub stack_height: u16,
+    pub min_player_width: u16,
+    pub min_panel_width: u16,
+    /// Panel first (left / top), card second
+    pub swapped: bool,
+}
+
+impl Default for LayoutSpec {
+    fn default() -> Self {
+        Self {
+            player: PlayerPlacement::Auto,
+            side_split: 65,
+            stack_split: 45,
+            panel: true,
+            footer: true,
+            wide_width: 90,
+            stack_height: 30,
+            min_player_width: 20,
+            min_panel_width: 10,
+            swapped: false,
+        }
+    }
+}
+
+impl LayoutPreset {
+    pub fn spec(self) -> LayoutSpec {
+        let classic = LayoutSpec::default();
+        match self {
+            LayoutPreset::Classic => classic,
+            LayoutPreset::Side => LayoutSpec {
+                player: PlayerPlacement::Left,
+                side_split: 45,
+                ..classic
+            },
+            LayoutPreset::Stacked => LayoutSpec {
+                player: PlayerPlacement::Top,
+                stack_height: 20,
+                ..classic
+            },
+            LayoutPreset::Focus => LayoutSpec {
+                side_split: 35,
+                stack_split: 30,
+                ..classic
+            },
+            LayoutPreset::Minimal => LayoutSpec {
+                panel: false,
+                footer: false,
+                ..classic
+            },
+        }
+    }
+}
+
+impl LayoutSpec {
+    /// Move the divider by `delta` percent (towards right / bottom when
+    /// positive) in the orientation on screen. Returns the card's new share.
+    pub fn nudge_split(&mut self, horizontal: bool, delta: i16) -> u16 {
+        let delta = if self.swapped { -delta } else { delta };
+        let split = if horizontal {
+            &mut self.side_split
+        } else {
+            &mut self.stack_split
+        };
+        *split = (*split as i16 + delta).clamp(10, 90) as u16;
+        *split
+    }
+}
+
+impl LayoutConfig {
+    pub fn resolve(&self) -> LayoutSpec {
+        let base = self.preset.spec();
+        LayoutSpec {
+            player: self.player.unwrap_or(base.player),
+            side_split: self.side_split.unwrap_or(base.side_split).clamp(10, 90),
+            stack_split: self.stack_split.unwrap_or(base.stack_split).clamp(10, 90),
+            panel: self.panel.unwrap_or(base.panel),
+            footer: self.footer.unwrap_or(base.footer),
+            wide_width: self.wide_width.unwrap_or(base.wide_width),
+            stack_height: self.stack_height.unwrap_or(base.stack_height),
+            min_player_width: self.min_player_width.unwrap_or(base.min_player_width),
+            min_panel_width: self.min_panel_width.unwrap_or(base.min_panel_width),
+            swapped: self.swap.unwrap_or(base.swapped),
+        }
+    }
+}
+
 pub struct ContentLayout {
     pub left: Rect,
     pub right: Option<Rect>,
@@ -30,53 +177,144 @@ pub struct ContentLayout {
 
 pub fn get_content_layout(
     area: Rect,
-    show_lyrics: bool,
-    wide_mode: bool,
+    show_panel: bool,
+    is_tmux: bool,
     height: u16,
+    spec: &LayoutSpec,
 ) -> ContentLayout {
-    if show_lyrics {
-        if wide_mode {
-            // Unified Horizontal Mode: Music Dominant (65%)
-            let chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([
-                    Constraint::Percentage(65), // Bigger Music
-                    Constraint::Min(10),        // Lyrics
-                ])
-                .split(area);
-            ContentLayout {
-                left: chunks[0],
-                right: Some(chunks[1]),
-                is_horizontal: true,
-            }
+    let card_only = ContentLayout {
+        left: area,
+        right: None,
+        is_horizontal: false,
+    };
+    if !show_panel || !spec.panel {
+        return card_only;
+    }
+
+    let fits_side = area.width >= spec.min_player_width + spec.min_panel_width;
+    let horizontal = match spec.player {
+        PlayerPlacement::Auto => !is_tmux && area.width >= spec.wide_width,
+        PlayerPlacement::Left => fits_side,
+        PlayerPlacement::Top => false,
+    };
+
+    // Constraints are listed card first; swapped puts the card second
+    let order = |card: Constraint, panel: Constraint| {
+        if spec.swapped {
+            [panel, card]
         } else {
-            // Vertical Mode
-            if height < 30 {
-                // Too short for stack -> Hide Lyrics (Compressed)
-                ContentLayout {
-                    left: area,
-                    right: None,
-                    is_horizontal: false,
-                }
-            } else {
-                // Stack Mode: User requested 45% Top, 55% Bottom
-                let chunks = Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
-                    .split(area);
-                ContentLayout {
-                    left: chunks[0],
-                    right: Some(chunks[1]),
-                    is_horizontal: false,
-                }
-            }
+            [card, panel]
         }
-    } else {
-        // No Lyrics Mode
-        ContentLayout {
-            left: area,
-            right: None,
-            is_horizontal: false,
+    };
+    let unorder = |[a, b]: [Rect; 2]| if spec.swapped { (b, a) } else { (a, b) };
+
+    if horizontal {
+        let (mut card, mut panel) = unorder(
+            Layout::horizontal(order(
+                Constraint::Percentage(spec.side_split),
+                Constraint::Min(spec.min_panel_width),
+            ))
+            .areas(area),
+        );
+        // Keep the card usable when the percentage leaves it too thin
+        if card.width < spec.min_player_width {
+            (card, panel) = unorder(
+                Layout::horizontal(order(
+                    Constraint::Length(spec.min_player_width.min(area.width)),
+                    Constraint::Min(0),
+                ))
+                .areas(area),
+            );
         }
+        return ContentLayout {
+            left: card,
+            right: Some(panel),
+            is_horizontal: true,
+        };
+    }
+
+    // Too short to stack: card only
+    if height < spec.stack_height {
+        return card_only;
+    }
+    let (card, panel) = unorder(
+        Layout::vertical(order(
+            Constraint::Percentage(spec.stack_split),
+            Constraint::Percentage(100 - spec.stack_split),
+        ))
+        .areas(area),
+    );
+    ContentLayout {
+        left: card,
+        right: Some(panel),
+        is_horizontal: false,
+    }
+}
+
+/// Two panes inside the panel for split view: side by side when wide,
+/// stacked when tall, `None` when there's no room for two
+pub fn get_split_panel(area: Rect) -> Option<[Rect; 2]> {
+    if area.width >= 100 {
+        Some(Layout::horizontal([Constraint::Percentage(50); 2]).areas(area))
+    } else if area.height >= 24 {
+        Some(Layout::vertical([Constraint::Percentage(50); 2]).areas(area))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classic_matches_old_splits() {
+        let spec = LayoutConfig::default().resolve();
+        let wide = get_content_layout(Rect::new(0, 0, 100, 40), true, false, 40, &spec);
+        assert!(wide.is_horizontal);
+        assert_eq!(wide.left.width, 65);
+
+        // tmux sidebar and short panes keep the old behavior
+        let tmux = get_content_layout(Rect::new(0, 0, 100, 40), true, true, 40, &spec);
+        assert!(!tmux.is_horizontal);
+        assert_eq!(tmux.left.height, 18);
+        let short = get_content_layout(Rect::new(0, 0, 60, 20), true, false, 20, &spec);
+        assert!(short.right.is_none());
+    }
+
+    #[test]
+    fn test_preset_fields_can_be_overridden() {
+        let config: LayoutConfig =
+            toml::from_str("preset = \"side\"\nside_split = 5\nfooter = false").unwrap();
+        let spec = config.resolve();
+        assert_eq!(spec.player, PlayerPlacement::Left);
+        assert_eq!(spec.side_split, 10, "Clamped");
+        assert!(!spec.footer);
+
+        // Too thin a share still leaves room for the card
+        let layout = get_content_layout(Rect::new(0, 0, 60, 20), true, true, 20, &spec);
+        assert!(layout.is_horizontal);
+        assert_eq!(layout.left.width, spec.min_player_width);
+
+        let minimal = LayoutPreset::Minimal.spec();
+        let card = get_content_layout(Rect::new(0, 0, 120, 50), true, false, 50, &minimal);
+        assert!(card.right.is_none());
+    }
+
+    #[test]
+    fn test_swap_and_nudge_follow_the_divider() {
+        let mut spec = LayoutSpec::default();
+        assert_eq!(spec.nudge_split(true, 5), 70);
+        assert_eq!(spec.stack_split, 45, "Only the split on screen moves");
+
+        spec.swapped = true;
+        let layout = get_content_layout(Rect::new(0, 0, 100, 40), true, false, 40, &spec);
+        let panel = layout.right.unwrap();
+        assert_eq!((panel.x, panel.width), (0, 30));
+        assert_eq!((layout.left.x, layout.left.width), (30, 70));
+
+        // Divider right = card (now on the right) shrinks
+        assert_eq!(spec.nudge_split(true, 5), 65);
+        assert_eq!(spec.nudge_split(false, -100), 90);
     }
 }
@@ -1,6 +1,10 @@
+pub mod ascii;
 pub mod components;
+pub mod dither;
 pub mod layout;
+pub mod termcolors;
 pub mod theme;
+pub mod themes;
 pub mod utils;
 pub mod widgets;
 
@@ -9,41 +13,92 @@ pub use theme::Theme;
 use crate::app::{App, ViewMode};
 use ratatui::Frame;
 
+/// Below this many rows only the one-line bar fits
+const BAR_MAX_ROWS: u16 = 6;
+
 pub fn ui(f: &mut Frame, app: &mut App) {
+    draw(f, app);
+    if app.ascii_only {
+        ascii::downgrade(f.buffer_mut());
+    }
+}
+
+fn draw(f: &mut Frame, app: &mut App) {
     let area = f.area();
 
+    app.bar_active = app.bar_mode || area.height < BAR_MAX_ROWS;
+    if app.bar_active {
+        widgets::bar::render(f, area, app);
+        return;
+    }
+
+    // Full-screen art hides everything but popups
+    if app.fullscreen_art {
+        widgets::player::art::render_fullscreen(f, area, app);
+        widgets::popups::render(f, app);
+        return;
+    }
+
     // 1. Layout
-    let main_layout = layout::get_main_layout(area);
+    let spec = app.layout;
+    let main_layout = layout::get_main_layout(area, spec.footer);
 
     // 2. Content Layout
-    let show_lyrics = app.app_show_lyrics;
-    let wide_mode = !app.is_tmux && area.width >= 90;
-
-    let content_layout =
-        layout::get_content_layout(main_layout.body_area, show_lyrics, wide_mode, area.height);
+    let content_layout = layout::get_content_layout(
+        main_layout.body_area,
+        app.app_show_lyrics,
+        app.is_tmux,
+        area.height,
+        &spec,
+    );
+    app.panes_horizontal = content_layout.is_horizontal;
 
     // 3. Render Music Card (Left)
     widgets::player::render(f, content_layout.left, app);
 
     // 4. Render Right Panel (Lyrics / Visualizer / Library / EQ)
     if let Some(right_area) = content_layout.right {
-        match app.view_mode {
-            ViewMode::Lyrics => components::lyrics::render(f, right_area, app),
-            ViewMode::Visualizer => components::visualizer::render(f, right_area, app),
-            ViewMode::Library => widgets::library::render(f, right_area, app),
-            ViewMode::EQ => components::eq::render(f, right_area, app),
+        let (first, second) = app.panel_views();
+        match second.zip(layout::get_split_panel(right_area)) {
+            Some((second, [a, b])) => {
+                render_view(f, a, app, first);
+                render_view(f, b, app, second);
+                // Dim the pane that doesn't get keys
+                let unfocused = if app.split_focus_second { a } else { b };
+                dim_border(f, unfocused, app.theme.overlay);
+            }
+            // No room for two: just the focused view
+            None => render_view(f, right_area, app, app.view_mode),
         }
     }
 
     // 5. Render Footer Hint (if no popup active)
-    if !app.show_keyhints {
+    if !app.show_keyhints && spec.footer {
         use ratatui::layout::Alignment;
         use ratatui::style::{Modifier, Style};
         use ratatui::text::{Line, Span};
         use ratatui::widgets::Paragraph;
 
         let theme = &app.theme;
-        let hint = Line::from(vec![
+        let mut spans = Vec::new();
+        // Workspace numbers, once more than one is in use
+        if app.workspaces.iter().any(Option::is_some) {
+            for n in 0..crate::app::WORKSPACES {
+                if n == app.workspace {
+                    spans.push(Span::styled(
+                        format!(" {} ", n + 1),
+                        Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+                    ));
+                } else if app.workspaces[n].is_some() {
+                    spans.push(Span::styled(
+                        format!(" {} ", n + 1),
+                        Style::default().fg(theme.overlay),
+                    ));
+                }
+            }
+            spans.push(Span::raw(" "));
+        }
+        spans.extend([
             Span::styled(
                 " ? ",
                 Style::default()
@@ -52,11 +107,75 @@ pub fn ui(f: &mut Frame, app: &mut App) {
             ),
             Span::styled("keys", Style::default().fg(theme.overlay)),
         ]);
+        let hint = Line::from(spans);
         let footer = Paragraph::new(hint).alignment(Alignment::Right);
         f.render_widget(footer, main_layout.footer_area);
     }
 
-    // 6. Render Popups (Overlays)
+    // 6. Connection banner, over the top border so nothing moves
+    if let Some(text) = app.connection.banner().filter(|_| app.is_mpd) {
+        use ratatui::layout::{Alignment, Rect};
+        use ratatui::style::{Modifier, Style};
+        use ratatui::widgets::Paragraph;
+
+        let color = match app.connection {
+            crate::app::connection::ConnectionState::Down { .. } => app.theme.red,
+            _ => app.theme.yellow,
+        };
+        let text = utils::truncate(
+            &format!(" {} ", text),
+            area.width.saturating_sub(4) as usize,
+        );
+        let width = unicode_width::UnicodeWidthStr::width(text.as_str()) as u16;
+        let banner = Rect::new(
+            area.x + area.width.saturating_sub(width) / 2,
+            area.y,
+            width,
+            1,
+        );
+        f.render_widget(
+            Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            banner,
+        );
+    }
+
+    // 7. Render Popups (Overlays)
     // Note: widgets::popups::render handles active states internally
     widgets::popups::render(f, app);
 }
+
+fn render_view(f: &mut Frame, area: ratatui::layout::Rect, app: &mut App, view: ViewMode) {
+    match view {
+        ViewMode::Lyrics => components::lyrics::render(f, area, app),
+        ViewMode::Visualizer => components::visualizer::render(f, area, app),
+        ViewMode::Library => widgets::library::render(f, area, app),
+        ViewMode::EQ => components::eq::render(f, area, app),
+        ViewMode::Info => components::info::render(f, area, app),
+        ViewMode::Related => components::related::render(f, area, app),
+    }
+}
+
+/// Recolor the border line around `area`
+fn dim_border(f: &mut Frame, area: ratatui::layout::Rect, color: ratatui::style::Color) {
+    let buf = f.buffer_mut();
+    let (right, bottom) = (
+        area.right().saturating_sub(1),
+        area.bottom().saturating_sub(1),
+    );
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let edge = y == area.top() || y == bottom || x == area.left() || x == right;
+            if !edge {
+                continue;
+            }
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                // Only the line itself; titles keep their colors
+                if matches!(cell.symbol(), "─" | "│" | "╭" | "╮" | "╰" | "╯") {
+                    cell.set_fg(color);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Palettes from outside Vyom: pywal and the terminal itself 🖥️
+//!
+//! Both give a background, a foreground and the 16 ANSI colors, which map
+//! onto `Theme` the same way. pywal's `colors.json` is re-read whenever it
+//! changes; the terminal is asked once at startup (OSC 10/11/4), because the
+//! replies arrive on stdin and the event reader owns it afterwards. It's
+//! only asked when one of these two themes is the one starting up.
+
+use super::theme::{lerp_color, Rgb, Theme};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// A terminal-style palette
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TermPalette {
+    pub background: Option<Rgb>,
+    pub foreground: Option<Rgb>,
+    /// ANSI colors 0-15
+    pub colors: [Option<Rgb>; 16],
+}
+
+impl TermPalette {
+    /// Map onto a theme; anything missing keeps the default color
+    pub fn to_theme(&self) -> Theme {
+        let default = Theme::default();
+        let rgb =
+            |c: Option<Rgb>, fallback: Color| c.map_or(fallback, |(r, g, b)| Color::Rgb(r, g, b));
+        let ansi = |i: usize, fallback: Color| rgb(self.colors[i], fallback);
+
+        let base = rgb(self.background, default.base);
+        let text = rgb(self.foreground, default.text);
+        // Bright black is the usual "comment" grey; otherwise blend one in
+        let blend = |t: f32| lerp_color(base, text, t);
+
+        Theme {
+            base,
+            surface: blend(0.15),
+            overlay: self.colors[8].map_or_else(|| blend(0.45), |(r, g, b)| Color::Rgb(r, g, b)),
+            text,
+            red: ansi(1, default.red),
+            green: ansi(2, default.green),
+            yellow: ansi(3, default.yellow),
+            blue: ansi(4, default.blue),
+            magenta: ansi(5, default.magenta),
+            cyan: ansi(6, default.cyan),
+        }
+    }
+}
+
+// --- pywal ---
+
+#[derive(Deserialize)]
+struct WalFile {
+    special: HashMap<String, String>,
+    colors: HashMap<String, String>,
+}
+
+/// pywal's `colors.json`: in `$PYWAL_CACHE_DIR`, else `~/.cache/wal` (on
+/// every platform, not the OS cache directory)
+pub fn wal_colors_path() -> PathBuf {
+    std::env::var_os("PYWAL_CACHE_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cache").join("wal")))
+        .unwrap_or_else(|| PathBuf::from(".cache/wal"))
+        .join("colors.json")
+}
+
+/// Parse pywal's `colors.json`
+pub fn parse_wal(json: &str) -> Result<TermPalette, String> {
+    let wal: WalFile = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let mut palette = TermPalette {
+        background: wal.special.get("background").and_then(|c| parse_hex(c)),
+        foreground: wal.special.get("foreground").and_then(|c| parse_hex(c)),
+        ..Default::default()
+    };
+    for (i, slot) in palette.colors.iter_mut().enumerate() {
+        *slot = wal
+            .colors
+            .get(&format!("color{}", i))
+            .and_then(|c| parse_hex(c));
+    }
+    Ok(palette)
+}
+
+pub fn load_wal() -> Result<TermPalette, String> {
+    let path = wal_colors_path();
+    let json =
+        std::fs::read_to_string(&path).map_err(|e| format!("pywal: {} ({})", e, path.display()))?;
+    parse_wal(&json).map_err(|e| format!("pywal: {}", e))
+}
+
+fn parse_hex(s: &str) -> Option<Rgb> {
+    let hex = s.trim().strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let v = u32::from_str_radix(hex, 16).ok()?;
+    Some(((v >> 16) as u8, (v >> 8) as u8, v as u8))
+}
+
+// --- Terminal (OSC) ---
+
+static TERMINAL_PALETTE: OnceLock<TermPalette> = OnceLock::new();
+
+/// Palette reported by the terminal at startup, if it answered
+pub fn terminal_palette() -> Option<&'static TermPalette> {
+    TERMINAL_PALETTE.get()
+}
+
+/// Ask the terminal for its colors. Needs raw mode and must run before
+/// anything else reads stdin. Ends on the DA1 reply every terminal sends,
+/// so terminals that ignore OSC queries cost one round trip, not the timeout.
+#[cfg(unix)]
+pub fn query_terminal(timeout: Duration) {
+    use std::io::Write;
+
+    // SAFETY: isatty only inspects the descriptor
+    if unsafe { libc::isatty(libc::STDIN_FILENO) } != 1 {
+        return;
+    }
+
+    let mut query = String::from("\x1b]10;?\x07\x1b]11;?\x07");
+    for i in 0..16 {
+        query.push_str(&format!("\x1b]4;{};?\x07", i));
+    }
+    query.push_str("\x1b[c");
+    let mut out = std::io::stdout();
+    if out
+        .write_all(query.as_bytes())
+        .and_then(|_| out.flush())
+        .is_err()
+    {
+        return;
+    }
+
+    let deadline = std::time::Instant::now() + timeout;
+    let mut reply = Vec::new();
+    let mut buf = [0u8; 512];
+    loop {
+        let left = deadline.saturating_duration_since(std::time::Instant::now());
+        if left.is_zero() {
+            break;
+        }
+        let mut fds = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: one valid pollfd, and buf outlives the read
+        let ready = unsafe { libc::poll(&mut fds, 1, left.as_millis() as i32) };
+        if ready <= 0 {
+            break;
+        }
+        let n = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr().cast(), buf.len()) };
+        if n <= 0 {
+            break;
+        }
+        reply.extend_from_slice(&buf[..n as usize]);
+        if has_da1(&reply) {
+            break;
+        }
+    }
+
+    let palette = parse_osc_replies(&String::from_utf8_lossy(&reply));
+    if palette != TermPalette::default() {
+        let _ = TERMINAL_PALETTE.set(palette);
+    }
+}
+
+#[cfg(not(unix))]
+pub fn query_terminal(_timeout: Duration) {}
+
+/// Complete `ESC [ ? <digits;...> c` somewhere in the reply
+fn has_da1(reply: &[u8]) -> bool {
+    reply.windows(3).enumerate().any(|(i, w)| {
+        w == b"\x1b[?"
+            && reply[i + 3..]
+                .iter()
+                .find(|b| !(b.is_ascii_digit() || **b == b';'))
+                == Some(&b'c')
+    })
+}
+
+/// Collect `OSC 10/11/4` color replies (BEL or ST terminated)
+pub fn parse_osc_replies(reply: &str) -> TermPalette {
+    let mut palette = TermPalette::default();
+    for chunk in reply.split("\x1b]").skip(1) {
+        let body = chunk.split(['\x07', '\x1b']).next().unwrap_or_default();
+        let mut parts = body.split(';');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("10"), Some(spec), None) => palette.foreground = parse_rgb_spec(spec),
+            (Some("11"), Some(spec), None) => palette.background = parse_rgb_spec(spec),
+            (Some("4"), Some(idx), Some(spec)) => {
+                if let Some(slot) = idx
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| palette.colors.get_mut(i))
+                {
+                    *slot = parse_rgb_spec(spec);
+                }
+            }
+            _ => {}
+        }
+    }
+    palette
+}
+
+/// X11 color spec `rgb:R/G/B` with 1-4 hex digits per channel
+fn parse_rgb_spec(spec: &str) -> Option<Rgb> {
+    let rest = spec.strip_prefix("rgb:")?;
+    let mut channels = rest.split('/').map(|c| {
+        if c.is_empty() || c.len() > 4 {
+            return None;
+        }
+        let max = 16u32.pow(c.len() as u32) - 1;
+        let v = u32::from_str_radix(c, 16).ok()?;
+        Some(((v * 255 + max / 2) / max) as u8)
+    });
+    Some((channels.next()??, channels.next()??, channels.next()??))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wal_maps_onto_theme() {
+        let json = r##"{
+            "wallpaper": "/x.png",
+            "special": {"background": "#101010", "foreground": "#F0F0F0", "cursor": "#F0F0F0"},
+            "colors": {"color0": "#101010", "color1": "#AA0000", "color4": "#0000AA", "color8": "#555555"}
+        }"##;
+        let theme = parse_wal(json).unwrap().to_theme();
+        assert_eq!(theme.base, Color::Rgb(16, 16, 16));
+        assert_eq!(theme.text, Color::Rgb(240, 240, 240));
+        assert_eq!(theme.red, Color::Rgb(170, 0, 0));
+        assert_eq!(theme.blue, Color::Rgb(0, 0, 170));
+        assert_eq!(theme.overlay, Color::Rgb(85, 85, 85));
+        // Missing colors keep the defaults
+        assert_eq!(theme.green, Theme::default().green);
+        assert!(parse_wal("{}").is_err());
+    }
+
+    #[test]
+    fn test_osc_replies_parse_with_bel_and_st() {
+        let reply = "\x1b]11;rgb:1e1e/1e1e/2e2e\x07\x1b]10;rgb:ff/80/00\x1b\\\
+                     \x1b]4;1;rgb:f3f3/8b8b/a8a8\x07\x1b[?62;22c";
+        let palette = parse_osc_replies(reply);
+        assert_eq!(palette.background, Some((30, 30, 46)));
+        assert_eq!(palette.foreground, Some((255, 128, 0)));
+        assert_eq!(palette.colors[1], Some((243, 139, 168)));
+        assert_eq!(palette.colors[2], None);
+        assert!(has_da1(reply.as_bytes()));
+        assert!(!has_da1(b"\x1b[?62;2"));
+        assert_eq!(parse_rgb_spec("rgb://"), None);
+    }
+}
@@ -1,6 +1,12 @@
+use image::DynamicImage;
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::time::{Duration, Instant};
+
+/// How long accent colors take to blend to a new album's palette
+const THEME_FADE: Duration = Duration::from_millis(600);
+
+pub type Rgb = (u8, u8, u8);
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(default)]
@@ -34,54 +40,241 @@ impl Default for Theme {
     }
 }
 
-// Helper for serialization/deserialization
-#[derive(Serialize, Deserialize)]
-struct ThemeFile {
-    theme: Theme,
+impl Theme {
+    /// Copy of this theme with the accent colors (blue, magenta, cyan) taken
+    /// from an album palette. Semantic colors (red/green/yellow) and the
+    /// base/surface/text tones are kept so status colors still read correctly.
+    pub fn with_accents(&self, palette: &[Rgb]) -> Theme {
+        // Skip greys and near-blacks: they make poor accents
+        let vivid: Vec<(f32, f32, f32)> = palette
+            .iter()
+            .map(|&c| rgb_to_hsl(c))
+            .filter(|&(_, s, l)| s >= 0.25 && (0.12..=0.92).contains(&l))
+            .collect();
+        let Some(&(h0, s0, l0)) = vivid.first() else {
+            return self.clone();
+        };
+
+        // Lift into a range that stays readable on a dark background
+        let accent = |i: usize| {
+            let (h, s, l) =
+                vivid
+                    .get(i)
+                    .copied()
+                    .unwrap_or(((h0 + 40.0 * i as f32) % 360.0, s0, l0));
+            let (r, g, b) = hsl_to_rgb(h, s.max(0.45), l.clamp(0.62, 0.80));
+            Color::Rgb(r, g, b)
+        };
+
+        Theme {
+            blue: accent(0),
+            magenta: accent(1),
+            cyan: accent(2),
+            ..self.clone()
+        }
+    }
+
+    /// Blend towards `other` (`t` = 0.0 → self, 1.0 → other)
+    pub fn lerp(&self, other: &Theme, t: f32) -> Theme {
+        let mix = |a: Color, b: Color| lerp_color(a, b, t);
+        Theme {
+            base: mix(self.base, other.base),
+            surface: mix(self.surface, other.surface),
+            overlay: mix(self.overlay, other.overlay),
+            text: mix(self.text, other.text),
+            red: mix(self.red, other.red),
+            green: mix(self.green, other.green),
+            yellow: mix(self.yellow, other.yellow),
+            blue: mix(self.blue, other.blue),
+            magenta: mix(self.magenta, other.magenta),
+            cyan: mix(self.cyan, other.cyan),
+        }
+    }
+}
+
+/// Smooth transition between two themes
+#[derive(Clone, Debug)]
+pub struct ThemeFade {
+    from: Theme,
+    to: Theme,
+    started: Instant,
 }
 
-pub fn get_theme_path() -> std::path::PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    std::path::PathBuf::from(&home).join(".config/vyom/theme.toml")
+impl ThemeFade {
+    pub fn new(from: Theme, to: Theme, now: Instant) -> Self {
+        Self {
+            from,
+            to,
+            started: now,
+        }
+    }
+
+    /// Theme at `now`, and whether the fade has finished
+    pub fn sample(&self, now: Instant) -> (Theme, bool) {
+        let t = now.duration_since(self.started).as_secs_f32() / THEME_FADE.as_secs_f32();
+        if t >= 1.0 {
+            (self.to.clone(), true)
+        } else {
+            // Ease-out so the change settles gently
+            let eased = 1.0 - (1.0 - t).powi(3);
+            (self.from.lerp(&self.to, eased), false)
+        }
+    }
 }
 
-pub fn load_current_theme() -> Theme {
-    let path = get_theme_path();
+pub(crate) fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    match (a, b) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let mix = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        // Named/indexed colors can't be blended; switch halfway
+        _ if t < 0.5 => a,
+        _ => b,
+    }
+}
 
-    if path.exists() {
-        if let Ok(content) = fs::read_to_string(&path) {
-            // Try parsing as nested [theme] first (Theme Selector format)
-            if let Ok(wrapper) = toml::from_str::<ThemeFile>(&content) {
-                return wrapper.theme;
+/// Dominant colors of an image, most common first (k-means in RGB) 🎨
+pub fn dominant_palette(img: &DynamicImage, k: usize) -> Vec<Rgb> {
+    let small = img.thumbnail(48, 48).to_rgb8();
+    let pixels: Vec<[f32; 3]> = small
+        .pixels()
+        .map(|p| [p[0] as f32, p[1] as f32, p[2] as f32])
+        .collect();
+    if pixels.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    // Deterministic seeds spread over the brightness range
+    let luma = |p: &[f32; 3]| 0.299 * p[0] + 0.587 * p[1] + 0.114 * p[2];
+    let mut sorted = pixels.clone();
+    sorted.sort_by(|a, b| luma(a).total_cmp(&luma(b)));
+    let mut centroids: Vec<[f32; 3]> = (0..k)
+        .map(|i| sorted[(2 * i + 1) * sorted.len() / (2 * k)])
+        .collect();
+
+    let dist = |a: &[f32; 3], b: &[f32; 3]| (0..3).map(|i| (a[i] - b[i]).powi(2)).sum::<f32>();
+    let mut counts = vec![0usize; k];
+    for _ in 0..12 {
+        let mut sums = vec![[0f32; 3]; k];
+        counts = vec![0; k];
+        for p in &pixels {
+            let nearest = (0..k)
+                .min_by(|&a, &b| dist(p, &centroids[a]).total_cmp(&dist(p, &centroids[b])))
+                .unwrap_or(0);
+            for c in 0..3 {
+                sums[nearest][c] += p[c];
             }
-            // Fallback: Try parsing as flat file (Manual/Legacy format)
-            if let Ok(theme) = toml::from_str::<Theme>(&content) {
-                return theme;
+            counts[nearest] += 1;
+        }
+        for i in 0..k {
+            if counts[i] > 0 {
+                centroids[i] = sums[i].map(|s| s / counts[i] as f32);
             }
         }
+    }
+
+    let mut order: Vec<usize> = (0..k).filter(|&i| counts[i] > 0).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(counts[i]));
+    order
+        .into_iter()
+        .map(|i| {
+            let [r, g, b] = centroids[i];
+            (r.round() as u8, g.round() as u8, b.round() as u8)
+        })
+        .collect()
+}
+
+/// RGB → (hue degrees, saturation 0-1, lightness 0-1)
+fn rgb_to_hsl((r, g, b): Rgb) -> (f32, f32, f32) {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d < f32::EPSILON {
+        return (0.0, 0.0, l);
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        60.0 * ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / d + 2.0)
     } else {
-        // Auto-create default theme file if it doesn't exist
-        let default_theme = Theme::default();
-        let wrapper = ThemeFile {
-            theme: default_theme.clone(),
-        };
+        60.0 * ((r - g) / d + 4.0)
+    };
+    (h, s, l)
+}
 
-        // Ensure directory exists
-        if let Some(parent) = path.parent() {
-            if let Err(e) = fs::create_dir_all(parent) {
-                tracing::warn!("Failed to create theme cache dir: {}", e);
-            }
-        }
+fn hsl_to_rgb(h: f32, s: f32, l: f32) -> Rgb {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let m = l - c / 2.0;
+    let (r, g, b) = match (h.rem_euclid(360.0) / 60.0) as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let to_u8 = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (to_u8(r), to_u8(g), to_u8(b))
+}
 
-        // Write to file
-        if let Ok(toml_str) = toml::to_string_pretty(&wrapper) {
-            if let Err(e) = fs::write(&path, toml_str) {
-                tracing::warn!("Failed to write default theme: {}", e);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dominant_palette_orders_by_coverage() {
+        // Three quarters red, one quarter blue
+        let img = image::RgbImage::from_fn(40, 40, |x, _| {
+            if x < 30 {
+                image::Rgb([220, 30, 30])
+            } else {
+                image::Rgb([20, 40, 200])
             }
-        }
+        });
+        let palette = dominant_palette(&DynamicImage::ImageRgb8(img), 3);
+        let (r, _, b) = palette[0];
+        assert!(r > 200 && b < 50, "Red dominates: {:?}", palette);
+        assert!(palette.iter().any(|&(r, _, b)| b > 180 && r < 40));
+    }
+
+    #[test]
+    fn test_accents_stay_readable_and_keep_semantics() {
+        let base = Theme::default();
+        // A very dark navy should be lifted, greys ignored
+        let themed = base.with_accents(&[(128, 128, 128), (10, 20, 90)]);
+        let Color::Rgb(r, g, b) = themed.blue else {
+            panic!("Expected RGB accent");
+        };
+        let (_, _, l) = rgb_to_hsl((r, g, b));
+        assert!(l >= 0.6, "Lightness {} too dark", l);
+        assert!(b > r && b > g, "Still blue-ish: {:?}", (r, g, b));
+        assert_eq!(themed.red, base.red);
+        assert_eq!(themed.base, base.base);
 
-        return default_theme;
+        // Nothing colorful: theme unchanged
+        assert_eq!(base.with_accents(&[(0, 0, 0)]).blue, base.blue);
     }
 
-    Theme::default()
+    #[test]
+    fn test_fade_reaches_target() {
+        let from = Theme::default();
+        let to = Theme {
+            blue: Color::Rgb(255, 0, 0),
+            ..Theme::default()
+        };
+        let t0 = Instant::now();
+        let fade = ThemeFade::new(from.clone(), to.clone(), t0);
+        let (mid, done) = fade.sample(t0 + THEME_FADE / 2);
+        assert!(!done);
+        assert_ne!(mid.blue, from.blue);
+        assert_ne!(mid.blue, to.blue);
+        let (end, done) = fade.sample(t0 + THEME_FADE);
+        assert!(done);
+        assert_eq!(end.blue, to.blue);
+    }
 }
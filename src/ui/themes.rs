@@ -0,0 +1,186 @@
+//! Theme files and the bundled theme gallery 🎨
+//!
+//! A theme is a flat TOML file with one `#RRGGBB` (or named) color per `Theme`
+//! field. Files in `~/.config/vyom/themes/` are picked up by name and shadow
+//! the built-ins, so copying a bundled theme there is the way to tweak it.
+
+use super::termcolors;
+use super::theme::Theme;
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Used when nothing is configured
+pub const DEFAULT_THEME: &str = "catppuccin-mocha";
+
+/// Follows pywal's `colors.json`
+pub const PYWAL_THEME: &str = "pywal";
+/// The palette the terminal reported at startup
+pub const TERMINAL_THEME: &str = "terminal";
+
+/// Themes compiled into the binary
+const BUILTIN: &[(&str, &str)] = &[
+    (
+        "catppuccin-mocha",
+        include_str!("../../assets/themes/catppuccin-mocha.toml"),
+    ),
+    (
+        "catppuccin-macchiato",
+        include_str!("../../assets/themes/catppuccin-macchiato.toml"),
+    ),
+    (
+        "catppuccin-frappe",
+        include_str!("../../assets/themes/catppuccin-frappe.toml"),
+    ),
+    (
+        "catppuccin-latte",
+        include_str!("../../assets/themes/catppuccin-latte.toml"),
+    ),
+    (
+        "gruvbox-dark",
+        include_str!("../../assets/themes/gruvbox-dark.toml"),
+    ),
+    ("nord", include_str!("../../assets/themes/nord.toml")),
+    ("dracula", include_str!("../../assets/themes/dracula.toml")),
+    (
+        "tokyo-night",
+        include_str!("../../assets/themes/tokyo-night.toml"),
+    ),
+    (
+        "rose-pine",
+        include_str!("../../assets/themes/rose-pine.toml"),
+    ),
+];
+
+/// Old external theme-selector format (`[theme]` table)
+#[derive(Deserialize)]
+struct ThemeFile {
+    theme: Theme,
+}
+
+/// `~/.config/vyom/themes`
+pub fn themes_dir() -> PathBuf {
+    crate::app::config::AppConfig::get_config_dir().join("themes")
+}
+
+/// `~/.config/vyom/theme.toml`, still honored when no `theme` is set
+pub fn legacy_theme_path() -> PathBuf {
+    crate::app::config::AppConfig::get_config_dir().join("theme.toml")
+}
+
+/// Parse a theme file (flat, or the old `[theme]` table)
+pub fn parse(content: &str) -> Result<Theme, String> {
+    if let Ok(wrapper) = toml::from_str::<ThemeFile>(content) {
+        return Ok(wrapper.theme);
+    }
+    toml::from_str::<Theme>(content).map_err(|e| e.message().to_string())
+}
+
+/// Theme by name: pywal / terminal, a user file, then the built-ins
+pub fn load(name: &str) -> Result<Theme, String> {
+    match name {
+        PYWAL_THEME => return termcolors::load_wal().map(|p| p.to_theme()),
+        TERMINAL_THEME => {
+            return termcolors::terminal_palette()
+                .map(|p| p.to_theme())
+                .ok_or_else(|| "The terminal didn't report its colors".to_string())
+        }
+        _ => {}
+    }
+    let path = themes_dir().join(format!("{}.toml", name));
+    if let Ok(content) = fs::read_to_string(&path) {
+        return parse(&content).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+    BUILTIN
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, content)| parse(content))
+        .unwrap_or_else(|| Err(format!("Unknown theme \"{}\"", name)))
+}
+
+/// The theme to start with: `name` if given, else the legacy `theme.toml`,
+/// else the default. Errors fall back to the default and are returned too.
+pub fn resolve(name: Option<&str>) -> (Theme, Option<String>) {
+    let result = match name {
+        Some(name) => load(name),
+        None => match fs::read_to_string(legacy_theme_path()) {
+            Ok(content) => parse(&content),
+            Err(_) => load(DEFAULT_THEME),
+        },
+    };
+    match result {
+        Ok(theme) => (theme, None),
+        Err(e) => {
+            tracing::warn!("Theme load failed: {}", e);
+            (Theme::default(), Some(e))
+        }
+    }
+}
+
+/// Every selectable theme name (built-ins and user files), sorted
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN.iter().map(|(n, _)| n.to_string()).collect();
+    if let Ok(entries) = fs::read_dir(themes_dir()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "toml") {
+                if let Some(stem) = path.file_stem() {
+                    names.push(stem.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    if termcolors::wal_colors_path().exists() {
+        names.push(PYWAL_THEME.to_string());
+    }
+    if termcolors::terminal_palette().is_some() {
+        names.push(TERMINAL_THEME.to_string());
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    #[test]
+    fn test_builtins_parse_and_define_every_color() {
+        for (name, content) in BUILTIN {
+            let theme = parse(content).unwrap_or_else(|e| panic!("{}: {}", name, e));
+            // No field may silently fall back to the Mocha default
+            for key in [
+                "base", "surface", "overlay", "text", "red", "green", "yellow", "blue", "magenta",
+                "cyan",
+            ] {
+                assert!(
+                    content
+                        .lines()
+                        .any(|l| l.starts_with(&format!("{} =", key))),
+                    "{} misses {}",
+                    name,
+                    key
+                );
+            }
+            assert!(matches!(theme.base, Color::Rgb(..)));
+        }
+        assert_eq!(
+            parse(BUILTIN[0].1).unwrap().blue,
+            Theme::default().blue,
+            "Default theme matches the bundled Mocha"
+        );
+    }
+
+    #[test]
+    fn test_parse_accepts_legacy_table_and_partial_files() {
+        let legacy = "[theme]\nblue = \"#010203\"\n";
+        assert_eq!(parse(legacy).unwrap().blue, Color::Rgb(1, 2, 3));
+        let partial = parse("red = \"#FF0000\"").unwrap();
+        assert_eq!(partial.red, Color::Rgb(255, 0, 0));
+        assert_eq!(partial.text, Theme::default().text);
+        assert!(parse("red = 12").is_err());
+        assert!(load("no-such-theme").is_err());
+    }
+}
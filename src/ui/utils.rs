@@ -1,13 +1,122 @@
-/// Safely truncate string to max characters, appending "…" if truncated 🛡️
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::widgets::{Clear, Widget};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Blank `area` for a popup, even over a Kitty image: ratatui-image marks
+/// the cells under an image as skipped, so without un-skipping them the
+/// popup would draw underneath the picture
+pub fn clear_popup_area(buf: &mut Buffer, area: Rect) {
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_skip(false);
+                cell.set_char(' ');
+                cell.set_bg(Color::Reset);
+                cell.set_fg(Color::Reset);
+            }
+        }
+    }
+    Clear.render(area, buf);
+}
+
+/// Cut `s` to at most `max_width` terminal columns, ending in "…" if cut 🛡️
+/// Goes by grapheme, so wide CJK characters, emoji sequences and accents
+/// are never split and a double-width character never overhangs.
 pub fn truncate(s: &str, max_width: usize) -> String {
-    let chars: Vec<char> = s.chars().collect();
-    if chars.len() > max_width {
-        chars
-            .into_iter()
-            .take(max_width.saturating_sub(1))
-            .collect::<String>()
-            + "…"
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+    let room = max_width.saturating_sub(1);
+    let mut used = 0;
+    let mut out = String::new();
+    for g in s.graphemes(true) {
+        let w = g.width();
+        if used + w > room {
+            break;
+        }
+        used += w;
+        out.push_str(g);
+    }
+    if max_width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// `truncate`, then padded with spaces to exactly `width` columns, for rows
+/// whose columns must line up (`{:width$}` pads by chars, not columns)
+pub fn fit(s: &str, width: usize) -> String {
+    let mut out = truncate(s, width);
+    let pad = width.saturating_sub(out.width());
+    out.extend(std::iter::repeat_n(' ', pad));
+    out
+}
+
+/// `width` characters of `text`, scrolled left by `step` and looping with a
+/// gap. Text that fits is returned as is.
+pub fn marquee(text: &str, width: usize, step: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= width {
+        return text.to_string();
+    }
+    let looped: Vec<char> = chars.into_iter().chain("   ".chars()).collect();
+    looped
+        .iter()
+        .cycle()
+        .skip(step % looped.len())
+        .take(width)
+        .collect()
+}
+
+/// Rough length for summaries: `38 min`, `5h12m`
+pub fn short_duration(ms: u64) -> String {
+    let min = ms / 60_000;
+    if min < 60 {
+        format!("{} min", min)
     } else {
-        s.to_string()
+        format!("{}h{:02}m", min / 60, min % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_counts_columns_not_bytes() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("abcdef", 4), "abc…");
+        // Double-width: never half a character, never past the limit
+        assert_eq!(truncate("東京事変", 6), "東京…");
+        assert_eq!(truncate("東京事変", 5), "東京…");
+        assert_eq!(truncate("東京事変", 4), "東…");
+        // Accents and emoji sequences stay whole
+        assert_eq!(truncate("e\u{301}e\u{301}e\u{301}", 2), "e\u{301}…");
+        assert_eq!(truncate("👩‍👩‍👧 family", 3), "👩‍👩‍👧…");
+        assert_eq!(truncate("abc", 0), "");
+
+        assert_eq!(fit("東京事変", 5), "東京…");
+        assert_eq!(fit("東京", 6), "東京  ");
+        assert_eq!(fit("東京事変", 7).width(), 7);
+    }
+
+    #[test]
+    fn test_marquee_scrolls_and_loops() {
+        assert_eq!(marquee("short", 10, 7), "short");
+        assert_eq!(marquee("abcdef", 4, 0), "abcd");
+        assert_eq!(marquee("abcdef", 4, 4), "ef  ");
+        // Wraps around after the text and its gap
+        assert_eq!(marquee("abcdef", 4, 9), "abcd");
+    }
+
+    #[test]
+    fn test_short_duration() {
+        assert_eq!(short_duration(59_000), "0 min");
+        assert_eq!(short_duration(38 * 60_000 + 30_000), "38 min");
+        assert_eq!(short_duration(312 * 60_000), "5h12m");
+        assert_eq!(short_duration(60 * 60_000), "1h00m");
     }
 }
@@ -0,0 +1,76 @@
+use crate::app::App;
+use crate::player::PlayerState;
+use crate::ui::utils::marquee;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// Time per marquee step
+const SCROLL_STEP_MS: u128 = 240;
+
+/// One-line now playing bar: state, scrolling `artist – title`, elapsed / total
+pub fn render(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    // Middle row when there are a few to spare
+    let row = Rect::new(
+        area.x,
+        area.y + area.height / 2,
+        area.width,
+        1.min(area.height),
+    );
+
+    let Some(track) = &app.track else {
+        let idle = Paragraph::new(Span::styled(
+            " ■ Nothing playing",
+            Style::default().fg(theme.overlay),
+        ));
+        f.render_widget(idle, row);
+        return;
+    };
+
+    let (icon, icon_color) = match track.state {
+        PlayerState::Playing => ("▶", theme.green),
+        PlayerState::Paused => ("⏸", theme.yellow),
+        PlayerState::Stopped => ("■", theme.overlay),
+    };
+    let time = format!(
+        "  {} / {} ",
+        fmt_ms(app.get_current_position_ms()),
+        fmt_ms(track.duration_ms)
+    );
+
+    let label = if track.artist.is_empty() {
+        track.name.clone()
+    } else {
+        format!("{} – {}", track.artist, track.name)
+    };
+    let room = (row.width as usize).saturating_sub(3 + time.chars().count());
+    // Only move while playing, so a paused bar stays readable
+    let step = if track.state == PlayerState::Playing {
+        (app.started.elapsed().as_millis() / SCROLL_STEP_MS) as usize
+    } else {
+        0
+    };
+    let text = marquee(&label, room, step);
+    let pad = room.saturating_sub(text.chars().count());
+
+    let line = Line::from(vec![
+        Span::styled(
+            format!(" {} ", icon),
+            Style::default().fg(icon_color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(text, Style::default().fg(theme.text)),
+        Span::raw(" ".repeat(pad)),
+        Span::styled(time, Style::default().fg(theme.overlay)),
+    ]);
+    f.render_widget(Paragraph::new(line), row);
+}
+
+fn fmt_ms(ms: u64) -> String {
+    let s = ms / 1000;
+    format!("{}:{:02}", s / 60, s % 60)
+}
@@ -0,0 +1,220 @@
+use crate::app::{AlbumEntry, App, ArtworkState};
+use crate::t;
+use crate::ui::utils::{fit, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+/// Grid tile: a 12x6 cover, album and artist lines, and a gap row
+const TILE_W: u16 = 14;
+const TILE_H: u16 = 9;
+const ART_W: u16 = 12;
+const ART_H: u16 = 6;
+
+/// Covers are kept at the size they are drawn at (half-blocks: 2 pixels per row)
+pub const THUMB_PX: u32 = ART_W as u32 * 2;
+
+/// Section title, plus the open album's tracks (the grid itself is drawn by `render_grid`)
+pub fn render(app: &App, width: usize, lines: &mut Vec<Line>) -> Vec<ListItem<'static>> {
+    let theme = &app.theme;
+    let cream = theme.yellow;
+    let green = theme.green;
+    let muted = theme.overlay;
+    let grid = theme.surface;
+
+    // ━━━ CENTERED TITLE ━━━
+    lines.push(Line::from(""));
+    let title = match &app.album_open {
+        Some(album) => format!("  ALBUM  ·  {}  ", album.name),
+        None => format!("  ALBUMS  ·  {}  ", app.albums.len()),
+    };
+    lines.push(
+        Line::from(Span::styled(title, Style::default().fg(cream))).alignment(Alignment::Center),
+    );
+    lines.push(Line::from(""));
+
+    let Some(_) = app.album_open else {
+        if app.albums.is_empty() {
+            lines.push(
+                Line::from(Span::styled(
+                    t!("library.no_albums"),
+                    Style::default().fg(muted),
+                ))
+                .alignment(Alignment::Center),
+            );
+            lines.push(
+                Line::from(Span::styled(
+                    t!("library.no_albums_hint"),
+                    Style::default().fg(grid),
+                ))
+                .alignment(Alignment::Center),
+            );
+        }
+        return Vec::new();
+    };
+
+    // ━━━ TRACKS ━━━
+    let time_w = 6;
+    let title_w = width.saturating_sub(time_w + 10);
+    app.library_items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let is_sel = idx == app.library_selected;
+            let name = truncate(&item.name, title_w.saturating_sub(1));
+            let time = item
+                .duration_ms
+                .map(|ms| {
+                    let s = ms / 1000;
+                    format!("{}:{:02}", s / 60, s % 60)
+                })
+                .unwrap_or_default();
+
+            let (marker, m_color, t_style) = if is_sel {
+                (
+                    "●",
+                    cream,
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("○", grid, Style::default().fg(theme.text))
+            };
+
+            // Marked for batch tag editing (`v`)
+            let (marker, m_color) = if item.path.as_deref().is_some_and(|p| app.is_marked(p)) {
+                ("◆", theme.magenta)
+            } else {
+                (marker, m_color)
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {} ", marker), Style::default().fg(m_color)),
+                Span::styled(
+                    format!("{:>2}  ", idx + 1),
+                    Style::default().fg(if is_sel { green } else { muted }),
+                ),
+                Span::styled(fit(&name, title_w), t_style),
+                Span::styled(
+                    format!("{:>time_w$}", time, time_w = time_w),
+                    Style::default().fg(muted),
+                ),
+            ]))
+        })
+        .collect()
+}
+
+/// Cover grid, scrolled by whole rows to keep the selected tile on screen
+pub fn render_grid(f: &mut Frame, area: Rect, app: &mut App) {
+    // Last column is the scrollbar's
+    let cols = (area.width.saturating_sub(1) / TILE_W).max(1) as usize;
+    let rows = (area.height / TILE_H) as usize;
+    app.album_cols = cols;
+    app.library_page = cols * rows.max(1);
+    if rows == 0 || app.albums.is_empty() {
+        return;
+    }
+
+    let selected = app.library_selected.min(app.albums.len() - 1);
+    let sel_row = selected / cols;
+    let total_rows = app.albums.len().div_ceil(cols);
+    if sel_row < app.album_top_row {
+        app.album_top_row = sel_row;
+    } else if sel_row >= app.album_top_row + rows {
+        app.album_top_row = sel_row + 1 - rows;
+    }
+    app.album_top_row = app.album_top_row.min(total_rows.saturating_sub(rows));
+
+    let app: &App = app;
+    let grid_w = cols as u16 * TILE_W;
+    let x0 = area.x + area.width.saturating_sub(1).saturating_sub(grid_w) / 2;
+    let first = app.album_top_row * cols;
+    for (i, album) in app.albums.iter().enumerate().skip(first).take(rows * cols) {
+        let slot = (i - first) as u16;
+        let tile = Rect::new(
+            x0 + (slot % cols as u16) * TILE_W,
+            area.y + (slot / cols as u16) * TILE_H,
+            TILE_W,
+            TILE_H,
+        );
+        render_tile(f, tile, app, album, i == selected);
+    }
+
+    if total_rows > rows {
+        let mut scroll = ScrollbarState::new(total_rows - rows + 1).position(app.album_top_row);
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .track_symbol(Some("│"))
+                .track_style(Style::default().fg(app.theme.surface))
+                .thumb_symbol("┃")
+                .thumb_style(Style::default().fg(app.theme.overlay)),
+            area,
+            &mut scroll,
+        );
+    }
+}
+
+fn render_tile(f: &mut Frame, tile: Rect, app: &App, album: &AlbumEntry, is_sel: bool) {
+    let theme = &app.theme;
+    let art_area = Rect::new(tile.x + 1, tile.y, ART_W, ART_H);
+
+    match app.album_art.get(&album.file) {
+        Some(ArtworkState::Loaded(img)) => {
+            let lines =
+                crate::ui::widgets::player::art::render_block(img, art_area, app.art_quality);
+            f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), art_area);
+        }
+        state => {
+            let glyph = if matches!(state, Some(ArtworkState::Loading)) {
+                "…"
+            } else {
+                "♪"
+            };
+            let mut lines = vec![Line::default(); (ART_H / 2) as usize];
+            lines.push(Line::from(glyph));
+            f.render_widget(
+                Paragraph::new(lines)
+                    .alignment(Alignment::Center)
+                    .style(Style::default().fg(theme.overlay).bg(theme.surface)),
+                art_area,
+            );
+        }
+    }
+
+    let (name_style, artist_style) = if is_sel {
+        (
+            Style::default()
+                .fg(theme.yellow)
+                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.text),
+        )
+    } else {
+        (
+            Style::default().fg(theme.text),
+            Style::default().fg(theme.overlay),
+        )
+    };
+    let caption_w = ART_W as usize;
+    let mut caption = vec![
+        Line::from(Span::styled(truncate(&album.name, caption_w), name_style)),
+        Line::from(Span::styled(
+            truncate(&album.artist, caption_w),
+            artist_style,
+        )),
+    ];
+    if is_sel {
+        caption.push(Line::from(Span::styled(
+            "━".repeat(caption_w),
+            Style::default().fg(theme.yellow),
+        )));
+    }
+    f.render_widget(
+        Paragraph::new(caption).alignment(Alignment::Center),
+        Rect::new(tile.x + 1, tile.y + ART_H, ART_W, TILE_H - ART_H),
+    );
+}
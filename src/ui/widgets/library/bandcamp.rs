@@ -0,0 +1,83 @@
+use crate::app::bandcamp::CollectionState;
+use crate::app::App;
+use crate::t;
+use crate::ui::utils::{fit, truncate};
+use ratatui::{
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::ListItem,
+};
+
+pub fn render(app: &App, width: usize, lines: &mut Vec<Line>) -> Vec<ListItem<'static>> {
+    let theme = &app.theme;
+
+    let artist_w = width / 3;
+    let title_w = width.saturating_sub(artist_w + 8);
+
+    let cyan = theme.cyan;
+    let cream = theme.yellow;
+    let muted = theme.overlay;
+    let grid = theme.surface;
+
+    // ━━━ CENTERED TITLE ━━━
+    lines.push(Line::from(""));
+    let purchases = app.bandcamp.purchases();
+    lines.push(
+        Line::from(Span::styled(
+            format!("  BANDCAMP  ·  {} purchased  ", purchases.len()),
+            Style::default().fg(cyan),
+        ))
+        .alignment(Alignment::Center),
+    );
+    lines.push(Line::from(""));
+
+    // ━━━ CONTENT ━━━
+    let status = match &app.bandcamp {
+        _ if app.bandcamp_identity.is_none() => Some(t!("bandcamp.no_identity")),
+        CollectionState::Idle | CollectionState::Loading => Some(t!("bandcamp.listing")),
+        CollectionState::Loaded(purchases) if purchases.is_empty() => Some(t!("bandcamp.empty")),
+        CollectionState::Loaded(_) => None,
+        CollectionState::Failed(e) => Some(t!("bandcamp.failed", error = e)),
+    };
+    if let Some(status) = status {
+        lines.push(
+            Line::from(Span::styled(status, Style::default().fg(muted)))
+                .alignment(Alignment::Center),
+        );
+    }
+
+    purchases
+        .iter()
+        .enumerate()
+        .map(|(idx, purchase)| {
+            let is_sel = idx == app.library_selected;
+            let (marker, m_color, t_style, a_style) = if is_sel {
+                (
+                    "●",
+                    cream,
+                    Style::default().fg(cyan).add_modifier(Modifier::BOLD),
+                    Style::default().fg(theme.text),
+                )
+            } else {
+                (
+                    "○",
+                    grid,
+                    Style::default().fg(theme.text),
+                    Style::default().fg(muted),
+                )
+            };
+            let icon = if purchase.is_track { "♪" } else { "💿" };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {} ", marker), Style::default().fg(m_color)),
+                Span::styled(format!("{} ", icon), Style::default().fg(cyan)),
+                Span::styled(fit(&purchase.title, title_w), t_style),
+                Span::styled(
+                    truncate(&purchase.artist, artist_w.saturating_sub(1)),
+                    a_style,
+                ),
+            ]))
+        })
+        .collect()
+}
@@ -1,19 +1,20 @@
 use crate::app::App;
-use crate::ui::utils::truncate;
+use crate::t;
+use crate::ui::utils::{fit, truncate};
 use ratatui::{
     layout::Alignment,
     style::{Modifier, Style},
     text::{Line, Span},
+    widgets::ListItem,
 };
 
-pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>) {
+pub fn render(app: &App, width: usize, lines: &mut Vec<Line>) -> Vec<ListItem<'static>> {
     let theme = &app.theme;
 
     // Unified aesthetic: simple list, no split
     let time_w = 6;
     let artist_w = width / 4;
     let title_w = width.saturating_sub(artist_w + time_w + 10);
-    let content_h = height;
 
     let blue = theme.blue;
     let green = theme.green;
@@ -42,24 +43,19 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
     // ━━━ CONTENT ━━━
     if app.library_items.is_empty() {
         lines.push(
-            Line::from(Span::styled("Empty folder", Style::default().fg(muted)))
-                .alignment(Alignment::Center),
+            Line::from(Span::styled(
+                t!("library.empty_folder"),
+                Style::default().fg(muted),
+            ))
+            .alignment(Alignment::Center),
         );
-    } else {
-        let start_idx = app
-            .library_selected
-            .saturating_sub(content_h / 2)
-            .min(app.library_items.len().saturating_sub(content_h));
+    }
 
-        for (display_idx, item) in app
-            .library_items
-            .iter()
-            .skip(start_idx)
-            .take(content_h)
-            .enumerate()
-        {
-            let actual_idx = start_idx + display_idx;
-            let is_sel = actual_idx == app.library_selected;
+    app.library_items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let is_sel = idx == app.library_selected;
             let is_folder = matches!(item.item_type, crate::app::LibraryItemType::Folder);
 
             let raw_name = if item.name.trim().is_empty() {
@@ -83,11 +79,11 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
                 };
                 let icon = "📁";
 
-                lines.push(Line::from(vec![
+                ListItem::new(Line::from(vec![
                     Span::styled(format!("  {} ", marker), Style::default().fg(m_color)),
                     Span::styled(format!("{} ", icon), Style::default().fg(blue)),
                     Span::styled(name, n_style),
-                ]));
+                ]))
             } else {
                 // Song row
                 let artist = item.artist.clone().unwrap_or_default();
@@ -119,17 +115,21 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
                 };
                 let icon = "♪";
 
-                lines.push(Line::from(vec![
+                // Marked for batch tag editing (`v`)
+                let (marker, m_color) = if item.path.as_deref().is_some_and(|p| app.is_marked(p)) {
+                    ("◆", theme.magenta)
+                } else {
+                    (marker, m_color)
+                };
+
+                ListItem::new(Line::from(vec![
                     Span::styled(format!("  {} ", marker), Style::default().fg(m_color)),
                     Span::styled(format!("{} ", icon), Style::default().fg(green)),
-                    Span::styled(format!("{:title_w$}", name, title_w = title_w), t_style),
-                    Span::styled(
-                        format!("{:artist_w$}", artist_disp, artist_w = artist_w),
-                        a_style,
-                    ),
+                    Span::styled(fit(&name, title_w), t_style),
+                    Span::styled(fit(&artist_disp, artist_w), a_style),
                     Span::styled(format!("{:>time_w$}", time, time_w = time_w), tm_style),
-                ]));
+                ]))
             }
-        }
-    }
+        })
+        .collect()
 }
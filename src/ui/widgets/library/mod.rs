@@ -1,22 +1,31 @@
-use crate::app::{App, LibraryMode};
+use crate::app::{App, ArtworkState, LibraryMode};
+use crate::t;
+use crate::ui::utils::truncate;
 use ratatui::{
     layout::Alignment,
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Paragraph},
+    widgets::{Block, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
 
+pub mod albums;
+pub mod bandcamp;
 pub mod browser;
 pub mod playlists;
 pub mod queue;
 pub mod search;
 
+/// Thumbnail panel width in cells (half-blocks: square at width / 2 rows)
+const THUMB_W: u16 = 22;
+/// Header lines above the list (search bar, tabs, section title)
+const HEADER_H: u16 = 8;
+
 pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
     let theme = &app.theme;
 
-    let title_text = " Library ";
+    let title_text = t!("library.title");
 
     let lib_block = Block::default()
         .borders(ratatui::widgets::Borders::ALL)
@@ -33,7 +42,6 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
     f.render_widget(lib_block, area);
 
     let w = inner_area.width as usize;
-    let h = inner_area.height as usize;
     let mut lines: Vec<Line> = Vec::new();
 
     // ═══════════════════════════════════════════════════════════════
@@ -74,82 +82,222 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
     // Tab bar with filled dot indicators
     let queue_active = app.library_mode == LibraryMode::Queue;
     let dir_active = app.library_mode == LibraryMode::Directory;
+    let al_active = app.library_mode == LibraryMode::Albums;
     let pl_active = app.library_mode == LibraryMode::Playlists;
+    let bc_active = app.library_mode == LibraryMode::Bandcamp;
 
     // Use filled dots for active, empty for inactive
     let q_dot = if queue_active { "●" } else { "○" };
     let d_dot = if dir_active { "●" } else { "○" };
+    let a_dot = if al_active { "●" } else { "○" };
     let p_dot = if pl_active { "●" } else { "○" };
+    let b_dot = if bc_active { "●" } else { "○" };
 
-    lines.push(
-        Line::from(vec![
-            // Queue
-            Span::styled(format!("{} ", q_dot), Style::default().fg(theme.green)),
+    let mut tabs = vec![
+        // Queue
+        Span::styled(format!("{} ", q_dot), Style::default().fg(theme.green)),
+        Span::styled(
+            t!("library.tab_queue"),
+            if queue_active {
+                Style::default()
+                    .fg(theme.green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.green)
+            },
+        ),
+        Span::styled("    ", Style::default()),
+        // Directory
+        Span::styled(format!("{} ", d_dot), Style::default().fg(theme.blue)),
+        Span::styled(
+            t!("library.tab_directory"),
+            if dir_active {
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.blue)
+            },
+        ), // Inactive is dimmed blue
+        Span::styled("    ", Style::default()),
+        // Albums
+        Span::styled(format!("{} ", a_dot), Style::default().fg(theme.yellow)),
+        Span::styled(
+            t!("library.tab_albums"),
+            if al_active {
+                Style::default()
+                    .fg(theme.yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.yellow)
+            },
+        ),
+        Span::styled("    ", Style::default()),
+        // Playlists
+        Span::styled(format!("{} ", p_dot), Style::default().fg(theme.magenta)),
+        Span::styled(
+            t!("library.tab_playlists"),
+            if pl_active {
+                Style::default()
+                    .fg(theme.magenta)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.magenta)
+            },
+        ), // Inactive is dimmed magenta
+    ];
+    // Bandcamp only once signed in
+    if app.bandcamp_identity.is_some() || bc_active {
+        tabs.extend([
+            Span::styled("    ", Style::default()),
+            Span::styled(format!("{} ", b_dot), Style::default().fg(theme.cyan)),
             Span::styled(
-                "Queue",
-                if queue_active {
-                    Style::default()
-                        .fg(theme.green)
-                        .add_modifier(Modifier::BOLD)
+                t!("library.tab_bandcamp"),
+                if bc_active {
+                    Style::default().fg(theme.cyan).add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(theme.green)
+                    Style::default().fg(theme.cyan)
                 },
             ),
-            Span::styled("      ", Style::default()),
-            // Directory
-            Span::styled(format!("{} ", d_dot), Style::default().fg(theme.blue)),
-            Span::styled(
-                "Directory",
-                if dir_active {
-                    Style::default().fg(theme.blue).add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default().fg(theme.blue)
-                },
-            ), // Inactive is dimmed blue
-            Span::styled("      ", Style::default()),
-            // Playlists
-            Span::styled(format!("{} ", p_dot), Style::default().fg(theme.magenta)),
-            Span::styled(
-                "Playlists",
-                if pl_active {
-                    Style::default()
-                        .fg(theme.magenta)
-                        .add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default().fg(theme.magenta)
-                },
-            ), // Inactive is dimmed magenta
-        ])
-        .alignment(Alignment::Center),
-    );
+        ]);
+    }
+    lines.push(Line::from(tabs).alignment(Alignment::Center));
 
     lines.push(Line::from(""));
 
-    // Determine remaining height for content
-    // We added: "", search, sep, tab, "" -> 5 lines?
-    // Let's count properly:
-    // 1: ""
-    // 2: Search
-    // 3: Sep
-    // 4: Tab
-    // 5: ""
-    // 6: (First line of content usually starts with logic)
-    // Actually, sub-widgets add 3 header lines ("\n TITLE \n").
-    // So we need to pass a height that accounts for this too if we want precision.
-    // The original code used `content_h = h.saturating_sub(8)`.
-    // Since we consumed 5 lines here, passing `h.saturating_sub(5)` seems right,
-    // but the sub-widgets often start with `lines.push("")`.
-    // Let's pass `h.saturating_sub(8)` to be safe/consistent with old logic.
-    let content_h = h.saturating_sub(8);
-
-    match app.library_mode {
-        LibraryMode::Queue => queue::render(app, w, content_h, &mut lines),
-        LibraryMode::Directory => browser::render(app, w, content_h, &mut lines),
-        LibraryMode::Search => search::render(app, w, content_h, &mut lines),
-        LibraryMode::Playlists => playlists::render(app, w, content_h, &mut lines),
-    }
+    // Thumbnail split for the directory browser on wide enough panes
+    let show_thumb = app.library_mode == LibraryMode::Directory
+        && app.library_art.is_some()
+        && inner_area.width >= 70
+        && inner_area.height >= HEADER_H + THUMB_W / 2 + 2;
+    let list_w = if show_thumb {
+        w.saturating_sub(THUMB_W as usize + 2)
+    } else {
+        w
+    };
 
+    // Rows leave the last column to the scrollbar
+    let row_w = list_w.saturating_sub(1);
+    let mut selected = app.library_selected;
+    let items = match app.library_mode {
+        LibraryMode::Queue => {
+            let (items, row) = queue::render(app, row_w, &mut lines);
+            selected = row;
+            items
+        }
+        LibraryMode::Directory => browser::render(app, row_w, &mut lines),
+        LibraryMode::Albums => albums::render(app, row_w, &mut lines),
+        LibraryMode::Search => search::render(app, row_w, &mut lines),
+        LibraryMode::Playlists => playlists::render(app, row_w, &mut lines),
+        LibraryMode::Bandcamp => bandcamp::render(app, row_w, &mut lines),
+    };
+
+    // Header, section title and empty-state hints above; the list below
     let library_widget =
         Paragraph::new(lines).block(Block::default().style(Style::default().bg(Color::Reset)));
     f.render_widget(library_widget, inner_area);
+
+    let list_area = Rect::new(
+        inner_area.x,
+        inner_area.y + HEADER_H.min(inner_area.height),
+        list_w as u16,
+        inner_area.height.saturating_sub(HEADER_H),
+    );
+    if app.library_mode == LibraryMode::Albums && app.album_open.is_none() {
+        albums::render_grid(f, list_area, app);
+    } else {
+        render_list(f, list_area, app, items, selected);
+    }
+
+    if show_thumb {
+        let thumb_area = Rect::new(
+            inner_area.right().saturating_sub(THUMB_W + 1),
+            inner_area.y + HEADER_H,
+            THUMB_W,
+            THUMB_W / 2,
+        );
+        render_thumbnail(f, thumb_area, app);
+    }
+}
+
+/// The entries of the current tab, scrolled to keep the selection in view
+fn render_list(
+    f: &mut Frame,
+    area: Rect,
+    app: &mut App,
+    items: Vec<ListItem<'static>>,
+    selected: usize,
+) {
+    let rows = area.height as usize;
+    let total = items.len();
+    app.library_page = rows;
+    if rows == 0 || total == 0 {
+        return;
+    }
+
+    // ListState keeps its offset between frames, so the view only scrolls
+    // once the selection reaches an edge
+    let selected = selected.min(total - 1);
+    app.library_list.select(Some(selected));
+    f.render_stateful_widget(List::new(items), area, &mut app.library_list);
+
+    if total > rows {
+        // One thumb position per possible offset
+        let mut scroll = ScrollbarState::new(total - rows + 1)
+            .position(app.library_list.offset())
+            .viewport_content_length(rows);
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .track_symbol(Some("│"))
+                .track_style(Style::default().fg(app.theme.surface))
+                .thumb_symbol("┃")
+                .thumb_style(Style::default().fg(app.theme.overlay)),
+            area,
+            &mut scroll,
+        );
+    }
+}
+
+/// Artwork of the selected folder/song, with its name underneath
+fn render_thumbnail(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let Some((_, state)) = &app.library_art else {
+        return;
+    };
+
+    let lines = match state {
+        ArtworkState::Loaded(img) => {
+            crate::ui::widgets::player::art::render_block(img, area, app.art_quality)
+        }
+        ArtworkState::Loading | ArtworkState::Failed | ArtworkState::Idle => {
+            let glyph = if matches!(state, ArtworkState::Loading) {
+                "…"
+            } else {
+                "♪"
+            };
+            let mut lines = vec![Line::default(); (area.height / 2) as usize];
+            lines.push(Line::from(Span::styled(
+                glyph,
+                Style::default().fg(theme.overlay),
+            )));
+            lines
+        }
+    };
+    f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
+
+    // Caption
+    let name = app
+        .library_items
+        .get(app.library_selected)
+        .map(|item| item.name.clone())
+        .unwrap_or_default();
+    let caption_area = Rect::new(area.x, area.bottom(), area.width, 1);
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            truncate(&name, area.width as usize),
+            Style::default().fg(theme.overlay),
+        ))
+        .alignment(Alignment::Center),
+        caption_area,
+    );
 }
@@ -1,14 +1,15 @@
 use crate::app::App;
+use crate::t;
 use crate::ui::utils::truncate;
 use ratatui::{
     layout::Alignment,
     style::{Modifier, Style},
     text::{Line, Span},
+    widgets::ListItem,
 };
 
-pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>) {
+pub fn render(app: &App, width: usize, lines: &mut Vec<Line>) -> Vec<ListItem<'static>> {
     let theme = &app.theme;
-    let content_h = height;
 
     let magenta = theme.magenta;
     let green = theme.green;
@@ -31,32 +32,30 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
     // ━━━ CONTENT ━━━
     if app.playlists.is_empty() {
         lines.push(
-            Line::from(Span::styled("No playlists", Style::default().fg(muted)))
-                .alignment(Alignment::Center),
+            Line::from(Span::styled(
+                t!("library.no_playlists"),
+                Style::default().fg(muted),
+            ))
+            .alignment(Alignment::Center),
         );
         lines.push(
             Line::from(Span::styled(
-                "Press 's' to save queue as playlist",
+                t!(
+                    "library.no_playlists_hint",
+                    key = app.keys.display(&app.keys.save_playlist)
+                ),
                 Style::default().fg(grid),
             ))
             .alignment(Alignment::Center),
         );
-    } else {
-        let start_idx = app
-            .library_selected
-            .saturating_sub(content_h / 2)
-            .min(app.playlists.len().saturating_sub(content_h));
+    }
 
-        for (display_idx, pl) in app
-            .playlists
-            .iter()
-            .skip(start_idx)
-            .take(content_h)
-            .enumerate()
-        {
-            let actual_idx = start_idx + display_idx;
-            let is_sel = actual_idx == app.library_selected;
-            let num = actual_idx + 1;
+    app.playlists
+        .iter()
+        .enumerate()
+        .map(|(idx, pl)| {
+            let is_sel = idx == app.library_selected;
+            let num = idx + 1;
 
             let name_max = width.saturating_sub(12);
             let name = truncate(pl, name_max);
@@ -70,9 +69,14 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
             } else {
                 ("○", grid, Style::default().fg(theme.text))
             };
-            let icon = "📜"; // Standard playlist icon
+            // Smart playlists are rebuilt from their rule on load
+            let icon = if app.smart_playlist(pl).is_some() {
+                "✨"
+            } else {
+                "📜"
+            };
 
-            lines.push(Line::from(vec![
+            ListItem::new(Line::from(vec![
                 Span::styled(format!("  {} ", marker), Style::default().fg(m_color)),
                 Span::styled(
                     format!("{:>2}  ", num),
@@ -80,7 +84,7 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
                 ),
                 Span::styled(format!("{} ", icon), Style::default().fg(magenta)),
                 Span::styled(name, n_style),
-            ]));
-        }
-    }
+            ]))
+        })
+        .collect()
 }
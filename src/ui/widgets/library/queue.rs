@@ -1,22 +1,21 @@
-use crate::app::App;
-use crate::ui::utils::truncate;
+use crate::app::{App, ArtworkState, QueueGroup, QueueRow};
+use crate::t;
+use crate::ui::utils::{fit, short_duration, truncate};
 use ratatui::{
     layout::Alignment,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
+    widgets::ListItem,
 };
 
-pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>) {
+/// The list rows and which of them shows the selection
+pub fn render(app: &App, width: usize, lines: &mut Vec<Line>) -> (Vec<ListItem<'static>>, usize) {
     let theme = &app.theme;
 
     // Unified aesthetic: spacious, centered, clean
     let time_w = 6;
     let artist_w = width / 4;
     let title_w = width.saturating_sub(artist_w + time_w + 10);
-    // content_h is passed as height.
-    // In original code, it subtracted 8 from inner height, but here we might pass the remaining height?
-    // Let's assume height IS the content height available.
-    let content_h = height;
 
     let green = theme.green;
     let pink = theme.red;
@@ -27,9 +26,28 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
     // ━━━ CENTERED TITLE ━━━
     lines.push(Line::from(""));
     let queue_count = app.queue.len();
+    let (playing, remaining_ms, total_ms) = app.queue_time();
+    let mut parts = match playing {
+        Some(idx) => vec![
+            format!("Track {}/{}", idx + 1, queue_count),
+            format!("{} remaining", short_duration(remaining_ms)),
+        ],
+        None => vec![format!("{} songs", queue_count)],
+    };
+    if queue_count > 0 {
+        parts.push(format!("{} total", short_duration(total_ms)));
+    }
+    // Narrow pane: drop from the end
+    let summary = loop {
+        let summary = parts.join("  ·  ");
+        if parts.len() == 1 || summary.chars().count() + 15 <= width {
+            break summary;
+        }
+        parts.pop();
+    };
     lines.push(
         Line::from(Span::styled(
-            format!("  QUEUE  ·  {} songs  ", queue_count),
+            format!("  QUEUE  ·  {}  ", summary),
             Style::default().fg(green),
         ))
         .alignment(Alignment::Center),
@@ -39,33 +57,36 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
     // ━━━ CONTENT ━━━
     if app.queue.is_empty() {
         lines.push(
-            Line::from(Span::styled("Empty queue", Style::default().fg(muted)))
-                .alignment(Alignment::Center),
+            Line::from(Span::styled(
+                t!("library.empty_queue"),
+                Style::default().fg(muted),
+            ))
+            .alignment(Alignment::Center),
         );
         lines.push(
             Line::from(Span::styled(
-                "Browse Directory to add songs",
+                t!("library.empty_queue_hint"),
                 Style::default().fg(grid),
             ))
             .alignment(Alignment::Center),
         );
-    } else {
-        let start_idx = app
-            .library_selected
-            .saturating_sub(content_h / 2)
-            .min(app.queue.len().saturating_sub(content_h));
+    }
 
-        for (display_idx, (_, item)) in app
-            .queue
-            .iter()
-            .enumerate()
-            .skip(start_idx)
-            .take(content_h)
-            .enumerate()
-        {
-            let actual_idx = start_idx + display_idx;
-            let is_sel = actual_idx == app.library_selected;
-            let num = actual_idx + 1;
+    let rows = app.queue_rows();
+    let selected = crate::app::queue_selected_row(&rows, app.library_selected);
+    let items = rows
+        .iter()
+        .map(|row| {
+            let idx = match row {
+                QueueRow::Album(group) => {
+                    let is_sel = group.folded && group.contains(app.library_selected);
+                    return album_header(app, group, width, is_sel);
+                }
+                QueueRow::Song(idx) => *idx,
+            };
+            let item = &app.queue[idx];
+            let is_sel = idx == app.library_selected;
+            let num = idx + 1;
 
             let title = truncate(&item.title, title_w.saturating_sub(2));
             let artist = truncate(&item.artist, artist_w.saturating_sub(1));
@@ -101,7 +122,14 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
                 )
             };
 
-            lines.push(Line::from(vec![
+            // Marked for batch tag editing (`v`)
+            let (marker, m_color) = if app.is_marked(&item.file_path) {
+                ("◆", theme.magenta)
+            } else {
+                (marker, m_color)
+            };
+
+            ListItem::new(Line::from(vec![
                 Span::styled(format!("  {} ", marker), Style::default().fg(m_color)),
                 Span::styled(
                     format!("{:>2}  ", num),
@@ -111,16 +139,90 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
                     "♪ ",
                     Style::default().fg(if item.is_current { pink } else { green }),
                 ),
-                Span::styled(
-                    format!("{:title_w$}", title, title_w = title_w.saturating_sub(2)),
-                    t_style,
-                ),
-                Span::styled(
-                    format!("{:artist_w$}", artist, artist_w = artist_w),
-                    a_style,
-                ),
+                Span::styled(fit(&title, title_w.saturating_sub(2)), t_style),
+                Span::styled(fit(&artist, artist_w), a_style),
                 Span::styled(format!("{:>time_w$}", time, time_w = time_w), tm_style),
-            ]));
-        }
+            ]))
+        })
+        .collect();
+    (items, selected)
+}
+
+/// `▾ [art] Album · 2001 ... 12 songs 48:12`; `▸` when folded
+fn album_header(app: &App, group: &QueueGroup, width: usize, is_sel: bool) -> ListItem<'static> {
+    let theme = &app.theme;
+    let (fold, fold_color) = if group.folded {
+        ("▸", theme.yellow)
+    } else {
+        ("▾", theme.overlay)
+    };
+    let label_style = if is_sel {
+        Style::default()
+            .fg(theme.yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.blue).add_modifier(Modifier::BOLD)
+    };
+    let muted = Style::default().fg(theme.overlay);
+
+    let label = if group.year.is_empty() {
+        group.album.clone()
+    } else {
+        format!("{}  ·  {}", group.album, group.year)
+    };
+    let count = format!("{} songs", group.len);
+    let time = total_time(group.duration_ms);
+    // Marker, thumbnail and the gaps around count and time
+    let label_w = width.saturating_sub(4 + 3 + count.len() + time.len() + 4);
+
+    let mut spans = vec![Span::styled(
+        format!("  {} ", fold),
+        Style::default().fg(if is_sel { theme.yellow } else { fold_color }),
+    )];
+    let first_file = app
+        .queue
+        .get(group.start)
+        .map(|q| q.file_path.as_str())
+        .unwrap_or_default();
+    spans.extend(thumbnail(app, first_file));
+    spans.push(Span::raw(" "));
+    spans.push(Span::styled(
+        fit(&truncate(&label, label_w), label_w),
+        label_style,
+    ));
+    spans.push(Span::styled(format!("  {}", count), muted));
+    spans.push(Span::styled(format!("  {}", time), muted));
+    ListItem::new(Line::from(spans))
+}
+
+/// Two cells of cover, from the album grid's cache
+fn thumbnail(app: &App, file: &str) -> Vec<Span<'static>> {
+    let Some(ArtworkState::Loaded(img)) = app.album_art.get(file) else {
+        return vec![Span::styled("♪ ", Style::default().fg(app.theme.overlay))];
+    };
+    let px = img
+        .resize_exact(2, 2, image::imageops::FilterType::Triangle)
+        .to_rgb8();
+    (0..2)
+        .map(|x| {
+            let top = px.get_pixel(x, 0);
+            let bottom = px.get_pixel(x, 1);
+            Span::styled(
+                "▀",
+                Style::default()
+                    .fg(Color::Rgb(top[0], top[1], top[2]))
+                    .bg(Color::Rgb(bottom[0], bottom[1], bottom[2])),
+            )
+        })
+        .collect()
+}
+
+/// `48:12`, or `1:02:03` past an hour
+fn total_time(ms: u64) -> String {
+    let s = ms / 1000;
+    if s >= 3600 {
+        format!("{}:{:02}:{:02}", s / 3600, s / 60 % 60, s % 60)
+    } else {
+        format!("{}:{:02}", s / 60, s % 60)
     }
 }
@@ -1,12 +1,14 @@
 use crate::app::App;
-use crate::ui::utils::truncate;
+use crate::t;
+use crate::ui::utils::{fit, truncate};
 use ratatui::{
     layout::Alignment,
     style::{Modifier, Style},
     text::{Line, Span},
+    widgets::ListItem,
 };
 
-pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>) {
+pub fn render(app: &App, width: usize, lines: &mut Vec<Line>) -> Vec<ListItem<'static>> {
     let theme = &app.theme;
 
     // Unified aesthetic for Search
@@ -14,7 +16,6 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
     // Match Directory Layout: 25% Artist
     let artist_w = width / 4;
     // let title_w = w.saturating_sub(artist_w + time_w + 10); // Unused
-    let content_h = height;
 
     let green = theme.green;
     let cream = theme.yellow;
@@ -37,12 +38,15 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
     // ━━━ CONTENT ━━━
     if app.library_items.is_empty() && !app.search_query.is_empty() {
         lines.push(
-            Line::from(Span::styled("No results found", Style::default().fg(muted)))
-                .alignment(Alignment::Center),
+            Line::from(Span::styled(
+                t!("library.no_results"),
+                Style::default().fg(muted),
+            ))
+            .alignment(Alignment::Center),
         );
         lines.push(
             Line::from(Span::styled(
-                "Try a different search",
+                t!("library.no_results_hint"),
                 Style::default().fg(grid),
             ))
             .alignment(Alignment::Center),
@@ -50,26 +54,18 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
     } else if app.library_items.is_empty() {
         lines.push(
             Line::from(Span::styled(
-                "Type to search your library",
+                t!("library.search_hint"),
                 Style::default().fg(muted),
             ))
             .alignment(Alignment::Center),
         );
-    } else {
-        let start_idx = app
-            .library_selected
-            .saturating_sub(content_h / 2)
-            .min(app.library_items.len().saturating_sub(content_h));
-
-        for (display_idx, item) in app
-            .library_items
-            .iter()
-            .skip(start_idx)
-            .take(content_h)
-            .enumerate()
-        {
-            let actual_idx = start_idx + display_idx;
-            let is_sel = actual_idx == app.library_selected;
+    }
+
+    app.library_items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let is_sel = idx == app.library_selected;
 
             // Clean up name by removing path components if present
             let clean_name = item.name.split('/').next_back().unwrap_or(&item.name);
@@ -124,23 +120,22 @@ pub fn render(app: &mut App, width: usize, height: usize, lines: &mut Vec<Line>)
                 _ => " ",
             };
 
-            lines.push(Line::from(vec![
+            // Marked for batch tag editing (`v`)
+            let (marker, m_color) = if item.path.as_deref().is_some_and(|p| app.is_marked(p)) {
+                ("◆", theme.magenta)
+            } else {
+                (marker, m_color)
+            };
+
+            ListItem::new(Line::from(vec![
                 Span::styled(format!("  {} ", marker), Style::default().fg(m_color)),
                 Span::styled(
-                    format!(
-                        "{} {:title_w$}",
-                        icon,
-                        name,
-                        title_w = row_title_w.saturating_sub(1)
-                    ),
+                    format!("{} {}", icon, fit(&name, row_title_w.saturating_sub(1))),
                     t_style,
                 ),
-                Span::styled(
-                    format!("{:artist_w$}", artist_disp, artist_w = row_artist_w),
-                    a_style,
-                ),
+                Span::styled(fit(&artist_disp, row_artist_w), a_style),
                 Span::styled(format!("{:>time_w$}", time, time_w = row_time_w), tm_style),
-            ]));
-        }
-    }
+            ]))
+        })
+        .collect()
 }
@@ -1,3 +1,5 @@
+pub mod bar;
 pub mod library;
 pub mod player;
 pub mod popups;
+pub mod wizard;
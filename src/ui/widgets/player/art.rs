@@ -1,8 +1,11 @@
-use crate::app::{App, ArtStyle, ArtworkState};
-use image::{imageops::FilterType, GenericImageView};
+use crate::app::{App, ArtCells, ArtCellsKey, ArtQuality, ArtStyle, ArtworkState};
+use crate::t;
+use crate::ui::dither;
+use crate::ui::utils::truncate;
+use image::GenericImageView;
 use ratatui::{
-    layout::{Alignment, Rect},
-    style::{Color, Style},
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Paragraph},
     Frame,
@@ -15,24 +18,27 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
 
     match &app.artwork {
         ArtworkState::Loaded(raw_image) => {
-            if app.art_style == ArtStyle::Off {
+            let style = app.effective_art_style();
+            if style == ArtStyle::Off {
                 return;
             }
 
-            if app.art_style == ArtStyle::Image {
+            if style == ArtStyle::Image {
                 if app.image_protocol.is_none() {
                     let picker = app.image_picker.clone();
-                    app.image_protocol = Some(picker.new_resize_protocol(raw_image.clone()));
+                    app.image_protocol =
+                        Some(picker.new_resize_protocol(raw_image.as_ref().clone()));
                 }
 
                 if let Some(protocol) = &mut app.image_protocol {
                     // Center the image within the available area
                     // Use Scale instead of Fit to maximize the area properly
                     // like the Block art style does.
-                    // IMPORTANT: Override default `Nearest` neighbor scaling with `Triangle` (Bilinear).
-                    // This provides 80% of the sharpness of Lanczos3 but is literally 10x faster,
-                    // completely eliminating the UI rendering latency/lag when the image resizes.
-                    let resize = ratatui_image::Resize::Scale(Some(FilterType::Triangle));
+                    // IMPORTANT: Override default `Nearest` neighbor scaling with `art_filter`
+                    // (`Triangle` unless configured). Bilinear gives 80% of the sharpness of
+                    // Lanczos3 at a tenth of the cost, so resizing doesn't lag.
+                    let resize =
+                        ratatui_image::Resize::Scale(Some(app.art_quality.filter.filter_type()));
                     // size_for calculates the exact rect in cells that the image needs
                     let img_size = protocol.size_for(resize.clone(), area);
 
@@ -51,14 +57,25 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
                 return;
             }
 
-            let lines = match app.art_style {
-                ArtStyle::Block => render_block(raw_image, area),
-                ArtStyle::Ascii => render_ascii(raw_image, area),
-                ArtStyle::Braille => render_braille(raw_image, area),
-                _ => vec![], // Image and Off handled above
+            // The runner builds the cells for this size in the background;
+            // until then the last size of this cover is shown
+            let key = ArtCellsKey {
+                generation: app.art_generation,
+                style,
+                quality: app.art_quality,
+                width: area.width,
+                height: area.height,
+            };
+            app.art_cells_wanted = Some(key);
+            let Some(cells) = app
+                .art_cells
+                .as_ref()
+                .filter(|c| c.key.generation == key.generation && c.key.style == style)
+            else {
+                return;
             };
 
-            let artwork_widget = Paragraph::new(lines)
+            let artwork_widget = Paragraph::new(cells.lines.clone())
                 .alignment(Alignment::Center)
                 .block(Block::default().style(Style::default().bg(Color::Reset)));
             f.render_widget(artwork_widget, area);
@@ -82,7 +99,60 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
     }
 }
 
-fn render_block(raw_image: &image::DynamicImage, area: Rect) -> Vec<Line<'static>> {
+/// Artwork over the whole terminal with the track title along the bottom
+pub fn render_fullscreen(f: &mut Frame, area: Rect, app: &mut App) {
+    let [art_area, title_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(2)]).areas(area);
+    render(f, art_area, app);
+
+    let theme = &app.theme;
+    let width = area.width.saturating_sub(2) as usize;
+    let lines = match &app.track {
+        Some(track) => {
+            let mut subtitle = track.artist.clone();
+            if !track.album.is_empty() {
+                subtitle = format!("{} — {}", subtitle, track.album);
+            }
+            vec![
+                Line::from(Span::styled(
+                    truncate(&track.name, width),
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                )),
+                Line::from(Span::styled(
+                    truncate(&subtitle, width),
+                    Style::default().fg(theme.overlay),
+                )),
+            ]
+        }
+        None => vec![Line::from(Span::styled(
+            t!("common.nothing_playing"),
+            Style::default().fg(theme.overlay),
+        ))],
+    };
+    f.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center),
+        title_area,
+    );
+}
+
+/// Resize `img` into text art for `key`'s style and size (slow for big
+/// covers: call it off the render thread)
+pub fn build_cells(img: &image::DynamicImage, key: ArtCellsKey) -> ArtCells {
+    let area = Rect::new(0, 0, key.width, key.height);
+    let lines = match key.style {
+        ArtStyle::Block => render_block(img, area, key.quality),
+        ArtStyle::Ascii => render_ascii(img, area, key.quality),
+        ArtStyle::Braille => render_braille(img, area, key.quality),
+        _ => vec![], // Auto is resolved before; Image and Off draw no text
+    };
+    ArtCells { key, lines }
+}
+
+pub fn render_block(
+    raw_image: &image::DynamicImage,
+    area: Rect,
+    quality: ArtQuality,
+) -> Vec<Line<'static>> {
     let available_width = area.width as u32;
     let available_height = area.height as u32;
     let target_width = available_width;
@@ -92,7 +162,11 @@ fn render_block(raw_image: &image::DynamicImage, area: Rect) -> Vec<Line<'static
         return vec![];
     }
 
-    let resized = raw_image.resize(target_width, target_height, FilterType::Triangle);
+    let resized = raw_image
+        .resize(target_width, target_height, quality.filter.filter_type())
+        .to_rgb8();
+    let colors = dither::colors(&resized, quality.dither);
+    let color = |x: u32, y: u32| colors[(y * resized.width() + x) as usize];
     let img_height_subpixels = resized.height();
     let img_rows = img_height_subpixels.div_ceil(2);
     let padding_top = available_height.saturating_sub(img_rows) / 2;
@@ -105,32 +179,28 @@ fn render_block(raw_image: &image::DynamicImage, area: Rect) -> Vec<Line<'static
     for y in (0..img_height_subpixels).step_by(2) {
         let mut spans = Vec::new();
         for x in 0..resized.width() {
-            let p1 = resized.get_pixel(x, y);
-            let p2 = if y + 1 < img_height_subpixels {
-                resized.get_pixel(x, y + 1)
+            let fg = color(x, y);
+            let bg = if y + 1 < img_height_subpixels {
+                color(x, y + 1)
             } else {
-                p1
+                fg
             };
 
-            let fg = (p1[0], p1[1], p1[2]);
-            let bg = (p2[0], p2[1], p2[2]);
-
             // Simple alpha blending check: if mostly transparent, use default bg?
             // For now, assume opaque.
 
-            spans.push(Span::styled(
-                "▀",
-                Style::default()
-                    .fg(Color::Rgb(fg.0, fg.1, fg.2))
-                    .bg(Color::Rgb(bg.0, bg.1, bg.2)),
-            ));
+            spans.push(Span::styled("▀", Style::default().fg(fg).bg(bg)));
         }
         lines.push(Line::from(spans));
     }
     lines
 }
 
-fn render_ascii(raw_image: &image::DynamicImage, area: Rect) -> Vec<Line<'static>> {
+fn render_ascii(
+    raw_image: &image::DynamicImage,
+    area: Rect,
+    quality: ArtQuality,
+) -> Vec<Line<'static>> {
     let available_width = area.width as u32;
     let available_height = area.height as u32;
 
@@ -158,7 +228,10 @@ fn render_ascii(raw_image: &image::DynamicImage, area: Rect) -> Vec<Line<'static
         final_w = (available_height as f32 * 2.0 * src_ar) as u32;
     }
 
-    let resized = raw_image.resize_exact(final_w, final_h, FilterType::Triangle);
+    let resized = raw_image
+        .resize_exact(final_w, final_h, quality.filter.filter_type())
+        .to_rgb8();
+    let colors = dither::colors(&resized, quality.dither);
 
     // Center vertically
     let padding_top = available_height.saturating_sub(final_h) / 2;
@@ -185,7 +258,7 @@ fn render_ascii(raw_image: &image::DynamicImage, area: Rect) -> Vec<Line<'static
 
             spans.push(Span::styled(
                 c.to_string(),
-                Style::default().fg(Color::Rgb(p[0], p[1], p[2])),
+                Style::default().fg(colors[(y * resized.width() + x) as usize]),
             ));
         }
         lines.push(Line::from(spans));
@@ -194,7 +267,11 @@ fn render_ascii(raw_image: &image::DynamicImage, area: Rect) -> Vec<Line<'static
     lines
 }
 
-fn render_braille(raw_image: &image::DynamicImage, area: Rect) -> Vec<Line<'static>> {
+fn render_braille(
+    raw_image: &image::DynamicImage,
+    area: Rect,
+    quality: ArtQuality,
+) -> Vec<Line<'static>> {
     let available_width = area.width as u32;
     let available_height = area.height as u32;
 
@@ -213,7 +290,7 @@ fn render_braille(raw_image: &image::DynamicImage, area: Rect) -> Vec<Line<'stat
     }
 
     // 1. Resize for dot resolution
-    let resized = raw_image.resize_exact(final_dot_w, final_dot_h, FilterType::Triangle);
+    let resized = raw_image.resize_exact(final_dot_w, final_dot_h, quality.filter.filter_type());
 
     // 2. Apply Floyd-Steinberg Dithering to get binary (on/off) state matrix 🔳
     let dithered_width = resized.width() as usize;
@@ -271,19 +348,18 @@ fn render_braille(raw_image: &image::DynamicImage, area: Rect) -> Vec<Line<'stat
 
             let ch = char::from_u32(0x2800 + mask).unwrap_or(' ');
 
-            let (r, g, b) = if count > 0 {
-                (
-                    (r_sum / count) as u8,
-                    (g_sum / count) as u8,
-                    (b_sum / count) as u8,
-                )
-            } else {
-                (255, 255, 255)
+            let (r, g, b) = match (
+                r_sum.checked_div(count),
+                g_sum.checked_div(count),
+                b_sum.checked_div(count),
+            ) {
+                (Some(r), Some(g), Some(b)) => (r as u8, g as u8, b as u8),
+                _ => (255, 255, 255),
             };
 
             spans.push(Span::styled(
                 ch.to_string(),
-                Style::default().fg(Color::Rgb(r, g, b)),
+                Style::default().fg(dither::color([r, g, b], quality.dither)),
             ));
         }
         lines.push(Line::from(spans));
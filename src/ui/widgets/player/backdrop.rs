@@ -0,0 +1,94 @@
+//! Dimmed, blurred album art behind the player card 🌫️
+//!
+//! Drawn as a post-pass: only cells that nothing else painted a background on
+//! are tinted, so text, badges and the artwork itself stay untouched.
+
+use crate::app::{App, ArtworkState};
+use image::{imageops::FilterType, DynamicImage};
+use ratatui::{layout::Rect, style::Color, Frame};
+
+/// How much of the art shows through the theme base color
+const OPACITY: f32 = 0.22;
+
+/// Pre-blended cell colors for one art/size/base combination
+pub struct ArtBackdrop {
+    size: (u16, u16),
+    base: (u8, u8, u8),
+    /// (top, bottom) half-block colors, row-major
+    cells: Vec<(Color, Color)>,
+}
+
+impl ArtBackdrop {
+    pub fn new(img: &DynamicImage, width: u16, height: u16, base: (u8, u8, u8)) -> Self {
+        let (w, h) = (width.max(1) as u32, height.max(1) as u32 * 2);
+        // Shrinking hard and scaling back up does most of the blurring cheaply
+        let tiny = img.resize_to_fill((w / 8).max(2), (h / 8).max(2), FilterType::Triangle);
+        let soft = tiny.resize_exact(w, h, FilterType::Triangle).blur(1.5);
+        let rgb = soft.to_rgb8();
+
+        let dim = |x: u32, y: u32| {
+            let p = rgb.get_pixel(x, y);
+            let mix = |b: u8, c: u8| (b as f32 + (c as f32 - b as f32) * OPACITY).round() as u8;
+            Color::Rgb(mix(base.0, p[0]), mix(base.1, p[1]), mix(base.2, p[2]))
+        };
+        let cells = (0..height as u32)
+            .flat_map(|row| (0..w).map(move |x| (x, row)))
+            .map(|(x, row)| (dim(x, row * 2), dim(x, row * 2 + 1)))
+            .collect();
+
+        Self {
+            size: (width, height),
+            base,
+            cells,
+        }
+    }
+
+    fn matches(&self, width: u16, height: u16, base: (u8, u8, u8)) -> bool {
+        self.size == (width, height) && self.base == base
+    }
+
+    fn cell(&self, x: u16, y: u16) -> (Color, Color) {
+        self.cells[y as usize * self.size.0 as usize + x as usize]
+    }
+}
+
+/// Tint the unpainted cells of `area` with the current album art
+pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+    let ArtworkState::Loaded(img) = &app.artwork else {
+        return;
+    };
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let base = match app.theme.base {
+        Color::Rgb(r, g, b) => (r, g, b),
+        _ => (0, 0, 0),
+    };
+    if !app
+        .art_backdrop
+        .as_ref()
+        .is_some_and(|b| b.matches(area.width, area.height, base))
+    {
+        app.art_backdrop = Some(ArtBackdrop::new(img, area.width, area.height, base));
+    }
+    let Some(backdrop) = &app.art_backdrop else {
+        return;
+    };
+
+    let buf = f.buffer_mut();
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let (top, bottom) = backdrop.cell(x, y);
+            let cell = &mut buf[(area.x + x, area.y + y)];
+            // Leave graphics-protocol images and anything with its own background alone
+            if cell.skip || cell.bg != Color::Reset {
+                continue;
+            }
+            if cell.symbol() == " " {
+                cell.set_symbol("▀").set_fg(top).set_bg(bottom);
+            } else {
+                cell.set_bg(bottom);
+            }
+        }
+    }
+}
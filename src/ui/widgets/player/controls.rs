@@ -108,6 +108,11 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
 
             // " + "
             bar_spans.push(Span::styled(" +", Style::default().fg(theme.overlay)));
+            match app.volume_hold {
+                Some((crate::app::VolumeHold::Muted, _)) => bar_spans.push(Span::raw(" 🔇")),
+                Some((crate::app::VolumeHold::Ducked, _)) => bar_spans.push(Span::raw(" 🦆")),
+                None => {}
+            }
 
             // Match button layout for perfect alignment
             let volume_layout = Layout::default()
@@ -159,6 +159,38 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         // Helper to truncate strings that are too long
         let max_width = area.width.saturating_sub(4) as usize; // -4 for padding/prefixes
 
+        // Album progress 💿 ("4/11") plus the pending finish-album action
+        let mut album_suffix: Vec<Span> = Vec::new();
+        if let Some(num) = track.track_number {
+            let pos = match track.track_total {
+                Some(total) => format!("{}/{}", num, total),
+                None => format!("#{}", num),
+            };
+            album_suffix.push(Span::styled(
+                format!(" · {}", pos),
+                Style::default().fg(theme.overlay),
+            ));
+        }
+        match app.finish_album {
+            crate::app::FinishAlbumMode::Stop => {
+                album_suffix.push(Span::styled(" ⏹", Style::default().fg(theme.yellow)))
+            }
+            crate::app::FinishAlbumMode::Shuffle => {
+                album_suffix.push(Span::styled(" 🔀", Style::default().fg(theme.yellow)))
+            }
+            crate::app::FinishAlbumMode::Off => {}
+        }
+        let suffix_w: usize = album_suffix.iter().map(|s| s.width()).sum();
+
+        let mut album_line = vec![
+            Span::raw("💿 "),
+            Span::styled(
+                truncate(&track.album, max_width.saturating_sub(2 + suffix_w)),
+                Style::default().fg(theme.cyan).add_modifier(Modifier::DIM),
+            ),
+        ];
+        album_line.extend(album_suffix);
+
         let mut info_text = vec![
             Line::from(Span::styled(
                 format!("🎵 {}", truncate(&track.name, max_width.saturating_sub(2))),
@@ -171,20 +203,39 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
                     Style::default().fg(theme.magenta),
                 ),
             ]),
-            Line::from(vec![
-                Span::raw("💿 "),
+            Line::from(album_line),
+        ];
+
+        // Chapter playing now 📖 (audiobooks, CUE albums)
+        if let Some(i) = app.current_chapter() {
+            let count = format!(" · {}/{}", i + 1, app.chapters.len());
+            info_text.push(Line::from(vec![
+                Span::raw("📖 "),
                 Span::styled(
-                    truncate(&track.album, max_width.saturating_sub(2)),
-                    Style::default().fg(theme.cyan).add_modifier(Modifier::DIM),
+                    truncate(
+                        &app.chapters[i].title,
+                        max_width.saturating_sub(2 + count.len()),
+                    ),
+                    Style::default().fg(theme.yellow),
                 ),
-            ]),
-        ];
+                Span::styled(count, Style::default().fg(theme.overlay)),
+            ]));
+        }
 
         // Add audio badge if available
         if let Some(badge) = audio_badge {
             info_text.push(badge);
         }
 
+        // Pitch shift 🎼 (only shown while active)
+        let semitones = app.pitch.get();
+        if semitones != 0 {
+            info_text.push(Line::from(Span::styled(
+                format!("🎼 Pitch {:+} st", semitones),
+                Style::default().fg(theme.yellow),
+            )));
+        }
+
         let info = Paragraph::new(info_text)
             .alignment(Alignment::Center)
             .block(Block::default().style(Style::default().bg(Color::Reset)));
@@ -0,0 +1,104 @@
+use crate::app::{App, ChannelMeter};
+use crate::ui::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use std::time::Instant;
+
+/// Meter scale (dBFS) from the left edge to the right edge
+const FLOOR_DB: f32 = -60.0;
+
+fn level_color(theme: &Theme, db: f32) -> Color {
+    if db >= -6.0 {
+        theme.red
+    } else if db >= -18.0 {
+        theme.yellow
+    } else {
+        theme.green
+    }
+}
+
+/// Number of cells lit for a level
+fn cells(db: f32, width: usize) -> usize {
+    (((db - FLOOR_DB) / -FLOOR_DB).clamp(0.0, 1.0) * width as f32).round() as usize
+}
+
+/// One channel for the audio-info popup:
+/// `L ████▒▒▒···│·· -12.3 CLIP` (solid = RMS, shaded = peak, bar = hold)
+pub fn channel_line<'a>(
+    theme: &Theme,
+    label: &'a str,
+    meter: &ChannelMeter,
+    width: usize,
+    now: Instant,
+) -> Line<'a> {
+    let rms = cells(meter.rms_db, width);
+    let peak = cells(meter.peak_db, width).max(rms);
+    let hold = cells(meter.hold_db, width);
+
+    let mut spans = vec![Span::styled(
+        format!("  {} ", label),
+        Style::default().fg(theme.overlay),
+    )];
+    for i in 0..width {
+        // Color by the position of the cell, like a hardware meter
+        let cell_db = FLOOR_DB + (i as f32 + 1.0) / width as f32 * -FLOOR_DB;
+        let color = level_color(theme, cell_db);
+        let span = if i < rms {
+            Span::styled("█", Style::default().fg(color))
+        } else if i < peak {
+            Span::styled("▒", Style::default().fg(color))
+        } else if hold > 0 && i == hold - 1 {
+            Span::styled("│", Style::default().fg(color))
+        } else {
+            Span::styled("·", Style::default().fg(theme.surface))
+        };
+        spans.push(span);
+    }
+    spans.push(Span::styled(
+        format!(" {:>5.1}", meter.hold_db.max(-90.0)),
+        Style::default().fg(theme.text),
+    ));
+    if meter.is_clipping(now) {
+        spans.push(Span::styled(
+            " CLIP",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Slim single-row stereo meter for the player card (top half = L, bottom half = R)
+pub fn render_slim(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let now = Instant::now();
+    let [left, right] = &app.meters.channels;
+    let clipping = left.is_clipping(now) || right.is_clipping(now);
+
+    let width = area.width.saturating_sub(4) as usize;
+    let l = cells(left.peak_db, width);
+    let r = cells(right.peak_db, width);
+
+    let mut spans = vec![Span::raw("  ")];
+    for i in 0..width {
+        let cell_db = FLOOR_DB + (i as f32 + 1.0) / width as f32 * -FLOOR_DB;
+        let color = if clipping && i + 1 == width {
+            theme.red
+        } else {
+            level_color(theme, cell_db)
+        };
+        let glyph = match (i < l, i < r) {
+            (true, true) => "█",
+            (true, false) => "▀",
+            (false, true) => "▄",
+            (false, false) => " ",
+        };
+        spans.push(Span::styled(glyph, Style::default().fg(color)));
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
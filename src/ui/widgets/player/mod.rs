@@ -1,3 +1,4 @@
+use crate::app::connection::ConnectionState;
 use crate::app::App;
 use ratatui::{
     layout::Alignment,
@@ -11,18 +12,36 @@ use ratatui::{
 };
 
 pub mod art;
+pub mod backdrop;
 pub mod controls;
 pub mod info;
+pub mod meter;
 pub mod progress;
 
 pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
     let theme = &app.theme;
 
     // --- MUSIC CARD ---
-    let music_title = Line::from(vec![Span::styled(
+    let mut title = vec![Span::styled(
         " Now Playing ",
         Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
-    )]);
+    )];
+    // MPD connection dot
+    if app.is_mpd {
+        let color = match app.connection {
+            ConnectionState::Connected => theme.green,
+            ConnectionState::Reconnecting { .. } => theme.yellow,
+            ConnectionState::Down { .. } => theme.red,
+        };
+        title.push(Span::styled(
+            format!("{} ", app.connection.icon()),
+            Style::default().fg(color),
+        ));
+    }
+    if app.party_mode {
+        title.push(Span::styled("🎉 ", Style::default().fg(theme.magenta)));
+    }
+    let music_title = Line::from(title);
 
     let music_block = Block::default()
         .borders(Borders::ALL)
@@ -98,6 +117,11 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         info::render(f, music_chunks[info_idx], app);
     }
 
+    // Slim level meter lives in the spacer row (opt-in via config)
+    if app.show_level_meter && m_height >= 10 && music_chunks[2].height > 0 {
+        meter::render_slim(f, music_chunks[2], app);
+    }
+
     // 3. Gauge
     // Cramped mode logic reused from chunks layout
     let gauge_idx = if m_height < 10 { 2 } else { 3 };
@@ -116,4 +140,9 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
     if controls_idx < music_chunks.len() && music_chunks[controls_idx].height > 0 {
         controls::render(f, music_chunks[controls_idx], app);
     }
+
+    // Last, so it only fills what the widgets above left blank
+    if app.show_art_backdrop {
+        backdrop::render(f, inner_music_area, app);
+    }
 }
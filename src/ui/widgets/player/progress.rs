@@ -1,7 +1,8 @@
+use crate::app::markers::MarkerKind;
 use crate::app::App;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Paragraph},
     Frame,
@@ -32,9 +33,56 @@ pub fn render_progress(f: &mut Frame, area: Rect, app: &mut App) {
         let fill_style = Style::default().fg(theme.magenta);
         let empty_style = Style::default().fg(theme.surface);
 
+        // Seek mode: where Enter would land
+        let ghost = app
+            .seek_preview
+            .filter(|_| track.duration_ms > 0)
+            .map(|ms| {
+                let ghost_ratio = ms as f64 / track.duration_ms as f64;
+                ((width as f64 * ghost_ratio.clamp(0.0, 1.0)) as usize)
+                    .saturating_sub(1)
+                    .min(width.saturating_sub(1))
+            });
+
+        // A-B loop ends
+        let cell = |ms: u64| {
+            let r = ms as f64 / track.duration_ms.max(1) as f64;
+            ((width as f64 * r.clamp(0.0, 1.0)) as usize).min(width.saturating_sub(1))
+        };
+        let loop_a = app.ab_loop.a.map(cell);
+        let loop_b = app.ab_loop.b.map(cell);
+        let loop_style = Style::default()
+            .fg(theme.yellow)
+            .add_modifier(Modifier::BOLD);
+
+        // Structure points; a bookmark wins over a chapter over a section
+        let mut marks: Vec<Option<MarkerKind>> = vec![None; width];
+        for marker in app.markers().iter().filter(|m| m.ms > 0 && width > 0) {
+            let slot = &mut marks[cell(marker.ms)];
+            *slot = (*slot).max(Some(marker.kind));
+        }
+
         let mut bar_spans: Vec<Span> = Vec::with_capacity(width);
-        for i in 0..width {
-            if i < occupied_width {
+        for (i, mark) in marks.iter().enumerate() {
+            if ghost == Some(i) {
+                bar_spans.push(Span::styled(
+                    "◆",
+                    Style::default()
+                        .fg(theme.yellow)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            } else if loop_a == Some(i) {
+                bar_spans.push(Span::styled("[", loop_style));
+            } else if loop_b == Some(i) {
+                bar_spans.push(Span::styled("]", loop_style));
+            } else if let Some(kind) = mark.filter(|_| i + 1 != occupied_width) {
+                let (glyph, color) = match kind {
+                    MarkerKind::Bookmark => ("▼", theme.green),
+                    MarkerKind::Chapter => ("┃", theme.cyan),
+                    MarkerKind::Section => ("╏", theme.overlay),
+                };
+                bar_spans.push(Span::styled(glyph, Style::default().fg(color)));
+            } else if i < occupied_width {
                 if i == occupied_width.saturating_sub(1) {
                     // Playhead knob
                     bar_spans.push(Span::styled("●", fill_style));
@@ -59,6 +107,29 @@ pub fn render_time(f: &mut Frame, area: Rect, app: &mut App) {
     let theme = &app.theme;
     if let Some(track) = &app.track {
         let current_pos = app.get_current_position_ms();
+        if let Some(target) = app.seek_preview {
+            // Seek mode: show the target and how far it is from here
+            let delta = (target as i64 - current_pos as i64) / 1000;
+            let preview = Line::from(vec![
+                Span::styled(
+                    format!("→ {:02}:{:02}", target / 60000, (target % 60000) / 1000),
+                    Style::default()
+                        .fg(theme.yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(
+                        " ({:+}s) / {:02}:{:02}",
+                        delta,
+                        track.duration_ms / 60000,
+                        (track.duration_ms % 60000) / 1000
+                    ),
+                    Style::default().fg(theme.overlay),
+                ),
+            ]);
+            f.render_widget(Paragraph::new(preview).alignment(Alignment::Center), area);
+            return;
+        }
         let time_str = format!(
             "{:02}:{:02} / {:02}:{:02}",
             current_pos / 60000,
@@ -66,9 +137,18 @@ pub fn render_time(f: &mut Frame, area: Rect, app: &mut App) {
             track.duration_ms / 60000,
             (track.duration_ms % 60000) / 1000
         );
-        let time_label = Paragraph::new(time_str)
-            .alignment(Alignment::Center)
-            .style(Style::default().fg(theme.overlay));
+        let mmss = |ms: u64| format!("{:02}:{:02}", ms / 60000, (ms % 60000) / 1000);
+        let looping = match (app.ab_loop.a, app.ab_loop.range()) {
+            (_, Some((a, b))) => format!("  🔁 {}–{}", mmss(a), mmss(b)),
+            (Some(a), None) => format!("  🔁 {}–", mmss(a)),
+            (None, None) => String::new(),
+        };
+        let time_label = Paragraph::new(Line::from(vec![
+            Span::raw(time_str),
+            Span::styled(looping, Style::default().fg(theme.yellow)),
+        ]))
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.overlay));
         f.render_widget(time_label, area);
     }
 }
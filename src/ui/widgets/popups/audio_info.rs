@@ -1,15 +1,19 @@
 use crate::app::App;
+use crate::audio::output::BitDepth;
 use crate::player::RepeatMode;
+use crate::t;
+use crate::ui::utils::clear_popup_area;
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    widgets::{Block, BorderType, Borders, Paragraph},
     Frame,
 };
 
 pub fn render(f: &mut Frame, app: &App) {
     let theme = &app.theme;
+    let width = 60.min(f.area().width.saturating_sub(4));
 
     // 1. Generate Content First
     let mut lines: Vec<Line> = Vec::new();
@@ -18,7 +22,7 @@ pub fn render(f: &mut Frame, app: &App) {
     lines.push(Line::from(vec![
         Span::styled("♫ ", Style::default().fg(theme.magenta)),
         Span::styled(
-            "Track",
+            t!("audio_info.section_track"),
             Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         ),
     ]));
@@ -26,15 +30,15 @@ pub fn render(f: &mut Frame, app: &App) {
     // Current song info from TrackInfo
     if let Some(ref track) = app.track {
         lines.push(Line::from(vec![
-            Span::styled("  Title: ", Style::default().fg(theme.overlay)),
+            Span::styled(t!("audio_info.title"), Style::default().fg(theme.overlay)),
             Span::styled(&track.name, Style::default().fg(theme.text)),
         ]));
         lines.push(Line::from(vec![
-            Span::styled("  Artist: ", Style::default().fg(theme.overlay)),
+            Span::styled(t!("audio_info.artist"), Style::default().fg(theme.overlay)),
             Span::styled(&track.artist, Style::default().fg(theme.text)),
         ]));
         lines.push(Line::from(vec![
-            Span::styled("  Album: ", Style::default().fg(theme.overlay)),
+            Span::styled(t!("audio_info.album"), Style::default().fg(theme.overlay)),
             Span::styled(&track.album, Style::default().fg(theme.text)),
         ]));
 
@@ -51,13 +55,13 @@ pub fn render(f: &mut Frame, app: &App) {
                 format_parts.push(format!("{} kbps", bitrate));
             }
             lines.push(Line::from(vec![
-                Span::styled("  Format: ", Style::default().fg(theme.overlay)),
+                Span::styled(t!("audio_info.format"), Style::default().fg(theme.overlay)),
                 Span::styled(format_parts.join(" / "), Style::default().fg(theme.green)),
             ]));
         }
     } else {
         lines.push(Line::from(vec![Span::styled(
-            "  No track playing",
+            t!("audio_info.no_track"),
             Style::default().fg(theme.overlay),
         )]));
     }
@@ -65,20 +69,31 @@ pub fn render(f: &mut Frame, app: &App) {
     // Lyrics Source Info (Requested by User) 🎤
     if let crate::app::LyricsState::Loaded(_, source) = &app.lyrics {
         lines.push(Line::from(vec![
-            Span::styled("  Lyrics: ", Style::default().fg(theme.overlay)),
+            Span::styled(t!("audio_info.lyrics"), Style::default().fg(theme.overlay)),
             Span::styled(source, Style::default().fg(theme.cyan)),
         ]));
+    } else if let crate::app::LyricsState::Plain(_, source) = &app.lyrics {
+        lines.push(Line::from(vec![
+            Span::styled(t!("audio_info.lyrics"), Style::default().fg(theme.overlay)),
+            Span::styled(
+                t!("audio_info.unsynced", source = source),
+                Style::default().fg(theme.cyan),
+            ),
+        ]));
     } else if let crate::app::LyricsState::Instrumental = &app.lyrics {
         lines.push(Line::from(vec![
-            Span::styled("  Lyrics: ", Style::default().fg(theme.overlay)),
-            Span::styled("Instrumental", Style::default().fg(theme.yellow)),
+            Span::styled(t!("audio_info.lyrics"), Style::default().fg(theme.overlay)),
+            Span::styled(
+                t!("audio_info.instrumental"),
+                Style::default().fg(theme.yellow),
+            ),
         ]));
     }
 
     // Queue position
     let queue_pos = format!("{} / {}", app.library_selected + 1, app.queue.len());
     lines.push(Line::from(vec![
-        Span::styled("  Queue: ", Style::default().fg(theme.overlay)),
+        Span::styled(t!("audio_info.queue"), Style::default().fg(theme.overlay)),
         Span::styled(queue_pos, Style::default().fg(theme.text)),
     ]));
 
@@ -88,7 +103,7 @@ pub fn render(f: &mut Frame, app: &App) {
     lines.push(Line::from(vec![
         Span::styled("▶ ", Style::default().fg(theme.green)),
         Span::styled(
-            "Playback",
+            t!("audio_info.section_playback"),
             Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         ),
     ]));
@@ -98,9 +113,13 @@ pub fn render(f: &mut Frame, app: &App) {
         .as_ref()
         .map(|t| t.state == crate::player::PlayerState::Paused)
         .unwrap_or(true);
-    let status = if is_paused { "Paused" } else { "Playing" };
+    let status = if is_paused {
+        t!("audio_info.paused")
+    } else {
+        t!("audio_info.playing")
+    };
     lines.push(Line::from(vec![
-        Span::styled("  Status: ", Style::default().fg(theme.overlay)),
+        Span::styled(t!("audio_info.status"), Style::default().fg(theme.overlay)),
         Span::styled(
             status,
             Style::default().fg(if is_paused { theme.yellow } else { theme.green }),
@@ -109,9 +128,13 @@ pub fn render(f: &mut Frame, app: &App) {
 
     // Shuffle/Repeat only shown in MPD mode (not available in controller)
     if app.is_mpd {
-        let shuffle_str = if app.shuffle { "ON" } else { "OFF" };
+        let shuffle_str = if app.shuffle {
+            t!("common.on")
+        } else {
+            t!("common.off")
+        };
         lines.push(Line::from(vec![
-            Span::styled("  Shuffle: ", Style::default().fg(theme.overlay)),
+            Span::styled(t!("audio_info.shuffle"), Style::default().fg(theme.overlay)),
             Span::styled(
                 shuffle_str,
                 Style::default().fg(if app.shuffle {
@@ -123,13 +146,13 @@ pub fn render(f: &mut Frame, app: &App) {
         ]));
 
         let repeat_str = match app.repeat {
-            RepeatMode::Off => "OFF",
-            RepeatMode::Playlist => "All",
-            RepeatMode::Single => "One",
+            RepeatMode::Off => t!("common.off"),
+            RepeatMode::Playlist => t!("audio_info.repeat_all"),
+            RepeatMode::Single => t!("audio_info.repeat_one"),
         };
 
         lines.push(Line::from(vec![
-            Span::styled("  Repeat: ", Style::default().fg(theme.overlay)),
+            Span::styled(t!("audio_info.repeat"), Style::default().fg(theme.overlay)),
             Span::styled(
                 repeat_str,
                 Style::default().fg(if app.repeat != RepeatMode::Off {
@@ -149,18 +172,21 @@ pub fn render(f: &mut Frame, app: &App) {
         lines.push(Line::from(vec![
             Span::styled("🎛 ", Style::default().fg(theme.blue)),
             Span::styled(
-                "DSP / EQ",
+                t!("audio_info.section_dsp"),
                 Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
         ]));
 
         let eq_status = if app.eq_enabled {
-            "Enabled"
+            t!("audio_info.enabled")
         } else {
-            "Disabled"
+            t!("audio_info.disabled")
         };
         lines.push(Line::from(vec![
-            Span::styled("  Equalizer: ", Style::default().fg(theme.overlay)),
+            Span::styled(
+                t!("audio_info.equalizer"),
+                Style::default().fg(theme.overlay),
+            ),
             Span::styled(
                 eq_status,
                 Style::default().fg(if app.eq_enabled {
@@ -172,7 +198,7 @@ pub fn render(f: &mut Frame, app: &App) {
         ]));
 
         lines.push(Line::from(vec![
-            Span::styled("  Preset: ", Style::default().fg(theme.overlay)),
+            Span::styled(t!("audio_info.preset"), Style::default().fg(theme.overlay)),
             Span::styled(app.get_preset_name(), Style::default().fg(theme.magenta)),
         ]));
 
@@ -182,97 +208,164 @@ pub fn render(f: &mut Frame, app: &App) {
         lines.push(Line::from(vec![
             Span::styled("🔊 ", Style::default().fg(theme.yellow)),
             Span::styled(
-                "Output",
+                t!("audio_info.section_output"),
                 Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
         ]));
         lines.push(Line::from(vec![
-            Span::styled("  Device: ", Style::default().fg(theme.overlay)),
+            Span::styled(t!("audio_info.device"), Style::default().fg(theme.overlay)),
             Span::styled(&app.output_device, Style::default().fg(theme.cyan)),
         ]));
 
+        // What the device was actually opened with
+        let negotiated = app.output.negotiated();
+        if let Some(format) = negotiated {
+            let mut parts = vec![
+                format!("{:.1} kHz", format.sample_rate as f32 / 1000.0),
+                match format.depth {
+                    BitDepth::Float32 => t!("audio_info.float"),
+                    depth => format!("{} bit", depth.label()),
+                },
+                format!("{} ch", format.channels),
+            ];
+            if format.depth.full_scale().is_some() && app.output.dither() {
+                parts.push(t!("audio_info.dither"));
+            }
+            let mut spans = vec![
+                Span::styled(t!("audio_info.stream"), Style::default().fg(theme.overlay)),
+                Span::styled(parts.join(" / "), Style::default().fg(theme.green)),
+            ];
+            if format.sample_rate != format.source_rate {
+                spans.push(Span::styled(
+                    t!(
+                        "audio_info.resampled_from",
+                        rate = format!("{:.1}", format.source_rate as f32 / 1000.0)
+                    ),
+                    Style::default().fg(theme.yellow),
+                ));
+            }
+            if format.depth != app.output.depth() {
+                spans.push(Span::styled(
+                    t!(
+                        "audio_info.depth_refused",
+                        depth = app.output.depth().label()
+                    ),
+                    Style::default().fg(theme.red),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let resampled = negotiated.is_some_and(|f| f.sample_rate != f.source_rate);
         let (mode_text, mode_color) = if app.eq_enabled {
-            ("DSP Active (EQ Enabled)", theme.yellow)
+            (t!("audio_info.dsp_active"), theme.yellow)
+        } else if resampled {
+            (t!("audio_info.resampled"), theme.yellow)
         } else {
-            ("Bit-Perfect (No DSP)", theme.green)
+            (t!("audio_info.bit_perfect"), theme.green)
         };
         lines.push(Line::from(vec![
-            Span::styled("  Mode: ", Style::default().fg(theme.overlay)),
+            Span::styled(t!("audio_info.mode"), Style::default().fg(theme.overlay)),
             Span::styled(mode_text, Style::default().fg(mode_color)),
         ]));
 
         lines.push(Line::from(vec![
-            Span::styled("  Backend: ", Style::default().fg(theme.overlay)),
-            Span::styled("MPD", Style::default().fg(theme.text)),
+            Span::styled(t!("audio_info.backend"), Style::default().fg(theme.overlay)),
+            Span::styled(
+                if negotiated.is_some_and(|f| f.pipewire) {
+                    "MPD → PipeWire"
+                } else {
+                    "MPD"
+                },
+                Style::default().fg(theme.text),
+            ),
+        ]));
+
+        lines.push(Line::from(""));
+
+        // Level Meters Section 📶
+        lines.push(Line::from(vec![
+            Span::styled("📶 ", Style::default().fg(theme.green)),
+            Span::styled(
+                t!("audio_info.section_levels"),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                t!("audio_info.meter_legend"),
+                Style::default().fg(theme.surface),
+            ),
         ]));
+        // Borders (2) + label (4) + value (6) + clip (5)
+        let bar_w = (width as usize).saturating_sub(17);
+        let now = std::time::Instant::now();
+        for (label, meter) in ["L", "R"].into_iter().zip(app.meters.channels.iter()) {
+            lines.push(crate::ui::widgets::player::meter::channel_line(
+                theme, label, meter, bar_w, now,
+            ));
+        }
     } else {
         // Streaming Source Section (Controller mode)
         lines.push(Line::from(vec![
             Span::styled("📡 ", Style::default().fg(theme.blue)),
             Span::styled(
-                "Source",
+                t!("audio_info.section_source"),
                 Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
         ]));
 
         lines.push(Line::from(vec![
-            Span::styled("  Streaming: ", Style::default().fg(theme.overlay)),
+            Span::styled(
+                t!("audio_info.streaming"),
+                Style::default().fg(theme.overlay),
+            ),
             Span::styled(&app.source_app, Style::default().fg(theme.green)),
         ]));
 
         lines.push(Line::from(vec![
-            Span::styled("  Mode: ", Style::default().fg(theme.overlay)),
-            Span::styled("Controller", Style::default().fg(theme.magenta)),
+            Span::styled(t!("audio_info.mode"), Style::default().fg(theme.overlay)),
+            Span::styled(
+                t!("audio_info.controller"),
+                Style::default().fg(theme.magenta),
+            ),
         ]));
     }
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
-        Span::styled("  Press ", Style::default().fg(theme.surface)),
+        Span::styled(t!("audio_info.press"), Style::default().fg(theme.surface)),
         Span::styled(
             "i",
             Style::default()
                 .fg(theme.green)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(" or ", Style::default().fg(theme.surface)),
+        Span::styled(t!("audio_info.or"), Style::default().fg(theme.surface)),
         Span::styled(
             "Esc",
             Style::default()
                 .fg(theme.green)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(" to close", Style::default().fg(theme.surface)),
+        Span::styled(
+            t!("audio_info.to_close"),
+            Style::default().fg(theme.surface),
+        ),
     ]));
 
     // 2. Calculate Scalable Height
     let height = (lines.len() as u16 + 2).min(f.area().height.saturating_sub(4)); // +2 for borders
-    let width = 60.min(f.area().width.saturating_sub(4));
     let x = (f.area().width.saturating_sub(width)) / 2;
     let y = (f.area().height.saturating_sub(height)) / 2;
     let area = Rect::new(x, y, width, height);
 
-    // SEAMLESS Z-INDEX FIX: Un-skip cells so Ratatui overwrites Kitty images
-    let buf = f.buffer_mut();
-    for y in area.top()..area.bottom() {
-        for x in area.left()..area.right() {
-            if let Some(cell) = buf.cell_mut((x, y)) {
-                cell.set_skip(false);
-                cell.set_char(' ');
-                cell.set_bg(ratatui::style::Color::Reset);
-                cell.set_fg(ratatui::style::Color::Reset);
-            }
-        }
-    }
-
     // 3. Clear and Render
-    f.render_widget(Clear, area);
+    clear_popup_area(f.buffer_mut(), area);
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(theme.blue))
-        .title(" Audio Info ")
+        .title(t!("audio_info.heading"))
         .title_alignment(Alignment::Left)
         .style(Style::default().bg(Color::Reset));
 
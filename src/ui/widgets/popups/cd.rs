@@ -0,0 +1,153 @@
+use crate::app::cd::CdState;
+use crate::app::App;
+use crate::t;
+use crate::ui::utils::{clear_popup_area, fit, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(view) = &app.cd else {
+        return;
+    };
+    let theme = &app.theme;
+    let tracks = view.disc().map_or(0, |d| d.toc.tracks.len());
+
+    let width = f.area().width.saturating_sub(4).min(72);
+    let height = f
+        .area()
+        .height
+        .saturating_sub(4)
+        .min(tracks.max(1) as u16 + 7);
+    if width < 30 || height < 8 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let inner = (width as usize).saturating_sub(2);
+    let muted = Style::default().fg(theme.overlay);
+    let mut lines: Vec<Line> = Vec::new();
+    match &view.state {
+        CdState::Reading => lines.push(Line::from(Span::styled(t!("cd.reading"), muted))),
+        CdState::Failed(e) => lines.push(Line::from(Span::styled(
+            t!("cd.failed", error = e),
+            Style::default().fg(theme.red),
+        ))),
+        CdState::Ready(disc) => {
+            // Which release names the tracks
+            let release = disc.release(view.release);
+            let heading = match (release, &disc.releases) {
+                (Some(r), _) => {
+                    let mut heading = t!("cd.release", artist = r.artist, album = r.title);
+                    if let Some(year) = &r.year {
+                        heading = format!("{} ({})", heading, year);
+                    }
+                    heading
+                }
+                (None, Err(e)) => t!("cd.lookup_failed", error = e),
+                (None, Ok(_)) => t!("cd.unknown", tracks = tracks),
+            };
+            lines.push(Line::from(Span::styled(
+                truncate(&heading, inner),
+                Style::default()
+                    .fg(theme.yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            let count = disc.releases.as_ref().map_or(0, Vec::len);
+            lines.push(Line::from(Span::styled(
+                if count > 1 {
+                    t!("cd.releases", n = view.release + 1, count = count)
+                } else {
+                    String::new()
+                },
+                muted,
+            )));
+
+            let rows = (height as usize).saturating_sub(6);
+            let start = view
+                .selected
+                .saturating_sub(rows.saturating_sub(1))
+                .min(tracks.saturating_sub(rows));
+            let streams = disc.streams(release);
+            for (i, stream) in streams.iter().enumerate().skip(start).take(rows) {
+                let style = if i == view.selected {
+                    Style::default()
+                        .fg(theme.magenta)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let marker = if i == view.selected { "▶ " } else { "  " };
+                let secs = stream.duration_ms / 1000;
+                let length = format!(" {}:{:02}", secs / 60, secs % 60);
+                lines.push(Line::from(vec![
+                    Span::styled(marker, style),
+                    Span::styled(
+                        format!("{:>2}  ", disc.toc.tracks[i].number),
+                        Style::default().fg(theme.cyan),
+                    ),
+                    Span::styled(
+                        fit(&stream.title, inner.saturating_sub(6 + length.len())),
+                        style,
+                    ),
+                    Span::styled(length, muted),
+                ]));
+            }
+        }
+    }
+
+    // Pin the hint row to the bottom
+    for _ in lines.len()..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    let key = |k: &'static str, color: Color| {
+        Span::styled(k, Style::default().fg(color).add_modifier(Modifier::BOLD))
+    };
+    let mut hints = Vec::new();
+    if tracks > 0 {
+        hints.extend([
+            key("Enter", theme.green),
+            Span::styled(t!("hint.play"), muted),
+            key("a", theme.blue),
+            Span::styled(t!("hint.queue"), muted),
+            key("r", theme.yellow),
+            Span::styled(t!("hint.rip"), muted),
+        ]);
+    }
+    if view
+        .disc()
+        .is_some_and(|d| d.releases.as_ref().map_or(0, Vec::len) > 1)
+    {
+        hints.extend([
+            key("Tab", theme.cyan),
+            Span::styled(t!("hint.next_release"), muted),
+        ]);
+    }
+    hints.extend([key("Esc", theme.red), Span::styled(t!("hint.close"), muted)]);
+    lines.push(Line::from(hints));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("cd.title"),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
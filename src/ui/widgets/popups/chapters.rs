@@ -0,0 +1,107 @@
+use crate::app::App;
+use crate::t;
+use crate::ui::utils::{clear_popup_area, fit};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(selected) = app.chapter_list else {
+        return;
+    };
+    let theme = &app.theme;
+
+    let width = f.area().width.saturating_sub(4).min(60);
+    let height = f
+        .area()
+        .height
+        .saturating_sub(4)
+        .min(app.chapters.len() as u16 + 4);
+    if width < 24 || height < 5 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    // Keep the selection in view
+    let rows = height.saturating_sub(4) as usize;
+    let start = selected
+        .saturating_sub(rows.saturating_sub(1))
+        .min(app.chapters.len().saturating_sub(rows));
+    let playing = app.current_chapter();
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, chapter) in app.chapters.iter().enumerate().skip(start).take(rows) {
+        let style = if i == selected {
+            Style::default()
+                .fg(theme.magenta)
+                .add_modifier(Modifier::BOLD)
+        } else if playing == Some(i) {
+            Style::default().fg(theme.green)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let marker = match (i == selected, playing == Some(i)) {
+            (true, _) => "▶ ",
+            (false, true) => "♪ ",
+            _ => "  ",
+        };
+        let ms = chapter.start_ms;
+        let time = if ms >= 3_600_000 {
+            format!(
+                "{}:{:02}:{:02}",
+                ms / 3_600_000,
+                (ms % 3_600_000) / 60000,
+                (ms % 60000) / 1000
+            )
+        } else {
+            format!("{:02}:{:02}", ms / 60000, (ms % 60000) / 1000)
+        };
+        let title_width = (width as usize).saturating_sub(2 + 2 + time.len() + 1);
+        lines.push(Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(fit(&chapter.title, title_width), style),
+            Span::styled(format!(" {}", time), Style::default().fg(theme.overlay)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            "Enter",
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.go"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.close"), Style::default().fg(theme.overlay)),
+    ]));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("chapters.title", count = app.chapters.len()),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
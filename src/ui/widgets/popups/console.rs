@@ -0,0 +1,91 @@
+use crate::app::{App, ConsoleLineKind};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let console = &app.console;
+
+    let width = (f.area().width * 4 / 5).max(40).min(f.area().width);
+    let height = (f.area().height * 7 / 10).max(8).min(f.area().height);
+    let x = (f.area().width.saturating_sub(width)) / 2;
+    let y = (f.area().height.saturating_sub(height)) / 2;
+    let area = Rect::new(x, y, width, height);
+
+    f.render_widget(Clear, area);
+
+    // Borders (2) + input line (1)
+    let visible = height.saturating_sub(3) as usize;
+    let end = console.output.len().saturating_sub(console.scroll);
+    let start = end.saturating_sub(visible);
+
+    let mut lines: Vec<Line> = console.output[start..end]
+        .iter()
+        .map(|(kind, text)| {
+            let style = match kind {
+                ConsoleLineKind::Command => {
+                    Style::default().fg(theme.blue).add_modifier(Modifier::BOLD)
+                }
+                ConsoleLineKind::Response => Style::default().fg(theme.text),
+                ConsoleLineKind::Error => Style::default().fg(theme.red),
+                ConsoleLineKind::Info => Style::default().fg(theme.yellow),
+            };
+            Line::from(Span::styled(text.as_str(), style))
+        })
+        .collect();
+
+    // Pad so the prompt stays pinned to the bottom
+    while lines.len() < visible {
+        lines.insert(0, Line::from(""));
+    }
+
+    let prompt = if console.pending_confirm.is_some() {
+        Span::styled(
+            " y/N ",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::styled(
+            " mpd> ",
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    lines.push(Line::from(vec![
+        prompt,
+        Span::styled(console.input.as_str(), Style::default().fg(theme.text)),
+        Span::styled(
+            "▌",
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::SLOW_BLINK),
+        ),
+    ]));
+
+    let scroll_hint = if console.scroll > 0 {
+        format!(" ↑{} ", console.scroll)
+    } else {
+        String::new()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.magenta))
+        .title(format!(" MPD Console{} ", scroll_hint))
+        .title_alignment(Alignment::Left)
+        .title_bottom(
+            Line::from(" Enter Send · ↑↓ History · PgUp/PgDn Scroll · C-l Clear · Esc Close ")
+                .style(Style::default().fg(theme.overlay))
+                .alignment(Alignment::Right),
+        )
+        .style(Style::default().bg(Color::Reset));
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,150 @@
+use crate::app::downloads::{Transfer, TransferKind, TransferState};
+use crate::app::App;
+use crate::t;
+use crate::ui::theme::Theme;
+use crate::ui::utils::{clear_popup_area, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(selected) = app.downloads_view else {
+        return;
+    };
+    let theme = &app.theme;
+    let list = &app.downloads.list;
+
+    let width = f.area().width.saturating_sub(4).min(80);
+    let height = f
+        .area()
+        .height
+        .saturating_sub(4)
+        .min(list.len().max(1) as u16 + 4);
+    if width < 30 || height < 5 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let rows = height.saturating_sub(4) as usize;
+    let start = selected
+        .saturating_sub(rows.saturating_sub(1))
+        .min(list.len().saturating_sub(rows));
+
+    let mut lines: Vec<Line> = Vec::new();
+    if list.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t!("downloads.empty"),
+            Style::default().fg(theme.overlay),
+        )));
+    }
+    for (i, transfer) in list.iter().enumerate().skip(start).take(rows) {
+        let icon = match transfer.kind {
+            TransferKind::Download => "⬇",
+            TransferKind::Recording => "⏺",
+            TransferKind::Rip => "💿",
+        };
+        let (status, color) = status(transfer, theme);
+        let status = truncate(&format!(" {}", status), width as usize / 2);
+        let room = (width as usize).saturating_sub(2 + 4 + status.width());
+        let style = if i == selected {
+            Style::default()
+                .fg(theme.magenta)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let marker = if i == selected { "▶ " } else { "  " };
+        lines.push(Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(format!("{} ", icon), Style::default().fg(color)),
+            Span::styled(truncate(&transfer.title, room), style),
+            Span::styled(status, Style::default().fg(color)),
+        ]));
+    }
+
+    // Pin the hint row to the bottom
+    for _ in lines.len()..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(
+            "x",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.stop"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "c",
+            Style::default()
+                .fg(theme.yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            t!("hint.clear_finished"),
+            Style::default().fg(theme.overlay),
+        ),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.close"), Style::default().fg(theme.overlay)),
+    ]));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("downloads.title", count = app.downloads.running()),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
+
+/// "12.5 / 40.0 MB", "3 songs · 45.2 MB", "saved to ...", the error
+fn status(transfer: &Transfer, theme: &Theme) -> (String, Color) {
+    let mb = |bytes: u64| bytes as f64 / 1_048_576.0;
+    match &transfer.state {
+        TransferState::Running => {
+            let text = match (transfer.kind, transfer.total) {
+                (TransferKind::Recording | TransferKind::Rip, _) => t!(
+                    "downloads.recorded",
+                    songs = transfer.songs,
+                    mb = format!("{:.1}", mb(transfer.bytes))
+                ),
+                (TransferKind::Download, Some(total)) if total > 0 => format!(
+                    "{:.1} / {:.1} MB ({}%)",
+                    mb(transfer.bytes),
+                    mb(total),
+                    transfer.bytes * 100 / total
+                ),
+                (TransferKind::Download, _) => format!("{:.1} MB", mb(transfer.bytes)),
+            };
+            (text, theme.green)
+        }
+        TransferState::Done => (
+            t!(
+                "downloads.saved",
+                path = transfer.path.as_deref().unwrap_or_default()
+            ),
+            theme.blue,
+        ),
+        TransferState::Failed(e) => (truncate(e, 40), theme.red),
+        TransferState::Stopped => (t!("downloads.stopped_status"), theme.overlay),
+    }
+}
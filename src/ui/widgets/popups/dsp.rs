@@ -0,0 +1,253 @@
+use crate::app::inputs::dsp::{control_rate, stage_name};
+use crate::app::App;
+use crate::audio::dsp::chain::StageKind;
+use crate::t;
+use crate::ui::utils::{clear_popup_area, fit};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(selected) = app.dsp_view else {
+        return;
+    };
+    let theme = &app.theme;
+    let stages = app.dsp.config().stages();
+    // Latency comes from the chain the audio thread is running
+    let report = app.dsp.report();
+
+    let width = f.area().width.saturating_sub(4).min(56);
+    let height = f
+        .area()
+        .height
+        .saturating_sub(4)
+        .min(stages.len().max(1) as u16 + 6);
+    if width < 30 || height < 7 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let inner = (width as usize).saturating_sub(2);
+    let muted = Style::default().fg(theme.overlay);
+    let ms = |frames: usize| match &report {
+        Some((rate, _)) if *rate > 0 => format!("{:.1} ms", frames as f64 * 1000.0 / *rate as f64),
+        _ => "—".to_string(),
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+    if stages.is_empty() {
+        lines.push(Line::from(Span::styled(t!("dsp.empty"), muted)));
+    }
+    let rows = (height as usize).saturating_sub(5);
+    let start = selected
+        .saturating_sub(rows.saturating_sub(1))
+        .min(stages.len().saturating_sub(rows));
+    for (i, &kind) in stages.iter().enumerate().skip(start).take(rows) {
+        let enabled = if kind == StageKind::Eq {
+            app.eq_enabled
+        } else {
+            app.dsp.is_enabled(kind)
+        };
+        let failed =
+            matches!(kind, StageKind::Plugin(i) if matches!(app.dsp.plugin(i), Some(Err(_))));
+        let latency = report
+            .as_ref()
+            .and_then(|(_, running)| running.iter().find(|s| s.kind == kind))
+            .map_or_else(|| "—".to_string(), |s| ms(s.latency_frames));
+        let style = if i == selected {
+            Style::default()
+                .fg(theme.magenta)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let marker = if i == selected { "▶ " } else { "  " };
+        let (state, color) = if failed {
+            (t!("dsp.failed"), theme.red)
+        } else if enabled {
+            (t!("common.on"), theme.green)
+        } else {
+            (t!("common.off"), theme.overlay)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(format!("{}  ", i + 1), Style::default().fg(theme.cyan)),
+            Span::styled(fit(&stage_name(app, kind), inner.saturating_sub(25)), style),
+            Span::styled(fit(&state, 6), Style::default().fg(color)),
+            Span::styled(format!("{:>10}", latency), muted),
+        ]));
+    }
+
+    // Pin the summary and hint rows to the bottom
+    for _ in lines.len()..(height as usize).saturating_sub(4) {
+        lines.push(Line::from(""));
+    }
+    let summary = match &report {
+        Some((rate, running)) => t!(
+            "dsp.summary",
+            rate = rate,
+            latency = ms(running.iter().map(|s| s.latency_frames).sum())
+        ),
+        None => t!("dsp.not_running"),
+    };
+    lines.push(Line::from(Span::styled(fit(&summary, inner), muted)));
+    let key = |k: &'static str, color: Color| {
+        Span::styled(k, Style::default().fg(color).add_modifier(Modifier::BOLD))
+    };
+    let mut hints = vec![
+        key("Space", theme.green),
+        Span::styled(t!("hint.toggle"), muted),
+    ];
+    if matches!(stages.get(selected), Some(StageKind::Plugin(_))) {
+        hints.extend([
+            key("Enter", theme.yellow),
+            Span::styled(t!("hint.controls"), muted),
+        ]);
+    }
+    hints.extend([key("Esc", theme.red), Span::styled(t!("hint.close"), muted)]);
+    lines.push(Line::from(hints));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("dsp.title"),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
+
+/// Sliders for one plugin's controls, over the chain popup
+pub fn render_controls(f: &mut Frame, app: &App) {
+    let Some((plugin, selected)) = app.plugin_controls else {
+        return;
+    };
+    let Some(Ok(loaded)) = app.dsp.plugin(plugin) else {
+        return;
+    };
+    let theme = &app.theme;
+    let controls = &loaded.info.controls;
+    let rate = control_rate(app);
+
+    let width = f.area().width.saturating_sub(4).min(72);
+    let height = f
+        .area()
+        .height
+        .saturating_sub(4)
+        .min(controls.len().max(1) as u16 + 5);
+    if width < 40 || height < 6 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let inner = (width as usize).saturating_sub(2);
+    let muted = Style::default().fg(theme.overlay);
+    let name_width = (inner / 3).min(24);
+    let bar_width = inner.saturating_sub(name_width + 14);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if controls.is_empty() {
+        lines.push(Line::from(Span::styled(t!("dsp.no_controls"), muted)));
+    }
+    let rows = (height as usize).saturating_sub(4);
+    let start = selected
+        .saturating_sub(rows.saturating_sub(1))
+        .min(controls.len().saturating_sub(rows));
+    for (i, control) in controls.iter().enumerate().skip(start).take(rows) {
+        let value = app.dsp.control(plugin, i);
+        let (min, max) = control.range(rate);
+        // Where the value sits, on the port's own scale
+        let share = if max <= min {
+            0.0
+        } else if control.logarithmic {
+            (value.max(min) / min).ln() / (max / min).ln()
+        } else {
+            (value - min) / (max - min)
+        };
+        let filled = ((share.clamp(0.0, 1.0) * bar_width as f32).round() as usize).min(bar_width);
+        let shown = if control.toggled {
+            if value > 0.0 {
+                t!("common.on")
+            } else {
+                t!("common.off")
+            }
+        } else if control.integer {
+            format!("{}", value.round())
+        } else {
+            format!("{:.2}", value)
+        };
+        let style = if i == selected {
+            Style::default()
+                .fg(theme.magenta)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let marker = if i == selected { "▶ " } else { "  " };
+        lines.push(Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(fit(&control.name, name_width), style),
+            Span::styled(" ", muted),
+            Span::styled("━".repeat(filled), Style::default().fg(theme.cyan)),
+            Span::styled("─".repeat(bar_width - filled), muted),
+            Span::styled(format!("{:>10}", shown), style),
+        ]));
+    }
+
+    // Pin the hint row to the bottom
+    for _ in lines.len()..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    let key = |k: &'static str, color: Color| {
+        Span::styled(k, Style::default().fg(color).add_modifier(Modifier::BOLD))
+    };
+    lines.push(Line::from(vec![
+        key("h/l", theme.green),
+        Span::styled(t!("hint.adjust"), muted),
+        key("d", theme.yellow),
+        Span::styled(t!("hint.default"), muted),
+        key("Esc", theme.red),
+        Span::styled(t!("hint.back"), muted),
+    ]));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!(
+                    "dsp.controls_title",
+                    name = app.dsp.stage_name(StageKind::Plugin(plugin)),
+                    plugin = loaded.info.name
+                ),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
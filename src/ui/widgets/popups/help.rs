@@ -1,114 +1,443 @@
-use crate::app::{App, ViewMode};
+use crate::app::App;
+use crate::t;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Modifier, Style},
-    text::Span,
+    text::{Line, Span},
     widgets::{Block, BorderType, Borders, Clear, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-pub fn render(f: &mut Frame, app: &App) {
-    let theme = &app.theme;
-
-    // 🎹 WhichKey-style floating popup (Helix-inspired, centered)
+/// One page of the help browser
+pub struct HelpPage {
+    /// Stable name for picking a page, whatever the language
+    pub id: &'static str,
+    pub title: String,
+    pub entries: Vec<(String, &'static str, String)>,
+}
 
-    // Get context-specific keybindings with icons
+/// Help pages generated from the keymap: Global, then one per context
+pub fn pages(app: &App) -> Vec<HelpPage> {
     // Use String for key display to support dynamic config
-    let (title, keys): (&str, Vec<(String, &str, &str)>) = match app.view_mode {
-        ViewMode::EQ => (
-            "EQ Controls",
-            vec![
+    let global = if app.is_mpd {
+        // MPD mode: full feature set
+        vec![
+            (
+                app.keys.display(&app.keys.play_pause),
+                "▶️",
+                t!("help.play_pause"),
+            ),
+            (
+                app.keys.display(&app.keys.next_track),
+                "⏭️",
+                t!("help.next_track"),
+            ),
+            (
+                app.keys.display(&app.keys.prev_track),
+                "⏮️",
+                t!("help.previous_track"),
+            ),
+            (
+                app.keys.display(&app.keys.shuffle),
+                "🔀",
+                t!("help.shuffle"),
+            ),
+            (
+                app.keys.display(&app.keys.shuffle_mode),
+                "🎲",
+                t!("help.shuffle_mode"),
+            ),
+            (app.keys.display(&app.keys.repeat), "🔁", t!("help.repeat")),
+            (
+                app.keys.display(&app.keys.finish_album),
+                "💿",
+                t!("help.finish_album"),
+            ),
+            (
+                format!(
+                    "{}/{}",
+                    app.keys.display(&app.keys.pitch_down),
+                    app.keys.display(&app.keys.pitch_up)
+                ),
+                "🎼",
+                t!("help.pitch_1_semitone"),
+            ),
+            (
+                app.keys.display(&app.keys.silence_trim),
+                "✂️",
+                t!("help.trim_silence_track"),
+            ),
+            (
+                app.keys.display(&app.keys.ab_loop),
+                "🔁",
+                t!("help.ab_loop"),
+            ),
+            (
+                app.keys.display(&app.keys.repeat_once),
+                "🔂",
+                t!("help.repeat_once"),
+            ),
+            (app.keys.display(&app.keys.resume), "⏯️", t!("help.resume")),
+            (
+                app.keys.display(&app.keys.search_global),
+                "🔍",
+                t!("help.search"),
+            ),
+            (
+                format!(
+                    "{}/{}",
+                    app.keys.display(&app.keys.volume_up),
+                    app.keys.display(&app.keys.volume_down)
+                ),
+                "🔊",
+                t!("help.volume"),
+            ),
+            (app.keys.display(&app.keys.mute), "🔇", t!("help.mute")),
+            (app.keys.display(&app.keys.duck), "🦆", t!("help.duck")),
+            (format!("1-{}", "6"), "🖼️", t!("help.view_modes")),
+            (
+                format!(
+                    "{}/{}",
+                    app.keys.display(&app.keys.seek_backward),
+                    app.keys.display(&app.keys.seek_forward)
+                ),
+                "⏩",
+                t!("help.seek_5s"),
+            ),
+            (
+                app.keys.display(&app.keys.seek_mode),
+                "🎯",
+                t!("help.seek_mode"),
+            ),
+            (
+                format!(
+                    "{}/{}",
+                    app.keys.display(&app.keys.device_next),
+                    app.keys.display(&app.keys.device_prev)
+                ),
+                "🎧",
+                t!("help.output_device"),
+            ),
+            (
+                app.keys.display(&app.keys.toggle_audio_info),
+                "ℹ️",
+                t!("help.audio_info"),
+            ),
+            (
+                app.keys.display(&app.keys.track_info),
+                "🏷️",
+                t!("help.track_info"),
+            ),
+            (
+                app.keys.display(&app.keys.chapters),
+                "📖",
+                t!("help.chapters"),
+            ),
+            (
+                app.keys.display(&app.keys.bookmark),
+                "🔖",
+                t!("help.bookmark"),
+            ),
+            (
+                format!(
+                    "{}/{}",
+                    app.keys.display(&app.keys.marker_prev),
+                    app.keys.display(&app.keys.marker_next)
+                ),
+                "📍",
+                t!("help.markers"),
+            ),
+            (
+                app.keys.display(&app.keys.fullscreen_art),
+                "🖼️",
+                t!("help.full_screen_art"),
+            ),
+            (
+                format!(
+                    "{}/{}",
+                    app.keys.display(&app.keys.pane_left),
+                    app.keys.display(&app.keys.pane_right)
+                ),
+                "↔️",
+                t!("help.resize_panes"),
+            ),
+            (
+                app.keys.display(&app.keys.pane_swap),
+                "🔀",
+                t!("help.swap_panes"),
+            ),
+            (
+                format!(
+                    "{} {}",
+                    app.keys.display(&app.keys.split_view),
+                    app.keys.display(&app.keys.split_focus)
+                ),
+                "🪟",
+                t!("help.split_view_focus"),
+            ),
+            (
+                format!(
+                    "{}..{}",
+                    app.keys.display(&app.keys.workspace_1),
+                    app.keys.display(&app.keys.workspace_4)
+                ),
+                "🗂️",
+                t!("help.workspaces"),
+            ),
+            (
+                app.keys.display(&app.keys.theme_picker),
+                "🎨",
+                t!("help.themes"),
+            ),
+            (
+                app.keys.display(&app.keys.command_line),
+                "💬",
+                t!("help.commands"),
+            ),
+            (
+                app.keys.display(&app.keys.palette),
+                "🧭",
+                t!("help.palette"),
+            ),
+            (app.keys.display(&app.keys.log_view), "📝", t!("help.log")),
+            (app.keys.display(&app.keys.retry), "🔁", t!("help.retry")),
+            (app.keys.display(&app.keys.quit), "🚪", t!("help.quit")),
+        ]
+    } else {
+        // Controller mode: limited keys (no shuffle/repeat - not available)
+        vec![
+            (
+                app.keys.display(&app.keys.play_pause),
+                "▶️",
+                t!("help.play_pause"),
+            ),
+            (
+                app.keys.display(&app.keys.next_track),
+                "⏭️",
+                t!("help.next_track"),
+            ),
+            (
+                app.keys.display(&app.keys.prev_track),
+                "⏮️",
+                t!("help.previous_track"),
+            ),
+            (
+                format!(
+                    "{}/{}",
+                    app.keys.display(&app.keys.volume_up),
+                    app.keys.display(&app.keys.volume_down)
+                ),
+                "🔊",
+                t!("help.volume"),
+            ),
+            (app.keys.display(&app.keys.mute), "🔇", t!("help.mute")),
+            (app.keys.display(&app.keys.duck), "🦆", t!("help.duck")),
+            (
+                format!(
+                    "{}/{}",
+                    app.keys.display(&app.keys.seek_backward),
+                    app.keys.display(&app.keys.seek_forward)
+                ),
+                "⏩",
+                t!("help.seek_5s"),
+            ),
+            (
+                app.keys.display(&app.keys.seek_mode),
+                "🎯",
+                t!("help.seek_mode"),
+            ),
+            (
+                format!(
+                    "{}/{}",
+                    app.keys.display(&app.keys.device_next),
+                    app.keys.display(&app.keys.device_prev)
+                ),
+                "🎧",
+                t!("help.output_device"),
+            ),
+            (
+                app.keys.display(&app.keys.toggle_audio_info),
+                "ℹ️",
+                t!("help.audio_info"),
+            ),
+            (
+                app.keys.display(&app.keys.track_info),
+                "🏷️",
+                t!("help.track_info"),
+            ),
+            (
+                app.keys.display(&app.keys.fullscreen_art),
+                "🖼️",
+                t!("help.full_screen_art"),
+            ),
+            (
+                format!(
+                    "{}/{}",
+                    app.keys.display(&app.keys.pane_left),
+                    app.keys.display(&app.keys.pane_right)
+                ),
+                "↔️",
+                t!("help.resize_panes"),
+            ),
+            (
+                app.keys.display(&app.keys.pane_swap),
+                "🔀",
+                t!("help.swap_panes"),
+            ),
+            (
+                format!(
+                    "{} {}",
+                    app.keys.display(&app.keys.split_view),
+                    app.keys.display(&app.keys.split_focus)
+                ),
+                "🪟",
+                t!("help.split_view_focus"),
+            ),
+            (
+                format!(
+                    "{}..{}",
+                    app.keys.display(&app.keys.workspace_1),
+                    app.keys.display(&app.keys.workspace_4)
+                ),
+                "🗂️",
+                t!("help.workspaces"),
+            ),
+            (
+                app.keys.display(&app.keys.theme_picker),
+                "🎨",
+                t!("help.themes"),
+            ),
+            (
+                app.keys.display(&app.keys.command_line),
+                "💬",
+                t!("help.commands"),
+            ),
+            (
+                app.keys.display(&app.keys.palette),
+                "🧭",
+                t!("help.palette"),
+            ),
+            (app.keys.display(&app.keys.log_view), "📝", t!("help.log")),
+            (app.keys.display(&app.keys.retry), "🔁", t!("help.retry")),
+            (app.keys.display(&app.keys.quit), "🚪", t!("help.quit")),
+        ]
+    };
+
+    let mut pages = vec![HelpPage {
+        id: "global",
+        title: t!("help.page_global"),
+        entries: global,
+    }];
+    if app.is_mpd {
+        pages.push(HelpPage {
+            id: "library",
+            title: t!("help.page_library"),
+            entries: vec![
                 (
                     format!(
                         "{}/{}",
-                        app.keys.display(&app.keys.band_prev),
-                        app.keys.display(&app.keys.band_next)
+                        app.keys.display(&app.keys.nav_down),
+                        app.keys.display(&app.keys.nav_up)
                     ),
-                    "🎚️",
-                    "Select band",
+                    "📋",
+                    t!("help.navigate"),
                 ),
                 (
                     format!(
                         "{}/{}",
-                        app.keys.display(&app.keys.gain_up),
-                        app.keys.display(&app.keys.gain_down)
+                        app.keys.display(&app.keys.page_down),
+                        app.keys.display(&app.keys.page_up)
                     ),
-                    "📊",
-                    "Adjust gain",
+                    "📄",
+                    t!("help.page_down_up"),
                 ),
-                (app.keys.display(&app.keys.next_preset), "🎵", "Next preset"),
-                (app.keys.display(&app.keys.toggle_eq), "⚡", "Toggle EQ"),
-                (app.keys.display(&app.keys.reset_eq), "↺", "Reset EQ"),
                 (
-                    app.keys.display(&app.keys.reset_levels),
+                    format!(
+                        "{0}{0}/{1}",
+                        app.keys.display(&app.keys.jump_top),
+                        app.keys.display(&app.keys.jump_bottom)
+                    ),
+                    "↕️",
+                    t!("help.top_bottom"),
+                ),
+                (
+                    app.keys.display(&app.keys.jump_to_playing),
                     "🎯",
-                    "Reset Levels",
+                    t!("help.jump_to_playing"),
                 ),
                 (
-                    format!(
-                        "{}/{}",
-                        app.keys.display(&app.keys.preamp_up),
-                        app.keys.display(&app.keys.preamp_down)
-                    ),
-                    "🔊",
-                    "Preamp ±1dB",
+                    app.keys.display(&app.keys.follow_playback),
+                    "🔗",
+                    t!("help.follow_playback"),
                 ),
                 (
-                    format!(
-                        "{}/{}",
-                        app.keys.display(&app.keys.balance_right),
-                        app.keys.display(&app.keys.balance_left)
-                    ),
-                    "⚖️",
-                    "Balance ±0.1",
+                    app.keys.display(&app.keys.fold_album),
+                    "💿",
+                    t!("help.fold_album"),
                 ),
-                (app.keys.display(&app.keys.crossfade), "🔀", "Crossfade"),
-                (app.keys.display(&app.keys.replay_gain), "📀", "ReplayGain"),
-                (app.keys.display(&app.keys.save_preset), "💾", "Save preset"),
                 (
-                    app.keys.display(&app.keys.delete_preset),
-                    "🗑️",
-                    "Delete preset",
+                    app.keys.display(&app.keys.tab_next),
+                    "🔄",
+                    t!("help.switch_mode"),
                 ),
-            ],
-        ),
-        ViewMode::Library => (
-            "Library",
-            vec![
                 (
-                    format!(
-                        "{}/{}",
-                        app.keys.display(&app.keys.nav_down),
-                        app.keys.display(&app.keys.nav_up)
-                    ),
-                    "📋",
-                    "Navigate",
+                    app.keys.display(&app.keys.enter_dir),
+                    "▶️",
+                    t!("help.select_play"),
+                ),
+                (
+                    app.keys.display(&app.keys.back_dir),
+                    "←",
+                    t!("help.go_back"),
+                ),
+                (
+                    app.keys.display(&app.keys.search_global),
+                    "🔍",
+                    t!("help.search"),
                 ),
-                (app.keys.display(&app.keys.tab_next), "🔄", "Switch mode"),
-                (app.keys.display(&app.keys.enter_dir), "▶️", "Select/Play"),
-                (app.keys.display(&app.keys.back_dir), "←", "Go back"),
-                (app.keys.display(&app.keys.search_global), "🔍", "Search"),
                 (
                     app.keys.display(&app.keys.add_to_queue),
                     "➕",
-                    "Add to Queue",
+                    t!("help.add_to_queue"),
                 ),
                 (
                     app.keys.display(&app.keys.save_playlist),
                     "💾",
-                    "Save playlist",
+                    t!("help.save_playlist"),
                 ),
                 (
                     app.keys.display(&app.keys.rename_playlist),
                     "✏️",
-                    "Rename playlist",
+                    t!("help.rename_playlist"),
+                ),
+                (
+                    app.keys.display(&app.keys.import_playlist),
+                    "📥",
+                    t!("help.import_playlist"),
+                ),
+                (
+                    app.keys.display(&app.keys.export_playlist),
+                    "📤",
+                    t!("help.export_playlist"),
+                ),
+                (
+                    app.keys.display(&app.keys.download),
+                    "⬇️",
+                    t!("help.download"),
                 ),
                 (
                     app.keys.display(&app.keys.delete_item),
                     "🗑️",
-                    "Delete/Remove",
+                    t!("help.delete_remove"),
+                ),
+                (
+                    app.keys.display(&app.keys.edit_tags),
+                    "🏷️",
+                    t!("help.edit_tags"),
                 ),
-                (app.keys.display(&app.keys.edit_tags), "🏷️", "Edit tags"),
+                (app.keys.display(&app.keys.mark), "◆", t!("help.mark")),
                 (
                     format!(
                         "{}/{}",
@@ -116,158 +445,249 @@ pub fn render(f: &mut Frame, app: &App) {
                         app.keys.display(&app.keys.move_up)
                     ),
                     "🔃",
-                    "Reorder",
+                    t!("help.reorder"),
                 ),
+                (app.keys.display(&app.keys.undo), "↩️", t!("help.undo")),
+                (app.keys.display(&app.keys.redo), "↪️", t!("help.redo")),
             ],
-        ),
-        ViewMode::Lyrics => (
-            "Lyrics",
-            vec![
+        });
+        pages.push(HelpPage {
+            id: "eq",
+            title: t!("help.page_eq"),
+            entries: vec![
                 (
                     format!(
                         "{}/{}",
-                        app.keys.display(&app.keys.nav_down),
-                        app.keys.display(&app.keys.nav_up)
+                        app.keys.display(&app.keys.band_prev),
+                        app.keys.display(&app.keys.band_next)
+                    ),
+                    "🎚️",
+                    t!("help.select_band"),
+                ),
+                (
+                    format!(
+                        "{}/{}",
+                        app.keys.display(&app.keys.gain_up),
+                        app.keys.display(&app.keys.gain_down)
+                    ),
+                    "📊",
+                    t!("help.adjust_gain"),
+                ),
+                (
+                    app.keys.display(&app.keys.next_preset),
+                    "🎵",
+                    t!("help.next_preset"),
+                ),
+                (
+                    app.keys.display(&app.keys.toggle_eq),
+                    "⚡",
+                    t!("help.toggle_eq"),
+                ),
+                (
+                    app.keys.display(&app.keys.ab_compare),
+                    "🅰️",
+                    t!("help.a_b_compare"),
+                ),
+                (
+                    app.keys.display(&app.keys.ab_store),
+                    "📌",
+                    t!("help.store_as_b"),
+                ),
+                (
+                    app.keys.display(&app.keys.spectrum_overlay),
+                    "📈",
+                    t!("help.spectrum_pre_post"),
+                ),
+                (
+                    app.keys.display(&app.keys.output_depth),
+                    "🔌",
+                    t!("help.output_depth"),
+                ),
+                (
+                    app.keys.display(&app.keys.output_dither),
+                    "〰️",
+                    t!("help.output_dither"),
+                ),
+                (
+                    app.keys.display(&app.keys.output_rate),
+                    "⏱️",
+                    t!("help.output_rate"),
+                ),
+                (
+                    app.keys.display(&app.keys.reset_eq),
+                    "↺",
+                    t!("help.reset_eq"),
+                ),
+                (
+                    app.keys.display(&app.keys.reset_levels),
+                    "🎯",
+                    t!("help.reset_levels"),
+                ),
+                (
+                    format!(
+                        "{}/{}",
+                        app.keys.display(&app.keys.preamp_up),
+                        app.keys.display(&app.keys.preamp_down)
+                    ),
+                    "🔊",
+                    t!("help.preamp_1db"),
+                ),
+                (
+                    format!(
+                        "{}/{}",
+                        app.keys.display(&app.keys.balance_right),
+                        app.keys.display(&app.keys.balance_left)
                     ),
-                    "📜",
-                    "Scroll lyrics",
+                    "⚖️",
+                    t!("help.balance_01"),
+                ),
+                (
+                    app.keys.display(&app.keys.crossfade),
+                    "🔀",
+                    t!("help.crossfade"),
+                ),
+                (
+                    app.keys.display(&app.keys.replay_gain),
+                    "📀",
+                    t!("help.replaygain"),
+                ),
+                (
+                    app.keys.display(&app.keys.save_preset),
+                    "💾",
+                    t!("help.save_preset"),
+                ),
+                (
+                    app.keys.display(&app.keys.delete_preset),
+                    "🗑️",
+                    t!("help.delete_preset"),
                 ),
                 (
-                    app.keys.display(&app.keys.seek_to_line),
-                    "🎤",
-                    "Jump to line",
+                    app.keys.display(&app.keys.import_preset),
+                    "📥",
+                    t!("help.import_preset"),
                 ),
             ],
-        ),
-        ViewMode::Visualizer => ("Visualizer", vec![]),
-    };
-
-    // Global keys - mode-specific
-    let global_keys: Vec<(String, &str, &str)> = if app.is_mpd {
-        // MPD mode: full feature set
-        vec![
-            (app.keys.display(&app.keys.play_pause), "▶️", "Play/Pause"),
-            (app.keys.display(&app.keys.next_track), "⏭️", "Next track"),
-            (
-                app.keys.display(&app.keys.prev_track),
-                "⏮️",
-                "Previous track",
-            ),
-            (app.keys.display(&app.keys.shuffle), "🔀", "Shuffle"),
-            (app.keys.display(&app.keys.repeat), "🔁", "Repeat"),
-            (app.keys.display(&app.keys.search_global), "🔍", "Search"),
+        });
+    }
+    pages.push(HelpPage {
+        id: "lyrics",
+        title: t!("help.page_lyrics"),
+        entries: vec![
             (
                 format!(
                     "{}/{}",
-                    app.keys.display(&app.keys.volume_up),
-                    app.keys.display(&app.keys.volume_down)
+                    app.keys.display(&app.keys.nav_down),
+                    app.keys.display(&app.keys.nav_up)
                 ),
-                "🔊",
-                "Volume",
+                "📜",
+                t!("help.scroll_lyrics"),
             ),
-            (format!("1-{}", "4"), "🖼️", "View modes"),
             (
-                format!(
-                    "{}/{}",
-                    app.keys.display(&app.keys.seek_backward),
-                    app.keys.display(&app.keys.seek_forward)
-                ),
-                "⏩",
-                "Seek ±5s",
+                app.keys.display(&app.keys.seek_to_line),
+                "🎤",
+                t!("help.jump_to_line"),
             ),
             (
                 format!(
                     "{}/{}",
-                    app.keys.display(&app.keys.device_next),
-                    app.keys.display(&app.keys.device_prev)
+                    app.keys.display(&app.keys.lyrics_sync_earlier),
+                    app.keys.display(&app.keys.lyrics_sync_later)
                 ),
-                "🎧",
-                "Output device",
-            ),
-            (
-                app.keys.display(&app.keys.toggle_audio_info),
-                "ℹ️",
-                "Audio info",
-            ),
-            (app.keys.display(&app.keys.quit), "🚪", "Quit"),
-        ]
-    } else {
-        // Controller mode: limited keys (no shuffle/repeat - not available)
-        vec![
-            (app.keys.display(&app.keys.play_pause), "▶️", "Play/Pause"),
-            (app.keys.display(&app.keys.next_track), "⏭️", "Next track"),
-            (
-                app.keys.display(&app.keys.prev_track),
-                "⏮️",
-                "Previous track",
+                "⏱️",
+                t!("help.lyrics_earlier_later"),
             ),
             (
                 format!(
-                    "{}/{}",
-                    app.keys.display(&app.keys.volume_up),
-                    app.keys.display(&app.keys.volume_down)
+                    "{} {}/{}",
+                    app.keys.display(&app.keys.search_global),
+                    app.keys.display(&app.keys.search_next),
+                    app.keys.display(&app.keys.search_prev)
                 ),
-                "🔊",
-                "Volume",
+                "🔎",
+                t!("help.search_lyrics"),
             ),
             (
-                format!(
-                    "{}/{}",
-                    app.keys.display(&app.keys.seek_backward),
-                    app.keys.display(&app.keys.seek_forward)
-                ),
-                "⏩",
-                "Seek ±5s",
+                app.keys.display(&app.keys.lyrics_edit),
+                "✍️",
+                t!("help.edit_lyrics"),
             ),
             (
-                format!(
-                    "{}/{}",
-                    app.keys.display(&app.keys.device_next),
-                    app.keys.display(&app.keys.device_prev)
-                ),
-                "🎧",
-                "Output device",
+                app.keys.display(&app.keys.lyrics_lookup),
+                "🔍",
+                t!("help.find_other_lyrics"),
             ),
             (
-                app.keys.display(&app.keys.toggle_audio_info),
-                "ℹ️",
-                "Audio info",
+                app.keys.display(&app.keys.lyrics_romanize),
+                "🔤",
+                t!("help.romanization"),
             ),
-            (app.keys.display(&app.keys.quit), "🚪", "Quit"),
-        ]
-    };
-
-    // Mathematical Explicit Tracking - Disjoint Grid Renderer
-    // Completely unlinks Emoji width calculations from text rendering shifts!
-    use unicode_width::UnicodeWidthStr;
-
-    let max_key_w = keys
-        .iter()
-        .chain(global_keys.iter())
-        .map(|(k, _, _)| k.width())
-        .max()
-        .unwrap_or(7)
-        .max(7);
+        ],
+    });
+    pages
+}
 
-    let max_desc_w = keys
-        .iter()
-        .chain(global_keys.iter())
-        .map(|(_, _, d)| d.width())
-        .max()
-        .unwrap_or(20);
+enum Row<'a> {
+    Heading(&'a str),
+    Entry(&'a str, &'static str, &'a str),
+}
 
-    let content_width = (max_key_w + max_desc_w + 7).max(22);
+pub fn render(f: &mut Frame, app: &mut App) {
+    // 🎹 WhichKey-style floating popup (Helix-inspired), one page per context
+    let pages = pages(app);
+    let page = app.help.page.min(pages.len() - 1);
 
-    let mut num_lines = keys.len();
-    if !keys.is_empty() {
-        num_lines += 1;
+    // Typing filters every page at once, grouped by context
+    let query = app.help.query.trim();
+    let mut rows: Vec<Row> = Vec::new();
+    if query.is_empty() {
+        rows.extend(
+            pages[page]
+                .entries
+                .iter()
+                .map(|(k, i, d)| Row::Entry(k, i, d)),
+        );
+    } else {
+        let matcher = SkimMatcherV2::default();
+        for p in &pages {
+            let mut hits: Vec<_> = p
+                .entries
+                .iter()
+                .filter_map(|e| {
+                    matcher
+                        .fuzzy_match(&format!("{} {}", e.2, e.0), query)
+                        .map(|score| (score, e))
+                })
+                .collect();
+            if hits.is_empty() {
+                continue;
+            }
+            hits.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            rows.push(Row::Heading(&p.title));
+            rows.extend(hits.into_iter().map(|(_, (k, i, d))| Row::Entry(k, i, d)));
+        }
     }
-    num_lines += 2; // global title + empty line
-    num_lines += global_keys.len();
+
+    // Size for the biggest page so flipping pages doesn't resize the popup
+    let all = || pages.iter().flat_map(|p| p.entries.iter());
+    let max_key_w = all().map(|(k, _, _)| k.width()).max().unwrap_or(7).max(7);
+    let max_desc_w = all().map(|(_, _, d)| d.width()).max().unwrap_or(20);
+    let tabs_w: usize = pages.iter().map(|p| p.title.width() + 3).sum();
+    let content_width = (max_key_w + max_desc_w + 7).max(tabs_w + 2).max(22);
+    let longest = pages.iter().map(|p| p.entries.len()).max().unwrap_or(0);
 
     let max_height = f.area().height.saturating_sub(4);
-    let popup_height = (num_lines as u16 + 2).min(max_height);
+    // Borders + tab row + blank + hint row
+    let popup_height = (longest as u16 + 5).min(max_height);
     let popup_width = (content_width as u16 + 2).min(f.area().width.saturating_sub(2));
+    if popup_height < 5 {
+        return;
+    }
+
+    let list_rows = popup_height.saturating_sub(5) as usize;
+    app.help.page = page;
+    app.help.scroll = app.help.scroll.min(rows.len().saturating_sub(list_rows));
+    let scroll = app.help.scroll;
+    let theme = &app.theme;
 
     // Anchored identically to the original layout (flush right)
     let margin_right = 1;
@@ -284,19 +704,57 @@ pub fn render(f: &mut Frame, app: &App) {
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(theme.blue))
-        .title(format!(" {} ", title))
+        .title(t!("help.title"))
         .title_alignment(Alignment::Left);
 
     let inner_area = block.inner(popup_area);
     f.render_widget(block, popup_area);
 
-    let mut current_y = inner_area.y;
-
-    let draw_row = |f: &mut Frame, y: u16, key: &str, icon: &str, desc: &str| {
-        if y >= inner_area.bottom() {
-            return;
+    // Page tabs, or the filter while there is one
+    let header = if app.help.searching || !query.is_empty() {
+        let cursor = if app.help.searching { "▌" } else { "" };
+        let matches = rows.iter().filter(|r| matches!(r, Row::Entry(..))).count();
+        Line::from(vec![
+            Span::styled(
+                format!("/{}", app.help.query),
+                Style::default()
+                    .fg(theme.yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(cursor, Style::default().fg(theme.yellow)),
+            Span::styled(
+                t!("help.found", count = matches),
+                Style::default().fg(theme.overlay),
+            ),
+        ])
+    } else {
+        let mut spans = Vec::new();
+        for (i, p) in pages.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" │ ", Style::default().fg(theme.surface)));
+            }
+            let style = if i == page {
+                Style::default()
+                    .fg(theme.magenta)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.overlay)
+            };
+            spans.push(Span::styled(p.title.as_str(), style));
         }
+        Line::from(spans)
+    };
+    f.render_widget(
+        Paragraph::new(header),
+        Rect::new(
+            inner_area.x + 1,
+            inner_area.y,
+            inner_area.width.saturating_sub(2),
+            1,
+        ),
+    );
 
+    let draw_row = |f: &mut Frame, y: u16, key: &str, icon: &str, desc: &str| {
         let key_span = Span::styled(
             format!("{:<width$}", key, width = max_key_w),
             Style::default()
@@ -325,23 +783,34 @@ pub fn render(f: &mut Frame, app: &App) {
         );
     };
 
-    // Draw Context Keys
-    for (key, icon, desc) in &keys {
-        draw_row(f, current_y, key, icon, desc);
-        current_y += 1;
-    }
-
-    if !keys.is_empty() {
+    let mut current_y = inner_area.y + 2;
+    for row in rows.iter().skip(scroll).take(list_rows) {
+        match row {
+            Row::Heading(title) => {
+                let heading = Span::styled(
+                    format!("────── {} ──────", title),
+                    Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+                );
+                f.render_widget(
+                    Paragraph::new(heading),
+                    Rect::new(
+                        inner_area.x + 1,
+                        current_y,
+                        inner_area.width.saturating_sub(2),
+                        1,
+                    ),
+                );
+            }
+            Row::Entry(key, icon, desc) => draw_row(f, current_y, key, icon, desc),
+        }
         current_y += 1;
     }
-
-    if current_y < inner_area.bottom() {
-        let global_title = Span::styled(
-            "────── Global ──────",
-            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
-        );
+    if rows.is_empty() {
         f.render_widget(
-            Paragraph::new(global_title),
+            Paragraph::new(Span::styled(
+                t!("help.no_match"),
+                Style::default().fg(theme.overlay),
+            )),
             Rect::new(
                 inner_area.x + 1,
                 current_y,
@@ -349,12 +818,39 @@ pub fn render(f: &mut Frame, app: &App) {
                 1,
             ),
         );
-        current_y += 2;
     }
 
-    // Draw Global Keys
-    for (key, icon, desc) in &global_keys {
-        draw_row(f, current_y, key, icon, desc);
-        current_y += 1;
-    }
+    // Hint row, with a hint that there is more below
+    let more = if scroll + list_rows < rows.len() {
+        "↓ "
+    } else {
+        ""
+    };
+    let hint = Line::from(vec![
+        Span::styled(more, Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Tab",
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.page"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "/",
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.filter"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.close"), Style::default().fg(theme.overlay)),
+    ]);
+    f.render_widget(
+        Paragraph::new(hint),
+        Rect::new(
+            inner_area.x + 1,
+            inner_area.bottom().saturating_sub(1),
+            inner_area.width.saturating_sub(2),
+            1,
+        ),
+    );
 }
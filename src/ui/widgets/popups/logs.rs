@@ -0,0 +1,126 @@
+use crate::app::App;
+use crate::t;
+use crate::ui::utils::{clear_popup_area, fit, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use tracing::Level;
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(view) = &app.log_view else {
+        return;
+    };
+    let theme = &app.theme;
+    let lines_all = crate::logging::recent(view.min_level);
+
+    let width = f.area().width.saturating_sub(4).min(120);
+    let height = f.area().height.saturating_sub(4);
+    if width < 30 || height < 6 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    // `scroll` is the bottom line; following shows the newest
+    let rows = height.saturating_sub(4) as usize;
+    let end = view
+        .scroll
+        .map_or(lines_all.len(), |s| (s + 1).max(rows))
+        .min(lines_all.len());
+    let start = end.saturating_sub(rows);
+    let inner = (width as usize).saturating_sub(2);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if lines_all.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t!("logs.empty"),
+            Style::default().fg(theme.overlay),
+        )));
+    }
+    for entry in &lines_all[start..end] {
+        let color = match entry.level {
+            Level::ERROR => theme.red,
+            Level::WARN => theme.yellow,
+            Level::INFO => theme.blue,
+            _ => theme.overlay,
+        };
+        let target = entry.target.strip_prefix("vyom::").unwrap_or(&entry.target);
+        let target = fit(target, 18);
+        // time (8) + level (6) + target (18) + gaps
+        let room = inner.saturating_sub(8 + 1 + 6 + 18 + 1);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<8} ", entry.time),
+                Style::default().fg(theme.overlay),
+            ),
+            Span::styled(
+                format!("{:<6}", entry.level.as_str()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(target, Style::default().fg(theme.magenta)),
+            Span::raw(" "),
+            Span::styled(
+                truncate(&entry.message, room),
+                Style::default().fg(theme.text),
+            ),
+        ]));
+    }
+
+    // Pin the hint row to the bottom
+    for _ in lines.len()..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        )
+    };
+    lines.push(Line::from(vec![
+        key("j/k"),
+        Span::styled(t!("hint.scroll"), Style::default().fg(theme.overlay)),
+        key("G"),
+        Span::styled(t!("logs.hint_follow"), Style::default().fg(theme.overlay)),
+        key("f"),
+        Span::styled(t!("logs.hint_level"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.close"), Style::default().fg(theme.overlay)),
+    ]));
+
+    let follow = if view.scroll.is_none() {
+        t!("logs.following")
+    } else {
+        String::new()
+    };
+    let title = t!(
+        "logs.title",
+        level = view.min_level.as_str().to_lowercase(),
+        follow = follow
+    );
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                title,
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
@@ -0,0 +1,154 @@
+use crate::app::{App, LookupStatus};
+use crate::t;
+use crate::ui::utils::{clear_popup_area, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(ref lookup) = app.lyrics_lookup else {
+        return;
+    };
+    let theme = &app.theme;
+
+    let width = f.area().width.saturating_sub(4).min(64);
+    let height = f.area().height.saturating_sub(4).min(20);
+    if width < 30 || height < 10 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    // Query fields
+    for (i, (field, value)) in [
+        (t!("field.artist"), &lookup.artist),
+        (t!("field.title"), &lookup.title),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let is_active = i == lookup.active_field;
+        let field_style = if is_active {
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.overlay)
+        };
+        let cursor = if is_active { "▌" } else { "" };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:>7}: ", field), field_style),
+            Span::styled(value.to_string(), Style::default().fg(theme.text)),
+            Span::styled(cursor, Style::default().fg(theme.green)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Results (or what's going on instead)
+    let list_rows = (height as usize).saturating_sub(8);
+    let status = match &lookup.status {
+        LookupStatus::Editing => Some(t!("lyrics_lookup.editing")),
+        LookupStatus::Searching => Some(t!("lyrics_lookup.searching")),
+        LookupStatus::Done if lookup.results.is_empty() => Some(t!("lyrics_lookup.none")),
+        LookupStatus::Done => None,
+        LookupStatus::Failed(e) => Some(t!("lyrics_lookup.failed", error = e)),
+    };
+    if let Some(status) = status {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", status),
+            Style::default().fg(theme.overlay),
+        )));
+    } else {
+        let start = lookup
+            .selected
+            .saturating_sub(list_rows.saturating_sub(1))
+            .min(lookup.results.len().saturating_sub(list_rows));
+        let inner_width = width.saturating_sub(2) as usize;
+        for (i, c) in lookup
+            .results
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(list_rows)
+        {
+            let is_selected = lookup.active_field == 2 && i == lookup.selected;
+            let marker = if is_selected { "▶ " } else { "  " };
+            let kind = if c.is_synced() { "⏱" } else { "¶" };
+            let duration = c
+                .duration_secs
+                .map(|d| format!(" {}:{:02}", d as u64 / 60, d as u64 % 60))
+                .unwrap_or_default();
+            let mut label = format!("{} - {}", c.artist, c.title);
+            if !c.album.is_empty() {
+                label.push_str(&format!(" ({})", c.album));
+            }
+            let room = inner_width.saturating_sub(6 + duration.width());
+            let label = truncate(&label, room);
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.magenta)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(format!("{} ", kind), Style::default().fg(theme.cyan)),
+                Span::styled(label, style),
+                Span::styled(duration, Style::default().fg(theme.overlay)),
+            ]));
+        }
+    }
+
+    // Pin the hint row to the bottom
+    let used = lines.len();
+    for _ in used..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(
+            "Tab",
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.next"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Enter",
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.search_use"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.cancel"), Style::default().fg(theme.overlay)),
+    ]));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("lyrics_lookup.title"),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
@@ -0,0 +1,106 @@
+use crate::app::{App, ToastLevel};
+use crate::t;
+use crate::ui::utils::{clear_popup_area, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(scroll) = app.messages_view else {
+        return;
+    };
+    let theme = &app.theme;
+    let history = &app.toasts.history;
+
+    let width = f.area().width.saturating_sub(4).min(72);
+    let height = f
+        .area()
+        .height
+        .saturating_sub(4)
+        .min(history.len().max(1) as u16 + 4);
+    if width < 24 || height < 5 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let rows = height.saturating_sub(4) as usize;
+    let start = scroll.min(history.len().saturating_sub(rows));
+    let now = Instant::now();
+
+    let mut lines: Vec<Line> = Vec::new();
+    if history.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t!("messages.empty"),
+            Style::default().fg(theme.overlay),
+        )));
+    }
+    for entry in history.iter().skip(start).take(rows) {
+        let (icon, color) = match entry.level {
+            ToastLevel::Info => ("ℹ", theme.blue),
+            ToastLevel::Warn => ("⚠", theme.yellow),
+            ToastLevel::Error => ("✖", theme.red),
+        };
+        let ago = format!("{:>8} ", ago(now.saturating_duration_since(entry.at)));
+        let room = (width as usize).saturating_sub(2 + 3 + ago.width());
+        let message = truncate(&entry.message, room);
+        lines.push(Line::from(vec![
+            Span::styled(ago, Style::default().fg(theme.overlay)),
+            Span::styled(format!("{} ", icon), Style::default().fg(color)),
+            Span::styled(message, Style::default().fg(theme.text)),
+        ]));
+    }
+
+    // Pin the hint row to the bottom
+    for _ in lines.len()..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(
+            "j/k",
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.scroll"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.close"), Style::default().fg(theme.overlay)),
+    ]));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("messages.title"),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
+
+/// "12s ago", "3m ago", "1h ago"
+fn ago(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match secs {
+        0..=59 => t!("messages.seconds_ago", n = secs),
+        60..=3599 => t!("messages.minutes_ago", n = secs / 60),
+        _ => t!("messages.hours_ago", n = secs / 3600),
+    }
+}
@@ -0,0 +1,109 @@
+use crate::app::missing::Place;
+use crate::app::App;
+use crate::t;
+use crate::ui::utils::{clear_popup_area, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(report) = &app.missing else {
+        return;
+    };
+    let theme = &app.theme;
+
+    let width = f.area().width.saturating_sub(4).min(100);
+    let height = f
+        .area()
+        .height
+        .saturating_sub(4)
+        .min(report.entries.len() as u16 + 7);
+    if width < 30 || height < 8 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let mut lines: Vec<Line> = vec![Line::from(Span::styled(
+        t!("missing.summary", count = report.entries.len()),
+        Style::default()
+            .fg(theme.magenta)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    lines.push(Line::from(Span::styled(
+        if report.disk_checked {
+            t!("missing.checked_all")
+        } else {
+            t!("missing.checked_playlists")
+        },
+        Style::default().fg(theme.overlay),
+    )));
+
+    // Where it was found on the left, the file on the right
+    let inner = (width as usize).saturating_sub(4);
+    let place_w = inner / 4;
+    let rows = height.saturating_sub(6) as usize;
+    let start = report.scroll.min(report.entries.len().saturating_sub(rows));
+    for entry in report.entries.iter().skip(start).take(rows) {
+        let (place, color) = match &entry.place {
+            Place::Library => (t!("missing.library"), theme.yellow),
+            Place::Playlist(name, pos) => (format!("{} #{}", name, pos + 1), theme.blue),
+        };
+        let place = truncate(&place, place_w);
+        let file = truncate(&entry.file, inner.saturating_sub(place_w + 3));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<w$}", place, w = place_w),
+                Style::default().fg(color),
+            ),
+            Span::styled(" ✖ ", Style::default().fg(theme.red)),
+            Span::styled(file, Style::default().fg(theme.text)),
+        ]));
+    }
+
+    // Pin the hint row to the bottom
+    for _ in lines.len()..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(
+            "j/k",
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.scroll"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "d",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.purge"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.close"), Style::default().fg(theme.overlay)),
+    ]));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("missing.title"),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
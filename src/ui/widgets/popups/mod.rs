@@ -2,10 +2,26 @@ use crate::app::App;
 use ratatui::Frame;
 
 pub mod audio_info;
+pub mod cd;
+pub mod chapters;
+pub mod console;
+pub mod downloads;
+pub mod dsp;
 pub mod help;
 pub mod input;
+pub mod logs;
+pub mod lyrics_lookup;
+pub mod messages;
+pub mod missing;
+pub mod palette;
+pub mod seek_preview;
+pub mod stream_search;
 pub mod tag_editor;
+pub mod tag_tool;
+pub mod theme_picker;
 pub mod toast;
+pub mod track_info;
+pub mod wrapped;
 
 pub fn render(f: &mut Frame, app: &mut App) {
     // AUDIO INFO POPUP
@@ -13,8 +29,13 @@ pub fn render(f: &mut Frame, app: &mut App) {
         audio_info::render(f, app);
     }
 
+    // SEEK PREVIEW: target time and the lyric there
+    if app.seek_target().is_some() {
+        seek_preview::render(f, app);
+    }
+
     // TOAST NOTIFICATION
-    if let Some(ref _val) = app.toast {
+    if !app.toasts.is_empty() {
         toast::render(f, app);
     }
 
@@ -28,6 +49,84 @@ pub fn render(f: &mut Frame, app: &mut App) {
         tag_editor::render(f, app);
     }
 
+    // FILENAME <-> TAG PREVIEW
+    if app.tag_tool.is_some() {
+        tag_tool::render(f, app);
+    }
+
+    // JUMP-TO-ANYTHING PALETTE
+    if app.palette.is_some() {
+        palette::render(f, app);
+    }
+
+    // MISSING FILES REPORT
+    if app.missing.is_some() {
+        missing::render(f, app);
+    }
+
+    // LYRICS LOOKUP POPUP
+    if app.lyrics_lookup.is_some() {
+        lyrics_lookup::render(f, app);
+    }
+
+    // STREAM SEARCH POPUP
+    if app.stream_search.is_some() {
+        stream_search::render(f, app);
+    }
+
+    // TRACK INFO POPUP
+    if app.track_details.is_some() {
+        track_info::render(f, app);
+    }
+
+    // CHAPTER LIST POPUP
+    if app.chapter_list.is_some() {
+        chapters::render(f, app);
+    }
+
+    // MESSAGES POPUP
+    if app.messages_view.is_some() {
+        messages::render(f, app);
+    }
+
+    // DOWNLOADS POPUP
+    if app.downloads_view.is_some() {
+        downloads::render(f, app);
+    }
+
+    // CD POPUP
+    if app.cd.is_some() {
+        cd::render(f, app);
+    }
+
+    // DSP CHAIN POPUP
+    if app.dsp_view.is_some() {
+        dsp::render(f, app);
+    }
+    if app.plugin_controls.is_some() {
+        dsp::render_controls(f, app);
+    }
+
+    // WRAPPED POPUP
+    if app.wrapped.is_some() {
+        wrapped::render(f, app);
+    }
+
+    // LOG VIEWER POPUP
+    if app.log_view.is_some() {
+        logs::render(f, app);
+    }
+
+    // THEME GALLERY POPUP
+    if app.theme_picker.is_some() {
+        theme_picker::render(f, app);
+    }
+
+    // MPD CONSOLE POPUP
+    if app.console.open {
+        console::render(f, app);
+    }
+
     // FOOTER / WHICHKEY POPUP
     if app.show_keyhints {
         help::render(f, app);
@@ -0,0 +1,119 @@
+use crate::app::App;
+use crate::t;
+use crate::ui::utils::{clear_popup_area, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(palette) = &app.palette else {
+        return;
+    };
+    let theme = &app.theme;
+
+    // Up top, like an editor's, so the list grows downwards
+    let width = f.area().width.saturating_sub(4).min(80);
+    let height = f.area().height.saturating_sub(4).min(22);
+    if width < 30 || height < 7 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        f.area().height.saturating_sub(height) / 4,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::styled(
+                "› ",
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(palette.query.clone(), Style::default().fg(theme.text)),
+            Span::styled("▌", Style::default().fg(theme.green)),
+        ]),
+        Line::from(""),
+    ];
+
+    // Keep the selection in view
+    let rows = height.saturating_sub(5) as usize;
+    let start = palette
+        .selected
+        .saturating_sub(rows.saturating_sub(1))
+        .min(palette.matches.len().saturating_sub(rows));
+    let inner = (width as usize).saturating_sub(4);
+    if palette.matches.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", t!("palette.none")),
+            Style::default().fg(theme.overlay),
+        )));
+    }
+    for (i, &at) in palette.matches.iter().enumerate().skip(start).take(rows) {
+        let entry = &palette.entries[at];
+        let is_selected = i == palette.selected;
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.magenta)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        // Label first; the detail gets what's left, at most a third
+        let detail = truncate(&entry.detail, inner / 3);
+        let label = truncate(&entry.label, inner.saturating_sub(detail.width() + 6));
+        let gap = inner.saturating_sub(label.width() + detail.width() + 5);
+        lines.push(Line::from(vec![
+            Span::styled(if is_selected { "▶ " } else { "  " }, style),
+            Span::raw(format!("{} ", entry.icon())),
+            Span::styled(label, style),
+            Span::raw(" ".repeat(gap)),
+            Span::styled(detail, Style::default().fg(theme.overlay)),
+        ]));
+    }
+
+    // Pin the hint row to the bottom
+    for _ in lines.len()..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(
+            "↑↓",
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.pick"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Enter",
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.go"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.close"), Style::default().fg(theme.overlay)),
+    ]));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("palette.title"),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
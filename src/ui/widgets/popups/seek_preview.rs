@@ -0,0 +1,73 @@
+use crate::app::App;
+use crate::ui::utils::{clear_popup_area, fit};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+/// Seeking: the target time and the lyric line sung there, so the verse or
+/// chorus you were after is easy to hit
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(target) = app.seek_target() else {
+        return;
+    };
+    let Some((line, next)) = app.lyrics_at(target) else {
+        return;
+    };
+    let theme = &app.theme;
+
+    let width = f.area().width.saturating_sub(4).min(60);
+    let height = if next.is_some() { 4 } else { 3 };
+    if width < 20 || f.area().height < height + 4 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        f.area().height / 3,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let text_width = width.saturating_sub(4) as usize;
+    // Blank lines are the gaps between verses
+    let or_gap = |text: &str| {
+        if text.trim().is_empty() {
+            "♪".to_string()
+        } else {
+            text.to_string()
+        }
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        fit(&or_gap(line), text_width),
+        Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+    ))];
+    if let Some(next) = next {
+        lines.push(Line::from(Span::styled(
+            fit(&or_gap(next), text_width),
+            Style::default().fg(theme.overlay),
+        )));
+    }
+
+    let time = format!(" → {:02}:{:02} ", target / 60000, (target % 60000) / 1000);
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.yellow))
+            .title(Span::styled(
+                time,
+                Style::default()
+                    .fg(theme.yellow)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .padding(ratatui::widgets::Padding::horizontal(1))
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
@@ -0,0 +1,141 @@
+use crate::app::{App, LookupStatus};
+use crate::t;
+use crate::ui::utils::{clear_popup_area, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(ref search) = app.stream_search else {
+        return;
+    };
+    let theme = &app.theme;
+
+    let width = f.area().width.saturating_sub(4).min(72);
+    let height = f.area().height.saturating_sub(4).min(22);
+    if width < 30 || height < 8 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::styled(
+                " > ",
+                Style::default()
+                    .fg(theme.green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(search.query.clone(), Style::default().fg(theme.text)),
+            Span::styled("▌", Style::default().fg(theme.green)),
+        ]),
+        Line::from(""),
+    ];
+
+    // Results (or what's going on instead)
+    let list_rows = (height as usize).saturating_sub(6);
+    let status = match &search.status {
+        LookupStatus::Editing => Some(t!("stream_search.editing")),
+        LookupStatus::Searching => Some(t!("stream_search.searching")),
+        LookupStatus::Done if search.results.is_empty() => Some(t!("stream_search.none")),
+        LookupStatus::Done => None,
+        LookupStatus::Failed(e) => Some(t!("stream_search.failed", error = e)),
+    };
+    if let Some(status) = status {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", status),
+            Style::default().fg(theme.overlay),
+        )));
+    } else {
+        // Results for an older query stay up, dimmed, until Enter
+        let current = search.chosen().is_some();
+        let start = search
+            .selected
+            .saturating_sub(list_rows.saturating_sub(1))
+            .min(search.results.len().saturating_sub(list_rows));
+        let inner_width = width.saturating_sub(2) as usize;
+        for (i, hit) in search
+            .results
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(list_rows)
+        {
+            let is_selected = current && i == search.selected;
+            let marker = if is_selected { "▶ " } else { "  " };
+            let duration = hit
+                .duration_secs
+                .map(|d| format!(" {}:{:02}", d / 60, d % 60))
+                .unwrap_or_default();
+            let label = if hit.artist.is_empty() {
+                hit.title.clone()
+            } else {
+                format!("{} - {}", hit.artist, hit.title)
+            };
+            let room = inner_width.saturating_sub(4 + duration.width());
+            let style = match (is_selected, current) {
+                (true, _) => Style::default()
+                    .fg(theme.magenta)
+                    .add_modifier(Modifier::BOLD),
+                (false, true) => Style::default().fg(theme.text),
+                (false, false) => Style::default().fg(theme.overlay),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(truncate(&label, room), style),
+                Span::styled(duration, Style::default().fg(theme.overlay)),
+            ]));
+        }
+    }
+
+    // Pin the hint row to the bottom
+    let used = lines.len();
+    for _ in used..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(
+            "Enter",
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.search_play"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "↑↓",
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.pick"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.cancel"), Style::default().fg(theme.overlay)),
+    ]));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("stream_search.title"),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
@@ -1,9 +1,11 @@
-use crate::app::App;
+use crate::app::{App, LookupStatus, TagLookup};
+use crate::t;
+use crate::ui::utils::{clear_popup_area, truncate};
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    widgets::{Block, BorderType, Borders, Paragraph},
     Frame,
 };
 
@@ -12,8 +14,9 @@ pub fn render(f: &mut Frame, app: &App) {
         let theme = &app.theme;
 
         // Make popup responsive to terminal size
-        let max_popup_width = f.area().width.saturating_sub(4).min(50);
-        let max_popup_height = f.area().height.saturating_sub(4).min(12);
+        let max_popup_width = f.area().width.saturating_sub(4).min(60);
+        let wanted_height = tag_state.fields.len() as u16 + 8;
+        let max_popup_height = f.area().height.saturating_sub(4).min(wanted_height);
 
         // Only show if terminal is big enough
         if max_popup_width >= 30 && max_popup_height >= 8 {
@@ -21,37 +24,33 @@ pub fn render(f: &mut Frame, app: &App) {
             let popup_y = (f.area().height.saturating_sub(max_popup_height)) / 2;
             let popup_area = Rect::new(popup_x, popup_y, max_popup_width, max_popup_height);
 
-            // SEAMLESS Z-INDEX FIX: Un-skip cells so Ratatui overwrites Kitty images
-            let buf = f.buffer_mut();
-            for y in popup_area.top()..popup_area.bottom() {
-                for x in popup_area.left()..popup_area.right() {
-                    if let Some(cell) = buf.cell_mut((x, y)) {
-                        cell.set_skip(false);
-                        cell.set_char(' ');
-                        cell.set_bg(ratatui::style::Color::Reset);
-                        cell.set_fg(ratatui::style::Color::Reset);
-                    }
-                }
-            }
-
-            f.render_widget(Clear, popup_area);
+            clear_popup_area(f.buffer_mut(), popup_area);
 
             let mut lines: Vec<Line> = Vec::new();
 
             // Title
+            let heading = if tag_state.is_batch() {
+                t!("tag_editor.heading_batch", count = tag_state.files.len())
+            } else {
+                t!("tag_editor.heading")
+            };
             lines.push(Line::from(vec![Span::styled(
-                "🏷️ Edit Tags",
+                heading,
                 Style::default()
                     .fg(theme.magenta)
                     .add_modifier(Modifier::BOLD),
             )]));
             lines.push(Line::from(""));
 
-            // Fields with active highlighting
-            let fields = ["Title", "Artist", "Album"];
-            let values = [&tag_state.title, &tag_state.artist, &tag_state.album];
+            if let Some(lookup) = &tag_state.lookup {
+                lookup_lines(&mut lines, app, lookup, popup_area);
+                let popup = Paragraph::new(lines).block(popup_block(app));
+                f.render_widget(popup, popup_area);
+                return;
+            }
 
-            for (i, (field, value)) in fields.iter().zip(values.iter()).enumerate() {
+            // Fields with active highlighting
+            for (i, row) in tag_state.fields.iter().enumerate() {
                 let is_active = i == tag_state.active_field;
                 let field_style = if is_active {
                     Style::default()
@@ -69,11 +68,50 @@ pub fn render(f: &mut Frame, app: &App) {
                 };
 
                 let cursor = if is_active { "▌" } else { "" };
-                lines.push(Line::from(vec![
-                    Span::styled(format!("{:>8}: ", field), field_style),
-                    Span::styled(value.to_string(), value_style),
+                let mut spans = vec![
+                    Span::styled(format!("{:>12}: ", row.field.label()), field_style),
+                    Span::styled(row.value.clone(), value_style),
                     Span::styled(cursor, Style::default().fg(theme.green)),
+                ];
+                // Batch mode: which fields saving leaves as they are
+                if tag_state.is_batch() && row.keep {
+                    let note = if row.mixed {
+                        t!("tag_editor.mixed")
+                    } else {
+                        t!("tag_editor.keep")
+                    };
+                    spans.push(Span::styled(
+                        format!(" {}", note),
+                        Style::default()
+                            .fg(theme.overlay)
+                            .add_modifier(Modifier::ITALIC),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+
+            if let Some(done) = tag_state.progress {
+                let total = tag_state.files.len().max(1);
+                let width = 16;
+                let filled = done * width / total;
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "{}{} ",
+                            "▰".repeat(filled),
+                            "▱".repeat(width.saturating_sub(filled))
+                        ),
+                        Style::default().fg(theme.green),
+                    ),
+                    Span::styled(
+                        t!("tag_editor.writing", done = done, total = total),
+                        Style::default().fg(theme.overlay),
+                    ),
                 ]));
+                let popup = Paragraph::new(lines).block(popup_block(app));
+                f.render_widget(popup, popup_area);
+                return;
             }
 
             lines.push(Line::from(""));
@@ -82,34 +120,138 @@ pub fn render(f: &mut Frame, app: &App) {
                     "Tab",
                     Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(" next  ", Style::default().fg(theme.overlay)),
+                Span::styled(t!("hint.next"), Style::default().fg(theme.overlay)),
                 Span::styled(
                     "Enter",
                     Style::default()
                         .fg(theme.green)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(" save  ", Style::default().fg(theme.overlay)),
+                Span::styled(t!("hint.save"), Style::default().fg(theme.overlay)),
                 Span::styled(
                     "Esc",
                     Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(" cancel", Style::default().fg(theme.overlay)),
+                Span::styled(t!("hint.cancel"), Style::default().fg(theme.overlay)),
+            ]));
+            if tag_state.is_batch() {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        "Ctrl+k",
+                        Style::default()
+                            .fg(theme.yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(t!("hint.keep_existing"), Style::default().fg(theme.overlay)),
+                ]));
+            }
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "Ctrl+f",
+                    Style::default().fg(theme.cyan).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(t!("hint.fetch_tags"), Style::default().fg(theme.overlay)),
             ]));
 
-            let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(theme.blue))
-                    .title(Span::styled(
-                        " Edit Song Tags ",
-                        Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
-                    ))
-                    .title_alignment(Alignment::Left)
-                    .style(Style::default().bg(Color::Reset)),
-            );
+            let popup = Paragraph::new(lines)
+                .alignment(Alignment::Left)
+                .block(popup_block(app));
             f.render_widget(popup, popup_area);
         }
     }
 }
+
+/// "Fetch tags": the matches (or what's going on instead) and their keys
+fn lookup_lines(lines: &mut Vec<Line<'static>>, app: &App, lookup: &TagLookup, area: Rect) {
+    let theme = &app.theme;
+    lines.push(Line::from(Span::styled(
+        t!("tag_lookup.heading"),
+        Style::default().fg(theme.cyan).add_modifier(Modifier::BOLD),
+    )));
+    let status = match &lookup.status {
+        LookupStatus::Editing | LookupStatus::Searching => Some(t!("tag_lookup.searching")),
+        LookupStatus::Done if lookup.results.is_empty() => Some(t!("tag_lookup.none")),
+        LookupStatus::Done => None,
+        LookupStatus::Failed(e) => Some(t!("tag_lookup.failed", error = e)),
+    };
+    if let Some(status) = status {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", status),
+            Style::default().fg(theme.overlay),
+        )));
+    }
+
+    // Borders, headings and the hint row
+    let list_rows = (area.height as usize).saturating_sub(7).max(1);
+    let start = lookup
+        .selected
+        .saturating_sub(list_rows.saturating_sub(1))
+        .min(lookup.results.len().saturating_sub(list_rows));
+    let inner_width = area.width.saturating_sub(2) as usize;
+    for (i, c) in lookup
+        .results
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(list_rows)
+    {
+        let is_selected = i == lookup.selected;
+        let marker = if is_selected { "▶ " } else { "  " };
+        let mut label = format!("{} - {}", c.artist, c.title);
+        if let Some(album) = &c.album {
+            label.push_str(&format!(" · {}", album));
+        }
+        if let Some(year) = &c.year {
+            label.push_str(&format!(" ({})", year));
+        }
+        let score = format!(" {}%", c.score);
+        let label = truncate(&label, inner_width.saturating_sub(2 + score.len()));
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.magenta)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(label, style),
+            Span::styled(score, Style::default().fg(theme.overlay)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            "↑↓",
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.pick"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Enter",
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.fill"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.back"), Style::default().fg(theme.overlay)),
+    ]));
+}
+
+fn popup_block(app: &App) -> Block<'static> {
+    let theme = &app.theme;
+    Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.blue))
+        .title(Span::styled(
+            t!("tag_editor.title"),
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ))
+        .title_alignment(Alignment::Left)
+        .style(Style::default().bg(Color::Reset))
+}
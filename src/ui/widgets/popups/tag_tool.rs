@@ -0,0 +1,124 @@
+use crate::app::{App, TagToolAction};
+use crate::t;
+use crate::ui::utils::{clear_popup_area, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(tool) = &app.tag_tool else {
+        return;
+    };
+    let theme = &app.theme;
+
+    let width = f.area().width.saturating_sub(4).min(100);
+    let height = f
+        .area()
+        .height
+        .saturating_sub(4)
+        .min(tool.rows.len() as u16 + 6);
+    if width < 30 || height < 7 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let mut lines: Vec<Line> = vec![Line::from(Span::styled(
+        t!(
+            "tag_tool.summary",
+            ready = tool.ready(),
+            total = tool.rows.len(),
+            pattern = tool.pattern
+        ),
+        Style::default()
+            .fg(theme.magenta)
+            .add_modifier(Modifier::BOLD),
+    ))];
+
+    // Old name on the left, what happens on the right
+    let inner = (width as usize).saturating_sub(4);
+    let name_w = inner * 2 / 5;
+    let rows = height.saturating_sub(5) as usize;
+    let start = tool.scroll.min(tool.rows.len().saturating_sub(rows));
+    for row in tool.rows.iter().skip(start).take(rows) {
+        let name = row.file.rsplit('/').next().unwrap_or(&row.file);
+        let (arrow, result, color) = match &row.action {
+            Ok(TagToolAction::Tags(fields)) => {
+                let tags: Vec<String> = fields
+                    .iter()
+                    .map(|(field, value)| format!("{}: {}", field.label(), value))
+                    .collect();
+                ("→", tags.join(" · "), theme.green)
+            }
+            Ok(TagToolAction::Rename(target)) => {
+                let target = target.rsplit('/').next().unwrap_or(target);
+                ("→", target.to_string(), theme.green)
+            }
+            Err(reason) => ("✖", reason.clone(), theme.red),
+        };
+        let name = truncate(name, name_w);
+        let result = truncate(&result, inner.saturating_sub(name_w + 3));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<w$}", name, w = name_w),
+                Style::default().fg(theme.text),
+            ),
+            Span::styled(format!(" {} ", arrow), Style::default().fg(color)),
+            Span::styled(result, Style::default().fg(color)),
+        ]));
+    }
+
+    // Pin the hint row to the bottom
+    for _ in lines.len()..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    match tool.progress {
+        Some(done) => lines.push(Line::from(Span::styled(
+            t!("tag_editor.writing", done = done, total = tool.ready()),
+            Style::default().fg(theme.overlay),
+        ))),
+        None => lines.push(Line::from(vec![
+            Span::styled(
+                "j/k",
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(t!("hint.scroll"), Style::default().fg(theme.overlay)),
+            Span::styled(
+                "Enter",
+                Style::default()
+                    .fg(theme.green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(t!("hint.apply"), Style::default().fg(theme.overlay)),
+            Span::styled(
+                "Esc",
+                Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(t!("hint.cancel"), Style::default().fg(theme.overlay)),
+        ])),
+    }
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("tag_tool.title"),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
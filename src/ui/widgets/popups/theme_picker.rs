@@ -0,0 +1,105 @@
+use crate::app::App;
+use crate::t;
+use crate::ui::utils::{clear_popup_area, fit};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(ref picker) = app.theme_picker else {
+        return;
+    };
+    let theme = &app.theme;
+
+    let width = f.area().width.saturating_sub(4).min(44);
+    let height = f
+        .area()
+        .height
+        .saturating_sub(4)
+        .min(picker.entries.len() as u16 + 4);
+    if width < 24 || height < 5 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    // Keep the selection in view
+    let rows = height.saturating_sub(4) as usize;
+    let start = picker
+        .selected
+        .saturating_sub(rows.saturating_sub(1))
+        .min(picker.entries.len().saturating_sub(rows));
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, (name, entry)) in picker.entries.iter().enumerate().skip(start).take(rows) {
+        let is_selected = i == picker.selected;
+        let style = if is_selected {
+            Style::default()
+                .fg(theme.magenta)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let marker = if is_selected { "▶ " } else { "  " };
+        let name_width = (width as usize).saturating_sub(2 + 2 + 9);
+
+        let mut spans = vec![
+            Span::styled(marker, style),
+            Span::styled(fit(name, name_width), style),
+        ];
+        // Color swatches: the tones, then the accents
+        for color in [
+            entry.surface,
+            entry.text,
+            entry.red,
+            entry.green,
+            entry.yellow,
+            entry.blue,
+            entry.magenta,
+            entry.cyan,
+        ] {
+            spans.push(Span::styled("■", Style::default().fg(color)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            "Enter",
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.keep"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.revert"), Style::default().fg(theme.overlay)),
+    ]));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("theme_picker.title"),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
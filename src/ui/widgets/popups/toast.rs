@@ -1,89 +1,81 @@
-use crate::app::App;
+use crate::app::toasts::SLIDE;
+use crate::app::{App, Toast, ToastLevel};
+use crate::ui::utils::clear_popup_area;
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    widgets::{Block, BorderType, Borders, Paragraph},
     Frame,
 };
 use unicode_width::UnicodeWidthStr;
 
+/// Stacked toasts, newest on top
 pub fn render(f: &mut Frame, app: &App) {
-    if let Some(ref toast) = app.toast {
-        let theme = &app.theme;
-        let now = std::time::Instant::now();
+    for (i, toast) in app.toasts.active.iter().rev().enumerate() {
+        render_toast(f, app, toast, 1 + 3 * i as u16);
+    }
+}
 
-        // Auto-dismiss handled in App::on_tick()
-        let message = &toast.message;
-        let width = (message.width() as u16 + 6).min(f.area().width.saturating_sub(4));
-        let height = 3;
-        let target_x = f.area().width.saturating_sub(width + 1); // Top-right fixed
-        let mut x = target_x;
+fn render_toast(f: &mut Frame, app: &App, toast: &Toast, y: u16) {
+    let theme = &app.theme;
+    let now = std::time::Instant::now();
+    let color = match toast.level {
+        ToastLevel::Info => theme.blue,
+        ToastLevel::Warn => theme.yellow,
+        ToastLevel::Error => theme.red,
+    };
 
-        let entrance_elapsed = now.duration_since(toast.start_time).as_millis();
-        let time_remaining = toast.deadline.saturating_duration_since(now).as_millis();
+    // Auto-dismiss handled in App::on_tick()
+    let message = &toast.message;
+    let width = (message.width() as u16 + 6).min(f.area().width.saturating_sub(4));
+    let height = 3;
+    let target_x = f.area().width.saturating_sub(width + 1); // Top-right fixed
+    let mut x = target_x;
 
-        // Animation: Slide In/Out 🌊
-        if entrance_elapsed < 300 {
-            // Entrance (0-300ms from start): Slide LEFT
-            let t = entrance_elapsed as f32 / 300.0;
-            let ease = 1.0 - (1.0 - t).powi(3); // Cubic Out
-            let offset = (width as f32 * (1.0 - ease)) as u16;
-            x += offset;
-        } else if time_remaining < 300 {
-            // Exit (Last 300ms before deadline): Slide RIGHT
-            // t goes 0 -> 1 as we approach deadline
-            let t = (300 - time_remaining) as f32 / 300.0;
-            let ease = t.powi(3); // Cubic In
-            let offset = (width as f32 * ease) as u16;
-            x += offset;
-        }
-        // Else: Hold position
+    let entrance_elapsed = now.duration_since(toast.start_time).as_millis();
+    let time_remaining = toast.deadline.saturating_duration_since(now).as_millis();
 
-        // Don't render if off-screen (start/end)
-        if x < f.area().width {
-            let y = 1; // Near top
-            let full_area = Rect::new(x, y, width, height);
-            // Clip to screen bounds to avoid panic
-            let visible_area = full_area.intersection(f.area());
+    // Animation: Slide In/Out 🌊
+    let slide = SLIDE.as_millis();
+    if entrance_elapsed < slide {
+        // Entrance (0-300ms from start): Slide LEFT
+        let t = entrance_elapsed as f32 / slide as f32;
+        let ease = 1.0 - (1.0 - t).powi(3); // Cubic Out
+        let offset = (width as f32 * (1.0 - ease)) as u16;
+        x += offset;
+    } else if time_remaining < slide {
+        // Exit (Last 300ms before deadline): Slide RIGHT
+        // t goes 0 -> 1 as we approach deadline
+        let t = (slide - time_remaining) as f32 / slide as f32;
+        let ease = t.powi(3); // Cubic In
+        let offset = (width as f32 * ease) as u16;
+        x += offset;
+    }
+    // Else: Hold position
 
-            if !visible_area.is_empty() {
-                // SEAMLESS Z-INDEX FIX:
-                // Ratatui-image sets `skip = true` for all cells underneath a Kitty image.
-                // This forces Ratatui to ignore them on render, making popups draw *under* Kitty!
-                // By manually un-skipping the cells within our popup's bounding box AND clearing them,
-                // we carve a perfect hole out of the Kitty graphic layer, ensuring our popup draws on top!
-                let buf = f.buffer_mut();
-                for y in visible_area.top()..visible_area.bottom() {
-                    for x in visible_area.left()..visible_area.right() {
-                        if let Some(cell) = buf.cell_mut((x, y)) {
-                            // Un-skip the cell so Ratatui's renderer acknowledges it
-                            cell.set_skip(false);
-                            // Wipe it clean
-                            cell.set_char(' ');
-                            cell.set_bg(ratatui::style::Color::Reset);
-                            cell.set_fg(ratatui::style::Color::Reset);
-                        }
-                    }
-                }
+    // Don't render if off-screen (start/end)
+    if x < f.area().width && y < f.area().height {
+        let full_area = Rect::new(x, y, width, height);
+        // Clip to screen bounds to avoid panic
+        let visible_area = full_area.intersection(f.area());
 
-                // Render Background Clear (Redundant physically but good for semantic layout if needed)
-                f.render_widget(Clear, visible_area);
+        if !visible_area.is_empty() {
+            clear_popup_area(f.buffer_mut(), visible_area);
 
-                let block = Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(theme.blue))
-                    .style(Style::default().bg(Color::Reset));
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(color))
+                .style(Style::default().bg(Color::Reset));
 
-                let style = Style::default().fg(theme.blue).add_modifier(Modifier::BOLD);
+            let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
 
-                let text = Paragraph::new(Line::from(vec![Span::styled(message.as_str(), style)]))
-                    .alignment(Alignment::Center)
-                    .block(block);
+            let text = Paragraph::new(Line::from(vec![Span::styled(message.as_str(), style)]))
+                .alignment(Alignment::Center)
+                .block(block);
 
-                f.render_widget(text, visible_area);
-            }
+            f.render_widget(text, visible_area);
         }
     }
 }
@@ -0,0 +1,102 @@
+use crate::app::App;
+use crate::t;
+use crate::ui::utils::{clear_popup_area, fit, truncate};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(f: &mut Frame, app: &mut App) {
+    let Some(details) = app.track_details.as_mut() else {
+        return;
+    };
+    let theme = &app.theme;
+
+    let rows_total: usize = details.sections.iter().map(|(_, r)| r.len() + 2).sum();
+    let width = f.area().width.saturating_sub(4).min(76);
+    let height = f.area().height.saturating_sub(4).min(rows_total as u16 + 3);
+    if width < 30 || height < 6 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let label_w = details
+        .sections
+        .iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(l, _)| l.width()))
+        .max()
+        .unwrap_or(8)
+        .min(24);
+    let value_w = (width as usize).saturating_sub(2 + 2 + label_w + 2);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (heading, rows) in &details.sections {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            heading.clone(),
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        )));
+        for (label, value) in rows {
+            // Long values (paths, lyrics) keep their start
+            let value = truncate(&value.replace(['\n', '\r'], " "), value_w);
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {}  ", fit(label, label_w)),
+                    Style::default().fg(theme.overlay),
+                ),
+                Span::styled(value, Style::default().fg(theme.text)),
+            ]));
+        }
+    }
+
+    // Keep the scroll inside the list
+    let rows = height.saturating_sub(3) as usize;
+    details.scroll = details.scroll.min(lines.len().saturating_sub(rows));
+    let lines: Vec<Line> = lines.into_iter().skip(details.scroll).take(rows).collect();
+    let more = details.scroll + rows < rows_total.saturating_sub(1);
+
+    let title = format!(" 🏷️ {} ", details.title);
+    let mut hint = vec![
+        Span::styled(
+            "j/k",
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.scroll"), Style::default().fg(theme.overlay)),
+        Span::styled(
+            "Esc",
+            Style::default().fg(theme.red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(t!("hint.close") + " ", Style::default().fg(theme.overlay)),
+    ];
+    if more {
+        hint.insert(0, Span::styled(" ↓ ", Style::default().fg(theme.overlay)));
+    }
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                title,
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .title_bottom(Line::from(hint).right_aligned())
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
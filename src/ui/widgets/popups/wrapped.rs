@@ -0,0 +1,124 @@
+use crate::app::App;
+use crate::t;
+use crate::ui::utils::{clear_popup_area, fit};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+/// Rows of each top list
+const TOP_ROWS: usize = 5;
+
+pub fn render(f: &mut Frame, app: &App) {
+    let Some(report) = app.wrapped.as_ref() else {
+        return;
+    };
+    let theme = &app.theme;
+
+    let width = f.area().width.saturating_sub(4).min(80);
+    let height = f.area().height.saturating_sub(2).min(TOP_ROWS as u16 + 17);
+    if width < 40 || height < 12 {
+        return;
+    }
+    let popup_area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    clear_popup_area(f.buffer_mut(), popup_area);
+
+    let inner_width = width.saturating_sub(2) as usize;
+    let heading = Style::default()
+        .fg(theme.yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut lines: Vec<Line> = vec![
+        Line::from(Span::styled(
+            report.summary(),
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    // The three top lists side by side
+    let lists = [
+        (t!("wrapped.top_artists"), &report.top_artists),
+        (t!("wrapped.top_songs"), &report.top_songs),
+        (t!("wrapped.top_genres"), &report.top_genres),
+    ];
+    let column = inner_width / lists.len();
+    lines.push(Line::from(
+        lists
+            .iter()
+            .map(|(title, _)| Span::styled(fit(title, column), heading))
+            .collect::<Vec<_>>(),
+    ));
+    for row in 0..TOP_ROWS {
+        let spans: Vec<Span> = lists
+            .iter()
+            .map(|(_, list)| match list.get(row) {
+                Some((name, count)) => {
+                    let count = format!(" {}", count);
+                    let name_width = column.saturating_sub(count.len() + 5);
+                    Span::styled(
+                        format!("{}. {}{}  ", row + 1, fit(name, name_width), count),
+                        Style::default().fg(theme.text),
+                    )
+                }
+                None => Span::raw(" ".repeat(column)),
+            })
+            .collect();
+        lines.push(Line::from(spans));
+    }
+
+    // Listening heat map, two cells per hour when there's room
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(t!("wrapped.when"), heading)));
+    let cell = if inner_width >= 4 + 48 { 2 } else { 1 };
+    for (i, row) in report.heat_rows(cell).into_iter().enumerate() {
+        let style = if i == 0 {
+            Style::default().fg(theme.overlay)
+        } else {
+            Style::default().fg(theme.magenta)
+        };
+        let label: String = row.chars().take(4).collect();
+        let cells: String = row.chars().skip(4).collect();
+        lines.push(Line::from(vec![
+            Span::styled(label, Style::default().fg(theme.overlay)),
+            Span::styled(cells, style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let key = |k: &'static str, color| {
+        Span::styled(k, Style::default().fg(color).add_modifier(Modifier::BOLD))
+    };
+    lines.push(Line::from(vec![
+        key("←/→", theme.blue),
+        Span::styled(t!("wrapped.year"), Style::default().fg(theme.overlay)),
+        key("e", theme.green),
+        Span::styled(t!("wrapped.export"), Style::default().fg(theme.overlay)),
+        key("Esc", theme.red),
+        Span::styled(t!("hint.close"), Style::default().fg(theme.overlay)),
+    ]));
+
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                t!("wrapped.title", year = report.year),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(Color::Reset)),
+    );
+    f.render_widget(popup, popup_area);
+}
@@ -0,0 +1,201 @@
+use crate::app::wizard::{MpdCheck, Wizard, WizardStep};
+use crate::t;
+use crate::ui::theme::Theme;
+use crate::ui::utils::truncate;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// The first-run wizard, drawn in the theme being picked
+pub fn render(f: &mut Frame, wizard: &Wizard, theme: &Theme) {
+    f.render_widget(Clear, f.area());
+    f.render_widget(
+        Block::default().style(Style::default().bg(theme.base)),
+        f.area(),
+    );
+
+    let width = f.area().width.saturating_sub(4).min(64);
+    let height = f.area().height.saturating_sub(2).min(18);
+    if width < 30 || height < 10 {
+        return;
+    }
+    let area = Rect::new(
+        (f.area().width.saturating_sub(width)) / 2,
+        (f.area().height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+    let inner_w = width.saturating_sub(4) as usize;
+
+    let label = Style::default().fg(theme.overlay);
+    let text = Style::default().fg(theme.text);
+    let heading = Style::default()
+        .fg(theme.magenta)
+        .add_modifier(Modifier::BOLD);
+    let field = |active: bool| {
+        if active {
+            Style::default()
+                .fg(theme.green)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            label
+        }
+    };
+    let cursor = |active: bool| Span::styled(if active { "▌" } else { "" }, field(true));
+
+    let steps = wizard.steps();
+    let at = steps.iter().position(|s| *s == wizard.step).unwrap_or(0);
+
+    let mut lines: Vec<Line> = Vec::new();
+    match wizard.step {
+        WizardStep::Mpd => {
+            lines.push(Line::from(Span::styled(t!("wizard.mpd_heading"), heading)));
+            lines.push(Line::from(Span::styled(t!("wizard.mpd_text"), label)));
+            lines.push(Line::from(""));
+            for (i, (name, value)) in [
+                (t!("wizard.host"), &wizard.host),
+                (t!("wizard.port"), &wizard.port),
+            ]
+            .iter()
+            .enumerate()
+            {
+                let active = i == wizard.active_field;
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:>6}: ", name), field(active)),
+                    Span::styled(value.to_string(), text),
+                    cursor(active),
+                ]));
+            }
+            lines.push(Line::from(""));
+            let (status, color) = match &wizard.mpd {
+                MpdCheck::Untested => (t!("wizard.mpd_untested"), theme.overlay),
+                MpdCheck::Connected(version, songs) => (
+                    t!("wizard.mpd_connected", version = version, songs = songs),
+                    theme.green,
+                ),
+                MpdCheck::Failed(e) => (t!("wizard.mpd_failed", error = e), theme.red),
+            };
+            lines.push(Line::from(Span::styled(
+                truncate(&status, inner_w),
+                Style::default().fg(color),
+            )));
+            if matches!(wizard.mpd, MpdCheck::Failed(_)) {
+                lines.push(Line::from(Span::styled(t!("wizard.mpd_continue"), label)));
+            }
+        }
+        WizardStep::MusicDir => {
+            lines.push(Line::from(Span::styled(t!("wizard.dir_heading"), heading)));
+            lines.push(Line::from(Span::styled(t!("wizard.dir_text"), label)));
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(truncate(&wizard.music_dir, inner_w.saturating_sub(1)), text),
+                cursor(true),
+            ]));
+            lines.push(Line::from(""));
+            let (status, color) = if wizard.music_path().is_dir() {
+                (t!("wizard.dir_found"), theme.green)
+            } else {
+                (t!("wizard.dir_missing"), theme.yellow)
+            };
+            lines.push(Line::from(Span::styled(status, Style::default().fg(color))));
+        }
+        WizardStep::Theme => {
+            lines.push(Line::from(Span::styled(
+                t!("wizard.theme_heading"),
+                heading,
+            )));
+            lines.push(Line::from(""));
+            // Keep the selection in view
+            let rows = (height as usize).saturating_sub(9);
+            let start = wizard
+                .theme_selected
+                .saturating_sub(rows.saturating_sub(1))
+                .min(wizard.themes.len().saturating_sub(rows));
+            for (i, name) in wizard.themes.iter().enumerate().skip(start).take(rows) {
+                let selected = i == wizard.theme_selected;
+                let style = if selected {
+                    Style::default()
+                        .fg(theme.magenta)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    text
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(if selected { "▶ " } else { "  " }, style),
+                    Span::styled(name.clone(), style),
+                ]));
+            }
+        }
+        WizardStep::Summary => {
+            lines.push(Line::from(Span::styled(
+                t!("wizard.summary_heading"),
+                heading,
+            )));
+            lines.push(Line::from(""));
+            let mut rows = Vec::new();
+            if wizard.use_mpd {
+                let mpd = match &wizard.mpd {
+                    MpdCheck::Connected(..) => format!("{}:{} ✔", wizard.host, wizard.port),
+                    _ => format!("{}:{} ✖", wizard.host, wizard.port),
+                };
+                rows.push((t!("wizard.summary_mpd"), mpd));
+            }
+            rows.push((t!("wizard.summary_dir"), wizard.music_dir.clone()));
+            rows.push((
+                t!("wizard.summary_theme"),
+                wizard.selected_theme().unwrap_or_default().to_string(),
+            ));
+            for (name, value) in rows {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:>8}: ", name), label),
+                    Span::styled(truncate(&value, inner_w.saturating_sub(10)), text),
+                ]));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(t!("wizard.summary_text"), label)));
+        }
+    }
+
+    // Pin the hint row to the bottom
+    for _ in lines.len()..(height as usize).saturating_sub(3) {
+        lines.push(Line::from(""));
+    }
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        )
+    };
+    let enter_hint = match wizard.step {
+        WizardStep::Mpd => t!("wizard.hint_test"),
+        WizardStep::Summary => t!("wizard.hint_save"),
+        _ => t!("hint.next"),
+    };
+    lines.push(Line::from(vec![
+        key("Enter"),
+        Span::styled(enter_hint, label),
+        key("S-Tab"),
+        Span::styled(t!("wizard.hint_back"), label),
+        key("Esc"),
+        Span::styled(t!("wizard.hint_skip"), label),
+    ]));
+
+    let title = t!("wizard.title", step = at + 1, steps = steps.len());
+    let popup = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                title,
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .style(Style::default().bg(theme.base)),
+    );
+    f.render_widget(popup, area);
+}
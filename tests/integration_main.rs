@@ -1,5 +1,7 @@
 use vyom::app::config::UserConfig;
-use vyom::app::{App, LibraryMode, QueueItem, TagEditState, ViewMode};
+use vyom::app::{
+    App, LibraryMode, QueueItem, TagEditState, TagField, TagValue, ToastLevel, ViewMode,
+};
 
 /// Helper to create a test app instance
 fn create_test_app() -> App {
@@ -15,6 +17,29 @@ fn create_test_app() -> App {
     )
 }
 
+/// Minimal paused MPD track
+fn test_track(name: &str, artist: &str, album: &str) -> vyom::player::TrackInfo {
+    vyom::player::TrackInfo {
+        name: name.to_string(),
+        artist: artist.to_string(),
+        album: album.to_string(),
+        artwork_url: None,
+        duration_ms: 330_000,
+        position_ms: 0,
+        state: vyom::player::PlayerState::Paused,
+        source: "MPD".to_string(),
+        codec: None,
+        bitrate: None,
+        sample_rate: None,
+        bit_depth: None,
+        file_path: None,
+        volume: None,
+        track_number: None,
+        track_total: None,
+        genre: None,
+    }
+}
+
 #[test]
 fn test_app_initialization() {
     let app = create_test_app();
@@ -59,13 +84,65 @@ fn test_tag_edit_logic() {
     tag_state.next_field();
     assert_eq!(tag_state.active_field, 2);
 
+    // On through album artist, genre, year, track, disc and comment
+    for _ in 3..TagEditState::SINGLE.len() {
+        tag_state.next_field();
+    }
+    assert_eq!(
+        tag_state.fields[tag_state.active_field].field,
+        TagField::Comment
+    );
+
     // Loop back -> Title
     tag_state.next_field();
     assert_eq!(tag_state.active_field, 0);
 
-    // Prev field -> Album (Loop back)
+    // Prev field -> Comment (Loop back)
     tag_state.prev_field();
-    assert_eq!(tag_state.active_field, 2);
+    assert_eq!(
+        tag_state.fields[tag_state.active_field].field,
+        TagField::Comment
+    );
+
+    // Only title, artist and album are known up front
+    assert_eq!(tag_state.fields[2].value, "Album");
+    assert!(tag_state.fields[3..].iter().all(|f| f.value.is_empty()));
+}
+
+#[test]
+fn test_batch_tag_edit_only_writes_touched_fields() {
+    let album = TagValue::shared(
+        TagField::Album,
+        &[Some("Kind of Blue".into()), Some("Kind of Blue".into())],
+    );
+    let genre = TagValue::shared(TagField::Genre, &[Some("Jazz".into()), None]);
+    assert_eq!(album.value, "Kind of Blue");
+    assert!(!album.mixed);
+    assert!(genre.mixed && genre.value.is_empty());
+
+    let mut edit =
+        TagEditState::with_fields(vec!["a.flac".into(), "b.flac".into()], vec![album, genre]);
+    assert!(edit.is_batch());
+    assert!(edit.changes().is_empty());
+
+    // Typing into a field takes it off "keep existing"
+    edit.next_field();
+    for c in "Modal".chars() {
+        edit.push_char(c);
+    }
+    assert_eq!(edit.changes(), vec![(TagField::Genre, "Modal".to_string())]);
+
+    // Ctrl+k puts it back
+    edit.keep_active();
+    assert!(edit.changes().is_empty());
+    assert!(edit.fields[1].value.is_empty());
+
+    // Emptying a field clears the tag everywhere
+    edit.prev_field();
+    for _ in 0.."Kind of Blue".len() {
+        edit.pop_char();
+    }
+    assert_eq!(edit.changes(), vec![(TagField::Album, String::new())]);
 }
 
 #[test]
@@ -96,6 +173,7 @@ fn test_queue_manipulation() {
         duration_ms: 1000,
         is_current: true,
         file_path: "song_a.mp3".to_string(),
+        ..Default::default()
     };
 
     app.queue.push(item);
@@ -108,40 +186,76 @@ fn test_queue_manipulation() {
 #[test]
 fn test_toast_creation() {
     let mut app = create_test_app();
-    assert!(app.toast.is_none());
+    assert!(app.toasts.is_empty());
 
     app.show_toast("Hello!");
-    assert!(app.toast.is_some());
-    assert_eq!(app.toast.as_ref().unwrap().message, "Hello!");
+    assert!(!app.toasts.is_empty());
+    assert_eq!(app.toasts.latest().unwrap().message, "Hello!");
+}
+
+#[test]
+fn test_error_report_arms_retry_until_it_expires() {
+    use vyom::app::events::Retry;
+    use vyom::error::VyomError;
+
+    let mut app = create_test_app();
+    app.report(
+        &VyomError::LyricsFetch("timeout".into()),
+        Some(Retry::Lyrics),
+    );
+    let toast = app.toasts.latest().unwrap();
+    assert_eq!(toast.level, ToastLevel::Error);
+    assert!(toast.message.contains("timeout"));
+    assert!(toast.message.contains("Ctrl+t"));
+    assert_eq!(app.take_retry(), Some(Retry::Lyrics));
+    assert_eq!(app.take_retry(), None, "a retry runs once");
+
+    // A stale retry is dropped
+    app.report(
+        &VyomError::AudioPipeline("no stream".into()),
+        Some(Retry::Pipeline),
+    );
+    app.retry.as_mut().unwrap().1 = std::time::Instant::now() - std::time::Duration::from_millis(1);
+    assert_eq!(app.take_retry(), None);
+
+    // Errors without a retry clear an older one
+    app.report(
+        &VyomError::PlayerCommand("gone".into()),
+        Some(Retry::Pipeline),
+    );
+    app.report(&VyomError::MpdConnection("refused".into()), None);
+    assert_eq!(app.take_retry(), None);
+    assert!(!app.toasts.latest().unwrap().message.contains("Ctrl+t"));
 }
 
 #[test]
 fn test_toast_stacking_updates_message() {
     let mut app = create_test_app();
     app.show_toast("First");
-    let start_time = app.toast.as_ref().unwrap().start_time;
+    let start_time = app.toasts.latest().unwrap().start_time;
 
     app.show_toast("Second");
-    // Message should be updated
-    assert_eq!(app.toast.as_ref().unwrap().message, "Second");
+    // Message should be updated in place
+    assert_eq!(app.toasts.active.len(), 1);
+    assert_eq!(app.toasts.latest().unwrap().message, "Second");
     // start_time should be preserved (no re-animation)
-    assert_eq!(app.toast.as_ref().unwrap().start_time, start_time);
+    assert_eq!(app.toasts.latest().unwrap().start_time, start_time);
 }
 
 #[test]
 fn test_toast_expiry_on_tick() {
     let mut app = create_test_app();
     app.show_toast("Expiring");
-    assert!(app.toast.is_some());
+    assert!(!app.toasts.is_empty());
 
     // Manually set deadline to the past to simulate expiry
-    if let Some(ref mut toast) = app.toast {
+    for toast in app.toasts.active.iter_mut() {
         toast.deadline = std::time::Instant::now() - std::time::Duration::from_millis(1);
     }
 
     app.on_tick();
     assert!(
-        app.toast.is_none(),
+        app.toasts.is_empty(),
         "Toast should be cleared after deadline"
     );
 }
@@ -153,5 +267,1058 @@ fn test_toast_not_expired_during_active() {
 
     // Don't modify deadline — it should be 2s in the future
     app.on_tick();
-    assert!(app.toast.is_some(), "Toast should still be visible");
+    assert!(!app.toasts.is_empty(), "Toast should still be visible");
+}
+
+#[test]
+fn test_error_toast_stacks_and_is_logged() {
+    let mut app = create_test_app();
+    app.show_toast("Saved");
+    app.show_error("Connection refused");
+
+    // Different levels stack instead of replacing each other
+    assert_eq!(app.toasts.active.len(), 2);
+    assert_eq!(app.toasts.latest().unwrap().level, ToastLevel::Error);
+
+    // The history outlives the toasts
+    for toast in app.toasts.active.iter_mut() {
+        toast.deadline = std::time::Instant::now() - std::time::Duration::from_millis(1);
+    }
+    app.on_tick();
+    assert!(app.toasts.is_empty());
+    assert_eq!(app.toasts.history[0].message, "Connection refused");
+    assert_eq!(app.toasts.history[1].message, "Saved");
+}
+
+#[test]
+fn test_seek_mode_clamps_ghost_playhead() {
+    let mut app = create_test_app();
+    // Nothing to seek in
+    app.start_seek_mode();
+    assert!(app.seek_preview.is_none());
+
+    let mut track = test_track("Song", "Artist", "Album");
+    track.position_ms = 5_000;
+    app.track = Some(track);
+    app.start_seek_mode();
+    assert_eq!(app.seek_preview, Some(5_000));
+
+    app.nudge_seek_preview(-10_000);
+    assert_eq!(app.seek_preview, Some(0));
+    app.nudge_seek_preview(1_000);
+    app.nudge_seek_preview(1_000);
+    assert_eq!(app.seek_preview, Some(2_000));
+    app.nudge_seek_preview(1_000_000);
+    assert_eq!(app.seek_preview, Some(330_000));
+
+    // A new song drops the preview
+    app.on_track_changed();
+    assert!(app.seek_preview.is_none());
+}
+
+#[test]
+fn test_seek_preview_finds_the_lyric_line() {
+    use vyom::app::lyrics::LyricLine;
+    let mut app = create_test_app();
+    app.track = Some(test_track("Song", "Artist", "Album"));
+    let line = |ms: u64, text: &str| LyricLine {
+        timestamp_ms: ms,
+        text: text.to_string(),
+    };
+    app.lyrics = vyom::app::LyricsState::Loaded(
+        vec![
+            line(10_000, "Verse one"),
+            line(40_000, ""),
+            line(60_000, "Chorus"),
+        ],
+        "Test".to_string(),
+    );
+
+    // Before the first line there's nothing to show
+    assert_eq!(app.lyrics_at(5_000), None);
+    assert_eq!(app.lyrics_at(12_000), Some(("Verse one", Some(""))));
+    assert_eq!(app.lyrics_at(75_000), Some(("Chorus", None)));
+
+    // The sync offset counts, as it does for the lyrics view
+    app.lyrics_offsets
+        .insert("Artist - Song".to_string(), 20_000);
+    assert_eq!(app.lyrics_at(75_000), Some(("", Some("Chorus"))));
+
+    assert_eq!(app.seek_target(), None);
+    app.seek_flash = Some((61_000, std::time::Instant::now()));
+    assert_eq!(app.seek_target(), Some(61_000));
+    app.on_tick();
+    assert_eq!(app.seek_target(), None);
+}
+
+#[test]
+fn test_bookmarks_join_chapters_and_sections() {
+    use vyom::app::chapters::Chapter;
+    use vyom::app::lyrics::LyricLine;
+    use vyom::app::markers::MarkerKind;
+    let mut app = create_test_app();
+    let mut track = test_track("Song", "Artist", "Album");
+    track.file_path = Some("Artist/Album/01.flac".to_string());
+    track.position_ms = 90_000;
+    app.track = Some(track);
+    app.chapters = vec![Chapter {
+        title: "Part 2".to_string(),
+        start_ms: 120_000,
+    }];
+    app.lyrics = vyom::app::LyricsState::Loaded(
+        vec![
+            LyricLine {
+                timestamp_ms: 10_000,
+                text: "Verse one".to_string(),
+            },
+            LyricLine {
+                timestamp_ms: 40_000,
+                text: String::new(),
+            },
+            LyricLine {
+                timestamp_ms: 60_000,
+                text: "Chorus".to_string(),
+            },
+        ],
+        "Test".to_string(),
+    );
+
+    assert_eq!(app.toggle_bookmark(), Some(true));
+    let kinds: Vec<_> = app.markers().iter().map(|m| (m.ms, m.kind)).collect();
+    assert_eq!(
+        kinds,
+        [
+            (10_000, MarkerKind::Section),
+            (60_000, MarkerKind::Section),
+            (90_000, MarkerKind::Bookmark),
+            (120_000, MarkerKind::Chapter),
+        ]
+    );
+
+    // Pressing again near the bookmark takes it away
+    app.track.as_mut().unwrap().position_ms = 91_000;
+    assert_eq!(app.toggle_bookmark(), Some(false));
+    assert!(app.bookmarks.is_empty());
+
+    // Nothing to bookmark without a file
+    app.track.as_mut().unwrap().file_path = None;
+    assert_eq!(app.toggle_bookmark(), None);
+}
+
+#[test]
+fn test_eq_ab_toggle_swaps_curves() {
+    let mut app = create_test_app();
+    app.eq_bands = [0.8; 10];
+
+    // B defaults to a flat reference
+    app.toggle_ab();
+    assert!(app.eq_ab_on_b);
+    assert_eq!(app.eq_bands, [0.5; 10]);
+    // Saved state always reflects the A side
+    assert_eq!(app.eq_bands_a(), [0.8; 10]);
+
+    app.toggle_ab();
+    assert!(!app.eq_ab_on_b);
+    assert_eq!(app.eq_bands, [0.8; 10]);
+}
+
+#[test]
+fn test_console_history_navigation() {
+    let mut app = create_test_app();
+    app.console.remember("status");
+    app.console.remember("stats");
+    app.console.remember("stats");
+    assert_eq!(app.console.history.len(), 2, "Duplicates should collapse");
+
+    app.console.history_prev();
+    assert_eq!(app.console.input, "stats");
+    app.console.history_prev();
+    assert_eq!(app.console.input, "status");
+    app.console.history_next();
+    app.console.history_next();
+    assert!(app.console.input.is_empty());
+    assert!(app.console.history_pos.is_none());
+}
+
+#[test]
+fn test_spectrum_db_peaks_at_tone() {
+    let mut app = create_test_app();
+    let buffer = app.visualizer.get_audio_buffer();
+
+    // 1kHz sine at half scale (-6 dBFS), mono
+    let samples: Vec<f32> = (0..8192)
+        .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / 44100.0).sin())
+        .collect();
+    vyom::audio::visualizer::Visualizer::push_samples(&buffer, &samples, 1);
+
+    let levels = app.visualizer.spectrum_db(&[1000.0, 100.0], false);
+    assert!(
+        (levels[0] + 6.0).abs() < 1.5,
+        "Tone should read about -6 dBFS, got {}",
+        levels[0]
+    );
+    assert!(levels[1] < levels[0] - 40.0, "Off-tone bins stay low");
+
+    // The pre-DSP tap is its own buffer
+    let mut app = create_test_app();
+    let pre = app.visualizer.get_pre_buffer();
+    vyom::audio::visualizer::Visualizer::push_samples(&pre, &samples, 1);
+    let levels = app.visualizer.spectrum_db(&[1000.0], true);
+    assert!((levels[0] + 6.0).abs() < 1.5);
+    let post = app.visualizer.spectrum_db(&[1000.0], false);
+    assert!(post[0] < -90.0, "Nothing reached the output tap");
+}
+
+#[test]
+fn test_meter_hold_and_clip_latch() {
+    use std::time::{Duration, Instant};
+    use vyom::audio::dsp::meter::MeterFrame;
+
+    let mut app = create_test_app();
+    let t0 = Instant::now();
+    let loud = MeterFrame {
+        peak: [1.0, 0.1],
+        rms: [0.7, 0.05],
+    };
+    app.meters.update(Some(loud), t0);
+    assert!(app.meters.channels[0].is_clipping(t0));
+    assert!(!app.meters.channels[1].is_clipping(t0));
+
+    // Silence: bars fall, hold stays put, clip stays lit for a while
+    let t1 = t0 + Duration::from_millis(200);
+    app.meters.update(None, t1);
+    let left = app.meters.channels[0];
+    assert!(left.peak_db < -1.0);
+    assert!(left.hold_db.abs() < 0.01);
+    assert!(left.is_clipping(t1));
+    assert!(!left.is_clipping(t0 + Duration::from_secs(4)));
+}
+
+#[test]
+fn test_crossfade_toggle_cycles() {
+    let mut app = create_test_app();
+    app.crossfade_secs = 0;
+
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        app.toggle_crossfade();
+        seen.push(app.crossfade_secs);
+    }
+    assert_eq!(seen, [2, 4, 6, 0]);
+}
+
+#[test]
+fn test_pitch_shift_clamps() {
+    let mut app = create_test_app();
+    assert_eq!(app.pitch.get(), 0);
+    app.shift_pitch(1);
+    app.shift_pitch(1);
+    assert_eq!(app.pitch.get(), 2);
+    assert!((app.pitch.ratio() - 2f32.powf(2.0 / 12.0)).abs() < 1e-6);
+
+    for _ in 0..30 {
+        app.shift_pitch(-1);
+    }
+    assert_eq!(app.pitch.get(), -12);
+}
+
+#[test]
+fn test_art_style_falls_back_without_graphics() {
+    use vyom::app::ArtStyle;
+
+    let mut app = create_test_app();
+    // Tests run with a half-block picker, like a terminal without graphics
+    assert!(!app.has_graphics());
+    app.art_style = ArtStyle::Auto;
+    assert_eq!(app.effective_art_style(), ArtStyle::Block);
+    app.art_style = ArtStyle::Image;
+    assert_eq!(app.effective_art_style(), ArtStyle::Block);
+
+    app.image_picker
+        .set_protocol_type(ratatui_image::picker::ProtocolType::Kitty);
+    app.art_style = ArtStyle::Auto;
+    assert_eq!(app.effective_art_style(), ArtStyle::Image);
+    app.art_style = ArtStyle::Braille;
+    assert_eq!(app.effective_art_style(), ArtStyle::Braille);
+}
+
+#[test]
+fn test_art_backdrop_fills_blank_cells_only() {
+    use ratatui::{backend::TestBackend, style::Color, Terminal};
+    use vyom::app::{ArtStyle, ArtworkState};
+
+    let mut app = create_test_app();
+    app.show_art_backdrop = true;
+    app.art_style = ArtStyle::Off;
+    let img = image::RgbImage::from_pixel(8, 8, image::Rgb([255, 0, 0]));
+    app.artwork = ArtworkState::Loaded(std::sync::Arc::new(image::DynamicImage::ImageRgb8(img)));
+
+    let mut terminal = Terminal::new(TestBackend::new(40, 30)).unwrap();
+    terminal
+        .draw(|f| vyom::ui::widgets::player::render(f, f.area(), &mut app))
+        .unwrap();
+    let buf = terminal.backend().buffer();
+
+    // Blank cell inside the card: red-tinted, but still dark
+    let cell = &buf[(20, 3)];
+    assert_eq!(cell.symbol(), "▀");
+    let Color::Rgb(r, g, _) = cell.bg else {
+        panic!("Expected a tinted background, got {:?}", cell.bg);
+    };
+    assert!(r > g && r < 128, "Dimmed red expected, got {:?}", cell.bg);
+
+    // The border is left alone
+    assert_eq!(buf[(0, 0)].symbol(), "╭");
+    assert!(app.art_backdrop.is_some());
+}
+
+#[test]
+fn test_fullscreen_art_shows_title_only() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut app = create_test_app();
+    app.track = Some(test_track("Teardrop", "Massive Attack", "Mezzanine"));
+    app.toggle_fullscreen_art();
+    assert!(app.fullscreen_art);
+
+    let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+    terminal.draw(|f| vyom::ui::ui(f, &mut app)).unwrap();
+    let buf = terminal.backend().buffer();
+    let row = |y: u16| (0..60).map(|x| buf[(x, y)].symbol()).collect::<String>();
+
+    assert!(row(18).contains("Teardrop"));
+    assert!(row(19).contains("Massive Attack — Mezzanine"));
+    // No player card border
+    assert!(!(0..20).any(|y| row(y).contains("Now Playing")));
+}
+
+#[test]
+fn test_lyrics_sync_offset_is_per_track() {
+    let mut app = create_test_app();
+    let mut track = test_track("Teardrop", "Massive Attack", "Mezzanine");
+    track.position_ms = 10_000;
+    app.track = Some(track);
+    assert_eq!(app.lyrics_position_ms(), 10_000);
+
+    // Positive offset = lyrics later, so they see an earlier position
+    app.lyrics_offsets
+        .insert("Massive Attack - Teardrop".to_string(), 300);
+    assert_eq!(app.lyrics_sync_ms(), 300);
+    assert_eq!(app.lyrics_position_ms(), 9_700);
+
+    app.track = Some(test_track("Angel", "Massive Attack", "Mezzanine"));
+    assert_eq!(app.lyrics_sync_ms(), 0);
+}
+
+#[test]
+fn test_theme_picker_previews_and_reverts() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use vyom::app::inputs::theme_picker::handle_theme_picker;
+
+    let mut app = create_test_app();
+    let original = app.base_theme.blue;
+    app.open_theme_picker();
+    let picker = app.theme_picker.as_ref().expect("Picker open");
+    assert!(picker.entries.len() >= 2, "Built-in themes are listed");
+
+    // Moving previews a different theme right away
+    let down = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+    assert!(handle_theme_picker(down, &mut app));
+    let previewed = app.theme_picker.as_ref().unwrap().current().unwrap().1.blue;
+    assert_eq!(app.theme.blue, previewed);
+
+    // Esc puts the old theme back and nothing is remembered
+    let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+    assert!(handle_theme_picker(esc, &mut app));
+    assert!(app.theme_picker.is_none());
+    assert_eq!(app.base_theme.blue, original);
+    assert!(app.picked_theme.is_none());
+
+    // A pick lasts until config.toml names another theme
+    let state = vyom::app::config::PersistentState {
+        theme: Some("nord".into()),
+        theme_config: Some("dracula".into()),
+        ..Default::default()
+    };
+    assert_eq!(state.picked_theme(Some("dracula")), Some("nord".into()));
+    assert_eq!(state.picked_theme(Some("gruvbox-dark")), None);
+    assert_eq!(state.picked_theme(None), None);
+}
+
+#[test]
+fn test_split_view_shows_both_panels_and_moves_focus() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut app = create_test_app();
+    app.view_mode = ViewMode::Lyrics;
+    app.toggle_split_view(&[ViewMode::Visualizer, ViewMode::Lyrics]);
+    assert_eq!(app.split_view, Some(ViewMode::Visualizer));
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|f| vyom::ui::ui(f, &mut app)).unwrap();
+    let screen: String = {
+        let buf = terminal.backend().buffer();
+        (0..40)
+            .flat_map(|y| (0..120).map(move |x| (x, y)))
+            .map(|p| buf[p].symbol().to_string())
+            .collect()
+    };
+    assert!(screen.contains("Lyrics") && screen.contains("Visualizer"));
+
+    // Focus moves without moving the panes
+    app.focus_other_pane();
+    assert_eq!(app.view_mode, ViewMode::Visualizer);
+    assert_eq!(
+        app.panel_views(),
+        (ViewMode::Lyrics, Some(ViewMode::Visualizer))
+    );
+    // Picking the other pane's view just focuses it
+    app.show_view(ViewMode::Lyrics);
+    assert_eq!(app.view_mode, ViewMode::Lyrics);
+    assert!(!app.split_focus_second);
+
+    app.toggle_split_view(&[]);
+    assert_eq!(app.split_view, None);
+}
+
+#[tokio::test]
+async fn test_library_list_jumps_and_pages() {
+    use clap::Parser;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{backend::TestBackend, Terminal};
+    use vyom::app::inputs::library::handle_library_events;
+
+    let mut app = create_test_app();
+    let args = vyom::app::cli::Args::parse_from(["vyom"]);
+    app.view_mode = ViewMode::Library;
+    app.library_mode = LibraryMode::Queue;
+    app.queue = (1..=200)
+        .map(|i| QueueItem {
+            title: format!("Song {}", i),
+            artist: "Artist".to_string(),
+            duration_ms: 180_000,
+            is_current: false,
+            file_path: format!("song{}.flac", i),
+            ..Default::default()
+        })
+        .collect();
+    let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+    let mut draw = |app: &mut App| {
+        terminal.draw(|f| vyom::ui::ui(f, app)).unwrap();
+        let buf = terminal.backend().buffer().clone();
+        (0..30)
+            .flat_map(|y| (0..80).map(move |x| (x, y)))
+            .map(|p| buf[p].symbol().to_string())
+            .collect::<String>()
+    };
+
+    // G lands on the last song and the list scrolls to it
+    assert!(handle_library_events(press(KeyCode::Char('G')), &mut app, &args).await);
+    assert_eq!(app.library_selected, 199);
+    assert!(draw(&mut app).contains("Song 200"));
+
+    // PageUp moves by the rows on screen, and the view follows
+    let page = app.library_page;
+    assert!(page > 0);
+    handle_library_events(press(KeyCode::PageUp), &mut app, &args).await;
+    assert_eq!(app.library_selected, 199 - page);
+    let screen = draw(&mut app);
+    assert!(screen.contains(&format!("Song {}", 200 - page)));
+
+    // A single g waits; the second one jumps to the top
+    handle_library_events(press(KeyCode::Char('g')), &mut app, &args).await;
+    assert_eq!(app.library_selected, 199 - page);
+    handle_library_events(press(KeyCode::Char('g')), &mut app, &args).await;
+    assert_eq!(app.library_selected, 0);
+    assert!(draw(&mut app).contains("Song 1 "));
+}
+
+#[tokio::test]
+async fn test_album_grid_moves_by_rows_and_returns_to_tile() {
+    use clap::Parser;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{backend::TestBackend, Terminal};
+    use vyom::app::inputs::library::handle_library_events;
+
+    let mut app = create_test_app();
+    let args = vyom::app::cli::Args::parse_from(["vyom"]);
+    app.view_mode = ViewMode::Library;
+    app.library_mode = LibraryMode::Albums;
+    app.albums = (1..=40)
+        .map(|i| vyom::app::AlbumEntry {
+            name: format!("Album {}", i),
+            artist: "Artist".to_string(),
+            file: format!("a{}/01.flac", i),
+        })
+        .collect();
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|f| vyom::ui::ui(f, &mut app)).unwrap();
+    let cols = app.album_cols;
+    assert!(cols > 1, "Several tiles per row");
+    let screen: String = {
+        let buf = terminal.backend().buffer();
+        (0..40)
+            .flat_map(|y| (0..120).map(move |x| (x, y)))
+            .map(|p| buf[p].symbol().to_string())
+            .collect()
+    };
+    assert!(screen.contains("Album 1") && screen.contains("Albums"));
+
+    let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    handle_library_events(press(KeyCode::Down), &mut app, &args).await;
+    assert_eq!(app.library_selected, cols);
+    handle_library_events(press(KeyCode::Right), &mut app, &args).await;
+    assert_eq!(app.library_selected, cols + 1);
+
+    // Leaving an album's track list lands back on its tile
+    app.album_open = Some(app.albums[6].key());
+    app.library_selected = 0;
+    handle_library_events(press(KeyCode::Esc), &mut app, &args).await;
+    assert_eq!(app.album_open, None);
+    assert_eq!(app.library_selected, 6);
+}
+
+#[test]
+fn test_tiny_terminal_shows_now_playing_bar() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut app = create_test_app();
+    app.track = Some(test_track("Pyramid Song", "Radiohead", "Amnesiac"));
+
+    let mut terminal = Terminal::new(TestBackend::new(60, 1)).unwrap();
+    terminal.draw(|f| vyom::ui::ui(f, &mut app)).unwrap();
+    assert!(app.bar_active);
+    let line: String = {
+        let buf = terminal.backend().buffer();
+        (0..60).map(|x| buf[(x, 0)].symbol().to_string()).collect()
+    };
+    assert!(line.contains("⏸"), "Paused state shown: {}", line);
+    assert!(line.contains("Radiohead – Pyramid Song"));
+    assert!(line.contains("/ 5:30"));
+
+    // Full UI again once there is room
+    let mut terminal = Terminal::new(TestBackend::new(60, 30)).unwrap();
+    terminal.draw(|f| vyom::ui::ui(f, &mut app)).unwrap();
+    assert!(!app.bar_active);
+}
+
+#[test]
+fn test_workspaces_keep_their_own_views() {
+    let mut app = create_test_app();
+    app.view_mode = ViewMode::Library;
+    app.library_mode = LibraryMode::Search;
+    app.search_query = "radiohead".to_string();
+    app.library_selected = 3;
+
+    // A new workspace starts as a copy, then goes its own way
+    app.switch_workspace(1);
+    assert_eq!(app.search_query, "radiohead");
+    app.library_mode = LibraryMode::Directory;
+    app.search_query.clear();
+    app.browse_path = vec!["Albums".to_string()];
+    app.library_selected = 0;
+
+    app.switch_workspace(0);
+    assert_eq!(app.library_mode, LibraryMode::Search);
+    assert_eq!(app.search_query, "radiohead");
+    assert_eq!(app.library_selected, 3);
+    assert!(app.browse_path.is_empty());
+
+    app.switch_workspace(1);
+    assert_eq!(app.library_mode, LibraryMode::Directory);
+    assert_eq!(app.browse_path, vec!["Albums".to_string()]);
+}
+
+#[test]
+fn test_session_survives_a_restart() {
+    use vyom::app::config::{PersistentState, SessionState};
+
+    let mut app = create_test_app();
+    app.view_mode = ViewMode::Library;
+    app.split_view = Some(ViewMode::Lyrics);
+    app.library_mode = LibraryMode::Directory;
+    app.browse_path = vec!["Rock".to_string(), "Live".to_string()];
+    app.library_selected = 7;
+    app.eq_selected = 4;
+
+    // Through state.toml and back
+    let state = PersistentState {
+        session: Some(app.session()),
+        ..Default::default()
+    };
+    let state: PersistentState = toml::from_str(&toml::to_string_pretty(&state).unwrap()).unwrap();
+    let restored = App::new(
+        true,
+        false,
+        true,
+        "MPD",
+        true,
+        UserConfig::default(),
+        state.clone(),
+    );
+    assert_eq!(restored.session(), app.session());
+
+    // Controller mode has no library: back to lyrics, the rest is kept
+    let controller = App::new(
+        true,
+        false,
+        false,
+        "Spotify",
+        true,
+        UserConfig::default(),
+        state,
+    );
+    assert_eq!(controller.view_mode, ViewMode::Lyrics);
+    assert_eq!(controller.split_view, None);
+    assert_eq!(controller.browse_path, app.browse_path);
+
+    // Older state files have no session
+    let old: PersistentState = toml::from_str("volume = 40").unwrap();
+    assert_eq!(old.session, None);
+    assert_eq!(SessionState::default().view_mode, ViewMode::Lyrics);
+}
+
+#[tokio::test]
+async fn test_jump_to_playing_and_follow() {
+    use clap::Parser;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use vyom::app::inputs::library::handle_library_events;
+
+    let mut app = create_test_app();
+    let args = vyom::app::cli::Args::parse_from(["vyom"]);
+    app.view_mode = ViewMode::Library;
+    app.library_mode = LibraryMode::Queue;
+    app.music_directory = "/music".to_string();
+    app.queue = (0..50)
+        .map(|i| QueueItem {
+            title: format!("Song {}", i),
+            artist: "Artist".to_string(),
+            duration_ms: 180_000,
+            is_current: i == 3,
+            file_path: format!("album/{:02}.flac", i),
+            ..Default::default()
+        })
+        .collect();
+    let mut track = test_track("Song 30", "Artist", "Album");
+    track.file_path = Some("/music/album/30.flac".to_string());
+    app.track = Some(track);
+
+    // The file wins over MPD's stale current flag
+    let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    assert!(handle_library_events(press(KeyCode::Char('o')), &mut app, &args).await);
+    assert_eq!(app.library_selected, 30);
+
+    // Following: the cursor moves with the next track
+    handle_library_events(press(KeyCode::Char('U')), &mut app, &args).await;
+    assert!(app.follow_playback);
+    app.library_selected = 0;
+    app.track.as_mut().unwrap().file_path = Some("/music/album/31.flac".to_string());
+    app.on_track_changed();
+    assert_eq!(app.library_selected, 31);
+    assert!(app.queue[31].is_current && !app.queue[3].is_current);
+}
+
+#[test]
+fn test_help_browser_pages_and_filter() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{backend::TestBackend, Terminal};
+    use vyom::app::inputs::help::{handle_help, open_help};
+
+    let mut app = create_test_app();
+    app.view_mode = ViewMode::EQ;
+    open_help(&mut app);
+    assert!(app.show_keyhints);
+    // Opens on the page for the current view: Global, Library, EQ, Lyrics
+    assert_eq!(app.help.page, 2);
+
+    let press = |app: &mut vyom::app::App, code: KeyCode| {
+        handle_help(KeyEvent::new(code, KeyModifiers::NONE), app)
+    };
+    press(&mut app, KeyCode::Tab);
+    assert_eq!(app.help.page, 3);
+    press(&mut app, KeyCode::Tab);
+    assert_eq!(app.help.page, 0);
+
+    // The filter searches every page
+    press(&mut app, KeyCode::Char('/'));
+    for c in "romaniz".chars() {
+        press(&mut app, KeyCode::Char(c));
+    }
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.help.query, "romaniz");
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+    terminal
+        .draw(|f| vyom::ui::widgets::popups::help::render(f, &mut app))
+        .unwrap();
+    let buf = terminal.backend().buffer();
+    let screen: String = (0..40)
+        .map(|y| (0..80).map(|x| buf[(x, y)].symbol()).collect::<String>())
+        .collect();
+    assert!(screen.contains("Lyrics"));
+    assert!(screen.contains("Romanization"));
+    assert!(!screen.contains("Play/Pause"));
+
+    // Esc clears the filter first, then closes
+    press(&mut app, KeyCode::Esc);
+    assert!(app.help.query.is_empty() && app.show_keyhints);
+    press(&mut app, KeyCode::Esc);
+    assert!(!app.show_keyhints);
+}
+
+#[cfg(feature = "mpd")]
+#[test]
+fn test_track_details_read_from_file() {
+    use vyom::app::library_helpers::file_details;
+
+    // One second of 16-bit stereo silence at 48 kHz
+    let (rate, channels, bits) = (48_000u32, 2u16, 16u16);
+    let data_len = rate * channels as u32 * bits as u32 / 8;
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&rate.to_le_bytes());
+    wav.extend_from_slice(&(rate * channels as u32 * bits as u32 / 8).to_le_bytes());
+    wav.extend_from_slice(&(channels * bits / 8).to_le_bytes());
+    wav.extend_from_slice(&bits.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(wav.len() + data_len as usize, 0);
+
+    let path = std::env::temp_dir().join(format!("vyom-details-{}.wav", std::process::id()));
+    std::fs::write(&path, &wav).unwrap();
+    let sections = file_details(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    let get = |section: &str, label: &str| {
+        sections
+            .iter()
+            .find(|(s, _)| s == section)
+            .and_then(|(_, rows)| rows.iter().find(|(l, _)| l == label))
+            .map(|(_, v)| v.clone())
+    };
+    assert_eq!(get("Audio", "Sample rate").as_deref(), Some("48.0 kHz"));
+    assert_eq!(get("Audio", "Bit depth").as_deref(), Some("16 bit"));
+    assert_eq!(get("Audio", "Duration").as_deref(), Some("0:01"));
+    assert!(get("File", "Path").unwrap().ends_with(".wav"));
+    assert!(get("File", "Size").is_some());
+
+    assert!(file_details(std::path::Path::new("/nonexistent.flac")).is_none());
+}
+
+#[test]
+fn test_log_viewer_opens_filters_and_closes() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{backend::TestBackend, Terminal};
+    use vyom::app::inputs::logs::handle_log_view;
+
+    let mut app = create_test_app();
+    let press = |app: &mut App, code: KeyCode| {
+        handle_log_view(KeyEvent::new(code, KeyModifiers::NONE), app)
+    };
+    // Closed: only F12 is taken
+    assert!(!press(&mut app, KeyCode::Char('j')));
+    assert!(press(&mut app, KeyCode::F(12)));
+    let view = app.log_view.as_ref().unwrap();
+    assert_eq!(view.min_level, tracing::Level::DEBUG);
+    assert_eq!(view.scroll, None);
+
+    // Scrolling up stops following, End follows again
+    press(&mut app, KeyCode::Char('k'));
+    assert!(app.log_view.as_ref().unwrap().scroll.is_some());
+    press(&mut app, KeyCode::End);
+    assert_eq!(app.log_view.as_ref().unwrap().scroll, None);
+    press(&mut app, KeyCode::Char('f'));
+    assert_eq!(
+        app.log_view.as_ref().unwrap().min_level,
+        tracing::Level::INFO
+    );
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    terminal
+        .draw(|f| vyom::ui::widgets::popups::logs::render(f, &app))
+        .unwrap();
+    let buf = terminal.backend().buffer();
+    let screen: String = (0..20)
+        .map(|y| (0..100).map(|x| buf[(x, y)].symbol()).collect::<String>())
+        .collect();
+    assert!(screen.contains("Log · info and up"));
+
+    press(&mut app, KeyCode::Esc);
+    assert!(app.log_view.is_none());
+}
+
+#[test]
+fn test_frame_rate_follows_what_moves() {
+    use std::time::{Duration, Instant};
+    use vyom::app::{FrameRate, ViewMode};
+
+    let mut app = create_test_app();
+    app.view_mode = ViewMode::Visualizer;
+    app.track = Some(test_track("Song", "Artist", "Album"));
+    // Paused: the visualizer has nothing to show
+    assert_eq!(app.frame_rate(Instant::now()), FrameRate::Idle);
+
+    app.track.as_mut().unwrap().state = vyom::player::PlayerState::Playing;
+    assert_eq!(app.frame_rate(Instant::now()), FrameRate::Live);
+
+    // Sliding in, then still, then sliding out
+    app.show_toast("Hello!");
+    assert_eq!(app.frame_rate(Instant::now()), FrameRate::Animation);
+    app.view_mode = ViewMode::Lyrics;
+    assert_eq!(
+        app.frame_rate(Instant::now() + Duration::from_millis(500)),
+        FrameRate::Idle
+    );
+    let deadline = app.toasts.latest().unwrap().deadline;
+    assert_eq!(app.frame_rate(deadline), FrameRate::Animation);
+}
+
+#[test]
+fn test_text_art_is_built_outside_the_frame() {
+    use ratatui::{backend::TestBackend, Terminal};
+    use vyom::app::{ArtStyle, ArtworkState};
+    use vyom::ui::widgets::player::art;
+
+    let mut app = create_test_app();
+    app.art_style = ArtStyle::Block;
+    let img = image::RgbImage::from_pixel(64, 64, image::Rgb([0, 0, 255]));
+    app.artwork = ArtworkState::Loaded(std::sync::Arc::new(image::DynamicImage::ImageRgb8(img)));
+
+    // The first frame only asks for the cells
+    let mut terminal = Terminal::new(TestBackend::new(20, 10)).unwrap();
+    terminal
+        .draw(|f| art::render(f, f.area(), &mut app))
+        .unwrap();
+    let key = app.art_cells_wanted.expect("cells requested");
+    assert_eq!((key.width, key.height), (20, 10));
+    assert!(app.art_cells.is_none());
+
+    let ArtworkState::Loaded(img) = &app.artwork else {
+        unreachable!()
+    };
+    let cells = art::build_cells(img, key);
+    assert_eq!(cells.lines.len(), 10);
+    app.art_cells = Some(cells);
+    terminal
+        .draw(|f| art::render(f, f.area(), &mut app))
+        .unwrap();
+    let drawn = terminal.backend().buffer().clone();
+    assert!(drawn.content.iter().any(|c| c.symbol() == "▀"));
+}
+
+#[test]
+fn test_ascii_only_draws_plain_ascii() {
+    use ratatui::{backend::TestBackend, Terminal};
+    use vyom::app::ArtStyle;
+
+    let mut app = create_test_app();
+    app.ascii_only = true;
+    app.track = Some(test_track("Song", "Artist", "Album"));
+    app.art_style = ArtStyle::Block;
+    assert_eq!(app.effective_art_style(), ArtStyle::Ascii);
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    terminal.draw(|f| vyom::ui::ui(f, &mut app)).unwrap();
+    let drawn = terminal.backend().buffer().clone();
+    assert!(drawn.content.iter().all(|c| c.symbol().is_ascii()));
+    assert!(drawn.content.iter().any(|c| c.symbol() == "+"));
+}
+
+#[test]
+fn test_queue_delta_edits_in_place() {
+    use vyom::player::QueueDelta;
+
+    let song = |i: usize| {
+        (
+            format!("Song {}", i),
+            "Artist".to_string(),
+            180_000,
+            false,
+            format!("song{}.flac", i),
+            String::new(),
+            String::new(),
+        )
+    };
+    let mut queue: Vec<QueueItem> = (0..10_000).map(|i| song(i).into()).collect();
+
+    // Song 2 replaced, two added at the end, playing moved to the second
+    vyom::app::apply_queue_delta(
+        &mut queue,
+        QueueDelta {
+            len: 10_002,
+            changed: vec![
+                (2, song(99)),
+                (10_000, song(10_000)),
+                (10_001, song(10_001)),
+            ],
+            current: Some(1),
+        },
+    );
+    assert_eq!(queue.len(), 10_002);
+    assert_eq!(queue[2].title, "Song 99");
+    assert_eq!(queue[10_001].file_path, "song10001.flac");
+    assert_eq!(
+        queue.iter().position(|q| q.is_current),
+        Some(1),
+        "only the playing song is marked"
+    );
+
+    // Cleared down to three songs, nothing playing
+    vyom::app::apply_queue_delta(
+        &mut queue,
+        QueueDelta {
+            len: 3,
+            ..Default::default()
+        },
+    );
+    assert_eq!(queue.len(), 3);
+    assert!(queue.iter().all(|q| !q.is_current));
+}
+
+#[tokio::test]
+async fn test_queue_groups_albums_and_folds() {
+    use clap::Parser;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use vyom::app::inputs::library::handle_library_events;
+    use vyom::app::QueueRow;
+
+    let mut app = create_test_app();
+    let args = vyom::app::cli::Args::parse_from(["vyom"]);
+    app.view_mode = ViewMode::Library;
+    app.library_mode = LibraryMode::Queue;
+    let song = |album: &str, i: usize| QueueItem {
+        title: format!("{} {}", album, i),
+        artist: "Artist".to_string(),
+        duration_ms: 200_000,
+        file_path: format!("{}/{}.flac", album, i),
+        album: album.to_string(),
+        year: "1999".to_string(),
+        ..Default::default()
+    };
+    // A: 3 songs, a single, B: 2 songs
+    app.queue = vec![
+        song("A", 1),
+        song("A", 2),
+        song("A", 3),
+        song("Single", 1),
+        song("B", 1),
+        song("B", 2),
+    ];
+
+    let rows = app.queue_rows();
+    assert_eq!(rows.len(), 8, "two headers and six songs");
+    let QueueRow::Album(a) = &rows[0] else {
+        panic!("album header first");
+    };
+    assert_eq!((a.start, a.len, a.duration_ms), (0, 3, 600_000));
+    assert_eq!(rows[4], QueueRow::Song(3));
+
+    // Folding A leaves its header as one stop
+    let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    app.library_selected = 1;
+    assert!(handle_library_events(press(KeyCode::Char('c')), &mut app, &args).await);
+    assert_eq!(app.library_selected, 0);
+    assert_eq!(app.queue_rows().len(), 5);
+    handle_library_events(press(KeyCode::Char('j')), &mut app, &args).await;
+    assert_eq!(app.library_selected, 3);
+    handle_library_events(press(KeyCode::Char('k')), &mut app, &args).await;
+    assert_eq!(app.library_selected, 0);
+
+    // The single isn't under a header; flat when grouping is off
+    app.library_selected = 3;
+    handle_library_events(press(KeyCode::Char('c')), &mut app, &args).await;
+    assert_eq!(app.queue_rows().len(), 5);
+    app.group_queue = false;
+    assert_eq!(app.queue_rows().len(), 6);
+}
+
+#[test]
+fn test_queue_summary_counts_what_is_left() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut app = create_test_app();
+    app.view_mode = ViewMode::Library;
+    app.library_mode = LibraryMode::Queue;
+    app.queue = (0..87)
+        .map(|i| QueueItem {
+            title: format!("Song {}", i),
+            artist: "Artist".to_string(),
+            duration_ms: 240_000,
+            is_current: i == 11,
+            file_path: format!("{:02}.flac", i),
+            ..Default::default()
+        })
+        .collect();
+    assert_eq!(app.queue_time(), (None, 87 * 240_000, 87 * 240_000));
+
+    // Paused two minutes into the 12th song
+    let mut track = test_track("Song 11", "Artist", "");
+    track.file_path = Some("11.flac".to_string());
+    track.duration_ms = 240_000;
+    track.position_ms = 120_000;
+    app.track = Some(track);
+    let remaining = 87 * 240_000 - 11 * 240_000 - 120_000;
+    assert_eq!(app.queue_time(), (Some(11), remaining, 87 * 240_000));
+
+    let mut screen = |width| {
+        let mut terminal = Terminal::new(TestBackend::new(width, 30)).unwrap();
+        terminal.draw(|f| vyom::ui::ui(f, &mut app)).unwrap();
+        let buf = terminal.backend().buffer().clone();
+        buf.content.iter().map(|c| c.symbol()).collect::<String>()
+    };
+    // Narrow panes keep what fits
+    assert!(screen(100).contains("QUEUE  ·  Track 12/87  "));
+    assert!(screen(200).contains("Track 12/87  ·  5h02m remaining  ·  5h48m total"));
+}
+
+#[tokio::test]
+async fn test_party_mode_locks_destructive_keys() {
+    use clap::Parser;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use vyom::app::inputs::common::handle_common_events;
+    use vyom::app::inputs::library::handle_library_events;
+
+    let mut app = create_test_app();
+    let args = vyom::app::cli::Args::parse_from(["vyom"]);
+    let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    app.party_mode = true;
+    app.view_mode = ViewMode::Library;
+    app.library_mode = LibraryMode::Queue;
+    app.queue = vec![QueueItem {
+        title: "Song".to_string(),
+        file_path: "song.flac".to_string(),
+        ..Default::default()
+    }];
+
+    for key in ['d', 't'] {
+        assert!(handle_library_events(press(KeyCode::Char(key)), &mut app, &args).await);
+        assert_eq!(app.queue.len(), 1);
+        assert!(app.tag_edit.is_none());
+        assert_eq!(app.toasts.latest().unwrap().level, ToastLevel::Warn);
+    }
+
+    // Journaled edits can't be replayed either
+    app.journal.record(vyom::app::Operation::QueueClear {
+        files: vec!["song.flac".to_string()],
+    });
+    handle_common_events(press(KeyCode::Char('u')), &mut app, &args).await;
+    assert!(app.journal.can_undo());
+    app.journal
+        .undone(vyom::app::Operation::QueueClear { files: Vec::new() });
+    let ctrl_r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+    handle_common_events(ctrl_r, &mut app, &args).await;
+    assert!(app.journal.can_redo());
+
+    // Quitting takes a second press
+    handle_common_events(press(KeyCode::Char('q')), &mut app, &args).await;
+    assert!(app.is_running);
+    handle_common_events(press(KeyCode::Char('q')), &mut app, &args).await;
+    assert!(!app.is_running);
 }